@@ -6,8 +6,12 @@
 
 #![warn(missing_docs)]
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use network_errors::NetworkError;
 use sha2::{Digest, Sha256, Sha384, Sha512};
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 /// Pin types supported for certificate hashing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +24,17 @@ pub enum PinType {
     Sha512,
 }
 
+impl PinType {
+    /// Length in bytes of a hash produced by this algorithm
+    pub fn hash_len(&self) -> usize {
+        match self {
+            PinType::Sha256 => 32,
+            PinType::Sha384 => 48,
+            PinType::Sha512 => 64,
+        }
+    }
+}
+
 /// A certificate pin with hash type and hash value
 #[derive(Debug, Clone)]
 pub struct Pin {
@@ -27,6 +42,65 @@ pub struct Pin {
     pub pin_type: PinType,
     /// Hash value
     pub hash: Vec<u8>,
+    /// Time after which the pin is no longer enforced (None = never expires)
+    pub expires_at: Option<SystemTime>,
+}
+
+impl Pin {
+    /// Create a pin from a base64-encoded hash, as written in HPKP
+    /// `pin-sha256="..."` directives
+    ///
+    /// Surrounding whitespace and double quotes are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidConfig` if the string is not valid base64
+    /// or the decoded hash length doesn't match `pin_type`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use certificate_pinning::{Pin, PinType};
+    ///
+    /// let pin = Pin::from_base64(
+    ///     PinType::Sha256,
+    ///     "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(pin.hash.len(), 32);
+    /// ```
+    pub fn from_base64(pin_type: PinType, s: &str) -> Result<Pin, NetworkError> {
+        let encoded = s.trim().trim_matches('"');
+        let hash = BASE64
+            .decode(encoded)
+            .map_err(|e| NetworkError::InvalidConfig(format!("Invalid base64 pin: {}", e)))?;
+
+        if hash.len() != pin_type.hash_len() {
+            return Err(NetworkError::InvalidConfig(format!(
+                "{:?} pin must be {} bytes, got {}",
+                pin_type,
+                pin_type.hash_len(),
+                hash.len()
+            )));
+        }
+
+        Ok(Pin {
+            pin_type,
+            hash,
+            expires_at: None,
+        })
+    }
+
+    /// Stop enforcing this pin after `expires_at` (builder pattern)
+    pub fn with_expiry(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Whether the pin has expired at `now`
+    pub fn is_expired_at(&self, now: SystemTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
 }
 
 /// Result of pin verification
@@ -41,6 +115,10 @@ pub enum PinResult {
     },
     /// Host is not pinned
     NotPinned,
+    /// Host was pinned, but all of its pins have expired
+    ///
+    /// Like `NotPinned`, normal certificate validation should proceed.
+    Expired,
 }
 
 /// Certificate pinner for managing and verifying certificate pins
@@ -82,6 +160,7 @@ impl CertificatePinner {
     /// let pin = Pin {
     ///     pin_type: PinType::Sha256,
     ///     hash: vec![0x01, 0x02, 0x03],
+    ///     expires_at: None,
     /// };
     /// pinner.add_pin("example.com", pin);
     /// ```
@@ -92,8 +171,47 @@ impl CertificatePinner {
             .push(pin);
     }
 
+    /// Add a base64-encoded pin for a specific host
+    ///
+    /// See [`Pin::from_base64`] for the accepted format.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pin is invalid; no pin is added in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use certificate_pinning::{CertificatePinner, PinType};
+    ///
+    /// let mut pinner = CertificatePinner::new();
+    /// pinner
+    ///     .add_pin_base64(
+    ///         "example.com",
+    ///         PinType::Sha256,
+    ///         "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+    ///     )
+    ///     .unwrap();
+    /// ```
+    pub fn add_pin_base64(
+        &mut self,
+        host: &str,
+        pin_type: PinType,
+        pin: &str,
+    ) -> Result<(), NetworkError> {
+        let pin = Pin::from_base64(pin_type, pin)?;
+        self.add_pin(host, pin);
+        Ok(())
+    }
+
     /// Verify a certificate against stored pins for a host
     ///
+    /// Pins stored under the exact hostname take precedence; otherwise pins
+    /// under a single-label wildcard (`*.example.com`) apply to hosts one label
+    /// below the wildcard domain (`api.example.com`, but neither `example.com`
+    /// nor `a.b.example.com`). Expired pins are ignored; if all of a host's
+    /// pins have expired the result is `PinResult::Expired`.
+    ///
     /// # Arguments
     ///
     /// * `host` - The hostname to verify
@@ -104,6 +222,7 @@ impl CertificatePinner {
     /// * `PinResult::Valid` - Certificate matches a pin
     /// * `PinResult::Invalid` - Certificate doesn't match any pins
     /// * `PinResult::NotPinned` - Host has no pins
+    /// * `PinResult::Expired` - All of the host's pins have expired
     ///
     /// # Examples
     ///
@@ -122,6 +241,7 @@ impl CertificatePinner {
     /// let pin = Pin {
     ///     pin_type: PinType::Sha256,
     ///     hash,
+    ///     expires_at: None,
     /// };
     /// pinner.add_pin("example.com", pin);
     ///
@@ -129,14 +249,24 @@ impl CertificatePinner {
     /// assert!(matches!(result, PinResult::Valid));
     /// ```
     pub fn verify(&self, host: &str, cert_der: &[u8]) -> PinResult {
-        // Check if host has any pins
-        let pins = match self.pins.get(host) {
+        // Check if host has any pins, exact entries first
+        let pins = match self
+            .pins
+            .get(host)
+            .or_else(|| Self::wildcard_for(host).and_then(|wildcard| self.pins.get(&wildcard)))
+        {
             Some(pins) => pins,
             None => return PinResult::NotPinned,
         };
 
-        // Try to match against any pin
-        for pin in pins {
+        let now = SystemTime::now();
+        let mut active = pins.iter().filter(|pin| !pin.is_expired_at(now)).peekable();
+        if active.peek().is_none() {
+            return PinResult::Expired;
+        }
+
+        // Try to match against any unexpired pin
+        for pin in active {
             let computed_hash = Self::compute_hash(pin.pin_type, cert_der);
 
             if computed_hash == pin.hash {
@@ -165,6 +295,7 @@ impl CertificatePinner {
     /// let pin = Pin {
     ///     pin_type: PinType::Sha256,
     ///     hash: vec![0x01, 0x02, 0x03],
+    ///     expires_at: None,
     /// };
     /// pinner.add_pin("example.com", pin);
     /// pinner.remove_pins("example.com");
@@ -173,6 +304,19 @@ impl CertificatePinner {
         self.pins.remove(host);
     }
 
+    /// Wildcard key that would cover `host`, e.g. `*.example.com` for
+    /// `api.example.com`
+    ///
+    /// The wildcard replaces exactly the first label and must leave at least
+    /// two labels, so `example.com` has no wildcard key.
+    fn wildcard_for(host: &str) -> Option<String> {
+        let (label, parent) = host.split_once('.')?;
+        if label.is_empty() || !parent.contains('.') {
+            return None;
+        }
+        Some(format!("*.{}", parent))
+    }
+
     /// Compute hash of certificate using specified algorithm
     fn compute_hash(pin_type: PinType, cert_der: &[u8]) -> Vec<u8> {
         match pin_type {
@@ -205,6 +349,16 @@ impl Default for CertificatePinner {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_wildcard_for() {
+        assert_eq!(
+            CertificatePinner::wildcard_for("api.example.com"),
+            Some("*.example.com".to_string())
+        );
+        assert_eq!(CertificatePinner::wildcard_for("example.com"), None);
+        assert_eq!(CertificatePinner::wildcard_for("localhost"), None);
+    }
+
     #[test]
     fn test_compute_hash_sha256() {
         let data = b"test data";
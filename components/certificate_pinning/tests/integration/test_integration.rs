@@ -21,6 +21,7 @@ fn test_complete_pinning_workflow() {
     let pin = Pin {
         pin_type: PinType::Sha256,
         hash: sha256_hash.clone(),
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
@@ -53,6 +54,7 @@ fn test_multiple_hosts_and_algorithms() {
         Pin {
             pin_type: PinType::Sha256,
             hash: hash1,
+            expires_at: None,
         },
     );
 
@@ -67,6 +69,7 @@ fn test_multiple_hosts_and_algorithms() {
         Pin {
             pin_type: PinType::Sha384,
             hash: hash2,
+            expires_at: None,
         },
     );
 
@@ -81,6 +84,7 @@ fn test_multiple_hosts_and_algorithms() {
         Pin {
             pin_type: PinType::Sha512,
             hash: hash3,
+            expires_at: None,
         },
     );
 
@@ -124,6 +128,7 @@ fn test_pin_rotation() {
         Pin {
             pin_type: PinType::Sha256,
             hash: old_hash,
+            expires_at: None,
         },
     );
 
@@ -145,6 +150,7 @@ fn test_pin_rotation() {
         Pin {
             pin_type: PinType::Sha256,
             hash: new_hash,
+            expires_at: None,
         },
     );
 
@@ -169,6 +175,7 @@ fn test_pin_rotation() {
         Pin {
             pin_type: PinType::Sha256,
             hash: new_hash,
+            expires_at: None,
         },
     );
 
@@ -205,6 +212,7 @@ fn test_backup_pins() {
         Pin {
             pin_type: PinType::Sha256,
             hash: primary_hash,
+            expires_at: None,
         },
     );
     pinner.add_pin(
@@ -212,6 +220,7 @@ fn test_backup_pins() {
         Pin {
             pin_type: PinType::Sha256,
             hash: backup_hash,
+            expires_at: None,
         },
     );
 
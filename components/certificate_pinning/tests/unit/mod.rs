@@ -1 +1,4 @@
+mod test_base64_pins;
+mod test_pin_expiry;
 mod test_pinner;
+mod test_wildcard_pins;
@@ -0,0 +1,70 @@
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use certificate_pinning::{CertificatePinner, Pin, PinResult, PinType};
+use network_errors::NetworkError;
+use sha2::{Digest, Sha256};
+
+#[test]
+fn test_from_base64_valid_sha256_pin() {
+    let pin = Pin::from_base64(
+        PinType::Sha256,
+        "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+    )
+    .unwrap();
+
+    assert_eq!(pin.pin_type, PinType::Sha256);
+    assert_eq!(pin.hash.len(), 32);
+    assert_eq!(pin.hash[0], 0x13);
+}
+
+#[test]
+fn test_from_base64_accepts_quoted_header_value() {
+    let pin = Pin::from_base64(
+        PinType::Sha256,
+        " \"E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=\" ",
+    )
+    .unwrap();
+
+    assert_eq!(pin.hash.len(), 32);
+}
+
+#[test]
+fn test_from_base64_rejects_wrong_length() {
+    // A valid SHA-256 pin used where SHA-384 is expected
+    let result = Pin::from_base64(
+        PinType::Sha384,
+        "E9CZ9INDbd+2eRQozYqqbQ2yXLVKB9+xcprMF+44U1g=",
+    );
+
+    assert!(matches!(result, Err(NetworkError::InvalidConfig(msg)) if msg.contains("48 bytes")));
+}
+
+#[test]
+fn test_from_base64_rejects_invalid_base64() {
+    let result = Pin::from_base64(PinType::Sha256, "not base64!");
+
+    assert!(matches!(result, Err(NetworkError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_add_pin_base64_verifies_certificate() {
+    let cert_der = vec![0x30, 0x82, 0x01, 0x00];
+    let encoded = BASE64.encode(Sha256::digest(&cert_der));
+    let mut pinner = CertificatePinner::new();
+
+    pinner
+        .add_pin_base64("example.com", PinType::Sha256, &encoded)
+        .unwrap();
+
+    assert_eq!(pinner.verify("example.com", &cert_der), PinResult::Valid);
+}
+
+#[test]
+fn test_add_pin_base64_invalid_pin_is_not_added() {
+    let mut pinner = CertificatePinner::new();
+
+    let result = pinner.add_pin_base64("example.com", PinType::Sha512, "AAAA");
+
+    assert!(result.is_err());
+    assert!(!pinner.pins.contains_key("example.com"));
+}
@@ -0,0 +1,71 @@
+use certificate_pinning::{CertificatePinner, Pin, PinResult, PinType};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime};
+
+const CERT: &[u8] = &[0x30, 0x82, 0x01, 0x00];
+const OTHER_CERT: &[u8] = &[0x30, 0x82, 0x02, 0x00];
+
+fn pin_for(cert_der: &[u8]) -> Pin {
+    Pin {
+        pin_type: PinType::Sha256,
+        hash: Sha256::digest(cert_der).to_vec(),
+        expires_at: None,
+    }
+}
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[test]
+fn test_expired_pin_is_not_enforced() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin(
+        "example.com",
+        pin_for(CERT).with_expiry(SystemTime::now() - DAY),
+    );
+
+    // A mismatching certificate no longer fails; validation falls back
+    assert_eq!(pinner.verify("example.com", OTHER_CERT), PinResult::Expired);
+    assert_eq!(pinner.verify("example.com", CERT), PinResult::Expired);
+}
+
+#[test]
+fn test_unexpired_pin_is_enforced() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin(
+        "example.com",
+        pin_for(CERT).with_expiry(SystemTime::now() + DAY),
+    );
+
+    assert_eq!(pinner.verify("example.com", CERT), PinResult::Valid);
+    assert!(matches!(
+        pinner.verify("example.com", OTHER_CERT),
+        PinResult::Invalid { .. }
+    ));
+}
+
+#[test]
+fn test_expired_pins_are_ignored_alongside_active_ones() {
+    // Rotation: the old pin expired, the new one is active
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin(
+        "example.com",
+        pin_for(CERT).with_expiry(SystemTime::now() - DAY),
+    );
+    pinner.add_pin("example.com", pin_for(OTHER_CERT));
+
+    assert_eq!(pinner.verify("example.com", OTHER_CERT), PinResult::Valid);
+    assert!(matches!(
+        pinner.verify("example.com", CERT),
+        PinResult::Invalid { .. }
+    ));
+}
+
+#[test]
+fn test_is_expired_at() {
+    let now = SystemTime::now();
+    let pin = pin_for(CERT).with_expiry(now);
+
+    assert!(pin.is_expired_at(now));
+    assert!(!pin.is_expired_at(now - DAY));
+    assert!(!pin_for(CERT).is_expired_at(now + DAY));
+}
@@ -13,6 +13,7 @@ fn test_add_pin_sha256() {
     let pin = Pin {
         pin_type: PinType::Sha256,
         hash: vec![0x01, 0x02, 0x03],
+        expires_at: None,
     };
 
     pinner.add_pin("example.com", pin);
@@ -28,10 +29,12 @@ fn test_add_multiple_pins_for_same_host() {
     let pin1 = Pin {
         pin_type: PinType::Sha256,
         hash: vec![0x01, 0x02, 0x03],
+        expires_at: None,
     };
     let pin2 = Pin {
         pin_type: PinType::Sha384,
         hash: vec![0x04, 0x05, 0x06],
+        expires_at: None,
     };
 
     pinner.add_pin("example.com", pin1);
@@ -71,6 +74,7 @@ fn test_verify_valid_pin() {
     let pin = Pin {
         pin_type: PinType::Sha256,
         hash: hash.clone(),
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
@@ -91,6 +95,7 @@ fn test_verify_invalid_pin() {
     let pin = Pin {
         pin_type: PinType::Sha256,
         hash: vec![0x01, 0x02, 0x03], // Wrong hash
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
@@ -123,6 +128,7 @@ fn test_verify_with_multiple_pins() {
     let wrong_pin = Pin {
         pin_type: PinType::Sha256,
         hash: vec![0x01, 0x02, 0x03],
+        expires_at: None,
     };
     pinner.add_pin("example.com", wrong_pin);
 
@@ -130,6 +136,7 @@ fn test_verify_with_multiple_pins() {
     let correct_pin = Pin {
         pin_type: PinType::Sha256,
         hash: correct_hash,
+        expires_at: None,
     };
     pinner.add_pin("example.com", correct_pin);
 
@@ -149,6 +156,7 @@ fn test_remove_pins() {
     let pin = Pin {
         pin_type: PinType::Sha256,
         hash: vec![0x01, 0x02, 0x03],
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
@@ -174,6 +182,7 @@ fn test_sha384_hash_verification() {
     let pin = Pin {
         pin_type: PinType::Sha384,
         hash: hash.clone(),
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
@@ -200,6 +209,7 @@ fn test_sha512_hash_verification() {
     let pin = Pin {
         pin_type: PinType::Sha512,
         hash: hash.clone(),
+        expires_at: None,
     };
     pinner.add_pin("example.com", pin);
 
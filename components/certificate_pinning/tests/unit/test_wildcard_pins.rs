@@ -0,0 +1,64 @@
+use certificate_pinning::{CertificatePinner, Pin, PinResult, PinType};
+use sha2::{Digest, Sha256};
+
+const CERT: &[u8] = &[0x30, 0x82, 0x01, 0x00];
+const OTHER_CERT: &[u8] = &[0x30, 0x82, 0x02, 0x00];
+
+fn pin_for(cert_der: &[u8]) -> Pin {
+    Pin {
+        pin_type: PinType::Sha256,
+        hash: Sha256::digest(cert_der).to_vec(),
+        expires_at: None,
+    }
+}
+
+#[test]
+fn test_wildcard_pin_applies_to_single_label_subdomain() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin("*.example.com", pin_for(CERT));
+
+    assert_eq!(pinner.verify("api.example.com", CERT), PinResult::Valid);
+    assert!(matches!(
+        pinner.verify("api.example.com", OTHER_CERT),
+        PinResult::Invalid { .. }
+    ));
+}
+
+#[test]
+fn test_wildcard_pin_does_not_apply_to_bare_domain() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin("*.example.com", pin_for(CERT));
+
+    assert_eq!(
+        pinner.verify("example.com", OTHER_CERT),
+        PinResult::NotPinned
+    );
+}
+
+#[test]
+fn test_wildcard_pin_does_not_apply_to_multiple_labels() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin("*.example.com", pin_for(CERT));
+
+    assert_eq!(
+        pinner.verify("a.b.example.com", OTHER_CERT),
+        PinResult::NotPinned
+    );
+}
+
+#[test]
+fn test_exact_pin_takes_precedence_over_wildcard() {
+    let mut pinner = CertificatePinner::new();
+    pinner.add_pin("*.example.com", pin_for(CERT));
+    pinner.add_pin("api.example.com", pin_for(OTHER_CERT));
+
+    assert_eq!(
+        pinner.verify("api.example.com", OTHER_CERT),
+        PinResult::Valid
+    );
+    assert!(matches!(
+        pinner.verify("api.example.com", CERT),
+        PinResult::Invalid { .. }
+    ));
+    assert_eq!(pinner.verify("www.example.com", CERT), PinResult::Valid);
+}
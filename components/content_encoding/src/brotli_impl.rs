@@ -1,14 +1,29 @@
 use network_errors::NetworkError;
 use std::io::{Read, Write};
 
+/// Default quality (0-11, 11 is best)
+pub const DEFAULT_QUALITY: u32 = 11;
+
+/// Default base-2 logarithm of the sliding window size
+pub const DEFAULT_LGWIN: u32 = 22;
+
+/// Highest quality level
+pub const MAX_QUALITY: u32 = 11;
+
+/// Smallest window size the format allows
+pub const MIN_LGWIN: u32 = 10;
+
+/// Largest window size the format allows
+pub const MAX_LGWIN: u32 = 24;
+
 /// Encode data using brotli compression
-pub fn encode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
+pub fn encode(data: &[u8], quality: u32, lgwin: u32) -> Result<Vec<u8>, NetworkError> {
     let mut output = Vec::new();
     let mut compressor = brotli::CompressorWriter::new(
         &mut output,
         4096, // buffer size
-        11,   // quality (0-11, 11 is best)
-        22,   // lg_window_size
+        quality,
+        lgwin,
     );
 
     compressor.write_all(data)
@@ -39,7 +54,7 @@ mod tests {
     #[test]
     fn test_brotli_roundtrip() {
         let data = b"Hello, brotli!";
-        let encoded = encode(data).unwrap();
+        let encoded = encode(data, DEFAULT_QUALITY, DEFAULT_LGWIN).unwrap();
         let decoded = decode(&encoded).unwrap();
         assert_eq!(decoded.as_slice(), data);
     }
@@ -1,7 +1,11 @@
-use flate2::write::{GzEncoder, GzDecoder};
+use flate2::bufread::GzDecoder;
+use flate2::write::GzEncoder;
 use flate2::Compression;
 use network_errors::NetworkError;
-use std::io::Write;
+use std::io::{Read, Write};
+
+/// Magic bytes opening every gzip member (RFC 1952)
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
 
 /// Encode data using gzip compression
 pub fn encode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
@@ -13,12 +17,32 @@ pub fn encode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
 }
 
 /// Decode gzip-compressed data
-pub fn decode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
-    let mut decoder = GzDecoder::new(Vec::new());
-    decoder.write_all(data)
-        .map_err(|e| NetworkError::ProtocolError(format!("Gzip decoding failed: {}", e)))?;
-    decoder.finish()
-        .map_err(|e| NetworkError::ProtocolError(format!("Gzip finish failed: {}", e)))
+///
+/// Concatenated gzip members are decoded in turn, and bytes following the
+/// last member that do not start another member are ignored. When `strict` is
+/// true, any bytes after the first member's trailer are rejected instead.
+pub fn decode(data: &[u8], strict: bool) -> Result<Vec<u8>, NetworkError> {
+    let mut remaining = data;
+    let mut output = Vec::new();
+
+    loop {
+        GzDecoder::new(&mut remaining)
+            .read_to_end(&mut output)
+            .map_err(|e| NetworkError::ProtocolError(format!("Gzip decoding failed: {}", e)))?;
+
+        if remaining.is_empty() {
+            return Ok(output);
+        }
+        if strict {
+            return Err(NetworkError::ProtocolError(format!(
+                "Gzip decoding failed: {} trailing bytes after gzip member",
+                remaining.len()
+            )));
+        }
+        if !remaining.starts_with(&GZIP_MAGIC) {
+            return Ok(output);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -29,14 +53,32 @@ mod tests {
     fn test_gzip_roundtrip() {
         let data = b"Hello, gzip!";
         let encoded = encode(data).unwrap();
-        let decoded = decode(&encoded).unwrap();
+        let decoded = decode(&encoded, false).unwrap();
         assert_eq!(decoded.as_slice(), data);
     }
 
     #[test]
     fn test_gzip_invalid_data() {
         let invalid = b"not gzip data";
-        let result = decode(invalid);
+        let result = decode(invalid, false);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_gzip_concatenated_members() {
+        let mut encoded = encode(b"Hello, ").unwrap();
+        encoded.extend(encode(b"gzip!").unwrap());
+
+        assert_eq!(decode(&encoded, false).unwrap().as_slice(), b"Hello, gzip!");
+        assert!(decode(&encoded, true).is_err());
+    }
+
+    #[test]
+    fn test_gzip_trailing_junk() {
+        let mut encoded = encode(b"Hello, gzip!").unwrap();
+        encoded.extend_from_slice(b"junk");
+
+        assert_eq!(decode(&encoded, false).unwrap().as_slice(), b"Hello, gzip!");
+        assert!(decode(&encoded, true).is_err());
+    }
 }
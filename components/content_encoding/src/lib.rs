@@ -1,11 +1,13 @@
 mod gzip;
 mod deflate;
 mod brotli_impl;
+mod zstd_impl;
 mod stream;
 
 use bytes::Bytes;
 use futures::Stream;
 use network_errors::NetworkError;
+use network_types::{NetworkRequest, RequestBody};
 
 /// Supported content encodings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,13 +18,31 @@ pub enum Encoding {
     Deflate,
     /// Brotli compression
     Brotli,
+    /// Zstandard compression (RFC 8878)
+    Zstd,
     /// No encoding (identity)
     Identity,
 }
 
+impl Encoding {
+    /// Content-coding token used in `Content-Encoding` headers
+    pub fn token(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Brotli => "br",
+            Encoding::Zstd => "zstd",
+            Encoding::Identity => "identity",
+        }
+    }
+}
+
 /// Content encoder/decoder for HTTP content encoding
 pub struct ContentEncoder {
     supported_encodings: Vec<Encoding>,
+    strict: bool,
+    brotli_quality: u32,
+    brotli_lgwin: u32,
 }
 
 impl ContentEncoder {
@@ -33,17 +53,54 @@ impl ContentEncoder {
                 Encoding::Gzip,
                 Encoding::Deflate,
                 Encoding::Brotli,
+                Encoding::Zstd,
                 Encoding::Identity,
             ],
+            strict: false,
+            brotli_quality: brotli_impl::DEFAULT_QUALITY,
+            brotli_lgwin: brotli_impl::DEFAULT_LGWIN,
         }
     }
 
+    /// Set whether decoding rejects trailing bytes after a gzip member
+    ///
+    /// By default concatenated gzip members are all decoded and unrecognised
+    /// trailing bytes are ignored. In strict mode anything after the first
+    /// member's trailer, including further members, is a `ProtocolError`.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether strict decoding is enabled
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Set the Brotli quality and window size used by `encode`
+    ///
+    /// `quality` ranges from 0 (fastest) to 11 (smallest output, the
+    /// default); `lgwin` is the base-2 logarithm of the sliding window size,
+    /// from 10 to 24 (default 22). Out-of-range values are clamped. Decoding
+    /// reads the window size from the stream and doesn't use either setting.
+    pub fn with_brotli_params(mut self, quality: u32, lgwin: u32) -> Self {
+        self.brotli_quality = quality.min(brotli_impl::MAX_QUALITY);
+        self.brotli_lgwin = lgwin.clamp(brotli_impl::MIN_LGWIN, brotli_impl::MAX_LGWIN);
+        self
+    }
+
+    /// Brotli quality and window size used by `encode`
+    pub fn brotli_params(&self) -> (u32, u32) {
+        (self.brotli_quality, self.brotli_lgwin)
+    }
+
     /// Encode data using the specified encoding
     pub fn encode(&self, data: &[u8], encoding: Encoding) -> Result<Vec<u8>, NetworkError> {
         match encoding {
             Encoding::Gzip => gzip::encode(data),
             Encoding::Deflate => deflate::encode(data),
-            Encoding::Brotli => brotli_impl::encode(data),
+            Encoding::Brotli => brotli_impl::encode(data, self.brotli_quality, self.brotli_lgwin),
+            Encoding::Zstd => zstd_impl::encode(data),
             Encoding::Identity => Ok(data.to_vec()),
         }
     }
@@ -51,13 +108,68 @@ impl ContentEncoder {
     /// Decode data using the specified encoding
     pub fn decode(&self, data: &[u8], encoding: Encoding) -> Result<Vec<u8>, NetworkError> {
         match encoding {
-            Encoding::Gzip => gzip::decode(data),
+            Encoding::Gzip => gzip::decode(data, self.strict),
             Encoding::Deflate => deflate::decode(data),
             Encoding::Brotli => brotli_impl::decode(data),
+            Encoding::Zstd => zstd_impl::decode(data),
             Encoding::Identity => Ok(data.to_vec()),
         }
     }
 
+    /// Compress a request's body and label it with `Content-Encoding`
+    ///
+    /// `Bytes` and `Text` bodies are replaced by their encoded bytes, the
+    /// coding is appended to the request's `Content-Encoding` header and
+    /// `Content-Length` is set to the encoded size. Requests without a body
+    /// and the `Identity` encoding are left unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::Other` for `FormData` and streaming bodies,
+    /// which can't be encoded up front, or if compression fails.
+    pub fn encode_request_body(
+        &self,
+        request: &mut NetworkRequest,
+        encoding: Encoding,
+    ) -> Result<(), NetworkError> {
+        if encoding == Encoding::Identity {
+            return Ok(());
+        }
+        let encoded = match &request.body {
+            None => return Ok(()),
+            Some(RequestBody::Bytes(bytes)) => self.encode(bytes, encoding)?,
+            Some(RequestBody::Text(text)) => self.encode(text.as_bytes(), encoding)?,
+            Some(RequestBody::FormData(_)) | Some(RequestBody::Stream(_)) => {
+                return Err(NetworkError::Other(
+                    "Only byte and text request bodies can be content-encoded".to_string(),
+                ));
+            }
+        };
+
+        // Codings are listed in the order they were applied
+        let coding = match request
+            .headers
+            .get(http::header::CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(existing) if !existing.trim().is_empty() => {
+                format!("{}, {}", existing, encoding.token())
+            }
+            _ => encoding.token().to_string(),
+        };
+        request.headers.insert(
+            http::header::CONTENT_ENCODING,
+            http::HeaderValue::from_str(&coding)
+                .map_err(|e| NetworkError::Other(format!("Invalid Content-Encoding: {}", e)))?,
+        );
+        request.headers.insert(
+            http::header::CONTENT_LENGTH,
+            http::HeaderValue::from(encoded.len()),
+        );
+        request.body = Some(RequestBody::Bytes(encoded));
+        Ok(())
+    }
+
     /// Decode a stream of encoded data
     pub fn decode_stream(
         &self,
@@ -69,7 +181,7 @@ impl ContentEncoder {
 
     /// Get the Accept-Encoding header value
     pub fn get_accept_encoding(&self) -> String {
-        "gzip, deflate, br".to_string()
+        "gzip, deflate, br, zstd".to_string()
     }
 }
 
@@ -86,10 +198,156 @@ mod tests {
     #[test]
     fn test_new_encoder_has_all_encodings() {
         let encoder = ContentEncoder::new();
-        assert_eq!(encoder.supported_encodings.len(), 4);
+        assert_eq!(encoder.supported_encodings.len(), 5);
         assert!(encoder.supported_encodings.contains(&Encoding::Gzip));
         assert!(encoder.supported_encodings.contains(&Encoding::Deflate));
         assert!(encoder.supported_encodings.contains(&Encoding::Brotli));
+        assert!(encoder.supported_encodings.contains(&Encoding::Zstd));
         assert!(encoder.supported_encodings.contains(&Encoding::Identity));
     }
+
+    /// Compressible text whose repeats are spread out enough to reward effort
+    fn compressible_text() -> Vec<u8> {
+        (0..2000)
+            .map(|i| {
+                format!(
+                    "line {} of the access log: GET /assets/{}.css 200\n",
+                    i % 97,
+                    i % 13
+                )
+            })
+            .collect::<String>()
+            .into_bytes()
+    }
+
+    #[test]
+    fn test_brotli_default_params() {
+        let encoder = ContentEncoder::new();
+        assert_eq!(encoder.brotli_params(), (11, 22));
+    }
+
+    #[test]
+    fn test_brotli_higher_quality_is_not_larger() {
+        let data = compressible_text();
+        let fast = ContentEncoder::new().with_brotli_params(1, 22);
+        let best = ContentEncoder::new().with_brotli_params(11, 22);
+
+        let fast_encoded = fast.encode(&data, Encoding::Brotli).unwrap();
+        let best_encoded = best.encode(&data, Encoding::Brotli).unwrap();
+
+        assert!(
+            best_encoded.len() <= fast_encoded.len(),
+            "quality 11 gave {} bytes, quality 1 gave {}",
+            best_encoded.len(),
+            fast_encoded.len()
+        );
+        assert!(fast_encoded.len() < data.len());
+    }
+
+    #[test]
+    fn test_brotli_tuned_output_roundtrips() {
+        let data = compressible_text();
+        // Decoding doesn't depend on the encoder's parameters
+        let decoder = ContentEncoder::new();
+
+        for (quality, lgwin) in [(0, 10), (5, 16), (11, 24)] {
+            let encoder = ContentEncoder::new().with_brotli_params(quality, lgwin);
+            let encoded = encoder.encode(&data, Encoding::Brotli).unwrap();
+            let decoded = decoder.decode(&encoded, Encoding::Brotli).unwrap();
+            assert_eq!(decoded, data, "quality {} lgwin {}", quality, lgwin);
+        }
+    }
+
+    #[test]
+    fn test_brotli_params_are_clamped() {
+        let encoder = ContentEncoder::new().with_brotli_params(42, 30);
+        assert_eq!(encoder.brotli_params(), (11, 24));
+
+        let encoder = ContentEncoder::new().with_brotli_params(0, 2);
+        assert_eq!(encoder.brotli_params(), (0, 10));
+
+        let data = compressible_text();
+        let encoded = encoder.encode(&data, Encoding::Brotli).unwrap();
+        assert_eq!(encoder.decode(&encoded, Encoding::Brotli).unwrap(), data);
+    }
+
+    fn post(body: Option<RequestBody>) -> NetworkRequest {
+        let mut request =
+            NetworkRequest::builder(url::Url::parse("https://api.example.com/upload").unwrap())
+                .method(network_types::HttpMethod::Post)
+                .build();
+        request.body = body;
+        request
+    }
+
+    #[test]
+    fn test_encode_request_body_gzip() {
+        let encoder = ContentEncoder::new();
+        let original = "payload payload payload payload payload";
+        let mut request = post(Some(RequestBody::Text(original.to_string())));
+
+        encoder
+            .encode_request_body(&mut request, Encoding::Gzip)
+            .unwrap();
+
+        let encoded = match &request.body {
+            Some(RequestBody::Bytes(bytes)) => bytes.clone(),
+            other => panic!("Expected encoded bytes, got {:?}", other),
+        };
+        assert_eq!(request.headers["content-encoding"], "gzip");
+        assert_eq!(
+            request.headers["content-length"],
+            encoded.len().to_string().as_str()
+        );
+        assert_eq!(
+            encoder.decode(&encoded, Encoding::Gzip).unwrap(),
+            original.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_encode_request_body_appends_coding() {
+        let encoder = ContentEncoder::new();
+        let mut request = post(Some(RequestBody::Bytes(b"data".to_vec())));
+        request.headers.insert(
+            http::header::CONTENT_ENCODING,
+            http::HeaderValue::from_static("deflate"),
+        );
+
+        encoder
+            .encode_request_body(&mut request, Encoding::Brotli)
+            .unwrap();
+
+        assert_eq!(request.headers["content-encoding"], "deflate, br");
+    }
+
+    #[test]
+    fn test_encode_request_body_skips_identity_and_empty_requests() {
+        let encoder = ContentEncoder::new();
+
+        let mut request = post(Some(RequestBody::Bytes(b"data".to_vec())));
+        encoder
+            .encode_request_body(&mut request, Encoding::Identity)
+            .unwrap();
+        assert!(request.headers.is_empty());
+        assert!(matches!(request.body, Some(RequestBody::Bytes(ref bytes)) if bytes == b"data"));
+
+        let mut request = post(None);
+        encoder
+            .encode_request_body(&mut request, Encoding::Gzip)
+            .unwrap();
+        assert!(request.headers.is_empty());
+        assert!(request.body.is_none());
+    }
+
+    #[test]
+    fn test_encode_request_body_rejects_form_data() {
+        let encoder = ContentEncoder::new();
+        let mut request = post(Some(RequestBody::FormData(network_types::FormData::new())));
+
+        let result = encoder.encode_request_body(&mut request, Encoding::Gzip);
+
+        assert!(matches!(result, Err(NetworkError::Other(_))));
+        assert!(!request.headers.contains_key(http::header::CONTENT_ENCODING));
+    }
 }
@@ -15,6 +15,7 @@ pub fn decode_stream(
         Encoding::Gzip => Box::pin(decode_gzip_stream(input)) as std::pin::Pin<Box<dyn Stream<Item = Result<Bytes, NetworkError>> + Send>>,
         Encoding::Deflate => Box::pin(decode_deflate_stream(input)),
         Encoding::Brotli => Box::pin(decode_brotli_stream(input)),
+        Encoding::Zstd => Box::pin(decode_zstd_stream(input)),
         Encoding::Identity => Box::pin(decode_identity_stream(input)),
     }
 }
@@ -130,6 +131,33 @@ fn decode_brotli_stream(
     )
 }
 
+/// Decode a zstd stream
+fn decode_zstd_stream(
+    input: impl Stream<Item = Bytes> + Send + 'static + Unpin,
+) -> impl Stream<Item = Result<Bytes, NetworkError>> + Send {
+    stream::unfold(
+        (input, Vec::new()),
+        |(mut input, mut buffer)| async move {
+            // Collect all chunks first so truncated frames are reported as errors
+            while let Some(chunk) = input.next().await {
+                buffer.extend_from_slice(&chunk);
+            }
+
+            if buffer.is_empty() {
+                return None;
+            }
+
+            match zstd::stream::decode_all(&buffer[..]) {
+                Ok(output) => Some((Ok(Bytes::from(output)), (input, Vec::new()))),
+                Err(e) => Some((
+                    Err(NetworkError::ProtocolError(format!("Zstd stream decoding failed: {}", e))),
+                    (input, Vec::new()),
+                )),
+            }
+        },
+    )
+}
+
 /// Pass-through stream for identity encoding
 fn decode_identity_stream(
     input: impl Stream<Item = Bytes> + Send + 'static + Unpin,
@@ -0,0 +1,36 @@
+use network_errors::NetworkError;
+
+/// Compression level used for zstd encoding (zstd's own default)
+const LEVEL: i32 = 3;
+
+/// Encode data using zstd compression
+pub fn encode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    zstd::stream::encode_all(data, LEVEL)
+        .map_err(|e| NetworkError::ProtocolError(format!("Zstd encoding failed: {}", e)))
+}
+
+/// Decode zstd-compressed data
+pub fn decode(data: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    zstd::stream::decode_all(data)
+        .map_err(|e| NetworkError::ProtocolError(format!("Zstd decoding failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zstd_roundtrip() {
+        let data = b"Hello, zstd!";
+        let encoded = encode(data).unwrap();
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded.as_slice(), data);
+    }
+
+    #[test]
+    fn test_zstd_invalid_data() {
+        let invalid = b"not zstd data";
+        let result = decode(invalid);
+        assert!(result.is_err());
+    }
+}
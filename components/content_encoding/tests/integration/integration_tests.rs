@@ -9,12 +9,14 @@ fn test_multiple_encoding_types_on_same_encoder() {
     let gzip_encoded = encoder.encode(data, Encoding::Gzip).unwrap();
     let deflate_encoded = encoder.encode(data, Encoding::Deflate).unwrap();
     let brotli_encoded = encoder.encode(data, Encoding::Brotli).unwrap();
+    let zstd_encoded = encoder.encode(data, Encoding::Zstd).unwrap();
     let identity_encoded = encoder.encode(data, Encoding::Identity).unwrap();
 
     // All should decode correctly
     assert_eq!(encoder.decode(&gzip_encoded, Encoding::Gzip).unwrap().as_slice(), data);
     assert_eq!(encoder.decode(&deflate_encoded, Encoding::Deflate).unwrap().as_slice(), data);
     assert_eq!(encoder.decode(&brotli_encoded, Encoding::Brotli).unwrap().as_slice(), data);
+    assert_eq!(encoder.decode(&zstd_encoded, Encoding::Zstd).unwrap().as_slice(), data);
     assert_eq!(encoder.decode(&identity_encoded, Encoding::Identity).unwrap().as_slice(), data);
 }
 
@@ -1,4 +1,5 @@
 use content_encoding::{ContentEncoder, Encoding};
+use network_errors::NetworkError;
 
 #[test]
 fn test_gzip_encode_decode_roundtrip() {
@@ -52,6 +53,38 @@ fn test_brotli_encode_decode_roundtrip() {
     assert_eq!(decoded.as_slice(), original_data);
 }
 
+#[test]
+fn test_zstd_encode_decode_roundtrip() {
+    let encoder = ContentEncoder::new();
+    let original_data = b"Zstd compression test data with repeated patterns patterns patterns";
+
+    let encoded = encoder.encode(original_data, Encoding::Zstd)
+        .expect("Zstd encoding should succeed");
+
+    assert_ne!(encoded.as_slice(), original_data);
+
+    let decoded = encoder.decode(&encoded, Encoding::Zstd)
+        .expect("Zstd decoding should succeed");
+
+    assert_eq!(decoded.as_slice(), original_data);
+}
+
+#[test]
+fn test_zstd_decode_known_fixture() {
+    let encoder = ContentEncoder::new();
+    // Output of `printf 'Hello, zstd! Hello, zstd! Hello, zstd!' | zstd -19 -c --no-check`
+    let fixture: [u8; 29] = [
+        0x28, 0xb5, 0x2f, 0xfd, 0x00, 0x68, 0xa5, 0x00, 0x00, 0x70, 0x48, 0x65, 0x6c, 0x6c,
+        0x6f, 0x2c, 0x20, 0x7a, 0x73, 0x74, 0x64, 0x21, 0x20, 0x48, 0x01, 0x00, 0x00, 0x4e,
+        0x25,
+    ];
+
+    let decoded = encoder.decode(&fixture, Encoding::Zstd)
+        .expect("Zstd fixture should decode");
+
+    assert_eq!(decoded.as_slice(), b"Hello, zstd! Hello, zstd! Hello, zstd!");
+}
+
 #[test]
 fn test_identity_encoding_is_noop() {
     let encoder = ContentEncoder::new();
@@ -112,6 +145,59 @@ fn test_decode_corrupted_brotli_data_fails() {
     assert!(result.is_err(), "Decoding corrupted brotli data should fail");
 }
 
+#[test]
+fn test_decode_corrupted_zstd_data_fails() {
+    let encoder = ContentEncoder::new();
+    let corrupted_data = b"This is not valid zstd data";
+
+    let result = encoder.decode(corrupted_data, Encoding::Zstd);
+
+    assert!(result.is_err(), "Decoding corrupted zstd data should fail");
+}
+
+#[test]
+fn test_strict_gzip_accepts_single_member() {
+    let encoder = ContentEncoder::new().with_strict(true);
+    let encoded = encoder.encode(b"single member", Encoding::Gzip).unwrap();
+
+    let decoded = encoder.decode(&encoded, Encoding::Gzip)
+        .expect("A single gzip member should decode in strict mode");
+
+    assert_eq!(decoded.as_slice(), b"single member");
+}
+
+#[test]
+fn test_gzip_concatenated_members() {
+    let lenient = ContentEncoder::new();
+    let strict = ContentEncoder::new().with_strict(true);
+    let mut encoded = lenient.encode(b"first, ", Encoding::Gzip).unwrap();
+    encoded.extend(lenient.encode(b"second", Encoding::Gzip).unwrap());
+
+    let decoded = lenient.decode(&encoded, Encoding::Gzip)
+        .expect("Concatenated members should decode by default");
+    assert_eq!(decoded.as_slice(), b"first, second");
+
+    let result = strict.decode(&encoded, Encoding::Gzip);
+    assert!(result.is_err(), "Strict mode should reject a second member");
+}
+
+#[test]
+fn test_gzip_trailing_junk() {
+    let lenient = ContentEncoder::new();
+    let strict = ContentEncoder::new().with_strict(true);
+    let mut encoded = lenient.encode(b"payload", Encoding::Gzip).unwrap();
+    encoded.extend_from_slice(b"\r\nHTTP/1.1 200 OK\r\n");
+
+    let decoded = lenient.decode(&encoded, Encoding::Gzip)
+        .expect("Trailing junk should be ignored by default");
+    assert_eq!(decoded.as_slice(), b"payload");
+
+    match strict.decode(&encoded, Encoding::Gzip) {
+        Err(NetworkError::ProtocolError(msg)) => assert!(msg.contains("trailing")),
+        other => panic!("Expected ProtocolError, got {:?}", other),
+    }
+}
+
 #[test]
 fn test_get_accept_encoding_header() {
     let encoder = ContentEncoder::new();
@@ -121,6 +207,7 @@ fn test_get_accept_encoding_header() {
     assert!(accept_encoding.contains("gzip"));
     assert!(accept_encoding.contains("deflate"));
     assert!(accept_encoding.contains("br"));
+    assert!(accept_encoding.contains("zstd"));
 }
 
 #[test]
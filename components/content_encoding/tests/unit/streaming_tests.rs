@@ -67,6 +67,27 @@ async fn test_brotli_stream_decode() {
     assert_eq!(result.as_slice(), original_data);
 }
 
+#[tokio::test]
+async fn test_zstd_stream_decode() {
+    let encoder = ContentEncoder::new();
+    let original_data = b"Zstd streaming test with multiple chunks of data";
+
+    let encoded = encoder.encode(original_data, Encoding::Zstd)
+        .expect("Encoding should succeed");
+
+    let (first, second) = encoded.split_at(encoded.len() / 2);
+    let stream = stream::iter(vec![Bytes::copy_from_slice(first), Bytes::copy_from_slice(second)]);
+    let mut decode_stream = encoder.decode_stream(stream, Encoding::Zstd);
+
+    let mut result = Vec::new();
+    while let Some(chunk_result) = decode_stream.next().await {
+        let chunk = chunk_result.expect("Stream decoding should succeed");
+        result.extend_from_slice(&chunk);
+    }
+
+    assert_eq!(result.as_slice(), original_data);
+}
+
 #[tokio::test]
 async fn test_identity_stream_decode() {
     let encoder = ContentEncoder::new();
@@ -2,14 +2,16 @@
 //!
 //! Provides CookieStore for storing and retrieving cookies with policy enforcement.
 
-use cookie::Cookie;
+use cookie::{Cookie, SameSite};
 use cookie_store::CookieStore as ExternalCookieStore;
 use network_errors::NetworkError;
+use network_types::public_suffix;
 use url::Url;
 
 /// Cookie storage structure
 ///
 /// Manages cookies per domain with support for Secure, HttpOnly, Path, and Domain matching.
+/// Cookies whose Domain attribute is a public suffix (e.g. `com` or `co.uk`) are rejected.
 pub struct CookieStore {
     inner: ExternalCookieStore,
 }
@@ -26,7 +28,7 @@ impl CookieStore {
     /// ```
     pub fn new() -> Self {
         Self {
-            inner: ExternalCookieStore::default(),
+            inner: ExternalCookieStore::new(Some(public_suffix::list().clone())),
         }
     }
 
@@ -43,7 +45,8 @@ impl CookieStore {
     /// # Returns
     ///
     /// * `Ok(())` if the cookie was stored successfully
-    /// * `Err(NetworkError)` if the cookie could not be stored
+    /// * `Err(NetworkError)` if the cookie could not be stored, including when its
+    ///   Domain attribute is a public suffix
     ///
     /// # Examples
     ///
@@ -117,6 +120,61 @@ impl CookieStore {
             .collect()
     }
 
+    /// Get the cookies to attach to a request, applying SameSite rules
+    ///
+    /// Starts from the cookies matching `url` (see [`get_cookies`](Self::get_cookies))
+    /// and, for cross-site requests, withholds cookies according to their
+    /// SameSite attribute:
+    /// - `Strict` cookies are never sent cross-site
+    /// - `Lax` cookies (and cookies without SameSite, which default to Lax) are only
+    ///   sent on top-level navigations
+    /// - `None` cookies are sent cross-site only if they are also `Secure`
+    ///
+    /// A request is same-site when `url` and `site_for_cookies` share the same
+    /// scheme and registrable domain. Lax cookies should only be sent on top-level
+    /// navigations using a safe method (e.g. GET); callers pass
+    /// `is_top_level_navigation = false` otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The URL being requested
+    /// * `site_for_cookies` - The URL of the top-level document initiating the request
+    /// * `is_top_level_navigation` - Whether the request navigates the top-level browsing context
+    ///
+    /// # Returns
+    ///
+    /// A vector of cookies to send with the request
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use cookie_manager::CookieStore;
+    /// use cookie::Cookie;
+    /// use url::Url;
+    ///
+    /// let mut store = CookieStore::new();
+    /// let url = Url::parse("https://example.com").unwrap();
+    /// let cookie = Cookie::parse("session=abc; SameSite=Strict").unwrap().into_owned();
+    /// store.add_cookie(cookie, &url).unwrap();
+    ///
+    /// let other_site = Url::parse("https://other.com").unwrap();
+    /// assert_eq!(store.cookies_for_request(&url, &url, false).len(), 1);
+    /// assert!(store.cookies_for_request(&url, &other_site, false).is_empty());
+    /// ```
+    pub fn cookies_for_request(
+        &self,
+        url: &Url,
+        site_for_cookies: &Url,
+        is_top_level_navigation: bool,
+    ) -> Vec<Cookie<'static>> {
+        let same_site = is_same_site(url, site_for_cookies);
+
+        self.get_cookies(url)
+            .into_iter()
+            .filter(|cookie| same_site || allowed_cross_site(cookie, is_top_level_navigation))
+            .collect()
+    }
+
     /// Clear all cookies from the store
     ///
     /// Removes all stored cookies.
@@ -141,6 +199,32 @@ impl CookieStore {
     }
 }
 
+/// Check whether a cookie may be sent on a cross-site request
+fn allowed_cross_site(cookie: &Cookie<'_>, is_top_level_navigation: bool) -> bool {
+    match cookie.same_site() {
+        Some(SameSite::Strict) => false,
+        Some(SameSite::None) => cookie.secure().unwrap_or(false),
+        Some(SameSite::Lax) | None => is_top_level_navigation,
+    }
+}
+
+/// Check whether two URLs are same-site (same scheme and registrable domain)
+fn is_same_site(a: &Url, b: &Url) -> bool {
+    if a.scheme() != b.scheme() {
+        return false;
+    }
+    match (a.host_str(), b.host_str()) {
+        (Some(host_a), Some(host_b)) => site_of(host_a) == site_of(host_b),
+        _ => false,
+    }
+}
+
+/// The site of a host: its registrable domain, or the host itself for IP
+/// addresses and hosts that are public suffixes
+fn site_of(host: &str) -> String {
+    public_suffix::registrable_domain(host).unwrap_or_else(|| host.to_ascii_lowercase())
+}
+
 impl Default for CookieStore {
     fn default() -> Self {
         Self::new()
@@ -170,4 +254,39 @@ mod tests {
         assert_eq!(cookies.len(), 1);
         assert_eq!(cookies[0].name(), "test");
     }
+
+    #[test]
+    fn test_samesite_none_requires_secure_cross_site() {
+        let mut secure = Cookie::new("a", "b");
+        secure.set_same_site(SameSite::None);
+        secure.set_secure(true);
+        assert!(allowed_cross_site(&secure, false));
+
+        let mut insecure = Cookie::new("a", "b");
+        insecure.set_same_site(SameSite::None);
+        insecure.set_secure(false);
+        assert!(!allowed_cross_site(&insecure, false));
+    }
+
+    #[test]
+    fn test_is_same_site_uses_public_suffix_list() {
+        let a = Url::parse("https://a.example.co.uk").unwrap();
+        let b = Url::parse("https://b.example.co.uk").unwrap();
+        let c = Url::parse("https://other.co.uk").unwrap();
+
+        assert!(is_same_site(&a, &b));
+        assert!(!is_same_site(&a, &c));
+    }
+
+    #[test]
+    fn test_is_same_site() {
+        let a = Url::parse("https://www.example.com").unwrap();
+        let b = Url::parse("https://api.example.com").unwrap();
+        let c = Url::parse("https://example.org").unwrap();
+        let d = Url::parse("http://www.example.com").unwrap();
+
+        assert!(is_same_site(&a, &b));
+        assert!(!is_same_site(&a, &c));
+        assert!(!is_same_site(&a, &d));
+    }
 }
@@ -223,3 +223,131 @@ fn test_subdomain_cookie_matching() {
     // Then
     assert_eq!(cookies.len(), 1, "Cookie should be available on subdomain");
 }
+
+/// Stores a cookie parsed from a Set-Cookie string for https://example.com
+fn store_with(set_cookie: &str) -> (CookieStore, Url) {
+    let mut store = CookieStore::new();
+    let url = Url::parse("https://example.com/").unwrap();
+    let cookie = Cookie::parse(set_cookie.to_string()).unwrap();
+    store.add_cookie(cookie, &url).unwrap();
+    (store, url)
+}
+
+/// Given a SameSite=Strict cookie
+/// When a cross-site subresource or navigation request is made
+/// Then the cookie is withheld, but it is sent same-site
+#[test]
+fn test_samesite_strict_withheld_cross_site() {
+    // Given
+    let (store, url) = store_with("session=abc; SameSite=Strict; Secure");
+    let other_site = Url::parse("https://attacker.com/").unwrap();
+
+    // When & Then
+    assert!(store.cookies_for_request(&url, &other_site, false).is_empty());
+    assert!(store.cookies_for_request(&url, &other_site, true).is_empty());
+    assert_eq!(store.cookies_for_request(&url, &url, false).len(), 1);
+}
+
+/// Given a SameSite=Lax cookie
+/// When a cross-site request is made
+/// Then it is sent only on top-level navigation
+#[test]
+fn test_samesite_lax_sent_on_top_level_navigation() {
+    // Given
+    let (store, url) = store_with("session=abc; SameSite=Lax");
+    let other_site = Url::parse("https://other.com/").unwrap();
+
+    // When & Then
+    assert_eq!(store.cookies_for_request(&url, &other_site, true).len(), 1);
+    assert!(store.cookies_for_request(&url, &other_site, false).is_empty());
+}
+
+/// Given a cookie without a SameSite attribute
+/// When a cross-site subresource request is made
+/// Then it is treated as Lax and withheld
+#[test]
+fn test_samesite_default_is_lax() {
+    // Given
+    let (store, url) = store_with("session=abc");
+    let other_site = Url::parse("https://other.com/").unwrap();
+
+    // When & Then
+    assert!(store.cookies_for_request(&url, &other_site, false).is_empty());
+    assert_eq!(store.cookies_for_request(&url, &other_site, true).len(), 1);
+}
+
+/// Given a Secure SameSite=None cookie
+/// When a cross-site subresource request is made
+/// Then the cookie is sent
+#[test]
+fn test_samesite_none_secure_sent_cross_site() {
+    // Given
+    let (store, url) = store_with("session=abc; SameSite=None; Secure");
+    let other_site = Url::parse("https://other.com/").unwrap();
+
+    // When
+    let cookies = store.cookies_for_request(&url, &other_site, false);
+
+    // Then
+    assert_eq!(cookies.len(), 1);
+}
+
+/// Given a SameSite=Strict cookie
+/// When a request comes from a subdomain of the same site
+/// Then the cookie is sent
+#[test]
+fn test_samesite_strict_sent_for_same_site_subdomain() {
+    // Given
+    let (store, url) = store_with("session=abc; SameSite=Strict");
+    let subdomain = Url::parse("https://www.example.com/").unwrap();
+
+    // When & Then
+    assert_eq!(store.cookies_for_request(&url, &subdomain, false).len(), 1);
+}
+
+/// Given a cookie with a public suffix Domain attribute
+/// When it is added to the store
+/// Then it is rejected to prevent supercookies
+#[test]
+fn test_public_suffix_domain_rejected() {
+    // Given
+    let mut store = CookieStore::new();
+    let url = Url::parse("https://www.example.co.uk/").unwrap();
+
+    for domain in ["co.uk", ".co.uk", "uk"] {
+        let mut cookie = Cookie::new("tracker", "1");
+        cookie.set_domain(domain);
+
+        // When
+        let result = store.add_cookie(cookie, &url);
+
+        // Then
+        assert!(result.is_err(), "Domain={} should be rejected", domain);
+    }
+
+    let com_url = Url::parse("https://example.com/").unwrap();
+    let mut cookie = Cookie::new("tracker", "1");
+    cookie.set_domain("com");
+    assert!(store.add_cookie(cookie, &com_url).is_err());
+    assert!(store.get_cookies(&url).is_empty());
+}
+
+/// Given a cookie with a registrable Domain attribute under a multi-label suffix
+/// When it is added to the store
+/// Then it is accepted and shared with subdomains
+#[test]
+fn test_registrable_domain_accepted() {
+    // Given
+    let mut store = CookieStore::new();
+    let url = Url::parse("https://www.example.co.uk/").unwrap();
+    let mut cookie = Cookie::new("session", "abc");
+    cookie.set_domain("example.co.uk");
+
+    // When
+    let result = store.add_cookie(cookie, &url);
+
+    // Then
+    assert!(result.is_ok());
+    let other_subdomain = Url::parse("https://shop.example.co.uk/").unwrap();
+    assert_eq!(store.get_cookies(&other_subdomain).len(), 1);
+}
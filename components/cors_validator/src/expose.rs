@@ -0,0 +1,96 @@
+//! Response header filtering for cross-origin responses
+
+use http::header::{
+    HeaderName, ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_EXPOSE_HEADERS, CACHE_CONTROL,
+    CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE, EXPIRES, LAST_MODIFIED, PRAGMA, SET_COOKIE,
+};
+use http::HeaderMap;
+use network_types::{NetworkResponse, Origin, RequestMode};
+use url::Url;
+
+/// CORS-safelisted response header names, readable on every CORS response
+const SAFELISTED_RESPONSE_HEADERS: [HeaderName; 7] = [
+    CACHE_CONTROL,
+    CONTENT_LANGUAGE,
+    CONTENT_LENGTH,
+    CONTENT_TYPE,
+    EXPIRES,
+    LAST_MODIFIED,
+    PRAGMA,
+];
+
+/// Whether `response` is a cross-origin response to a request from `origin`
+///
+/// An origin that can't be parsed, such as `null`, is treated as opaque
+/// and so is cross-origin with every URL.
+pub(crate) fn is_cross_origin(response: &NetworkResponse, origin: &str) -> bool {
+    let request_origin = match Url::parse(origin) {
+        Ok(url) => Origin::from_url(&url),
+        Err(_) => return true,
+    };
+    !request_origin.is_same_origin(&Origin::from_url(&response.url))
+}
+
+/// Headers of `response` that a document at `origin` may read
+///
+/// Same-origin responses and responses to navigations keep all their
+/// headers. A cross-origin `cors` response only exposes the CORS-safelisted
+/// headers and those named in `Access-Control-Expose-Headers`, and a
+/// cross-origin `no-cors` response is opaque and exposes none. `Set-Cookie`
+/// is never exposed to a cross-origin document.
+///
+/// An `Access-Control-Expose-Headers` value of `*` exposes every header,
+/// unless the response allows credentials
+/// (`Access-Control-Allow-Credentials: true`), where `*` is only a header
+/// name.
+pub(crate) fn exposed_headers(
+    response: &NetworkResponse,
+    origin: &str,
+    mode: RequestMode,
+) -> HeaderMap {
+    if !is_cross_origin(response, origin) {
+        return response.headers.clone();
+    }
+
+    match mode {
+        RequestMode::Navigate | RequestMode::SameOrigin => response.headers.clone(),
+        RequestMode::NoCors => HeaderMap::new(),
+        RequestMode::Cors => {
+            let exposed = exposed_names(&response.headers);
+            let mut headers = response.headers.clone();
+            headers.remove(SET_COOKIE);
+            headers.remove("set-cookie2");
+            if exposed.iter().any(|name| name == "*") && !allows_credentials(&response.headers) {
+                return headers;
+            }
+
+            let mut filtered = HeaderMap::new();
+            for (name, value) in &headers {
+                if SAFELISTED_RESPONSE_HEADERS.contains(name)
+                    || exposed.iter().any(|exposed| name.as_str() == exposed)
+                {
+                    filtered.append(name, value.clone());
+                }
+            }
+            filtered
+        }
+    }
+}
+
+/// Lowercased header names listed in `Access-Control-Expose-Headers`
+fn exposed_names(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(ACCESS_CONTROL_EXPOSE_HEADERS)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn allows_credentials(headers: &HeaderMap) -> bool {
+    headers
+        .get(ACCESS_CONTROL_ALLOW_CREDENTIALS)
+        .is_some_and(|value| value.as_bytes() == b"true")
+}
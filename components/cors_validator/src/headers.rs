@@ -59,28 +59,16 @@ impl HeaderBuilder {
             );
         }
 
-        // Add Access-Control-Allow-Methods for preflight responses
-        headers.insert(
-            "Access-Control-Allow-Methods",
-            HeaderValue::from_static("GET, POST, PUT, DELETE, PATCH, OPTIONS"),
-        );
-
-        // Add Access-Control-Allow-Headers for preflight responses
-        headers.insert(
-            "Access-Control-Allow-Headers",
-            HeaderValue::from_static("Content-Type, Authorization, X-Requested-With"),
-        );
-
-        // Add Access-Control-Max-Age (cache preflight for 1 hour)
-        headers.insert(
-            "Access-Control-Max-Age",
-            HeaderValue::from_static("3600"),
-        );
+        // Add Access-Control-Allow-Methods, -Headers and -Max-Age for preflight responses
+        self.insert_preflight_headers(&mut headers);
 
         headers
     }
 
     /// Build headers for a preflight OPTIONS response
+    ///
+    /// Echoes `requested_method` in Access-Control-Allow-Methods; the allowed
+    /// headers and max age come from the configuration.
     pub fn build_preflight_response_headers(&self, origin: &str, requested_method: &str) -> HeaderMap {
         let mut headers = HeaderMap::new();
 
@@ -97,18 +85,36 @@ impl HeaderBuilder {
                 .unwrap_or(HeaderValue::from_static("GET, POST, PUT, DELETE, OPTIONS")),
         );
 
-        // Add Access-Control-Allow-Headers
-        headers.insert(
-            "Access-Control-Allow-Headers",
-            HeaderValue::from_static("Content-Type, Authorization, X-Requested-With"),
-        );
+        // Add Access-Control-Allow-Headers and -Max-Age
+        self.insert_allowed_headers_and_max_age(&mut headers);
+
+        // Add Access-Control-Allow-Credentials if enabled
+        if self.config.allow_credentials {
+            headers.insert(
+                "Access-Control-Allow-Credentials",
+                HeaderValue::from_static("true"),
+            );
+        }
 
-        // Add Access-Control-Max-Age
+        headers
+    }
+
+    /// Build headers for a preflight OPTIONS response from the allowlist
+    ///
+    /// Unlike [`HeaderBuilder::build_preflight_response_headers`], the
+    /// allowed methods also come from the configuration.
+    pub fn build_allowlist_preflight_headers(&self, origin: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+
+        // Add Access-Control-Allow-Origin
         headers.insert(
-            "Access-Control-Max-Age",
-            HeaderValue::from_static("3600"),
+            "Access-Control-Allow-Origin",
+            HeaderValue::from_str(origin).unwrap_or(HeaderValue::from_static("*")),
         );
 
+        // Add Access-Control-Allow-Methods, -Headers and -Max-Age
+        self.insert_preflight_headers(&mut headers);
+
         // Add Access-Control-Allow-Credentials if enabled
         if self.config.allow_credentials {
             headers.insert(
@@ -119,6 +125,39 @@ impl HeaderBuilder {
 
         headers
     }
+
+    /// Insert the configured Access-Control-Allow-Methods, -Allow-Headers and
+    /// -Max-Age headers
+    fn insert_preflight_headers(&self, headers: &mut HeaderMap) {
+        let methods = self
+            .config
+            .allowed_methods
+            .iter()
+            .map(|method| method.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if let Ok(value) = HeaderValue::from_str(&methods) {
+            headers.insert("Access-Control-Allow-Methods", value);
+        }
+
+        self.insert_allowed_headers_and_max_age(headers);
+    }
+
+    /// Insert the configured Access-Control-Allow-Headers and -Max-Age headers
+    fn insert_allowed_headers_and_max_age(&self, headers: &mut HeaderMap) {
+        if !self.config.allowed_headers.is_empty() {
+            if let Ok(value) = HeaderValue::from_str(&self.config.allowed_headers.join(", ")) {
+                headers.insert("Access-Control-Allow-Headers", value);
+            }
+        }
+
+        if let Some(max_age) = self.config.max_age {
+            headers.insert(
+                "Access-Control-Max-Age",
+                HeaderValue::from(max_age.as_secs()),
+            );
+        }
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +186,7 @@ mod tests {
             enforce_same_origin: false,
             allow_credentials: true,
             allowed_origins: Some(vec!["https://example.com".to_string()]),
+            ..CorsConfig::default()
         };
         let builder = HeaderBuilder::new(config);
         let headers = builder.build_response_headers(
@@ -167,6 +207,7 @@ mod tests {
             enforce_same_origin: false,
             allow_credentials: false,
             allowed_origins: None,
+            ..CorsConfig::default()
         };
         let builder = HeaderBuilder::new(config);
         let headers = builder.build_response_headers(
@@ -199,6 +240,8 @@ mod tests {
             redirected: false,
             type_: network_types::ResponseType::Cors,
             timing: network_types::ResourceTiming::default(),
+            trailers: None,
+            source: network_types::ResponseSource::Network,
         }
     }
 }
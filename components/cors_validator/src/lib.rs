@@ -15,6 +15,7 @@ mod validator;
 mod preflight;
 mod headers;
 mod policy;
+mod expose;
 
 pub use validator::CorsValidator;
 pub use policy::CorsConfig;
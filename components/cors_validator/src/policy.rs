@@ -1,5 +1,8 @@
 //! CORS policy configuration
 
+use network_types::HttpMethod;
+use std::time::Duration;
+
 /// CORS policy configuration
 ///
 /// Controls how CORS validation is performed.
@@ -23,6 +26,19 @@ pub struct CorsConfig {
     /// If Some, only the specified origins are allowed.
     /// Note: Cannot include wildcard (*) when credentials are allowed.
     pub allowed_origins: Option<Vec<String>>,
+
+    /// Methods advertised in Access-Control-Allow-Methods
+    pub allowed_methods: Vec<HttpMethod>,
+
+    /// Request headers advertised in Access-Control-Allow-Headers
+    ///
+    /// Header names are compared case-insensitively.
+    pub allowed_headers: Vec<String>,
+
+    /// How long a preflight result may be cached (Access-Control-Max-Age)
+    ///
+    /// If None, the header is omitted.
+    pub max_age: Option<Duration>,
 }
 
 impl Default for CorsConfig {
@@ -31,6 +47,20 @@ impl Default for CorsConfig {
             enforce_same_origin: false,
             allow_credentials: false,
             allowed_origins: None,
+            allowed_methods: vec![
+                HttpMethod::Get,
+                HttpMethod::Post,
+                HttpMethod::Put,
+                HttpMethod::Delete,
+                HttpMethod::Patch,
+                HttpMethod::Options,
+            ],
+            allowed_headers: vec![
+                "Content-Type".to_string(),
+                "Authorization".to_string(),
+                "X-Requested-With".to_string(),
+            ],
+            max_age: Some(Duration::from_secs(3600)),
         }
     }
 }
@@ -41,34 +71,119 @@ impl CorsConfig {
         Self {
             enforce_same_origin,
             allow_credentials,
-            allowed_origins: None,
+            ..Self::default()
         }
     }
 
+    /// Add an origin to the allowlist
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.allowed_origins
+            .get_or_insert_with(Vec::new)
+            .push(origin.into());
+        self
+    }
+
+    /// Replace the allowed methods
+    pub fn allow_methods(mut self, methods: impl IntoIterator<Item = HttpMethod>) -> Self {
+        self.allowed_methods = methods.into_iter().collect();
+        self
+    }
+
+    /// Replace the allowed request headers
+    pub fn allow_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_headers = headers.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set whether credentials are allowed
+    pub fn allow_credentials(mut self, allow: bool) -> Self {
+        self.allow_credentials = allow;
+        self
+    }
+
+    /// Set how long preflight results may be cached
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Finish building the configuration
+    ///
+    /// Start from [`CorsConfig::default`] and chain the builder methods to
+    /// define an allowlist:
+    ///
+    /// ```
+    /// use cors_validator::CorsConfig;
+    /// use network_types::HttpMethod;
+    /// use std::time::Duration;
+    ///
+    /// let config = CorsConfig::default()
+    ///     .allow_origin("https://app.example.com")
+    ///     .allow_methods([HttpMethod::Get, HttpMethod::Post])
+    ///     .allow_headers(["content-type"])
+    ///     .allow_credentials(true)
+    ///     .max_age(Duration::from_secs(600))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(config.is_origin_allowed("https://app.example.com"));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration fails [`CorsConfig::validate`],
+    /// e.g. a wildcard origin combined with credentials.
+    pub fn build(self) -> Result<Self, String> {
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Check whether an origin is in the allowlist
+    ///
+    /// All origins are allowed when no allowlist is configured.
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        match &self.allowed_origins {
+            None => true,
+            Some(origins) => origins
+                .iter()
+                .any(|allowed| allowed == "*" || allowed == origin),
+        }
+    }
+
+    /// Check whether a method is allowed
+    pub fn is_method_allowed(&self, method: HttpMethod) -> bool {
+        self.allowed_methods.contains(&method)
+    }
+
+    /// Check whether a request header is allowed
+    pub fn is_header_allowed(&self, name: &str) -> bool {
+        self.allowed_headers
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(name))
+    }
+
     /// Create a configuration that enforces same-origin policy
     pub fn same_origin_only() -> Self {
         Self {
             enforce_same_origin: true,
-            allow_credentials: false,
-            allowed_origins: None,
+            ..Self::default()
         }
     }
 
     /// Create a configuration that allows all origins
     pub fn allow_all_origins() -> Self {
-        Self {
-            enforce_same_origin: false,
-            allow_credentials: false,
-            allowed_origins: None,
-        }
+        Self::default()
     }
 
     /// Create a configuration that allows credentials
     pub fn with_credentials() -> Self {
         Self {
-            enforce_same_origin: false,
             allow_credentials: true,
-            allowed_origins: None,
+            ..Self::default()
         }
     }
 
@@ -1,5 +1,6 @@
 //! Preflight request handling
 
+use crate::CorsConfig;
 use network_types::{HttpMethod, NetworkRequest, RequestMode, CredentialsMode};
 use http::{HeaderMap, HeaderValue};
 
@@ -31,6 +32,37 @@ impl PreflightChecker {
         !self.is_simple_method(&request.method)
     }
 
+    /// Check if a request is not a "simple request" and must be preflighted
+    ///
+    /// Follows the Fetch standard's CORS safelist: the method must be GET,
+    /// HEAD or POST and every header must be a CORS-safelisted request header
+    /// (`Accept`, `Accept-Language`, `Content-Language`, or `Content-Type` with
+    /// a value of `application/x-www-form-urlencoded`, `multipart/form-data`
+    /// or `text/plain`). Requests in same-origin or no-cors mode are never
+    /// preflighted.
+    pub fn requires_preflight(&self, request: &NetworkRequest) -> bool {
+        if request.mode == RequestMode::SameOrigin || request.mode == RequestMode::NoCors {
+            return false;
+        }
+
+        if !matches!(
+            request.method,
+            HttpMethod::Get | HttpMethod::Head | HttpMethod::Post
+        ) {
+            return true;
+        }
+
+        let mut safelisted_size = 0;
+        for (name, value) in &request.headers {
+            if !is_safelisted_header(name.as_str(), value.as_bytes()) {
+                return true;
+            }
+            safelisted_size += value.len();
+        }
+
+        safelisted_size > MAX_SAFELISTED_HEADERS_SIZE
+    }
+
     /// Build a preflight OPTIONS request
     ///
     /// Creates a preflight request with appropriate Access-Control-Request-* headers.
@@ -54,11 +86,9 @@ impl PreflightChecker {
         };
 
         // Add Access-Control-Request-Method header
-        let method_str = method_to_string(&request.method);
         preflight.headers.insert(
             "Access-Control-Request-Method",
-            HeaderValue::from_str(&method_str)
-                .unwrap_or_else(|_| HeaderValue::from_static("GET")),
+            HeaderValue::from_static(request.method.as_str()),
         );
 
         // If original request has custom headers, add Access-Control-Request-Headers
@@ -79,6 +109,63 @@ impl PreflightChecker {
         preflight
     }
 
+    /// Check a received preflight request against the configured allowlists
+    ///
+    /// Parses Access-Control-Request-Method and Access-Control-Request-Headers
+    /// and verifies every requested method and header is permitted. Header
+    /// names are matched case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason, naming the offending method or header, if the
+    /// preflight must be rejected.
+    pub fn check_preflight_request(
+        &self,
+        preflight: &NetworkRequest,
+        config: &CorsConfig,
+    ) -> Result<(), String> {
+        let method = preflight
+            .headers
+            .get("Access-Control-Request-Method")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<HttpMethod>().ok())
+            .ok_or_else(|| {
+                "Preflight is missing a valid Access-Control-Request-Method".to_string()
+            })?;
+        if !config.is_method_allowed(method) {
+            return Err(format!(
+                "Method {} is not allowed by Access-Control-Allow-Methods",
+                method
+            ));
+        }
+
+        for header in self.requested_headers(preflight) {
+            if !config.is_header_allowed(&header) {
+                return Err(format!(
+                    "Header {} is not allowed by Access-Control-Allow-Headers",
+                    header
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse the header names listed in Access-Control-Request-Headers
+    ///
+    /// Names are lowercased; empty entries are skipped.
+    fn requested_headers(&self, preflight: &NetworkRequest) -> Vec<String> {
+        preflight
+            .headers
+            .get_all("Access-Control-Request-Headers")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim().to_ascii_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect()
+    }
+
     /// Check if a method is a "simple" method (doesn't require preflight)
     ///
     /// Simple methods are: GET, HEAD, POST (with simple content type)
@@ -87,28 +174,55 @@ impl PreflightChecker {
     }
 }
 
+/// Safelisted header values longer than this are not safelisted
+const MAX_SAFELISTED_VALUE_LENGTH: usize = 128;
+
+/// Safelisted headers whose values total more than this need a preflight
+const MAX_SAFELISTED_HEADERS_SIZE: usize = 1024;
+
+/// Content types a simple request may send
+const SAFELISTED_CONTENT_TYPES: [&str; 3] = [
+    "application/x-www-form-urlencoded",
+    "multipart/form-data",
+    "text/plain",
+];
+
+/// Check if a header is a CORS-safelisted request header
+fn is_safelisted_header(name: &str, value: &[u8]) -> bool {
+    if value.len() > MAX_SAFELISTED_VALUE_LENGTH {
+        return false;
+    }
+
+    match name.to_ascii_lowercase().as_str() {
+        "accept" => !value.iter().copied().any(is_cors_unsafe_byte),
+        "accept-language" | "content-language" => value
+            .iter()
+            .all(|&b| b.is_ascii_alphanumeric() || b" *,-.;=".contains(&b)),
+        "content-type" => {
+            if value.iter().copied().any(is_cors_unsafe_byte) {
+                return false;
+            }
+            let essence = String::from_utf8_lossy(value);
+            let essence = essence.split(';').next().unwrap_or("").trim();
+            SAFELISTED_CONTENT_TYPES
+                .iter()
+                .any(|safe| safe.eq_ignore_ascii_case(essence))
+        }
+        _ => false,
+    }
+}
+
+/// Check if a byte is a CORS-unsafe request-header byte
+fn is_cors_unsafe_byte(b: u8) -> bool {
+    (b < 0x20 && b != b'\t') || b"\"():<>?@[\\]{}\x7f".contains(&b)
+}
+
 impl Default for PreflightChecker {
     fn default() -> Self {
         Self::new()
     }
 }
 
-/// Convert HTTP method to string
-fn method_to_string(method: &HttpMethod) -> String {
-    match method {
-        HttpMethod::Get => "GET",
-        HttpMethod::Head => "HEAD",
-        HttpMethod::Post => "POST",
-        HttpMethod::Put => "PUT",
-        HttpMethod::Delete => "DELETE",
-        HttpMethod::Connect => "CONNECT",
-        HttpMethod::Options => "OPTIONS",
-        HttpMethod::Trace => "TRACE",
-        HttpMethod::Patch => "PATCH",
-    }
-    .to_string()
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -172,6 +286,34 @@ mod tests {
         assert!(!checker.is_preflight_needed(&request));
     }
 
+    #[test]
+    fn test_safelisted_content_type_ignores_parameters_and_case() {
+        assert!(is_safelisted_header(
+            "Content-Type",
+            b"Text/Plain; charset=utf-8"
+        ));
+        assert!(!is_safelisted_header("content-type", b"application/json"));
+    }
+
+    #[test]
+    fn test_safelisted_header_rejects_unsafe_values() {
+        assert!(is_safelisted_header("accept-language", b"en-US,en;q=0.9"));
+        assert!(!is_safelisted_header("accept-language", b"en\"US"));
+        assert!(!is_safelisted_header("accept", b"text/html{"));
+        assert!(!is_safelisted_header("accept", &[b'a'; 129]));
+    }
+
+    #[test]
+    fn test_requires_preflight_when_safelisted_headers_too_large() {
+        let checker = PreflightChecker::new();
+        let mut request = create_request(HttpMethod::Get, RequestMode::Cors);
+        let long = HeaderValue::from_str(&"a".repeat(100)).unwrap();
+        for _ in 0..11 {
+            request.headers.append("accept", long.clone());
+        }
+        assert!(checker.requires_preflight(&request));
+    }
+
     #[test]
     fn test_build_preflight_has_options_method() {
         let checker = PreflightChecker::new();
@@ -180,6 +322,37 @@ mod tests {
         assert_eq!(preflight.method, HttpMethod::Options);
     }
 
+    fn create_preflight(method: &str, request_headers: &str) -> NetworkRequest {
+        let mut preflight = create_request(HttpMethod::Options, RequestMode::Cors);
+        preflight.headers.insert(
+            "Access-Control-Request-Method",
+            HeaderValue::from_str(method).unwrap(),
+        );
+        preflight.headers.insert(
+            "Access-Control-Request-Headers",
+            HeaderValue::from_str(request_headers).unwrap(),
+        );
+        preflight
+    }
+
+    #[test]
+    fn test_requested_headers_are_lowercased_and_trimmed() {
+        let checker = PreflightChecker::new();
+        let preflight = create_preflight("PUT", "X-Custom ,Content-Type,,");
+        assert_eq!(
+            checker.requested_headers(&preflight),
+            vec!["x-custom".to_string(), "content-type".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_check_preflight_request_matches_headers_case_insensitively() {
+        let checker = PreflightChecker::new();
+        let config = CorsConfig::default().allow_headers(["X-Custom"]);
+        let preflight = create_preflight("PUT", "x-custom");
+        assert!(checker.check_preflight_request(&preflight, &config).is_ok());
+    }
+
     #[test]
     fn test_build_preflight_has_request_method_header() {
         let checker = PreflightChecker::new();
@@ -1,8 +1,10 @@
 //! CORS validator implementation
 
 use crate::{CorsConfig, CorsResult};
+use crate::expose;
 use crate::headers::HeaderBuilder;
 use crate::preflight::PreflightChecker;
+use http::HeaderMap;
 use network_types::{NetworkRequest, NetworkResponse, RequestMode};
 use url::Url;
 
@@ -130,6 +132,25 @@ impl CorsValidator {
         self.preflight_checker.is_preflight_needed(request)
     }
 
+    /// Check if a request must be preflighted with an OPTIONS request
+    ///
+    /// A request needs no preflight if it is a "simple request": a GET, HEAD
+    /// or POST carrying only CORS-safelisted headers (`Accept`,
+    /// `Accept-Language`, `Content-Language`, and `Content-Type` limited to
+    /// `application/x-www-form-urlencoded`, `multipart/form-data` or
+    /// `text/plain`).
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The network request to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the request is not a simple request, `false` otherwise.
+    pub fn requires_preflight(&self, request: &NetworkRequest) -> bool {
+        self.preflight_checker.requires_preflight(request)
+    }
+
     /// Build a preflight OPTIONS request
     ///
     /// Creates a preflight request that can be used to check if the actual request
@@ -146,6 +167,70 @@ impl CorsValidator {
         self.preflight_checker.build_preflight_request(request)
     }
 
+    /// Respond to a preflight OPTIONS request
+    ///
+    /// Server-side counterpart of [`CorsValidator::build_preflight_request`]:
+    /// checks the origin, Access-Control-Request-Method and
+    /// Access-Control-Request-Headers against the configured allowlists.
+    ///
+    /// # Arguments
+    ///
+    /// * `preflight` - The preflight request received
+    /// * `origin` - The origin that made the request
+    ///
+    /// # Returns
+    ///
+    /// A `CorsResult` with the Access-Control-Allow-* headers to send, or the
+    /// reason the preflight is rejected.
+    pub fn validate_preflight(&self, preflight: &NetworkRequest, origin: &str) -> CorsResult {
+        if !self.config.is_origin_allowed(origin) {
+            return CorsResult::blocked(format!("Origin {} is not allowed", origin));
+        }
+
+        if let Err(reason) = self
+            .preflight_checker
+            .check_preflight_request(preflight, &self.config)
+        {
+            return CorsResult::blocked(reason);
+        }
+
+        CorsResult::allowed_with_headers(
+            self.header_builder.build_allowlist_preflight_headers(origin),
+        )
+    }
+
+    /// Filter a response's headers down to those the requesting document may read
+    ///
+    /// For a cross-origin response to a `cors` request, only the
+    /// CORS-safelisted response headers (`Cache-Control`, `Content-Language`,
+    /// `Content-Length`, `Content-Type`, `Expires`, `Last-Modified`, `Pragma`)
+    /// and those listed in `Access-Control-Expose-Headers` are kept. A
+    /// cross-origin `no-cors` response exposes no headers. Same-origin
+    /// responses keep all headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The network response
+    /// * `origin` - The origin that made the request
+    /// * `mode` - The request's mode
+    ///
+    /// # Returns
+    ///
+    /// The headers the requesting document is allowed to read.
+    pub fn exposed_headers(
+        &self,
+        response: &NetworkResponse,
+        origin: &str,
+        mode: RequestMode,
+    ) -> HeaderMap {
+        expose::exposed_headers(response, origin, mode)
+    }
+
+    /// Check if a response is cross-origin to the origin that requested it
+    pub fn is_cross_origin(&self, response: &NetworkResponse, origin: &str) -> bool {
+        expose::is_cross_origin(response, origin)
+    }
+
     /// Check if a URL and origin are same-origin
     fn is_same_origin(&self, url: &Url, origin: &str) -> bool {
         // Parse origin URL
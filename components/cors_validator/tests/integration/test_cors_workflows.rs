@@ -1,5 +1,5 @@
 use cors_validator::{CorsValidator, CorsConfig};
-use network_types::{NetworkRequest, NetworkResponse, HttpMethod, RequestMode, CredentialsMode, ResponseType};
+use network_types::{NetworkRequest, NetworkResponse, HttpMethod, RequestMode, CredentialsMode, ResponseSource, ResponseType};
 use url::Url;
 use http::HeaderMap;
 
@@ -10,6 +10,7 @@ fn test_complete_cors_preflight_workflow() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -42,6 +43,7 @@ fn test_same_origin_workflow_no_preflight() {
         enforce_same_origin: true,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -65,6 +67,7 @@ fn test_credentials_workflow() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://trusted.com".to_string()]),
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -98,6 +101,7 @@ fn test_blocked_cross_origin_workflow() {
         enforce_same_origin: true,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -145,5 +149,7 @@ fn create_response(status: u16, response_type: ResponseType) -> NetworkResponse
         redirected: false,
         type_: response_type,
         timing: network_types::ResourceTiming::default(),
+        trailers: None,
+        source: ResponseSource::Network,
     }
 }
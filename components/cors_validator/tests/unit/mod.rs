@@ -1,3 +1,5 @@
+mod test_cors_config;
 mod test_cors_validator;
 mod test_preflight;
 mod test_headers;
+mod test_exposed_headers;
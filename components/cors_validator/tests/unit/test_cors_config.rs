@@ -0,0 +1,184 @@
+use cors_validator::{CorsConfig, CorsValidator};
+use http::{HeaderMap, HeaderValue};
+use network_types::{CredentialsMode, HttpMethod, NetworkRequest, RequestMode};
+use std::time::Duration;
+use url::Url;
+
+fn app_config() -> CorsConfig {
+    CorsConfig::default()
+        .allow_origin("https://app.example.com")
+        .allow_methods([HttpMethod::Get, HttpMethod::Post])
+        .allow_headers(["content-type"])
+        .allow_credentials(true)
+        .max_age(Duration::from_secs(600))
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_builder_sets_allowlists() {
+    // Given/When: A config built from the builder methods
+    let config = app_config();
+
+    // Then: Every setting is recorded
+    assert_eq!(
+        config.allowed_origins,
+        Some(vec!["https://app.example.com".to_string()])
+    );
+    assert_eq!(
+        config.allowed_methods,
+        vec![HttpMethod::Get, HttpMethod::Post]
+    );
+    assert_eq!(config.allowed_headers, vec!["content-type".to_string()]);
+    assert!(config.allow_credentials);
+    assert_eq!(config.max_age, Some(Duration::from_secs(600)));
+}
+
+#[test]
+fn test_wildcard_origin_with_credentials_rejected_at_build() {
+    // Given/When: A wildcard origin combined with credentials
+    let result = CorsConfig::default()
+        .allow_origin("*")
+        .allow_credentials(true)
+        .build();
+
+    // Then: Building fails
+    assert!(result.unwrap_err().contains("wildcard"));
+}
+
+#[test]
+fn test_credentials_without_origin_allowlist_rejected_at_build() {
+    let result = CorsConfig::default().allow_credentials(true).build();
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_wildcard_origin_without_credentials_builds() {
+    let config = CorsConfig::default().allow_origin("*").build().unwrap();
+
+    assert!(config.is_origin_allowed("https://anything.example"));
+}
+
+#[test]
+fn test_preflight_response_headers_from_config() {
+    // Given: A validator for the app allowlist
+    let validator = CorsValidator::new(app_config());
+
+    // When: An allowed preflight arrives
+    let preflight = create_preflight("POST", Some("Content-Type"));
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    // Then: The Access-Control-Allow-* headers reflect the config
+    assert!(result.allowed);
+    let headers = result.headers_to_add;
+    assert_eq!(
+        headers.get("Access-Control-Allow-Origin").unwrap(),
+        "https://app.example.com"
+    );
+    assert_eq!(
+        headers.get("Access-Control-Allow-Methods").unwrap(),
+        "GET, POST"
+    );
+    assert_eq!(
+        headers.get("Access-Control-Allow-Headers").unwrap(),
+        "content-type"
+    );
+    assert_eq!(
+        headers.get("Access-Control-Allow-Credentials").unwrap(),
+        "true"
+    );
+    assert_eq!(headers.get("Access-Control-Max-Age").unwrap(), "600");
+}
+
+#[test]
+fn test_preflight_from_unlisted_origin_blocked() {
+    let validator = CorsValidator::new(app_config());
+
+    let result =
+        validator.validate_preflight(&create_preflight("GET", None), "https://evil.example.com");
+
+    assert!(!result.allowed);
+    assert!(result.headers_to_add.is_empty());
+}
+
+#[test]
+fn test_preflight_for_unlisted_method_blocked() {
+    let validator = CorsValidator::new(app_config());
+
+    let result =
+        validator.validate_preflight(&create_preflight("DELETE", None), "https://app.example.com");
+
+    assert!(!result.allowed);
+    assert!(result.reason.unwrap().contains("DELETE"));
+}
+
+#[test]
+fn test_preflight_for_unlisted_header_blocked() {
+    let validator = CorsValidator::new(app_config());
+
+    let preflight = create_preflight("POST", Some("content-type, x-secret"));
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    assert!(!result.allowed);
+    assert!(result.reason.unwrap().contains("x-secret"));
+}
+
+#[test]
+fn test_preflight_without_request_method_blocked() {
+    let validator = CorsValidator::new(app_config());
+
+    let mut preflight = create_preflight("POST", None);
+    preflight.headers.clear();
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    assert!(!result.allowed);
+}
+
+#[test]
+fn test_max_age_header_omitted_when_unset() {
+    let config = CorsConfig {
+        max_age: None,
+        ..CorsConfig::default()
+    };
+    let validator = CorsValidator::new(config);
+
+    let result =
+        validator.validate_preflight(&create_preflight("GET", None), "https://app.example.com");
+
+    assert!(result.allowed);
+    assert!(!result.headers_to_add.contains_key("Access-Control-Max-Age"));
+}
+
+// Helper function
+fn create_preflight(method: &str, request_headers: Option<&str>) -> NetworkRequest {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "Access-Control-Request-Method",
+        HeaderValue::from_str(method).unwrap(),
+    );
+    if let Some(names) = request_headers {
+        headers.insert(
+            "Access-Control-Request-Headers",
+            HeaderValue::from_str(names).unwrap(),
+        );
+    }
+
+    NetworkRequest {
+        url: Url::parse("https://api.example.com/data").unwrap(),
+        method: HttpMethod::Options,
+        headers,
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::Include,
+        cache: network_types::CacheMode::Default,
+        redirect: network_types::RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: network_types::ReferrerPolicy::NoReferrerWhenDowngrade,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: network_types::RequestPriority::Auto,
+        window: None,
+    }
+}
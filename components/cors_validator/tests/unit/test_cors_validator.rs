@@ -10,6 +10,7 @@ fn test_same_origin_request_allowed() {
         enforce_same_origin: true,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -31,6 +32,7 @@ fn test_cross_origin_request_blocked_with_same_origin_enforcement() {
         enforce_same_origin: true,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -53,6 +55,7 @@ fn test_cross_origin_request_allowed_without_enforcement() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -73,6 +76,7 @@ fn test_preflight_needed_for_cors_mode() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -92,6 +96,7 @@ fn test_preflight_not_needed_for_same_origin_mode() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -111,6 +116,7 @@ fn test_preflight_not_needed_for_simple_get_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -130,6 +136,7 @@ fn test_credential_mode_validation() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://example.com".to_string()]),
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -152,6 +159,7 @@ fn test_validate_response_adds_cors_headers() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -174,6 +182,7 @@ fn test_build_preflight_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
     let request = create_test_request("https://example.com/api", HttpMethod::Post);
@@ -193,6 +202,7 @@ fn test_wildcard_origin_handling() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -213,6 +223,7 @@ fn test_wildcard_origin_blocked_with_credentials() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://example.com".to_string()]),
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -236,6 +247,7 @@ fn test_reject_wildcard_with_credentials_none() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
 
     // When/Then: Creating validator should panic
@@ -250,6 +262,7 @@ fn test_reject_wildcard_with_credentials_explicit() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["*".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When/Then: Creating validator should panic
@@ -263,6 +276,7 @@ fn test_allow_wildcard_without_credentials() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: Some(vec!["*".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When: Creating validator
@@ -279,6 +293,7 @@ fn test_allow_specific_origins_with_credentials() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://example.com".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When: Creating validator
@@ -295,6 +310,7 @@ fn test_try_new_returns_error_for_wildcard_with_credentials() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["*".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When: Using try_new
@@ -315,6 +331,7 @@ fn test_try_new_succeeds_for_valid_config() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://example.com".to_string(), "https://api.example.com".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When: Using try_new
@@ -331,6 +348,7 @@ fn test_config_validate_method() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
 
     // When: Calling validate
@@ -347,6 +365,7 @@ fn test_config_validate_method() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://example.com".to_string()]),
+        ..CorsConfig::default()
     };
 
     // When: Calling validate
@@ -399,5 +418,7 @@ fn create_test_response(status: u16) -> NetworkResponse {
         redirected: false,
         type_: network_types::ResponseType::Basic,
         timing: network_types::ResourceTiming::default(),
+        trailers: None,
+        source: network_types::ResponseSource::Network,
     }
 }
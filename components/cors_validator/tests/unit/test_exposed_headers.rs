@@ -0,0 +1,147 @@
+use cors_validator::{CorsConfig, CorsValidator};
+use http::{HeaderMap, HeaderValue};
+use network_types::{NetworkResponse, RequestMode, ResponseSource, ResponseType};
+use url::Url;
+
+const ORIGIN: &str = "https://app.example";
+
+#[test]
+fn test_non_exposed_header_is_stripped() {
+    // Given: A cross-origin response exposing only X-Exposed
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[
+        ("content-type", "application/json"),
+        ("x-exposed", "1"),
+        ("x-secret", "2"),
+        ("access-control-expose-headers", "X-Exposed"),
+    ]);
+
+    // When: Filtering headers for a CORS request
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::Cors);
+
+    // Then: Only the exposed and safelisted headers remain
+    assert_eq!(headers.get("x-exposed").unwrap(), "1");
+    assert_eq!(headers.get("content-type").unwrap(), "application/json");
+    assert!(headers.get("x-secret").is_none());
+    assert!(headers.get("access-control-expose-headers").is_none());
+}
+
+#[test]
+fn test_safelisted_headers_survive_without_expose_list() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[
+        ("cache-control", "no-cache"),
+        ("content-language", "en"),
+        ("content-length", "2"),
+        ("content-type", "text/plain"),
+        ("expires", "0"),
+        ("last-modified", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ("pragma", "no-cache"),
+        ("server", "test"),
+    ]);
+
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::Cors);
+
+    assert_eq!(headers.len(), 7);
+    assert!(headers.get("server").is_none());
+}
+
+#[test]
+fn test_expose_list_is_case_insensitive_and_keeps_repeated_values() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let mut response = create_response(&[("access-control-expose-headers", " x-one , X-TWO")]);
+    response
+        .headers
+        .append("x-one", HeaderValue::from_static("a"));
+    response
+        .headers
+        .append("x-one", HeaderValue::from_static("b"));
+    response
+        .headers
+        .append("x-two", HeaderValue::from_static("c"));
+
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::Cors);
+
+    let values: Vec<_> = headers.get_all("x-one").iter().collect();
+    assert_eq!(values, ["a", "b"]);
+    assert_eq!(headers.get("x-two").unwrap(), "c");
+}
+
+#[test]
+fn test_wildcard_exposes_all_but_set_cookie() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[
+        ("access-control-expose-headers", "*"),
+        ("x-secret", "1"),
+        ("set-cookie", "id=1"),
+    ]);
+
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::Cors);
+
+    assert_eq!(headers.get("x-secret").unwrap(), "1");
+    assert!(headers.get("set-cookie").is_none());
+}
+
+#[test]
+fn test_wildcard_is_literal_for_credentialed_response() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[
+        ("access-control-expose-headers", "*"),
+        ("access-control-allow-credentials", "true"),
+        ("x-secret", "1"),
+    ]);
+
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::Cors);
+
+    assert!(headers.get("x-secret").is_none());
+}
+
+#[test]
+fn test_same_origin_response_keeps_all_headers() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[("x-secret", "1"), ("set-cookie", "id=1")]);
+
+    let headers =
+        validator.exposed_headers(&response, "https://api.example.com", RequestMode::Cors);
+
+    assert_eq!(headers, response.headers);
+    assert!(!validator.is_cross_origin(&response, "https://api.example.com:443"));
+}
+
+#[test]
+fn test_no_cors_cross_origin_response_exposes_nothing() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[("content-type", "text/plain")]);
+
+    let headers = validator.exposed_headers(&response, ORIGIN, RequestMode::NoCors);
+
+    assert!(headers.is_empty());
+}
+
+#[test]
+fn test_null_origin_is_cross_origin() {
+    let validator = CorsValidator::new(CorsConfig::default());
+    let response = create_response(&[]);
+
+    assert!(validator.is_cross_origin(&response, "null"));
+}
+
+// Helper function
+fn create_response(headers: &[(&'static str, &'static str)]) -> NetworkResponse {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        header_map.append(*name, HeaderValue::from_static(value));
+    }
+    NetworkResponse {
+        url: Url::parse("https://api.example.com/data").unwrap(),
+        status: 200,
+        status_text: "OK".to_string(),
+        headers: header_map,
+        body: network_types::ResponseBody::Empty,
+        redirected: false,
+        type_: ResponseType::Basic,
+        timing: network_types::ResourceTiming::default(),
+        trailers: None,
+        source: ResponseSource::Network,
+    }
+}
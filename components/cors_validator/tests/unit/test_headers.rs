@@ -1,5 +1,5 @@
 use cors_validator::{CorsValidator, CorsConfig};
-use network_types::{NetworkResponse, ResponseType};
+use network_types::{NetworkResponse, ResponseSource, ResponseType};
 use url::Url;
 use http::HeaderMap;
 
@@ -10,6 +10,7 @@ fn test_access_control_allow_origin_header_added() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -33,6 +34,7 @@ fn test_access_control_allow_credentials_header() {
         enforce_same_origin: false,
         allow_credentials: true,
         allowed_origins: Some(vec!["https://trusted.com".to_string()]),
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -56,6 +58,7 @@ fn test_no_credentials_header_when_disabled() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -78,6 +81,7 @@ fn test_wildcard_origin_in_response() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -101,6 +105,7 @@ fn test_access_control_allow_methods_in_preflight() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -125,6 +130,8 @@ fn create_response(status: u16) -> NetworkResponse {
         redirected: false,
         type_: ResponseType::Cors,
         timing: network_types::ResourceTiming::default(),
+        trailers: None,
+        source: ResponseSource::Network,
     }
 }
 
@@ -1,7 +1,7 @@
 use cors_validator::{CorsValidator, CorsConfig};
 use network_types::{NetworkRequest, HttpMethod, RequestMode, CredentialsMode};
 use url::Url;
-use http::HeaderMap;
+use http::{HeaderMap, HeaderValue};
 
 #[test]
 fn test_preflight_for_put_request() {
@@ -10,6 +10,7 @@ fn test_preflight_for_put_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -29,6 +30,7 @@ fn test_preflight_for_delete_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -48,6 +50,7 @@ fn test_preflight_for_patch_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -67,6 +70,7 @@ fn test_preflight_request_has_correct_headers() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
     let request = create_cors_request("https://api.example.com/data", HttpMethod::Post);
@@ -87,6 +91,7 @@ fn test_no_preflight_for_head_request() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -106,6 +111,7 @@ fn test_no_preflight_for_no_cors_mode() {
         enforce_same_origin: false,
         allow_credentials: false,
         allowed_origins: None,
+        ..CorsConfig::default()
     };
     let validator = CorsValidator::new(config);
 
@@ -119,6 +125,161 @@ fn test_no_preflight_for_no_cors_mode() {
     assert!(!needs_preflight);
 }
 
+#[test]
+fn test_simple_get_does_not_require_preflight() {
+    // Given: CORS validator
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    // When: GET request with only safelisted headers
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Get);
+    request
+        .headers
+        .insert("accept", HeaderValue::from_static("application/json"));
+    request
+        .headers
+        .insert("accept-language", HeaderValue::from_static("en-US"));
+
+    // Then: It is a simple request
+    assert!(!validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_put_requires_preflight() {
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    let request = create_cors_request("https://api.example.com/data", HttpMethod::Put);
+
+    assert!(validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_post_with_form_content_type_does_not_require_preflight() {
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Post);
+    request.headers.insert(
+        "content-type",
+        HeaderValue::from_static("application/x-www-form-urlencoded"),
+    );
+
+    assert!(!validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_post_with_json_content_type_requires_preflight() {
+    // Given: CORS validator
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    // When: POST request with a JSON body
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Post);
+    request
+        .headers
+        .insert("content-type", HeaderValue::from_static("application/json"));
+
+    // Then: Content-Type is not safelisted, so it must be preflighted
+    assert!(validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_custom_header_requires_preflight() {
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Get);
+    request
+        .headers
+        .insert("x-requested-with", HeaderValue::from_static("fetch"));
+
+    assert!(validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_no_cors_mode_never_requires_preflight() {
+    let validator = CorsValidator::new(CorsConfig::default());
+
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Put);
+    request.mode = RequestMode::NoCors;
+
+    assert!(!validator.requires_preflight(&request));
+}
+
+#[test]
+fn test_preflight_with_allowed_method_and_headers_is_allowed() {
+    // Given: Server-side validator allowing PUT with X-Api-Key
+    let config = CorsConfig::default()
+        .allow_methods([HttpMethod::Get, HttpMethod::Put])
+        .allow_headers(["Content-Type", "X-Api-Key"]);
+    let validator = CorsValidator::new(config);
+
+    // When: Preflight for a PUT request carrying x-api-key
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Put);
+    request
+        .headers
+        .insert("x-api-key", HeaderValue::from_static("secret"));
+    let preflight = validator.build_preflight_request(&request);
+
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    // Then: Preflight should be allowed with Access-Control-Allow-* headers
+    assert!(result.allowed, "{:?}", result.reason);
+    assert_eq!(
+        result
+            .headers_to_add
+            .get("Access-Control-Allow-Methods")
+            .unwrap(),
+        "GET, PUT"
+    );
+    assert_eq!(
+        result
+            .headers_to_add
+            .get("Access-Control-Allow-Headers")
+            .unwrap(),
+        "Content-Type, X-Api-Key"
+    );
+}
+
+#[test]
+fn test_preflight_with_disallowed_custom_header_is_blocked() {
+    // Given: Server-side validator allowing only Content-Type
+    let config = CorsConfig::default()
+        .allow_methods([HttpMethod::Put])
+        .allow_headers(["Content-Type"]);
+    let validator = CorsValidator::new(config);
+
+    // When: Preflight for a PUT request carrying X-Tracking-Id
+    let mut request = create_cors_request("https://api.example.com/data", HttpMethod::Put);
+    request
+        .headers
+        .insert("content-type", HeaderValue::from_static("application/json"));
+    request
+        .headers
+        .insert("X-Tracking-Id", HeaderValue::from_static("42"));
+    let preflight = validator.build_preflight_request(&request);
+
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    // Then: Preflight should be blocked, naming the header
+    assert!(!result.allowed);
+    assert!(result.headers_to_add.is_empty());
+    assert!(result.reason.unwrap().contains("x-tracking-id"));
+}
+
+#[test]
+fn test_preflight_with_disallowed_method_is_blocked() {
+    // Given: Server-side validator allowing only GET and POST
+    let config = CorsConfig::default().allow_methods([HttpMethod::Get, HttpMethod::Post]);
+    let validator = CorsValidator::new(config);
+
+    // When: Preflight for a DELETE request
+    let request = create_cors_request("https://api.example.com/data", HttpMethod::Delete);
+    let preflight = validator.build_preflight_request(&request);
+
+    let result = validator.validate_preflight(&preflight, "https://app.example.com");
+
+    // Then: Preflight should be blocked, naming the method
+    assert!(!result.allowed);
+    assert!(result.reason.unwrap().contains("DELETE"));
+}
+
 // Helper function
 fn create_cors_request(url: &str, method: HttpMethod) -> NetworkRequest {
     NetworkRequest {
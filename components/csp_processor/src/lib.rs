@@ -9,6 +9,10 @@
 use std::collections::HashMap;
 use url::Url;
 
+mod sandbox;
+
+pub use sandbox::SandboxFlags;
+
 /// CSP directive types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CspDirective {
@@ -30,8 +34,22 @@ pub enum CspDirective {
     MediaSrc,
     /// frame-src directive
     FrameSrc,
+    /// frame-ancestors directive
+    FrameAncestors,
     /// report-uri directive
     ReportUri,
+    /// worker-src directive
+    WorkerSrc,
+    /// manifest-src directive
+    ManifestSrc,
+    /// base-uri directive
+    BaseUri,
+    /// form-action directive
+    FormAction,
+    /// upgrade-insecure-requests directive (takes no sources)
+    UpgradeInsecureRequests,
+    /// sandbox directive (takes sandboxing tokens, not sources)
+    Sandbox,
 }
 
 /// A Content Security Policy
@@ -59,7 +77,8 @@ pub struct CspViolation {
 /// CSP Processor - main entry point for CSP operations
 #[derive(Debug)]
 pub struct CspProcessor {
-    policy: CspPolicy,
+    /// Enforced policies; a check passes only if every policy allows it
+    policies: Vec<CspPolicy>,
     /// The document's origin for 'self' checks (scheme + host + port)
     document_origin: Option<Url>,
 }
@@ -83,7 +102,33 @@ impl CspProcessor {
     pub fn new(header: &str) -> Result<Self, CspError> {
         let policy = Self::parse_header(header)?;
         Ok(Self {
-            policy,
+            policies: vec![policy],
+            document_origin: None,
+        })
+    }
+
+    /// Create a CSP processor enforcing several CSP headers at once
+    ///
+    /// A response may carry multiple `Content-Security-Policy` headers; the
+    /// effective policy is their intersection, so a source or inline content
+    /// is allowed only if every policy allows it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `headers` is empty or any header fails to parse.
+    pub fn from_headers(headers: &[&str]) -> Result<Self, CspError> {
+        if headers.is_empty() {
+            return Err(CspError {
+                message: "No CSP headers".to_string(),
+            });
+        }
+
+        let policies = headers
+            .iter()
+            .map(|header| Self::parse_header(header))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            policies,
             document_origin: None,
         })
     }
@@ -136,14 +181,28 @@ impl CspProcessor {
 
     /// Check if a source is allowed for a given directive
     pub fn check_source(&self, directive: CspDirective, source: &str) -> bool {
-        let directive_name = directive.as_str();
+        self.policies
+            .iter()
+            .all(|policy| self.policy_allows_source(policy, directive, source))
+    }
+
+    /// Check if a source is allowed for a given directive by a single policy
+    fn policy_allows_source(
+        &self,
+        policy: &CspPolicy,
+        directive: CspDirective,
+        source: &str,
+    ) -> bool {
+        // These directives have no source list to restrict
+        if matches!(
+            directive,
+            CspDirective::UpgradeInsecureRequests | CspDirective::Sandbox
+        ) {
+            return true;
+        }
 
-        // Get sources for this directive, or fall back to default-src
-        let sources = self
-            .policy
-            .directives
-            .get(directive_name)
-            .or_else(|| self.policy.directives.get("default-src"));
+        // Get sources for this directive, or the directive it falls back to
+        let sources = Self::effective_sources(policy, directive);
 
         if let Some(sources) = sources {
             // Check if any source matches
@@ -159,6 +218,76 @@ impl CspProcessor {
         }
     }
 
+    /// Check if a source is allowed by a directive given by name
+    ///
+    /// Known directive names are checked like [`check_source`](Self::check_source).
+    /// Other directives, such as ones newer than this processor, don't fall
+    /// back to default-src since their fallback isn't known; a policy without
+    /// the directive allows the source.
+    pub fn check_source_by_name(&self, directive_name: &str, source: &str) -> bool {
+        if let Some(directive) = CspDirective::from_name(directive_name) {
+            return self.check_source(directive, source);
+        }
+
+        let directive_name = directive_name.to_ascii_lowercase();
+        self.policies
+            .iter()
+            .all(|policy| match policy.directives.get(&directive_name) {
+                Some(sources) => sources
+                    .iter()
+                    .any(|allowed| self.source_matches(allowed, source)),
+                None => true,
+            })
+    }
+
+    /// Whether insecure requests should be upgraded to HTTPS
+    ///
+    /// True when any enforced policy has the `upgrade-insecure-requests`
+    /// directive; report-only policies don't upgrade requests.
+    pub fn upgrade_insecure_requests(&self) -> bool {
+        self.policies.iter().any(|policy| {
+            !policy.report_only
+                && policy
+                    .directives
+                    .contains_key(CspDirective::UpgradeInsecureRequests.as_str())
+        })
+    }
+
+    /// Sandboxing applied to the document, if any policy has a `sandbox` directive
+    ///
+    /// The result holds the capabilities every such policy allows; a
+    /// `sandbox` directive without tokens allows nothing. Report-only
+    /// policies are ignored, as `sandbox` can't be used in them.
+    pub fn sandbox_flags(&self) -> Option<SandboxFlags> {
+        self.policies
+            .iter()
+            .filter(|policy| !policy.report_only)
+            .filter_map(|policy| policy.directives.get(CspDirective::Sandbox.as_str()))
+            .map(|tokens| SandboxFlags::from_tokens(tokens.iter().map(String::as_str)))
+            .reduce(|allowed, flags| allowed & flags)
+    }
+
+    /// Sources governing `directive` in a policy, following the fallback chain
+    ///
+    /// worker-src falls back to script-src and then default-src. base-uri,
+    /// form-action, upgrade-insecure-requests and sandbox have no fallback;
+    /// every other directive falls back to default-src.
+    fn effective_sources(policy: &CspPolicy, directive: CspDirective) -> Option<&Vec<String>> {
+        let fallbacks: &[CspDirective] = match directive {
+            CspDirective::DefaultSrc => &[],
+            CspDirective::WorkerSrc => &[CspDirective::ScriptSrc, CspDirective::DefaultSrc],
+            CspDirective::BaseUri
+            | CspDirective::FormAction
+            | CspDirective::UpgradeInsecureRequests
+            | CspDirective::Sandbox => &[],
+            _ => &[CspDirective::DefaultSrc],
+        };
+
+        std::iter::once(directive)
+            .chain(fallbacks.iter().copied())
+            .find_map(|directive| policy.directives.get(directive.as_str()))
+    }
+
     /// Check if a source matches the 'self' keyword
     ///
     /// The 'self' keyword matches only sources from the same origin as the document.
@@ -242,14 +371,19 @@ impl CspProcessor {
 
     /// Check if inline content is allowed for a directive
     pub fn is_inline_allowed(&self, directive: CspDirective, nonce: Option<&str>) -> bool {
-        let directive_name = directive.as_str();
+        self.policies
+            .iter()
+            .all(|policy| Self::policy_allows_inline(policy, directive, nonce))
+    }
 
-        // Get sources for this directive, or fall back to default-src
-        let sources = self
-            .policy
-            .directives
-            .get(directive_name)
-            .or_else(|| self.policy.directives.get("default-src"));
+    /// Check if inline content is allowed for a directive by a single policy
+    fn policy_allows_inline(
+        policy: &CspPolicy,
+        directive: CspDirective,
+        nonce: Option<&str>,
+    ) -> bool {
+        // Get sources for this directive, or the directive it falls back to
+        let sources = Self::effective_sources(policy, directive);
 
         if let Some(sources) = sources {
             // Check for unsafe-inline
@@ -272,6 +406,137 @@ impl CspProcessor {
         }
     }
 
+    /// Check a connection target against connect-src
+    ///
+    /// connect-src (falling back to default-src) governs `fetch`, XHR,
+    /// WebSocket and EventSource targets, so callers should run this before
+    /// opening any such connection. `ws:`/`wss:` targets are matched using
+    /// the CSP scheme rules: 'self' allows the document's host over `ws:`/`wss:`
+    /// (only `wss:` from an `https:` document), and `http:`/`https:` sources
+    /// do not allow WebSocket schemes, while a `ws:` source also allows `wss:`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the violation for the first policy that blocks `target`.
+    pub fn check_connect(&self, target: &Url) -> Result<(), CspViolation> {
+        for policy in &self.policies {
+            let (directive_name, sources) = match policy
+                .directives
+                .get_key_value(CspDirective::ConnectSrc.as_str())
+                .or_else(|| policy.directives.get_key_value("default-src"))
+            {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            if !sources
+                .iter()
+                .any(|allowed| self.connect_source_matches(allowed, target))
+            {
+                return Err(CspViolation {
+                    directive: CspDirective::ConnectSrc.as_str().to_string(),
+                    blocked_uri: target.to_string(),
+                    violated_directive: format!("{} {}", directive_name, sources.join(" ")),
+                    source_file: None,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a connect-src source expression matches a target URL
+    fn connect_source_matches(&self, allowed: &str, target: &Url) -> bool {
+        if allowed == "'none'" {
+            return false;
+        }
+
+        if allowed == "'self'" {
+            return self.document_origin.as_ref().is_some_and(|origin| {
+                self_scheme_matches(origin.scheme(), target.scheme())
+                    && origin.host_str() == target.host_str()
+                    && origin.port_or_known_default() == target.port_or_known_default()
+            });
+        }
+
+        // Scheme source, e.g. "wss:"
+        if let Some(scheme) = allowed.strip_suffix(':') {
+            if !scheme.contains('/') {
+                return scheme_part_matches(scheme, target.scheme());
+            }
+        }
+
+        // Host source with an explicit scheme, e.g. "wss://chat.example.com"
+        if let Some((scheme, _)) = allowed.split_once("://") {
+            if !scheme_part_matches(scheme, target.scheme()) {
+                return false;
+            }
+        }
+
+        self.source_matches(allowed, target.as_str())
+    }
+
+    /// Check if the document may be embedded by a page from `embedder_origin`
+    ///
+    /// Evaluates the frame-ancestors directive, which (unlike fetch directives)
+    /// does not fall back to default-src. Supports 'none', 'self', `*`, scheme
+    /// sources (`https:`) and host sources. Without the directive, embedding is
+    /// allowed.
+    pub fn can_embed_in(&self, embedder_origin: &Url) -> bool {
+        self.policies
+            .iter()
+            .all(|policy| self.policy_allows_embedding(policy, embedder_origin))
+    }
+
+    /// Check frame-ancestors of a single policy
+    fn policy_allows_embedding(&self, policy: &CspPolicy, embedder_origin: &Url) -> bool {
+        let sources = match policy.directives.get(CspDirective::FrameAncestors.as_str()) {
+            Some(sources) => sources,
+            None => return true,
+        };
+
+        sources.iter().any(|allowed| {
+            if allowed == "'none'" {
+                return false;
+            }
+
+            // Scheme source, e.g. "https:"
+            if let Some(scheme) = allowed.strip_suffix(':') {
+                if !scheme.contains('/') {
+                    return embedder_origin.scheme().eq_ignore_ascii_case(scheme);
+                }
+            }
+
+            self.source_matches(allowed, embedder_origin.as_str())
+        })
+    }
+
+    /// Check embedding against both frame-ancestors and a legacy
+    /// `X-Frame-Options` header value
+    ///
+    /// When any policy has a frame-ancestors directive, `X-Frame-Options` is
+    /// ignored, as required by the CSP specification. Otherwise the header is
+    /// enforced with [`x_frame_options_allows`], using the document origin.
+    pub fn can_embed_in_with_x_frame_options(
+        &self,
+        embedder_origin: &Url,
+        x_frame_options: Option<&str>,
+    ) -> bool {
+        if self.policies.iter().any(|policy| {
+            policy
+                .directives
+                .contains_key(CspDirective::FrameAncestors.as_str())
+        }) {
+            return self.can_embed_in(embedder_origin);
+        }
+
+        match x_frame_options {
+            Some(value) => {
+                x_frame_options_allows(value, self.document_origin.as_ref(), embedder_origin)
+            }
+            None => true,
+        }
+    }
+
     /// Report a CSP violation
     pub fn report_violation(&self, _violation: CspViolation) {
         // In a real implementation, this would send the violation report
@@ -279,6 +544,55 @@ impl CspProcessor {
     }
 }
 
+/// Check if an `X-Frame-Options` header value allows embedding
+///
+/// `DENY` blocks all embedding and `SAMEORIGIN` allows only embedders with the
+/// same origin as the document (blocking if the document origin is unknown).
+/// Any other value, including the obsolete `ALLOW-FROM`, is ignored.
+pub fn x_frame_options_allows(
+    value: &str,
+    document_origin: Option<&Url>,
+    embedder_origin: &Url,
+) -> bool {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "DENY" => false,
+        "SAMEORIGIN" => {
+            document_origin.is_some_and(|origin| origin.origin() == embedder_origin.origin())
+        }
+        _ => true,
+    }
+}
+
+/// Whether 'self' from a document with `document` scheme allows `target`
+///
+/// Besides the same scheme, an `http:` document allows `https:`, `ws:` and
+/// `wss:`, and an `https:` document allows `wss:`.
+fn self_scheme_matches(document: &str, target: &str) -> bool {
+    let document = document.to_ascii_lowercase();
+    let target = target.to_ascii_lowercase();
+    document == target
+        || match document.as_str() {
+            "http" => matches!(target.as_str(), "https" | "ws" | "wss"),
+            "https" => target == "wss",
+            _ => false,
+        }
+}
+
+/// CSP "scheme-part match": whether a source scheme allows a target scheme
+///
+/// Secure upgrades are allowed (`http:` to `https:`, `ws:` to `wss:`), and a
+/// `ws:`/`wss:` source also allows the corresponding HTTP schemes.
+fn scheme_part_matches(source: &str, target: &str) -> bool {
+    let source = source.to_ascii_lowercase();
+    let target = target.to_ascii_lowercase();
+    match source.as_str() {
+        "http" => matches!(target.as_str(), "http" | "https"),
+        "ws" => matches!(target.as_str(), "ws" | "wss" | "http" | "https"),
+        "wss" => matches!(target.as_str(), "wss" | "https"),
+        _ => source == target,
+    }
+}
+
 impl CspDirective {
     /// Convert directive to its string representation
     pub fn as_str(&self) -> &'static str {
@@ -292,7 +606,42 @@ impl CspDirective {
             CspDirective::ObjectSrc => "object-src",
             CspDirective::MediaSrc => "media-src",
             CspDirective::FrameSrc => "frame-src",
+            CspDirective::FrameAncestors => "frame-ancestors",
             CspDirective::ReportUri => "report-uri",
+            CspDirective::WorkerSrc => "worker-src",
+            CspDirective::ManifestSrc => "manifest-src",
+            CspDirective::BaseUri => "base-uri",
+            CspDirective::FormAction => "form-action",
+            CspDirective::UpgradeInsecureRequests => "upgrade-insecure-requests",
+            CspDirective::Sandbox => "sandbox",
         }
     }
+
+    /// Look up a directive by name, ignoring ASCII case
+    ///
+    /// Returns None for directives this processor has no variant for.
+    pub fn from_name(name: &str) -> Option<Self> {
+        const ALL: [CspDirective; 17] = [
+            CspDirective::DefaultSrc,
+            CspDirective::ScriptSrc,
+            CspDirective::StyleSrc,
+            CspDirective::ImgSrc,
+            CspDirective::ConnectSrc,
+            CspDirective::FontSrc,
+            CspDirective::ObjectSrc,
+            CspDirective::MediaSrc,
+            CspDirective::FrameSrc,
+            CspDirective::FrameAncestors,
+            CspDirective::ReportUri,
+            CspDirective::WorkerSrc,
+            CspDirective::ManifestSrc,
+            CspDirective::BaseUri,
+            CspDirective::FormAction,
+            CspDirective::UpgradeInsecureRequests,
+            CspDirective::Sandbox,
+        ];
+
+        ALL.into_iter()
+            .find(|directive| directive.as_str().eq_ignore_ascii_case(name))
+    }
 }
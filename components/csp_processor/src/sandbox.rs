@@ -0,0 +1,178 @@
+//! The `sandbox` directive
+
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+/// Capabilities allowed by a `sandbox` directive
+///
+/// Each flag corresponds to one `allow-*` token. A sandbox with no flags set
+/// (an empty `sandbox` directive) applies every restriction.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SandboxFlags(u16);
+
+/// Flags and the tokens that set them
+const TOKENS: [(SandboxFlags, &str); 13] = [
+    (SandboxFlags::ALLOW_DOWNLOADS, "allow-downloads"),
+    (SandboxFlags::ALLOW_FORMS, "allow-forms"),
+    (SandboxFlags::ALLOW_MODALS, "allow-modals"),
+    (
+        SandboxFlags::ALLOW_ORIENTATION_LOCK,
+        "allow-orientation-lock",
+    ),
+    (SandboxFlags::ALLOW_POINTER_LOCK, "allow-pointer-lock"),
+    (SandboxFlags::ALLOW_POPUPS, "allow-popups"),
+    (
+        SandboxFlags::ALLOW_POPUPS_TO_ESCAPE_SANDBOX,
+        "allow-popups-to-escape-sandbox",
+    ),
+    (SandboxFlags::ALLOW_PRESENTATION, "allow-presentation"),
+    (SandboxFlags::ALLOW_SAME_ORIGIN, "allow-same-origin"),
+    (SandboxFlags::ALLOW_SCRIPTS, "allow-scripts"),
+    (SandboxFlags::ALLOW_TOP_NAVIGATION, "allow-top-navigation"),
+    (
+        SandboxFlags::ALLOW_TOP_NAVIGATION_BY_USER_ACTIVATION,
+        "allow-top-navigation-by-user-activation",
+    ),
+    (
+        SandboxFlags::ALLOW_TOP_NAVIGATION_TO_CUSTOM_PROTOCOLS,
+        "allow-top-navigation-to-custom-protocols",
+    ),
+];
+
+impl SandboxFlags {
+    /// No capabilities allowed: the most restrictive sandbox
+    pub const NONE: Self = Self(0);
+    /// `allow-downloads`
+    pub const ALLOW_DOWNLOADS: Self = Self(1 << 0);
+    /// `allow-forms`
+    pub const ALLOW_FORMS: Self = Self(1 << 1);
+    /// `allow-modals`
+    pub const ALLOW_MODALS: Self = Self(1 << 2);
+    /// `allow-orientation-lock`
+    pub const ALLOW_ORIENTATION_LOCK: Self = Self(1 << 3);
+    /// `allow-pointer-lock`
+    pub const ALLOW_POINTER_LOCK: Self = Self(1 << 4);
+    /// `allow-popups`
+    pub const ALLOW_POPUPS: Self = Self(1 << 5);
+    /// `allow-popups-to-escape-sandbox`
+    pub const ALLOW_POPUPS_TO_ESCAPE_SANDBOX: Self = Self(1 << 6);
+    /// `allow-presentation`
+    pub const ALLOW_PRESENTATION: Self = Self(1 << 7);
+    /// `allow-same-origin`
+    pub const ALLOW_SAME_ORIGIN: Self = Self(1 << 8);
+    /// `allow-scripts`
+    pub const ALLOW_SCRIPTS: Self = Self(1 << 9);
+    /// `allow-top-navigation`
+    pub const ALLOW_TOP_NAVIGATION: Self = Self(1 << 10);
+    /// `allow-top-navigation-by-user-activation`
+    pub const ALLOW_TOP_NAVIGATION_BY_USER_ACTIVATION: Self = Self(1 << 11);
+    /// `allow-top-navigation-to-custom-protocols`
+    pub const ALLOW_TOP_NAVIGATION_TO_CUSTOM_PROTOCOLS: Self = Self(1 << 12);
+
+    /// Parse the tokens of a `sandbox` directive
+    ///
+    /// Tokens are matched ignoring ASCII case; unknown tokens are ignored.
+    pub fn from_tokens<'a>(tokens: impl IntoIterator<Item = &'a str>) -> Self {
+        tokens
+            .into_iter()
+            .filter_map(Self::from_token)
+            .fold(Self::NONE, |flags, flag| flags | flag)
+    }
+
+    /// Flag set by a single `allow-*` token
+    pub fn from_token(token: &str) -> Option<Self> {
+        TOKENS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(token))
+            .map(|(flag, _)| *flag)
+    }
+
+    /// Raw bit representation
+    pub fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Whether every flag in `other` is set
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether no capability is allowed
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether scripts may run
+    pub fn allows_scripts(self) -> bool {
+        self.contains(Self::ALLOW_SCRIPTS)
+    }
+
+    /// Whether the content keeps its origin instead of an opaque one
+    pub fn allows_same_origin(self) -> bool {
+        self.contains(Self::ALLOW_SAME_ORIGIN)
+    }
+
+    /// Whether forms may be submitted
+    pub fn allows_forms(self) -> bool {
+        self.contains(Self::ALLOW_FORMS)
+    }
+
+    /// Whether popups may be opened
+    pub fn allows_popups(self) -> bool {
+        self.contains(Self::ALLOW_POPUPS)
+    }
+
+    /// Whether modal dialogs such as `alert()` may be shown
+    pub fn allows_modals(self) -> bool {
+        self.contains(Self::ALLOW_MODALS)
+    }
+
+    /// Whether downloads may be started
+    pub fn allows_downloads(self) -> bool {
+        self.contains(Self::ALLOW_DOWNLOADS)
+    }
+
+    /// Whether the top-level browsing context may be navigated
+    pub fn allows_top_navigation(self) -> bool {
+        self.contains(Self::ALLOW_TOP_NAVIGATION)
+    }
+}
+
+impl BitOr for SandboxFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for SandboxFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for SandboxFlags {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for SandboxFlags {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl fmt::Debug for SandboxFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tokens: Vec<&str> = TOKENS
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| *name)
+            .collect();
+        f.debug_tuple("SandboxFlags").field(&tokens).finish()
+    }
+}
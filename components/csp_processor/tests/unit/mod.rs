@@ -1,2 +1,7 @@
+mod test_connect_src;
+mod test_directives;
+mod test_frame_ancestors;
+mod test_multiple_policies;
 mod test_parser;
+mod test_sandbox;
 mod test_validator;
@@ -0,0 +1,93 @@
+use csp_processor::CspProcessor;
+use url::Url;
+
+fn url(s: &str) -> Url {
+    Url::parse(s).unwrap()
+}
+
+fn processor(header: &str) -> CspProcessor {
+    CspProcessor::new(header)
+        .unwrap()
+        .with_document_origin(url("https://example.com"))
+}
+
+#[test]
+fn test_check_connect_allows_listed_websocket_host() {
+    // Given: connect-src allowing a WebSocket endpoint
+    let processor = processor("connect-src 'self' wss://chat.example.com");
+
+    // Then: Connecting to it is allowed
+    assert!(processor
+        .check_connect(&url("wss://chat.example.com/socket"))
+        .is_ok());
+}
+
+#[test]
+fn test_check_connect_blocks_unlisted_websocket_host() {
+    // Given: connect-src allowing only a single WebSocket endpoint
+    let processor = processor("connect-src wss://chat.example.com");
+
+    // When: Connecting somewhere else
+    let violation = processor
+        .check_connect(&url("wss://evil.com/socket"))
+        .unwrap_err();
+
+    // Then: The violation names connect-src and the blocked URL
+    assert_eq!(violation.directive, "connect-src");
+    assert_eq!(violation.blocked_uri, "wss://evil.com/socket");
+    assert_eq!(
+        violation.violated_directive,
+        "connect-src wss://chat.example.com"
+    );
+}
+
+#[test]
+fn test_check_connect_self_allows_secure_websocket_to_document_host() {
+    let processor = processor("connect-src 'self'");
+
+    assert!(processor
+        .check_connect(&url("wss://example.com/live"))
+        .is_ok());
+    assert!(processor
+        .check_connect(&url("https://example.com/api"))
+        .is_ok());
+    // An https: document may not downgrade to ws:
+    assert!(processor
+        .check_connect(&url("ws://example.com/live"))
+        .is_err());
+}
+
+#[test]
+fn test_check_connect_https_source_does_not_allow_websocket() {
+    let processor = processor("connect-src https://chat.example.com");
+
+    assert!(processor
+        .check_connect(&url("wss://chat.example.com"))
+        .is_err());
+}
+
+#[test]
+fn test_check_connect_scheme_sources() {
+    let processor = processor("connect-src ws:");
+
+    assert!(processor.check_connect(&url("ws://any.com")).is_ok());
+    assert!(processor.check_connect(&url("wss://any.com")).is_ok());
+    assert!(processor.check_connect(&url("ftp://any.com")).is_err());
+}
+
+#[test]
+fn test_check_connect_falls_back_to_default_src() {
+    let processor = processor("default-src 'none'");
+
+    let violation = processor
+        .check_connect(&url("https://api.example.com"))
+        .unwrap_err();
+    assert_eq!(violation.violated_directive, "default-src 'none'");
+}
+
+#[test]
+fn test_check_connect_without_directive_allows_all() {
+    let processor = processor("script-src 'self'");
+
+    assert!(processor.check_connect(&url("wss://anywhere.com")).is_ok());
+}
@@ -0,0 +1,131 @@
+use csp_processor::{CspDirective, CspProcessor};
+
+#[test]
+fn test_worker_src_checked_directly() {
+    // Given: A policy with worker-src
+    let processor = CspProcessor::new("worker-src https://workers.example.com").unwrap();
+
+    // Then: Only the listed worker host is allowed
+    assert!(processor.check_source(CspDirective::WorkerSrc, "https://workers.example.com/w.js"));
+    assert!(!processor.check_source(CspDirective::WorkerSrc, "https://evil.com/w.js"));
+}
+
+#[test]
+fn test_worker_src_falls_back_to_script_src() {
+    // Given: A policy with script-src and default-src but no worker-src
+    let processor =
+        CspProcessor::new("default-src https://default.com; script-src https://scripts.com")
+            .unwrap();
+
+    // Then: script-src governs workers before default-src does
+    assert!(processor.check_source(CspDirective::WorkerSrc, "https://scripts.com/w.js"));
+    assert!(!processor.check_source(CspDirective::WorkerSrc, "https://default.com/w.js"));
+}
+
+#[test]
+fn test_worker_src_falls_back_to_default_src() {
+    // Given: A policy with only default-src
+    let processor = CspProcessor::new("default-src https://default.com").unwrap();
+
+    // Then: default-src governs workers
+    assert!(processor.check_source(CspDirective::WorkerSrc, "https://default.com/w.js"));
+    assert!(!processor.check_source(CspDirective::WorkerSrc, "https://evil.com/w.js"));
+}
+
+#[test]
+fn test_manifest_src_falls_back_to_default_src() {
+    // Given: A policy with manifest-src, and one with only default-src
+    let explicit = CspProcessor::new("default-src *; manifest-src https://app.com").unwrap();
+    let fallback = CspProcessor::new("default-src https://app.com").unwrap();
+
+    // Then: Both restrict manifests to the listed host
+    assert!(explicit.check_source(CspDirective::ManifestSrc, "https://app.com/app.webmanifest"));
+    assert!(!explicit.check_source(CspDirective::ManifestSrc, "https://evil.com/m.json"));
+    assert!(fallback.check_source(CspDirective::ManifestSrc, "https://app.com/app.webmanifest"));
+    assert!(!fallback.check_source(CspDirective::ManifestSrc, "https://evil.com/m.json"));
+}
+
+#[test]
+fn test_base_uri_and_form_action_are_enforced() {
+    // Given: A policy restricting base URIs and form targets
+    let processor =
+        CspProcessor::new("base-uri https://example.com; form-action https://forms.example.com")
+            .unwrap();
+
+    // Then: Each directive allows only its listed host
+    assert!(processor.check_source(CspDirective::BaseUri, "https://example.com/"));
+    assert!(!processor.check_source(CspDirective::BaseUri, "https://evil.com/"));
+    assert!(processor.check_source(CspDirective::FormAction, "https://forms.example.com/submit"));
+    assert!(!processor.check_source(CspDirective::FormAction, "https://evil.com/submit"));
+}
+
+#[test]
+fn test_base_uri_and_form_action_do_not_fall_back_to_default_src() {
+    // Given: A restrictive default-src only
+    let processor = CspProcessor::new("default-src 'none'").unwrap();
+
+    // Then: base-uri and form-action remain unrestricted
+    assert!(processor.check_source(CspDirective::BaseUri, "https://evil.com/"));
+    assert!(processor.check_source(CspDirective::FormAction, "https://evil.com/submit"));
+}
+
+#[test]
+fn test_upgrade_insecure_requests() {
+    // Given: Policies with and without upgrade-insecure-requests
+    let upgrading = CspProcessor::new("upgrade-insecure-requests; default-src https:").unwrap();
+    let plain = CspProcessor::new("default-src https:").unwrap();
+
+    // Then: Only the first asks for upgrades
+    assert!(upgrading.upgrade_insecure_requests());
+    assert!(!plain.upgrade_insecure_requests());
+
+    // And: The boolean directive doesn't restrict any source
+    assert!(upgrading.check_source(CspDirective::UpgradeInsecureRequests, "http://example.com/"));
+}
+
+#[test]
+fn test_upgrade_insecure_requests_from_any_policy() {
+    // Given: Two policies, one of which upgrades requests
+    let processor =
+        CspProcessor::from_headers(&["default-src *", "upgrade-insecure-requests"]).unwrap();
+
+    // Then: Requests are upgraded
+    assert!(processor.upgrade_insecure_requests());
+}
+
+#[test]
+fn test_check_source_by_name_for_known_directive() {
+    // Given: A policy with worker-src
+    let processor = CspProcessor::new("default-src *; worker-src https://workers.com").unwrap();
+
+    // Then: Checking by name matches the typed API, ignoring case
+    assert!(processor.check_source_by_name("worker-src", "https://workers.com/w.js"));
+    assert!(!processor.check_source_by_name("Worker-Src", "https://evil.com/w.js"));
+}
+
+#[test]
+fn test_check_source_by_name_for_unknown_directive() {
+    // Given: A policy with a directive the processor has no variant for
+    let processor =
+        CspProcessor::new("default-src 'none'; prefetch-src https://cdn.example.com").unwrap();
+
+    // Then: The directive's own sources are enforced
+    assert!(processor.check_source_by_name("prefetch-src", "https://cdn.example.com/a.js"));
+    assert!(!processor.check_source_by_name("prefetch-src", "https://evil.com/a.js"));
+
+    // And: An absent unknown directive doesn't fall back to default-src
+    assert!(processor.check_source_by_name("fenced-frame-src", "https://evil.com/"));
+}
+
+#[test]
+fn test_directive_from_name() {
+    assert_eq!(
+        CspDirective::from_name("base-uri"),
+        Some(CspDirective::BaseUri)
+    );
+    assert_eq!(
+        CspDirective::from_name("UPGRADE-INSECURE-REQUESTS"),
+        Some(CspDirective::UpgradeInsecureRequests)
+    );
+    assert_eq!(CspDirective::from_name("prefetch-src"), None);
+}
@@ -0,0 +1,113 @@
+use csp_processor::{x_frame_options_allows, CspDirective, CspProcessor};
+use url::Url;
+
+fn url(s: &str) -> Url {
+    Url::parse(s).unwrap()
+}
+
+#[test]
+fn test_frame_ancestors_directive_name() {
+    assert_eq!(CspDirective::FrameAncestors.as_str(), "frame-ancestors");
+}
+
+#[test]
+fn test_frame_ancestors_is_parsed() {
+    // Given: A header with frame-ancestors
+    let policy = CspProcessor::parse_header("frame-ancestors 'self' https://partner.com").unwrap();
+
+    // Then: The source list is recorded
+    assert_eq!(
+        policy.directives.get("frame-ancestors").unwrap(),
+        &vec!["'self'".to_string(), "https://partner.com".to_string()]
+    );
+}
+
+#[test]
+fn test_frame_ancestors_none_blocks_all_embedding() {
+    // Given: A policy forbidding all embedding
+    let processor = CspProcessor::new("frame-ancestors 'none'")
+        .unwrap()
+        .with_document_origin(url("https://example.com"));
+
+    // Then: No embedder is allowed, not even the same origin
+    assert!(!processor.can_embed_in(&url("https://example.com")));
+    assert!(!processor.can_embed_in(&url("https://other.com")));
+}
+
+#[test]
+fn test_frame_ancestors_host_list_allows_specific_embedder() {
+    // Given: A policy allowing a single partner
+    let processor = CspProcessor::new("frame-ancestors https://partner.com").unwrap();
+
+    // Then: Only that embedder is allowed
+    assert!(processor.can_embed_in(&url("https://partner.com/app")));
+    assert!(!processor.can_embed_in(&url("https://evil.com")));
+}
+
+#[test]
+fn test_frame_ancestors_self_and_wildcard_subdomain() {
+    let processor = CspProcessor::new("frame-ancestors 'self' *.example.net")
+        .unwrap()
+        .with_document_origin(url("https://example.com"));
+
+    assert!(processor.can_embed_in(&url("https://example.com/embedder")));
+    assert!(processor.can_embed_in(&url("https://app.example.net")));
+    assert!(!processor.can_embed_in(&url("http://example.com")));
+}
+
+#[test]
+fn test_frame_ancestors_scheme_source() {
+    let processor = CspProcessor::new("frame-ancestors https:").unwrap();
+
+    assert!(processor.can_embed_in(&url("https://anything.com")));
+    assert!(!processor.can_embed_in(&url("http://anything.com")));
+}
+
+#[test]
+fn test_frame_ancestors_does_not_fall_back_to_default_src() {
+    let processor = CspProcessor::new("default-src 'none'").unwrap();
+
+    assert!(processor.can_embed_in(&url("https://other.com")));
+}
+
+#[test]
+fn test_frame_ancestors_overrides_x_frame_options() {
+    // Given: A policy allowing a partner and a conflicting X-Frame-Options
+    let processor = CspProcessor::new("frame-ancestors https://partner.com").unwrap();
+
+    // Then: frame-ancestors wins
+    assert!(processor.can_embed_in_with_x_frame_options(&url("https://partner.com"), Some("DENY")));
+}
+
+#[test]
+fn test_x_frame_options_applies_without_frame_ancestors() {
+    let processor = CspProcessor::new("script-src 'self'")
+        .unwrap()
+        .with_document_origin(url("https://example.com"));
+
+    assert!(!processor.can_embed_in_with_x_frame_options(&url("https://other.com"), Some("deny")));
+    assert!(processor
+        .can_embed_in_with_x_frame_options(&url("https://example.com"), Some("SAMEORIGIN")));
+    assert!(
+        !processor.can_embed_in_with_x_frame_options(&url("https://other.com"), Some("SAMEORIGIN"))
+    );
+    assert!(processor.can_embed_in_with_x_frame_options(&url("https://other.com"), None));
+}
+
+#[test]
+fn test_x_frame_options_allows() {
+    let document = url("https://example.com");
+
+    assert!(!x_frame_options_allows("DENY", Some(&document), &document));
+    assert!(x_frame_options_allows(
+        "SAMEORIGIN",
+        Some(&document),
+        &document
+    ));
+    assert!(!x_frame_options_allows("SAMEORIGIN", None, &document));
+    assert!(x_frame_options_allows(
+        "ALLOW-FROM https://other.com",
+        Some(&document),
+        &url("https://other.com")
+    ));
+}
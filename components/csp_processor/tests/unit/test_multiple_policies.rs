@@ -0,0 +1,60 @@
+use csp_processor::{CspDirective, CspProcessor};
+use url::Url;
+
+#[test]
+fn test_from_headers_stricter_policy_wins_for_sources() {
+    // Given: One policy allows cdn.example.com, the other only allows 'self'
+    let processor = CspProcessor::from_headers(&[
+        "script-src 'self' https://cdn.example.com",
+        "script-src 'self'",
+    ])
+    .unwrap()
+    .with_document_origin(Url::parse("https://example.com").unwrap());
+
+    // Then: The CDN is blocked, since the second policy forbids it
+    assert!(!processor.check_source(CspDirective::ScriptSrc, "https://cdn.example.com/app.js"));
+    // And: Same-origin scripts pass both policies
+    assert!(processor.check_source(CspDirective::ScriptSrc, "https://example.com/app.js"));
+}
+
+#[test]
+fn test_from_headers_policy_without_directive_does_not_relax_other() {
+    // Given: Only the first policy restricts images
+    let processor =
+        CspProcessor::from_headers(&["img-src https://img.example.com", "script-src 'none'"])
+            .unwrap();
+
+    // Then: The image restriction still applies
+    assert!(processor.check_source(CspDirective::ImgSrc, "https://img.example.com/a.png"));
+    assert!(!processor.check_source(CspDirective::ImgSrc, "https://other.com/a.png"));
+}
+
+#[test]
+fn test_from_headers_inline_requires_every_policy() {
+    // Given: Only one policy allows inline scripts
+    let processor =
+        CspProcessor::from_headers(&["script-src 'unsafe-inline'", "script-src 'nonce-abc123'"])
+            .unwrap();
+
+    // Then: Inline scripts need the nonce to satisfy both
+    assert!(!processor.is_inline_allowed(CspDirective::ScriptSrc, None));
+    assert!(processor.is_inline_allowed(CspDirective::ScriptSrc, Some("abc123")));
+}
+
+#[test]
+fn test_from_headers_frame_ancestors_intersection() {
+    let processor = CspProcessor::from_headers(&[
+        "frame-ancestors https://a.com https://b.com",
+        "frame-ancestors https://b.com",
+    ])
+    .unwrap();
+
+    assert!(!processor.can_embed_in(&Url::parse("https://a.com").unwrap()));
+    assert!(processor.can_embed_in(&Url::parse("https://b.com").unwrap()));
+}
+
+#[test]
+fn test_from_headers_rejects_empty_input() {
+    assert!(CspProcessor::from_headers(&[]).is_err());
+    assert!(CspProcessor::from_headers(&["script-src 'self'", "  "]).is_err());
+}
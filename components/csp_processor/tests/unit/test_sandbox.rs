@@ -0,0 +1,77 @@
+use csp_processor::{CspProcessor, SandboxFlags};
+
+#[test]
+fn test_sandbox_with_tokens() {
+    // Given: A sandbox allowing scripts and same-origin access
+    let processor = CspProcessor::new("sandbox allow-scripts allow-same-origin").unwrap();
+
+    // When: Reading the sandbox flags
+    let flags = processor.sandbox_flags().unwrap();
+
+    // Then: Exactly those capabilities are allowed
+    assert!(flags.allows_scripts());
+    assert!(flags.allows_same_origin());
+    assert!(!flags.allows_forms());
+    assert!(!flags.allows_popups());
+    assert!(!flags.allows_top_navigation());
+    assert_eq!(
+        flags,
+        SandboxFlags::ALLOW_SCRIPTS | SandboxFlags::ALLOW_SAME_ORIGIN
+    );
+}
+
+#[test]
+fn test_empty_sandbox_is_maximally_restrictive() {
+    // Given: A sandbox directive without tokens
+    let processor = CspProcessor::new("default-src 'self'; sandbox").unwrap();
+
+    // When: Reading the sandbox flags
+    let flags = processor.sandbox_flags().unwrap();
+
+    // Then: Nothing is allowed
+    assert!(flags.is_empty());
+    assert_eq!(flags, SandboxFlags::NONE);
+    assert!(!flags.allows_scripts());
+    assert!(!flags.allows_same_origin());
+    assert!(!flags.allows_forms());
+}
+
+#[test]
+fn test_no_sandbox_directive() {
+    // Given: A policy without sandbox
+    let processor = CspProcessor::new("default-src 'self'").unwrap();
+
+    // Then: No sandboxing applies
+    assert_eq!(processor.sandbox_flags(), None);
+}
+
+#[test]
+fn test_sandbox_tokens_ignore_case_and_unknown_values() {
+    // Given: Mixed-case and unknown tokens
+    let processor = CspProcessor::new("sandbox Allow-Forms allow-teleport").unwrap();
+
+    // Then: Known tokens are applied and unknown ones ignored
+    assert_eq!(processor.sandbox_flags(), Some(SandboxFlags::ALLOW_FORMS));
+}
+
+#[test]
+fn test_sandbox_across_policies_intersects() {
+    // Given: Two policies with different sandbox allowances
+    let processor = CspProcessor::from_headers(&[
+        "sandbox allow-scripts allow-forms",
+        "sandbox allow-scripts allow-popups",
+    ])
+    .unwrap();
+
+    // Then: Only capabilities both allow remain
+    assert_eq!(processor.sandbox_flags(), Some(SandboxFlags::ALLOW_SCRIPTS));
+}
+
+#[test]
+fn test_sandbox_flag_from_token() {
+    assert_eq!(
+        SandboxFlags::from_token("allow-popups-to-escape-sandbox"),
+        Some(SandboxFlags::ALLOW_POPUPS_TO_ESCAPE_SANDBOX)
+    );
+    assert_eq!(SandboxFlags::from_token("allow-everything"), None);
+}
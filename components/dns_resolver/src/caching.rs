@@ -0,0 +1,84 @@
+//! Caching layer over any DNS resolver
+
+use crate::single_flight::SingleFlight;
+use crate::{DnsCache, DnsResolver, SrvRecord};
+use async_trait::async_trait;
+use network_errors::{NetworkError, NetworkResult};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Time-to-live of cached lookups when none is given
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Resolver that answers repeated lookups from a [`DnsCache`]
+///
+/// Successful lookups of the wrapped resolver are cached for the configured
+/// TTL; failures aren't cached. The cache is shared, so entries inserted
+/// through [`cache`](Self::cache) are served as well.
+///
+/// Concurrent lookups of the same uncached hostname share a single call to
+/// the wrapped resolver, and all of them get its result.
+pub struct CachingResolver {
+    inner: Arc<dyn DnsResolver>,
+    cache: Arc<Mutex<DnsCache>>,
+    ttl: Duration,
+    lookups: SingleFlight,
+}
+
+impl CachingResolver {
+    /// Wrap `inner`, caching its answers for [`DEFAULT_CACHE_TTL`]
+    pub fn new(inner: Arc<dyn DnsResolver>) -> Self {
+        Self::with_ttl(inner, DEFAULT_CACHE_TTL)
+    }
+
+    /// Wrap `inner`, caching its answers for `ttl`
+    pub fn with_ttl(inner: Arc<dyn DnsResolver>, ttl: Duration) -> Self {
+        Self {
+            inner,
+            cache: Arc::new(Mutex::new(DnsCache::new())),
+            ttl,
+            lookups: SingleFlight::new(),
+        }
+    }
+
+    /// Handle to the cache of resolved addresses
+    pub fn cache(&self) -> Arc<Mutex<DnsCache>> {
+        Arc::clone(&self.cache)
+    }
+}
+
+#[async_trait]
+impl DnsResolver for CachingResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        if let Some(addresses) = self.cache.lock().await.get(&hostname) {
+            return Ok(addresses);
+        }
+
+        self.lookups
+            .run(&hostname, || async {
+                let addresses = self.inner.resolve(hostname.clone()).await?;
+                self.cache
+                    .lock()
+                    .await
+                    .insert(hostname.clone(), addresses.clone(), self.ttl);
+                Ok(addresses)
+            })
+            .await
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        tokio::time::timeout(timeout, self.resolve(hostname))
+            .await
+            .map_err(|_| NetworkError::Timeout(timeout))?
+    }
+
+    async fn resolve_srv(&self, name: String) -> NetworkResult<Vec<SrvRecord>> {
+        self.inner.resolve_srv(name).await
+    }
+}
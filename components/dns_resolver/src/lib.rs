@@ -25,7 +25,7 @@
 #![warn(rust_2018_idioms)]
 
 use async_trait::async_trait;
-use network_errors::NetworkResult;
+use network_errors::{NetworkError, NetworkResult};
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::time::{Duration, Instant};
@@ -70,6 +70,55 @@ pub trait DnsResolver: Send + Sync {
         hostname: String,
         timeout: Duration,
     ) -> NetworkResult<Vec<IpAddr>>;
+
+    /// Resolve SRV records for a service name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The service name to look up (e.g. `_turn._udp.example.com`)
+    ///
+    /// # Returns
+    ///
+    /// SRV records ordered as described by [`sort_srv_records`]
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::DnsError` if resolution fails. The default
+    /// implementation returns `NetworkError::Other` for resolvers without
+    /// SRV support.
+    async fn resolve_srv(&self, name: String) -> NetworkResult<Vec<SrvRecord>> {
+        Err(NetworkError::Other(format!(
+            "SRV resolution of {} is unsupported by this resolver",
+            name
+        )))
+    }
+}
+
+/// DNS SRV record (RFC 2782)
+///
+/// Locates the host and port providing a service.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SrvRecord {
+    /// Priority of the target host; lower values are preferred
+    pub priority: u16,
+    /// Relative weight for records with the same priority
+    pub weight: u16,
+    /// Port on the target host
+    pub port: u16,
+    /// Target hostname, without the trailing dot
+    pub target: String,
+}
+
+/// Order SRV records by preference
+///
+/// Records are sorted by ascending priority, then by descending weight so the
+/// most heavily weighted target of the preferred priority comes first.
+pub fn sort_srv_records(records: &mut [SrvRecord]) {
+    records.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then_with(|| b.weight.cmp(&a.weight))
+    });
 }
 
 /// Cache entry with TTL
@@ -199,6 +248,9 @@ impl Default for DohConfig {
     }
 }
 
+mod caching;
 mod resolver;
+mod single_flight;
 
+pub use caching::{CachingResolver, DEFAULT_CACHE_TTL};
 pub use resolver::StandardResolver;
@@ -1,6 +1,7 @@
 //! DNS resolver implementation using hickory-resolver
 
-use crate::{DnsCache, DnsResolver, DohConfig};
+use crate::single_flight::SingleFlight;
+use crate::{sort_srv_records, DnsCache, DnsResolver, DohConfig, SrvRecord};
 use async_trait::async_trait;
 use hickory_resolver::config::{ResolverConfig, ResolverOpts};
 use hickory_resolver::TokioAsyncResolver;
@@ -12,10 +13,12 @@ use tokio::sync::Mutex;
 
 /// Standard DNS resolver implementation
 ///
-/// Supports both standard DNS and DNS-over-HTTPS with caching.
+/// Supports both standard DNS and DNS-over-HTTPS with caching. Concurrent
+/// lookups of the same uncached hostname share one query.
 pub struct StandardResolver {
     resolver: TokioAsyncResolver,
     cache: Arc<Mutex<DnsCache>>,
+    lookups: SingleFlight,
     #[allow(dead_code)] // Kept for future DoH enhancements
     doh_config: Option<DohConfig>,
 }
@@ -54,6 +57,7 @@ impl StandardResolver {
         Ok(Self {
             resolver,
             cache: Arc::new(Mutex::new(DnsCache::new())),
+            lookups: SingleFlight::new(),
             doh_config,
         })
     }
@@ -92,29 +96,37 @@ impl DnsResolver for StandardResolver {
             }
         }
 
-        // Perform DNS lookup
-        let lookup = self
-            .resolver
-            .lookup_ip(hostname.as_str())
+        // Perform DNS lookup, shared with concurrent resolves of the name
+        self.lookups
+            .run(&hostname, || async {
+                let lookup = self
+                    .resolver
+                    .lookup_ip(hostname.as_str())
+                    .await
+                    .map_err(|e| NetworkError::DnsError(format!("DNS resolution failed: {}", e)))?;
+
+                let addresses: Vec<IpAddr> = lookup.iter().collect();
+
+                if addresses.is_empty() {
+                    return Err(NetworkError::DnsError(format!(
+                        "No addresses found for {}",
+                        hostname
+                    )));
+                }
+
+                // Cache the result with default TTL of 5 minutes
+                {
+                    let mut cache = self.cache.lock().await;
+                    cache.insert(
+                        hostname.clone(),
+                        addresses.clone(),
+                        Duration::from_secs(300),
+                    );
+                }
+
+                Ok(addresses)
+            })
             .await
-            .map_err(|e| NetworkError::DnsError(format!("DNS resolution failed: {}", e)))?;
-
-        let addresses: Vec<IpAddr> = lookup.iter().collect();
-
-        if addresses.is_empty() {
-            return Err(NetworkError::DnsError(format!(
-                "No addresses found for {}",
-                hostname
-            )));
-        }
-
-        // Cache the result with default TTL of 5 minutes
-        {
-            let mut cache = self.cache.lock().await;
-            cache.insert(hostname, addresses.clone(), Duration::from_secs(300));
-        }
-
-        Ok(addresses)
     }
 
     async fn resolve_with_timeout(
@@ -126,6 +138,34 @@ impl DnsResolver for StandardResolver {
             .await
             .map_err(|_| NetworkError::Timeout(timeout))?
     }
+
+    async fn resolve_srv(&self, name: String) -> NetworkResult<Vec<SrvRecord>> {
+        let lookup = self
+            .resolver
+            .srv_lookup(name.as_str())
+            .await
+            .map_err(|e| NetworkError::DnsError(format!("SRV resolution failed: {}", e)))?;
+
+        let mut records: Vec<SrvRecord> = lookup
+            .iter()
+            .map(|srv| SrvRecord {
+                priority: srv.priority(),
+                weight: srv.weight(),
+                port: srv.port(),
+                target: srv.target().to_utf8().trim_end_matches('.').to_string(),
+            })
+            .collect();
+
+        if records.is_empty() {
+            return Err(NetworkError::DnsError(format!(
+                "No SRV records found for {}",
+                name
+            )));
+        }
+
+        sort_srv_records(&mut records);
+        Ok(records)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,67 @@
+//! Single-flight deduplication of concurrent lookups
+
+use network_errors::{NetworkError, NetworkResult};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+/// Outcome of one lookup, shared by everyone waiting on it
+type Flight = Arc<OnceCell<NetworkResult<Vec<IpAddr>>>>;
+
+/// Lookups in progress, keyed by hostname
+///
+/// Concurrent [`run`](Self::run) calls for the same hostname share a single
+/// lookup: the first caller performs it and the others wait for its result.
+/// If the caller performing the lookup is cancelled, a waiting caller takes
+/// over. Results are not kept once the lookup has completed.
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    in_flight: Mutex<HashMap<String, Flight>>,
+}
+
+impl SingleFlight {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `hostname` with `lookup`, unless a lookup of it is in progress
+    pub(crate) async fn run<F, Fut>(&self, hostname: &str, lookup: F) -> NetworkResult<Vec<IpAddr>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = NetworkResult<Vec<IpAddr>>>,
+    {
+        let flight = Arc::clone(self.lock().entry(hostname.to_string()).or_default());
+
+        let result = match flight.get_or_init(lookup).await {
+            Ok(addresses) => Ok(addresses.clone()),
+            Err(error) => Err(duplicate_error(error)),
+        };
+
+        // Later lookups start afresh; the flight may already have been replaced
+        let mut in_flight = self.lock();
+        if in_flight
+            .get(hostname)
+            .is_some_and(|current| Arc::ptr_eq(current, &flight))
+        {
+            in_flight.remove(hostname);
+        }
+        result
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Flight>> {
+        self.in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Recreate a lookup error for each waiter, since `NetworkError` is not `Clone`
+fn duplicate_error(error: &NetworkError) -> NetworkError {
+    match error {
+        NetworkError::DnsError(msg) => NetworkError::DnsError(msg.clone()),
+        NetworkError::Timeout(duration) => NetworkError::Timeout(*duration),
+        other => NetworkError::DnsError(other.to_string()),
+    }
+}
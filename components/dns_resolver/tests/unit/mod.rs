@@ -1,9 +1,66 @@
 //! Unit tests for dns_resolver component
 
-use dns_resolver::{DnsCache, DohConfig};
+use async_trait::async_trait;
+use dns_resolver::{
+    sort_srv_records, CachingResolver, DnsCache, DnsResolver, DohConfig, SrvRecord,
+};
+use network_errors::{NetworkError, NetworkResult};
 use std::net::IpAddr;
 use std::time::Duration;
 
+fn srv(priority: u16, weight: u16, port: u16, target: &str) -> SrvRecord {
+    SrvRecord {
+        priority,
+        weight,
+        port,
+        target: target.to_string(),
+    }
+}
+
+/// Resolver serving crafted SRV records in the order given
+struct SrvMockResolver {
+    records: Vec<SrvRecord>,
+}
+
+#[async_trait]
+impl DnsResolver for SrvMockResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        Err(NetworkError::DnsError(format!("no A records for {}", hostname)))
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+
+    async fn resolve_srv(&self, _name: String) -> NetworkResult<Vec<SrvRecord>> {
+        let mut records = self.records.clone();
+        sort_srv_records(&mut records);
+        Ok(records)
+    }
+}
+
+/// Resolver relying on the default `resolve_srv`
+struct AddressOnlyResolver;
+
+#[async_trait]
+impl DnsResolver for AddressOnlyResolver {
+    async fn resolve(&self, _hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        Ok(vec!["192.0.2.1".parse().unwrap()])
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
 /// Test that DnsResolver trait can be imported
 #[test]
 fn test_dns_resolver_trait_exists() {
@@ -82,3 +139,234 @@ fn test_doh_config_default() {
     assert!(!config.resolver_url.is_empty());
     assert!(config.use_fallback);
 }
+
+/// Test SRV records sort by ascending priority, then descending weight
+#[test]
+fn test_sort_srv_records_priority_then_weight() {
+    let mut records = vec![
+        srv(20, 100, 3478, "backup.example.com"),
+        srv(10, 5, 3478, "light.example.com"),
+        srv(10, 60, 3479, "heavy.example.com"),
+    ];
+
+    sort_srv_records(&mut records);
+
+    let targets: Vec<&str> = records.iter().map(|r| r.target.as_str()).collect();
+    assert_eq!(
+        targets,
+        vec!["heavy.example.com", "light.example.com", "backup.example.com"]
+    );
+}
+
+/// Test a resolver returning crafted SRV records orders them by preference
+#[tokio::test]
+async fn test_mock_resolver_resolve_srv_ordering() {
+    let resolver = SrvMockResolver {
+        records: vec![
+            srv(1, 0, 5349, "turn2.example.com"),
+            srv(0, 10, 3478, "turn1.example.com"),
+            srv(0, 90, 3478, "turn0.example.com"),
+        ],
+    };
+
+    let records = resolver
+        .resolve_srv("_turn._udp.example.com".to_string())
+        .await
+        .unwrap();
+
+    assert_eq!(records[0], srv(0, 90, 3478, "turn0.example.com"));
+    assert_eq!(records[1], srv(0, 10, 3478, "turn1.example.com"));
+    assert_eq!(records[2], srv(1, 0, 5349, "turn2.example.com"));
+}
+
+/// Test the default resolve_srv reports SRV lookups as unsupported
+#[tokio::test]
+async fn test_default_resolve_srv_unsupported() {
+    let result = AddressOnlyResolver
+        .resolve_srv("_turn._udp.example.com".to_string())
+        .await;
+
+    match result {
+        Err(NetworkError::Other(msg)) => assert!(msg.contains("unsupported")),
+        other => panic!("Expected NetworkError::Other, got {:?}", other),
+    }
+}
+
+/// Resolver counting lookups, answering with a fixed address or failing
+struct CountingResolver {
+    address: Option<IpAddr>,
+    lookups: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl DnsResolver for CountingResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        self.lookups
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.address
+            .map(|address| vec![address])
+            .ok_or_else(|| NetworkError::DnsError(format!("no answer for {}", hostname)))
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
+/// Test the caching resolver answers repeated lookups from its cache
+#[tokio::test]
+async fn test_caching_resolver_caches_answers() {
+    let address: IpAddr = "192.0.2.7".parse().unwrap();
+    let inner = std::sync::Arc::new(CountingResolver {
+        address: Some(address),
+        lookups: Default::default(),
+    });
+    let resolver = CachingResolver::new(inner.clone());
+
+    assert_eq!(
+        resolver.resolve("a.test".to_string()).await.unwrap(),
+        vec![address]
+    );
+    assert_eq!(
+        resolver.resolve("a.test".to_string()).await.unwrap(),
+        vec![address]
+    );
+
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 1);
+    assert_eq!(
+        resolver.cache().lock().await.get("a.test"),
+        Some(vec![address])
+    );
+}
+
+/// Test the caching resolver looks the host up again once the entry expires
+#[tokio::test]
+async fn test_caching_resolver_expires_entries() {
+    let inner = std::sync::Arc::new(CountingResolver {
+        address: Some("192.0.2.7".parse().unwrap()),
+        lookups: Default::default(),
+    });
+    let resolver = CachingResolver::with_ttl(inner.clone(), Duration::from_millis(20));
+
+    resolver.resolve("a.test".to_string()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    resolver.resolve("a.test".to_string()).await.unwrap();
+
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+/// Test failed lookups aren't cached
+#[tokio::test]
+async fn test_caching_resolver_skips_failures() {
+    let inner = std::sync::Arc::new(CountingResolver {
+        address: None,
+        lookups: Default::default(),
+    });
+    let resolver = CachingResolver::new(inner.clone());
+
+    assert!(resolver.resolve("a.test".to_string()).await.is_err());
+    assert!(resolver.resolve("a.test".to_string()).await.is_err());
+
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+    assert!(resolver.cache().lock().await.get("a.test").is_none());
+}
+
+/// Resolver counting lookups that take `delay` to answer
+struct SlowCountingResolver {
+    address: Option<IpAddr>,
+    delay: Duration,
+    lookups: std::sync::atomic::AtomicUsize,
+}
+
+#[async_trait]
+impl DnsResolver for SlowCountingResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        self.lookups
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        tokio::time::sleep(self.delay).await;
+        self.address
+            .map(|address| vec![address])
+            .ok_or_else(|| NetworkError::DnsError(format!("no answer for {}", hostname)))
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
+/// Test concurrent lookups of one uncached name share a single resolution
+#[tokio::test]
+async fn test_caching_resolver_coalesces_concurrent_lookups() {
+    let address: IpAddr = "192.0.2.7".parse().unwrap();
+    let inner = std::sync::Arc::new(SlowCountingResolver {
+        address: Some(address),
+        delay: Duration::from_millis(50),
+        lookups: Default::default(),
+    });
+    let resolver = std::sync::Arc::new(CachingResolver::new(inner.clone()));
+
+    let lookups: Vec<_> = (0..50)
+        .map(|_| {
+            let resolver = resolver.clone();
+            tokio::spawn(async move { resolver.resolve("storm.test".to_string()).await })
+        })
+        .collect();
+    for lookup in lookups {
+        assert_eq!(lookup.await.unwrap().unwrap(), vec![address]);
+    }
+
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 1);
+    assert_eq!(
+        resolver.cache().lock().await.get("storm.test"),
+        Some(vec![address])
+    );
+}
+
+/// Test concurrent lookups of different names aren't coalesced
+#[tokio::test]
+async fn test_caching_resolver_resolves_distinct_names_separately() {
+    let inner = std::sync::Arc::new(SlowCountingResolver {
+        address: Some("192.0.2.7".parse().unwrap()),
+        delay: Duration::from_millis(20),
+        lookups: Default::default(),
+    });
+    let resolver = CachingResolver::new(inner.clone());
+
+    let (a, b) = tokio::join!(
+        resolver.resolve("a.test".to_string()),
+        resolver.resolve("b.test".to_string())
+    );
+
+    assert!(a.is_ok() && b.is_ok());
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
+
+/// Test a shared failed lookup fails every waiter and is retried afterwards
+#[tokio::test]
+async fn test_caching_resolver_shares_failures_without_caching() {
+    let inner = std::sync::Arc::new(SlowCountingResolver {
+        address: None,
+        delay: Duration::from_millis(20),
+        lookups: Default::default(),
+    });
+    let resolver = CachingResolver::new(inner.clone());
+
+    let (first, second) = tokio::join!(
+        resolver.resolve("a.test".to_string()),
+        resolver.resolve("a.test".to_string())
+    );
+    assert!(matches!(first, Err(NetworkError::DnsError(_))));
+    assert!(matches!(second, Err(NetworkError::DnsError(_))));
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    assert!(resolver.resolve("a.test".to_string()).await.is_err());
+    assert_eq!(inner.lookups.load(std::sync::atomic::Ordering::SeqCst), 2);
+}
@@ -0,0 +1,398 @@
+//! Server-Sent Events client
+//!
+//! [`EventSource`] opens a `text/event-stream` response through an
+//! [`Http1Client`], parses it with an [`EventStreamParser`] and reconnects
+//! when the stream ends, as described in the HTML standard's EventSource
+//! section.
+
+use crate::Http1Client;
+use futures::{Stream, StreamExt};
+use network_errors::NetworkError;
+use network_types::{CacheMode, NetworkRequest, NetworkResponse, ResponseBody};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Reconnection delay used until the server sends a `retry:` field
+pub const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// Upper bound on the reconnection delay after repeated failed attempts
+pub const MAX_RETRY: Duration = Duration::from_secs(60);
+
+/// Byte order mark skipped at the start of a stream
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// One event dispatched from a `text/event-stream`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSentEvent {
+    /// Event type from the `event:` field, `"message"` when none was given
+    pub event: String,
+    /// Concatenated `data:` lines, joined with `\n`
+    pub data: String,
+    /// Last event ID in effect when the event was dispatched
+    pub id: String,
+}
+
+/// Incremental parser for the `text/event-stream` format
+///
+/// Bytes may be fed in chunks split anywhere, including inside a line or a
+/// `\r\n` pair. Events are dispatched on a blank line; an event still being
+/// built when the stream ends is discarded.
+#[derive(Debug, Default)]
+pub struct EventStreamParser {
+    /// Bytes of a line whose end hasn't arrived yet
+    line: Vec<u8>,
+    /// Whether the start of the stream has been checked for a BOM
+    bom_checked: bool,
+    /// The previous chunk ended in `\r`, so a leading `\n` belongs to it
+    after_cr: bool,
+    event_type: String,
+    data: String,
+    last_event_id: String,
+    retry: Option<Duration>,
+}
+
+impl EventStreamParser {
+    /// Create a parser for a new stream
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next chunk of the stream, returning the events it completes
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<ServerSentEvent> {
+        let mut chunk = chunk;
+        let bom_stripped: Vec<u8>;
+        if !self.bom_checked {
+            self.line.extend_from_slice(chunk);
+            if self.line.len() < BOM.len() && BOM.starts_with(&self.line) {
+                return Vec::new();
+            }
+            self.bom_checked = true;
+            bom_stripped = std::mem::take(&mut self.line);
+            chunk = bom_stripped.strip_prefix(BOM).unwrap_or(&bom_stripped);
+        } else if self.after_cr {
+            chunk = chunk.strip_prefix(b"\n").unwrap_or(chunk);
+        }
+        self.after_cr = false;
+
+        // Split on CR, LF or CRLF and process each complete line
+        let mut events = Vec::new();
+        while let Some(end) = chunk.iter().position(|b| *b == b'\r' || *b == b'\n') {
+            self.line.extend_from_slice(&chunk[..end]);
+            let line = std::mem::take(&mut self.line);
+            events.extend(self.process_line(&String::from_utf8_lossy(&line)));
+
+            let mut next = end + 1;
+            if chunk[end] == b'\r' {
+                match chunk.get(next) {
+                    Some(b'\n') => next += 1,
+                    Some(_) => {}
+                    None => self.after_cr = true,
+                }
+            }
+            chunk = &chunk[next..];
+        }
+        self.line.extend_from_slice(chunk);
+        events
+    }
+
+    /// Apply one line, returning the event a blank line dispatches
+    fn process_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // Comment
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event_type = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.last_event_id = value.to_string(),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Build the event collected since the last blank line, if it has data
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        let event_type = std::mem::take(&mut self.event_type);
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        Some(ServerSentEvent {
+            event: if event_type.is_empty() {
+                "message".to_string()
+            } else {
+                event_type
+            },
+            data,
+            id: self.last_event_id.clone(),
+        })
+    }
+
+    /// ID to send as `Last-Event-ID` when reconnecting
+    pub fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// Reconnection time most recently set by a `retry:` field
+    pub fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    /// Prepare for a new connection's stream
+    ///
+    /// Drops any partly received line or event but keeps the last event ID
+    /// and reconnection time.
+    pub fn reset(&mut self) {
+        *self = Self {
+            last_event_id: std::mem::take(&mut self.last_event_id),
+            retry: self.retry,
+            ..Self::default()
+        };
+    }
+}
+
+/// Connection state of an [`EventSource`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadyState {
+    /// Connecting, or waiting to reconnect
+    Connecting,
+    /// Receiving the event stream
+    Open,
+    /// Failed or closed; no more events will arrive
+    Closed,
+}
+
+/// A Server-Sent Events connection
+///
+/// Events are read with [`next_event`](Self::next_event) or as a stream via
+/// [`into_stream`](Self::into_stream). When the response ends or a
+/// connection attempt fails, the source reconnects after the reconnection
+/// time (the server's last `retry:` value, or [`DEFAULT_RETRY`]), sending
+/// `Last-Event-ID` if an ID was received. Each consecutive failed attempt
+/// doubles the delay, up to [`MAX_RETRY`].
+///
+/// A response other than `200` with a `text/event-stream` content type
+/// fails the source for good.
+pub struct EventSource {
+    client: Arc<Http1Client>,
+    url: Url,
+    parser: EventStreamParser,
+    body: Option<ResponseBody>,
+    events: VecDeque<ServerSentEvent>,
+    state: ReadyState,
+    default_retry: Duration,
+    /// Whether the next connection attempt is a reconnection
+    reconnecting: bool,
+    /// Consecutive connection attempts that failed
+    failures: u32,
+}
+
+impl EventSource {
+    /// Create an event source for `url`
+    ///
+    /// Nothing is sent until the first event is requested.
+    pub fn new(client: Arc<Http1Client>, url: Url) -> Self {
+        Self {
+            client,
+            url,
+            parser: EventStreamParser::new(),
+            body: None,
+            events: VecDeque::new(),
+            state: ReadyState::Connecting,
+            default_retry: DEFAULT_RETRY,
+            reconnecting: false,
+            failures: 0,
+        }
+    }
+
+    /// Use `retry` as the reconnection time until the server sets one
+    pub fn with_retry(mut self, retry: Duration) -> Self {
+        self.default_retry = retry;
+        self
+    }
+
+    /// URL of the event stream
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Current connection state
+    pub fn ready_state(&self) -> ReadyState {
+        self.state
+    }
+
+    /// ID sent as `Last-Event-ID` on the next reconnection
+    pub fn last_event_id(&self) -> &str {
+        self.parser.last_event_id()
+    }
+
+    /// Reconnection time before backoff
+    pub fn retry_interval(&self) -> Duration {
+        self.parser.retry().unwrap_or(self.default_retry)
+    }
+
+    /// Close the connection; no further events are returned
+    pub fn close(&mut self) {
+        self.state = ReadyState::Closed;
+        self.body = None;
+        self.events.clear();
+    }
+
+    /// Wait for the next event, reconnecting as needed
+    ///
+    /// Returns None once the source is closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ProtocolError` if the server answers with a
+    /// status other than `200` or a content type other than
+    /// `text/event-stream`. The source is closed afterwards.
+    pub async fn next_event(&mut self) -> Option<Result<ServerSentEvent, NetworkError>> {
+        loop {
+            if self.state == ReadyState::Closed {
+                return None;
+            }
+            if let Some(event) = self.events.pop_front() {
+                return Some(Ok(event));
+            }
+
+            let Some(ResponseBody::Stream(body)) = self.body.as_mut() else {
+                if let Err(e) = self.connect().await {
+                    self.close();
+                    return Some(Err(e));
+                }
+                continue;
+            };
+
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    let events = self.parser.feed(&chunk);
+                    self.events.extend(events);
+                }
+                Some(Err(_)) | None => {
+                    self.body = None;
+                    self.state = ReadyState::Connecting;
+                    self.reconnecting = true;
+                }
+            }
+        }
+    }
+
+    /// Turn the source into a stream of events
+    pub fn into_stream(self) -> impl Stream<Item = Result<ServerSentEvent, NetworkError>> + Send {
+        futures::stream::unfold(self, |mut source| async move {
+            let event = source.next_event().await?;
+            Some((event, source))
+        })
+    }
+
+    /// Open the stream, waiting out the reconnection delay first if needed
+    ///
+    /// Connection failures are retried; only a fatal response is returned.
+    async fn connect(&mut self) -> Result<(), NetworkError> {
+        loop {
+            if self.reconnecting {
+                tokio::time::sleep(self.reconnect_delay()).await;
+            }
+            self.reconnecting = true;
+            self.parser.reset();
+
+            let response = match self.client.stream_response(self.request()).await {
+                Ok(response) => response,
+                Err(_) => {
+                    self.failures = self.failures.saturating_add(1);
+                    continue;
+                }
+            };
+            check_response(&response)?;
+
+            self.failures = 0;
+            self.state = ReadyState::Open;
+            self.body = Some(response.body);
+            return Ok(());
+        }
+    }
+
+    /// Reconnection time, doubled for each consecutive failed attempt
+    fn reconnect_delay(&self) -> Duration {
+        let backoff = 2u32.saturating_pow(self.failures.min(16));
+        self.retry_interval()
+            .saturating_mul(backoff)
+            .min(MAX_RETRY.max(self.retry_interval()))
+    }
+
+    /// Build the request for one connection attempt
+    fn request(&self) -> NetworkRequest {
+        let mut builder = NetworkRequest::builder(self.url.clone())
+            .header(
+                http::header::ACCEPT,
+                http::HeaderValue::from_static("text/event-stream"),
+            )
+            .header(
+                http::header::CACHE_CONTROL,
+                http::HeaderValue::from_static("no-store"),
+            )
+            .cache(CacheMode::NoStore);
+        let last_event_id = self.parser.last_event_id();
+        if !last_event_id.is_empty() {
+            if let Ok(value) = http::HeaderValue::from_str(last_event_id) {
+                builder = builder.header("last-event-id", value);
+            }
+        }
+        builder.build()
+    }
+}
+
+impl std::fmt::Debug for EventSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSource")
+            .field("url", &self.url.as_str())
+            .field("state", &self.state)
+            .field("last_event_id", &self.parser.last_event_id())
+            .finish()
+    }
+}
+
+/// Fail the connection unless the response is a `200` event stream
+fn check_response(response: &NetworkResponse) -> Result<(), NetworkError> {
+    if response.status != 200 {
+        return Err(NetworkError::ProtocolError(format!(
+            "EventSource response has status {}",
+            response.status
+        )));
+    }
+
+    let content_type = response
+        .headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    let mime = content_type.split(';').next().unwrap_or_default().trim();
+    if !mime.eq_ignore_ascii_case("text/event-stream") {
+        return Err(NetworkError::ProtocolError(format!(
+            "EventSource response has content type {:?}",
+            content_type
+        )));
+    }
+    Ok(())
+}
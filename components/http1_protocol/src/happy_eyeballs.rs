@@ -0,0 +1,84 @@
+//! Happy Eyeballs (RFC 8305) connection racing
+//!
+//! Connection attempts to a host's addresses are started one after another,
+//! a short delay apart, alternating between IPv6 and IPv4. The first attempt
+//! to succeed wins and the others are dropped, so a broken or slow address
+//! family doesn't stall the connection.
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::VecDeque;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// Delay before starting the next attempt while earlier ones are pending
+///
+/// RFC 8305 section 5 recommends 250ms.
+pub const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Order addresses for connection attempts
+///
+/// Address families are interleaved, starting with the family of the first
+/// address; the relative order within each family is preserved.
+pub(crate) fn interleave(addresses: Vec<IpAddr>) -> Vec<IpAddr> {
+    let first_is_v6 = addresses.first().is_some_and(IpAddr::is_ipv6);
+    let (mut preferred, mut other): (VecDeque<_>, VecDeque<_>) = addresses
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_v6);
+
+    let mut ordered = Vec::with_capacity(preferred.len() + other.len());
+    loop {
+        match (preferred.pop_front(), other.pop_front()) {
+            (None, None) => return ordered,
+            (first, second) => ordered.extend(first.into_iter().chain(second)),
+        }
+    }
+}
+
+/// Connect to the first reachable address, racing attempts `delay` apart
+///
+/// An attempt that fails starts the next one immediately. If every attempt
+/// fails, the last error is returned.
+pub(crate) async fn connect(
+    addresses: Vec<IpAddr>,
+    port: u16,
+    delay: Duration,
+) -> io::Result<TcpStream> {
+    let mut pending: VecDeque<SocketAddr> = interleave(addresses)
+        .into_iter()
+        .map(|ip| SocketAddr::new(ip, port))
+        .collect();
+    let mut attempts = FuturesUnordered::new();
+    let mut last_error = None;
+
+    loop {
+        if attempts.is_empty() {
+            match pending.pop_front() {
+                Some(addr) => attempts.push(TcpStream::connect(addr)),
+                None => {
+                    return Err(last_error.unwrap_or_else(|| {
+                        io::Error::new(io::ErrorKind::NotFound, "no addresses to connect to")
+                    }))
+                }
+            }
+        }
+
+        tokio::select! {
+            Some(result) = attempts.next() => match result {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    last_error = Some(e);
+                    if let Some(addr) = pending.pop_front() {
+                        attempts.push(TcpStream::connect(addr));
+                    }
+                }
+            },
+            _ = tokio::time::sleep(delay), if !pending.is_empty() => {
+                if let Some(addr) = pending.pop_front() {
+                    attempts.push(TcpStream::connect(addr));
+                }
+            }
+        }
+    }
+}
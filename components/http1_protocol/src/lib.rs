@@ -5,18 +5,38 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+pub mod event_source;
+mod happy_eyeballs;
+mod request_body;
+
+pub use event_source::{EventSource, EventStreamParser, ReadyState, ServerSentEvent};
+pub use happy_eyeballs::CONNECTION_ATTEMPT_DELAY;
+
+use bytes::Bytes;
+use dns_resolver::DnsResolver;
+use futures::Stream;
 use http_body_util::BodyExt;
+use hyper::body::{Body, Incoming};
 use hyper::client::conn::http1;
 use hyper_util::rt::TokioIo;
 use network_errors::NetworkError;
 use network_types::{
-    HttpMethod, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseType,
+    ContentRange, NetworkErrorKind, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody,
+    ResponseSource, ResponseType,
 };
-use std::collections::HashMap;
+use request_body::{expect_continue, ContinueHandle, RequestBody};
+use socket2::{SockRef, TcpKeepalive};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::task::{ready, Context, Poll};
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Notify};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
 
 /// Configuration for HTTP/1.1 client behavior
 ///
@@ -37,6 +57,28 @@ pub struct Http1Config {
 
     /// Enable HTTP pipelining for request optimization
     pub enable_pipelining: bool,
+
+    /// Disable Nagle's algorithm (`TCP_NODELAY`) on new connections
+    pub tcp_nodelay: bool,
+
+    /// Enable TCP keepalive (`SO_KEEPALIVE`) with this idle time before probes
+    ///
+    /// `None` leaves keepalive disabled.
+    pub tcp_keepalive: Option<Duration>,
+
+    /// Largest response body [`Http1Client::fetch`] will buffer, in bytes
+    ///
+    /// Responses whose body grows past this fail with
+    /// `NetworkError::Other("response body too large")` as soon as the
+    /// offending data arrives. `None` buffers bodies of any size.
+    pub max_response_body_bytes: Option<usize>,
+
+    /// Send `Expect: 100-continue` with request bodies
+    ///
+    /// The body is held back until the server answers `100 Continue` (or a
+    /// short wait passes without an answer), so a server that rejects the
+    /// request up front, e.g. with `401`, never receives it.
+    pub use_expect_continue: bool,
 }
 
 impl Default for Http1Config {
@@ -47,6 +89,10 @@ impl Default for Http1Config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: Some(Duration::from_secs(60)),
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         }
     }
 }
@@ -54,7 +100,7 @@ impl Default for Http1Config {
 /// Represents an HTTP/1.1 connection
 pub struct Http1Connection {
     /// The underlying hyper SendRequest handle
-    sender: http1::SendRequest<String>,
+    sender: http1::SendRequest<RequestBody>,
     /// Host this connection is for
     host: String,
     /// Port this connection is for
@@ -72,6 +118,23 @@ struct PoolState {
     idle_connections: HashMap<PoolKey, Vec<Http1Connection>>,
     /// Count of active connections per host:port
     active_counts: HashMap<PoolKey, usize>,
+    /// Set by [`ConnectionPool::shutdown`]; no connections are handed out
+    closed: bool,
+}
+
+/// Point-in-time snapshot of connection pool usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolMetrics {
+    /// Idle connections available for reuse across all hosts
+    pub idle_total: usize,
+    /// Connections currently checked out of the pool
+    pub active_total: usize,
+    /// Distinct host:port pairs with idle or active connections
+    pub hosts: usize,
+    /// Requests served by reusing an idle connection
+    pub reuse_count: u64,
+    /// Connections newly established by the pool
+    pub created_count: u64,
 }
 
 /// Connection pool for HTTP/1.1 connections
@@ -80,23 +143,138 @@ struct PoolState {
 pub struct ConnectionPool {
     config: Http1Config,
     state: Arc<Mutex<PoolState>>,
+    /// Idle connections handed out again since creation
+    reuse_count: AtomicU64,
+    /// Connections established since creation
+    created_count: AtomicU64,
+    /// Resolver for host addresses; the system resolver is used when unset
+    resolver: Option<Arc<dyn DnsResolver>>,
+    /// Background task pruning expired idle connections, if started
+    reaper: std::sync::Mutex<Option<JoinHandle<()>>>,
+    /// Notified whenever an active connection is returned or discarded
+    released: Notify,
+}
+
+impl PoolState {
+    /// Drop idle connections older than `idle_timeout` across all hosts
+    ///
+    /// Host entries left without idle or active connections are removed so the
+    /// maps don't grow with every host ever contacted.
+    fn prune_expired(&mut self, idle_timeout: Duration) {
+        for conns in self.idle_connections.values_mut() {
+            conns.retain(|conn| conn.last_used.elapsed() < idle_timeout);
+        }
+        self.idle_connections.retain(|_, conns| !conns.is_empty());
+
+        let idle = &self.idle_connections;
+        self.active_counts
+            .retain(|key, count| *count > 0 || idle.contains_key(key));
+    }
+
+    /// Connections checked out of the pool across all hosts
+    fn active_total(&self) -> usize {
+        self.active_counts.values().sum()
+    }
 }
 
 impl ConnectionPool {
     /// Create a new connection pool with the given configuration
+    ///
+    /// Host names are resolved with the system resolver.
     pub fn new(config: Http1Config) -> Self {
         Self {
             config,
             state: Arc::new(Mutex::new(PoolState {
                 idle_connections: HashMap::new(),
                 active_counts: HashMap::new(),
+                closed: false,
             })),
+            reuse_count: AtomicU64::new(0),
+            created_count: AtomicU64::new(0),
+            resolver: None,
+            reaper: std::sync::Mutex::new(None),
+            released: Notify::new(),
+        }
+    }
+
+    /// Create a new connection pool that resolves host names with `resolver`
+    pub fn with_resolver(config: Http1Config, resolver: Arc<dyn DnsResolver>) -> Self {
+        let mut pool = Self::new(config);
+        pool.resolver = Some(resolver);
+        pool
+    }
+
+    /// Start a background task that drops expired idle connections
+    ///
+    /// Every `interval` the task prunes connections idle for longer than
+    /// `idle_timeout` across all hosts, not just those being requested. It
+    /// stops when the pool is dropped. Calling this again replaces the previous
+    /// task. Must be called from within a Tokio runtime.
+    pub fn start_reaper(&self, interval: Duration) {
+        let state = Arc::downgrade(&self.state);
+        let idle_timeout = self.config.idle_timeout;
+        let interval = interval.max(Duration::from_millis(1));
+
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(state) = state.upgrade() else {
+                    break;
+                };
+                state.lock().await.prune_expired(idle_timeout);
+            }
+        });
+
+        let mut reaper = self.reaper.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(previous) = reaper.replace(handle) {
+            previous.abort();
+        }
+    }
+
+    /// Number of idle connections currently held across all hosts
+    pub async fn idle_connection_count(&self) -> usize {
+        let state = self.state.lock().await;
+        state.idle_connections.values().map(Vec::len).sum()
+    }
+
+    /// Snapshot of the pool's connection counts and reuse counters
+    pub async fn metrics(&self) -> PoolMetrics {
+        let reuse_count = self.reuse_count.load(Ordering::Relaxed);
+        let created_count = self.created_count.load(Ordering::Relaxed);
+
+        let state = self.state.lock().await;
+        let idle_total = state.idle_connections.values().map(Vec::len).sum();
+        let active_total = state.active_total();
+        let hosts = state
+            .active_counts
+            .iter()
+            .filter(|(_, count)| **count > 0)
+            .map(|(key, _)| key)
+            .chain(
+                state
+                    .idle_connections
+                    .iter()
+                    .filter(|(_, conns)| !conns.is_empty())
+                    .map(|(key, _)| key),
+            )
+            .collect::<HashSet<_>>()
+            .len();
+
+        PoolMetrics {
+            idle_total,
+            active_total,
+            hosts,
+            reuse_count,
+            created_count,
         }
     }
 
     /// Get a connection to the specified host and port
     ///
     /// Reuses an existing idle connection if available, otherwise creates a new one.
+    /// Fails with `NetworkError::ConnectionFailed` once the pool is shut down.
     pub async fn get_connection(
         &self,
         host: &str,
@@ -107,15 +285,23 @@ impl ConnectionPool {
         // Try to reuse an idle connection
         {
             let mut state = self.state.lock().await;
+            if state.closed {
+                return Err(pool_closed());
+            }
 
-            // Remove expired connections
+            // Remove expired connections and ones the server has closed
             if let Some(conns) = state.idle_connections.get_mut(&key) {
-                conns.retain(|conn| conn.last_used.elapsed() < self.config.idle_timeout);
+                conns.retain(|conn| {
+                    conn.last_used.elapsed() < self.config.idle_timeout && !conn.sender.is_closed()
+                });
 
                 // Try to get a reusable connection
                 if let Some(conn) = conns.pop() {
                     let count = state.active_counts.entry(key.clone()).or_insert(0);
                     *count += 1;
+                    self.reuse_count.fetch_add(1, Ordering::Relaxed);
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(host, port, "connection reused");
                     return Ok(conn);
                 }
             }
@@ -127,23 +313,20 @@ impl ConnectionPool {
 
     /// Return a connection to the pool for reuse
     ///
-    /// If keep-alive is disabled, the connection is dropped instead.
+    /// If keep-alive is disabled or the pool is shut down, the connection is
+    /// dropped instead.
     pub async fn return_connection(&self, mut connection: Http1Connection) {
-        if !self.config.enable_keepalive {
-            // Drop the connection if keep-alive is disabled
-            return;
-        }
-
         let key = (connection.host.clone(), connection.port);
 
         // Update last used time
         connection.last_used = Instant::now();
 
         let mut state = self.state.lock().await;
+        self.release_slot(&mut state, &connection);
 
-        // Decrease active count
-        if let Some(count) = state.active_counts.get_mut(&key) {
-            *count = count.saturating_sub(1);
+        if !self.config.enable_keepalive || state.closed {
+            // Drop the connection if keep-alive is disabled
+            return;
         }
 
         // Add to idle pool
@@ -151,17 +334,187 @@ impl ConnectionPool {
         idle.push(connection);
     }
 
+    /// Close a connection that can't be reused, releasing its slot
+    async fn discard_connection(&self, connection: Http1Connection) {
+        let mut state = self.state.lock().await;
+        self.release_slot(&mut state, &connection);
+    }
+
+    /// Close a connection that can't be reused without waiting for the lock
+    ///
+    /// Hands the connection back if the pool state is locked elsewhere.
+    fn try_discard_connection(&self, connection: Http1Connection) -> Result<(), Http1Connection> {
+        match self.state.try_lock() {
+            Ok(mut state) => {
+                self.release_slot(&mut state, &connection);
+                Ok(())
+            }
+            Err(_) => Err(connection),
+        }
+    }
+
+    /// Decrease the active count for `connection`'s host and wake `shutdown`
+    fn release_slot(&self, state: &mut PoolState, connection: &Http1Connection) {
+        let key = (connection.host.clone(), connection.port);
+        if let Some(count) = state.active_counts.get_mut(&key) {
+            *count = count.saturating_sub(1);
+        }
+        self.released.notify_waiters();
+    }
+
+    /// Stop handing out connections and drain the pool
+    ///
+    /// Marks the pool closed, so [`get_connection`](Self::get_connection)
+    /// and [`preconnect`](Self::preconnect) fail from now on, and closes all
+    /// idle connections. Then waits up to `timeout` for connections in use by
+    /// in-flight requests to be returned; those are closed rather than pooled.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - Every active connection was returned
+    /// * `Err(NetworkError::Timeout)` - Connections were still in use after `timeout`
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), NetworkError> {
+        {
+            let mut state = self.state.lock().await;
+            state.closed = true;
+            state.idle_connections.clear();
+        }
+
+        let drained = async {
+            loop {
+                // Registered before checking so a release in between isn't missed
+                let released = self.released.notified();
+                if self.state.lock().await.active_total() == 0 {
+                    return;
+                }
+                released.await;
+            }
+        };
+        tokio::time::timeout(timeout, drained)
+            .await
+            .map_err(|_| NetworkError::Timeout(timeout))
+    }
+
+    /// Whether [`shutdown`](Self::shutdown) has been called
+    pub async fn is_shut_down(&self) -> bool {
+        self.state.lock().await.closed
+    }
+
+    /// Open a TCP connection to the specified host and port
+    ///
+    /// The host is resolved to all of its addresses, and IPv6/IPv4 attempts
+    /// are raced Happy Eyeballs style (RFC 8305), staggered by
+    /// [`CONNECTION_ATTEMPT_DELAY`]. The `tcp_nodelay` and `tcp_keepalive`
+    /// socket options from the pool configuration are applied before the
+    /// stream is returned.
+    pub async fn connect(&self, host: &str, port: u16) -> Result<TcpStream, NetworkError> {
+        let addresses = self.resolve(host, port).await?;
+        let stream = happy_eyeballs::connect(addresses, port, CONNECTION_ATTEMPT_DELAY)
+            .await
+            .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+
+        stream
+            .set_nodelay(self.config.tcp_nodelay)
+            .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+
+        if let Some(idle) = self.config.tcp_keepalive {
+            let keepalive = TcpKeepalive::new().with_time(idle);
+            SockRef::from(&stream)
+                .set_tcp_keepalive(&keepalive)
+                .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+        }
+
+        Ok(stream)
+    }
+
+    /// Resolve a host (or IP literal, optionally bracketed) to its addresses
+    async fn resolve(&self, host: &str, port: u16) -> Result<Vec<IpAddr>, NetworkError> {
+        let literal = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = literal.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+
+        let addresses = match &self.resolver {
+            Some(resolver) => resolver.resolve(host.to_string()).await,
+            None => tokio::net::lookup_host((host, port))
+                .await
+                .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+                .map_err(|e| NetworkError::DnsError(e.to_string())),
+        }?;
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(host, addresses = ?addresses, "dns resolved");
+        Ok(addresses)
+    }
+
+    /// Open a connection to `host:port` and park it in the idle pool
+    ///
+    /// Used to warm up a connection ahead of a request. Nothing is opened if
+    /// an idle connection to the host is already available, or if the host's
+    /// `max_connections_per_host` or the pool's `pool_size` limit is reached.
+    pub async fn preconnect(&self, host: &str, port: u16) -> Result<(), NetworkError> {
+        let key = (host.to_string(), port);
+        if self.is_shut_down().await {
+            return Err(pool_closed());
+        }
+        if !self.has_room_for_idle(&key).await {
+            return Ok(());
+        }
+
+        let connection = self.open_connection(host, port).await?;
+
+        // Limits are checked again since requests may have connected meanwhile
+        if self.has_room_for_idle(&key).await {
+            let mut state = self.state.lock().await;
+            state.idle_connections.entry(key).or_default().push(connection);
+        }
+        Ok(())
+    }
+
+    /// Whether a preconnected connection to `key` is useful and fits the limits
+    async fn has_room_for_idle(&self, key: &PoolKey) -> bool {
+        let state = self.state.lock().await;
+        if state.closed {
+            return false;
+        }
+        let idle = state.idle_connections.get(key).map_or(&[][..], Vec::as_slice);
+        if idle.iter().any(|conn| !conn.sender.is_closed()) {
+            return false;
+        }
+
+        let for_host = idle.len() + state.active_counts.get(key).copied().unwrap_or(0);
+        let total =
+            state.idle_connections.values().map(Vec::len).sum::<usize>() + state.active_total();
+        for_host < self.config.max_connections_per_host && total < self.config.pool_size
+    }
+
     /// Create a new HTTP/1.1 connection
     async fn create_new_connection(
         &self,
         host: &str,
         port: u16,
     ) -> Result<Http1Connection, NetworkError> {
-        // Connect TCP stream
-        let addr = format!("{}:{}", host, port);
-        let stream = TcpStream::connect(&addr)
-            .await
-            .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+        let connection = self.open_connection(host, port).await?;
+
+        // Update active count, unless the pool was shut down while connecting
+        let key = (host.to_string(), port);
+        {
+            let mut state = self.state.lock().await;
+            if state.closed {
+                return Err(pool_closed());
+            }
+            let count = state.active_counts.entry(key.clone()).or_insert(0);
+            *count += 1;
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(host, port, "connection created");
+        Ok(connection)
+    }
+
+    /// Connect and perform the HTTP/1.1 handshake, without tracking the connection
+    async fn open_connection(&self, host: &str, port: u16) -> Result<Http1Connection, NetworkError> {
+        let stream = self.connect(host, port).await?;
 
         let io = TokioIo::new(stream);
 
@@ -177,13 +530,7 @@ impl ConnectionPool {
             }
         });
 
-        // Update active count
-        let key = (host.to_string(), port);
-        {
-            let mut state = self.state.lock().await;
-            let count = state.active_counts.entry(key.clone()).or_insert(0);
-            *count += 1;
-        }
+        self.created_count.fetch_add(1, Ordering::Relaxed);
 
         Ok(Http1Connection {
             sender,
@@ -194,6 +541,129 @@ impl ConnectionPool {
     }
 }
 
+impl Drop for ConnectionPool {
+    fn drop(&mut self) {
+        let reaper = self.reaper.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Some(handle) = reaper.take() {
+            handle.abort();
+        }
+    }
+}
+
+/// A connection checked out of the pool by [`Http1Client`]
+///
+/// Dropping it closes the connection and gives its slot back to the pool, so
+/// the pool's active count stays right when a request fails part way or its
+/// future is dropped, as on abort. Use [`PooledConnection::release`] to hand
+/// a connection back for reuse instead.
+struct PooledConnection {
+    connection: Option<Http1Connection>,
+    pool: Arc<ConnectionPool>,
+}
+
+impl PooledConnection {
+    fn new(connection: Http1Connection, pool: Arc<ConnectionPool>) -> Self {
+        Self {
+            connection: Some(connection),
+            pool,
+        }
+    }
+
+    fn sender(&mut self) -> &mut http1::SendRequest<RequestBody> {
+        let connection = self
+            .connection
+            .as_mut()
+            .expect("connection is only taken when released");
+        &mut connection.sender
+    }
+
+    /// Return the connection to the pool for reuse, or close it if it can't
+    /// be reused
+    async fn release(mut self, reusable: bool) {
+        let Some(connection) = self.connection.take() else {
+            return;
+        };
+        if reusable {
+            self.pool.return_connection(connection).await;
+        } else {
+            self.pool.discard_connection(connection).await;
+        }
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        let Some(connection) = self.connection.take() else {
+            return;
+        };
+        // Released right away unless the pool is busy, so the slot is free
+        // by the time the failed request returns
+        let Err(connection) = self.pool.try_discard_connection(connection) else {
+            return;
+        };
+        let pool = Arc::clone(&self.pool);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                pool.discard_connection(connection).await;
+            });
+        }
+    }
+}
+
+/// Response body streamed by [`Http1Client::stream_response`]
+///
+/// Holds the connection until the body ends, then hands it back to the pool.
+struct Http1BodyStream {
+    body: Incoming,
+    connection: Option<PooledConnection>,
+    continue_handle: Option<ContinueHandle>,
+}
+
+impl Http1BodyStream {
+    /// Give the connection back to the pool, or drop it if it can't be reused
+    fn release(&mut self, reusable: bool) {
+        let Some(connection) = self.connection.take() else {
+            return;
+        };
+        let reusable = reusable
+            && self
+                .continue_handle
+                .as_ref()
+                .map_or(true, ContinueHandle::body_sent);
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(connection.release(reusable));
+        }
+    }
+}
+
+impl Stream for Http1BodyStream {
+    type Item = Result<Bytes, network_types::NetworkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match ready!(Pin::new(&mut self.body).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    // Trailers aren't exposed on a streamed body
+                    if let Ok(data) = frame.into_data() {
+                        return Poll::Ready(Some(Ok(data)));
+                    }
+                }
+                Some(Err(e)) => {
+                    self.release(false);
+                    return Poll::Ready(Some(Err(network_types::NetworkError::new(
+                        NetworkErrorKind::InvalidResponse,
+                        format!("Failed to read body: {}", e),
+                    ))));
+                }
+                None => {
+                    self.release(true);
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
 /// HTTP/1.1 client with connection pooling
 ///
 /// Provides high-level HTTP/1.1 request functionality with automatic connection management.
@@ -205,56 +675,150 @@ pub struct Http1Client {
 
 impl Http1Client {
     /// Create a new HTTP/1.1 client with the given configuration
+    ///
+    /// When called inside a Tokio runtime, the pool's idle-connection reaper
+    /// is started, running at half the idle timeout.
     pub fn new(config: Http1Config) -> Self {
-        let pool = Arc::new(ConnectionPool::new(config.clone()));
+        Self::from_pool(ConnectionPool::new(config.clone()), config)
+    }
+
+    /// Create a new HTTP/1.1 client that resolves host names with `resolver`
+    pub fn with_resolver(config: Http1Config, resolver: Arc<dyn DnsResolver>) -> Self {
+        Self::from_pool(
+            ConnectionPool::with_resolver(config.clone(), resolver),
+            config,
+        )
+    }
+
+    /// Wrap a pool, starting its reaper when inside a Tokio runtime
+    fn from_pool(pool: ConnectionPool, config: Http1Config) -> Self {
+        let pool = Arc::new(pool);
+        if tokio::runtime::Handle::try_current().is_ok() {
+            pool.start_reaper(config.idle_timeout / 2);
+        }
         Self { pool, config }
     }
 
-    /// Fetch a resource and return the complete response
+    /// Warm up a pooled connection to the origin of `url`
     ///
-    /// This method performs a complete HTTP request and returns the full response body.
-    pub async fn fetch(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
-        let start_time = Instant::now();
-
-        // Parse URL components
-        let host = request
-            .url
+    /// See [`ConnectionPool::preconnect`]; this is a no-op when the pool
+    /// already holds an idle connection to the origin or is at its limits.
+    pub async fn preconnect(&self, url: &url::Url) -> Result<(), NetworkError> {
+        let host = url
             .host_str()
-            .ok_or_else(|| NetworkError::InvalidUrl("Missing host in URL".to_string()))?
-            .to_string();
-
-        let port = request
-            .url
+            .ok_or_else(|| NetworkError::InvalidUrl("Missing host in URL".to_string()))?;
+        let port = url
             .port_or_known_default()
             .ok_or_else(|| NetworkError::InvalidUrl("Cannot determine port".to_string()))?;
+        self.pool.preconnect(host, port).await
+    }
 
-        // Get connection from pool
-        let mut conn = self.pool.get_connection(&host, port).await?;
+    /// Snapshot of the connection pool's usage
+    pub async fn pool_metrics(&self) -> PoolMetrics {
+        self.pool.metrics().await
+    }
 
-        // Build HTTP request
-        let http_request = self.build_hyper_request(&request)?;
+    /// Stop sending new requests and wait for in-flight ones to finish
+    ///
+    /// See [`ConnectionPool::shutdown`]: requests started afterwards fail
+    /// with `NetworkError::ConnectionFailed`.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), NetworkError> {
+        self.pool.shutdown(timeout).await
+    }
 
-        // Send request
-        let response = conn
-            .sender
-            .send_request(http_request)
-            .await
-            .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+    /// Fetch a resource and return the complete response
+    ///
+    /// This method performs a complete HTTP request and returns the full response body.
+    /// If the request carries an abort token, aborting it cancels the request and
+    /// returns `NetworkError::Aborted`.
+    ///
+    /// With the `tracing` feature, the request runs in an `http1.fetch` span
+    /// and reports its response status or error as events.
+    pub async fn fetch(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "http1.fetch",
+            method = ?request.method,
+            url = %request.url,
+            protocol = "http/1.1",
+        );
+
+        let fetch = async move {
+            let result = match request.signal.clone() {
+                Some(signal) => tokio::select! {
+                    biased;
+                    _ = signal.aborted() => Err(NetworkError::Aborted),
+                    result = self.send(request) => result,
+                },
+                None => self.send(request).await,
+            };
+
+            #[cfg(feature = "tracing")]
+            match &result {
+                Ok(response) => tracing::debug!(status = response.status, "response received"),
+                Err(error) => tracing::debug!(%error, "request failed"),
+            }
+            result
+        };
+
+        #[cfg(feature = "tracing")]
+        let fetch = tracing::Instrument::instrument(fetch, span);
+        fetch.await
+    }
+
+    /// Perform the request without observing its abort token
+    async fn send(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        let start_time = Instant::now();
+
+        let (conn, response, continue_handle) = self.send_head(&request).await?;
 
         let status = response.status();
         let headers = response.headers().clone();
 
-        // Collect response body
-        let body_bytes = response
-            .into_body()
-            .collect()
-            .await
-            .map_err(|e| NetworkError::Other(e.to_string()))?
-            .to_bytes()
-            .to_vec();
+        // Refuse up front if the declared length is already over the limit
+        let limit = self.config.max_response_body_bytes;
+        let declared_length = headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if let (Some(limit), Some(length)) = (limit, declared_length) {
+            if length > limit as u64 {
+                return Err(body_too_large());
+            }
+        }
+
+        // Collect response body frame by frame, along with any chunked trailers
+        let mut body = response.into_body();
+        let mut body_bytes = Vec::new();
+        let mut trailers: Option<http::HeaderMap> = None;
+        while let Some(frame) = body.frame().await {
+            let frame = frame.map_err(|e| NetworkError::Other(e.to_string()))?;
+            match frame.into_data() {
+                Ok(data) => {
+                    if limit.is_some_and(|limit| body_bytes.len() + data.len() > limit) {
+                        // Dropping the connection stops the transfer
+                        return Err(body_too_large());
+                    }
+                    body_bytes.extend_from_slice(&data);
+                }
+                Err(frame) => {
+                    if let Ok(frame_trailers) = frame.into_trailers() {
+                        trailers
+                            .get_or_insert_with(Default::default)
+                            .extend(frame_trailers);
+                    }
+                }
+            }
+        }
 
-        // Return connection to pool
-        self.pool.return_connection(conn).await;
+        // A final response that arrived before the body went out leaves the
+        // request unfinished, so the connection can't be reused
+        let reusable = continue_handle.map_or(true, |handle| handle.body_sent());
+        conn.release(reusable).await;
+
+        if status == http::StatusCode::PARTIAL_CONTENT {
+            check_partial_body(&headers, body_bytes.len())?;
+        }
 
         // Build timing information
         let elapsed = start_time.elapsed();
@@ -274,6 +838,7 @@ impl Http1Client {
             transfer_size: body_bytes.len() as u64,
             encoded_body_size: body_bytes.len() as u64,
             decoded_body_size: body_bytes.len() as u64,
+            server_timing: Vec::new(),
         };
 
         Ok(NetworkResponse {
@@ -285,69 +850,162 @@ impl Http1Client {
             redirected: false,
             type_: ResponseType::Basic,
             timing,
+            trailers,
+            source: ResponseSource::Network,
         })
     }
 
-    /// Stream a response without collecting the full body
+    /// Send the request and wait for the response head
     ///
-    /// This method allows streaming response data as it arrives.
-    /// Currently not implemented - returns an error.
+    /// Returns the connection, which stays busy until the body has been read,
+    /// along with the handle of a body held back for `100 Continue`. The
+    /// connection is closed if the request fails or is dropped before then.
+    async fn send_head(
+        &self,
+        request: &NetworkRequest,
+    ) -> Result<
+        (
+            PooledConnection,
+            hyper::Response<Incoming>,
+            Option<ContinueHandle>,
+        ),
+        NetworkError,
+    > {
+        // Parse URL components
+        let host = request
+            .url
+            .host_str()
+            .ok_or_else(|| NetworkError::InvalidUrl("Missing host in URL".to_string()))?
+            .to_string();
+
+        let port = request
+            .url
+            .port_or_known_default()
+            .ok_or_else(|| NetworkError::InvalidUrl("Cannot determine port".to_string()))?;
+
+        // Get connection from pool
+        let connection = self.pool.get_connection(&host, port).await?;
+        let mut conn = PooledConnection::new(connection, Arc::clone(&self.pool));
+
+        // Build HTTP request
+        let mut http_request = self.build_hyper_request(request)?;
+
+        // Hold a non-empty body back until the server asks for it
+        let continue_handle = (self.config.use_expect_continue
+            && !http_request.body().is_end_stream())
+        .then(|| expect_continue(&mut http_request));
+
+        // Send request
+        let response = conn
+            .sender()
+            .send_request(http_request)
+            .await
+            .map_err(|e| NetworkError::ConnectionFailed(e.to_string()))?;
+
+        Ok((conn, response, continue_handle))
+    }
+
+    /// Send a request and return as soon as the response headers arrive
+    ///
+    /// The response body is returned as `ResponseBody::Stream` and yields data
+    /// as it is received, without the `max_response_body_bytes` limit. The
+    /// connection goes back to the pool once the body has been read to the
+    /// end; dropping the stream early closes it.
     pub async fn stream_response(
         &self,
-        _request: NetworkRequest,
+        request: NetworkRequest,
     ) -> Result<NetworkResponse, NetworkError> {
-        Err(NetworkError::Other(
-            "Streaming responses not yet implemented".to_string(),
-        ))
+        let (conn, response, continue_handle) = self.send_head(&request).await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = Http1BodyStream {
+            body: response.into_body(),
+            connection: Some(conn),
+            continue_handle,
+        };
+
+        Ok(NetworkResponse {
+            url: request.url.clone(),
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers,
+            body: ResponseBody::Stream(Box::new(body)),
+            redirected: false,
+            type_: ResponseType::Basic,
+            timing: ResourceTiming::default(),
+            trailers: None,
+            source: ResponseSource::Network,
+        })
     }
 
     /// Build a hyper HTTP request from a NetworkRequest
     fn build_hyper_request(
         &self,
         request: &NetworkRequest,
-    ) -> Result<hyper::Request<String>, NetworkError> {
+    ) -> Result<hyper::Request<RequestBody>, NetworkError> {
         // Convert HttpMethod to hyper::Method
-        let method = match request.method {
-            HttpMethod::Get => hyper::Method::GET,
-            HttpMethod::Post => hyper::Method::POST,
-            HttpMethod::Put => hyper::Method::PUT,
-            HttpMethod::Delete => hyper::Method::DELETE,
-            HttpMethod::Head => hyper::Method::HEAD,
-            HttpMethod::Options => hyper::Method::OPTIONS,
-            HttpMethod::Patch => hyper::Method::PATCH,
-            HttpMethod::Trace => hyper::Method::TRACE,
-            HttpMethod::Connect => hyper::Method::CONNECT,
-        };
-
-        // Build request with body as String
-        let body_string = if let Some(body) = &request.body {
-            match body {
-                network_types::RequestBody::Bytes(bytes) => String::from_utf8(bytes.clone())
-                    .map_err(|e| {
-                        NetworkError::Other(format!("Invalid UTF-8 in request body: {}", e))
-                    })?,
-                network_types::RequestBody::Text(text) => text.clone(),
-                network_types::RequestBody::FormData(_) => {
-                    return Err(NetworkError::Other(
-                        "FormData not yet implemented".to_string(),
-                    ));
-                }
-                network_types::RequestBody::Stream(_) => {
-                    return Err(NetworkError::Other(
-                        "Streaming request bodies not yet implemented".to_string(),
-                    ));
-                }
+        let method = hyper::Method::from(request.method);
+
+        // Build request body bytes
+        let body_bytes = match &request.body {
+            Some(network_types::RequestBody::Bytes(bytes)) => bytes.clone(),
+            Some(network_types::RequestBody::Text(text)) => text.clone().into_bytes(),
+            Some(network_types::RequestBody::FormData(_)) => {
+                return Err(NetworkError::Other(
+                    "FormData not yet implemented".to_string(),
+                ));
             }
-        } else {
-            String::new()
+            Some(network_types::RequestBody::Stream(_)) => {
+                return Err(NetworkError::Other(
+                    "Streaming request bodies not yet implemented".to_string(),
+                ));
+            }
+            None => Vec::new(),
         };
 
-        let req = hyper::Request::builder()
+        let mut builder = hyper::Request::builder()
             .method(method)
-            .uri(request.url.as_str())
-            .body(body_string)
+            .uri(request.url.as_str());
+
+        // Forward caller-supplied headers
+        for (name, value) in request.headers.iter() {
+            builder = builder.header(name, value);
+        }
+
+        let req = builder
+            .body(RequestBody::new(body_bytes))
             .map_err(|e| NetworkError::Other(e.to_string()))?;
 
         Ok(req)
     }
 }
+
+/// Error for a connection requested from a pool that has been shut down
+fn pool_closed() -> NetworkError {
+    NetworkError::ConnectionFailed("connection pool is shut down".to_string())
+}
+
+/// Check that a `206` body is exactly as long as its `Content-Range` says
+///
+/// A partial response without a byte `Content-Range`, such as a
+/// `multipart/byteranges` one, isn't checked.
+fn check_partial_body(headers: &http::HeaderMap, body_len: usize) -> Result<(), NetworkError> {
+    let range = headers
+        .get(http::header::CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(ContentRange::parse);
+    match range {
+        Some(range) if range.len() != body_len as u64 => Err(NetworkError::ProtocolError(format!(
+            "partial response body is {} bytes but its Content-Range covers {}",
+            body_len,
+            range.len()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Error for a response body over `max_response_body_bytes`
+fn body_too_large() -> NetworkError {
+    NetworkError::Other("response body too large".to_string())
+}
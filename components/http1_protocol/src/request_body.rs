@@ -0,0 +1,151 @@
+//! Request bodies, optionally held back until the server sends `100 Continue`
+
+use bytes::Bytes;
+use http::StatusCode;
+use hyper::body::{Body, Frame, SizeHint};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// How long to wait for `100 Continue` before sending the body anyway
+///
+/// Servers that don't implement `Expect` never send the interim response
+/// (RFC 9110 section 10.1.1).
+pub(crate) const EXPECT_CONTINUE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// State of a body waiting on `100 Continue`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Gate {
+    Waiting,
+    Proceed,
+    Abort,
+}
+
+/// Body of a request sent over an HTTP/1.1 connection
+pub(crate) struct RequestBody {
+    data: Option<Bytes>,
+    len: u64,
+    /// Resolves to whether the held-back data may be sent
+    gate: Option<Pin<Box<dyn Future<Output = bool> + Send>>>,
+    sent: Arc<AtomicBool>,
+}
+
+impl RequestBody {
+    /// A body sent as soon as the request head is written
+    pub(crate) fn new(data: impl Into<Bytes>) -> Self {
+        let data: Bytes = data.into();
+        Self {
+            len: data.len() as u64,
+            data: (!data.is_empty()).then_some(data),
+            gate: None,
+            sent: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Body for RequestBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if let Some(gate) = this.gate.as_mut() {
+            let proceed = ready!(gate.as_mut().poll(cx));
+            this.gate = None;
+            if !proceed {
+                this.data = None;
+                return Poll::Ready(Some(Err(std::io::Error::other(
+                    "request body withheld after final response",
+                ))));
+            }
+        }
+
+        Poll::Ready(this.data.take().map(|data| {
+            this.sent.store(true, Ordering::SeqCst);
+            Ok(Frame::data(data))
+        }))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.data.is_none() && self.gate.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.len)
+    }
+}
+
+/// Tracks a body held back by [`expect_continue`]
+///
+/// Dropping the handle before the body went out withholds it for good, so a
+/// connection abandoned after a rejection never sends it.
+pub(crate) struct ContinueHandle {
+    gate: Arc<watch::Sender<Gate>>,
+    sent: Arc<AtomicBool>,
+}
+
+impl ContinueHandle {
+    /// Whether the body has been handed to the connection
+    pub(crate) fn body_sent(&self) -> bool {
+        self.sent.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for ContinueHandle {
+    fn drop(&mut self) {
+        self.gate.send_if_modified(|gate| settle(gate, Gate::Abort));
+    }
+}
+
+/// Send `Expect: 100-continue` and hold the body until the server agrees
+///
+/// The body goes out once the server answers `100 Continue`, or after
+/// [`EXPECT_CONTINUE_TIMEOUT`] without any answer.
+pub(crate) fn expect_continue(request: &mut hyper::Request<RequestBody>) -> ContinueHandle {
+    let (sender, mut receiver) = watch::channel(Gate::Waiting);
+    let sender = Arc::new(sender);
+
+    request.headers_mut().insert(
+        http::header::EXPECT,
+        http::HeaderValue::from_static("100-continue"),
+    );
+
+    let on_continue = Arc::clone(&sender);
+    hyper::ext::on_informational(request, move |response| {
+        if response.status() == StatusCode::CONTINUE {
+            on_continue.send_if_modified(|gate| settle(gate, Gate::Proceed));
+        }
+    });
+
+    let body = request.body_mut();
+    body.gate = Some(Box::pin(async move {
+        let decided = receiver.wait_for(|gate| *gate != Gate::Waiting);
+        match tokio::time::timeout(EXPECT_CONTINUE_TIMEOUT, decided).await {
+            Ok(Ok(gate)) => *gate == Gate::Proceed,
+            Ok(Err(_)) => false,
+            Err(_) => true,
+        }
+    }));
+
+    ContinueHandle {
+        gate: sender,
+        sent: Arc::clone(&body.sent),
+    }
+}
+
+/// Move a waiting gate to `outcome`; later updates are ignored
+fn settle(gate: &mut Gate, outcome: Gate) -> bool {
+    if *gate == Gate::Waiting {
+        *gate = outcome;
+        true
+    } else {
+        false
+    }
+}
@@ -1 +1,12 @@
 // Integration tests for http1_protocol
+
+mod test_body_limit;
+mod test_event_source;
+mod test_expect_continue;
+mod test_happy_eyeballs;
+mod test_pool_metrics;
+mod test_pool_reaper;
+mod test_pool_shutdown;
+mod test_range;
+mod test_socket_options;
+mod test_trailers;
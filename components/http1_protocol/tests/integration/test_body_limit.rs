@@ -0,0 +1,166 @@
+//! Response body size limit
+
+use http1_protocol::{Http1Client, Http1Config};
+use network_errors::NetworkError;
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority, ResponseBody,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Chunk of body data the streaming origin sends repeatedly
+const CHUNK: usize = 1024;
+
+/// Start a loopback origin that streams 1 KiB chunks until the client hangs up
+///
+/// Returns the port and a counter of body bytes written.
+async fn start_endless_origin() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let written = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&written);
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await;
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut chunk = format!("{:x}\r\n", CHUNK).into_bytes();
+        chunk.extend_from_slice(&[b'x'; CHUNK]);
+        chunk.extend_from_slice(b"\r\n");
+        // Stop after 64 MiB in case the client never gives up
+        for _ in 0..64 * 1024 {
+            if stream.write_all(&chunk).await.is_err() {
+                break;
+            }
+            counter.fetch_add(CHUNK, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+        }
+    });
+    (port, written)
+}
+
+/// Start a loopback origin that answers one request with `response`
+async fn start_origin(response: &'static [u8]) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await;
+        let _ = stream.write_all(response).await;
+        let _ = stream.read(&mut buf).await;
+    });
+    port
+}
+
+fn request(port: u16) -> NetworkRequest {
+    NetworkRequest {
+        url: Url::parse(&format!("http://127.0.0.1:{}/", port)).unwrap(),
+        method: HttpMethod::Get,
+        headers: http::HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrerWhenDowngrade,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn limited_client(limit: usize) -> Http1Client {
+    Http1Client::new(Http1Config {
+        max_response_body_bytes: Some(limit),
+        ..Http1Config::default()
+    })
+}
+
+#[tokio::test]
+async fn test_endless_chunked_body_is_cut_off() {
+    let (port, written) = start_endless_origin().await;
+    let client = limited_client(64 * 1024);
+
+    let result = tokio::time::timeout(Duration::from_secs(10), client.fetch(request(port)))
+        .await
+        .expect("fetch should stop at the limit rather than read the whole stream");
+
+    match result {
+        Err(NetworkError::Other(message)) => assert_eq!(message, "response body too large"),
+        other => panic!(
+            "Expected body too large error, got {:?}",
+            other.map(|r| r.status)
+        ),
+    }
+    // The origin gets nowhere near its 64 MiB before the client hangs up
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(written.load(Ordering::SeqCst) < 16 * 1024 * 1024);
+    // The dropped connection no longer counts as in use
+    assert_eq!(client.pool_metrics().await.active_total, 0);
+}
+
+#[tokio::test]
+async fn test_declared_length_over_limit_fails_early() {
+    let port = start_origin(b"HTTP/1.1 200 OK\r\nContent-Length: 1000000\r\n\r\nxxxx").await;
+    let client = limited_client(1024);
+
+    let result = client.fetch(request(port)).await;
+    assert!(matches!(result, Err(NetworkError::Other(ref m)) if m == "response body too large"));
+    assert_eq!(client.pool_metrics().await.active_total, 0);
+}
+
+#[tokio::test]
+async fn test_body_cut_short_releases_connection() {
+    // The origin closes the connection 4 bytes into a 100 byte body
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await;
+        let _ = stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nxxxx")
+            .await;
+    });
+    let client = limited_client(1024);
+
+    let result = client.fetch(request(port)).await;
+
+    assert!(result.is_err());
+    let metrics = client.pool_metrics().await;
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 0);
+}
+
+#[tokio::test]
+async fn test_body_within_limit_is_returned() {
+    let port = start_origin(
+        b"HTTP/1.1 200 OK\r\n\
+          Transfer-Encoding: chunked\r\n\
+          \r\n\
+          5\r\nhello\r\n\
+          0\r\n\
+          \r\n",
+    )
+    .await;
+    let client = limited_client(5);
+
+    let response = client.fetch(request(port)).await.unwrap();
+    match response.body {
+        ResponseBody::Bytes(ref bytes) => assert_eq!(bytes, b"hello"),
+        _ => panic!("Expected a buffered body"),
+    }
+}
@@ -0,0 +1,122 @@
+//! Server-Sent Events over HTTP/1.1
+
+use futures::StreamExt;
+use http1_protocol::{EventSource, Http1Client, Http1Config, ReadyState};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use url::Url;
+
+const EVENT_STREAM_HEAD: &str =
+    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n";
+
+/// Start a loopback origin answering one connection per entry of `responses`
+///
+/// Each connection's request head is sent on the returned channel, and the
+/// connection is closed after its response.
+async fn start_origin(responses: Vec<String>) -> (Url, mpsc::UnboundedReceiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = Url::parse(&format!("http://{}/events", listener.local_addr().unwrap())).unwrap();
+    let (requests, received) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut head = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !head.ends_with(b"\r\n\r\n") {
+                let read = stream.read(&mut buf).await.unwrap();
+                if read == 0 {
+                    break;
+                }
+                head.extend_from_slice(&buf[..read]);
+            }
+            let _ = requests.send(String::from_utf8_lossy(&head).to_lowercase());
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    (url, received)
+}
+
+fn client() -> Arc<Http1Client> {
+    Arc::new(Http1Client::new(Http1Config::default()))
+}
+
+#[tokio::test]
+async fn test_event_source_streams_events() {
+    let (url, mut requests) = start_origin(vec![format!(
+        "{}: hello\n\ndata: one\n\nevent: tick\ndata: two\n\nid: 7\ndata: three\n\n",
+        EVENT_STREAM_HEAD
+    )])
+    .await;
+
+    let events: Vec<_> = EventSource::new(client(), url)
+        .into_stream()
+        .take(3)
+        .map(Result::unwrap)
+        .collect()
+        .await;
+
+    let summary: Vec<_> = events
+        .iter()
+        .map(|event| (event.event.as_str(), event.data.as_str(), event.id.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("message", "one", ""),
+            ("tick", "two", ""),
+            ("message", "three", "7")
+        ]
+    );
+
+    let request = requests.recv().await.unwrap();
+    assert!(request.contains("accept: text/event-stream"));
+    assert!(!request.contains("last-event-id"));
+}
+
+#[tokio::test]
+async fn test_event_source_reconnects_with_last_event_id() {
+    let (url, mut requests) = start_origin(vec![
+        format!(
+            "{}retry: 200\nid: first-id\ndata: before\n\ndata: cut off",
+            EVENT_STREAM_HEAD
+        ),
+        format!("{}data: after\n\n", EVENT_STREAM_HEAD),
+    ])
+    .await;
+
+    let mut source = EventSource::new(client(), url);
+    let first = source.next_event().await.unwrap().unwrap();
+    assert_eq!(first.data, "before");
+    assert_eq!(first.id, "first-id");
+    assert_eq!(source.ready_state(), ReadyState::Open);
+
+    // The server drops the connection mid-event; the source waits out its retry time
+    let disconnected = Instant::now();
+    let second = source.next_event().await.unwrap().unwrap();
+    assert!(disconnected.elapsed() >= Duration::from_millis(200));
+    assert_eq!(second.data, "after");
+    assert_eq!(second.id, "first-id");
+    assert_eq!(source.retry_interval(), Duration::from_millis(200));
+
+    let first_request = requests.recv().await.unwrap();
+    assert!(!first_request.contains("last-event-id"));
+    let second_request = requests.recv().await.unwrap();
+    assert!(second_request.contains("last-event-id: first-id\r\n"));
+}
+
+#[tokio::test]
+async fn test_event_source_fails_on_wrong_content_type() {
+    let (url, _requests) = start_origin(vec![
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nConnection: close\r\n\r\ndata: x\n\n"
+            .to_string(),
+    ])
+    .await;
+
+    let mut source = EventSource::new(client(), url);
+    assert!(source.next_event().await.unwrap().is_err());
+    assert_eq!(source.ready_state(), ReadyState::Closed);
+    assert!(source.next_event().await.is_none());
+}
@@ -0,0 +1,151 @@
+//! `Expect: 100-continue` request bodies
+
+use http1_protocol::{Http1Client, Http1Config};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestBody, RequestMode, RequestPriority, ResponseBody,
+};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use url::Url;
+
+/// Request body large enough that sending it early would be noticed
+const BODY_LEN: usize = 64 * 1024;
+
+/// Read a request head, returning it as text
+async fn read_head(stream: &mut TcpStream) -> String {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
+    while !head.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).await.unwrap();
+        head.push(byte[0]);
+    }
+    String::from_utf8(head).unwrap()
+}
+
+/// Start a loopback origin that handles one connection with `handler`
+async fn start_origin<F, Fut, T>(handler: F) -> (u16, JoinHandle<T>)
+where
+    F: FnOnce(TcpStream) -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = T> + Send,
+    T: Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        handler(stream).await
+    });
+    (port, handle)
+}
+
+fn upload(port: u16) -> NetworkRequest {
+    NetworkRequest {
+        url: Url::parse(&format!("http://127.0.0.1:{}/upload", port)).unwrap(),
+        method: HttpMethod::Post,
+        headers: http::HeaderMap::new(),
+        body: Some(RequestBody::Text("x".repeat(BODY_LEN))),
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrerWhenDowngrade,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn client() -> Http1Client {
+    Http1Client::new(Http1Config {
+        use_expect_continue: true,
+        ..Http1Config::default()
+    })
+}
+
+#[tokio::test]
+async fn test_rejected_request_never_sends_body() {
+    let (port, origin) = start_origin(|mut stream| async move {
+        let head = read_head(&mut stream).await;
+        assert!(head.to_ascii_lowercase().contains("expect: 100-continue"));
+        stream
+            .write_all(b"HTTP/1.1 401 Unauthorized\r\nContent-Length: 6\r\n\r\ndenied")
+            .await
+            .unwrap();
+
+        // Count whatever the client sends until it hangs up
+        let mut received = 0;
+        let mut buf = [0u8; 4096];
+        while let Ok(Ok(n)) =
+            tokio::time::timeout(Duration::from_secs(3), stream.read(&mut buf)).await
+        {
+            if n == 0 {
+                break;
+            }
+            received += n;
+        }
+        received
+    })
+    .await;
+
+    let response = client().fetch(upload(port)).await.unwrap();
+    assert_eq!(response.status, 401);
+    match response.body {
+        ResponseBody::Bytes(ref bytes) => assert_eq!(bytes, b"denied"),
+        _ => panic!("Expected a buffered body"),
+    }
+
+    assert_eq!(origin.await.unwrap(), 0, "body must not be sent");
+}
+
+#[tokio::test]
+async fn test_body_sent_after_continue() {
+    let (port, origin) = start_origin(|mut stream| async move {
+        let head = read_head(&mut stream).await;
+        assert!(head.to_ascii_lowercase().contains("expect: 100-continue"));
+        stream
+            .write_all(b"HTTP/1.1 100 Continue\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut body = vec![0u8; BODY_LEN];
+        stream.read_exact(&mut body).await.unwrap();
+        stream
+            .write_all(b"HTTP/1.1 201 Created\r\nContent-Length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        body
+    })
+    .await;
+
+    let response = client().fetch(upload(port)).await.unwrap();
+    assert_eq!(response.status, 201);
+    assert_eq!(origin.await.unwrap(), vec![b'x'; BODY_LEN]);
+}
+
+#[tokio::test]
+async fn test_body_sent_when_server_ignores_expect() {
+    let (port, origin) = start_origin(|mut stream| async move {
+        // A server without 100-continue support just waits for the body
+        read_head(&mut stream).await;
+        let mut body = vec![0u8; BODY_LEN];
+        stream.read_exact(&mut body).await.unwrap();
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+            .await
+            .unwrap();
+    })
+    .await;
+
+    let response = tokio::time::timeout(Duration::from_secs(10), client().fetch(upload(port)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.status, 200);
+    origin.await.unwrap();
+}
@@ -0,0 +1,129 @@
+//! Happy Eyeballs connection racing across resolved addresses
+
+use async_trait::async_trait;
+use dns_resolver::DnsResolver;
+use http1_protocol::{ConnectionPool, Http1Config, CONNECTION_ATTEMPT_DELAY};
+use network_errors::{NetworkError, NetworkResult};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+/// Address from the IPv6 discard-only prefix (RFC 6666); never answers
+const UNREACHABLE_V6: &str = "100::1";
+
+/// Resolver returning a fixed address list for every host
+struct StaticResolver {
+    addresses: Vec<IpAddr>,
+    lookups: AtomicUsize,
+}
+
+impl StaticResolver {
+    fn new(addresses: &[&str]) -> Arc<Self> {
+        Arc::new(Self {
+            addresses: addresses.iter().map(|a| a.parse().unwrap()).collect(),
+            lookups: AtomicUsize::new(0),
+        })
+    }
+}
+
+#[async_trait]
+impl DnsResolver for StaticResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        if self.addresses.is_empty() {
+            return Err(NetworkError::DnsError(format!(
+                "No addresses found for {}",
+                hostname
+            )));
+        }
+        Ok(self.addresses.clone())
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
+/// Start a loopback IPv4 listener that accepts and holds connections
+async fn start_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn test_ipv4_wins_when_ipv6_is_unreachable() {
+    let port = start_listener().await;
+    let resolver = StaticResolver::new(&[UNREACHABLE_V6, "127.0.0.1"]);
+    let pool = ConnectionPool::with_resolver(Http1Config::default(), resolver.clone());
+
+    let started = Instant::now();
+    let stream = pool.connect("dual-stack.test", port).await.unwrap();
+
+    assert_eq!(
+        stream.peer_addr().unwrap().ip(),
+        "127.0.0.1".parse::<IpAddr>().unwrap()
+    );
+    assert!(started.elapsed() < CONNECTION_ATTEMPT_DELAY + Duration::from_millis(500));
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_later_addresses_are_tried_after_earlier_ones_hang() {
+    let port = start_listener().await;
+    let resolver = StaticResolver::new(&[UNREACHABLE_V6, "100::2", "100::3", "127.0.0.1"]);
+    let pool = ConnectionPool::with_resolver(Http1Config::default(), resolver);
+
+    // Families are interleaved, so the IPv4 address is the second attempt
+    let started = Instant::now();
+    let stream = pool.connect("dual-stack.test", port).await.unwrap();
+
+    assert!(stream.peer_addr().unwrap().is_ipv4());
+    assert!(started.elapsed() < CONNECTION_ATTEMPT_DELAY * 2);
+}
+
+#[tokio::test]
+async fn test_ip_literal_skips_resolver() {
+    let port = start_listener().await;
+    let resolver = StaticResolver::new(&[]);
+    let pool = ConnectionPool::with_resolver(Http1Config::default(), resolver.clone());
+
+    pool.connect("127.0.0.1", port).await.unwrap();
+
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_resolution_failure_is_dns_error() {
+    let resolver = StaticResolver::new(&[]);
+    let pool = ConnectionPool::with_resolver(Http1Config::default(), resolver);
+
+    let result = pool.connect("nowhere.test", 80).await;
+
+    assert!(matches!(result, Err(NetworkError::DnsError(_))));
+}
+
+#[tokio::test]
+async fn test_all_attempts_failing_is_connection_failed() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+    let resolver = StaticResolver::new(&["127.0.0.1", "127.0.0.2"]);
+    let pool = ConnectionPool::with_resolver(Http1Config::default(), resolver);
+
+    let result = pool.connect("refused.test", port).await;
+
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+}
@@ -0,0 +1,236 @@
+//! Connection pool metrics snapshots
+
+use http1_protocol::{ConnectionPool, Http1Client, Http1Config, PoolMetrics};
+use network_types::{AbortToken, NetworkRequest};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Start a loopback listener that accepts and holds connections
+async fn start_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+    port
+}
+
+/// Start a loopback listener that closes each connection once a request arrives
+async fn start_hanging_up_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).await;
+        }
+    });
+    port
+}
+
+fn url(port: u16) -> Url {
+    Url::parse(&format!("http://127.0.0.1:{}/", port)).unwrap()
+}
+
+#[tokio::test]
+async fn test_new_pool_reports_zero_metrics() {
+    let pool = ConnectionPool::new(Http1Config::default());
+
+    assert_eq!(pool.metrics().await, PoolMetrics::default());
+}
+
+#[tokio::test]
+async fn test_metrics_track_creation_and_reuse() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config::default());
+
+    // First request opens a new connection
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.reuse_count, 0);
+    assert_eq!(metrics.active_total, 1);
+    assert_eq!(metrics.idle_total, 0);
+    assert_eq!(metrics.hosts, 1);
+
+    // Returning it makes it idle
+    pool.return_connection(conn).await;
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 1);
+
+    // The next request reuses it instead of connecting again
+    let reused = pool.get_connection("127.0.0.1", port).await.unwrap();
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.reuse_count, 1);
+    assert_eq!(metrics.active_total, 1);
+    assert_eq!(metrics.idle_total, 0);
+
+    // A concurrent request needs a second connection
+    let second = pool.get_connection("127.0.0.1", port).await.unwrap();
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 2);
+    assert_eq!(metrics.reuse_count, 1);
+    assert_eq!(metrics.active_total, 2);
+    assert_eq!(metrics.hosts, 1);
+
+    pool.return_connection(reused).await;
+    pool.return_connection(second).await;
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 2);
+}
+
+#[tokio::test]
+async fn test_metrics_count_hosts_separately() {
+    let first = start_listener().await;
+    let second = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config::default());
+
+    let a = pool.get_connection("127.0.0.1", first).await.unwrap();
+    let b = pool.get_connection("127.0.0.1", second).await.unwrap();
+    pool.return_connection(a).await;
+
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.hosts, 2);
+    assert_eq!(metrics.idle_total, 1);
+    assert_eq!(metrics.active_total, 1);
+
+    pool.return_connection(b).await;
+}
+
+#[tokio::test]
+async fn test_without_keepalive_connections_are_not_reused() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config {
+        enable_keepalive: false,
+        ..Http1Config::default()
+    });
+
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    pool.return_connection(conn).await;
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    pool.return_connection(conn).await;
+
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 2);
+    assert_eq!(metrics.reuse_count, 0);
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 0);
+}
+
+#[tokio::test]
+async fn test_preconnect_parks_an_idle_connection() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config::default());
+
+    pool.preconnect("127.0.0.1", port).await.unwrap();
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.idle_total, 1);
+    assert_eq!(metrics.active_total, 0);
+
+    // A second preconnect doesn't open another connection
+    pool.preconnect("127.0.0.1", port).await.unwrap();
+    assert_eq!(pool.metrics().await.created_count, 1);
+
+    // The next request takes the warmed connection
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.reuse_count, 1);
+    pool.return_connection(conn).await;
+}
+
+#[tokio::test]
+async fn test_preconnect_respects_per_host_limit() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config {
+        max_connections_per_host: 1,
+        ..Http1Config::default()
+    });
+
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    pool.preconnect("127.0.0.1", port).await.unwrap();
+
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.idle_total, 0);
+    pool.return_connection(conn).await;
+}
+
+#[tokio::test]
+async fn test_preconnect_respects_pool_size() {
+    let first = start_listener().await;
+    let second = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config {
+        pool_size: 1,
+        ..Http1Config::default()
+    });
+
+    pool.preconnect("127.0.0.1", first).await.unwrap();
+    pool.preconnect("127.0.0.1", second).await.unwrap();
+
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.hosts, 1);
+}
+
+#[tokio::test]
+async fn test_metrics_after_failed_and_aborted_requests() {
+    let failing = start_hanging_up_listener().await;
+    let hanging = start_listener().await;
+    let client = Http1Client::new(Http1Config::default());
+
+    let failed = client
+        .fetch(NetworkRequest::builder(url(failing)).build())
+        .await;
+    let aborted = client
+        .fetch(
+            NetworkRequest::builder(url(hanging))
+                .signal(AbortToken::timeout(Duration::from_millis(50)))
+                .build(),
+        )
+        .await;
+    assert!(failed.is_err());
+    assert!(aborted.is_err());
+
+    // Both connections were closed rather than left counted as in use
+    let metrics = client.pool_metrics().await;
+    assert_eq!(metrics.created_count, 2);
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 0);
+    assert_eq!(metrics.hosts, 0);
+}
+
+#[tokio::test]
+async fn test_preconnect_after_aborted_request() {
+    let port = start_listener().await;
+    let client = Http1Client::new(Http1Config {
+        max_connections_per_host: 1,
+        ..Http1Config::default()
+    });
+
+    // The aborted request must not keep holding the host's only slot
+    let aborted = client
+        .fetch(
+            NetworkRequest::builder(url(port))
+                .signal(AbortToken::timeout(Duration::from_millis(50)))
+                .build(),
+        )
+        .await;
+    assert!(aborted.is_err());
+
+    client.preconnect(&url(port)).await.unwrap();
+
+    let metrics = client.pool_metrics().await;
+    assert_eq!(metrics.created_count, 2);
+    assert_eq!(metrics.idle_total, 1);
+    assert_eq!(metrics.active_total, 0);
+}
@@ -0,0 +1,79 @@
+//! Background reaping of idle pooled connections
+
+use http1_protocol::{ConnectionPool, Http1Config};
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Start a listener that accepts connections and keeps them open
+async fn start_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+    port
+}
+
+fn config(idle_timeout: Duration) -> Http1Config {
+    Http1Config {
+        idle_timeout,
+        ..Http1Config::default()
+    }
+}
+
+/// Open a connection to `port` and hand it back to the pool as idle
+async fn park_idle_connection(pool: &ConnectionPool, port: u16) {
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    pool.return_connection(conn).await;
+}
+
+#[tokio::test]
+async fn test_idle_connection_to_unqueried_host_is_reaped() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(config(Duration::from_millis(100)));
+    pool.start_reaper(Duration::from_millis(20));
+
+    park_idle_connection(&pool, port).await;
+    assert_eq!(pool.idle_connection_count().await, 1);
+
+    // The host is never queried again; only the reaper can drop it
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(pool.idle_connection_count().await, 0);
+}
+
+#[tokio::test]
+async fn test_fresh_idle_connections_are_kept() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(config(Duration::from_secs(60)));
+    pool.start_reaper(Duration::from_millis(20));
+
+    park_idle_connection(&pool, port).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(pool.idle_connection_count().await, 1);
+}
+
+#[tokio::test]
+async fn test_without_reaper_idle_connections_linger() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(config(Duration::from_millis(50)));
+
+    park_idle_connection(&pool, port).await;
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    assert_eq!(pool.idle_connection_count().await, 1);
+}
+
+#[tokio::test]
+async fn test_dropping_pool_stops_reaper() {
+    let pool = ConnectionPool::new(config(Duration::from_millis(50)));
+    pool.start_reaper(Duration::from_millis(10));
+    pool.start_reaper(Duration::from_millis(10));
+    drop(pool);
+
+    // Give the aborted task a chance to run; nothing should panic
+    tokio::time::sleep(Duration::from_millis(50)).await;
+}
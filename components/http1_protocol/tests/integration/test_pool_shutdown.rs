@@ -0,0 +1,122 @@
+//! Draining the connection pool on shutdown
+
+use http1_protocol::{ConnectionPool, Http1Client, Http1Config};
+use network_errors::NetworkError;
+use network_types::{AbortToken, NetworkRequest};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Start a loopback listener that accepts and holds connections
+async fn start_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+    port
+}
+
+/// Start a loopback listener that closes each connection once a request arrives
+async fn start_hanging_up_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = [0u8; 4096];
+            let _ = stream.read(&mut buf).await;
+        }
+    });
+    port
+}
+
+fn url(port: u16) -> Url {
+    Url::parse(&format!("http://127.0.0.1:{}/", port)).unwrap()
+}
+
+#[tokio::test]
+async fn test_shutdown_closes_idle_connections_and_refuses_new_ones() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config::default());
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+    pool.return_connection(conn).await;
+    assert_eq!(pool.idle_connection_count().await, 1);
+
+    pool.shutdown(Duration::from_secs(1)).await.unwrap();
+
+    assert!(pool.is_shut_down().await);
+    assert_eq!(pool.idle_connection_count().await, 0);
+    let result = pool.get_connection("127.0.0.1", port).await;
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+    assert!(pool.preconnect("127.0.0.1", port).await.is_err());
+}
+
+#[tokio::test]
+async fn test_shutdown_waits_for_active_connections() {
+    let port = start_listener().await;
+    let pool = Arc::new(ConnectionPool::new(Http1Config::default()));
+    let conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+
+    let returner = {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            pool.return_connection(conn).await;
+        })
+    };
+    pool.shutdown(Duration::from_secs(5)).await.unwrap();
+
+    // The returned connection was closed rather than pooled
+    let metrics = pool.metrics().await;
+    assert_eq!(metrics.active_total, 0);
+    assert_eq!(metrics.idle_total, 0);
+    returner.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_times_out_on_connections_still_in_use() {
+    let port = start_listener().await;
+    let pool = ConnectionPool::new(Http1Config::default());
+    let _conn = pool.get_connection("127.0.0.1", port).await.unwrap();
+
+    let result = pool.shutdown(Duration::from_millis(50)).await;
+
+    assert!(matches!(result, Err(NetworkError::Timeout(_))));
+    assert!(pool.is_shut_down().await);
+}
+
+#[tokio::test]
+async fn test_shutdown_after_failed_request_returns_promptly() {
+    let port = start_hanging_up_listener().await;
+    let client = Http1Client::new(Http1Config::default());
+    assert!(client
+        .fetch(NetworkRequest::builder(url(port)).build())
+        .await
+        .is_err());
+
+    let started = Instant::now();
+    client.shutdown(Duration::from_secs(5)).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn test_shutdown_after_aborted_request_returns_promptly() {
+    let port = start_listener().await;
+    let client = Http1Client::new(Http1Config::default());
+    let aborted = NetworkRequest::builder(url(port))
+        .signal(AbortToken::timeout(Duration::from_millis(50)))
+        .build();
+    let result = client.fetch(aborted).await;
+    assert!(matches!(result, Err(NetworkError::Aborted)));
+
+    let started = Instant::now();
+    client.shutdown(Duration::from_secs(5)).await.unwrap();
+
+    assert!(started.elapsed() < Duration::from_secs(1));
+}
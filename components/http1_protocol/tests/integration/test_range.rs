@@ -0,0 +1,189 @@
+//! Byte range requests and `206 Partial Content` responses
+
+use http1_protocol::{Http1Client, Http1Config};
+use network_errors::NetworkError;
+use network_types::{ContentRange, NetworkRequest, ResponseBody};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use url::Url;
+
+/// Representation served by the ranged origin
+const DATA: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Start a loopback origin that serves `DATA` and honors single byte ranges
+///
+/// Connections are kept alive. Returns the port and a count of accepted
+/// connections.
+async fn start_ranged_origin() -> (u16, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&connections);
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            counter.fetch_add(1, Ordering::SeqCst);
+            tokio::spawn(serve_ranges(stream));
+        }
+    });
+    (port, connections)
+}
+
+async fn serve_ranges(mut stream: TcpStream) {
+    let mut buf = Vec::new();
+    loop {
+        let head_end = loop {
+            if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break end + 4;
+            }
+            let mut chunk = [0u8; 1024];
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buf.extend_from_slice(&chunk[..n]),
+            }
+        };
+        let head = String::from_utf8_lossy(&buf[..head_end]).to_ascii_lowercase();
+        buf.drain(..head_end);
+
+        let range = head
+            .lines()
+            .find_map(|line| line.strip_prefix("range: bytes="))
+            .and_then(|spec| spec.trim().split_once('-'))
+            .map(|(first, last)| {
+                let first: usize = first.parse().unwrap();
+                let last = last.parse().unwrap_or(DATA.len() - 1).min(DATA.len() - 1);
+                (first, last)
+            });
+
+        let mut response = match range {
+            Some((first, last)) => format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\nContent-Length: {}\r\n\r\n",
+                first,
+                last,
+                DATA.len(),
+                last - first + 1
+            )
+            .into_bytes(),
+            None => format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", DATA.len()).into_bytes(),
+        };
+        let (first, last) = range.unwrap_or((0, DATA.len() - 1));
+        response.extend_from_slice(&DATA[first..=last]);
+        if stream.write_all(&response).await.is_err() {
+            return;
+        }
+    }
+}
+
+fn url(port: u16) -> Url {
+    Url::parse(&format!("http://127.0.0.1:{}/data", port)).unwrap()
+}
+
+fn body_bytes(body: &ResponseBody) -> &[u8] {
+    match body {
+        ResponseBody::Bytes(bytes) => bytes,
+        other => panic!("Expected a buffered body, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_range_request_returns_partial_content() {
+    let (port, _) = start_ranged_origin().await;
+    let client = Http1Client::new(Http1Config::default());
+
+    let response = client
+        .fetch(
+            NetworkRequest::builder(url(port))
+                .byte_range(10, Some(19))
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 206);
+    assert_eq!(response.status_text, "Partial Content");
+    assert_eq!(body_bytes(&response.body), b"abcdefghij");
+    assert_eq!(
+        response.content_range(),
+        Some(ContentRange {
+            first: 10,
+            last: 19,
+            complete_length: Some(DATA.len() as u64),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_open_ended_range_returns_tail() {
+    let (port, _) = start_ranged_origin().await;
+    let client = Http1Client::new(Http1Config::default());
+
+    let response = client
+        .fetch(
+            NetworkRequest::builder(url(port))
+                .byte_range(31, None)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 206);
+    assert_eq!(body_bytes(&response.body), b"vwxyz");
+    let range = response.content_range().unwrap();
+    assert_eq!((range.first, range.last), (31, 35));
+}
+
+#[tokio::test]
+async fn test_partial_bodies_are_read_exactly_on_a_reused_connection() {
+    let (port, connections) = start_ranged_origin().await;
+    let client = Http1Client::new(Http1Config::default());
+
+    // Resuming a download: each request picks up where the last one stopped
+    let mut downloaded = Vec::new();
+    for first in (0..DATA.len() as u64).step_by(8) {
+        let response = client
+            .fetch(
+                NetworkRequest::builder(url(port))
+                    .byte_range(first, Some(first + 7))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status, 206);
+        downloaded.extend_from_slice(body_bytes(&response.body));
+    }
+
+    assert_eq!(downloaded, DATA);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_partial_body_shorter_than_content_range_is_an_error() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await;
+        let _ = stream
+            .write_all(
+                b"HTTP/1.1 206 Partial Content\r\n\
+                  Content-Range: bytes 0-9/36\r\n\
+                  Transfer-Encoding: chunked\r\n\r\n\
+                  4\r\n0123\r\n0\r\n\r\n",
+            )
+            .await;
+        let _ = stream.read(&mut buf).await;
+    });
+    let client = Http1Client::new(Http1Config::default());
+
+    let result = client
+        .fetch(
+            NetworkRequest::builder(url(port))
+                .byte_range(0, Some(9))
+                .build(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::ProtocolError(_))));
+}
@@ -0,0 +1,82 @@
+//! Socket options applied to pooled TCP connections
+
+use http1_protocol::{ConnectionPool, Http1Config};
+use socket2::SockRef;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// Connect to a loopback listener through a pool using `config`
+async fn connect_loopback(config: Http1Config) -> tokio::net::TcpStream {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let accept = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+    let stream = ConnectionPool::new(config)
+        .connect("127.0.0.1", port)
+        .await
+        .unwrap();
+    accept.await.unwrap();
+    stream
+}
+
+#[tokio::test]
+async fn test_default_config_enables_nodelay_and_keepalive() {
+    let stream = connect_loopback(Http1Config::default()).await;
+    let socket = SockRef::from(&stream);
+
+    assert!(stream.nodelay().unwrap());
+    assert!(socket.keepalive().unwrap());
+}
+
+#[tokio::test]
+async fn test_nodelay_can_be_disabled() {
+    let config = Http1Config {
+        tcp_nodelay: false,
+        ..Http1Config::default()
+    };
+    let stream = connect_loopback(config).await;
+
+    assert!(!stream.nodelay().unwrap());
+}
+
+#[tokio::test]
+async fn test_keepalive_disabled_when_unset() {
+    let config = Http1Config {
+        tcp_keepalive: None,
+        ..Http1Config::default()
+    };
+    let stream = connect_loopback(config).await;
+
+    assert!(!SockRef::from(&stream).keepalive().unwrap());
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[tokio::test]
+async fn test_keepalive_idle_time_is_applied() {
+    let config = Http1Config {
+        tcp_keepalive: Some(Duration::from_secs(42)),
+        ..Http1Config::default()
+    };
+    let stream = connect_loopback(config).await;
+
+    assert_eq!(
+        SockRef::from(&stream).keepalive_time().unwrap(),
+        Duration::from_secs(42)
+    );
+}
+
+#[tokio::test]
+async fn test_connect_failure_is_connection_failed() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let result = ConnectionPool::new(Http1Config::default())
+        .connect("127.0.0.1", port)
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(network_errors::NetworkError::ConnectionFailed(_))
+    ));
+}
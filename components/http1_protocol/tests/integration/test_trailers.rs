@@ -0,0 +1,82 @@
+//! Chunked response trailers
+
+use http1_protocol::{Http1Client, Http1Config};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority, ResponseBody,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use url::Url;
+
+/// Start a loopback origin that answers one request with `response`
+async fn start_origin(response: &'static [u8]) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = stream.read(&mut buf).await;
+        stream.write_all(response).await.unwrap();
+        // Hold the connection open until the client is done with it
+        let _ = stream.read(&mut buf).await;
+    });
+    port
+}
+
+fn request(port: u16) -> NetworkRequest {
+    NetworkRequest {
+        url: Url::parse(&format!("http://127.0.0.1:{}/", port)).unwrap(),
+        method: HttpMethod::Get,
+        headers: http::HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrerWhenDowngrade,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+#[tokio::test]
+async fn test_chunked_trailers_are_captured() {
+    let port = start_origin(
+        b"HTTP/1.1 200 OK\r\n\
+          Transfer-Encoding: chunked\r\n\
+          Trailer: grpc-status, x-checksum\r\n\
+          \r\n\
+          5\r\nhello\r\n\
+          0\r\n\
+          grpc-status: 0\r\n\
+          x-checksum: abc123\r\n\
+          \r\n",
+    )
+    .await;
+    let client = Http1Client::new(Http1Config::default());
+
+    let response = client.fetch(request(port)).await.unwrap();
+
+    match response.body {
+        ResponseBody::Bytes(ref bytes) => assert_eq!(bytes, b"hello"),
+        _ => panic!("Expected a buffered body"),
+    }
+    let trailers = response.trailers.expect("trailers should be captured");
+    assert_eq!(trailers.get("grpc-status").unwrap(), "0");
+    assert_eq!(trailers.get("x-checksum").unwrap(), "abc123");
+}
+
+#[tokio::test]
+async fn test_response_without_trailers_has_none() {
+    let port = start_origin(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\nok").await;
+    let client = Http1Client::new(Http1Config::default());
+
+    let response = client.fetch(request(port)).await.unwrap();
+
+    assert!(response.trailers.is_none());
+}
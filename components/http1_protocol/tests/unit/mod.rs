@@ -1,6 +1,7 @@
 // Unit tests for http1_protocol
 
 mod test_curl_behavior;
+mod test_event_stream;
 
 mod test_config {
     use std::time::Duration;
@@ -45,6 +46,10 @@ mod test_config {
             max_connections_per_host: 10,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.pool_size, 50);
@@ -62,6 +67,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.idle_timeout, Duration::from_secs(300));
@@ -79,6 +88,10 @@ mod test_config {
             max_connections_per_host: 4,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.max_connections_per_host, 4);
@@ -96,6 +109,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(config.enable_keepalive);
@@ -113,6 +130,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(!config.enable_keepalive);
@@ -130,6 +151,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(config.enable_pipelining);
@@ -147,6 +172,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(!config.enable_pipelining);
@@ -164,6 +193,10 @@ mod test_config {
             max_connections_per_host: 8,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         // Verify all fields are accessible
@@ -186,6 +219,10 @@ mod test_config {
             max_connections_per_host: 7,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let cloned = config.clone();
@@ -212,6 +249,10 @@ mod test_config {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let debug_str = format!("{:?}", config);
@@ -239,6 +280,10 @@ mod test_connection_pool {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let pool = ConnectionPool::new(config);
@@ -264,6 +309,7 @@ mod test_connection_pool {
                 || matches!(
                     result,
                     Err(network_errors::NetworkError::ConnectionFailed(_))
+                        | Err(network_errors::NetworkError::DnsError(_))
                 )
         );
     }
@@ -298,6 +344,10 @@ mod test_connection_pool {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let _pool = ConnectionPool::new(config);
@@ -319,6 +369,10 @@ mod test_connection_pool {
             max_connections_per_host: 2,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let _pool = ConnectionPool::new(config);
@@ -339,6 +393,10 @@ mod test_connection_pool {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let _pool = ConnectionPool::new(config);
@@ -359,6 +417,10 @@ mod test_connection_pool {
             max_connections_per_host: 2,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let _pool = ConnectionPool::new(config);
@@ -378,6 +440,10 @@ mod test_connection_pool {
             max_connections_per_host: 6,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let _pool = ConnectionPool::new(config);
@@ -421,6 +487,10 @@ mod test_http1_client {
             max_connections_per_host: 10,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let client = Http1Client::new(config);
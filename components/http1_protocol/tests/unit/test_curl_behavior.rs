@@ -93,6 +93,10 @@ mod curl_options {
             max_connections_per_host: 20,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         // Should accept any valid configuration
@@ -113,6 +117,10 @@ mod curl_options {
             max_connections_per_host: 6,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(!config.enable_keepalive);
@@ -129,6 +137,10 @@ mod curl_options {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let long_timeout = Http1Config {
@@ -137,6 +149,10 @@ mod curl_options {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(short_timeout.idle_timeout, Duration::from_secs(5));
@@ -153,6 +169,10 @@ mod curl_options {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(config_with_pipelining.enable_pipelining);
@@ -168,6 +188,10 @@ mod curl_options {
             max_connections_per_host: 1,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(limited_config.pool_size, 5);
@@ -203,6 +227,10 @@ mod protocol_spec {
             max_connections_per_host: 6,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(
@@ -233,6 +261,10 @@ mod protocol_spec {
             max_connections_per_host: 10,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(full_config.enable_keepalive);
@@ -245,6 +277,10 @@ mod protocol_spec {
             max_connections_per_host: 1,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(!minimal_config.enable_keepalive);
@@ -280,6 +316,10 @@ mod pool_behavior_spec {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         // Even with large pool, per-host limit should be reasonable
@@ -296,6 +336,10 @@ mod pool_behavior_spec {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         // Very short timeout for testing
@@ -311,6 +355,10 @@ mod pool_behavior_spec {
             max_connections_per_host: 6,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert!(!config.enable_keepalive);
@@ -334,6 +382,10 @@ mod config_traits {
             max_connections_per_host: 7,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let cloned = original.clone();
@@ -357,6 +409,10 @@ mod config_traits {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let debug_string = format!("{:?}", config);
@@ -400,6 +456,10 @@ mod edge_cases {
             max_connections_per_host: 1,
             enable_keepalive: false,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.pool_size, 1);
@@ -416,6 +476,10 @@ mod edge_cases {
             max_connections_per_host: 100,
             enable_keepalive: true,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.pool_size, 10000);
@@ -432,6 +496,10 @@ mod edge_cases {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.idle_timeout, Duration::ZERO);
@@ -447,6 +515,10 @@ mod edge_cases {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         assert_eq!(config.idle_timeout, Duration::from_secs(604800));
@@ -461,6 +533,10 @@ mod edge_cases {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         let config2 = Http1Config {
@@ -469,6 +545,10 @@ mod edge_cases {
             max_connections_per_host: 12,
             enable_keepalive: false,
             enable_pipelining: true,
+            tcp_nodelay: true,
+            tcp_keepalive: None,
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
 
         // Modifying one config shouldn't affect another
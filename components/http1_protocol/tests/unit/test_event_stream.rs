@@ -0,0 +1,73 @@
+//! `text/event-stream` parsing
+
+use http1_protocol::EventStreamParser;
+use std::time::Duration;
+
+#[test]
+fn test_parse_multi_event_stream() {
+    let mut parser = EventStreamParser::new();
+    let events = parser.feed(
+        b": connected\n\
+          data: first\n\
+          \n\
+          event: update\n\
+          id: 42\n\
+          data: line one\n\
+          data:line two\n\
+          \n\
+          data\n\
+          \n",
+    );
+
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].event, "message");
+    assert_eq!(events[0].data, "first");
+    assert_eq!(events[0].id, "");
+    assert_eq!(events[1].event, "update");
+    assert_eq!(events[1].data, "line one\nline two");
+    assert_eq!(events[1].id, "42");
+    // The ID carries over to later events, and a bare field name has an empty value
+    assert_eq!(events[2].event, "message");
+    assert_eq!(events[2].data, "");
+    assert_eq!(events[2].id, "42");
+    assert_eq!(parser.last_event_id(), "42");
+}
+
+#[test]
+fn test_parse_split_chunks_and_line_endings() {
+    let stream = b"\xEF\xBB\xBFdata: a\r\n\r\ndata: b\r\rdata: c\n\n";
+    let mut parser = EventStreamParser::new();
+    let mut events = Vec::new();
+    for byte in stream.iter() {
+        events.extend(parser.feed(std::slice::from_ref(byte)));
+    }
+
+    let data: Vec<_> = events.iter().map(|event| event.data.as_str()).collect();
+    assert_eq!(data, ["a", "b", "c"]);
+}
+
+#[test]
+fn test_parse_retry_and_ignored_fields() {
+    let mut parser = EventStreamParser::new();
+    let events = parser.feed(
+        b"retry: 1500\n\
+          retry: soon\n\
+          id: bad\0id\n\
+          unknown: field\n\
+          event: ping\n\
+          \n\
+          data: incomplete",
+    );
+
+    // An event with no data isn't dispatched, and neither is an unterminated one
+    assert!(events.is_empty());
+    assert_eq!(parser.retry(), Some(Duration::from_millis(1500)));
+    assert_eq!(parser.last_event_id(), "");
+
+    parser.reset();
+    let events = parser.feed(b"\ndata: next\n\n");
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event, "message");
+    assert_eq!(events[0].data, "next");
+    assert_eq!(parser.retry(), Some(Duration::from_millis(1500)));
+}
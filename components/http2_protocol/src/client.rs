@@ -12,7 +12,7 @@ use network_types::{HttpMethod, NetworkRequest, NetworkResponse};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::net::TcpStream;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, error, info, trace};
 
 /// Connection pool key
@@ -44,13 +44,20 @@ impl PoolKey {
     }
 }
 
+/// Pooled connection to one origin, locked while it is being established
+///
+/// Requests to an origin that is still connecting wait on the lock and then
+/// share the new connection. If the connect fails or is cancelled the slot
+/// stays empty and the next request retries it.
+type ConnectionSlot = Arc<Mutex<Option<Arc<Http2Connection>>>>;
+
 /// HTTP/2 client with connection pooling and multiplexing
 pub struct Http2Client {
     /// Client configuration
     config: Http2Config,
 
     /// Connection pool (host:port -> connection)
-    connections: Arc<RwLock<HashMap<PoolKey, Arc<Http2Connection>>>>,
+    connections: Arc<RwLock<HashMap<PoolKey, ConnectionSlot>>>,
 
     /// DNS resolver
     dns_resolver: Arc<dyn DnsResolver>,
@@ -98,6 +105,19 @@ impl Http2Client {
         })
     }
 
+    /// Create HTTP/2 client that resolves hosts through `dns_resolver`
+    pub fn with_resolver(
+        config: Http2Config,
+        dns_resolver: Arc<dyn DnsResolver>,
+    ) -> Http2Result<Self> {
+        Self::with_components(
+            config,
+            dns_resolver,
+            Arc::new(RwLock::new(CookieStore::new())),
+            Arc::new(HttpCache::new(CacheConfig::default())),
+        )
+    }
+
     /// Create HTTP/2 client with custom components
     pub fn with_components(
         config: Http2Config,
@@ -138,11 +158,21 @@ impl Http2Client {
     ///
     /// # Returns
     ///
-    /// Network response or error
-    pub async fn fetch(
-        &self,
-        mut request: NetworkRequest,
-    ) -> Result<NetworkResponse, NetworkError> {
+    /// Network response or error; `NetworkError::Aborted` if the request's
+    /// abort token is aborted before the response completes
+    pub async fn fetch(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        match request.signal.clone() {
+            Some(signal) => tokio::select! {
+                biased;
+                _ = signal.aborted() => Err(NetworkError::Aborted),
+                result = self.send(request) => result,
+            },
+            None => self.send(request).await,
+        }
+    }
+
+    /// Perform the request without observing its abort token
+    async fn send(&self, mut request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
         trace!("Fetching: {:?} {}", request.method, request.url);
 
         // Check cache first
@@ -249,23 +279,46 @@ impl Http2Client {
     ) -> Result<Vec<NetworkResponse>, NetworkError> {
         info!("Fetching {} requests with multiplexing", requests.len());
 
-        // TODO: Implement true concurrent multiplexing using h2's capabilities
-        // For now, fetch sequentially (still uses connection pooling and multiplexing at h2 level)
-        let mut responses = Vec::new();
-
-        for request in requests {
-            match self.fetch(request).await {
-                Ok(response) => responses.push(response),
-                Err(e) => {
+        // All requests to the same host share one pooled connection, so issuing
+        // them concurrently multiplexes them as separate HTTP/2 streams
+        let responses =
+            futures::future::try_join_all(requests.into_iter().map(|request| self.fetch(request)))
+                .await
+                .map_err(|e| {
                     error!("Request failed: {}", e);
-                    return Err(e);
-                }
-            }
-        }
+                    e
+                })?;
 
         Ok(responses)
     }
 
+    /// Fetch a request and stream the response body
+    ///
+    /// Returns once the response headers arrive; the body is delivered as
+    /// `ResponseBody::Stream`. Redirects are not followed and the response is
+    /// not cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Network request to fetch
+    ///
+    /// # Returns
+    ///
+    /// Network response with a streaming body or error
+    pub async fn stream_response(
+        &self,
+        request: NetworkRequest,
+    ) -> Result<NetworkResponse, NetworkError> {
+        trace!("Streaming: {:?} {}", request.method, request.url);
+
+        let connection = self.get_or_create_connection(request.url.as_str()).await?;
+
+        match tokio::time::timeout(self.timeout, connection.stream_response(request)).await {
+            Ok(result) => result,
+            Err(_) => Err(NetworkError::Timeout(self.timeout)),
+        }
+    }
+
     /// Fetch a single request without redirect handling
     async fn fetch_once(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
         // Get or create connection
@@ -279,19 +332,27 @@ impl Http2Client {
     }
 
     /// Get or create a connection from the pool
+    ///
+    /// Concurrent requests to the same origin share a single connection. The
+    /// pool lock is only held to find or add the origin's slot, so connecting
+    /// to one origin doesn't hold up requests to others.
     async fn get_or_create_connection(
         &self,
         url: &str,
     ) -> Result<Arc<Http2Connection>, NetworkError> {
         let pool_key = PoolKey::from_url(url)?;
 
-        // Check if connection exists
-        {
-            let connections = self.connections.read().await;
-            if let Some(conn) = connections.get(&pool_key) {
-                debug!("Reusing existing HTTP/2 connection for {}", url);
-                return Ok(Arc::clone(conn));
-            }
+        let slot = Arc::clone(
+            self.connections
+                .write()
+                .await
+                .entry(pool_key.clone())
+                .or_default(),
+        );
+        let mut slot = slot.lock().await;
+        if let Some(conn) = slot.as_ref() {
+            debug!("Reusing existing HTTP/2 connection for {}", url);
+            return Ok(Arc::clone(conn));
         }
 
         // Create new connection
@@ -330,14 +391,18 @@ impl Http2Client {
         let connection = Arc::new(connection);
 
         // Store in pool
-        {
-            let mut connections = self.connections.write().await;
-            connections.insert(pool_key, Arc::clone(&connection));
-        }
+        *slot = Some(Arc::clone(&connection));
 
         Ok(connection)
     }
 
+    /// Open the pooled connection for the origin of `url` ahead of a request
+    ///
+    /// Does nothing if a connection to the origin is already pooled.
+    pub async fn preconnect(&self, url: &str) -> Result<(), NetworkError> {
+        self.get_or_create_connection(url).await.map(|_| ())
+    }
+
     /// Perform health check on a connection
     pub async fn health_check(&self, url: &str) -> Http2Result<std::time::Duration> {
         let connection = self
@@ -356,9 +421,14 @@ impl Http2Client {
     }
 
     /// Get number of pooled connections
+    ///
+    /// Connections still being established are not counted.
     pub async fn connection_count(&self) -> usize {
         let connections = self.connections.read().await;
-        connections.len()
+        connections
+            .values()
+            .filter(|slot| slot.try_lock().is_ok_and(|slot| slot.is_some()))
+            .count()
     }
 
     /// Check if a status code indicates a redirect
@@ -3,23 +3,58 @@
 use crate::config::Http2Config;
 use crate::error::{Http2Error, Http2Result};
 use bytes::Bytes;
+use futures::Stream;
 use h2::client::{self, SendRequest};
+use h2::RecvStream;
 use http::{Method, Request, Response, Version};
 use network_errors::NetworkError;
 use network_types::{
-    HttpMethod, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseType,
+    HttpMethod, NetworkErrorKind, NetworkRequest, NetworkResponse, RequestBody, ResourceTiming,
+    ResponseBody, ResponseSource, ResponseType,
 };
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, error, trace};
 
+/// Streaming response body backed by an HTTP/2 stream
+///
+/// Holds the stream's concurrency permit until the body is fully consumed or dropped.
+struct Http2BodyStream {
+    body: RecvStream,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl Stream for Http2BodyStream {
+    type Item = Result<Bytes, network_types::NetworkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.body.poll_data(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                let _ = self.body.flow_control().release_capacity(chunk.len());
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(network_types::NetworkError::new(
+                NetworkErrorKind::InvalidResponse,
+                format!("Failed to read body: {}", e),
+            )))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 /// HTTP/2 connection wrapper
 pub struct Http2Connection {
     /// HTTP/2 send request handle
     sender: Arc<Mutex<SendRequest<Bytes>>>,
 
+    /// Limits the number of concurrently open streams on this connection
+    stream_permits: Arc<Semaphore>,
+
     /// Connection configuration
     #[allow(dead_code)] // Reserved for future use (custom settings, timeouts, etc.)
     config: Http2Config,
@@ -43,8 +78,13 @@ impl Http2Connection {
         // Validate configuration first
         config.validate()?;
 
-        // Create HTTP/2 handshake
-        let (sender, connection) = client::handshake(stream)
+        // Create HTTP/2 handshake with the configured settings
+        let (sender, connection) = client::Builder::new()
+            .initial_window_size(config.initial_window_size())
+            .max_frame_size(config.max_frame_size())
+            .max_concurrent_streams(config.max_concurrent_streams())
+            .enable_push(config.enable_push())
+            .handshake(stream)
             .await
             .map_err(|e| Http2Error::ConnectionError(format!("HTTP/2 handshake failed: {}", e)))?;
 
@@ -59,6 +99,7 @@ impl Http2Connection {
 
         Ok(Self {
             sender: Arc::new(Mutex::new(sender)),
+            stream_permits: Arc::new(Semaphore::new(config.max_concurrent_streams() as usize)),
             config,
             created_at: Instant::now(),
         })
@@ -83,34 +124,115 @@ impl Http2Connection {
             request.url
         );
 
-        // Convert NetworkRequest to http::Request
-        let http_request = self.convert_request(&request)?;
-
-        // Get sender and prepare request
-        let (response, _) = {
-            let sender = self.sender.lock().await;
-
-            // Clone the sender (cheaply clonable per h2 docs) and check if connection is ready
-            let mut ready_sender =
-                sender.clone().ready().await.map_err(|e| {
-                    NetworkError::ProtocolError(format!("Connection not ready: {}", e))
-                })?;
-
-            // Send request
-            ready_sender
-                .send_request(http_request, false)
-                .map_err(|e| {
-                    NetworkError::ProtocolError(format!("Failed to send request: {}", e))
-                })?
+        let (http_response, _permit) = self.open_stream(&request).await?;
+
+        // Convert response
+        self.convert_response(http_response, &request).await
+    }
+
+    /// Send a request and return as soon as the response headers arrive
+    ///
+    /// The response body is returned as `ResponseBody::Stream` and yields data
+    /// frames as they are received.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - Network request to send
+    ///
+    /// # Returns
+    ///
+    /// Network response with a streaming body or error
+    pub async fn stream_response(
+        &self,
+        request: NetworkRequest,
+    ) -> Result<NetworkResponse, NetworkError> {
+        trace!(
+            "Streaming HTTP/2 request: {:?} {}",
+            request.method,
+            request.url
+        );
+
+        let (http_response, permit) = self.open_stream(&request).await?;
+        let status = http_response.status();
+        let headers = http_response.headers().clone();
+        let body = Http2BodyStream {
+            body: http_response.into_body(),
+            _permit: permit,
         };
 
+        Ok(NetworkResponse {
+            url: request.url.clone(),
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers,
+            body: ResponseBody::Stream(Box::new(body)),
+            redirected: false,
+            type_: ResponseType::Basic,
+            timing: ResourceTiming::default(),
+            trailers: None,
+            source: ResponseSource::Network,
+        })
+    }
+
+    /// Open a stream, send the request and wait for the response headers
+    ///
+    /// Waits for a free stream slot if `max_concurrent_streams` requests are
+    /// already in flight on this connection.
+    async fn open_stream(
+        &self,
+        request: &NetworkRequest,
+    ) -> Result<(Response<RecvStream>, OwnedSemaphorePermit), NetworkError> {
+        let permit = Arc::clone(&self.stream_permits)
+            .acquire_owned()
+            .await
+            .map_err(|_| NetworkError::ConnectionFailed("Connection closed".to_string()))?;
+
+        // Convert NetworkRequest to http::Request
+        let http_request = self.convert_request(request)?;
+        let body = Self::request_body_bytes(request)?;
+
+        // Clone the sender (cheaply clonable per h2 docs) so the lock is not held
+        // while waiting for the connection to become ready
+        let sender = self.sender.lock().await.clone();
+        let mut ready_sender = sender
+            .ready()
+            .await
+            .map_err(|e| NetworkError::ProtocolError(format!("Connection not ready: {}", e)))?;
+
+        // Send request
+        let (response, mut send_stream) = ready_sender
+            .send_request(http_request, body.is_none())
+            .map_err(|e| {
+            NetworkError::ProtocolError(format!("Failed to send request: {}", e))
+        })?;
+
+        if let Some(body) = body {
+            send_stream.send_data(body, true).map_err(|e| {
+                NetworkError::ProtocolError(format!("Failed to send request body: {}", e))
+            })?;
+        }
+
         // Wait for response
         let http_response = response.await.map_err(|e| {
             NetworkError::ProtocolError(format!("Failed to receive response: {}", e))
         })?;
 
-        // Convert response
-        self.convert_response(http_response, &request).await
+        Ok((http_response, permit))
+    }
+
+    /// Extract the request body as bytes
+    fn request_body_bytes(request: &NetworkRequest) -> Result<Option<Bytes>, NetworkError> {
+        match &request.body {
+            None => Ok(None),
+            Some(RequestBody::Bytes(bytes)) => Ok(Some(Bytes::from(bytes.clone()))),
+            Some(RequestBody::Text(text)) => Ok(Some(Bytes::from(text.clone()))),
+            Some(RequestBody::FormData(_)) => Err(NetworkError::Other(
+                "FormData not yet implemented".to_string(),
+            )),
+            Some(RequestBody::Stream(_)) => Err(NetworkError::Other(
+                "Streaming request bodies not yet implemented".to_string(),
+            )),
+        }
     }
 
     /// Send a ping to measure round-trip time
@@ -128,6 +250,11 @@ impl Http2Connection {
         Ok(rtt)
     }
 
+    /// Number of additional streams that can be opened right now
+    pub fn available_streams(&self) -> usize {
+        self.stream_permits.available_permits()
+    }
+
     /// Get connection age
     pub fn age(&self) -> Duration {
         self.created_at.elapsed()
@@ -135,17 +262,7 @@ impl Http2Connection {
 
     /// Convert HttpMethod to http::Method
     fn convert_http_method(method: &HttpMethod) -> Method {
-        match method {
-            HttpMethod::Get => Method::GET,
-            HttpMethod::Head => Method::HEAD,
-            HttpMethod::Post => Method::POST,
-            HttpMethod::Put => Method::PUT,
-            HttpMethod::Delete => Method::DELETE,
-            HttpMethod::Connect => Method::CONNECT,
-            HttpMethod::Options => Method::OPTIONS,
-            HttpMethod::Trace => Method::TRACE,
-            HttpMethod::Patch => Method::PATCH,
-        }
+        Method::from(*method)
     }
 
     /// Convert NetworkRequest to http::Request
@@ -187,6 +304,11 @@ impl Http2Connection {
             let _ = body_stream.flow_control().release_capacity(chunk.len());
         }
 
+        let trailers = body_stream
+            .trailers()
+            .await
+            .map_err(|e| NetworkError::ProtocolError(format!("Failed to read trailers: {}", e)))?;
+
         // Build NetworkResponse manually
         Ok(NetworkResponse {
             url: network_request.url.clone(),
@@ -197,6 +319,8 @@ impl Http2Connection {
             redirected: false,
             type_: ResponseType::Basic,
             timing: ResourceTiming::default(),
+            trailers,
+            source: ResponseSource::Network,
         })
     }
 }
@@ -2,13 +2,22 @@
 //!
 //! These tests verify the component works with real dependencies
 
-use http2_protocol::{Http2Client, Http2Config};
+use async_trait::async_trait;
+use bytes::Bytes;
+use dns_resolver::DnsResolver;
+use futures::StreamExt;
 use http::HeaderMap;
+use http2_protocol::{Http2Client, Http2Config};
+use network_errors::NetworkResult;
 use network_types::{
-    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
-    RequestMode, RequestPriority,
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, NetworkResponse, RedirectMode,
+    ReferrerPolicy, RequestMode, RequestPriority, ResponseBody,
 };
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::net::TcpListener;
 use url::Url;
 
 #[tokio::test]
@@ -201,6 +210,163 @@ fn test_http2_config_validation_integration() {
     assert!(invalid3.validate().is_err());
 }
 
+/// Counters observed by the local HTTP/2 test server
+#[derive(Default)]
+struct ServerStats {
+    connections: AtomicUsize,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+}
+
+/// Start a cleartext (h2c, prior knowledge) HTTP/2 server on localhost
+///
+/// Each response echoes the request path after a short delay, so concurrent
+/// requests overlap on the server.
+async fn start_h2_server() -> (SocketAddr, Arc<ServerStats>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let stats = Arc::new(ServerStats::default());
+
+    let server_stats = Arc::clone(&stats);
+    tokio::spawn(async move {
+        loop {
+            let (socket, _) = listener.accept().await.unwrap();
+            server_stats.connections.fetch_add(1, Ordering::SeqCst);
+            let stats = Arc::clone(&server_stats);
+
+            tokio::spawn(async move {
+                let mut connection = h2::server::handshake(socket).await.unwrap();
+                while let Some(Ok((request, mut respond))) = connection.accept().await {
+                    let stats = Arc::clone(&stats);
+                    tokio::spawn(async move {
+                        let current = stats.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        stats.max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        stats.in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                        let response = http::Response::builder().status(200).body(()).unwrap();
+                        let mut send = respond.send_response(response, false).unwrap();
+                        let body = format!("path={}", request.uri().path());
+                        send.send_data(Bytes::from(body), true).unwrap();
+                    });
+                }
+            });
+        }
+    });
+
+    (addr, stats)
+}
+
+fn get_request(url: &str) -> NetworkRequest {
+    NetworkRequest {
+        url: Url::parse(url).unwrap(),
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn body_text(response: &NetworkResponse) -> String {
+    match &response.body {
+        ResponseBody::Bytes(bytes) => String::from_utf8(bytes.clone()).unwrap(),
+        other => panic!("unexpected body: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_http2_get_against_local_server() {
+    //! Given: A local HTTP/2 server
+    //! When: A GET request is fetched
+    //! Then: The response status and body are returned
+
+    let (addr, stats) = start_h2_server().await;
+    let client = Http2Client::new(Http2Config::default()).unwrap();
+
+    let response = client
+        .fetch(get_request(&format!("http://{}/hello", addr)))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(body_text(&response), "path=/hello");
+    assert_eq!(stats.connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_http2_multiplexes_streams_over_one_connection() {
+    //! Given: A local HTTP/2 server
+    //! When: Several requests to the same host are fetched together
+    //! Then: They run concurrently as streams of a single connection
+
+    let (addr, stats) = start_h2_server().await;
+    let client = Http2Client::new(Http2Config::default()).unwrap();
+
+    let requests = (1..=4)
+        .map(|i| get_request(&format!("http://{}/{}", addr, i)))
+        .collect();
+    let responses = client.fetch_multiple(requests).await.unwrap();
+
+    let bodies: Vec<String> = responses.iter().map(body_text).collect();
+    assert_eq!(bodies, vec!["path=/1", "path=/2", "path=/3", "path=/4"]);
+    assert_eq!(stats.connections.load(Ordering::SeqCst), 1);
+    assert_eq!(client.connection_count().await, 1);
+    assert!(stats.max_in_flight.load(Ordering::SeqCst) > 1);
+}
+
+#[tokio::test]
+async fn test_http2_max_concurrent_streams_limits_in_flight_requests() {
+    //! Given: A client limited to one concurrent stream
+    //! When: Several requests are fetched together
+    //! Then: The server never sees more than one request at a time
+
+    let (addr, stats) = start_h2_server().await;
+    let client = Http2Client::new(Http2Config::new().with_max_concurrent_streams(1)).unwrap();
+
+    let requests = (1..=3)
+        .map(|i| get_request(&format!("http://{}/{}", addr, i)))
+        .collect();
+    let responses = client.fetch_multiple(requests).await.unwrap();
+
+    assert_eq!(responses.len(), 3);
+    assert_eq!(stats.max_in_flight.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_http2_stream_response() {
+    //! Given: A local HTTP/2 server
+    //! When: A response is streamed
+    //! Then: The body arrives as a stream of data frames
+
+    let (addr, _stats) = start_h2_server().await;
+    let client = Http2Client::new(Http2Config::default()).unwrap();
+
+    let response = client
+        .stream_response(get_request(&format!("http://{}/stream", addr)))
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+
+    let ResponseBody::Stream(mut stream) = response.body else {
+        panic!("expected streaming body");
+    };
+    let mut body = Vec::new();
+    while let Some(chunk) = stream.next().await {
+        body.extend_from_slice(&chunk.unwrap());
+    }
+    assert_eq!(body, b"path=/stream");
+}
+
 // Note: Full integration tests with real HTTP/2 servers would go here
 // These would use wiremock or a test HTTP/2 server to verify:
 // - Actual request/response cycles
@@ -214,3 +380,55 @@ fn test_http2_config_validation_integration() {
 // - Cache integration
 // - TLS integration
 // - DNS resolution
+
+/// Resolver whose lookups of `stalled.test` never finish
+///
+/// Every other host resolves to localhost.
+struct StallingResolver;
+
+#[async_trait]
+impl DnsResolver for StallingResolver {
+    async fn resolve(&self, hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        if hostname == "stalled.test" {
+            std::future::pending::<()>().await;
+        }
+        Ok(vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
+#[tokio::test]
+async fn test_http2_stalled_connect_does_not_block_other_origins() {
+    //! Given: A request whose DNS lookup never finishes
+    //! When: A request to another origin is fetched meanwhile
+    //! Then: It connects and completes without waiting for the stalled one
+
+    let (addr, _stats) = start_h2_server().await;
+    let client = Arc::new(
+        Http2Client::with_resolver(Http2Config::default(), Arc::new(StallingResolver)).unwrap(),
+    );
+
+    let stalled = {
+        let client = Arc::clone(&client);
+        let url = format!("http://stalled.test:{}/", addr.port());
+        tokio::spawn(async move { client.fetch(get_request(&url)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let url = format!("http://localhost:{}/other", addr.port());
+    let response = tokio::time::timeout(Duration::from_secs(2), client.fetch(get_request(&url)))
+        .await
+        .expect("fetch from another origin should not wait for the stalled connect")
+        .unwrap();
+
+    assert_eq!(body_text(&response), "path=/other");
+    assert_eq!(client.connection_count().await, 1);
+    stalled.abort();
+}
@@ -0,0 +1,429 @@
+//! Alt-Svc discovery and caching
+//!
+//! Clients usually learn that an origin speaks HTTP/3 from an `Alt-Svc` response
+//! header (RFC 7838) received over HTTP/1.1 or HTTP/2, e.g. `Alt-Svc: h3=":443"; ma=3600`.
+//! [`AltSvcCache`] remembers those advertisements until their `ma` (max-age) expires
+//! so the network stack can decide whether to attempt HTTP/3 for a host.
+
+use network_errors::{NetworkError, NetworkResult};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Default freshness lifetime of an alternative when `ma` is absent (RFC 7838 §3.1)
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Longest time an alternative is cached, however large its `ma`
+const MAX_CACHED_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// ALPN protocol identifier for HTTP/3
+const H3_PROTOCOL_ID: &str = "h3";
+
+/// A single alternative service advertised in an `Alt-Svc` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AltService {
+    /// ALPN protocol identifier (e.g. `h3`)
+    pub protocol_id: String,
+    /// Alternative host, or `None` when the origin host should be reused
+    pub host: Option<String>,
+    /// Alternative port
+    pub port: u16,
+    /// Freshness lifetime from the `ma` parameter
+    pub max_age: Duration,
+    /// Whether the entry survives network configuration changes (`persist=1`)
+    pub persist: bool,
+}
+
+/// Parsed value of an `Alt-Svc` header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AltSvcValue {
+    /// `Alt-Svc: clear` - all alternatives for the origin are invalidated
+    Clear,
+    /// One or more advertised alternatives, in preference order
+    Services(Vec<AltService>),
+}
+
+/// Parse an `Alt-Svc` header value
+///
+/// Alternatives that cannot be parsed are ignored, as required by RFC 7838.
+///
+/// # Errors
+///
+/// Returns `NetworkError::ProtocolError` if the value contains no usable alternative
+///
+/// # Example
+///
+/// ```rust
+/// use http3_protocol::{parse_alt_svc, AltSvcValue};
+///
+/// let value = parse_alt_svc(r#"h3=":443"; ma=3600, h3-29=":8443""#).unwrap();
+/// match value {
+///     AltSvcValue::Services(services) => assert_eq!(services.len(), 2),
+///     AltSvcValue::Clear => unreachable!(),
+/// }
+/// ```
+pub fn parse_alt_svc(value: &str) -> NetworkResult<AltSvcValue> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("clear") {
+        return Ok(AltSvcValue::Clear);
+    }
+
+    let services: Vec<AltService> = split_unquoted(value, ',')
+        .into_iter()
+        .filter_map(|alternative| parse_alternative(&alternative))
+        .collect();
+
+    if services.is_empty() {
+        return Err(NetworkError::ProtocolError(format!(
+            "No valid alternative in Alt-Svc header: {}",
+            value
+        )));
+    }
+
+    Ok(AltSvcValue::Services(services))
+}
+
+/// Parse one `protocol-id="alt-authority"; param=value` alternative
+fn parse_alternative(alternative: &str) -> Option<AltService> {
+    let mut parts = split_unquoted(alternative, ';').into_iter();
+
+    let first = parts.next()?;
+    let (protocol_id, authority) = first.split_once('=')?;
+    let protocol_id = protocol_id.trim();
+    if protocol_id.is_empty() {
+        return None;
+    }
+    let (host, port) = parse_authority(unquote(authority.trim())?)?;
+
+    let mut max_age = DEFAULT_MAX_AGE;
+    let mut persist = false;
+    for param in parts {
+        let Some((name, value)) = param.split_once('=') else {
+            continue;
+        };
+        let value = unquote(value.trim()).unwrap_or(value.trim());
+        match name.trim().to_ascii_lowercase().as_str() {
+            "ma" => max_age = Duration::from_secs(value.parse().ok()?),
+            "persist" => persist = value == "1",
+            _ => {}
+        }
+    }
+
+    Some(AltService {
+        protocol_id: protocol_id.to_string(),
+        host,
+        port,
+        max_age,
+        persist,
+    })
+}
+
+/// Parse an alt-authority of the form `:port`, `host:port` or `[ipv6]:port`
+fn parse_authority(authority: &str) -> Option<(Option<String>, u16)> {
+    let (host, port) = authority.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.is_empty() {
+        Some((None, port))
+    } else {
+        Some((Some(host.to_ascii_lowercase()), port))
+    }
+}
+
+/// Strip surrounding double quotes, returning `None` if the value is not quoted
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Split on `separator`, ignoring separators inside quoted strings
+fn split_unquoted(value: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in value.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c == separator && !in_quotes => {
+                parts.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.trim().is_empty() {
+        parts.push(current.trim().to_string());
+    }
+
+    parts.retain(|part| !part.is_empty());
+    parts
+}
+
+/// Cached alternative with its absolute expiry time
+#[derive(Debug, Clone)]
+struct CachedAltService {
+    service: AltService,
+    expires_at: Instant,
+}
+
+/// Cache of alternative services learned from `Alt-Svc` headers
+///
+/// Entries are keyed by origin host and port and expire according to their `ma`
+/// parameter.
+///
+/// # Example
+///
+/// ```rust
+/// use http3_protocol::AltSvcCache;
+///
+/// let mut cache = AltSvcCache::new();
+/// cache.update("example.com", 443, r#"h3=":443"; ma=3600"#).unwrap();
+///
+/// assert!(cache.supports_http3("example.com", 443));
+/// assert!(!cache.supports_http3("other.com", 443));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AltSvcCache {
+    entries: HashMap<String, Vec<CachedAltService>>,
+}
+
+impl AltSvcCache {
+    /// Create a new empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the `Alt-Svc` header received from an origin
+    ///
+    /// Replaces any previously cached alternatives for the origin. A value of
+    /// `clear` removes them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ProtocolError` if the header cannot be parsed
+    pub fn update(&mut self, host: &str, port: u16, header_value: &str) -> NetworkResult<()> {
+        self.update_at(host, port, header_value, Instant::now())
+    }
+
+    /// Record an `Alt-Svc` header as if received at `now`
+    ///
+    /// Alternatives are cached for at most 30 days, even if `ma` is longer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ProtocolError` if the header cannot be parsed
+    pub fn update_at(
+        &mut self,
+        host: &str,
+        port: u16,
+        header_value: &str,
+        now: Instant,
+    ) -> NetworkResult<()> {
+        let key = Self::origin_key(host, port);
+        match parse_alt_svc(header_value)? {
+            AltSvcValue::Clear => {
+                self.entries.remove(&key);
+            }
+            AltSvcValue::Services(services) => {
+                let cached = services
+                    .into_iter()
+                    .map(|service| CachedAltService {
+                        expires_at: now
+                            .checked_add(service.max_age.min(MAX_CACHED_LIFETIME))
+                            .unwrap_or(now),
+                        service,
+                    })
+                    .collect();
+                self.entries.insert(key, cached);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the preferred HTTP/3 alternative for an origin, if one is fresh
+    pub fn http3_alternative(&self, host: &str, port: u16) -> Option<AltService> {
+        self.http3_alternative_at(host, port, Instant::now())
+    }
+
+    /// Get the preferred HTTP/3 alternative for an origin that is fresh at `now`
+    pub fn http3_alternative_at(&self, host: &str, port: u16, now: Instant) -> Option<AltService> {
+        self.entries
+            .get(&Self::origin_key(host, port))?
+            .iter()
+            .find(|cached| cached.service.protocol_id == H3_PROTOCOL_ID && cached.expires_at > now)
+            .map(|cached| cached.service.clone())
+    }
+
+    /// Whether HTTP/3 should be attempted for an origin
+    pub fn supports_http3(&self, host: &str, port: u16) -> bool {
+        self.http3_alternative(host, port).is_some()
+    }
+
+    /// Drop alternatives not marked `persist=1`
+    ///
+    /// Should be called when the network configuration changes.
+    pub fn clear_non_persistent(&mut self) {
+        for services in self.entries.values_mut() {
+            services.retain(|cached| cached.service.persist);
+        }
+        self.entries.retain(|_, services| !services.is_empty());
+    }
+
+    /// Remove all expired alternatives
+    pub fn purge_expired(&mut self) {
+        let now = Instant::now();
+        for services in self.entries.values_mut() {
+            services.retain(|cached| cached.expires_at > now);
+        }
+        self.entries.retain(|_, services| !services.is_empty());
+    }
+
+    /// Number of origins with cached alternatives
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn origin_key(host: &str, port: u16) -> String {
+        format!("{}:{}", host.to_ascii_lowercase(), port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_single_alternative() {
+        let value = parse_alt_svc(r#"h3=":443"; ma=3600"#).unwrap();
+        assert_eq!(
+            value,
+            AltSvcValue::Services(vec![AltService {
+                protocol_id: "h3".to_string(),
+                host: None,
+                port: 443,
+                max_age: Duration::from_secs(3600),
+                persist: false,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_parse_multiple_alternatives_with_persist() {
+        let value =
+            parse_alt_svc(r#"h3="alt.example.com:8443"; ma=60; persist=1, h2=":443""#).unwrap();
+        let AltSvcValue::Services(services) = value else {
+            panic!("expected services");
+        };
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[0].host.as_deref(), Some("alt.example.com"));
+        assert_eq!(services[0].port, 8443);
+        assert_eq!(services[0].max_age, Duration::from_secs(60));
+        assert!(services[0].persist);
+        assert_eq!(services[1].protocol_id, "h2");
+        assert_eq!(services[1].max_age, DEFAULT_MAX_AGE);
+        assert!(!services[1].persist);
+    }
+
+    #[test]
+    fn test_parse_ipv6_authority() {
+        let AltSvcValue::Services(services) = parse_alt_svc(r#"h3="[::1]:443""#).unwrap() else {
+            panic!("expected services");
+        };
+        assert_eq!(services[0].host.as_deref(), Some("::1"));
+    }
+
+    #[test]
+    fn test_parse_clear() {
+        assert_eq!(parse_alt_svc("clear").unwrap(), AltSvcValue::Clear);
+    }
+
+    #[test]
+    fn test_parse_skips_invalid_alternatives() {
+        let AltSvcValue::Services(services) = parse_alt_svc(r#"h3=443, h3=":443"; ma=10"#).unwrap()
+        else {
+            panic!("expected services");
+        };
+        assert_eq!(services.len(), 1);
+        assert!(parse_alt_svc("garbage").is_err());
+    }
+
+    #[test]
+    fn test_cache_lookup() {
+        let mut cache = AltSvcCache::new();
+        cache
+            .update("Example.com", 443, r#"h2=":443", h3=":443"; ma=3600"#)
+            .unwrap();
+
+        let alt = cache.http3_alternative("example.com", 443).unwrap();
+        assert_eq!(alt.protocol_id, "h3");
+        assert!(cache.supports_http3("example.com", 443));
+        assert!(!cache.supports_http3("example.com", 8443));
+    }
+
+    #[test]
+    fn test_cache_expiry() {
+        let mut cache = AltSvcCache::new();
+        let now = Instant::now();
+        cache
+            .update_at("example.com", 443, r#"h3=":443"; ma=60"#, now)
+            .unwrap();
+
+        assert!(cache
+            .http3_alternative_at("example.com", 443, now + Duration::from_secs(59))
+            .is_some());
+        assert!(cache
+            .http3_alternative_at("example.com", 443, now + Duration::from_secs(60))
+            .is_none());
+    }
+
+    #[test]
+    fn test_cache_caps_huge_max_age() {
+        let mut cache = AltSvcCache::new();
+        let now = Instant::now();
+        cache
+            .update_at(
+                "example.com",
+                443,
+                r#"h3=":443"; ma=18446744073709551615"#,
+                now,
+            )
+            .unwrap();
+
+        assert!(cache
+            .http3_alternative_at("example.com", 443, now + MAX_CACHED_LIFETIME / 2)
+            .is_some());
+        assert!(cache
+            .http3_alternative_at("example.com", 443, now + MAX_CACHED_LIFETIME)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cache_clear_header() {
+        let mut cache = AltSvcCache::new();
+        cache.update("example.com", 443, r#"h3=":443""#).unwrap();
+        cache.update("example.com", 443, "clear").unwrap();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_clear_non_persistent() {
+        let mut cache = AltSvcCache::new();
+        cache
+            .update("a.com", 443, r#"h3=":443"; persist=1"#)
+            .unwrap();
+        cache.update("b.com", 443, r#"h3=":443""#).unwrap();
+
+        cache.clear_non_persistent();
+
+        assert!(cache.supports_http3("a.com", 443));
+        assert!(!cache.supports_http3("b.com", 443));
+        assert_eq!(cache.len(), 1);
+    }
+}
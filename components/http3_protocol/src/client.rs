@@ -1,15 +1,48 @@
 //! HTTP/3 client implementation
 
-use crate::{config::Http3Config, connection::QuicConnection};
-use http::HeaderMap;
+use crate::{
+    config::Http3Config,
+    connection::{QuicConnection, ZeroRttStatus},
+    error::{connection_error, h3_error},
+    request::build_request,
+};
+use bytes::{BufMut, Bytes};
+use dns_resolver::DnsResolver;
+use h3::client::SendRequest;
 use network_errors::{NetworkError, NetworkResult};
-use network_types::{NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseType};
+use network_types::{
+    NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseSource, ResponseType,
+};
 use quinn::{ClientConfig, Endpoint};
 use rustls::RootCertStore;
-use std::net::{SocketAddr, ToSocketAddrs};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::Mutex;
+use tracing::debug;
+
+/// ALPN protocol identifier for HTTP/3 (RFC 9114)
+const H3_ALPN: &[u8] = b"h3";
+
+/// Handle for sending HTTP/3 requests on a connection
+type H3Sender = SendRequest<h3_quinn::OpenStreams, Bytes>;
+
+/// Connection to one server, locked while it is being established
+///
+/// Requests to a server that is still connecting wait on the lock and then
+/// share the new connection. If the connect fails or is cancelled the slot
+/// stays empty and the next request retries it.
+type ConnectionSlot = Arc<Mutex<Option<PooledConnection>>>;
+
+/// Pooled HTTP/3 connection
+#[derive(Clone)]
+struct PooledConnection {
+    /// QUIC connection the HTTP/3 session runs on
+    quic: QuicConnection,
+    /// Request sender for the HTTP/3 session
+    sender: H3Sender,
+}
 
 /// HTTP/3 client
 ///
@@ -42,6 +75,10 @@ pub struct Http3Client {
     endpoint: Arc<Mutex<Option<Endpoint>>>,
     /// Whether 0-RTT is currently enabled
     enable_0rtt: Arc<Mutex<bool>>,
+    /// Open and in-progress connections, keyed by host and port
+    connections: Arc<Mutex<HashMap<(String, u16), ConnectionSlot>>>,
+    /// Resolver for host names; the system resolver is used if None
+    resolver: Option<Arc<dyn DnsResolver>>,
 }
 
 impl Http3Client {
@@ -64,11 +101,24 @@ impl Http3Client {
             enable_0rtt: Arc::new(Mutex::new(config.enable_0rtt)),
             config,
             endpoint: Arc::new(Mutex::new(None)),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            resolver: None,
         }
     }
 
+    /// Create a new HTTP/3 client that resolves host names with `resolver`
+    pub fn with_resolver(config: Http3Config, resolver: Arc<dyn DnsResolver>) -> Self {
+        let mut client = Self::new(config);
+        client.resolver = Some(resolver);
+        client
+    }
+
     /// Enable or disable 0-RTT connections
     ///
+    /// 0-RTT needs early data, which TLS only allows when
+    /// [`Http3Config::enable_0rtt`] is set; otherwise enabling it here has no
+    /// effect and connections use a full handshake.
+    ///
     /// # Arguments
     ///
     /// * `enabled` - Whether to enable 0-RTT
@@ -90,7 +140,10 @@ impl Http3Client {
 
     /// Fetch a resource using HTTP/3
     ///
-    /// Performs an HTTP/3 request to the specified URL and returns the response.
+    /// Performs an HTTP/3 request to the specified URL and returns the response
+    /// with its body fully read. Connections are pooled per host and port, so
+    /// later requests to the same server are sent as new streams on the open
+    /// connection. Redirects are not followed.
     ///
     /// # Arguments
     ///
@@ -102,7 +155,8 @@ impl Http3Client {
     ///
     /// # Errors
     ///
-    /// Returns `NetworkError` if the request fails
+    /// Returns `NetworkError` if the request fails, or `NetworkError::Aborted`
+    /// if the request's abort token is aborted first
     ///
     /// # Example
     ///
@@ -121,6 +175,18 @@ impl Http3Client {
     /// }
     /// ```
     pub async fn fetch(&self, request: NetworkRequest) -> NetworkResult<NetworkResponse> {
+        match request.signal.clone() {
+            Some(signal) => tokio::select! {
+                biased;
+                _ = signal.aborted() => Err(NetworkError::Aborted),
+                result = self.send(request) => result,
+            },
+            None => self.send(request).await,
+        }
+    }
+
+    /// Perform the request without observing its abort token
+    async fn send(&self, request: NetworkRequest) -> NetworkResult<NetworkResponse> {
         let start_time = Instant::now();
 
         // Validate request
@@ -130,29 +196,116 @@ impl Http3Client {
             ));
         }
 
-        // Create endpoint if not exists
-        let endpoint = self.get_or_create_endpoint().await?;
-
-        // Resolve host and connect
         let host = request
             .url
             .host_str()
-            .ok_or_else(|| NetworkError::InvalidUrl("Missing host in URL".to_string()))?;
-
+            .ok_or_else(|| NetworkError::InvalidUrl("Missing host in URL".to_string()))?
+            .to_string();
         let port = request.url.port().unwrap_or(443);
 
-        // Resolve address
-        let remote_addr = self.resolve_address(host, port).await?;
+        let (http_request, body) = build_request(&request)?;
 
-        // Create connection
-        let connection = self.connect(&endpoint, remote_addr, host).await?;
+        let mut timing = ResourceTiming::default();
+        let sender = self
+            .get_or_create_connection(&host, port, start_time, &mut timing)
+            .await?;
 
         // Perform HTTP/3 request
-        let response = self
-            .perform_request(connection, request, start_time)
+        self.perform_request(sender, http_request, body, request, start_time, timing)
+            .await
+    }
+
+    /// Get a pooled connection to `host:port`, connecting if there is none
+    ///
+    /// Concurrent requests to the same server share a single connection: the
+    /// first one connects and the others wait for its handshake. The pool lock
+    /// is only held to find or add the server's slot, so connecting to one
+    /// server doesn't hold up requests to others.
+    ///
+    /// Records the DNS lookup and connection milestones in `timing`. For a
+    /// reused connection they are left at the fetch start.
+    async fn get_or_create_connection(
+        &self,
+        host: &str,
+        port: u16,
+        start_time: Instant,
+        timing: &mut ResourceTiming,
+    ) -> NetworkResult<H3Sender> {
+        let slot = Arc::clone(
+            self.connections
+                .lock()
+                .await
+                .entry((host.to_string(), port))
+                .or_default(),
+        );
+        let mut slot = slot.lock().await;
+
+        if let Some(pooled) = slot.as_ref().filter(|pooled| !pooled.quic.is_closed()) {
+            debug!("Reusing HTTP/3 connection to {}:{}", host, port);
+            return Ok(pooled.sender.clone());
+        }
+
+        let pooled = self
+            .create_connection(host, port, start_time, timing)
             .await?;
+        let sender = pooled.sender.clone();
+        *slot = Some(pooled);
+        Ok(sender)
+    }
 
-        Ok(response)
+    /// Open a new HTTP/3 connection to `host:port`
+    async fn create_connection(
+        &self,
+        host: &str,
+        port: u16,
+        start_time: Instant,
+        timing: &mut ResourceTiming,
+    ) -> NetworkResult<PooledConnection> {
+        debug!("Creating new HTTP/3 connection to {}:{}", host, port);
+
+        // Create endpoint if not exists
+        let endpoint = self.get_or_create_endpoint().await?;
+
+        // Resolve address
+        timing.domain_lookup_start = elapsed_ms(start_time);
+        let remote_addr = self.resolve_address(host, port).await?;
+        timing.domain_lookup_end = elapsed_ms(start_time);
+
+        // The QUIC handshake includes the TLS handshake
+        timing.connect_start = elapsed_ms(start_time);
+        timing.secure_connection_start = timing.connect_start;
+        let quic = self.connect(&endpoint, remote_addr, host).await?;
+
+        let (mut driver, sender) =
+            h3::client::new(h3_quinn::Connection::new(quic.quinn_connection().clone()))
+                .await
+                .map_err(h3_error)?;
+        timing.connect_end = elapsed_ms(start_time);
+
+        // The driver processes the control streams and must be polled for the
+        // lifetime of the connection
+        tokio::spawn(async move {
+            if let Err(e) = std::future::poll_fn(|cx| driver.poll_close(cx)).await {
+                debug!("HTTP/3 connection closed: {}", e);
+            }
+        });
+
+        Ok(PooledConnection { quic, sender })
+    }
+
+    /// Number of open pooled connections
+    ///
+    /// Connections still being established are not counted.
+    pub async fn connection_count(&self) -> usize {
+        self.connections
+            .lock()
+            .await
+            .values()
+            .filter(|slot| {
+                slot.try_lock()
+                    .is_ok_and(|slot| slot.as_ref().is_some_and(|pooled| !pooled.quic.is_closed()))
+            })
+            .count()
     }
 
     /// Get or create QUIC endpoint
@@ -177,17 +330,21 @@ impl Http3Client {
                 .map_err(|e| NetworkError::TlsError(format!("Failed to add certificate: {}", e)))?;
         }
 
-        // Create Quinn client config with root certificates
-        let mut client_config = ClientConfig::with_root_certificates(roots);
+        // Create Quinn client config with root certificates, advertising h3.
+        // Early data is only allowed when the configuration enables 0-RTT.
+        let mut crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        crypto.alpn_protocols = vec![H3_ALPN.to_vec()];
+        crypto.enable_early_data = self.config.enable_0rtt;
+        let mut client_config = ClientConfig::new(Arc::new(crypto));
 
         // Configure transport
-        let mut transport_config = quinn::TransportConfig::default();
-        let idle_timeout = self.config.max_idle_timeout.try_into()
-            .map_err(|_| NetworkError::InvalidConfig("Invalid max_idle_timeout value".to_string()))?;
-        transport_config.max_idle_timeout(Some(idle_timeout));
-        let initial_mtu = self.config.max_udp_payload_size.try_into()
-            .map_err(|_| NetworkError::InvalidConfig("Invalid max_udp_payload_size value".to_string()))?;
-        transport_config.initial_mtu(initial_mtu);
+        let transport_config = self
+            .config
+            .transport_config()
+            .map_err(NetworkError::InvalidConfig)?;
 
         client_config.transport_config(Arc::new(transport_config));
 
@@ -206,6 +363,23 @@ impl Http3Client {
 
     /// Resolve hostname to socket address
     async fn resolve_address(&self, host: &str, port: u16) -> NetworkResult<SocketAddr> {
+        if let Some(resolver) = &self.resolver {
+            // IP literals aren't looked up
+            let literal = host.trim_start_matches('[').trim_end_matches(']');
+            if let Ok(ip) = literal.parse::<IpAddr>() {
+                return Ok(SocketAddr::new(ip, port));
+            }
+            let ip = resolver
+                .resolve(host.to_string())
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    NetworkError::DnsError(format!("Could not resolve host: {}", host))
+                })?;
+            return Ok(SocketAddr::new(ip, port));
+        }
+
         let addr_str = format!("{}:{}", host, port);
 
         // Use blocking DNS resolution in a spawn_blocking task
@@ -229,56 +403,92 @@ impl Http3Client {
         remote_addr: SocketAddr,
         server_name: &str,
     ) -> NetworkResult<QuicConnection> {
-        let connection = endpoint
+        let mut connecting = endpoint
             .connect(remote_addr, server_name)
-            .map_err(|e| NetworkError::ConnectionFailed(format!("Connection failed: {}", e)))?
-            .await
             .map_err(|e| NetworkError::ConnectionFailed(format!("Connection failed: {}", e)))?;
 
-        Ok(QuicConnection::new(connection, remote_addr))
+        // Attempt 0-RTT when enabled; this only succeeds if a session ticket
+        // from a previous connection to the server is cached
+        if *self.enable_0rtt.lock().await {
+            match connecting.into_0rtt() {
+                Ok((connection, accepted)) => {
+                    let zero_rtt = ZeroRttStatus::default();
+                    let tracker = zero_rtt.clone();
+                    tokio::spawn(async move { tracker.track(accepted).await });
+                    return Ok(
+                        QuicConnection::with_zero_rtt(connection, remote_addr, zero_rtt)
+                            .with_migration(
+                                endpoint.clone(),
+                                self.config.enable_connection_migration,
+                            ),
+                    );
+                }
+                Err(fallback) => connecting = fallback,
+            }
+        }
+
+        let connection = connecting.await.map_err(connection_error)?;
+
+        Ok(QuicConnection::new(connection, remote_addr)
+            .with_migration(endpoint.clone(), self.config.enable_connection_migration))
     }
 
     /// Perform HTTP/3 request over QUIC connection
+    ///
+    /// Sends the request head and body on a new stream, then reads the
+    /// response head, body and trailers.
     async fn perform_request(
         &self,
-        _connection: QuicConnection,
+        mut sender: H3Sender,
+        http_request: http::Request<()>,
+        body: Option<Bytes>,
         request: NetworkRequest,
         start_time: Instant,
+        mut timing: ResourceTiming,
     ) -> NetworkResult<NetworkResponse> {
-        // For now, return a minimal response
-        // Full HTTP/3 integration with h3 crate would go here
+        timing.request_start = elapsed_ms(start_time);
+        let mut stream = sender.send_request(http_request).await.map_err(h3_error)?;
+        if let Some(body) = body {
+            stream.send_data(body).await.map_err(h3_error)?;
+        }
+        stream.finish().await.map_err(h3_error)?;
+
+        let response = stream.recv_response().await.map_err(h3_error)?;
+        timing.response_start = elapsed_ms(start_time);
 
-        let elapsed = start_time.elapsed();
+        let mut body_bytes = Vec::new();
+        while let Some(chunk) = stream.recv_data().await.map_err(h3_error)? {
+            body_bytes.put(chunk);
+        }
+        let trailers = stream.recv_trailers().await.map_err(h3_error)?;
+        timing.response_end = elapsed_ms(start_time);
+
+        timing.transfer_size = body_bytes.len() as u64;
+        timing.encoded_body_size = body_bytes.len() as u64;
+        timing.decoded_body_size = body_bytes.len() as u64;
+
+        let status = response.status();
 
         Ok(NetworkResponse {
             url: request.url,
-            status: 200,
-            status_text: "OK".to_string(),
-            headers: HeaderMap::new(),
-            body: ResponseBody::Empty,
+            status: status.as_u16(),
+            status_text: status.canonical_reason().unwrap_or("").to_string(),
+            headers: response.headers().clone(),
+            body: ResponseBody::Bytes(body_bytes),
             redirected: false,
             type_: ResponseType::Basic,
-            timing: ResourceTiming {
-                start_time: 0.0,
-                redirect_start: 0.0,
-                redirect_end: 0.0,
-                fetch_start: 0.0,
-                domain_lookup_start: 0.0,
-                domain_lookup_end: 0.0,
-                connect_start: 0.0,
-                connect_end: elapsed.as_millis() as f64,
-                secure_connection_start: 0.0,
-                request_start: elapsed.as_millis() as f64,
-                response_start: elapsed.as_millis() as f64,
-                response_end: elapsed.as_millis() as f64,
-                transfer_size: 0,
-                encoded_body_size: 0,
-                decoded_body_size: 0,
-            },
+            timing,
+            trailers,
+            source: ResponseSource::Network,
         })
     }
 }
 
+/// Milliseconds elapsed since `start`
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
 impl std::fmt::Debug for Http3Client {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Http3Client")
@@ -296,6 +506,7 @@ impl Default for Http3Client {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use http::HeaderMap;
     use network_types::HttpMethod;
     use url::Url;
 
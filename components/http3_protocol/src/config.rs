@@ -1,6 +1,48 @@
 //! HTTP/3 configuration
 
-use std::time::Duration;
+use quinn::congestion::{BbrConfig, Controller, ControllerFactory, CubicConfig, NewRenoConfig};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Smallest accepted stream or connection receive window, in bytes
+const MIN_RECEIVE_WINDOW: u64 = 16 * 1024;
+
+/// Largest value representable as a QUIC variable-length integer (2^62 - 1)
+const MAX_VARINT: u64 = (1 << 62) - 1;
+
+/// QUIC congestion control algorithm
+///
+/// Selects the congestion controller used by the underlying quinn transport.
+///
+/// # Example
+///
+/// ```rust
+/// use http3_protocol::{CongestionController, Http3Config};
+///
+/// let config = Http3Config::default()
+///     .with_congestion_controller(CongestionController::Bbr);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CongestionController {
+    /// CUBIC (RFC 8312), quinn's default controller
+    Cubic,
+    /// BBR, which performs better on lossy, high bandwidth-delay product links
+    Bbr,
+    /// NewReno (RFC 6582)
+    NewReno,
+}
+
+impl ControllerFactory for CongestionController {
+    fn build(&self, now: Instant, current_mtu: u16) -> Box<dyn Controller> {
+        match self {
+            CongestionController::Cubic => Arc::new(CubicConfig::default()).build(now, current_mtu),
+            CongestionController::Bbr => Arc::new(BbrConfig::default()).build(now, current_mtu),
+            CongestionController::NewReno => {
+                Arc::new(NewRenoConfig::default()).build(now, current_mtu)
+            }
+        }
+    }
+}
 
 /// Configuration for HTTP/3 client
 ///
@@ -46,6 +88,31 @@ pub struct Http3Config {
     /// WiFi to cellular). This is a core QUIC feature for mobile devices.
     /// Default: true
     pub enable_connection_migration: bool,
+
+    /// QUIC congestion control algorithm
+    ///
+    /// When `None`, the quinn default controller is used.
+    /// Default: None
+    pub congestion_controller: Option<CongestionController>,
+
+    /// Maximum number of concurrent bidirectional streams the peer may open
+    ///
+    /// Each HTTP/3 request uses one bidirectional stream. When `None`, the quinn
+    /// default is used.
+    /// Default: None
+    pub max_concurrent_streams: Option<u32>,
+
+    /// Per-stream flow-control receive window in bytes
+    ///
+    /// When `None`, the quinn default is used.
+    /// Default: None
+    pub stream_receive_window: Option<u64>,
+
+    /// Connection-wide flow-control receive window in bytes
+    ///
+    /// When `None`, the quinn default is used.
+    /// Default: None
+    pub connection_receive_window: Option<u64>,
 }
 
 impl Default for Http3Config {
@@ -55,6 +122,10 @@ impl Default for Http3Config {
             max_udp_payload_size: 1350,
             enable_0rtt: false,
             enable_connection_migration: true,
+            congestion_controller: None,
+            max_concurrent_streams: None,
+            stream_receive_window: None,
+            connection_receive_window: None,
         }
     }
 }
@@ -156,6 +227,144 @@ impl Http3Config {
         self
     }
 
+    /// Set the QUIC congestion control algorithm
+    ///
+    /// # Arguments
+    ///
+    /// * `controller` - Congestion controller to use for new connections
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use http3_protocol::{CongestionController, Http3Config};
+    ///
+    /// let config = Http3Config::default()
+    ///     .with_congestion_controller(CongestionController::Bbr);
+    /// ```
+    pub fn with_congestion_controller(mut self, controller: CongestionController) -> Self {
+        self.congestion_controller = Some(controller);
+        self
+    }
+
+    /// Set the maximum number of concurrent bidirectional streams
+    ///
+    /// # Arguments
+    ///
+    /// * `streams` - Maximum concurrent streams the peer may open (at least 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `streams` is 0
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use http3_protocol::Http3Config;
+    ///
+    /// let config = Http3Config::default().with_max_concurrent_streams(256);
+    /// ```
+    pub fn with_max_concurrent_streams(mut self, streams: u32) -> Self {
+        assert!(streams >= 1, "Max concurrent streams must be at least 1");
+        self.max_concurrent_streams = Some(streams);
+        self
+    }
+
+    /// Set the per-stream flow-control receive window
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Receive window per stream in bytes (16 KiB to 2^62 - 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is outside the valid range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use http3_protocol::Http3Config;
+    ///
+    /// let config = Http3Config::default().with_stream_receive_window(4 * 1024 * 1024);
+    /// ```
+    pub fn with_stream_receive_window(mut self, bytes: u64) -> Self {
+        assert!(
+            (MIN_RECEIVE_WINDOW..=MAX_VARINT).contains(&bytes),
+            "Stream receive window must be between 16384 and 2^62 - 1 bytes"
+        );
+        self.stream_receive_window = Some(bytes);
+        self
+    }
+
+    /// Set the connection-wide flow-control receive window
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Receive window across all streams in bytes (16 KiB to 2^62 - 1)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is outside the valid range
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use http3_protocol::Http3Config;
+    ///
+    /// let config = Http3Config::default().with_connection_receive_window(16 * 1024 * 1024);
+    /// ```
+    pub fn with_connection_receive_window(mut self, bytes: u64) -> Self {
+        assert!(
+            (MIN_RECEIVE_WINDOW..=MAX_VARINT).contains(&bytes),
+            "Connection receive window must be between 16384 and 2^62 - 1 bytes"
+        );
+        self.connection_receive_window = Some(bytes);
+        self
+    }
+
+    /// Build the quinn transport configuration for this config
+    ///
+    /// # Returns
+    ///
+    /// `Ok(TransportConfig)` on success, `Err(String)` with error message if a value
+    /// cannot be represented in the QUIC transport parameters
+    pub(crate) fn transport_config(&self) -> Result<quinn::TransportConfig, String> {
+        let mut transport_config = quinn::TransportConfig::default();
+
+        let idle_timeout = self
+            .max_idle_timeout
+            .try_into()
+            .map_err(|_| "Invalid max_idle_timeout value".to_string())?;
+        transport_config.max_idle_timeout(Some(idle_timeout));
+
+        let initial_mtu = self
+            .max_udp_payload_size
+            .try_into()
+            .map_err(|_| "Invalid max_udp_payload_size value".to_string())?;
+        transport_config.initial_mtu(initial_mtu);
+
+        if let Some(controller) = self.congestion_controller {
+            transport_config.congestion_controller_factory(controller);
+        }
+
+        if let Some(streams) = self.max_concurrent_streams {
+            transport_config.max_concurrent_bidi_streams(streams.into());
+        }
+
+        if let Some(window) = self.stream_receive_window {
+            let window = quinn::VarInt::from_u64(window)
+                .map_err(|_| "Invalid stream_receive_window value".to_string())?;
+            transport_config.stream_receive_window(window);
+        }
+
+        if let Some(window) = self.connection_receive_window {
+            let window = quinn::VarInt::from_u64(window)
+                .map_err(|_| "Invalid connection_receive_window value".to_string())?;
+            transport_config.receive_window(window);
+        }
+
+        Ok(transport_config)
+    }
+
     /// Validate the configuration
     ///
     /// Checks that all configuration values are valid.
@@ -172,6 +381,38 @@ impl Http3Config {
                 "UDP payload size must not exceed 65527 (UDP maximum - headers)".to_string(),
             );
         }
+        if self.max_concurrent_streams == Some(0) {
+            return Err("Max concurrent streams must be at least 1".to_string());
+        }
+        for (name, window) in [
+            ("Stream", self.stream_receive_window),
+            ("Connection", self.connection_receive_window),
+        ] {
+            if let Some(window) = window {
+                if window < MIN_RECEIVE_WINDOW {
+                    return Err(format!(
+                        "{} receive window must be at least {} bytes",
+                        name, MIN_RECEIVE_WINDOW
+                    ));
+                }
+                if window > MAX_VARINT {
+                    return Err(format!(
+                        "{} receive window must not exceed 2^62 - 1 bytes",
+                        name
+                    ));
+                }
+            }
+        }
+        if let (Some(stream), Some(connection)) =
+            (self.stream_receive_window, self.connection_receive_window)
+        {
+            if connection < stream {
+                return Err(
+                    "Connection receive window must not be smaller than stream receive window"
+                        .to_string(),
+                );
+            }
+        }
         Ok(())
     }
 }
@@ -187,6 +428,7 @@ mod tests {
         assert_eq!(config.max_udp_payload_size, 1350);
         assert!(!config.enable_0rtt);
         assert!(config.enable_connection_migration);
+        assert_eq!(config.congestion_controller, None);
     }
 
     #[test]
@@ -227,6 +469,89 @@ mod tests {
         assert!(config.validate().is_err());
     }
 
+    #[test]
+    fn test_with_congestion_controller_bbr() {
+        let config = Http3Config::default().with_congestion_controller(CongestionController::Bbr);
+        assert_eq!(
+            config.congestion_controller,
+            Some(CongestionController::Bbr)
+        );
+        assert!(config.transport_config().is_ok());
+    }
+
+    #[test]
+    fn test_congestion_controller_builds_matching_controller() {
+        let now = Instant::now();
+
+        let bbr = CongestionController::Bbr.build(now, 1200).into_any();
+        assert!(bbr.downcast::<quinn::congestion::Bbr>().is_ok());
+
+        let cubic = CongestionController::Cubic.build(now, 1200).into_any();
+        assert!(cubic.downcast::<quinn::congestion::Cubic>().is_ok());
+
+        let new_reno = CongestionController::NewReno.build(now, 1200).into_any();
+        assert!(new_reno.downcast::<quinn::congestion::NewReno>().is_ok());
+    }
+
+    #[test]
+    fn test_flow_control_builders() {
+        let config = Http3Config::default()
+            .with_max_concurrent_streams(42)
+            .with_stream_receive_window(65536)
+            .with_connection_receive_window(1048576);
+
+        assert_eq!(config.max_concurrent_streams, Some(42));
+        assert_eq!(config.stream_receive_window, Some(65536));
+        assert_eq!(config.connection_receive_window, Some(1048576));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_flow_control_carried_into_transport_config() {
+        let transport = Http3Config::default()
+            .with_max_concurrent_streams(42)
+            .with_stream_receive_window(65536)
+            .with_connection_receive_window(1048576)
+            .transport_config()
+            .unwrap();
+
+        let debug = format!("{:?}", transport);
+        assert!(debug.contains("max_concurrent_bidi_streams: 42"));
+        assert!(debug.contains("stream_receive_window: 65536"));
+        assert!(debug.contains("receive_window: 1048576"));
+    }
+
+    #[test]
+    fn test_validate_receive_window_too_small() {
+        let config = Http3Config {
+            stream_receive_window: Some(1024),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_connection_window_smaller_than_stream_window() {
+        let config = Http3Config {
+            stream_receive_window: Some(1048576),
+            connection_receive_window: Some(65536),
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Max concurrent streams must be at least 1")]
+    fn test_with_zero_concurrent_streams_panics() {
+        Http3Config::default().with_max_concurrent_streams(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Stream receive window must be between")]
+    fn test_with_tiny_stream_window_panics() {
+        Http3Config::default().with_stream_receive_window(100);
+    }
+
     #[test]
     #[should_panic(expected = "UDP payload size must be between 1200 and 65527")]
     fn test_with_invalid_udp_size_panics() {
@@ -1,8 +1,39 @@
 //! QUIC connection management
 
 use crate::error::Http3Error;
-use quinn::{Connection, RecvStream, SendStream};
+use quinn::{Connection, Endpoint, RecvStream, SendStream};
+use std::future::Future;
 use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+/// Outcome of a 0-RTT attempt
+///
+/// Shared between the connection and the task awaiting the handshake, so the
+/// result can be read synchronously once it is known.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ZeroRttStatus {
+    accepted: Arc<Mutex<Option<bool>>>,
+}
+
+impl ZeroRttStatus {
+    /// Record whether the server accepted early data
+    pub(crate) fn record(&self, accepted: bool) {
+        *self.accepted.lock().expect("0-RTT status lock poisoned") = Some(accepted);
+    }
+
+    /// Wait for the handshake outcome and record it
+    pub(crate) async fn track<F>(&self, outcome: F)
+    where
+        F: Future<Output = bool>,
+    {
+        self.record(outcome.await);
+    }
+
+    /// Recorded outcome, or `None` if not yet known
+    pub(crate) fn get(&self) -> Option<bool> {
+        *self.accepted.lock().expect("0-RTT status lock poisoned")
+    }
+}
 
 /// QUIC connection wrapper
 ///
@@ -26,6 +57,12 @@ pub struct QuicConnection {
     connection: Connection,
     /// Remote address
     remote_addr: SocketAddr,
+    /// Outcome of the 0-RTT attempt, if one was made
+    zero_rtt: ZeroRttStatus,
+    /// Endpoint owning the UDP socket, used for migration
+    endpoint: Option<Endpoint>,
+    /// Whether connection migration is enabled
+    migration_enabled: bool,
 }
 
 impl QuicConnection {
@@ -39,9 +76,45 @@ impl QuicConnection {
         Self {
             connection,
             remote_addr,
+            zero_rtt: ZeroRttStatus::default(),
+            endpoint: None,
+            migration_enabled: false,
         }
     }
 
+    /// Create a QUIC connection wrapper for a connection established with 0-RTT
+    ///
+    /// # Arguments
+    ///
+    /// * `connection` - Quinn QUIC connection
+    /// * `remote_addr` - Remote server address
+    /// * `zero_rtt` - Status updated once the handshake completes
+    pub(crate) fn with_zero_rtt(
+        connection: Connection,
+        remote_addr: SocketAddr,
+        zero_rtt: ZeroRttStatus,
+    ) -> Self {
+        Self {
+            connection,
+            remote_addr,
+            zero_rtt,
+            endpoint: None,
+            migration_enabled: false,
+        }
+    }
+
+    /// Attach the endpoint used for connection migration
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Endpoint that owns the connection's UDP socket
+    /// * `enabled` - Whether migration is enabled in the configuration
+    pub(crate) fn with_migration(mut self, endpoint: Endpoint, enabled: bool) -> Self {
+        self.endpoint = Some(endpoint);
+        self.migration_enabled = enabled;
+        self
+    }
+
     /// Open a new bidirectional stream
     ///
     /// Opens a new QUIC stream for HTTP/3 communication. Each HTTP/3 request
@@ -111,23 +184,116 @@ impl QuicConnection {
         self.remote_addr
     }
 
+    /// Migrate the connection to a new local address
+    ///
+    /// Binds a new UDP socket to `new_local_addr` and moves the connection onto it,
+    /// initiating QUIC path validation with the peer. Useful when the device switches
+    /// networks, e.g. from Wi-Fi to cellular. Use port 0 to let the OS pick a port.
+    ///
+    /// The socket belongs to the client's shared endpoint, so every connection
+    /// opened by the same client migrates together.
+    ///
+    /// quinn does not expose the peer's `disable_active_migration` transport
+    /// parameter; a peer that forbids migration drops packets from the new path and
+    /// the connection fails with `Http3Error::QuicError` once it times out.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_local_addr` - Local address to bind the new socket to
+    ///
+    /// # Errors
+    ///
+    /// Returns `Http3Error::MigrationFailed` if migration is disabled, the address
+    /// family does not match the remote address, or the connection has no endpoint,
+    /// and `Http3Error::Io` if the socket cannot be bound
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use http3_protocol::QuicConnection;
+    /// # fn example(connection: &QuicConnection) -> Result<(), Box<dyn std::error::Error>> {
+    /// connection.migrate("0.0.0.0:0".parse()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn migrate(&self, new_local_addr: SocketAddr) -> Result<(), Http3Error> {
+        if !self.migration_enabled {
+            return Err(Http3Error::MigrationFailed(
+                "Connection migration is disabled".to_string(),
+            ));
+        }
+        validate_migration_address(new_local_addr, self.remote_addr)?;
+
+        let endpoint = self.endpoint.as_ref().ok_or_else(|| {
+            Http3Error::MigrationFailed("Connection has no endpoint to rebind".to_string())
+        })?;
+
+        let socket = std::net::UdpSocket::bind(new_local_addr)?;
+        endpoint.rebind(socket)?;
+        Ok(())
+    }
+
+    /// Whether the server accepted 0-RTT early data
+    ///
+    /// Returns `None` if 0-RTT was not attempted (disabled, or no session ticket was
+    /// cached for the server) or the handshake has not completed yet. Returns
+    /// `Some(false)` if the server rejected early data; streams opened before the
+    /// handshake completed must then be retried over 1-RTT.
+    ///
+    /// Non-idempotent requests should not be sent until this returns `Some(true)`,
+    /// because 0-RTT data can be replayed by an attacker.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use http3_protocol::QuicConnection;
+    /// # fn example(connection: &QuicConnection) {
+    /// if connection.zero_rtt_accepted() == Some(false) {
+    ///     // Early data was rejected and must be resent
+    /// }
+    /// # }
+    /// ```
+    pub fn zero_rtt_accepted(&self) -> Option<bool> {
+        self.zero_rtt.get()
+    }
+
     /// Check if connection is still alive
     ///
     /// # Returns
     ///
     /// `true` if connection is open, `false` if closed
     pub fn is_closed(&self) -> bool {
-        // Check if the underlying connection is in closing/draining state
-        // Note: Quinn doesn't expose a direct is_closed() method,
-        // so we rely on whether operations would fail
-        false // Simplified for now
+        // A close reason is only recorded once the connection is closing or lost
+        self.connection.close_reason().is_some()
+    }
+
+    /// Underlying quinn connection, used to run HTTP/3 on top of it
+    pub(crate) fn quinn_connection(&self) -> &Connection {
+        &self.connection
     }
 }
 
+/// Check that a migration target can reach the remote address
+///
+/// The new local address must use the same IP family as the remote peer.
+fn validate_migration_address(
+    new_local_addr: SocketAddr,
+    remote_addr: SocketAddr,
+) -> Result<(), Http3Error> {
+    if new_local_addr.is_ipv4() != remote_addr.is_ipv4() {
+        return Err(Http3Error::MigrationFailed(format!(
+            "Local address {} does not match the address family of remote {}",
+            new_local_addr, remote_addr
+        )));
+    }
+    Ok(())
+}
+
 impl std::fmt::Debug for QuicConnection {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QuicConnection")
             .field("remote_addr", &self.remote_addr)
+            .field("zero_rtt_accepted", &self.zero_rtt_accepted())
             .field("closed", &self.is_closed())
             .finish()
     }
@@ -135,6 +301,89 @@ impl std::fmt::Debug for QuicConnection {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_migration_address_same_family() {
+        let remote: SocketAddr = "203.0.113.1:443".parse().unwrap();
+        assert!(validate_migration_address("0.0.0.0:0".parse().unwrap(), remote).is_ok());
+
+        let remote: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+        assert!(validate_migration_address("[::]:0".parse().unwrap(), remote).is_ok());
+    }
+
+    #[test]
+    fn test_validate_migration_address_family_mismatch() {
+        let remote: SocketAddr = "203.0.113.1:443".parse().unwrap();
+        let result = validate_migration_address("[::]:0".parse().unwrap(), remote);
+        assert!(matches!(result, Err(Http3Error::MigrationFailed(_))));
+
+        let remote: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+        let result = validate_migration_address("0.0.0.0:0".parse().unwrap(), remote);
+        assert!(matches!(result, Err(Http3Error::MigrationFailed(_))));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires network access to a public HTTP/3 server"]
+    async fn test_migrate_live_connection() {
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in rustls_native_certs::load_native_certs().unwrap() {
+            roots.add(&rustls::Certificate(cert.0)).unwrap();
+        }
+        let mut crypto = rustls::ClientConfig::builder()
+            .with_safe_defaults()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        crypto.alpn_protocols = vec![b"h3".to_vec()];
+
+        let mut endpoint = Endpoint::client("0.0.0.0:0".parse().unwrap()).unwrap();
+        endpoint.set_default_client_config(quinn::ClientConfig::new(std::sync::Arc::new(crypto)));
+
+        let remote = tokio::net::lookup_host("cloudflare-quic.com:443")
+            .await
+            .unwrap()
+            .find(SocketAddr::is_ipv4)
+            .unwrap();
+        let connection = endpoint
+            .connect(remote, "cloudflare-quic.com")
+            .unwrap()
+            .await
+            .unwrap();
+        let connection = QuicConnection::new(connection, remote).with_migration(endpoint, true);
+
+        connection.migrate("0.0.0.0:0".parse().unwrap()).unwrap();
+        assert!(connection.open_stream().await.is_ok());
+    }
+
+    #[test]
+    fn test_zero_rtt_status_not_attempted() {
+        let status = ZeroRttStatus::default();
+        assert_eq!(status.get(), None);
+    }
+
+    #[tokio::test]
+    async fn test_zero_rtt_status_accepted() {
+        let status = ZeroRttStatus::default();
+        status.track(async { true }).await;
+        assert_eq!(status.get(), Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_zero_rtt_status_rejected_shared_between_clones() {
+        let status = ZeroRttStatus::default();
+        let tracker = status.clone();
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let handle = tokio::spawn(async move {
+            tracker.track(async { rx.await.unwrap_or(false) }).await;
+        });
+
+        assert_eq!(status.get(), None);
+        tx.send(false).unwrap();
+        handle.await.unwrap();
+        assert_eq!(status.get(), Some(false));
+    }
+
     #[test]
     fn test_quic_connection_debug() {
         // Note: We can't easily create a Quinn connection in tests without
@@ -29,6 +29,10 @@ pub enum Http3Error {
     #[error("Stream error: {0}")]
     StreamError(String),
 
+    /// Connection migration error
+    #[error("Connection migration failed: {0}")]
+    MigrationFailed(String),
+
     /// Invalid configuration
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
@@ -65,18 +69,31 @@ pub enum Http3Error {
 impl From<Http3Error> for network_errors::NetworkError {
     fn from(err: Http3Error) -> Self {
         match err {
-            Http3Error::QuicError(e) => {
-                network_errors::NetworkError::ConnectionFailed(e.to_string())
-            }
-            Http3Error::QuicWriteError(e) => {
-                network_errors::NetworkError::ConnectionFailed(e.to_string())
-            }
-            Http3Error::QuicReadError(e) => {
-                network_errors::NetworkError::ConnectionFailed(e.to_string())
-            }
-            Http3Error::H3Error(e) => network_errors::NetworkError::ProtocolError(e),
+            Http3Error::QuicError(e) => connection_error(e),
+            Http3Error::QuicWriteError(e) => match e {
+                quinn::WriteError::Stopped(code) => {
+                    network_errors::NetworkError::QuicStreamReset(code.into_inner())
+                }
+                quinn::WriteError::ConnectionLost(e) => connection_error(e),
+                quinn::WriteError::ZeroRttRejected => network_errors::NetworkError::ZeroRttRejected,
+                e @ quinn::WriteError::UnknownStream => {
+                    network_errors::NetworkError::Http3Error(e.to_string())
+                }
+            },
+            Http3Error::QuicReadError(e) => match e {
+                quinn::ReadError::Reset(code) => {
+                    network_errors::NetworkError::QuicStreamReset(code.into_inner())
+                }
+                quinn::ReadError::ConnectionLost(e) => connection_error(e),
+                quinn::ReadError::ZeroRttRejected => network_errors::NetworkError::ZeroRttRejected,
+                e @ (quinn::ReadError::UnknownStream | quinn::ReadError::IllegalOrderedRead) => {
+                    network_errors::NetworkError::Http3Error(e.to_string())
+                }
+            },
+            Http3Error::H3Error(e) => network_errors::NetworkError::Http3Error(e),
             Http3Error::ConnectionClosed(e) => network_errors::NetworkError::ConnectionFailed(e),
-            Http3Error::StreamError(e) => network_errors::NetworkError::ProtocolError(e),
+            Http3Error::StreamError(e) => network_errors::NetworkError::Http3Error(e),
+            Http3Error::MigrationFailed(e) => network_errors::NetworkError::ConnectionFailed(e),
             Http3Error::InvalidConfig(e) => network_errors::NetworkError::Other(e),
             Http3Error::TlsError(e) => network_errors::NetworkError::TlsError(e),
             Http3Error::DnsError(e) => network_errors::NetworkError::DnsError(e),
@@ -90,3 +107,115 @@ impl From<Http3Error> for network_errors::NetworkError {
         }
     }
 }
+
+/// Map a lost QUIC connection to the most specific `NetworkError`
+///
+/// Transport-level closes keep their error code; an application close
+/// carries an HTTP/3 error code and becomes `Http3Error`. Resets and idle
+/// timeouts stay retryable.
+pub(crate) fn connection_error(err: quinn::ConnectionError) -> network_errors::NetworkError {
+    use network_errors::NetworkError;
+
+    match err {
+        quinn::ConnectionError::TransportError(e) => NetworkError::QuicTransportError {
+            code: e.code.into(),
+            reason: e.reason,
+        },
+        quinn::ConnectionError::ConnectionClosed(close) => NetworkError::QuicTransportError {
+            code: close.error_code.into(),
+            reason: String::from_utf8_lossy(&close.reason).into_owned(),
+        },
+        e @ quinn::ConnectionError::ApplicationClosed(_) => NetworkError::Http3Error(e.to_string()),
+        quinn::ConnectionError::TimedOut => {
+            NetworkError::Timeout(std::time::Duration::from_secs(0))
+        }
+        e @ (quinn::ConnectionError::VersionMismatch
+        | quinn::ConnectionError::Reset
+        | quinn::ConnectionError::LocallyClosed) => NetworkError::ConnectionFailed(e.to_string()),
+    }
+}
+
+/// Map an `h3` protocol error to `NetworkError::Http3Error`
+pub(crate) fn h3_error(err: h3::Error) -> network_errors::NetworkError {
+    Http3Error::H3Error(err.to_string()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network_errors::NetworkError;
+    use quinn::{ConnectionClose, ConnectionError, VarInt};
+    use quinn_proto::{TransportError, TransportErrorCode};
+
+    #[test]
+    fn test_transport_error_keeps_code() {
+        let err = ConnectionError::TransportError(TransportError {
+            code: TransportErrorCode::crypto(40),
+            frame: None,
+            reason: "handshake failure".to_string(),
+        });
+
+        match NetworkError::from(Http3Error::QuicError(err)) {
+            NetworkError::QuicTransportError { code, reason } => {
+                assert_eq!(code, 0x0128);
+                assert_eq!(reason, "handshake failure");
+            }
+            other => panic!("Expected QuicTransportError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_peer_connection_close_keeps_code() {
+        let err = ConnectionError::ConnectionClosed(ConnectionClose {
+            error_code: TransportErrorCode::PROTOCOL_VIOLATION,
+            frame_type: None,
+            reason: bytes::Bytes::from_static(b"bad frame"),
+        });
+
+        let err = NetworkError::from(Http3Error::QuicError(err));
+        assert!(matches!(
+            err,
+            NetworkError::QuicTransportError { code: 0x0a, ref reason } if reason == "bad frame"
+        ));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_stream_reset_is_distinct_from_connection_failure() {
+        let err = NetworkError::from(Http3Error::QuicReadError(quinn::ReadError::Reset(
+            VarInt::from_u32(0x10b),
+        )));
+        assert!(matches!(err, NetworkError::QuicStreamReset(0x10b)));
+        assert!(err.is_retryable());
+
+        let err = NetworkError::from(Http3Error::QuicWriteError(quinn::WriteError::Stopped(
+            VarInt::from_u32(0x10c),
+        )));
+        assert!(matches!(err, NetworkError::QuicStreamReset(0x10c)));
+        assert!(!err.is_retryable());
+    }
+
+    #[test]
+    fn test_zero_rtt_rejected() {
+        let err = NetworkError::from(Http3Error::QuicWriteError(
+            quinn::WriteError::ZeroRttRejected,
+        ));
+        assert!(matches!(err, NetworkError::ZeroRttRejected));
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_lost_connection_mapping() {
+        let err = NetworkError::from(Http3Error::QuicReadError(quinn::ReadError::ConnectionLost(
+            ConnectionError::TimedOut,
+        )));
+        assert!(matches!(err, NetworkError::Timeout(_)));
+
+        let err = NetworkError::from(Http3Error::QuicError(ConnectionError::Reset));
+        assert!(matches!(err, NetworkError::ConnectionFailed(_)));
+        assert!(err.is_retryable());
+
+        let err = NetworkError::from(Http3Error::H3Error("H3_FRAME_UNEXPECTED".to_string()));
+        assert!(matches!(err, NetworkError::Http3Error(_)));
+    }
+}
@@ -48,11 +48,14 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+mod alt_svc;
 mod client;
 mod config;
 mod connection;
 mod error;
+mod request;
 
+pub use alt_svc::{parse_alt_svc, AltService, AltSvcCache, AltSvcValue};
 pub use client::Http3Client;
-pub use config::Http3Config;
+pub use config::{CongestionController, Http3Config};
 pub use connection::QuicConnection;
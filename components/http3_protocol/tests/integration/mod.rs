@@ -1 +1,4 @@
 // Integration tests for http3_protocol
+
+mod test_connect;
+mod test_fetch;
@@ -0,0 +1,77 @@
+//! Connecting to servers
+
+use async_trait::async_trait;
+use dns_resolver::DnsResolver;
+use http3_protocol::{Http3Client, Http3Config};
+use network_errors::{NetworkError, NetworkResult};
+use network_types::NetworkRequest;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use url::Url;
+
+/// Resolver that answers every lookup with the unspecified address
+#[derive(Default)]
+struct CountingResolver {
+    lookups: AtomicUsize,
+}
+
+#[async_trait]
+impl DnsResolver for CountingResolver {
+    async fn resolve(&self, _hostname: String) -> NetworkResult<Vec<IpAddr>> {
+        self.lookups.fetch_add(1, Ordering::SeqCst);
+        Ok(vec![IpAddr::V4(Ipv4Addr::UNSPECIFIED)])
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> NetworkResult<Vec<IpAddr>> {
+        self.resolve(hostname).await
+    }
+}
+
+fn request(url: &str) -> NetworkRequest {
+    NetworkRequest::builder(Url::parse(url).unwrap()).build()
+}
+
+#[tokio::test]
+async fn test_host_names_are_resolved_with_configured_resolver() {
+    let resolver = Arc::new(CountingResolver::default());
+    let client = Http3Client::with_resolver(Http3Config::default(), resolver.clone());
+
+    // The resolved unspecified address can't be connected to
+    let result = client.fetch(request("https://example.test/")).await;
+
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+    assert_eq!(resolver.lookups.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_stalled_connect_does_not_block_other_servers() {
+    // A UDP socket that never answers keeps the QUIC handshake pending
+    let silent = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let stalled_url = format!("https://{}/", silent.local_addr().unwrap());
+    let client = Arc::new(Http3Client::new(Http3Config::default()));
+
+    let stalled = {
+        let client = Arc::clone(&client);
+        tokio::spawn(async move { client.fetch(request(&stalled_url)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // A request to another server gets its own connect attempt, which fails
+    // straight away for an unspecified address
+    let result = tokio::time::timeout(
+        Duration::from_secs(2),
+        client.fetch(request("https://0.0.0.0/")),
+    )
+    .await
+    .expect("connect to another server should not wait for the stalled one");
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+
+    stalled.abort();
+}
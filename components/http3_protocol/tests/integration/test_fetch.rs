@@ -0,0 +1,41 @@
+//! Fetching over HTTP/3 from a public server
+
+use http3_protocol::{Http3Client, Http3Config};
+use network_types::{NetworkRequest, ResponseBody};
+use url::Url;
+
+const H3_URL: &str = "https://cloudflare-quic.com/";
+
+#[tokio::test]
+#[ignore = "requires network access to a public HTTP/3 server"]
+async fn test_fetch_from_h3_server_reuses_connection() {
+    let client = Http3Client::new(Http3Config::default());
+
+    let response = client
+        .fetch(NetworkRequest::builder(Url::parse(H3_URL).unwrap()).build())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    match &response.body {
+        ResponseBody::Bytes(body) => {
+            assert!(!body.is_empty());
+            assert_eq!(response.timing.decoded_body_size, body.len() as u64);
+        }
+        other => panic!("Expected a buffered body, got {:?}", other),
+    }
+    let timing = &response.timing;
+    assert!(timing.connect_end >= timing.connect_start);
+    assert!(timing.request_start >= timing.connect_end);
+    assert!(timing.response_start >= timing.request_start);
+    assert!(timing.response_end >= timing.response_start);
+
+    // A second request to the same server is sent on the pooled connection
+    let response = client
+        .fetch(NetworkRequest::builder(Url::parse(H3_URL).unwrap()).build())
+        .await
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.timing.connect_end, 0.0);
+    assert_eq!(client.connection_count().await, 1);
+}
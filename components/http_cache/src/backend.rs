@@ -0,0 +1,124 @@
+//! Storage backends for the HTTP cache
+//!
+//! [`HttpCache`](crate::HttpCache) keeps policy (freshness, size limits,
+//! eviction order) and delegates storing entries to a [`CacheBackend`].
+
+use crate::CachedResponse;
+use async_trait::async_trait;
+use lru::LruCache;
+use network_errors::NetworkResult;
+use std::num::NonZeroUsize;
+use tokio::sync::Mutex;
+
+/// Storage for cache entries, addressed by cache key
+///
+/// Backends track the size of every entry as given to [`put`](Self::put) and
+/// the order in which entries were last used, so the cache can evict the
+/// least recently used ones when it runs out of space.
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Look up an entry, marking it as most recently used
+    async fn get(&self, key: u64) -> Option<CachedResponse>;
+
+    /// Insert or replace the entry for `key`, accounted as `size` bytes
+    async fn put(&self, key: u64, entry: CachedResponse, size: usize) -> NetworkResult<()>;
+
+    /// Remove the entry for `key`, if any
+    async fn remove(&self, key: u64) -> NetworkResult<()>;
+
+    /// Remove the least recently used entry
+    ///
+    /// Returns whether an entry was removed.
+    async fn evict_lru(&self) -> NetworkResult<bool>;
+
+    /// Remove all entries
+    async fn clear(&self) -> NetworkResult<()>;
+
+    /// Number of stored entries
+    async fn len(&self) -> usize;
+
+    /// Whether the backend holds no entries
+    async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+
+    /// Total accounted size of all entries in bytes
+    async fn size(&self) -> usize;
+}
+
+/// In-memory LRU storage; entries are lost when the cache is dropped
+pub struct MemoryBackend {
+    state: Mutex<MemoryState>,
+}
+
+struct MemoryState {
+    entries: LruCache<u64, (CachedResponse, usize)>,
+    size: usize,
+}
+
+impl MemoryBackend {
+    /// Create a backend holding at most `capacity` entries
+    ///
+    /// When full, inserting evicts the least recently used entry regardless
+    /// of the cache's byte limit.
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            state: Mutex::new(MemoryState {
+                entries: LruCache::new(capacity),
+                size: 0,
+            }),
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryBackend {
+    async fn get(&self, key: u64) -> Option<CachedResponse> {
+        let mut state = self.state.lock().await;
+        state.entries.get(&key).map(|(entry, _)| entry.clone())
+    }
+
+    async fn put(&self, key: u64, entry: CachedResponse, size: usize) -> NetworkResult<()> {
+        let mut state = self.state.lock().await;
+        // `push` returns the replaced entry, or the one evicted to make room
+        if let Some((_, (_, old_size))) = state.entries.push(key, (entry, size)) {
+            state.size = state.size.saturating_sub(old_size);
+        }
+        state.size += size;
+        Ok(())
+    }
+
+    async fn remove(&self, key: u64) -> NetworkResult<()> {
+        let mut state = self.state.lock().await;
+        if let Some((_, size)) = state.entries.pop(&key) {
+            state.size = state.size.saturating_sub(size);
+        }
+        Ok(())
+    }
+
+    async fn evict_lru(&self) -> NetworkResult<bool> {
+        let mut state = self.state.lock().await;
+        match state.entries.pop_lru() {
+            Some((_, (_, size))) => {
+                state.size = state.size.saturating_sub(size);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn clear(&self) -> NetworkResult<()> {
+        let mut state = self.state.lock().await;
+        state.entries.clear();
+        state.size = 0;
+        Ok(())
+    }
+
+    async fn len(&self) -> usize {
+        self.state.lock().await.entries.len()
+    }
+
+    async fn size(&self) -> usize {
+        self.state.lock().await.size
+    }
+}
@@ -0,0 +1,306 @@
+//! Disk-backed cache storage that survives restarts
+//!
+//! Every entry lives in its own file named after the hex cache key: a line of
+//! JSON metadata followed by the raw body. An `index.json` file records each
+//! entry's size and last use for size accounting and LRU eviction.
+
+use crate::backend::CacheBackend;
+use crate::CachedResponse;
+use async_trait::async_trait;
+use http::{HeaderMap, HeaderName, HeaderValue};
+use network_errors::{NetworkError, NetworkResult};
+use network_types::{NetworkResponse, ResourceTiming, ResponseBody, ResponseSource, ResponseType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tokio::sync::Mutex;
+
+/// Name of the index file inside the cache directory
+const INDEX_FILE: &str = "index.json";
+
+/// Size and recency of a stored entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct IndexEntry {
+    size: usize,
+    last_used: u64,
+}
+
+/// Contents of `index.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Index {
+    /// Counter handed out as `last_used`; higher is more recent
+    next_use: u64,
+    entries: HashMap<u64, IndexEntry>,
+}
+
+impl Index {
+    fn touch(&mut self, key: u64) {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.next_use;
+            self.next_use += 1;
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.entries.values().map(|entry| entry.size).sum()
+    }
+}
+
+/// Entry metadata written before the body
+#[derive(Serialize, Deserialize)]
+struct StoredEntry {
+    url: String,
+    status: u16,
+    status_text: String,
+    headers: Vec<(String, Vec<u8>)>,
+    trailers: Option<Vec<(String, Vec<u8>)>>,
+    redirected: bool,
+    type_: ResponseType,
+    timing: ResourceTiming,
+    source: ResponseSource,
+    /// Whether the body was `Bytes` (possibly empty) rather than `Empty`
+    has_body: bool,
+    cached_at: SystemTime,
+    expires_at: SystemTime,
+}
+
+/// Cache storage in a directory on disk
+///
+/// Entries written by one instance are available to any later instance
+/// opened on the same directory. The index is rewritten whenever entries are
+/// added or removed; recency updates from lookups are persisted with the next
+/// such write.
+pub struct DiskBackend {
+    dir: PathBuf,
+    index: Mutex<Index>,
+}
+
+impl DiskBackend {
+    /// Open (or create) a cache directory
+    ///
+    /// Index entries whose file has gone missing are dropped. An unreadable
+    /// index starts the cache empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::CacheError` if the directory can't be created.
+    pub fn open(dir: impl Into<PathBuf>) -> NetworkResult<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir).map_err(|e| cache_error(&dir, e))?;
+
+        let mut index: Index = std::fs::read(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        index
+            .entries
+            .retain(|key, _| entry_path(&dir, *key).is_file());
+
+        Ok(Self {
+            dir,
+            index: Mutex::new(index),
+        })
+    }
+
+    /// Directory holding the cache files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    async fn write_index(&self, index: &Index) -> NetworkResult<()> {
+        let bytes = serde_json::to_vec(index).map_err(|e| {
+            NetworkError::CacheError(format!("Failed to encode cache index: {}", e))
+        })?;
+        write_atomic(&self.dir.join(INDEX_FILE), &bytes).await
+    }
+
+    async fn read_entry(&self, key: u64) -> Option<CachedResponse> {
+        let bytes = tokio::fs::read(entry_path(&self.dir, key)).await.ok()?;
+        decode_entry(&bytes)
+    }
+
+    async fn remove_file(&self, key: u64) -> NetworkResult<()> {
+        let path = entry_path(&self.dir, key);
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(cache_error(&path, e)),
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for DiskBackend {
+    async fn get(&self, key: u64) -> Option<CachedResponse> {
+        let mut index = self.index.lock().await;
+        if !index.entries.contains_key(&key) {
+            return None;
+        }
+
+        match self.read_entry(key).await {
+            Some(entry) => {
+                index.touch(key);
+                Some(entry)
+            }
+            None => {
+                // Missing or corrupt file: forget the entry
+                index.entries.remove(&key);
+                let _ = self.remove_file(key).await;
+                let _ = self.write_index(&index).await;
+                None
+            }
+        }
+    }
+
+    async fn put(&self, key: u64, entry: CachedResponse, size: usize) -> NetworkResult<()> {
+        let mut index = self.index.lock().await;
+        let bytes = encode_entry(&entry)?;
+        write_atomic(&entry_path(&self.dir, key), &bytes).await?;
+
+        let last_used = index.next_use;
+        index.next_use += 1;
+        index.entries.insert(key, IndexEntry { size, last_used });
+        self.write_index(&index).await
+    }
+
+    async fn remove(&self, key: u64) -> NetworkResult<()> {
+        let mut index = self.index.lock().await;
+        if index.entries.remove(&key).is_some() {
+            self.remove_file(key).await?;
+            self.write_index(&index).await?;
+        }
+        Ok(())
+    }
+
+    async fn evict_lru(&self) -> NetworkResult<bool> {
+        let mut index = self.index.lock().await;
+        let Some(key) = index
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| *key)
+        else {
+            return Ok(false);
+        };
+
+        index.entries.remove(&key);
+        self.remove_file(key).await?;
+        self.write_index(&index).await?;
+        Ok(true)
+    }
+
+    async fn clear(&self) -> NetworkResult<()> {
+        let mut index = self.index.lock().await;
+        for key in std::mem::take(&mut index.entries).into_keys() {
+            self.remove_file(key).await?;
+        }
+        self.write_index(&index).await
+    }
+
+    async fn len(&self) -> usize {
+        self.index.lock().await.entries.len()
+    }
+
+    async fn size(&self) -> usize {
+        self.index.lock().await.size()
+    }
+}
+
+/// File holding the entry for `key`
+fn entry_path(dir: &Path, key: u64) -> PathBuf {
+    dir.join(format!("{:016x}.entry", key))
+}
+
+/// Write a file so readers never observe it half-written
+async fn write_atomic(path: &Path, bytes: &[u8]) -> NetworkResult<()> {
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, bytes)
+        .await
+        .map_err(|e| cache_error(&tmp, e))?;
+    tokio::fs::rename(&tmp, path)
+        .await
+        .map_err(|e| cache_error(path, e))
+}
+
+fn encode_entry(entry: &CachedResponse) -> NetworkResult<Vec<u8>> {
+    let response = &entry.response;
+    let (has_body, body): (bool, &[u8]) = match &response.body {
+        ResponseBody::Bytes(bytes) => (true, bytes),
+        ResponseBody::Empty | ResponseBody::Stream(_) => (false, &[]),
+    };
+
+    let stored = StoredEntry {
+        url: response.url.to_string(),
+        status: response.status,
+        status_text: response.status_text.clone(),
+        headers: encode_headers(&response.headers),
+        trailers: response.trailers.as_ref().map(encode_headers),
+        redirected: response.redirected,
+        type_: response.type_,
+        timing: response.timing.clone(),
+        source: response.source,
+        has_body,
+        cached_at: entry.cached_at,
+        expires_at: entry.expires_at,
+    };
+
+    let mut bytes = serde_json::to_vec(&stored)
+        .map_err(|e| NetworkError::CacheError(format!("Failed to encode cache entry: {}", e)))?;
+    bytes.push(b'\n');
+    bytes.extend_from_slice(body);
+    Ok(bytes)
+}
+
+fn decode_entry(bytes: &[u8]) -> Option<CachedResponse> {
+    let split = bytes.iter().position(|b| *b == b'\n')?;
+    let stored: StoredEntry = serde_json::from_slice(&bytes[..split]).ok()?;
+    let body = &bytes[split + 1..];
+
+    let response = NetworkResponse {
+        url: stored.url.parse().ok()?,
+        status: stored.status,
+        status_text: stored.status_text,
+        headers: decode_headers(stored.headers),
+        body: if stored.has_body {
+            ResponseBody::Bytes(body.to_vec())
+        } else {
+            ResponseBody::Empty
+        },
+        redirected: stored.redirected,
+        type_: stored.type_,
+        timing: stored.timing,
+        trailers: stored.trailers.map(decode_headers),
+        source: stored.source,
+    };
+
+    Some(CachedResponse {
+        response,
+        cached_at: stored.cached_at,
+        expires_at: stored.expires_at,
+    })
+}
+
+fn encode_headers(headers: &HeaderMap) -> Vec<(String, Vec<u8>)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.as_bytes().to_vec()))
+        .collect()
+}
+
+fn decode_headers(headers: Vec<(String, Vec<u8>)>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_bytes(&value),
+        ) {
+            map.append(name, value);
+        }
+    }
+    map
+}
+
+fn cache_error(path: &Path, error: std::io::Error) -> NetworkError {
+    NetworkError::CacheError(format!("{}: {}", path.display(), error))
+}
@@ -8,21 +8,25 @@
 //! - Freshness validation
 //! - ETag and Last-Modified support
 //! - Cache-Control directive parsing
+//! - Byte range requests with `If-Range`
+//! - In-memory or persistent on-disk storage
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
-use lru::LruCache;
+mod backend;
+mod disk;
+mod range;
+
+pub use backend::{CacheBackend, MemoryBackend};
+pub use disk::DiskBackend;
 pub use network_errors::NetworkError;
 use network_errors::NetworkResult;
 pub use network_types::{HttpMethod, NetworkRequest, NetworkResponse};
 use serde::{Deserialize, Serialize};
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
-use tokio::sync::{Mutex, RwLock};
 
 /// Cache configuration
 ///
@@ -84,6 +88,8 @@ fn clone_network_response(response: &NetworkResponse) -> NetworkResponse {
         redirected: response.redirected,
         type_: response.type_,
         timing: response.timing.clone(),
+        trailers: response.trailers.clone(),
+        source: response.source,
     }
 }
 
@@ -144,20 +150,29 @@ impl CachedResponse {
     }
 }
 
-/// Cache entry with size tracking
-#[derive(Debug, Clone)]
-struct CacheEntry {
-    cached_response: CachedResponse,
-    size_bytes: usize,
+/// Outcome of looking up a byte range request in the cache
+#[derive(Debug)]
+pub enum RangeLookup {
+    /// Response built from a fresh cached body: `206 Partial Content` with a
+    /// `Content-Range` header, or `416 Range Not Satisfiable` if the range
+    /// lies outside the body
+    Hit(Box<NetworkResponse>),
+    /// The request's `If-Range` validator no longer matches the cached
+    /// response; the full representation must be fetched without the range
+    Refetch,
+    /// The cache can't answer the request: no fresh complete entry, or no
+    /// supported `Range` header
+    Miss,
 }
 
 /// HTTP cache implementation
 ///
 /// Provides async HTTP caching with LRU eviction, size limits, and freshness validation.
+/// Entries are kept in memory unless another [`CacheBackend`] is chosen with
+/// [`HttpCache::with_backend`].
 pub struct HttpCache {
     config: CacheConfig,
-    storage: Arc<Mutex<LruCache<u64, CacheEntry>>>,
-    current_size: Arc<RwLock<usize>>,
+    backend: Arc<dyn CacheBackend>,
 }
 
 impl HttpCache {
@@ -176,33 +191,40 @@ impl HttpCache {
         let cache_size = NonZeroUsize::new(capacity)
             .unwrap_or_else(|| NonZeroUsize::new(10).expect("Default cache size should be non-zero"));
 
-        Self {
-            config,
-            storage: Arc::new(Mutex::new(LruCache::new(cache_size))),
-            current_size: Arc::new(RwLock::new(0)),
-        }
+        Self::with_backend(config, Arc::new(MemoryBackend::new(cache_size)))
+    }
+
+    /// Create a new HTTP cache storing entries in `backend`
+    ///
+    /// Use a [`DiskBackend`] for a cache that persists across restarts.
+    /// Streamed response bodies are never cached, whatever the backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Cache configuration
+    /// * `backend` - Storage for cache entries
+    pub fn with_backend(config: CacheConfig, backend: Arc<dyn CacheBackend>) -> Self {
+        Self { config, backend }
     }
 
     /// Generate a cache key from a request
     ///
     /// The cache key includes the URL and HTTP method to ensure
     /// GET and POST requests to the same URL are cached separately.
+    /// FNV-1a is used so keys stay stable across builds for persistent backends.
     fn cache_key(request: &NetworkRequest) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        request.url.as_str().hash(&mut hasher);
-        // Include method in cache key
-        match request.method {
-            HttpMethod::Get => "GET".hash(&mut hasher),
-            HttpMethod::Post => "POST".hash(&mut hasher),
-            HttpMethod::Put => "PUT".hash(&mut hasher),
-            HttpMethod::Delete => "DELETE".hash(&mut hasher),
-            HttpMethod::Head => "HEAD".hash(&mut hasher),
-            HttpMethod::Options => "OPTIONS".hash(&mut hasher),
-            HttpMethod::Patch => "PATCH".hash(&mut hasher),
-            HttpMethod::Trace => "TRACE".hash(&mut hasher),
-            HttpMethod::Connect => "CONNECT".hash(&mut hasher),
-        }
-        hasher.finish()
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let url = request.url.as_str().as_bytes();
+        let method = request.method.as_str().as_bytes();
+        // The separator keeps the URL/method boundary unambiguous
+        url.iter()
+            .chain(b" ")
+            .chain(method)
+            .fold(FNV_OFFSET_BASIS, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+            })
     }
 
     /// Estimate the size of a response in bytes
@@ -250,26 +272,110 @@ impl HttpCache {
         }
 
         let key = Self::cache_key(request);
-        let mut storage = self.storage.lock().await;
+        let cached = self.backend.get(key).await?;
 
-        // Check if entry exists first
-        let is_expired = if let Some(entry) = storage.peek(&key) {
-            entry.cached_response.is_expired()
-        } else {
+        // Remove if expired
+        if cached.is_expired() {
+            let _ = self.backend.remove(key).await;
             return None;
+        }
+
+        Some(cached)
+    }
+
+    /// Get a cached response for a request even if it is no longer fresh
+    ///
+    /// Stale entries are kept so they can be revalidated with the origin;
+    /// callers check [`CachedResponse::is_fresh`] to decide whether to use them
+    /// directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The network request to lookup
+    ///
+    /// # Returns
+    ///
+    /// The cached response if found, None otherwise
+    pub async fn get_stale(&self, request: &NetworkRequest) -> Option<CachedResponse> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        self.backend.get(Self::cache_key(request)).await
+    }
+
+    /// Answer a byte range request from a fresh cached response
+    ///
+    /// Only complete `200 OK` entries with a buffered body serve ranges, and
+    /// only for a single range in the `bytes` unit. When the request carries
+    /// `If-Range`, the range is served only if the validator still matches
+    /// the cached `ETag` (strong comparison) or `Last-Modified` date;
+    /// otherwise [`RangeLookup::Refetch`] signals that the full response is
+    /// needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The network request with a `Range` header
+    pub async fn get_range(&self, request: &NetworkRequest) -> RangeLookup {
+        use network_types::{ResponseBody, ResponseSource};
+
+        let Some(range) = request
+            .headers
+            .get(http::header::RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(range::ByteRange::parse)
+        else {
+            return RangeLookup::Miss;
         };
 
-        // Remove if expired
-        if is_expired {
-            if let Some(entry) = storage.pop(&key) {
-                let mut current_size = self.current_size.write().await;
-                *current_size = current_size.saturating_sub(entry.size_bytes);
+        let Some(cached) = self.get(request).await else {
+            return RangeLookup::Miss;
+        };
+        let mut response = cached.response;
+        let body = match response.body {
+            ResponseBody::Bytes(ref body) if response.status == 200 => body,
+            _ => return RangeLookup::Miss,
+        };
+
+        if let Some(if_range) = request.headers.get(http::header::IF_RANGE) {
+            let matches = if_range
+                .to_str()
+                .is_ok_and(|if_range| range::if_range_matches(if_range, &response.headers));
+            if !matches {
+                return RangeLookup::Refetch;
             }
-            return None;
         }
 
-        // Get and return fresh response
-        storage.get(&key).map(|entry| entry.cached_response.clone())
+        let len = body.len() as u64;
+        let (status, status_text, content_range, body) = match range.resolve(len) {
+            Some((first, last)) => (
+                206,
+                "Partial Content",
+                format!("bytes {}-{}/{}", first, last, len),
+                body[first as usize..=last as usize].to_vec(),
+            ),
+            None => (
+                416,
+                "Range Not Satisfiable",
+                format!("bytes */{}", len),
+                Vec::new(),
+            ),
+        };
+
+        response.status = status;
+        response.status_text = status_text.to_string();
+        response.headers.insert(
+            http::header::CONTENT_RANGE,
+            http::HeaderValue::from_str(&content_range)
+                .expect("Content-Range is a valid header value"),
+        );
+        response.headers.insert(
+            http::header::CONTENT_LENGTH,
+            http::HeaderValue::from(body.len()),
+        );
+        response.body = ResponseBody::Bytes(body);
+        response.source = ResponseSource::Cache;
+        RangeLookup::Hit(Box::new(response))
     }
 
     /// Store a response in the cache
@@ -286,12 +392,38 @@ impl HttpCache {
         &self,
         request: &NetworkRequest,
         response: &NetworkResponse,
+    ) -> NetworkResult<()> {
+        let max_age = Duration::from_secs(self.config.max_age_seconds);
+        self.store_with_max_age(request, response, max_age).await
+    }
+
+    /// Store a response in the cache with its own freshness lifetime
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The network request
+    /// * `response` - The network response to cache
+    /// * `max_age` - How long the entry stays fresh; zero stores it already stale
+    ///
+    /// # Returns
+    ///
+    /// Ok if stored successfully, Err if caching failed
+    pub async fn store_with_max_age(
+        &self,
+        request: &NetworkRequest,
+        response: &NetworkResponse,
+        max_age: Duration,
     ) -> NetworkResult<()> {
         // If cache is disabled, succeed without storing
         if !self.config.enabled {
             return Ok(());
         }
 
+        // Partial content would be mistaken for the full representation
+        if response.status == 206 {
+            return Ok(());
+        }
+
         // Calculate response size
         let size = Self::estimate_response_size(response);
 
@@ -301,36 +433,21 @@ impl HttpCache {
         }
 
         let key = Self::cache_key(request);
-        let max_age = Duration::from_secs(self.config.max_age_seconds);
 
         // Create cached response
         let cached_response = CachedResponse::new(clone_network_response(response), max_age);
-        let entry = CacheEntry {
-            cached_response,
-            size_bytes: size,
-        };
 
-        let mut storage = self.storage.lock().await;
-        let mut current_size = self.current_size.write().await;
+        // Drop any previous entry so it isn't counted against the new one
+        self.backend.remove(key).await?;
 
-        // Evict entries if necessary to make room
-        while *current_size + size > self.config.max_size_bytes as usize && !storage.is_empty() {
-            // Pop least recently used
-            if let Some((_, evicted)) = storage.pop_lru() {
-                *current_size = current_size.saturating_sub(evicted.size_bytes);
+        // Evict least recently used entries if necessary to make room
+        while self.backend.size().await + size > self.config.max_size_bytes as usize {
+            if !self.backend.evict_lru().await? {
+                break;
             }
         }
 
-        // Insert new entry
-        if let Some(old_entry) = storage.put(key, entry) {
-            // Subtract old entry size
-            *current_size = current_size.saturating_sub(old_entry.size_bytes);
-        }
-
-        // Add new entry size
-        *current_size += size;
-
-        Ok(())
+        self.backend.put(key, cached_response, size).await
     }
 
     /// Clear all entries from the cache
@@ -339,29 +456,28 @@ impl HttpCache {
     ///
     /// Ok if cleared successfully, Err if clearing failed
     pub async fn clear(&self) -> NetworkResult<()> {
-        let mut storage = self.storage.lock().await;
-        let mut current_size = self.current_size.write().await;
-
-        storage.clear();
-        *current_size = 0;
-
-        Ok(())
+        self.backend.clear().await
     }
 
     /// Get the current cache size in bytes
     pub async fn current_size(&self) -> usize {
-        *self.current_size.read().await
+        self.backend.size().await
     }
 
     /// Get the number of entries in the cache
     pub async fn entry_count(&self) -> usize {
-        self.storage.lock().await.len()
+        self.backend.len().await
     }
 
     /// Check if the cache is enabled
     pub fn is_enabled(&self) -> bool {
         self.config.enabled
     }
+
+    /// Get the configured maximum age for cached entries
+    pub fn max_age(&self) -> Duration {
+        Duration::from_secs(self.config.max_age_seconds)
+    }
 }
 
 #[cfg(test)]
@@ -386,7 +502,7 @@ mod tests {
 
     #[test]
     fn test_cached_response_expiry() {
-        use network_types::{ResourceTiming, ResponseBody, ResponseType};
+        use network_types::{ResourceTiming, ResponseBody, ResponseSource, ResponseType};
         use url::Url;
 
         let response = NetworkResponse {
@@ -398,6 +514,8 @@ mod tests {
             redirected: false,
             type_: ResponseType::Basic,
             timing: ResourceTiming::default(),
+            trailers: None,
+            source: ResponseSource::Network,
         };
 
         let cached = CachedResponse::new(response, Duration::from_secs(1));
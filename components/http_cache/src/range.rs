@@ -0,0 +1,131 @@
+//! Byte range requests served from cached full responses (RFC 9110, section 14)
+
+use http::{header, HeaderMap};
+
+/// A single byte range from a `Range: bytes=...` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ByteRange {
+    /// `bytes=first-` or `bytes=first-last`
+    FromTo(u64, Option<u64>),
+    /// `bytes=-length`: the final `length` bytes
+    Suffix(u64),
+}
+
+impl ByteRange {
+    /// Parse a `Range` header value
+    ///
+    /// Only a single range in the `bytes` unit is supported; anything else
+    /// returns None and the header is ignored.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let (unit, spec) = value.trim().split_once('=')?;
+        if !unit.trim().eq_ignore_ascii_case("bytes") || spec.contains(',') {
+            return None;
+        }
+
+        let (first, last) = spec.trim().split_once('-')?;
+        let (first, last) = (first.trim(), last.trim());
+        if first.is_empty() {
+            return last.parse().ok().map(ByteRange::Suffix);
+        }
+
+        let first = first.parse().ok()?;
+        let last = if last.is_empty() {
+            None
+        } else {
+            Some(last.parse().ok()?)
+        };
+        if last.is_some_and(|last| last < first) {
+            return None;
+        }
+        Some(ByteRange::FromTo(first, last))
+    }
+
+    /// Resolve against a representation of `len` bytes
+    ///
+    /// Returns the inclusive `(first, last)` positions, or None if the range
+    /// is not satisfiable.
+    pub(crate) fn resolve(self, len: u64) -> Option<(u64, u64)> {
+        match self {
+            ByteRange::FromTo(first, _) if first >= len => None,
+            ByteRange::FromTo(first, last) => {
+                Some((first, last.map_or(len - 1, |last| last.min(len - 1))))
+            }
+            ByteRange::Suffix(0) => None,
+            ByteRange::Suffix(_) if len == 0 => None,
+            ByteRange::Suffix(length) => Some((len.saturating_sub(length), len - 1)),
+        }
+    }
+}
+
+/// Whether an `If-Range` condition holds for a stored response
+///
+/// An entity tag must match the stored `ETag` by strong comparison; weak tags
+/// never match. A date must equal the stored `Last-Modified` exactly.
+pub(crate) fn if_range_matches(if_range: &str, stored: &HeaderMap) -> bool {
+    let if_range = if_range.trim();
+    let stored_value = |name| {
+        stored
+            .get(name)
+            .and_then(|value: &http::HeaderValue| value.to_str().ok())
+            .map(str::trim)
+    };
+
+    if if_range.starts_with('"') || if_range.starts_with("W/") {
+        !if_range.starts_with("W/")
+            && stored_value(header::ETAG)
+                .is_some_and(|etag| !etag.starts_with("W/") && etag == if_range)
+    } else {
+        stored_value(header::LAST_MODIFIED) == Some(if_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_byte_ranges() {
+        assert_eq!(
+            ByteRange::parse("bytes=0-99"),
+            Some(ByteRange::FromTo(0, Some(99)))
+        );
+        assert_eq!(
+            ByteRange::parse("bytes=100-"),
+            Some(ByteRange::FromTo(100, None))
+        );
+        assert_eq!(ByteRange::parse("bytes=-20"), Some(ByteRange::Suffix(20)));
+        assert_eq!(ByteRange::parse("items=0-1"), None);
+        assert_eq!(ByteRange::parse("bytes=0-1,4-5"), None);
+        assert_eq!(ByteRange::parse("bytes=5-2"), None);
+        assert_eq!(ByteRange::parse("bytes=abc"), None);
+    }
+
+    #[test]
+    fn test_resolve_byte_ranges() {
+        assert_eq!(ByteRange::FromTo(0, Some(99)).resolve(10), Some((0, 9)));
+        assert_eq!(ByteRange::FromTo(4, None).resolve(10), Some((4, 9)));
+        assert_eq!(ByteRange::FromTo(10, None).resolve(10), None);
+        assert_eq!(ByteRange::Suffix(3).resolve(10), Some((7, 9)));
+        assert_eq!(ByteRange::Suffix(30).resolve(10), Some((0, 9)));
+        assert_eq!(ByteRange::Suffix(0).resolve(10), None);
+    }
+
+    #[test]
+    fn test_if_range_comparison() {
+        let mut stored = HeaderMap::new();
+        stored.insert(header::ETAG, "\"v1\"".parse().unwrap());
+        stored.insert(
+            header::LAST_MODIFIED,
+            "Tue, 15 Nov 1994 12:45:26 GMT".parse().unwrap(),
+        );
+
+        assert!(if_range_matches("\"v1\"", &stored));
+        assert!(!if_range_matches("\"v2\"", &stored));
+        assert!(!if_range_matches("W/\"v1\"", &stored));
+        assert!(if_range_matches("Tue, 15 Nov 1994 12:45:26 GMT", &stored));
+        assert!(!if_range_matches("Wed, 16 Nov 1994 12:45:26 GMT", &stored));
+
+        stored.insert(header::ETAG, "W/\"v1\"".parse().unwrap());
+        assert!(!if_range_matches("\"v1\"", &stored));
+    }
+}
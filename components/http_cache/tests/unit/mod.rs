@@ -1,10 +1,12 @@
 //! Unit tests for http_cache
 
-use http_cache::{CacheConfig, CachedResponse, HttpCache};
+use http_cache::{CacheConfig, CachedResponse, DiskBackend, HttpCache, RangeLookup};
 use network_types::{
     CacheMode, CredentialsMode, HttpMethod, NetworkRequest, NetworkResponse, RedirectMode,
-    ReferrerPolicy, RequestMode, RequestPriority, ResourceTiming, ResponseBody, ResponseType,
+    ReferrerPolicy, RequestMode, RequestPriority, ResourceTiming, ResponseBody, ResponseSource,
+    ResponseType,
 };
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use url::Url;
 
@@ -40,6 +42,8 @@ fn create_test_response(url: &str, status: u16, body: Vec<u8>) -> NetworkRespons
         redirected: false,
         type_: ResponseType::Basic,
         timing: ResourceTiming::default(),
+        trailers: None,
+        source: ResponseSource::Network,
     }
 }
 
@@ -253,6 +257,33 @@ async fn test_http_cache_expired_entry() {
     assert!(cached.is_none());
 }
 
+#[tokio::test]
+async fn test_http_cache_store_with_max_age_and_get_stale() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let request = create_test_request("https://example.com/stale", HttpMethod::Get);
+    let response = create_test_response("https://example.com/stale", 200, vec![4, 5, 6]);
+
+    // A zero lifetime stores the entry already stale
+    cache
+        .store_with_max_age(&request, &response, Duration::ZERO)
+        .await
+        .unwrap();
+
+    // Stale entries are still available for revalidation
+    let stale = cache
+        .get_stale(&request)
+        .await
+        .expect("stale entry should be kept");
+    assert!(!stale.is_fresh());
+    match stale.response.body {
+        ResponseBody::Bytes(ref bytes) => assert_eq!(bytes, &vec![4, 5, 6]),
+        _ => panic!("Expected bytes body"),
+    }
+
+    // get() only returns fresh entries
+    assert!(cache.get(&request).await.is_none());
+}
+
 #[test]
 fn test_cache_key_generation() {
     let req1 = create_test_request("https://example.com/api/1", HttpMethod::Get);
@@ -297,3 +328,196 @@ async fn test_http_cache_multiple_entries() {
         assert!(cached.is_some());
     }
 }
+
+/// Store a 26-byte alphabet body with an ETag and return the range request for it
+async fn store_range_capable(cache: &HttpCache, url: &str) -> NetworkRequest {
+    let request = create_test_request(url, HttpMethod::Get);
+    let mut response = create_test_response(url, 200, b"abcdefghijklmnopqrstuvwxyz".to_vec());
+    response.headers.insert("etag", "\"v1\"".parse().unwrap());
+    response
+        .headers
+        .insert("accept-ranges", "bytes".parse().unwrap());
+    cache.store(&request, &response).await.unwrap();
+    request
+}
+
+fn expect_hit(lookup: RangeLookup) -> NetworkResponse {
+    match lookup {
+        RangeLookup::Hit(response) => *response,
+        other => panic!("Expected a range hit, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_http_cache_range_hit() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let mut request = store_range_capable(&cache, "https://example.com/file").await;
+    request
+        .headers
+        .insert("range", "bytes=2-5".parse().unwrap());
+
+    let response = expect_hit(cache.get_range(&request).await);
+
+    assert_eq!(response.status, 206);
+    assert_eq!(response.headers["content-range"], "bytes 2-5/26");
+    assert_eq!(response.headers["content-length"], "4");
+    assert_eq!(response.source, ResponseSource::Cache);
+    match response.body {
+        ResponseBody::Bytes(bytes) => assert_eq!(bytes, b"cdef"),
+        _ => panic!("Expected bytes body"),
+    }
+}
+
+#[tokio::test]
+async fn test_http_cache_range_hit_with_matching_if_range() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let mut request = store_range_capable(&cache, "https://example.com/resume").await;
+    request.headers.insert("range", "bytes=-3".parse().unwrap());
+    request
+        .headers
+        .insert("if-range", "\"v1\"".parse().unwrap());
+
+    let response = expect_hit(cache.get_range(&request).await);
+
+    assert_eq!(response.status, 206);
+    assert_eq!(response.headers["content-range"], "bytes 23-25/26");
+}
+
+#[tokio::test]
+async fn test_http_cache_if_range_mismatch_requires_refetch() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let mut request = store_range_capable(&cache, "https://example.com/changed").await;
+    request
+        .headers
+        .insert("range", "bytes=10-".parse().unwrap());
+    request
+        .headers
+        .insert("if-range", "\"v0\"".parse().unwrap());
+
+    assert!(matches!(
+        cache.get_range(&request).await,
+        RangeLookup::Refetch
+    ));
+}
+
+#[tokio::test]
+async fn test_http_cache_range_not_satisfiable() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let mut request = store_range_capable(&cache, "https://example.com/short").await;
+    request
+        .headers
+        .insert("range", "bytes=100-".parse().unwrap());
+
+    let response = expect_hit(cache.get_range(&request).await);
+
+    assert_eq!(response.status, 416);
+    assert_eq!(response.headers["content-range"], "bytes */26");
+}
+
+#[tokio::test]
+async fn test_http_cache_range_miss() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let mut request = create_test_request("https://example.com/absent", HttpMethod::Get);
+    request
+        .headers
+        .insert("range", "bytes=0-1".parse().unwrap());
+    assert!(matches!(cache.get_range(&request).await, RangeLookup::Miss));
+
+    // Unsupported range syntax is ignored
+    let mut request = store_range_capable(&cache, "https://example.com/multi").await;
+    request
+        .headers
+        .insert("range", "bytes=0-1,5-6".parse().unwrap());
+    assert!(matches!(cache.get_range(&request).await, RangeLookup::Miss));
+}
+
+#[tokio::test]
+async fn test_http_cache_does_not_store_partial_content() {
+    let cache = HttpCache::new(CacheConfig::default());
+    let request = create_test_request("https://example.com/partial", HttpMethod::Get);
+    let response = create_test_response("https://example.com/partial", 206, b"cdef".to_vec());
+
+    cache.store(&request, &response).await.unwrap();
+
+    assert!(cache.get(&request).await.is_none());
+}
+
+fn disk_cache(dir: &std::path::Path, config: CacheConfig) -> HttpCache {
+    let backend = DiskBackend::open(dir).expect("cache directory should open");
+    HttpCache::with_backend(config, Arc::new(backend))
+}
+
+#[tokio::test]
+async fn test_disk_cache_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let request = create_test_request("https://example.com/persist", HttpMethod::Get);
+    let mut response = create_test_response("https://example.com/persist", 200, b"hello".to_vec());
+    response.headers.insert("etag", "\"v1\"".parse().unwrap());
+    response
+        .headers
+        .append("set-cookie", "a=1".parse().unwrap());
+    response
+        .headers
+        .append("set-cookie", "b=2".parse().unwrap());
+
+    let size = {
+        let cache = disk_cache(dir.path(), CacheConfig::default());
+        cache.store(&request, &response).await.unwrap();
+        cache.current_size().await
+    };
+
+    let cache = disk_cache(dir.path(), CacheConfig::default());
+    assert_eq!(cache.entry_count().await, 1);
+    assert_eq!(cache.current_size().await, size);
+
+    let cached = cache.get(&request).await.expect("entry should persist");
+    assert!(cached.is_fresh());
+    assert_eq!(cached.response.status, 200);
+    assert_eq!(cached.response.url, response.url);
+    assert_eq!(cached.response.headers.get("etag").unwrap(), "\"v1\"");
+    assert_eq!(
+        cached.response.headers.get_all("set-cookie").iter().count(),
+        2
+    );
+    match cached.response.body {
+        ResponseBody::Bytes(ref bytes) => assert_eq!(bytes, b"hello"),
+        _ => panic!("Expected bytes body"),
+    }
+
+    // Different methods still map to different entries after a restart
+    let post = create_test_request("https://example.com/persist", HttpMethod::Post);
+    assert!(cache.get(&post).await.is_none());
+}
+
+#[tokio::test]
+async fn test_disk_cache_evicts_and_clears() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = CacheConfig::new(700, 3600, true);
+    let cache = disk_cache(dir.path(), config);
+
+    let first = create_test_request("https://example.com/1", HttpMethod::Get);
+    let second = create_test_request("https://example.com/2", HttpMethod::Get);
+    let third = create_test_request("https://example.com/3", HttpMethod::Get);
+    for request in [&first, &second, &third] {
+        let response = create_test_response(request.url.as_str(), 200, vec![0; 64]);
+        cache.store(request, &response).await.unwrap();
+    }
+
+    // Only two entries fit; the least recently used one was evicted
+    assert_eq!(cache.entry_count().await, 2);
+    assert!(cache.current_size().await <= 700);
+    assert!(cache.get(&first).await.is_none());
+    assert!(cache.get(&third).await.is_some());
+
+    // Eviction is persisted too
+    drop(cache);
+    let cache = disk_cache(dir.path(), config);
+    assert_eq!(cache.entry_count().await, 2);
+
+    cache.clear().await.unwrap();
+    assert_eq!(cache.entry_count().await, 0);
+    assert_eq!(cache.current_size().await, 0);
+
+    let cache = disk_cache(dir.path(), config);
+    assert!(cache.get(&second).await.is_none());
+}
@@ -91,6 +91,71 @@ pub struct MixedContentResult {
     pub upgraded_url: Option<Url>,
 }
 
+/// Classification of a request alongside the outcome enforced for it
+///
+/// Unlike [`MixedContentResult`], the classification doesn't depend on
+/// upgrade-insecure-requests: an HTTP script on an HTTPS page is reported
+/// as mixed content that would be blocked even when it was upgraded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixedContentReport {
+    /// Whether the request is mixed content (an HTTP resource on an HTTPS page)
+    pub is_mixed_content: bool,
+
+    /// Type of content that was requested
+    pub content_type: ContentType,
+
+    /// Whether the policy blocks the request when it isn't upgraded
+    pub would_block: bool,
+
+    /// Outcome enforced for the request, as returned by `check_request`
+    pub enforcement: MixedContentResult,
+}
+
+impl MixedContentReport {
+    /// Whether the request was mixed content that got upgraded to HTTPS
+    pub fn was_upgraded(&self) -> bool {
+        self.is_mixed_content && self.enforcement.upgraded_url.is_some()
+    }
+}
+
+/// Aggregate counts over a batch of mixed content checks
+///
+/// Every result is counted exactly once: as blocked, as upgraded to HTTPS,
+/// or as allowed (including passive content allowed with a warning).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MixedContentSummary {
+    /// Requests that were blocked
+    pub blocked: usize,
+
+    /// Requests upgraded from HTTP to HTTPS
+    pub upgraded: usize,
+
+    /// Requests allowed as is
+    pub allowed: usize,
+}
+
+impl MixedContentSummary {
+    /// Tally a list of check results
+    pub fn from_results(results: &[MixedContentResult]) -> Self {
+        let mut summary = Self::default();
+        for result in results {
+            if result.blocked {
+                summary.blocked += 1;
+            } else if result.upgraded_url.is_some() {
+                summary.upgraded += 1;
+            } else {
+                summary.allowed += 1;
+            }
+        }
+        summary
+    }
+
+    /// Total number of requests counted
+    pub fn total(&self) -> usize {
+        self.blocked + self.upgraded + self.allowed
+    }
+}
+
 /// Mixed content blocker
 ///
 /// Detects and blocks HTTP resources loaded from HTTPS pages according to policy.
@@ -199,16 +264,7 @@ impl MixedContentBlocker {
         }
 
         // Determine if we should block based on content type and policy
-        let should_block = match content_type {
-            ContentType::Active => {
-                // Always block active mixed content
-                true
-            }
-            ContentType::Passive => {
-                // Block passive content only if policy is strict
-                self.policy.block_all_mixed_content
-            }
-        };
+        let should_block = self.blocks(content_type);
 
         let reason = if should_block {
             Some(format!(
@@ -235,6 +291,101 @@ impl MixedContentBlocker {
         }
     }
 
+    /// Check a request and report its mixed content classification
+    ///
+    /// The report's `enforcement` is what [`check_request`](Self::check_request)
+    /// returns. Its classification ignores upgrade-insecure-requests, so
+    /// insecure content that was upgraded still shows up as mixed content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mixed_content_blocker::{ContentType, MixedContentBlocker, MixedContentPolicy};
+    /// use url::Url;
+    ///
+    /// let policy = MixedContentPolicy {
+    ///     block_all_mixed_content: false,
+    ///     upgrade_insecure_requests: true,
+    /// };
+    /// let blocker = MixedContentBlocker::new(policy);
+    ///
+    /// let page = Url::parse("https://example.com").unwrap();
+    /// let resource = Url::parse("http://example.com/script.js").unwrap();
+    ///
+    /// let report = blocker.check_request_report(&page, &resource, ContentType::Active);
+    /// assert!(!report.enforcement.blocked);
+    /// assert!(report.would_block);
+    /// assert!(report.was_upgraded());
+    /// ```
+    pub fn check_request_report(
+        &self,
+        page_url: &Url,
+        resource_url: &Url,
+        content_type: ContentType,
+    ) -> MixedContentReport {
+        let is_mixed_content = page_url.scheme() == "https" && resource_url.scheme() == "http";
+        MixedContentReport {
+            is_mixed_content,
+            content_type,
+            would_block: is_mixed_content && self.blocks(content_type),
+            enforcement: self.check_request(page_url, resource_url, content_type),
+        }
+    }
+
+    /// Check every subresource a page loads
+    ///
+    /// Each resource is checked as by [`check_request`](Self::check_request),
+    /// and the results are returned in the same order as `resources`. Pass
+    /// them to [`MixedContentSummary::from_results`] for aggregate counts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mixed_content_blocker::{
+    ///     ContentType, MixedContentBlocker, MixedContentPolicy, MixedContentSummary,
+    /// };
+    /// use url::Url;
+    ///
+    /// let policy = MixedContentPolicy {
+    ///     block_all_mixed_content: false,
+    ///     upgrade_insecure_requests: false,
+    /// };
+    /// let blocker = MixedContentBlocker::new(policy);
+    ///
+    /// let page = Url::parse("https://example.com").unwrap();
+    /// let resources = [
+    ///     (Url::parse("http://example.com/app.js").unwrap(), ContentType::Active),
+    ///     (Url::parse("http://example.com/logo.png").unwrap(), ContentType::Passive),
+    /// ];
+    ///
+    /// let results = blocker.check_requests(&page, &resources);
+    /// let summary = MixedContentSummary::from_results(&results);
+    /// assert_eq!(summary.blocked, 1);
+    /// assert_eq!(summary.allowed, 1);
+    /// ```
+    pub fn check_requests(
+        &self,
+        page_url: &Url,
+        resources: &[(Url, ContentType)],
+    ) -> Vec<MixedContentResult> {
+        resources
+            .iter()
+            .map(|(resource_url, content_type)| {
+                self.check_request(page_url, resource_url, *content_type)
+            })
+            .collect()
+    }
+
+    /// Whether mixed content of this type is blocked when not upgraded
+    fn blocks(&self, content_type: ContentType) -> bool {
+        match content_type {
+            // Always block active mixed content
+            ContentType::Active => true,
+            // Block passive content only if policy is strict
+            ContentType::Passive => self.policy.block_all_mixed_content,
+        }
+    }
+
     /// Check if a URL should be upgraded to HTTPS
     ///
     /// Returns `true` if the URL is HTTP and upgrade-insecure-requests is enabled.
@@ -1,5 +1,5 @@
 use mixed_content_blocker::{
-    ContentType, MixedContentBlocker, MixedContentPolicy,
+    ContentType, MixedContentBlocker, MixedContentPolicy, MixedContentSummary,
 };
 use url::Url;
 
@@ -230,3 +230,201 @@ fn test_upgrade_to_https_is_noop_for_https() {
     let upgraded = result.unwrap();
     assert_eq!(upgraded.as_str(), https_url.as_str());
 }
+
+fn resource(url: &str, content_type: ContentType) -> (Url, ContentType) {
+    (Url::parse(url).unwrap(), content_type)
+}
+
+#[test]
+fn test_check_requests_preserves_order_and_summarizes() {
+    // Given: Permissive policy and a page mixing HTTP and HTTPS subresources
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: false,
+        upgrade_insecure_requests: false,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+    let resources = vec![
+        resource("https://example.com/app.js", ContentType::Active),
+        resource("http://example.com/tracker.js", ContentType::Active),
+        resource("http://example.com/logo.png", ContentType::Passive),
+        resource("https://example.com/photo.jpg", ContentType::Passive),
+        resource("http://cdn.example.com/style.css", ContentType::Active),
+    ];
+
+    // When: Checking the whole batch
+    let results = blocker.check_requests(&page_url, &resources);
+    let summary = MixedContentSummary::from_results(&results);
+
+    // Then: Each result matches a single check of the same resource, in order
+    assert_eq!(results.len(), resources.len());
+    for (result, (url, content_type)) in results.iter().zip(&resources) {
+        let single = blocker.check_request(&page_url, url, *content_type);
+        assert_eq!(*result, single);
+    }
+    let blocked: Vec<bool> = results.iter().map(|result| result.blocked).collect();
+    assert_eq!(blocked, [false, true, false, false, true]);
+    assert!(results[2].reason.as_ref().unwrap().contains("warning"));
+
+    // And: The summary counts blocked and allowed requests
+    assert_eq!(
+        summary,
+        MixedContentSummary {
+            blocked: 2,
+            upgraded: 0,
+            allowed: 3,
+        }
+    );
+    assert_eq!(summary.total(), 5);
+}
+
+#[test]
+fn test_check_requests_counts_upgrades() {
+    // Given: Policy upgrading insecure requests
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: true,
+        upgrade_insecure_requests: true,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+    let resources = vec![
+        resource("http://example.com/app.js", ContentType::Active),
+        resource("https://example.com/logo.png", ContentType::Passive),
+        resource("http://example.com/video.mp4", ContentType::Passive),
+    ];
+
+    // When: Checking the whole batch
+    let results = blocker.check_requests(&page_url, &resources);
+
+    // Then: HTTP resources are upgraded in place and HTTPS ones allowed
+    assert_eq!(
+        results[0].upgraded_url.as_ref().map(Url::as_str),
+        Some("https://example.com/app.js")
+    );
+    assert!(results[1].upgraded_url.is_none());
+    assert_eq!(
+        results[2].upgraded_url.as_ref().map(Url::as_str),
+        Some("https://example.com/video.mp4")
+    );
+    assert_eq!(
+        MixedContentSummary::from_results(&results),
+        MixedContentSummary {
+            blocked: 0,
+            upgraded: 2,
+            allowed: 1,
+        }
+    );
+}
+
+#[test]
+fn test_check_requests_empty_batch() {
+    // Given: Strict policy and a page without subresources
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: true,
+        upgrade_insecure_requests: false,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+
+    // When: Checking an empty batch
+    let results = blocker.check_requests(&page_url, &[]);
+
+    // Then: No results and an empty summary
+    assert!(results.is_empty());
+    assert_eq!(MixedContentSummary::from_results(&results).total(), 0);
+}
+
+#[test]
+fn test_report_shows_mixed_content_hidden_by_upgrade() {
+    // Given: Policy upgrading insecure requests
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: false,
+        upgrade_insecure_requests: true,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+    let resource_url = Url::parse("http://example.com/script.js").unwrap();
+
+    // When: Reporting on an HTTP script
+    let report = blocker.check_request_report(&page_url, &resource_url, ContentType::Active);
+
+    // Then: The enforced outcome is an upgrade, not a block
+    assert!(!report.enforcement.blocked);
+    assert_eq!(
+        report.enforcement,
+        blocker.check_request(&page_url, &resource_url, ContentType::Active)
+    );
+    assert!(report.was_upgraded());
+
+    // And: The report still classifies it as blockable active mixed content
+    assert!(report.is_mixed_content);
+    assert_eq!(report.content_type, ContentType::Active);
+    assert!(report.would_block);
+}
+
+#[test]
+fn test_report_passive_upgrade_would_not_block_when_permissive() {
+    // Given: Permissive policy upgrading insecure requests
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: false,
+        upgrade_insecure_requests: true,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+    let resource_url = Url::parse("http://example.com/logo.png").unwrap();
+
+    // When: Reporting on an HTTP image
+    let report = blocker.check_request_report(&page_url, &resource_url, ContentType::Passive);
+
+    // Then: It is mixed content that was upgraded but wouldn't have been blocked
+    assert!(report.is_mixed_content);
+    assert!(report.was_upgraded());
+    assert!(!report.would_block);
+}
+
+#[test]
+fn test_report_matches_enforcement_without_upgrade() {
+    // Given: Strict policy without upgrades
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: true,
+        upgrade_insecure_requests: false,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let page_url = Url::parse("https://example.com/page").unwrap();
+    let resource_url = Url::parse("http://example.com/logo.png").unwrap();
+
+    // When: Reporting on an HTTP image
+    let report = blocker.check_request_report(&page_url, &resource_url, ContentType::Passive);
+
+    // Then: The report and the enforcement agree that it is blocked
+    assert!(report.is_mixed_content);
+    assert!(report.would_block);
+    assert!(report.enforcement.blocked);
+    assert!(!report.was_upgraded());
+}
+
+#[test]
+fn test_report_secure_and_insecure_pages_are_not_mixed() {
+    // Given: Policy upgrading insecure requests
+    let policy = MixedContentPolicy {
+        block_all_mixed_content: true,
+        upgrade_insecure_requests: true,
+    };
+    let blocker = MixedContentBlocker::new(policy);
+    let https_page = Url::parse("https://example.com/page").unwrap();
+    let http_page = Url::parse("http://example.com/page").unwrap();
+    let https_script = Url::parse("https://example.com/app.js").unwrap();
+    let http_script = Url::parse("http://example.com/app.js").unwrap();
+
+    // When: Reporting on HTTPS content, and on HTTP content in an HTTP page
+    let secure = blocker.check_request_report(&https_page, &https_script, ContentType::Active);
+    let insecure_page = blocker.check_request_report(&http_page, &http_script, ContentType::Active);
+
+    // Then: Neither is mixed content
+    for report in [secure, insecure_page] {
+        assert!(!report.is_mixed_content);
+        assert!(!report.would_block);
+        assert!(!report.was_upgraded());
+        assert!(!report.enforcement.blocked);
+    }
+}
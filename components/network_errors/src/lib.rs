@@ -30,6 +30,9 @@
 use std::time::Duration;
 use thiserror::Error;
 
+/// HTTP/3 error code for a request the server didn't process (RFC 9114)
+const H3_REQUEST_REJECTED: u64 = 0x10b;
+
 /// Network error types
 ///
 /// Represents various failure modes that can occur during network operations.
@@ -133,6 +136,39 @@ pub enum NetworkError {
     #[error("WebRTC error: {0}")]
     WebRtcError(String),
 
+    /// HTTP/3 error
+    ///
+    /// Failure in the HTTP/3 layer above QUIC, such as an h3 protocol error
+    /// or the peer closing the connection with an application error code.
+    #[error("HTTP/3 error: {0}")]
+    Http3Error(String),
+
+    /// QUIC transport error
+    ///
+    /// The QUIC connection was closed with a transport error code (RFC 9000
+    /// section 20.1). Codes `0x0100..=0x01ff` are TLS handshake failures.
+    #[error("QUIC transport error {code:#x}: {reason}")]
+    QuicTransportError {
+        /// QUIC transport error code
+        code: u64,
+        /// Reason given by the peer or the local stack
+        reason: String,
+    },
+
+    /// QUIC stream reset
+    ///
+    /// The peer reset or stopped a single stream with an application error
+    /// code; the connection itself is still usable.
+    #[error("QUIC stream reset by peer: code {0:#x}")]
+    QuicStreamReset(u64),
+
+    /// 0-RTT data rejected
+    ///
+    /// The server refused early data; the request can be resent once the
+    /// handshake completes.
+    #[error("0-RTT data rejected")]
+    ZeroRttRejected,
+
     /// I/O error
     ///
     /// Low-level I/O error from std::io::Error.
@@ -147,6 +183,44 @@ pub enum NetworkError {
     Other(String),
 }
 
+impl NetworkError {
+    /// Whether the failure is transient, so repeating the request may succeed
+    ///
+    /// Covers failed connections, timeouts, and I/O errors where the peer
+    /// reset or dropped the connection. Rejected 0-RTT data and HTTP/3
+    /// streams reset with `H3_REQUEST_REJECTED` (the server didn't process
+    /// the request) are retryable too. Policy decisions (DNS, TLS, CORS, CSP,
+    /// ...), QUIC transport errors and malformed input are never retryable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use network_errors::NetworkError;
+    /// use std::time::Duration;
+    ///
+    /// assert!(NetworkError::Timeout(Duration::from_secs(5)).is_retryable());
+    /// assert!(!NetworkError::InvalidUrl("nope".to_string()).is_retryable());
+    /// ```
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            NetworkError::ConnectionFailed(_)
+            | NetworkError::Timeout(_)
+            | NetworkError::ZeroRttRejected => true,
+            NetworkError::QuicStreamReset(code) => *code == H3_REQUEST_REJECTED,
+            NetworkError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
+}
+
 /// Result type alias for network operations
 ///
 /// A convenience type alias that uses `NetworkError` as the error type.
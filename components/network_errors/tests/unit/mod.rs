@@ -238,6 +238,20 @@ mod display_tests {
         assert!(display.contains("ICE failed"));
     }
 
+    #[test]
+    fn test_quic_error_display() {
+        let error = NetworkError::QuicTransportError {
+            code: 0x0a,
+            reason: "bad frame".to_string(),
+        };
+        let display = format!("{}", error);
+        assert!(display.contains("QUIC transport error 0xa"));
+        assert!(display.contains("bad frame"));
+
+        let display = format!("{}", NetworkError::QuicStreamReset(0x10c));
+        assert!(display.contains("0x10c"));
+    }
+
     #[test]
     fn test_io_error_display() {
         let io_error = io::Error::new(io::ErrorKind::ConnectionRefused, "connection refused");
@@ -397,3 +411,35 @@ mod contract_verification_tests {
         let _err: NetworkResult<()> = Err(NetworkError::Aborted);
     }
 }
+
+#[cfg(test)]
+mod retryable_tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_errors_are_retryable() {
+        assert!(NetworkError::ConnectionFailed("reset".to_string()).is_retryable());
+        assert!(NetworkError::Timeout(Duration::from_secs(1)).is_retryable());
+        assert!(NetworkError::Io(io::Error::from(io::ErrorKind::ConnectionReset)).is_retryable());
+        assert!(NetworkError::Io(io::Error::from(io::ErrorKind::TimedOut)).is_retryable());
+        assert!(NetworkError::ZeroRttRejected.is_retryable());
+        assert!(NetworkError::QuicStreamReset(0x10b).is_retryable());
+    }
+
+    #[test]
+    fn test_permanent_errors_are_not_retryable() {
+        assert!(!NetworkError::DnsError("NXDOMAIN".to_string()).is_retryable());
+        assert!(!NetworkError::TlsError("handshake".to_string()).is_retryable());
+        assert!(!NetworkError::InvalidUrl("bad".to_string()).is_retryable());
+        assert!(!NetworkError::Aborted.is_retryable());
+        assert!(!NetworkError::TooManyRedirects.is_retryable());
+        assert!(!NetworkError::Io(io::Error::from(io::ErrorKind::NotFound)).is_retryable());
+        assert!(!NetworkError::QuicStreamReset(0x10c).is_retryable());
+        assert!(!NetworkError::Http3Error("H3_FRAME_ERROR".to_string()).is_retryable());
+        assert!(!NetworkError::QuicTransportError {
+            code: 0x0128,
+            reason: "handshake failure".to_string(),
+        }
+        .is_retryable());
+    }
+}
@@ -0,0 +1,23 @@
+//! Response body helpers
+
+use futures::StreamExt;
+use network_errors::NetworkError;
+use network_types::ResponseBody;
+
+/// Read a response body fully into memory
+///
+/// Returns `None` for an empty body; streaming bodies are drained.
+pub(crate) async fn buffer(body: ResponseBody) -> Result<Option<Vec<u8>>, NetworkError> {
+    match body {
+        ResponseBody::Bytes(bytes) => Ok(Some(bytes)),
+        ResponseBody::Empty => Ok(None),
+        ResponseBody::Stream(mut stream) => {
+            let mut bytes = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.map_err(|e| NetworkError::Other(e.message))?;
+                bytes.extend_from_slice(&chunk);
+            }
+            Ok(Some(bytes))
+        }
+    }
+}
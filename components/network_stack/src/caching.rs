@@ -0,0 +1,205 @@
+//! HTTP cache integration
+//!
+//! Decides which requests may use the HTTP cache, which responses are stored
+//! and for how long, and how stale entries are revalidated with the origin.
+
+use http::header::{self, HeaderMap, HeaderValue};
+use http_cache::CachedResponse;
+use network_types::{
+    CacheMode, HttpMethod, NetworkRequest, NetworkResponse, ResponseBody, ResponseSource,
+};
+use std::time::Duration;
+
+/// Headers describing the stored body that a `304 Not Modified` must not replace
+const BODY_HEADERS: [header::HeaderName; 3] = [
+    header::CONTENT_LENGTH,
+    header::CONTENT_ENCODING,
+    header::TRANSFER_ENCODING,
+];
+
+/// Whether a request may be served from or stored in the HTTP cache
+///
+/// Only bodyless GET requests outside `no-store` mode use the cache.
+pub(crate) fn is_cacheable_request(request: &NetworkRequest) -> bool {
+    request.method == HttpMethod::Get
+        && request.body.is_none()
+        && request.cache != CacheMode::NoStore
+}
+
+/// Parsed `Cache-Control` response directives relevant to storage
+#[derive(Debug, Default)]
+struct CacheControl {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+impl CacheControl {
+    fn parse(headers: &HeaderMap) -> Self {
+        let mut directives = CacheControl::default();
+        for value in headers.get_all(header::CACHE_CONTROL) {
+            let Ok(value) = value.to_str() else { continue };
+            for directive in value.split(',') {
+                let directive = directive.trim().to_ascii_lowercase();
+                match directive.split_once('=') {
+                    Some(("max-age", seconds)) => {
+                        directives.max_age = seconds.trim_matches('"').parse().ok();
+                    }
+                    None if directive == "no-store" => directives.no_store = true,
+                    None if directive == "no-cache" => directives.no_cache = true,
+                    _ => {}
+                }
+            }
+        }
+        directives
+    }
+}
+
+/// Freshness lifetime for storing a response, or `None` if it must not be stored
+///
+/// Only `200` responses with a buffered, unencoded body and explicit caching
+/// information are stored. `Cache-Control: max-age` sets the lifetime (capped
+/// at `limit`); a response with only an `ETag` or `Last-Modified` validator,
+/// or with `no-cache`, is stored already stale so it is revalidated before
+/// reuse. `no-store` responses are never stored.
+pub(crate) fn freshness_lifetime(response: &NetworkResponse, limit: Duration) -> Option<Duration> {
+    if response.status != 200
+        || matches!(response.body, ResponseBody::Stream(_))
+        || response.headers.contains_key(header::CONTENT_ENCODING)
+    {
+        return None;
+    }
+
+    let cache_control = CacheControl::parse(&response.headers);
+    if cache_control.no_store {
+        return None;
+    }
+
+    let has_validator = response.headers.contains_key(header::ETAG)
+        || response.headers.contains_key(header::LAST_MODIFIED);
+    match cache_control.max_age {
+        _ if cache_control.no_cache => has_validator.then_some(Duration::ZERO),
+        Some(seconds) => Some(Duration::from_secs(seconds).min(limit)),
+        None => has_validator.then_some(Duration::ZERO),
+    }
+}
+
+/// Add conditional headers for revalidating `cached`
+///
+/// Uses the stored `ETag` for `If-None-Match` and `Last-Modified` for
+/// `If-Modified-Since`, leaving any conditional header the caller set alone.
+/// Returns whether a validator was added.
+pub(crate) fn add_validators(request: &mut NetworkRequest, cached: &NetworkResponse) -> bool {
+    let mut added = false;
+    for (validator, conditional) in [
+        (header::ETAG, header::IF_NONE_MATCH),
+        (header::LAST_MODIFIED, header::IF_MODIFIED_SINCE),
+    ] {
+        if request.headers.contains_key(&conditional) {
+            continue;
+        }
+        if let Some(value) = cached.headers.get(&validator) {
+            request.headers.insert(conditional, value.clone());
+            added = true;
+        }
+    }
+    added
+}
+
+/// Serve a cached entry without contacting the origin
+pub(crate) fn from_cache(cached: CachedResponse) -> NetworkResponse {
+    let mut response = cached.response;
+    response.source = ResponseSource::Cache;
+    response
+}
+
+/// Combine a cached entry with the origin's `304 Not Modified` answer
+///
+/// The cached status and body are kept; headers sent with the `304` replace
+/// the stored ones, except those describing the stored body.
+pub(crate) fn not_modified(
+    cached: CachedResponse,
+    revalidation: NetworkResponse,
+) -> NetworkResponse {
+    let mut response = cached.response;
+    for name in revalidation.headers.keys() {
+        if BODY_HEADERS.contains(name) {
+            continue;
+        }
+        let values: Vec<HeaderValue> = revalidation.headers.get_all(name).iter().cloned().collect();
+        response.headers.remove(name);
+        for value in values {
+            response.headers.append(name.clone(), value);
+        }
+    }
+    response.timing = revalidation.timing;
+    response.source = ResponseSource::RevalidatedNotModified;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network_types::{ResourceTiming, ResponseType};
+    use url::Url;
+
+    fn response(headers: &[(&str, &str)]) -> NetworkResponse {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            map.append(
+                header::HeaderName::from_bytes(name.as_bytes()).unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        NetworkResponse {
+            url: Url::parse("https://example.com/").unwrap(),
+            status: 200,
+            status_text: "OK".to_string(),
+            headers: map,
+            body: ResponseBody::Bytes(b"body".to_vec()),
+            redirected: false,
+            type_: ResponseType::Basic,
+            timing: ResourceTiming::default(),
+            trailers: None,
+            source: ResponseSource::Network,
+        }
+    }
+
+    const LIMIT: Duration = Duration::from_secs(3600);
+
+    #[test]
+    fn test_max_age_sets_lifetime_capped_at_limit() {
+        let fresh = response(&[("cache-control", "public, max-age=60")]);
+        assert_eq!(
+            freshness_lifetime(&fresh, LIMIT),
+            Some(Duration::from_secs(60))
+        );
+
+        let long = response(&[("cache-control", "max-age=86400")]);
+        assert_eq!(freshness_lifetime(&long, LIMIT), Some(LIMIT));
+    }
+
+    #[test]
+    fn test_validators_without_max_age_are_stored_stale() {
+        let etag = response(&[("etag", "\"v1\"")]);
+        assert_eq!(freshness_lifetime(&etag, LIMIT), Some(Duration::ZERO));
+
+        let no_cache = response(&[
+            ("cache-control", "no-cache, max-age=60"),
+            ("etag", "\"v1\""),
+        ]);
+        assert_eq!(freshness_lifetime(&no_cache, LIMIT), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_unstorable_responses() {
+        assert_eq!(freshness_lifetime(&response(&[]), LIMIT), None);
+        let no_store = response(&[("cache-control", "no-store, max-age=60")]);
+        assert_eq!(freshness_lifetime(&no_store, LIMIT), None);
+        let encoded = response(&[
+            ("cache-control", "max-age=60"),
+            ("content-encoding", "gzip"),
+        ]);
+        assert_eq!(freshness_lifetime(&encoded, LIMIT), None);
+    }
+}
@@ -0,0 +1,237 @@
+//! Request coalescing
+//!
+//! Collapses concurrent identical requests into a single in-flight fetch whose
+//! response is shared with every waiter. Only safe, cacheable requests without a
+//! body are coalesced; everything else is sent independently.
+
+use crate::body;
+use network_errors::NetworkError;
+use network_types::{
+    CacheMode, HttpMethod, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody,
+    ResponseSource, ResponseType,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use url::Url;
+
+/// Outcome of a coalesced fetch, shared between the leader and its followers
+pub(crate) type SharedResult = Arc<Result<SharedResponse, NetworkError>>;
+
+/// Identifies requests that may share a single in-flight fetch
+///
+/// Mirrors the HTTP cache key (method and URL) and additionally includes the
+/// request headers, since two requests only produce the same response when they
+/// ask for the same representation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CoalescingKey {
+    method: HttpMethod,
+    url: String,
+    headers: Vec<(String, Vec<u8>)>,
+}
+
+impl CoalescingKey {
+    /// Build the key for a request, or `None` if the request must not be coalesced
+    pub(crate) fn for_request(request: &NetworkRequest) -> Option<Self> {
+        if !matches!(request.method, HttpMethod::Get | HttpMethod::Head) {
+            return None;
+        }
+        // Reload and no-store ask for a fresh response from the network, which
+        // one already in flight may not be
+        if request.body.is_some() || matches!(request.cache, CacheMode::Reload | CacheMode::NoStore)
+        {
+            return None;
+        }
+
+        let mut headers: Vec<(String, Vec<u8>)> = request
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str().to_string(), value.as_bytes().to_vec()))
+            .collect();
+        headers.sort();
+
+        Some(Self {
+            method: request.method,
+            url: request.url.as_str().to_string(),
+            headers,
+        })
+    }
+}
+
+/// A fully buffered response that can be handed to several callers
+#[derive(Debug)]
+pub(crate) struct SharedResponse {
+    url: Url,
+    status: u16,
+    status_text: String,
+    headers: http::HeaderMap,
+    body: Option<Vec<u8>>,
+    redirected: bool,
+    type_: ResponseType,
+    timing: ResourceTiming,
+}
+
+impl SharedResponse {
+    /// Buffer a response so it can be shared, draining streaming bodies
+    pub(crate) async fn buffer(response: NetworkResponse) -> Result<Self, NetworkError> {
+        let body = body::buffer(response.body).await?;
+
+        Ok(Self {
+            url: response.url,
+            status: response.status,
+            status_text: response.status_text,
+            headers: response.headers,
+            body,
+            redirected: response.redirected,
+            type_: response.type_,
+            timing: response.timing,
+        })
+    }
+
+    /// Produce an owned response for one caller
+    pub(crate) fn to_response(&self) -> NetworkResponse {
+        NetworkResponse {
+            url: self.url.clone(),
+            status: self.status,
+            status_text: self.status_text.clone(),
+            headers: self.headers.clone(),
+            body: match &self.body {
+                Some(bytes) => ResponseBody::Bytes(bytes.clone()),
+                None => ResponseBody::Empty,
+            },
+            redirected: self.redirected,
+            type_: self.type_,
+            timing: self.timing.clone(),
+            trailers: None,
+            source: ResponseSource::Network,
+        }
+    }
+}
+
+/// Convert a shared result into an owned result for one caller
+pub(crate) fn to_owned_result(result: &SharedResult) -> Result<NetworkResponse, NetworkError> {
+    match result.as_ref() {
+        Ok(response) => Ok(response.to_response()),
+        Err(error) => Err(duplicate_error(error)),
+    }
+}
+
+/// Recreate an error for a follower, since `NetworkError` is not `Clone`
+fn duplicate_error(error: &NetworkError) -> NetworkError {
+    match error {
+        NetworkError::ConnectionFailed(msg) => NetworkError::ConnectionFailed(msg.clone()),
+        NetworkError::DnsError(msg) => NetworkError::DnsError(msg.clone()),
+        NetworkError::TlsError(msg) => NetworkError::TlsError(msg.clone()),
+        NetworkError::ProtocolError(msg) => NetworkError::ProtocolError(msg.clone()),
+        NetworkError::Timeout(duration) => NetworkError::Timeout(*duration),
+        NetworkError::Aborted => NetworkError::Aborted,
+        NetworkError::InvalidUrl(msg) => NetworkError::InvalidUrl(msg.clone()),
+        NetworkError::InvalidConfig(msg) => NetworkError::InvalidConfig(msg.clone()),
+        NetworkError::TooManyRedirects => NetworkError::TooManyRedirects,
+        NetworkError::CacheError(msg) => NetworkError::CacheError(msg.clone()),
+        NetworkError::ProxyError(msg) => NetworkError::ProxyError(msg.clone()),
+        NetworkError::CorsError(msg) => NetworkError::CorsError(msg.clone()),
+        NetworkError::MixedContent => NetworkError::MixedContent,
+        NetworkError::CertificateError(msg) => NetworkError::CertificateError(msg.clone()),
+        NetworkError::WebSocketError(msg) => NetworkError::WebSocketError(msg.clone()),
+        NetworkError::WebRtcError(msg) => NetworkError::WebRtcError(msg.clone()),
+        NetworkError::Http3Error(msg) => NetworkError::Http3Error(msg.clone()),
+        NetworkError::QuicTransportError { code, reason } => NetworkError::QuicTransportError {
+            code: *code,
+            reason: reason.clone(),
+        },
+        NetworkError::QuicStreamReset(code) => NetworkError::QuicStreamReset(*code),
+        NetworkError::ZeroRttRejected => NetworkError::ZeroRttRejected,
+        NetworkError::Io(err) => NetworkError::Io(std::io::Error::new(err.kind(), err.to_string())),
+        NetworkError::Other(msg) => NetworkError::Other(msg.clone()),
+    }
+}
+
+/// Role assigned to a request when it joins the coalescer
+pub(crate) enum Slot {
+    /// No identical request is in flight; this caller performs the fetch
+    Leader(LeaderGuard),
+    /// An identical request is in flight; wait for its result
+    Follower(watch::Receiver<Option<SharedResult>>),
+}
+
+/// Tracks in-flight coalescable requests
+#[derive(Default)]
+pub(crate) struct RequestCoalescer {
+    in_flight: Mutex<HashMap<CoalescingKey, watch::Receiver<Option<SharedResult>>>>,
+}
+
+impl RequestCoalescer {
+    /// Create an empty coalescer
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Join the in-flight request for `key`, or become its leader
+    pub(crate) fn join(self: &Arc<Self>, key: CoalescingKey) -> Slot {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if let Some(receiver) = in_flight.get(&key) {
+            return Slot::Follower(receiver.clone());
+        }
+
+        let (sender, receiver) = watch::channel(None);
+        in_flight.insert(key.clone(), receiver);
+        Slot::Leader(LeaderGuard {
+            coalescer: Arc::clone(self),
+            key,
+            sender,
+            released: false,
+        })
+    }
+}
+
+/// Held by the caller performing a coalesced fetch
+///
+/// Dropping the guard without calling [`LeaderGuard::complete`] (for example when
+/// the leader's future is cancelled) releases the key so followers fall back to
+/// sending their own request.
+pub(crate) struct LeaderGuard {
+    coalescer: Arc<RequestCoalescer>,
+    key: CoalescingKey,
+    sender: watch::Sender<Option<SharedResult>>,
+    released: bool,
+}
+
+impl LeaderGuard {
+    /// Publish the result to every follower
+    pub(crate) fn complete(mut self, result: SharedResult) {
+        // Followers keep their own receivers, so the key can be released first;
+        // requests arriving after this point start a fresh fetch.
+        self.release();
+        self.sender.send_replace(Some(result));
+    }
+
+    fn release(&mut self) {
+        if !self.released {
+            self.coalescer.in_flight.lock().unwrap().remove(&self.key);
+            self.released = true;
+        }
+    }
+}
+
+impl Drop for LeaderGuard {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Wait for the leader's result
+///
+/// Returns `None` if the leader went away without publishing a result.
+pub(crate) async fn wait_for_leader(
+    mut receiver: watch::Receiver<Option<SharedResult>>,
+) -> Option<SharedResult> {
+    loop {
+        if let Some(result) = receiver.borrow_and_update().clone() {
+            return Some(result);
+        }
+        if receiver.changed().await.is_err() {
+            return receiver.borrow().clone();
+        }
+    }
+}
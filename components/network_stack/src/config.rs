@@ -2,12 +2,22 @@
 //!
 //! Defines configuration structures for all network protocols and features.
 
+use crate::{Interceptor, Transport};
+use dns_resolver::DnsResolver;
+use http::HeaderMap;
+use network_errors::NetworkError;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
+/// `User-Agent` sent when neither the request nor `default_headers` sets one
+pub const DEFAULT_USER_AGENT: &str = concat!("Corten-NetworkStack/", env!("CARGO_PKG_VERSION"));
+
 /// Network stack configuration
 ///
 /// Aggregates configuration for all network protocols and features.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct NetworkConfig {
     /// HTTP/1.1 configuration
     pub http1: Option<http1_protocol::Http1Config>,
@@ -36,6 +46,11 @@ pub struct NetworkConfig {
     /// DNS configuration
     pub dns: Option<dns_resolver::DohConfig>,
 
+    /// Resolver used for every connection the stack makes
+    ///
+    /// If None, a `StandardResolver` is built from `dns`.
+    pub dns_resolver: Option<Arc<dyn DnsResolver>>,
+
     // Phase 2 configurations
     /// CORS validation configuration
     pub cors: Option<cors_validator::CorsConfig>,
@@ -69,6 +84,21 @@ pub struct NetworkConfig {
 
     /// FTP protocol configuration
     pub ftp: Option<ftp_protocol::FtpConfig>,
+
+    /// Headers added to every HTTP(S) request (e.g. `User-Agent`,
+    /// `Accept-Language`); headers set on the request itself take precedence
+    pub default_headers: HeaderMap,
+
+    /// Retry policy for transient failures (disabled if None)
+    pub retry: Option<RetryPolicy>,
+
+    /// Interceptors invoked around every fetch, in registration order
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
+
+    /// Transport used for every HTTP(S) request the stack sends
+    ///
+    /// If None, requests go through the HTTP/1.1, HTTP/2 and HTTP/3 clients.
+    pub transport: Option<Arc<dyn Transport>>,
 }
 
 impl Default for NetworkConfig {
@@ -83,6 +113,7 @@ impl Default for NetworkConfig {
             security: Some(SecurityConfig::default()),
             proxy: None,
             dns: Some(dns_resolver::DohConfig::default()),
+            dns_resolver: None,
             // Phase 2 defaults
             cors: Some(cors_validator::CorsConfig::default()),
             content_encoding: Some(ContentEncodingConfig::default()),
@@ -95,17 +126,133 @@ impl Default for NetworkConfig {
             certificate_pinning: Some(CertificatePinningConfig::default()),
             platform_integration: Some(PlatformIntegrationConfig::default()),
             ftp: Some(ftp_protocol::FtpConfig::default()),
+            default_headers: HeaderMap::new(),
+            retry: None, // Requests are sent once by default
+            interceptors: Vec::new(),
+            transport: None,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Register an interceptor to run around every fetch
+    ///
+    /// Request hooks run in the order interceptors were added, response hooks
+    /// in reverse order.
+    pub fn add_interceptor(&mut self, interceptor: Arc<dyn Interceptor>) {
+        self.interceptors.push(interceptor);
+    }
+}
+
+impl fmt::Debug for NetworkConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NetworkConfig")
+            .field("http1", &self.http1)
+            .field("http2", &self.http2)
+            .field("http3", &self.http3)
+            .field("websocket", &self.websocket)
+            .field("webrtc", &self.webrtc)
+            .field("cache", &self.cache)
+            .field("security", &self.security)
+            .field("proxy", &self.proxy)
+            .field("dns", &self.dns)
+            .field(
+                "dns_resolver",
+                &self.dns_resolver.as_ref().map(|_| "dyn DnsResolver"),
+            )
+            .field("cors", &self.cors)
+            .field("content_encoding", &self.content_encoding)
+            .field("request_scheduling", &self.request_scheduling)
+            .field("bandwidth_limit", &self.bandwidth_limit)
+            .field("url_handlers", &self.url_handlers)
+            .field("mixed_content", &self.mixed_content)
+            .field("csp", &self.csp)
+            .field("certificate_transparency", &self.certificate_transparency)
+            .field("certificate_pinning", &self.certificate_pinning)
+            .field("platform_integration", &self.platform_integration)
+            .field("ftp", &self.ftp)
+            .field("default_headers", &self.default_headers)
+            .field("retry", &self.retry)
+            .field("interceptors", &self.interceptors.len())
+            .field(
+                "transport",
+                &self.transport.as_ref().map(|_| "dyn Transport"),
+            )
+            .finish()
+    }
+}
+
+/// Retry policy for transient failures
+///
+/// Failed exchanges are repeated up to `max_retries` times, waiting
+/// `base_delay * 2^n` (capped at `max_delay`) before the n-th retry. Only
+/// idempotent methods are retried unless `retry_non_idempotent` is set.
+#[derive(Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt
+    pub max_retries: u32,
+
+    /// Delay before the first retry
+    pub base_delay: Duration,
+
+    /// Upper bound on the delay between attempts
+    pub max_delay: Duration,
+
+    /// Decides whether a failed attempt is retried
+    ///
+    /// Defaults to [`NetworkError::is_retryable`].
+    pub retry_on: Arc<dyn Fn(&NetworkError) -> bool + Send + Sync>,
+
+    /// Response statuses that are retried (default: 502, 503, 504)
+    pub retry_statuses: HashSet<u16>,
+
+    /// Also retry non-idempotent methods such as POST and PATCH
+    pub retry_non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (starting at 0)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(5),
+            retry_on: Arc::new(NetworkError::is_retryable),
+            retry_statuses: HashSet::from([502, 503, 504]),
+            retry_non_idempotent: false,
         }
     }
 }
 
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("retry_on", &"dyn Fn(&NetworkError) -> bool")
+            .field("retry_statuses", &self.retry_statuses)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .finish()
+    }
+}
+
 /// WebSocket client configuration
 #[derive(Debug, Clone)]
 pub struct WebSocketConfig {
     /// Maximum message size (bytes)
     pub max_message_size: usize,
 
-    /// Connection timeout
+    /// Opening handshake timeout, covering connect, TLS and upgrade
     pub connect_timeout: Duration,
 
     /// Enable message compression
@@ -208,7 +355,8 @@ pub struct ProxyAuth {
 /// Content encoding configuration
 #[derive(Debug, Clone)]
 pub struct ContentEncodingConfig {
-    /// Enable content encoding/decoding
+    /// Advertise Accept-Encoding and transparently decode response bodies.
+    /// When disabled, bodies are returned exactly as received.
     pub enabled: bool,
 }
 
@@ -0,0 +1,122 @@
+//! Cookie handling for requests sent by the stack
+//!
+//! Attaches a `Cookie` header from the stack's [`CookieStore`] and stores
+//! the `Set-Cookie` headers of responses, when the request's credentials
+//! mode allows it.
+
+use cookie_manager::CookieStore;
+use http::HeaderValue;
+use network_types::{CredentialsMode, HttpMethod, NetworkRequest, NetworkResponse, RequestMode};
+use tokio::sync::RwLock;
+use tracing::debug;
+use url::Url;
+
+/// Whether cookies are sent with `request` and stored from its response
+///
+/// `SameOrigin` requests include credentials when the request is
+/// same-origin with its referrer, which stands in for the initiating
+/// document; requests without a referrer have no initiator to be
+/// cross-origin with.
+pub(crate) fn include_credentials(request: &NetworkRequest) -> bool {
+    match request.credentials {
+        CredentialsMode::Include => true,
+        CredentialsMode::Omit => false,
+        CredentialsMode::SameOrigin => {
+            initiator(request).map_or(true, |initiator| initiator.origin() == request.url.origin())
+        }
+    }
+}
+
+/// Add a `Cookie` header with the cookies the store holds for the request URL
+///
+/// SameSite rules are applied against the referrer, and a `Cookie` header
+/// set by the caller is left as is.
+pub(crate) async fn attach(store: &RwLock<CookieStore>, request: &mut NetworkRequest) {
+    if request.headers.contains_key(http::header::COOKIE) {
+        return;
+    }
+
+    let site_for_cookies = initiator(request).unwrap_or_else(|| request.url.clone());
+    let top_level_navigation = request.mode == RequestMode::Navigate
+        && matches!(request.method, HttpMethod::Get | HttpMethod::Head);
+    let cookies = store.read().await.cookies_for_request(
+        &request.url,
+        &site_for_cookies,
+        top_level_navigation,
+    );
+
+    if let Some(value) = cookie_header(&cookies) {
+        request.headers.insert(http::header::COOKIE, value);
+    }
+}
+
+/// Handshake headers carrying the stored cookies for a WebSocket to `url`
+///
+/// The handshake is matched like an HTTP request to the same host, so `wss`
+/// URLs also receive `Secure` cookies.
+pub(crate) async fn websocket_headers(store: &RwLock<CookieStore>, url: &Url) -> http::HeaderMap {
+    let mut headers = http::HeaderMap::new();
+    let mut http_url = url.clone();
+    let scheme = if url.scheme() == "wss" {
+        "https"
+    } else {
+        "http"
+    };
+    if http_url.set_scheme(scheme).is_err() {
+        return headers;
+    }
+
+    let cookies = store
+        .read()
+        .await
+        .cookies_for_request(&http_url, &http_url, false);
+    if let Some(value) = cookie_header(&cookies) {
+        headers.insert(http::header::COOKIE, value);
+    }
+    headers
+}
+
+/// Store the cookies set by a response to `url`
+///
+/// Malformed `Set-Cookie` headers and cookies the store rejects are skipped.
+pub(crate) async fn store(store: &RwLock<CookieStore>, url: &Url, response: &NetworkResponse) {
+    let set_cookies: Vec<&str> = response
+        .headers
+        .get_all(http::header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .collect();
+    if set_cookies.is_empty() {
+        return;
+    }
+
+    let mut store = store.write().await;
+    for set_cookie in set_cookies {
+        let result = cookie_manager::parse_set_cookie(set_cookie)
+            .and_then(|cookie| store.add_cookie(cookie, url));
+        if let Err(e) = result {
+            debug!("Ignoring Set-Cookie from {}: {}", url, e);
+        }
+    }
+}
+
+/// URL of the document that initiated the request, taken from its referrer
+fn initiator(request: &NetworkRequest) -> Option<Url> {
+    request
+        .referrer
+        .as_deref()
+        .and_then(|referrer| Url::parse(referrer).ok())
+}
+
+/// Serialize cookies as a `Cookie` header value
+fn cookie_header(cookies: &[cookie_manager::CookieType<'static>]) -> Option<HeaderValue> {
+    if cookies.is_empty() {
+        return None;
+    }
+    let value = cookies
+        .iter()
+        .map(|cookie| format!("{}={}", cookie.name(), cookie.value()))
+        .collect::<Vec<_>>()
+        .join("; ");
+    HeaderValue::from_str(&value).ok()
+}
@@ -0,0 +1,38 @@
+//! CORS response filtering for requests sent by the stack
+//!
+//! A `cors` mode request carrying an `Origin` header comes from a document
+//! at that origin. When its response is cross-origin, the response becomes
+//! a CORS response exposing only the headers the document may read.
+
+use cors_validator::CorsValidator;
+use network_types::{NetworkRequest, NetworkResponse, RequestMode, ResponseType};
+
+/// The requesting document's origin, for `cors` mode requests that carry
+/// an `Origin` header
+pub(crate) fn request_origin(request: &NetworkRequest) -> Option<String> {
+    if request.mode != RequestMode::Cors {
+        return None;
+    }
+    request
+        .headers
+        .get(http::header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Turn a cross-origin basic response into a CORS response
+///
+/// Only the CORS-safelisted headers and those listed in
+/// `Access-Control-Expose-Headers` are kept. Same-origin responses and
+/// responses that are already filtered are left as they are.
+pub(crate) fn filter_response(
+    validator: &CorsValidator,
+    origin: &str,
+    response: &mut NetworkResponse,
+) {
+    if response.type_ != ResponseType::Basic || !validator.is_cross_origin(response, origin) {
+        return;
+    }
+    response.headers = validator.exposed_headers(response, origin, RequestMode::Cors);
+    response.type_ = ResponseType::Cors;
+}
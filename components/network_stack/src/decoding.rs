@@ -0,0 +1,70 @@
+//! Content decoding
+//!
+//! Removes the `Content-Encoding` applied by the server so callers receive the
+//! resource as it was before compression.
+
+use crate::body;
+use content_encoding::{ContentEncoder, Encoding};
+use network_errors::NetworkError;
+use network_types::{NetworkResponse, ResponseBody};
+
+/// Parse a `Content-Encoding` header value into the codings in applied order
+///
+/// Returns `None` if any coding is unsupported, in which case the body must be
+/// left untouched.
+fn parse_encodings(value: &str) -> Option<Vec<Encoding>> {
+    value
+        .split(',')
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty())
+        .map(|coding| match coding.as_str() {
+            "gzip" | "x-gzip" => Some(Encoding::Gzip),
+            "deflate" => Some(Encoding::Deflate),
+            "br" => Some(Encoding::Brotli),
+            "zstd" => Some(Encoding::Zstd),
+            "identity" => Some(Encoding::Identity),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decode a response body according to its `Content-Encoding` header
+///
+/// Codings are undone in reverse order of application. On success the
+/// `Content-Encoding` and (now stale) `Content-Length` headers are removed and
+/// `decoded_body_size` reflects the decoded length. Responses with no body or
+/// an unsupported coding are returned unchanged.
+pub(crate) async fn decode_response(
+    encoder: &ContentEncoder,
+    mut response: NetworkResponse,
+) -> Result<NetworkResponse, NetworkError> {
+    let encodings = match response
+        .headers
+        .get(http::header::CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_encodings)
+    {
+        Some(encodings) => encodings,
+        None => return Ok(response),
+    };
+
+    let mut bytes =
+        match body::buffer(std::mem::replace(&mut response.body, ResponseBody::Empty)).await? {
+            Some(bytes) if !bytes.is_empty() => bytes,
+            Some(bytes) => {
+                response.body = ResponseBody::Bytes(bytes);
+                return Ok(response);
+            }
+            None => return Ok(response),
+        };
+
+    for encoding in encodings.into_iter().rev() {
+        bytes = encoder.decode(&bytes, encoding)?;
+    }
+
+    response.headers.remove(http::header::CONTENT_ENCODING);
+    response.headers.remove(http::header::CONTENT_LENGTH);
+    response.timing.decoded_body_size = bytes.len() as u64;
+    response.body = ResponseBody::Bytes(bytes);
+    Ok(response)
+}
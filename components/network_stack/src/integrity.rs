@@ -0,0 +1,98 @@
+//! Subresource Integrity
+//!
+//! Verifies response bodies against the `integrity` metadata of a request, as
+//! described in the W3C Subresource Integrity specification.
+
+use crate::body;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use network_errors::NetworkError;
+use network_types::{NetworkResponse, ResponseBody};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Hash algorithms supported by SRI, ordered from weakest to strongest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Algorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Algorithm {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix.to_ascii_lowercase().as_str() {
+            "sha256" => Some(Algorithm::Sha256),
+            "sha384" => Some(Algorithm::Sha384),
+            "sha512" => Some(Algorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    fn digest(self, body: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => Sha256::digest(body).to_vec(),
+            Algorithm::Sha384 => Sha384::digest(body).to_vec(),
+            Algorithm::Sha512 => Sha512::digest(body).to_vec(),
+        }
+    }
+}
+
+/// A single `<alg>-<base64 digest>` entry from integrity metadata
+#[derive(Debug)]
+struct IntegrityHash {
+    algorithm: Algorithm,
+    digest: Vec<u8>,
+}
+
+/// Parse integrity metadata, skipping entries with unknown algorithms or bad encoding
+fn parse_metadata(metadata: &str) -> Vec<IntegrityHash> {
+    metadata
+        .split_ascii_whitespace()
+        .filter_map(|token| {
+            // Options after '?' are reserved and ignored
+            let expression = token.split('?').next().unwrap_or_default();
+            let (prefix, encoded) = expression.split_once('-')?;
+            let algorithm = Algorithm::from_prefix(prefix)?;
+            let digest = STANDARD.decode(encoded).ok()?;
+            Some(IntegrityHash { algorithm, digest })
+        })
+        .collect()
+}
+
+/// Check `body` against SRI `metadata`
+///
+/// Only hashes using the strongest algorithm present are considered, and the
+/// body matches if any of them does. Metadata without any recognised hash
+/// imposes no requirement.
+pub(crate) fn verify(metadata: &str, body: &[u8]) -> Result<(), NetworkError> {
+    let hashes = parse_metadata(metadata);
+    let strongest = match hashes.iter().map(|hash| hash.algorithm).max() {
+        Some(algorithm) => algorithm,
+        None => return Ok(()),
+    };
+
+    let actual = strongest.digest(body);
+    if hashes
+        .iter()
+        .filter(|hash| hash.algorithm == strongest)
+        .any(|hash| hash.digest == actual)
+    {
+        Ok(())
+    } else {
+        Err(NetworkError::Other("integrity mismatch".to_string()))
+    }
+}
+
+/// Check a response against SRI `metadata`, buffering a streamed body
+pub(crate) async fn verify_response(
+    metadata: &str,
+    mut response: NetworkResponse,
+) -> Result<NetworkResponse, NetworkError> {
+    let bytes = body::buffer(std::mem::replace(&mut response.body, ResponseBody::Empty)).await?;
+    verify(metadata, bytes.as_deref().unwrap_or_default())?;
+
+    if let Some(bytes) = bytes {
+        response.body = ResponseBody::Bytes(bytes);
+    }
+    Ok(response)
+}
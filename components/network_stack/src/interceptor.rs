@@ -0,0 +1,26 @@
+//! Request/response interceptors
+//!
+//! Interceptors are middleware registered on the [`NetworkConfig`](crate::NetworkConfig)
+//! that observe or modify every fetch, e.g. for logging, injecting auth
+//! tokens, or collecting metrics.
+
+use async_trait::async_trait;
+use network_types::{NetworkRequest, NetworkResponse};
+
+/// Hooks invoked around every `fetch`
+///
+/// Request hooks run in registration order before the request is processed;
+/// response hooks run in reverse registration order once a response is
+/// available, so the first interceptor registered wraps all others. Failed
+/// fetches only run the request hooks. Both hooks default to doing nothing.
+#[async_trait]
+pub trait Interceptor: Send + Sync {
+    /// Inspect or modify an outgoing request
+    async fn on_request(&self, _request: &mut NetworkRequest) {}
+
+    /// Inspect or modify a response
+    ///
+    /// `request` is the request as sent by the interceptors, before the stack
+    /// added default headers; streaming bodies are not included.
+    async fn on_response(&self, _request: &NetworkRequest, _response: &mut NetworkResponse) {}
+}
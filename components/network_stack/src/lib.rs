@@ -16,18 +16,41 @@ use network_errors::NetworkError;
 use network_types::{NetworkRequest, NetworkResponse};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
+mod body;
+mod caching;
+mod coalescing;
 mod config;
+mod cookies;
+mod cors;
+mod decoding;
+mod integrity;
+mod interceptor;
+#[cfg(feature = "test-util")]
+mod mock_transport;
+mod network_quality;
+mod redirect;
+mod referrer;
+mod retry;
+mod schemes;
 mod stack_impl;
+mod transport;
 
 pub use config::{
     NetworkConfig, ProxyConfig, ProxyAuth,
     ContentEncodingConfig, RequestSchedulingConfig, UrlHandlersConfig,
     MixedContentConfig, CspConfig, CertificateTransparencyConfig,
-    CertificatePinningConfig, PlatformIntegrationConfig,
+    CertificatePinningConfig, PlatformIntegrationConfig, RetryPolicy, DEFAULT_USER_AGENT,
 };
+pub use interceptor::Interceptor;
+#[cfg(feature = "test-util")]
+pub use mock_transport::MockTransport;
+pub use network_quality::{NetworkQualityEstimator, MIN_THROUGHPUT_BYTES, SAMPLE_WINDOW};
+pub use referrer::compute_referrer;
 pub use stack_impl::{NetworkStackImpl, NetworkStatus, ConnectionType, EffectiveConnectionType};
+pub use transport::Transport;
 
 /// Main Network Stack component interface
 ///
@@ -41,6 +64,10 @@ pub trait NetworkStack: Send + Sync {
     /// Routes the request to the appropriate protocol handler (HTTP/1.1, HTTP/2, HTTP/3)
     /// based on URL scheme and configuration. Integrates with cookies, cache, DNS, and TLS.
     ///
+    /// Callers enforcing a document's Content Security Policy should check the
+    /// request URL with `CspProcessor::check_connect` (connect-src) before
+    /// calling this.
+    ///
     /// # Arguments
     /// * `request` - The network request to execute
     ///
@@ -68,6 +95,9 @@ pub trait NetworkStack: Send + Sync {
     ///
     /// Establishes a WebSocket connection (ws:// or wss://) to the specified URL.
     ///
+    /// As with `fetch`, callers enforcing a Content Security Policy should
+    /// check the URL with `CspProcessor::check_connect` first.
+    ///
     /// # Arguments
     /// * `url` - WebSocket URL (ws:// or wss://)
     /// * `protocols` - Optional subprotocols to negotiate
@@ -96,6 +126,22 @@ pub trait NetworkStack: Send + Sync {
         config: webrtc_peer::RtcConfiguration,
     ) -> Result<webrtc_peer::RtcPeerConnection, NetworkError>;
 
+    /// Warm up a connection to the origin of `url`
+    ///
+    /// Like `<link rel=preconnect>`, resolves the host and opens a pooled
+    /// connection in the background so a following request to the origin
+    /// skips connection setup. Returns immediately and ignores failures.
+    /// Nothing is opened when a connection is already available, when the
+    /// pool is at its limits, or while offline.
+    fn preconnect(&self, url: &Url);
+
+    /// Resolve `host` in the background
+    ///
+    /// Like `<link rel=dns-prefetch>`, populates the stack's DNS cache so a
+    /// later request to the host skips the lookup. Failures are only logged.
+    /// Returns immediately with a handle that cancels the lookup.
+    fn prefetch_dns(&self, host: &str) -> tokio::task::AbortHandle;
+
     /// Get current network status
     ///
     /// Returns information about network connectivity, connection type, and performance.
@@ -116,8 +162,14 @@ pub trait NetworkStack: Send + Sync {
 
     /// Get cookie store handle
     ///
-    /// Returns a reference to the cookie store for cookie management.
-    fn cookie_store(&self) -> Arc<cookie_manager::CookieStore>;
+    /// Returns the cookie store shared by all requests. `fetch` attaches its
+    /// cookies and stores `Set-Cookie` responses for requests whose
+    /// credentials mode allows it, and WebSocket handshakes send its cookies.
+    ///
+    /// This returns the store behind its lock, where earlier versions returned
+    /// `Arc<CookieStore>`; implementors and callers of the old accessor need
+    /// updating, and callers now `read()` or `write()` the lock to use it.
+    fn cookie_store(&self) -> Arc<tokio::sync::RwLock<cookie_manager::CookieStore>>;
 
     /// Get certificate store handle
     ///
@@ -155,6 +207,17 @@ pub trait NetworkStack: Send + Sync {
     /// * `host` - The hostname to pin
     /// * `pin_hash` - The certificate hash to pin
     fn add_certificate_pin(&mut self, host: &str, pin_hash: Vec<u8>);
+
+    /// Stop accepting requests and drain open connections
+    ///
+    /// Once called, new fetches fail fast with `NetworkError::ConnectionFailed`
+    /// and idle pooled connections are closed. Fetches already in flight keep
+    /// their connections and are given up to `timeout` to finish.
+    ///
+    /// # Returns
+    /// * `Ok(())` - All in-flight requests finished
+    /// * `Err(NetworkError::Timeout)` - Requests were still running after `timeout`
+    async fn shutdown(&self, timeout: Duration) -> Result<(), NetworkError>;
 }
 
 /// Network conditions for throttling and simulation
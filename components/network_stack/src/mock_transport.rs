@@ -0,0 +1,147 @@
+//! In-memory transport for testing code built on the stack
+
+use crate::Transport;
+use async_trait::async_trait;
+use network_errors::NetworkError;
+use network_types::{
+    HttpMethod, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseSource,
+    ResponseType,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use url::Url;
+
+/// A registered response, copied out for every matching request
+struct MockResponse {
+    status: u16,
+    status_text: String,
+    headers: http::HeaderMap,
+    body: Option<Vec<u8>>,
+    trailers: Option<http::HeaderMap>,
+}
+
+/// Transport answering requests from registered responses, without sockets
+///
+/// Responses are registered per method and URL, and every request sent is
+/// recorded so tests can assert on what the stack sent. Requests without a
+/// registered response fail with `NetworkError::ConnectionFailed`.
+///
+/// Available with the `test-util` feature.
+///
+/// # Examples
+///
+/// ```
+/// use network_stack::{MockTransport, NetworkConfig, NetworkStack, NetworkStackImpl};
+/// use network_types::{HttpMethod, NetworkRequest, NetworkResponse};
+/// use std::sync::Arc;
+/// use url::Url;
+///
+/// # tokio_test::block_on(async {
+/// let url = Url::parse("https://example.com/").unwrap();
+/// let mut ok = NetworkResponse::network_error(url.clone());
+/// ok.status = 200;
+///
+/// let transport = Arc::new(MockTransport::new());
+/// transport.on(HttpMethod::Get, url.clone(), ok);
+///
+/// let config = NetworkConfig {
+///     transport: Some(transport.clone()),
+///     ..NetworkConfig::default()
+/// };
+/// let stack = NetworkStackImpl::new(config).unwrap();
+/// let response = stack.fetch(NetworkRequest::builder(url).build()).await.unwrap();
+///
+/// assert_eq!(response.status, 200);
+/// assert_eq!(transport.requests().len(), 1);
+/// # })
+/// ```
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<HashMap<(HttpMethod, Url), MockResponse>>,
+    requests: Mutex<Vec<NetworkRequest>>,
+}
+
+impl MockTransport {
+    /// Create a transport with no registered responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answer every `method` request for `url` with `response`
+    ///
+    /// The response's URL is replaced with the request's, and its body is
+    /// repeated for each request. Registering again for the same method and
+    /// URL replaces the earlier response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `response` has a streaming body, which can't be repeated.
+    pub fn on(&self, method: HttpMethod, url: Url, response: NetworkResponse) -> &Self {
+        let body = match response.body {
+            ResponseBody::Bytes(bytes) => Some(bytes),
+            ResponseBody::Empty => None,
+            ResponseBody::Stream(_) => {
+                panic!("MockTransport responses can't have streaming bodies")
+            }
+        };
+        let response = MockResponse {
+            status: response.status,
+            status_text: response.status_text,
+            headers: response.headers,
+            body,
+            trailers: response.trailers,
+        };
+        self.responses
+            .lock()
+            .unwrap()
+            .insert((method, url), response);
+        self
+    }
+
+    /// Requests sent so far, in order
+    pub fn requests(&self) -> Vec<NetworkRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of requests sent so far
+    pub fn request_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl Transport for MockTransport {
+    async fn send(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        let key = (request.method, request.url.clone());
+        self.requests.lock().unwrap().push(request);
+
+        let responses = self.responses.lock().unwrap();
+        let response = responses.get(&key).ok_or_else(|| {
+            NetworkError::ConnectionFailed(format!("No mock response for {:?} {}", key.0, key.1))
+        })?;
+        Ok(NetworkResponse {
+            url: key.1,
+            status: response.status,
+            status_text: response.status_text.clone(),
+            headers: response.headers.clone(),
+            body: response
+                .body
+                .clone()
+                .map_or(ResponseBody::Empty, ResponseBody::Bytes),
+            redirected: false,
+            type_: ResponseType::Basic,
+            timing: ResourceTiming::default(),
+            trailers: response.trailers.clone(),
+            source: ResponseSource::Network,
+        })
+    }
+}
+
+impl std::fmt::Debug for MockTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockTransport")
+            .field("responses", &self.responses.lock().unwrap().len())
+            .field("requests", &self.request_count())
+            .finish()
+    }
+}
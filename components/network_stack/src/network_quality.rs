@@ -0,0 +1,102 @@
+//! Network quality estimation
+//!
+//! Keeps a rolling window of recent HTTP round-trip times and downlink
+//! throughput observations and maps them to an effective connection type
+//! using the thresholds of the Network Information API.
+
+use crate::EffectiveConnectionType;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of recent samples of each kind used for the estimate
+pub const SAMPLE_WINDOW: usize = 20;
+
+/// Transfers smaller than this are too short to measure throughput reliably
+pub const MIN_THROUGHPUT_BYTES: usize = 32 * 1024;
+
+/// Minimum RTT and maximum downlink (kbps) for each effective connection type,
+/// from slowest to fastest
+const THRESHOLDS: [(EffectiveConnectionType, u64, f64); 3] = [
+    (EffectiveConnectionType::Slow2G, 2000, 50.0),
+    (EffectiveConnectionType::Type2G, 1400, 70.0),
+    (EffectiveConnectionType::Type3G, 270, 700.0),
+];
+
+/// Estimates network quality from observed requests
+///
+/// Estimates are the median of the most recent [`SAMPLE_WINDOW`] samples, so
+/// a single outlier doesn't change the classification.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkQualityEstimator {
+    rtt_samples: VecDeque<Duration>,
+    downlink_samples: VecDeque<f64>,
+}
+
+impl NetworkQualityEstimator {
+    /// Create an estimator with no observations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the round-trip time of a request
+    pub fn record_rtt(&mut self, rtt: Duration) {
+        push_sample(&mut self.rtt_samples, rtt);
+    }
+
+    /// Record a transfer of `bytes` taking `elapsed`
+    ///
+    /// Transfers smaller than [`MIN_THROUGHPUT_BYTES`] or with no measurable
+    /// duration are ignored.
+    pub fn record_transfer(&mut self, bytes: usize, elapsed: Duration) {
+        if bytes < MIN_THROUGHPUT_BYTES || elapsed.is_zero() {
+            return;
+        }
+        let kbps = bytes as f64 * 8.0 / 1000.0 / elapsed.as_secs_f64();
+        push_sample(&mut self.downlink_samples, kbps);
+    }
+
+    /// Estimated round-trip time, if any requests have been observed
+    pub fn rtt(&self) -> Option<Duration> {
+        median(&self.rtt_samples)
+    }
+
+    /// Estimated downlink throughput in kilobits per second, if any transfers
+    /// have been observed
+    pub fn downlink_kbps(&self) -> Option<f64> {
+        median(&self.downlink_samples)
+    }
+
+    /// Classify the current estimates
+    ///
+    /// The slowest type whose RTT threshold is reached or whose downlink
+    /// threshold isn't exceeded wins; a missing estimate doesn't constrain the
+    /// result. With no observations at all this is `Type4G`.
+    pub fn effective_type(&self) -> EffectiveConnectionType {
+        let rtt_ms = self.rtt().map(|rtt| rtt.as_millis() as u64);
+        let downlink = self.downlink_kbps();
+
+        THRESHOLDS
+            .iter()
+            .find(|(_, min_rtt_ms, max_downlink)| {
+                rtt_ms.is_some_and(|rtt| rtt >= *min_rtt_ms)
+                    || downlink.is_some_and(|kbps| kbps <= *max_downlink)
+            })
+            .map(|(effective_type, _, _)| *effective_type)
+            .unwrap_or(EffectiveConnectionType::Type4G)
+    }
+}
+
+/// Append a sample, dropping the oldest once the window is full
+fn push_sample<T>(samples: &mut VecDeque<T>, sample: T) {
+    if samples.len() == SAMPLE_WINDOW {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+/// Median of the samples (upper median for an even count)
+fn median<T: Copy + PartialOrd>(samples: &VecDeque<T>) -> Option<T> {
+    let mut sorted: Vec<T> = samples.iter().copied().collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    sorted.get(sorted.len() / 2).copied()
+}
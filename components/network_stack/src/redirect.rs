@@ -0,0 +1,236 @@
+//! Redirect handling
+//!
+//! Implements the redirect steps of the Fetch standard: deciding whether a
+//! response is a redirect, resolving its `Location`, and building the request
+//! for the next hop.
+
+use crate::referrer;
+use network_errors::NetworkError;
+use network_types::{HttpMethod, NetworkRequest, NetworkResponse, RequestBody};
+use url::Url;
+
+/// Maximum number of redirects followed for a single fetch (Fetch standard: 20)
+pub(crate) const MAX_REDIRECTS: usize = 20;
+
+/// Headers describing the request body, dropped when a redirect changes the method to GET
+const REQUEST_BODY_HEADERS: [http::header::HeaderName; 4] = [
+    http::header::CONTENT_TYPE,
+    http::header::CONTENT_LENGTH,
+    http::header::CONTENT_ENCODING,
+    http::header::CONTENT_LANGUAGE,
+];
+
+/// Whether `status` is a redirect status
+pub(crate) fn is_redirect_status(status: u16) -> bool {
+    matches!(status, 301 | 302 | 303 | 307 | 308)
+}
+
+/// Resolve the redirect target of `response`
+///
+/// Returns `Ok(None)` if the response is not a redirect or has no `Location`
+/// header, in which case it is returned to the caller as-is.
+pub(crate) fn location(response: &NetworkResponse) -> Result<Option<Url>, NetworkError> {
+    if !is_redirect_status(response.status) {
+        return Ok(None);
+    }
+
+    let value = match response.headers.get(http::header::LOCATION) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let value = value
+        .to_str()
+        .map_err(|_| NetworkError::ProtocolError("Invalid Location header".to_string()))?;
+    let target = response.url.join(value).map_err(|e| {
+        NetworkError::InvalidUrl(format!("Invalid redirect location {}: {}", value, e))
+    })?;
+
+    if !matches!(target.scheme(), "http" | "https") {
+        return Err(NetworkError::InvalidUrl(format!(
+            "Redirect to unsupported scheme: {}",
+            target.scheme()
+        )));
+    }
+
+    Ok(Some(target))
+}
+
+/// Copy a request so it can be re-issued for the next hop of a redirect chain
+///
+/// Streaming bodies cannot be sent twice and are not carried over.
+pub(crate) fn replayable_copy(request: &NetworkRequest) -> NetworkRequest {
+    NetworkRequest {
+        url: request.url.clone(),
+        method: request.method,
+        headers: request.headers.clone(),
+        body: request.body.as_ref().and_then(replayable_body),
+        mode: request.mode,
+        credentials: request.credentials,
+        cache: request.cache,
+        redirect: request.redirect,
+        referrer: request.referrer.clone(),
+        referrer_policy: request.referrer_policy,
+        integrity: request.integrity.clone(),
+        keepalive: request.keepalive,
+        signal: request.signal.clone(),
+        priority: request.priority,
+        window: request.window,
+    }
+}
+
+/// Whether `request` has a body that [`replayable_copy`] can't carry over
+pub(crate) fn has_stream_body(request: &NetworkRequest) -> bool {
+    matches!(request.body, Some(RequestBody::Stream(_)))
+}
+
+/// Whether a redirect with `status` turns a `method` request into a bodyless GET
+///
+/// 303 See Other does so for anything but GET and HEAD. For 301 and 302 only
+/// POST is rewritten, as browsers historically have. 307 and 308 preserve
+/// the method and body.
+fn changes_to_get(status: u16, method: HttpMethod) -> bool {
+    match status {
+        301 | 302 => method == HttpMethod::Post,
+        303 => !matches!(method, HttpMethod::Get | HttpMethod::Head),
+        _ => false,
+    }
+}
+
+/// Turn the request that produced a redirect into the request for `target`
+///
+/// `request` is the [`replayable_copy`] of the request sent, and
+/// `streamed_body` whether that request had a stream body, which was not
+/// copied.
+///
+/// # Errors
+///
+/// Returns `NetworkError::Other` if the redirect preserves the body but the
+/// body was a stream, which can't be sent a second time.
+pub(crate) fn next_request(
+    mut request: NetworkRequest,
+    status: u16,
+    target: Url,
+    streamed_body: bool,
+) -> Result<NetworkRequest, NetworkError> {
+    if changes_to_get(status, request.method) {
+        request.method = HttpMethod::Get;
+        request.body = None;
+        for name in REQUEST_BODY_HEADERS.iter() {
+            request.headers.remove(name);
+        }
+    } else if streamed_body {
+        return Err(NetworkError::Other(format!(
+            "Cannot follow {} redirect to {}: the request body is a stream and can't be re-sent",
+            status, target
+        )));
+    }
+
+    // Credentials must not leak to another origin
+    if request.url.origin() != target.origin() {
+        request.headers.remove(http::header::AUTHORIZATION);
+    }
+
+    request.url = target;
+    referrer::apply(&mut request);
+    Ok(request)
+}
+
+/// Copy a request body if it can be sent again
+fn replayable_body(body: &RequestBody) -> Option<RequestBody> {
+    match body {
+        RequestBody::Bytes(bytes) => Some(RequestBody::Bytes(bytes.clone())),
+        RequestBody::Text(text) => Some(RequestBody::Text(text.clone())),
+        RequestBody::FormData(data) => Some(RequestBody::FormData(data.clone())),
+        RequestBody::Stream(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http::HeaderValue;
+
+    fn post(body: RequestBody) -> NetworkRequest {
+        NetworkRequest::builder(Url::parse("https://example.com/form").unwrap())
+            .method(HttpMethod::Post)
+            .header(
+                http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain"),
+            )
+            .body(body)
+            .build()
+    }
+
+    fn stream_body() -> RequestBody {
+        let chunks = futures::stream::iter(vec![Ok(Bytes::from_static(b"chunk"))]);
+        RequestBody::Stream(Box::pin(chunks))
+    }
+
+    fn target() -> Url {
+        Url::parse("https://example.com/next").unwrap()
+    }
+
+    #[test]
+    fn test_301_and_302_turn_post_into_get() {
+        for status in [301, 302] {
+            let sent = post(RequestBody::Text("payload".to_string()));
+            let next = next_request(replayable_copy(&sent), status, target(), false).unwrap();
+
+            assert_eq!(next.method, HttpMethod::Get);
+            assert!(next.body.is_none());
+            assert!(next.headers.get(http::header::CONTENT_TYPE).is_none());
+        }
+    }
+
+    #[test]
+    fn test_301_preserves_methods_other_than_post() {
+        let mut put = post(RequestBody::Text("payload".to_string()));
+        put.method = HttpMethod::Put;
+
+        let next = next_request(replayable_copy(&put), 301, target(), false).unwrap();
+
+        assert_eq!(next.method, HttpMethod::Put);
+        assert!(matches!(next.body, Some(RequestBody::Text(ref text)) if text == "payload"));
+    }
+
+    #[test]
+    fn test_307_and_308_preserve_method_and_body() {
+        for status in [307, 308] {
+            let sent = post(RequestBody::Text("payload".to_string()));
+            let next = next_request(replayable_copy(&sent), status, target(), false).unwrap();
+
+            assert_eq!(next.method, HttpMethod::Post);
+            assert!(matches!(next.body, Some(RequestBody::Text(ref text)) if text == "payload"));
+            assert_eq!(next.headers[http::header::CONTENT_TYPE], "text/plain");
+            assert_eq!(next.url, target());
+        }
+    }
+
+    #[test]
+    fn test_stream_body_on_307_is_an_error() {
+        let sent = post(stream_body());
+        let streamed = has_stream_body(&sent);
+
+        let result = next_request(replayable_copy(&sent), 307, target(), streamed);
+
+        match result {
+            Err(NetworkError::Other(msg)) => assert!(msg.contains("stream"), "{}", msg),
+            other => panic!(
+                "Expected a stream body error, got {:?}",
+                other.map(|r| r.url)
+            ),
+        }
+    }
+
+    #[test]
+    fn test_stream_body_is_dropped_when_redirect_changes_to_get() {
+        let sent = post(stream_body());
+        let streamed = has_stream_body(&sent);
+
+        let next = next_request(replayable_copy(&sent), 303, target(), streamed).unwrap();
+
+        assert_eq!(next.method, HttpMethod::Get);
+        assert!(next.body.is_none());
+    }
+}
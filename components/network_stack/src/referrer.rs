@@ -0,0 +1,94 @@
+//! Referrer policy
+//!
+//! Computes the `Referer` header value for a request following the
+//! "determine request's referrer" algorithm of the W3C Referrer Policy spec.
+
+use network_types::{NetworkRequest, ReferrerPolicy};
+use url::Url;
+
+/// Referrer URLs longer than this are reduced to their origin
+const MAX_REFERRER_LENGTH: usize = 4096;
+
+/// Compute the `Referer` header value sent from `referrer` to `target`
+///
+/// Returns `None` when the policy (or the referrer's scheme) means no referrer
+/// is sent. The referrer is stripped of credentials and fragment; where the
+/// policy only allows the origin, it is serialized as `scheme://host[:port]/`.
+///
+/// # Examples
+///
+/// ```
+/// use network_stack::compute_referrer;
+/// use network_types::ReferrerPolicy;
+/// use url::Url;
+///
+/// let referrer = Url::parse("https://example.com/page?q=1").unwrap();
+/// let target = Url::parse("https://cdn.example.net/app.js").unwrap();
+///
+/// assert_eq!(
+///     compute_referrer(ReferrerPolicy::StrictOriginWhenCrossOrigin, &referrer, &target),
+///     Some("https://example.com/".to_string())
+/// );
+/// ```
+pub fn compute_referrer(policy: ReferrerPolicy, referrer: &Url, target: &Url) -> Option<String> {
+    if !matches!(referrer.scheme(), "http" | "https") {
+        return None;
+    }
+
+    let origin = referrer.origin().ascii_serialization() + "/";
+    let mut full = referrer.clone();
+    let _ = full.set_username("");
+    let _ = full.set_password(None);
+    full.set_fragment(None);
+    let full = if full.as_str().len() > MAX_REFERRER_LENGTH {
+        origin.clone()
+    } else {
+        full.to_string()
+    };
+
+    let same_origin = referrer.origin() == target.origin();
+    let downgrade = is_secure(referrer) && !is_secure(target);
+
+    match policy {
+        ReferrerPolicy::NoReferrer => None,
+        ReferrerPolicy::NoReferrerWhenDowngrade => (!downgrade).then_some(full),
+        ReferrerPolicy::Origin => Some(origin),
+        ReferrerPolicy::OriginWhenCrossOrigin => Some(if same_origin { full } else { origin }),
+        ReferrerPolicy::SameOrigin => same_origin.then_some(full),
+        ReferrerPolicy::StrictOrigin => (!downgrade).then_some(origin),
+        ReferrerPolicy::StrictOriginWhenCrossOrigin => {
+            if same_origin {
+                Some(full)
+            } else if downgrade {
+                None
+            } else {
+                Some(origin)
+            }
+        }
+        ReferrerPolicy::UnsafeUrl => Some(full),
+    }
+}
+
+/// Whether a URL is delivered over a secure transport
+fn is_secure(url: &Url) -> bool {
+    matches!(url.scheme(), "https" | "wss")
+}
+
+/// Set (or clear) the `Referer` header of a request for its current URL
+///
+/// Called before the request is sent and again for every redirect hop, since
+/// the value depends on the target.
+pub(crate) fn apply(request: &mut NetworkRequest) {
+    request.headers.remove(http::header::REFERER);
+
+    let referrer = match request.referrer.as_deref().map(Url::parse) {
+        Some(Ok(referrer)) => referrer,
+        _ => return,
+    };
+
+    if let Some(value) = compute_referrer(request.referrer_policy, &referrer, &request.url)
+        .and_then(|value| http::HeaderValue::from_str(&value).ok())
+    {
+        request.headers.insert(http::header::REFERER, value);
+    }
+}
@@ -0,0 +1,30 @@
+//! Retrying transient failures
+//!
+//! Decides whether a request may be sent again and whether the outcome of an
+//! attempt warrants another one, according to the configured [`RetryPolicy`].
+
+use crate::RetryPolicy;
+use network_errors::NetworkError;
+use network_types::{NetworkRequest, NetworkResponse, RequestBody};
+
+/// Whether `request` may be sent more than once under `policy`
+///
+/// Non-idempotent methods need the policy's explicit opt-in, and streaming
+/// bodies are never retried since they cannot be replayed.
+pub(crate) fn is_retryable_request(policy: &RetryPolicy, request: &NetworkRequest) -> bool {
+    if matches!(request.body, Some(RequestBody::Stream(_))) {
+        return false;
+    }
+    request.method.is_idempotent() || policy.retry_non_idempotent
+}
+
+/// Whether the outcome of an attempt should be retried
+pub(crate) fn should_retry(
+    policy: &RetryPolicy,
+    result: &Result<NetworkResponse, NetworkError>,
+) -> bool {
+    match result {
+        Ok(response) => policy.retry_statuses.contains(&response.status),
+        Err(e) => (policy.retry_on)(e),
+    }
+}
@@ -0,0 +1,224 @@
+//! Fetching URLs that aren't served over HTTP
+//!
+//! `data:` URLs are decoded in place, `file:` URLs are read from disk
+//! under the configured [`FileSecurityPolicy`] and `blob:` URLs are looked
+//! up in the stack's [`BlobUrlRegistry`]; all produce a synthesized `200`
+//! response. HTTP(S) URLs are left to the network path, and any other
+//! scheme is rejected.
+
+use crate::UrlHandlersConfig;
+use http::{HeaderMap, HeaderValue};
+use network_errors::NetworkError;
+use network_types::{
+    HttpMethod, NetworkRequest, NetworkResponse, ResourceTiming, ResponseBody, ResponseSource,
+    ResponseType,
+};
+use std::sync::Arc;
+use tracing::debug;
+use url::Url;
+use url_handlers::{
+    BlobUrlHandler, BlobUrlRegistry, DataUrlHandler, FileSecurityPolicy, FileUrlHandler,
+};
+
+/// Content type of files whose extension isn't recognized
+const DEFAULT_FILE_TYPE: &str = "application/octet-stream";
+
+/// Whether `url` is fetched over the network rather than by [`SchemeDispatcher`]
+pub(crate) fn is_network_scheme(url: &Url) -> bool {
+    matches!(url.scheme(), "http" | "https")
+}
+
+/// Handlers for the schemes the stack serves without a network request
+pub(crate) struct SchemeDispatcher {
+    data_urls: bool,
+    file_urls: bool,
+    file_handler: FileUrlHandler,
+    blob_handler: BlobUrlHandler,
+}
+
+impl SchemeDispatcher {
+    /// Create a dispatcher with the schemes and file paths `config` allows
+    pub(crate) fn new(config: &UrlHandlersConfig) -> Self {
+        Self {
+            data_urls: config.enable_data_urls,
+            file_urls: config.enable_file_urls,
+            file_handler: FileUrlHandler::new(FileSecurityPolicy {
+                allow_directory_traversal: false,
+                allowed_paths: config.allowed_file_paths.clone(),
+            }),
+            blob_handler: BlobUrlHandler::new(Arc::new(BlobUrlRegistry::new())),
+        }
+    }
+
+    /// Registry the stack resolves `blob:` URLs against
+    pub(crate) fn blob_registry(&self) -> Arc<BlobUrlRegistry> {
+        Arc::clone(self.blob_handler.registry())
+    }
+
+    /// Fetch a request whose URL isn't an HTTP(S) URL
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidUrl` for a malformed URL, a disabled or
+    /// unsupported scheme, a file outside the allowed paths, or an unknown
+    /// or revoked blob;
+    /// `NetworkError::Io` when a file can't be read; and
+    /// `NetworkError::ProtocolError` for `ftp:` URLs.
+    pub(crate) async fn fetch(
+        &self,
+        request: NetworkRequest,
+    ) -> Result<NetworkResponse, NetworkError> {
+        let (headers, body) = match request.url.scheme() {
+            "data" if self.data_urls => {
+                debug!("Handling data: URL");
+                self.fetch_data(&request.url)?
+            }
+            "file" if self.file_urls => {
+                debug!("Handling file: URL");
+                self.fetch_file(&request.url).await?
+            }
+            "blob" => {
+                debug!("Handling blob: URL");
+                self.fetch_blob(&request)?
+            }
+            "data" | "file" => {
+                return Err(NetworkError::InvalidUrl(format!(
+                    "{}: URLs are disabled",
+                    request.url.scheme()
+                )));
+            }
+            "ftp" => {
+                return Err(NetworkError::ProtocolError(
+                    "FTP protocol not yet implemented".to_string(),
+                ));
+            }
+            scheme => {
+                return Err(NetworkError::InvalidUrl(format!(
+                    "Unsupported URL scheme: {}",
+                    scheme
+                )));
+            }
+        };
+
+        Ok(local_response(request, headers, body))
+    }
+
+    /// Decode a `data:` URL into its headers and body
+    fn fetch_data(&self, url: &Url) -> Result<(HeaderMap, Vec<u8>), NetworkError> {
+        let data = DataUrlHandler::parse(url.as_str())?;
+        let content_type = match &data.charset {
+            Some(charset) => format!("{};charset={}", data.mime_type, charset),
+            None => data.mime_type.clone(),
+        };
+
+        let mut headers = HeaderMap::new();
+        if let Ok(value) = HeaderValue::from_str(&content_type) {
+            headers.insert(http::header::CONTENT_TYPE, value);
+        }
+        Ok((headers, data.data))
+    }
+
+    /// Look up a `blob:` URL in the registry
+    ///
+    /// Only `GET` (and `HEAD`) may read a blob.
+    fn fetch_blob(&self, request: &NetworkRequest) -> Result<(HeaderMap, Vec<u8>), NetworkError> {
+        if !matches!(request.method, HttpMethod::Get | HttpMethod::Head) {
+            return Err(NetworkError::InvalidUrl(format!(
+                "blob: URLs can't be fetched with {:?}",
+                request.method
+            )));
+        }
+
+        let blob = self.blob_handler.read(request.url.as_str())?;
+        let mut headers = HeaderMap::new();
+        if !blob.mime_type.is_empty() {
+            if let Ok(value) = HeaderValue::from_str(&blob.mime_type) {
+                headers.insert(http::header::CONTENT_TYPE, value);
+            }
+        }
+        Ok((headers, blob.data))
+    }
+
+    /// Read a `file:` URL, rendering directories as an HTML index
+    async fn fetch_file(&self, url: &Url) -> Result<(HeaderMap, Vec<u8>), NetworkError> {
+        let is_directory = match url.to_file_path() {
+            Ok(path) => tokio::fs::metadata(&path)
+                .await
+                .is_ok_and(|metadata| metadata.is_dir()),
+            Err(()) => false,
+        };
+
+        let (content_type, body) = if is_directory {
+            let entries = self.file_handler.read_directory(url.as_str()).await?;
+            let index = FileUrlHandler::render_directory_index(url.path(), &entries);
+            ("text/html;charset=utf-8", index.into_bytes())
+        } else {
+            let body = self.file_handler.read(url.as_str()).await?;
+            (file_content_type(url.path()), body)
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static(content_type),
+        );
+        Ok((headers, body))
+    }
+}
+
+/// Content type for a file, guessed from the extension of its path
+fn file_content_type(path: &str) -> &'static str {
+    let Some((_, extension)) = path.rsplit_once('.') else {
+        return DEFAULT_FILE_TYPE;
+    };
+    if extension.contains('/') {
+        return DEFAULT_FILE_TYPE;
+    }
+
+    match extension.to_ascii_lowercase().as_str() {
+        "html" | "htm" => "text/html",
+        "txt" => "text/plain",
+        "css" => "text/css",
+        "js" | "mjs" => "text/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "wasm" => "application/wasm",
+        "pdf" => "application/pdf",
+        _ => DEFAULT_FILE_TYPE,
+    }
+}
+
+/// Build the `200` response for a locally served request
+///
+/// `HEAD` requests get the same headers with an empty body.
+fn local_response(
+    request: NetworkRequest,
+    mut headers: HeaderMap,
+    body: Vec<u8>,
+) -> NetworkResponse {
+    headers.insert(http::header::CONTENT_LENGTH, HeaderValue::from(body.len()));
+    let body = if request.method == HttpMethod::Head {
+        Vec::new()
+    } else {
+        body
+    };
+
+    NetworkResponse {
+        status: 200,
+        status_text: String::from("OK"),
+        headers,
+        body: ResponseBody::Bytes(body),
+        url: request.url,
+        redirected: false,
+        timing: ResourceTiming::default(),
+        type_: ResponseType::Basic,
+        trailers: None,
+        source: ResponseSource::Network,
+    }
+}
@@ -6,16 +6,33 @@
 //! Full integration with all protocol handlers will be completed as those components
 //! finalize their public APIs.
 
+use crate::caching;
+use crate::coalescing::{self, CoalescingKey, RequestCoalescer, SharedResponse, Slot};
+use crate::cookies;
+use crate::cors;
+use crate::decoding;
+use crate::integrity;
+use crate::network_quality::NetworkQualityEstimator;
+use crate::redirect::{self, MAX_REDIRECTS};
+use crate::referrer;
+use crate::retry;
+use crate::schemes::{self, SchemeDispatcher};
 use crate::{NetworkConditions, NetworkConfig, NetworkStack};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::Stream;
+use http_cache::RangeLookup;
 use network_errors::NetworkError;
-use network_types::{NetworkRequest, NetworkResponse};
+use network_types::{
+    CacheMode, NetworkRequest, NetworkResponse, RedirectMode, ResourceTiming, ResponseType,
+};
+use std::collections::HashMap;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use url::Url;
 
 /// Network connection type
@@ -75,17 +92,23 @@ pub struct NetworkStackImpl {
     /// HTTP/3 client (optional)
     http3_client: Option<Arc<http3_protocol::Http3Client>>,
 
+    /// HTTP/3 endpoints advertised by origins through `Alt-Svc`
+    alt_svc: Arc<Mutex<http3_protocol::AltSvcCache>>,
+
     /// WebSocket client
     websocket_client: Arc<websocket_protocol::WebSocketClient>,
 
     /// DNS resolver
     dns_resolver: Arc<dyn dns_resolver::DnsResolver>,
 
+    /// Cache of addresses looked up through `dns_resolver`
+    dns_cache: Arc<tokio::sync::Mutex<dns_resolver::DnsCache>>,
+
     /// TLS configuration
     tls_config: tls_manager::TlsConfig,
 
     /// Cookie store
-    cookie_store: Arc<cookie_manager::CookieStore>,
+    cookie_store: Arc<RwLock<cookie_manager::CookieStore>>,
 
     /// HTTP cache
     http_cache: Arc<http_cache::HttpCache>,
@@ -112,11 +135,8 @@ pub struct NetworkStackImpl {
     /// Bandwidth limiter
     bandwidth_limiter: Arc<bandwidth_limiter::BandwidthLimiter>,
 
-    /// Data URL handler
-    data_url_handler: Arc<url_handlers::DataUrlHandler>,
-
-    /// File URL handler
-    file_url_handler: Arc<url_handlers::FileUrlHandler>,
+    /// Handlers for data:, file: and blob: URLs
+    schemes: Arc<SchemeDispatcher>,
 
     /// Mixed content blocker
     mixed_content_blocker: Arc<mixed_content_blocker::MixedContentBlocker>,
@@ -138,6 +158,15 @@ pub struct NetworkStackImpl {
 
     /// FTP client
     ftp_client: Arc<tokio::sync::Mutex<ftp_protocol::FtpClient>>,
+
+    /// In-flight requests shared between identical concurrent fetches
+    coalescer: Arc<RequestCoalescer>,
+
+    /// RTT and throughput observations behind the effective connection type
+    quality: Arc<Mutex<NetworkQualityEstimator>>,
+
+    /// Set once `shutdown` is called; new fetches are refused
+    shut_down: AtomicBool,
 }
 
 impl NetworkStackImpl {
@@ -152,9 +181,20 @@ impl NetworkStackImpl {
     pub fn new(config: NetworkConfig) -> Result<Self, NetworkError> {
         info!("Initializing NetworkStack");
 
-        // Initialize DNS resolver
-        let dns_config = config.dns.clone().unwrap_or_default();
-        let dns_resolver = Arc::new(dns_resolver::StandardResolver::new(Some(dns_config))?) as Arc<dyn dns_resolver::DnsResolver>;
+        // Initialize DNS resolver, unless one was injected
+        let dns_resolver = match config.dns_resolver.clone() {
+            Some(resolver) => resolver,
+            None => {
+                let dns_config = config.dns.clone().unwrap_or_default();
+                Arc::new(dns_resolver::StandardResolver::new(Some(dns_config))?) as Arc<dyn dns_resolver::DnsResolver>
+            }
+        };
+
+        // Cache lookups so hosts resolved ahead of time by prefetch_dns skip
+        // resolution when requested
+        let caching_resolver = dns_resolver::CachingResolver::new(dns_resolver);
+        let dns_cache = caching_resolver.cache();
+        let dns_resolver = Arc::new(caching_resolver) as Arc<dyn dns_resolver::DnsResolver>;
 
         // Initialize TLS configuration
         let tls_config = config.security.clone().map(|_s| tls_manager::TlsConfig::new()
@@ -165,7 +205,7 @@ impl NetworkStackImpl {
         let cert_store = Arc::new(tls_manager::CertificateStore::new());
 
         // Initialize cookie store
-        let cookie_store = Arc::new(cookie_manager::CookieStore::new());
+        let cookie_store = Arc::new(RwLock::new(cookie_manager::CookieStore::new()));
 
         // Initialize HTTP cache
         let cache_config = config.cache.clone()
@@ -179,18 +219,18 @@ impl NetworkStackImpl {
 
         // Initialize HTTP/1.1 client
         let http1_config = config.http1.clone().unwrap_or_default();
-        let http1_client = Arc::new(http1_protocol::Http1Client::new(http1_config));
+        let http1_client = Arc::new(http1_protocol::Http1Client::with_resolver(http1_config, dns_resolver.clone()));
 
         // Initialize HTTP/2 client
         let http2_config = config.http2.clone().unwrap_or_default();
         let http2_client = Arc::new(
-            http2_protocol::Http2Client::new(http2_config)
+            http2_protocol::Http2Client::with_resolver(http2_config, dns_resolver.clone())
                 .map_err(|e| NetworkError::ProtocolError(format!("HTTP/2 initialization failed: {:?}", e)))?
         );
 
         // Initialize HTTP/3 client (if enabled)
         let http3_client = if let Some(http3_config) = config.http3.clone() {
-            Some(Arc::new(http3_protocol::Http3Client::new(http3_config)))
+            Some(Arc::new(http3_protocol::Http3Client::with_resolver(http3_config, dns_resolver.clone())))
         } else {
             None
         };
@@ -239,12 +279,7 @@ impl NetworkStackImpl {
 
         // URL handlers
         let url_config = config.url_handlers.clone().unwrap_or_default();
-        let data_url_handler = Arc::new(url_handlers::DataUrlHandler);
-        let file_security_policy = url_handlers::FileSecurityPolicy {
-            allow_directory_traversal: false,
-            allowed_paths: url_config.allowed_file_paths.clone(),
-        };
-        let file_url_handler = Arc::new(url_handlers::FileUrlHandler::new(file_security_policy));
+        let schemes = Arc::new(SchemeDispatcher::new(&url_config));
 
         // Mixed content blocker
         let mixed_content_config = config.mixed_content.clone().unwrap_or_default();
@@ -317,8 +352,10 @@ impl NetworkStackImpl {
             http1_client,
             http2_client,
             http3_client,
+            alt_svc: Arc::new(Mutex::new(http3_protocol::AltSvcCache::new())),
             websocket_client,
             dns_resolver,
+            dns_cache,
             tls_config,
             cookie_store,
             http_cache,
@@ -330,8 +367,7 @@ impl NetworkStackImpl {
             content_encoder,
             scheduler,
             bandwidth_limiter,
-            data_url_handler,
-            file_url_handler,
+            schemes,
             mixed_content_blocker,
             csp_processor,
             proxy_client,
@@ -339,9 +375,32 @@ impl NetworkStackImpl {
             cert_pinner,
             platform_integration,
             ftp_client,
+            coalescer: Arc::new(RequestCoalescer::new()),
+            quality: Arc::new(Mutex::new(NetworkQualityEstimator::new())),
+            shut_down: AtomicBool::new(false),
         })
     }
 
+    /// Registry of the `blob:` URLs `fetch` can read
+    ///
+    /// Object URLs created here resolve until they are revoked.
+    pub fn blob_registry(&self) -> Arc<url_handlers::BlobUrlRegistry> {
+        self.schemes.blob_registry()
+    }
+
+    /// Handle to the cache of resolved host addresses
+    ///
+    /// Every lookup made by the stack, including those started by
+    /// `prefetch_dns`, is cached here and reused until its TTL expires.
+    pub fn dns_cache(&self) -> Arc<tokio::sync::Mutex<dns_resolver::DnsCache>> {
+        self.dns_cache.clone()
+    }
+
+    /// Snapshot of the HTTP/1.1 connection pool's usage
+    pub async fn pool_metrics(&self) -> http1_protocol::PoolMetrics {
+        self.http1_client.pool_metrics().await
+    }
+
     /// Select the appropriate HTTP client based on URL and configuration
     fn select_http_client(&self, url: &Url) -> HttpProtocolClient {
         let scheme = url.scheme();
@@ -378,28 +437,84 @@ impl NetworkStackImpl {
         true // Default to supporting HTTP/2 for HTTPS
     }
 
-    /// Check if HTTP/3 is supported for this URL
-    fn supports_http3(&self, _url: &Url) -> bool {
-        // In a full implementation, this would check:
-        // - Alt-Svc headers indicating H3 support
-        // - Prior knowledge of QUIC support
-        // - Configuration settings
-        false // Conservative default
+    /// Whether the origin of `url` has advertised HTTP/3 on its own host and
+    /// port through an `Alt-Svc` header that is still fresh
+    ///
+    /// Alternatives on another host or port are ignored, as the HTTP/3 client
+    /// connects to the request URL's authority. `fetch` uses HTTP/3 for such
+    /// origins when [`NetworkConfig::http3`] is set.
+    pub fn supports_http3(&self, url: &Url) -> bool {
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return false;
+        };
+        self.alt_svc
+            .lock()
+            .unwrap()
+            .http3_alternative(host, port)
+            .is_some_and(|alt| {
+                alt.port == port
+                    && alt
+                        .host
+                        .map_or(true, |alt_host| alt_host.eq_ignore_ascii_case(host))
+            })
     }
-}
 
-/// HTTP protocol client selector
-enum HttpProtocolClient {
-    Http1(Arc<http1_protocol::Http1Client>),
-    Http2(Arc<http2_protocol::Http2Client>),
-    Http3(Arc<http3_protocol::Http3Client>),
-}
+    /// Remember the alternatives in an HTTPS response's `Alt-Svc` header
+    ///
+    /// Headers that cannot be parsed are ignored.
+    fn record_alt_svc(&self, url: &Url, response: &NetworkResponse) {
+        if url.scheme() != "https" {
+            return;
+        }
+        let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+            return;
+        };
+        let Some(value) = response
+            .headers
+            .get("alt-svc")
+            .and_then(|value| value.to_str().ok())
+        else {
+            return;
+        };
+        if let Err(e) = self.alt_svc.lock().unwrap().update(host, port, value) {
+            debug!("Ignoring Alt-Svc header from {}: {}", url, e);
+        }
+    }
 
-#[async_trait]
-impl NetworkStack for NetworkStackImpl {
-    async fn fetch(&self, mut request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+    /// Run a fetch to completion without observing its abort token
+    ///
+    /// Configured interceptors see the request before it is processed, in
+    /// registration order, and the response afterwards, in reverse order.
+    async fn perform_fetch(&self, mut request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        if self.shut_down.load(Ordering::SeqCst) {
+            return Err(NetworkError::ConnectionFailed("Network stack is shut down".to_string()));
+        }
+
+        let interceptors = &self.config.interceptors;
+        for interceptor in interceptors {
+            interceptor.on_request(&mut request).await;
+        }
+
+        let sent = (!interceptors.is_empty()).then(|| redirect::replayable_copy(&request));
+        let mut response = self.process_fetch(request).await?;
+
+        if let Some(sent) = sent {
+            for interceptor in interceptors.iter().rev() {
+                interceptor.on_response(&sent, &mut response).await;
+            }
+        }
+        Ok(response)
+    }
+
+    /// Route a request to its scheme handler, applying the stack's request processing
+    async fn process_fetch(&self, mut request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
         debug!("Fetching URL: {}", request.url);
 
+        // data:, file:, blob: and unsupported schemes never reach the network
+        if !schemes::is_network_scheme(&request.url) {
+            return self.schemes.fetch(request).await;
+        }
+
         // Check if offline mode is enabled
         let conditions = self.conditions.read().await;
         if conditions.offline {
@@ -407,66 +522,26 @@ impl NetworkStack for NetworkStackImpl {
         }
         drop(conditions);
 
-        let scheme = request.url.scheme();
-
-        // Route non-HTTP URLs to appropriate handlers
-        match scheme {
-            "data" => {
-                debug!("Handling data: URL");
-                let data = url_handlers::DataUrlHandler::parse(request.url.as_str())
-                    .map_err(|e| NetworkError::InvalidUrl(format!("Invalid data URL: {:?}", e)))?;
-
-                // Create response from data URL
-                let mut headers = http::HeaderMap::new();
-                headers.insert(
-                    http::header::CONTENT_TYPE,
-                    http::HeaderValue::from_str(&data.mime_type)
-                        .unwrap_or(http::HeaderValue::from_static("text/plain")),
-                );
-
-                return Ok(NetworkResponse {
-                    status: 200,
-                    status_text: String::from("OK"),
-                    headers,
-                    body: network_types::ResponseBody::Bytes(data.data),
-                    url: request.url,
-                    redirected: false,
-                    timing: network_types::ResourceTiming::default(),
-                    type_: network_types::ResponseType::Basic,
-                });
-            }
-            "file" => {
-                debug!("Handling file: URL");
-                let data = self.file_url_handler.read(request.url.as_str()).await
-                    .map_err(|e| NetworkError::InvalidUrl(format!("File URL error: {:?}", e)))?;
-
-                return Ok(NetworkResponse {
-                    status: 200,
-                    status_text: String::from("OK"),
-                    headers: http::HeaderMap::new(),
-                    body: network_types::ResponseBody::Bytes(data),
-                    url: request.url,
-                    redirected: false,
-                    timing: network_types::ResourceTiming::default(),
-                    type_: network_types::ResponseType::Basic,
-                });
-            }
-            "ftp" => {
-                debug!("Handling ftp: URL - FTP protocol not yet fully integrated");
-                // FTP handling would go here
-                return Err(NetworkError::ProtocolError("FTP protocol not yet implemented".to_string()));
-            }
-            _ => {
-                // Continue with HTTP/HTTPS processing
-            }
-        }
-
         // Mixed content blocking check (for HTTPS pages loading HTTP resources)
         // This would require page context which we don't have here, so we'll skip for now
         // In a full implementation, this would be handled at a higher level
 
-        // Add Accept-Encoding header for content encoding support
-        if !request.headers.contains_key(http::header::ACCEPT_ENCODING) {
+        // Configured default headers, without overriding the request's own
+        self.apply_default_headers(&mut request);
+
+        // Referer header according to the request's referrer policy
+        referrer::apply(&mut request);
+
+        // Add Accept-Encoding header for content encoding support. Bodies are only
+        // decoded when the stack negotiated the encoding itself; a caller that
+        // sets its own Accept-Encoding receives the raw encoded bytes.
+        let decode_content = self
+            .config
+            .content_encoding
+            .as_ref()
+            .is_some_and(|config| config.enabled)
+            && !request.headers.contains_key(http::header::ACCEPT_ENCODING);
+        if decode_content {
             let accept_encoding = self.content_encoder.get_accept_encoding();
             request.headers.insert(
                 http::header::ACCEPT_ENCODING,
@@ -477,6 +552,11 @@ impl NetworkStack for NetworkStackImpl {
         // CORS validation (validate request before sending)
         // This is a simplified version - full CORS requires origin context
         // In a real browser, this would be handled by the fetch API layer
+        //
+        // The request's Origin header identifies the requesting document, so
+        // a cross-origin response to it can be filtered to the headers the
+        // document may read
+        let cors_origin = cors::request_origin(&request);
 
         // CSP enforcement (check if request is allowed by CSP policy)
         let csp = self.csp_processor.read().await;
@@ -495,19 +575,399 @@ impl NetworkStack for NetworkStackImpl {
         // Bandwidth limiting - throttle if configured
         // This happens during the actual data transfer, not here
 
-        // Select appropriate protocol handler
+        // Serve from the HTTP cache where allowed; otherwise select the protocol
+        // handler and send, following redirects and coalescing identical
+        // in-flight requests
+        let integrity_metadata = request.integrity.clone();
+        let mut response = self.fetch_with_cache(request, decode_content).await?;
+
+        // Subresource Integrity check over the final response body
+        if let Some(metadata) = integrity_metadata {
+            response = integrity::verify_response(&metadata, response).await?;
+        }
+
+        // Cross-origin CORS responses only expose the safelisted headers and
+        // those listed in Access-Control-Expose-Headers
+        if let Some(origin) = cors_origin {
+            cors::filter_response(&self.cors_validator, &origin, &mut response);
+        }
+
+        debug!("Request completed with status: {}", response.status);
+        Ok(response)
+    }
+
+    /// Serve a request from the HTTP cache where its cache mode allows
+    ///
+    /// Fresh entries are returned without contacting the origin. Stale entries
+    /// are revalidated with a conditional request, and a `304 Not Modified`
+    /// answer serves the cached body. Storable network responses are written
+    /// back to the cache. The response's `source` records which case applied.
+    ///
+    /// Byte range requests are answered from a fresh cached body when their
+    /// `If-Range` validator (if any) still matches, and otherwise become a
+    /// request for the full representation.
+    async fn fetch_with_cache(
+        &self,
+        mut request: NetworkRequest,
+        decode_content: bool,
+    ) -> Result<NetworkResponse, NetworkError> {
+        if !caching::is_cacheable_request(&request) {
+            return self.fetch_from_network(request, decode_content).await;
+        }
+
+        let mode = request.cache;
+        if request.headers.contains_key(http::header::RANGE) {
+            match self.http_cache.get_range(&request).await {
+                RangeLookup::Hit(response) if mode == CacheMode::Default => {
+                    debug!("Serving range of {} from cache", request.url);
+                    return Ok(*response);
+                }
+                // The caller's copy is outdated: fetch the full representation
+                RangeLookup::Refetch => {
+                    request.headers.remove(http::header::RANGE);
+                    request.headers.remove(http::header::IF_RANGE);
+                }
+                _ => return self.fetch_from_network(request, decode_content).await,
+            }
+        }
+
+        let cache_key = request.clone();
+        let cached = match mode {
+            CacheMode::Reload => None,
+            _ => self.http_cache.get_stale(&cache_key).await,
+        };
+
+        let mut revalidating = None;
+        match cached {
+            Some(cached)
+                if matches!(mode, CacheMode::ForceCache | CacheMode::OnlyIfCached)
+                    || (mode == CacheMode::Default && cached.is_fresh()) =>
+            {
+                debug!("Serving {} from cache", request.url);
+                return Ok(caching::from_cache(cached));
+            }
+            None if mode == CacheMode::OnlyIfCached => {
+                return Err(NetworkError::Other(format!(
+                    "No cached response for {} in only-if-cached mode",
+                    request.url
+                )));
+            }
+            Some(cached) => {
+                revalidating =
+                    caching::add_validators(&mut request, &cached.response).then_some(cached);
+            }
+            None => {}
+        }
+
+        let mut response = self.fetch_from_network(request, decode_content).await?;
+        if response.status == 304 {
+            if let Some(cached) = revalidating {
+                response = caching::not_modified(cached, response);
+            }
+        }
+
+        if let Some(max_age) = caching::freshness_lifetime(&response, self.http_cache.max_age()) {
+            if let Err(e) = self
+                .http_cache
+                .store_with_max_age(&cache_key, &response, max_age)
+                .await
+            {
+                warn!(
+                    "Failed to store response for {} in cache: {}",
+                    cache_key.url, e
+                );
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Send a request to the network and decode its body if the stack negotiated the encoding
+    async fn fetch_from_network(
+        &self,
+        request: NetworkRequest,
+        decode_content: bool,
+    ) -> Result<NetworkResponse, NetworkError> {
+        let mut response = self.fetch_following_redirects(request).await?;
+
+        // Server-Timing may be split over several header lines
+        let server_timing = response
+            .headers
+            .get_all("server-timing")
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>()
+            .join(",");
+        response.timing.server_timing = ResourceTiming::parse_server_timing(&server_timing);
+
+        if decode_content {
+            return decoding::decode_response(&self.content_encoder, response).await;
+        }
+        Ok(response)
+    }
+
+    /// Merge the configured default headers into a request
+    ///
+    /// A header already present on the request keeps its values; otherwise
+    /// all configured values are copied. `User-Agent` falls back to
+    /// [`DEFAULT_USER_AGENT`](crate::DEFAULT_USER_AGENT).
+    fn apply_default_headers(&self, request: &mut NetworkRequest) {
+        for name in self.config.default_headers.keys() {
+            if request.headers.contains_key(name) {
+                continue;
+            }
+            for value in self.config.default_headers.get_all(name) {
+                request.headers.append(name.clone(), value.clone());
+            }
+        }
+
+        if !request.headers.contains_key(http::header::USER_AGENT) {
+            request.headers.insert(
+                http::header::USER_AGENT,
+                http::HeaderValue::from_static(crate::DEFAULT_USER_AGENT),
+            );
+        }
+    }
+
+    /// Send an HTTP(S) request, handling redirects according to its redirect mode
+    ///
+    /// In `Follow` mode up to [`MAX_REDIRECTS`] redirects are followed. A chain
+    /// may come back to a URL once with the same method, e.g. after a login hop
+    /// sets a cookie; coming back a second time is treated as a loop. Both
+    /// cases fail with `NetworkError::TooManyRedirects`.
+    async fn fetch_following_redirects(
+        &self,
+        mut request: NetworkRequest,
+    ) -> Result<NetworkResponse, NetworkError> {
+        let mut visits = HashMap::new();
+        visits.insert((request.method, request.url.clone()), 1);
+        let mut redirect_count = 0;
+
+        loop {
+            let mode = request.redirect;
+            let retained = redirect::replayable_copy(&request);
+            let streamed_body = redirect::has_stream_body(&request);
+
+            // Cookies are matched per hop, so a redirect to another host
+            // doesn't carry the previous host's cookies
+            let include_credentials = cookies::include_credentials(&request);
+            if include_credentials {
+                cookies::attach(&self.cookie_store, &mut request).await;
+            }
+            let mut response = self.fetch_with_retry(request).await?;
+            if include_credentials {
+                cookies::store(&self.cookie_store, &retained.url, &response).await;
+            }
+
+            let target = match redirect::location(&response)? {
+                Some(target) => target,
+                None => {
+                    response.redirected = redirect_count > 0;
+                    return Ok(response);
+                }
+            };
+
+            match mode {
+                RedirectMode::Follow => {}
+                RedirectMode::Error => {
+                    return Err(NetworkError::Other(format!(
+                        "Redirect to {} not allowed in error redirect mode",
+                        target
+                    )));
+                }
+                RedirectMode::Manual => {
+                    // An opaque-redirect response hides the redirect's status,
+                    // headers and body
+                    let mut opaque = NetworkResponse::network_error(response.url);
+                    opaque.type_ = ResponseType::OpaqueRedirect;
+                    opaque.redirected = redirect_count > 0;
+                    opaque.timing = response.timing;
+                    return Ok(opaque);
+                }
+            }
+
+            if redirect_count == MAX_REDIRECTS {
+                return Err(NetworkError::TooManyRedirects);
+            }
+
+            debug!("Following {} redirect to {}", response.status, target);
+            request = redirect::next_request(retained, response.status, target, streamed_body)?;
+            let seen = visits
+                .entry((request.method, request.url.clone()))
+                .or_insert(0);
+            *seen += 1;
+            if *seen > 2 {
+                debug!("Redirect loop detected at {}", request.url);
+                return Err(NetworkError::TooManyRedirects);
+            }
+            redirect_count += 1;
+        }
+    }
+
+    /// Send one hop of a fetch, retrying transient failures
+    ///
+    /// With a [`RetryPolicy`](crate::RetryPolicy) configured, failed attempts
+    /// and responses with a retryable status are repeated with exponential
+    /// backoff until an attempt succeeds or the retries are used up; the last
+    /// outcome is returned as-is.
+    async fn fetch_with_retry(
+        &self,
+        mut request: NetworkRequest,
+    ) -> Result<NetworkResponse, NetworkError> {
+        let policy = match self.config.retry.as_ref() {
+            Some(policy) if retry::is_retryable_request(policy, &request) => policy,
+            _ => return self.fetch_coalesced(request).await,
+        };
+
+        let mut retries = 0;
+        loop {
+            let retained = redirect::replayable_copy(&request);
+            let result = self.fetch_coalesced(request).await;
+            if retries >= policy.max_retries || !retry::should_retry(policy, &result) {
+                return result;
+            }
+
+            let delay = policy.backoff(retries);
+            match &result {
+                Ok(response) => debug!(
+                    "Retrying {} after status {} in {:?}",
+                    retained.url, response.status, delay
+                ),
+                Err(e) => debug!(
+                    "Retrying {} after error ({}) in {:?}",
+                    retained.url, e, delay
+                ),
+            }
+            tokio::time::sleep(delay).await;
+            retries += 1;
+            request = retained;
+        }
+    }
+
+    /// Send an HTTP(S) request, sharing the response with identical in-flight requests
+    ///
+    /// Safe requests (GET/HEAD without a body) that match an in-flight request on
+    /// method, URL and headers wait for that request instead of hitting the origin
+    /// again. If the in-flight request is cancelled before completing, waiters
+    /// fall back to sending their own request.
+    async fn fetch_coalesced(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        let key = match CoalescingKey::for_request(&request) {
+            Some(key) => key,
+            None => return self.send_http(request).await,
+        };
+
+        match self.coalescer.join(key) {
+            Slot::Follower(receiver) => {
+                debug!("Coalescing request for {} with in-flight fetch", request.url);
+                match coalescing::wait_for_leader(receiver).await {
+                    Some(result) => coalescing::to_owned_result(&result),
+                    None => self.send_http(request).await,
+                }
+            }
+            Slot::Leader(guard) => {
+                let result = match self.send_http(request).await {
+                    Ok(response) => SharedResponse::buffer(response).await,
+                    // The abort belongs to this caller only; dropping the guard
+                    // lets followers send their own request
+                    Err(NetworkError::Aborted) => return Err(NetworkError::Aborted),
+                    Err(e) => Err(e),
+                };
+                let result = Arc::new(result);
+                guard.complete(result.clone());
+                coalescing::to_owned_result(&result)
+            }
+        }
+    }
+
+    /// Route an HTTP(S) request to the configured transport, or else the
+    /// selected protocol client
+    ///
+    /// Successful exchanges feed the network quality estimate: the time to a
+    /// response is an RTT sample, and buffered bodies a throughput sample.
+    /// `Alt-Svc` headers on HTTPS responses are recorded for
+    /// [`supports_http3`](Self::supports_http3).
+    async fn send_http(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        let url = request.url.clone();
+        if let Some(transport) = &self.config.transport {
+            let response = transport.send(request).await?;
+            self.record_alt_svc(&url, &response);
+            return Ok(response);
+        }
+
         let client = self.select_http_client(&request.url);
 
-        // Route to appropriate protocol handler
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record(
+            "protocol",
+            match client {
+                HttpProtocolClient::Http1(_) => "http/1.1",
+                HttpProtocolClient::Http2(_) => "h2",
+                HttpProtocolClient::Http3(_) => "h3",
+            },
+        );
+
+        let started = Instant::now();
         let response = match client {
-            HttpProtocolClient::Http1(client) => client.fetch(request).await?,
-            HttpProtocolClient::Http2(client) => client.fetch(request).await?,
-            HttpProtocolClient::Http3(client) => client.fetch(request).await?,
-        };
+            HttpProtocolClient::Http1(client) => client.fetch(request).await,
+            HttpProtocolClient::Http2(client) => client.fetch(request).await,
+            HttpProtocolClient::Http3(client) => client.fetch(request).await,
+        }?;
+
+        let elapsed = started.elapsed();
+        let mut quality = self.quality.lock().unwrap();
+        quality.record_rtt(elapsed);
+        if let network_types::ResponseBody::Bytes(ref bytes) = response.body {
+            quality.record_transfer(bytes.len(), elapsed);
+        }
+        drop(quality);
 
-        debug!("Request completed with status: {}", response.status);
+        self.record_alt_svc(&url, &response);
         Ok(response)
     }
+}
+
+/// HTTP protocol client selector
+enum HttpProtocolClient {
+    Http1(Arc<http1_protocol::Http1Client>),
+    Http2(Arc<http2_protocol::Http2Client>),
+    Http3(Arc<http3_protocol::Http3Client>),
+}
+
+#[async_trait]
+impl NetworkStack for NetworkStackImpl {
+    async fn fetch(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError> {
+        // The protocol is recorded once an HTTP client has been selected
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "fetch",
+            method = ?request.method,
+            url = %request.url,
+            protocol = tracing::field::Empty,
+        );
+
+        let fetch = async move {
+            // Aborting the request's token cancels whatever stage the fetch is in
+            let result = match request.signal.clone() {
+                Some(signal) => tokio::select! {
+                    biased;
+                    _ = signal.aborted() => Err(NetworkError::Aborted),
+                    result = self.perform_fetch(request) => result,
+                },
+                None => self.perform_fetch(request).await,
+            };
+
+            #[cfg(feature = "tracing")]
+            match &result {
+                Ok(response) => tracing::debug!(status = response.status, "response received"),
+                Err(error) => tracing::debug!(%error, "request failed"),
+            }
+            result
+        };
+
+        #[cfg(feature = "tracing")]
+        let fetch = tracing::Instrument::instrument(fetch, span);
+        fetch.await
+    }
 
     async fn stream_response(
         &self,
@@ -532,8 +992,13 @@ impl NetworkStack for NetworkStackImpl {
         }
         drop(conditions);
 
-        // Delegate to WebSocket client
-        self.websocket_client.connect(url, protocols).await
+        // WebSocket handshakes always include credentials
+        let headers = cookies::websocket_headers(&self.cookie_store, &url).await;
+        let connect_timeout = self.config.websocket.as_ref().map(|ws| ws.connect_timeout);
+        let config = websocket_protocol::WebSocketConfig::new().handshake_timeout(connect_timeout);
+        self.websocket_client
+            .connect_with_headers(url, protocols, headers, config)
+            .await
     }
 
     async fn create_rtc_peer_connection(
@@ -553,15 +1018,56 @@ impl NetworkStack for NetworkStackImpl {
         webrtc_peer::RtcPeerConnection::new(config).await
     }
 
+    fn preconnect(&self, url: &Url) {
+        if !matches!(url.scheme(), "http" | "https") {
+            debug!("Not preconnecting to {}: unsupported scheme", url);
+            return;
+        }
+
+        let client = self.select_http_client(url);
+        let conditions = self.conditions.clone();
+        let url = url.clone();
+        tokio::spawn(async move {
+            if conditions.read().await.offline {
+                return;
+            }
+
+            let result = match client {
+                HttpProtocolClient::Http1(client) => client.preconnect(&url).await,
+                HttpProtocolClient::Http2(client) => client.preconnect(url.as_str()).await,
+                HttpProtocolClient::Http3(_) => Ok(()),
+            };
+            if let Err(e) = result {
+                debug!("Preconnect to {} failed: {}", url, e);
+            }
+        });
+    }
+
+    fn prefetch_dns(&self, host: &str) -> tokio::task::AbortHandle {
+        let resolver = self.dns_resolver.clone();
+        let conditions = self.conditions.clone();
+        let host = host.to_string();
+        tokio::spawn(async move {
+            let literal = host.trim_start_matches('[').trim_end_matches(']');
+            if literal.parse::<std::net::IpAddr>().is_ok() || conditions.read().await.offline {
+                return;
+            }
+
+            if let Err(e) = resolver.resolve(host.clone()).await {
+                debug!("DNS prefetch for {} failed: {}", host, e);
+            }
+        })
+        .abort_handle()
+    }
+
     fn get_network_status(&self) -> NetworkStatus {
-        // In async context, we'd use try_read() or block_on()
-        // For this synchronous method, we'll return a default or cached value
+        let quality = self.quality.lock().unwrap();
         NetworkStatus {
             online: true,
             connection_type: ConnectionType::Unknown,
-            effective_type: EffectiveConnectionType::Type4G,
-            downlink_mbps: 0.0,
-            rtt_ms: 0,
+            effective_type: quality.effective_type(),
+            downlink_mbps: quality.downlink_kbps().map_or(0.0, |kbps| kbps / 1000.0),
+            rtt_ms: quality.rtt().map_or(0, |rtt| rtt.as_millis() as u32),
         }
     }
 
@@ -580,7 +1086,7 @@ impl NetworkStack for NetworkStackImpl {
         self.http_cache.clear().await
     }
 
-    fn cookie_store(&self) -> Arc<cookie_manager::CookieStore> {
+    fn cookie_store(&self) -> Arc<RwLock<cookie_manager::CookieStore>> {
         self.cookie_store.clone()
     }
 
@@ -643,8 +1149,20 @@ impl NetworkStack for NetworkStackImpl {
             let pin = certificate_pinning::Pin {
                 pin_type: certificate_pinning::PinType::Sha256,
                 hash: pin_hash,
+                expires_at: None,
             };
             pinner.add_pin(&host, pin);
         });
     }
+
+    async fn shutdown(&self, timeout: Duration) -> Result<(), NetworkError> {
+        info!("Shutting down NetworkStack");
+        self.shut_down.store(true, Ordering::SeqCst);
+
+        // In-flight HTTP/1.1 requests hold their connection until the response
+        // is read, so draining the pool waits for them
+        let drained = self.http1_client.shutdown(timeout).await;
+        self.http2_client.clear_connections().await;
+        drained
+    }
 }
@@ -0,0 +1,22 @@
+//! Pluggable transport for HTTP(S) requests
+//!
+//! By default the stack sends HTTP(S) requests through its HTTP/1.1, HTTP/2
+//! and HTTP/3 clients. A [`Transport`] set on the
+//! [`NetworkConfig`](crate::NetworkConfig) replaces them, e.g. to serve
+//! responses from memory in tests.
+
+use async_trait::async_trait;
+use network_errors::NetworkError;
+use network_types::{NetworkRequest, NetworkResponse};
+
+/// Sends one HTTP(S) request and returns its response
+///
+/// The stack calls the transport once per hop, after applying default
+/// headers, cookies and the cache, and before following redirects,
+/// retrying or filtering the response, so all of those still apply to
+/// responses produced by a custom transport.
+#[async_trait]
+pub trait Transport: Send + Sync {
+    /// Send `request` and return the response
+    async fn send(&self, request: NetworkRequest) -> Result<NetworkResponse, NetworkError>;
+}
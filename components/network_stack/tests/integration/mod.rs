@@ -1,3 +1,24 @@
 // Integration tests for network_stack
 
+mod origin;
+mod test_abort;
+mod test_content_decoding;
+mod test_cookies;
+mod test_cors;
+mod test_default_headers;
+mod test_dns_resolver;
+mod test_http_cache;
+mod test_integrity;
+mod test_interceptors;
+mod test_mock_transport;
+mod test_network_quality;
 mod test_phase2_integration;
+mod test_preconnect;
+mod test_redirects;
+mod test_referrer;
+mod test_request_coalescing;
+mod test_retry;
+mod test_server_timing;
+mod test_shutdown;
+mod test_tracing;
+mod test_url_schemes;
@@ -0,0 +1,166 @@
+//! Minimal HTTP/1.1 origin server for integration tests
+//!
+//! Records every request it receives and answers using a caller-supplied handler.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// A request as seen by the origin
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    /// Request method, e.g. `GET`
+    pub method: String,
+    /// Path and query of the request target
+    pub path: String,
+    /// Headers with lowercased names
+    pub headers: Vec<(String, String)>,
+    /// Request body
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// Look up a header value by (lowercase) name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Handler producing the raw HTTP response for a request
+pub type Handler = Arc<dyn Fn(&RecordedRequest) -> Vec<u8> + Send + Sync>;
+
+/// Running test origin
+pub struct Origin {
+    /// Address the origin listens on
+    pub addr: SocketAddr,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl Origin {
+    /// Start an origin that waits `delay` before answering each request
+    pub async fn start(delay: Duration, handler: Handler) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    break;
+                };
+                tokio::spawn(serve_connection(
+                    stream,
+                    recorded.clone(),
+                    handler.clone(),
+                    delay,
+                ));
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    /// Absolute URL for `path` on this origin
+    pub fn url(&self, path: &str) -> url::Url {
+        url::Url::parse(&format!("http://{}{}", self.addr, path)).unwrap()
+    }
+
+    /// Requests received so far
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Number of requests received so far
+    pub fn hits(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+}
+
+/// Build a raw HTTP/1.1 response
+pub fn response(
+    status: u16,
+    reason: &str,
+    headers: &[(&str, &str)],
+    body: impl AsRef<[u8]>,
+) -> Vec<u8> {
+    let body = body.as_ref();
+    let mut raw = format!("HTTP/1.1 {} {}\r\n", status, reason);
+    for (name, value) in headers {
+        raw.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    raw.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+
+    let mut raw = raw.into_bytes();
+    raw.extend_from_slice(body);
+    raw
+}
+
+/// Serve keep-alive requests on one connection until the client hangs up
+async fn serve_connection(
+    mut stream: TcpStream,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    handler: Handler,
+    delay: Duration,
+) {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+
+    loop {
+        // Read until the end of the request head, then the body
+        let head_end = loop {
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            }
+        };
+        let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+        let mut lines = head.split("\r\n");
+        let mut request_line = lines.next().unwrap_or_default().split(' ');
+        let method = request_line.next().unwrap_or_default().to_string();
+        let target = request_line.next().unwrap_or_default();
+        let path = match url::Url::parse(target) {
+            Ok(url) => url[url::Position::BeforePath..].to_string(),
+            Err(_) => target.to_string(),
+        };
+        let headers: Vec<(String, String)> = lines
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+            .collect();
+        let body_len = headers
+            .iter()
+            .find(|(name, _)| name == "content-length")
+            .and_then(|(_, value)| value.parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < head_end + body_len {
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+            }
+        }
+        let body = buffer[head_end..head_end + body_len].to_vec();
+        buffer.drain(..head_end + body_len);
+
+        let request = RecordedRequest {
+            method,
+            path,
+            headers,
+            body,
+        };
+        let reply = handler(&request);
+        requests.lock().unwrap().push(request);
+
+        tokio::time::sleep(delay).await;
+        if stream.write_all(&reply).await.is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Integration tests for aborting in-flight fetches
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    AbortController, AbortSignal, AbortToken, CacheMode, CredentialsMode, HttpMethod,
+    NetworkRequest, RedirectMode, ReferrerPolicy, RequestMode, RequestPriority,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use url::Url;
+
+fn request(url: Url, signal: Option<AbortToken>) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+async fn start_slow_origin(delay: Duration) -> Origin {
+    Origin::start(
+        delay,
+        Arc::new(|_| origin::response(200, "OK", &[], "slow")),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_abort_from_another_task_cancels_pending_fetch() {
+    let origin = start_slow_origin(Duration::from_secs(5)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let controller = AbortController::new();
+
+    let aborter = controller.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        aborter.abort("navigation cancelled");
+    });
+
+    let started = Instant::now();
+    let result = stack
+        .fetch(request(origin.url("/slow"), Some(controller.token())))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Aborted)));
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_already_aborted_request_is_not_sent() {
+    let origin = start_slow_origin(Duration::ZERO).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let controller = AbortController::new();
+    controller.abort("too late");
+
+    let result = stack
+        .fetch(request(origin.url("/never"), Some(controller.token())))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Aborted)));
+    assert_eq!(origin.hits(), 0);
+}
+
+#[tokio::test]
+async fn test_unaborted_token_does_not_affect_fetch() {
+    let origin = start_slow_origin(Duration::ZERO).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let controller = AbortController::new();
+
+    let response = stack
+        .fetch(request(origin.url("/ok"), Some(controller.token())))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+}
+
+#[tokio::test]
+async fn test_aborting_coalesced_leader_does_not_fail_followers() {
+    let origin = start_slow_origin(Duration::from_millis(300)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/shared");
+    let controller = AbortController::new();
+
+    let aborter = controller.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        aborter.abort("leader cancelled");
+    });
+
+    let (leader, follower) = tokio::join!(
+        stack.fetch(request(url.clone(), Some(controller.token()))),
+        stack.fetch(request(url, None)),
+    );
+
+    assert!(matches!(leader, Err(NetworkError::Aborted)));
+    assert_eq!(follower.unwrap().status, 200);
+}
+
+#[tokio::test]
+async fn test_timeout_signal_aborts_fetch_on_its_own() {
+    let origin = start_slow_origin(Duration::from_secs(5)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let signal = AbortSignal::timeout(Duration::from_millis(50));
+
+    let started = Instant::now();
+    let result = stack
+        .fetch(request(origin.url("/slow"), Some(signal.clone())))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Aborted)));
+    assert!(started.elapsed() < Duration::from_secs(2));
+    assert_eq!(signal.reason().as_deref(), Some("timeout"));
+}
@@ -0,0 +1,197 @@
+//! Integration tests for transparent Content-Encoding decoding
+
+use super::origin::{self, Origin};
+use content_encoding::{ContentEncoder, Encoding};
+use http::{HeaderMap, HeaderValue};
+use network_stack::{ContentEncodingConfig, NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, NetworkResponse, RedirectMode,
+    ReferrerPolicy, RequestBody, RequestMode, RequestPriority, ResponseBody,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+const BODY: &str = "The quick brown fox jumps over the lazy dog. ";
+
+fn request(url: Url) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn encode(data: &[u8], encoding: Encoding) -> Vec<u8> {
+    ContentEncoder::new().encode(data, encoding).unwrap()
+}
+
+/// Origin serving `body` with the given `Content-Encoding`
+async fn start_encoding_origin(content_encoding: &'static str, body: Vec<u8>) -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(move |_| {
+            origin::response(
+                200,
+                "OK",
+                &[
+                    ("Content-Type", "text/plain"),
+                    ("Content-Encoding", content_encoding),
+                ],
+                &body,
+            )
+        }),
+    )
+    .await
+}
+
+fn body_bytes(response: NetworkResponse) -> Vec<u8> {
+    match response.body {
+        ResponseBody::Bytes(bytes) => bytes,
+        other => panic!("expected buffered body, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_gzip_response_is_decoded() {
+    let plain = BODY.repeat(20);
+    let encoded = encode(plain.as_bytes(), Encoding::Gzip);
+    let encoded_len = encoded.len() as u64;
+    let origin = start_encoding_origin("gzip", encoded).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack.fetch(request(origin.url("/gzip"))).await.unwrap();
+
+    assert!(response.headers.get("content-encoding").is_none());
+    assert_eq!(response.timing.encoded_body_size, encoded_len);
+    assert_eq!(response.timing.decoded_body_size, plain.len() as u64);
+    assert_eq!(body_bytes(response), plain.as_bytes());
+
+    let accept_encoding = origin.requests()[0]
+        .header("accept-encoding")
+        .unwrap()
+        .to_string();
+    assert!(accept_encoding.contains("gzip"));
+}
+
+#[tokio::test]
+async fn test_deflate_and_brotli_responses_are_decoded() {
+    for (name, encoding) in [("deflate", Encoding::Deflate), ("br", Encoding::Brotli)] {
+        let origin = start_encoding_origin(name, encode(BODY.as_bytes(), encoding)).await;
+        let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+        let response = stack.fetch(request(origin.url("/"))).await.unwrap();
+
+        assert_eq!(body_bytes(response), BODY.as_bytes(), "{}", name);
+    }
+}
+
+#[tokio::test]
+async fn test_zstd_response_is_decoded() {
+    let origin = start_encoding_origin("zstd", encode(BODY.as_bytes(), Encoding::Zstd)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    assert!(response.headers.get("content-encoding").is_none());
+    assert_eq!(body_bytes(response), BODY.as_bytes());
+}
+
+#[tokio::test]
+async fn test_stacked_encodings_are_decoded_in_reverse_order() {
+    let encoded = encode(&encode(BODY.as_bytes(), Encoding::Gzip), Encoding::Brotli);
+    let origin = start_encoding_origin("gzip, br", encoded).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    assert_eq!(body_bytes(response), BODY.as_bytes());
+}
+
+#[tokio::test]
+async fn test_unknown_encoding_is_passed_through() {
+    let origin = start_encoding_origin("compress", b"opaque".to_vec()).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    assert_eq!(response.headers.get("content-encoding").unwrap(), "compress");
+    assert_eq!(body_bytes(response), b"opaque");
+}
+
+#[tokio::test]
+async fn test_caller_supplied_accept_encoding_receives_raw_bytes() {
+    let encoded = encode(BODY.as_bytes(), Encoding::Gzip);
+    let origin = start_encoding_origin("gzip", encoded.clone()).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let mut raw = request(origin.url("/"));
+    raw.headers.insert(
+        http::header::ACCEPT_ENCODING,
+        HeaderValue::from_static("gzip"),
+    );
+    let response = stack.fetch(raw).await.unwrap();
+
+    assert_eq!(response.headers.get("content-encoding").unwrap(), "gzip");
+    assert_eq!(body_bytes(response), encoded);
+}
+
+#[tokio::test]
+async fn test_decoding_can_be_disabled_in_config() {
+    let encoded = encode(BODY.as_bytes(), Encoding::Gzip);
+    let origin = start_encoding_origin("gzip", encoded.clone()).await;
+    let config = NetworkConfig {
+        content_encoding: Some(ContentEncodingConfig { enabled: false }),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    let response = stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    assert_eq!(body_bytes(response), encoded);
+    assert!(origin.requests()[0].header("accept-encoding").is_none());
+}
+
+#[tokio::test]
+async fn test_gzip_request_body_arrives_compressed() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[], "stored")),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let original = BODY.repeat(20);
+
+    let mut upload = request(origin.url("/upload"));
+    upload.method = HttpMethod::Post;
+    upload.body = Some(RequestBody::Text(original.clone()));
+    ContentEncoder::new()
+        .encode_request_body(&mut upload, Encoding::Gzip)
+        .unwrap();
+    let response = stack.fetch(upload).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let received = &origin.requests()[0];
+    assert_eq!(received.header("content-encoding"), Some("gzip"));
+    assert_eq!(
+        received.header("content-length"),
+        Some(received.body.len().to_string().as_str())
+    );
+    assert!(received.body.len() < original.len());
+    let decoded = ContentEncoder::new()
+        .decode(&received.body, Encoding::Gzip)
+        .unwrap();
+    assert_eq!(decoded, original.as_bytes());
+}
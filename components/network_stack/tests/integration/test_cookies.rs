@@ -0,0 +1,191 @@
+//! Integration tests for cookies attached to and stored from fetches
+
+use super::origin::{self, Origin, RecordedRequest};
+use cookie_manager::CookieType;
+use http::HeaderMap;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use url::Url;
+
+fn request(url: Url, credentials: CredentialsMode) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|req: &RecordedRequest| match req.path.as_str() {
+            "/login" => origin::response(
+                200,
+                "OK",
+                &[
+                    ("Set-Cookie", "session=abc123; Path=/; HttpOnly"),
+                    ("Set-Cookie", "theme=dark"),
+                ],
+                "welcome",
+            ),
+            "/redirect" => origin::response(
+                302,
+                "Found",
+                &[("Location", "/page"), ("Set-Cookie", "hop=1; Path=/")],
+                "",
+            ),
+            _ => origin::response(200, "OK", &[], "ok"),
+        }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_set_cookie_is_stored_and_sent_back() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack
+        .fetch(request(origin.url("/login"), CredentialsMode::Include))
+        .await
+        .unwrap();
+    stack
+        .fetch(request(origin.url("/page"), CredentialsMode::Include))
+        .await
+        .unwrap();
+
+    let stored = stack
+        .cookie_store()
+        .read()
+        .await
+        .get_cookies(&origin.url("/page"));
+    let mut names: Vec<_> = stored.iter().map(|cookie| cookie.name()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["session", "theme"]);
+
+    let requests = origin.requests();
+    assert_eq!(requests[0].header("cookie"), None);
+    let cookie = requests[1].header("cookie").unwrap();
+    assert!(cookie.contains("session=abc123"));
+    assert!(cookie.contains("theme=dark"));
+}
+
+#[tokio::test]
+async fn test_stored_cookie_is_attached_to_request() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    stack
+        .cookie_store()
+        .write()
+        .await
+        .add_cookie(CookieType::new("pref", "1"), &origin.url("/"))
+        .unwrap();
+
+    stack
+        .fetch(request(origin.url("/page"), CredentialsMode::SameOrigin))
+        .await
+        .unwrap();
+
+    assert_eq!(origin.requests()[0].header("cookie"), Some("pref=1"));
+}
+
+#[tokio::test]
+async fn test_credentials_mode_controls_cookies() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    stack
+        .cookie_store()
+        .write()
+        .await
+        .add_cookie(CookieType::new("pref", "1"), &origin.url("/"))
+        .unwrap();
+
+    // Omit neither sends nor stores cookies
+    stack
+        .fetch(request(origin.url("/login"), CredentialsMode::Omit))
+        .await
+        .unwrap();
+    // SameOrigin from another origin's document doesn't send them either
+    let mut cross_origin = request(origin.url("/page"), CredentialsMode::SameOrigin);
+    cross_origin.referrer = Some("http://elsewhere.test/page".to_string());
+    stack.fetch(cross_origin).await.unwrap();
+
+    let requests = origin.requests();
+    assert_eq!(requests[0].header("cookie"), None);
+    assert_eq!(requests[1].header("cookie"), None);
+    let stored = stack
+        .cookie_store()
+        .read()
+        .await
+        .get_cookies(&origin.url("/"));
+    assert_eq!(stored.len(), 1);
+}
+
+#[tokio::test]
+async fn test_cookie_set_on_redirect_is_sent_to_next_hop() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack
+        .fetch(request(origin.url("/redirect"), CredentialsMode::Include))
+        .await
+        .unwrap();
+
+    let requests = origin.requests();
+    assert_eq!(requests[0].header("cookie"), None);
+    assert_eq!(requests[1].header("cookie"), Some("hop=1"));
+}
+
+#[tokio::test]
+async fn test_websocket_handshake_sends_cookies() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let handshake = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut head = Vec::new();
+        let mut buf = [0u8; 1024];
+        while !head.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buf).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            head.extend_from_slice(&buf[..read]);
+        }
+        // Closing without an answer fails the handshake, which is fine here
+        String::from_utf8_lossy(&head).to_lowercase()
+    });
+
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let page = Url::parse(&format!("http://{}/", addr)).unwrap();
+    stack
+        .cookie_store()
+        .write()
+        .await
+        .add_cookie(CookieType::new("session", "abc123"), &page)
+        .unwrap();
+
+    let socket = Url::parse(&format!("ws://{}/socket", addr)).unwrap();
+    let _ = stack.connect_websocket(socket, vec![]).await;
+
+    let head = handshake.await.unwrap();
+    assert!(head.contains("\r\ncookie: session=abc123\r\n"));
+}
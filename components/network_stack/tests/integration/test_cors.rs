@@ -0,0 +1,117 @@
+//! Integration tests for CORS response header filtering
+
+use super::origin::{self, Origin};
+use http::{HeaderMap, HeaderValue};
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority, ResponseType,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+fn request(url: Url, mode: RequestMode, origin: Option<&'static str>) -> NetworkRequest {
+    let mut headers = HeaderMap::new();
+    if let Some(origin) = origin {
+        headers.insert(http::header::ORIGIN, HeaderValue::from_static(origin));
+    }
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers,
+        body: None,
+        mode,
+        credentials: CredentialsMode::Omit,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| {
+            origin::response(
+                200,
+                "OK",
+                &[
+                    ("Content-Type", "application/json"),
+                    ("X-Exposed", "visible"),
+                    ("X-Secret", "hidden"),
+                    ("Access-Control-Allow-Origin", "*"),
+                    ("Access-Control-Expose-Headers", "X-Exposed"),
+                ],
+                "{}",
+            )
+        }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_cross_origin_cors_response_hides_non_exposed_headers() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack
+        .fetch(request(
+            origin.url("/data"),
+            RequestMode::Cors,
+            Some("https://app.example"),
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.type_, ResponseType::Cors);
+    assert_eq!(response.headers["x-exposed"], "visible");
+    assert_eq!(response.headers["content-type"], "application/json");
+    assert!(response.headers.get("x-secret").is_none());
+    assert!(response
+        .headers
+        .get("access-control-allow-origin")
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_same_origin_response_keeps_all_headers() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let own_origin = format!("http://{}", origin.addr);
+
+    let mut same_origin = request(origin.url("/data"), RequestMode::Cors, None);
+    same_origin.headers.insert(
+        http::header::ORIGIN,
+        HeaderValue::from_str(&own_origin).unwrap(),
+    );
+    let response = stack.fetch(same_origin).await.unwrap();
+
+    assert_eq!(response.type_, ResponseType::Basic);
+    assert_eq!(response.headers["x-secret"], "hidden");
+}
+
+#[tokio::test]
+async fn test_requests_without_origin_are_not_filtered() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    for (mode, origin_header) in [
+        (RequestMode::Cors, None),
+        (RequestMode::Navigate, Some("https://app.example")),
+    ] {
+        let response = stack
+            .fetch(request(origin.url("/data"), mode, origin_header))
+            .await
+            .unwrap();
+
+        assert_eq!(response.type_, ResponseType::Basic);
+        assert_eq!(response.headers["x-secret"], "hidden");
+    }
+}
@@ -0,0 +1,98 @@
+//! Integration tests for configured default request headers
+
+use super::origin::{self, Origin};
+use http::{HeaderMap, HeaderValue};
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl, DEFAULT_USER_AGENT};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+fn request(url: Url) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[], "ok")),
+    )
+    .await
+}
+
+fn config_with_defaults() -> NetworkConfig {
+    let mut default_headers = HeaderMap::new();
+    default_headers.insert(
+        http::header::USER_AGENT,
+        HeaderValue::from_static("TestBrowser/1.0"),
+    );
+    default_headers.insert(
+        http::header::ACCEPT_LANGUAGE,
+        HeaderValue::from_static("en-GB"),
+    );
+    NetworkConfig {
+        default_headers,
+        ..NetworkConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn test_configured_default_headers_are_sent() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(config_with_defaults()).unwrap();
+
+    stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    let sent = &origin.requests()[0];
+    assert_eq!(sent.header("user-agent"), Some("TestBrowser/1.0"));
+    assert_eq!(sent.header("accept-language"), Some("en-GB"));
+}
+
+#[tokio::test]
+async fn test_request_header_overrides_default() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(config_with_defaults()).unwrap();
+
+    let mut overridden = request(origin.url("/"));
+    overridden.headers.insert(
+        http::header::USER_AGENT,
+        HeaderValue::from_static("Override/2.0"),
+    );
+    stack.fetch(overridden).await.unwrap();
+
+    let sent = &origin.requests()[0];
+    assert_eq!(sent.header("user-agent"), Some("Override/2.0"));
+    assert_eq!(sent.header("accept-language"), Some("en-GB"));
+}
+
+#[tokio::test]
+async fn test_user_agent_defaults_when_not_configured() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    assert_eq!(
+        origin.requests()[0].header("user-agent"),
+        Some(DEFAULT_USER_AGENT)
+    );
+}
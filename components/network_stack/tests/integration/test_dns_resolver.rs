@@ -0,0 +1,177 @@
+//! Integration tests for resolving hosts through an injected DNS resolver
+
+use super::origin::{self, Origin};
+use async_trait::async_trait;
+use dns_resolver::DnsResolver;
+use http::HeaderMap;
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority,
+};
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+/// Resolver answering every lookup with a fixed address
+struct FixedResolver {
+    address: IpAddr,
+    lookups: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl DnsResolver for FixedResolver {
+    async fn resolve(&self, hostname: String) -> Result<Vec<IpAddr>, NetworkError> {
+        self.lookups.lock().unwrap().push(hostname);
+        Ok(vec![self.address])
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> Result<Vec<IpAddr>, NetworkError> {
+        self.resolve(hostname).await
+    }
+}
+
+/// Resolver that takes a while to answer, or fails when it has no address
+struct SlowResolver {
+    address: Option<IpAddr>,
+    delay: Duration,
+}
+
+#[async_trait]
+impl DnsResolver for SlowResolver {
+    async fn resolve(&self, hostname: String) -> Result<Vec<IpAddr>, NetworkError> {
+        tokio::time::sleep(self.delay).await;
+        self.address
+            .map(|address| vec![address])
+            .ok_or_else(|| NetworkError::DnsError(format!("no answer for {}", hostname)))
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> Result<Vec<IpAddr>, NetworkError> {
+        self.resolve(hostname).await
+    }
+}
+
+fn request(url: Url) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+#[tokio::test]
+async fn test_stack_connects_to_address_from_injected_resolver() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[], "resolved")),
+    )
+    .await;
+    let resolver = Arc::new(FixedResolver {
+        address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        lookups: Mutex::new(Vec::new()),
+    });
+    let config = NetworkConfig {
+        dns_resolver: Some(resolver.clone()),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    let url = Url::parse(&format!("http://app.invalid:{}/", origin.addr.port())).unwrap();
+    let response = stack.fetch(request(url)).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(*resolver.lookups.lock().unwrap(), vec!["app.invalid"]);
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_prefetch_dns_populates_cache() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[], "resolved")),
+    )
+    .await;
+    let resolver = Arc::new(FixedResolver {
+        address: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        lookups: Mutex::new(Vec::new()),
+    });
+    let config = NetworkConfig {
+        dns_resolver: Some(resolver.clone()),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    stack.prefetch_dns("app.invalid");
+    let mut cached = None;
+    for _ in 0..100 {
+        cached = stack.dns_cache().lock().await.get("app.invalid");
+        if cached.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(cached, Some(vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]));
+
+    // The request is served from the cache without another lookup
+    let url = Url::parse(&format!("http://app.invalid:{}/", origin.addr.port())).unwrap();
+    let response = stack.fetch(request(url)).await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(*resolver.lookups.lock().unwrap(), vec!["app.invalid"]);
+}
+
+#[tokio::test]
+async fn test_prefetch_dns_can_be_cancelled() {
+    let config = NetworkConfig {
+        dns_resolver: Some(Arc::new(SlowResolver {
+            address: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            delay: Duration::from_millis(100),
+        })),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    let prefetch = stack.prefetch_dns("app.invalid");
+    prefetch.abort();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    assert!(stack.dns_cache().lock().await.get("app.invalid").is_none());
+}
+
+#[tokio::test]
+async fn test_prefetch_dns_failure_is_quiet() {
+    let config = NetworkConfig {
+        dns_resolver: Some(Arc::new(SlowResolver {
+            address: None,
+            delay: Duration::ZERO,
+        })),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    stack.prefetch_dns("missing.invalid");
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert!(stack.dns_cache().lock().await.get("missing.invalid").is_none());
+}
@@ -0,0 +1,201 @@
+//! Integration tests for the HTTP cache in the fetch path
+//!
+//! Responses record whether they were served by the network, straight from the
+//! cache, or from the cache after a `304 Not Modified` revalidation.
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority, ResponseBody, ResponseSource,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Start an origin answering `body` with the given caching headers
+///
+/// Requests carrying `If-None-Match: "v1"` receive `304 Not Modified`.
+async fn start_cacheable_origin(headers: &'static [(&'static str, &'static str)]) -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(move |request| {
+            if request.header("if-none-match") == Some("\"v1\"") {
+                origin::response(304, "Not Modified", &[("ETag", "\"v1\"")], "")
+            } else {
+                origin::response(200, "OK", headers, "cached body")
+            }
+        }),
+    )
+    .await
+}
+
+fn request(url: &Url, cache: CacheMode) -> NetworkRequest {
+    NetworkRequest {
+        url: url.clone(),
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn body_bytes(body: ResponseBody) -> Vec<u8> {
+    match body {
+        ResponseBody::Bytes(bytes) => bytes,
+        ResponseBody::Empty => Vec::new(),
+        ResponseBody::Stream(_) => panic!("Expected a buffered body"),
+    }
+}
+
+#[tokio::test]
+async fn test_fresh_response_is_served_from_cache() {
+    let origin = start_cacheable_origin(&[("Cache-Control", "max-age=60")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/fresh");
+
+    let miss = stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+    assert_eq!(miss.source, ResponseSource::Network);
+
+    let hit = stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+    assert_eq!(hit.source, ResponseSource::Cache);
+    assert_eq!(hit.status, 200);
+    assert_eq!(body_bytes(hit.body), b"cached body");
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_stale_response_is_revalidated() {
+    let origin = start_cacheable_origin(&[("Cache-Control", "no-cache"), ("ETag", "\"v1\"")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/revalidate");
+
+    let miss = stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+    assert_eq!(miss.source, ResponseSource::Network);
+
+    let revalidated = stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+    assert_eq!(revalidated.source, ResponseSource::RevalidatedNotModified);
+    assert_eq!(revalidated.status, 200);
+    assert_eq!(body_bytes(revalidated.body), b"cached body");
+
+    let requests = origin.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].header("if-none-match"), None);
+    assert_eq!(requests[1].header("if-none-match"), Some("\"v1\""));
+}
+
+#[tokio::test]
+async fn test_uncacheable_response_always_hits_network() {
+    let origin = start_cacheable_origin(&[("Content-Type", "text/plain")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/plain");
+
+    for _ in 0..2 {
+        let response = stack
+            .fetch(request(&url, CacheMode::Default))
+            .await
+            .unwrap();
+        assert_eq!(response.source, ResponseSource::Network);
+    }
+    assert_eq!(origin.hits(), 2);
+}
+
+#[tokio::test]
+async fn test_cache_modes() {
+    let origin = start_cacheable_origin(&[("Cache-Control", "max-age=60")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/modes");
+
+    // only-if-cached fails before anything is stored
+    assert!(stack
+        .fetch(request(&url, CacheMode::OnlyIfCached))
+        .await
+        .is_err());
+
+    // no-store neither reads nor writes the cache
+    let response = stack
+        .fetch(request(&url, CacheMode::NoStore))
+        .await
+        .unwrap();
+    assert_eq!(response.source, ResponseSource::Network);
+    assert!(stack
+        .fetch(request(&url, CacheMode::OnlyIfCached))
+        .await
+        .is_err());
+
+    // reload bypasses the lookup but stores the result
+    let response = stack.fetch(request(&url, CacheMode::Reload)).await.unwrap();
+    assert_eq!(response.source, ResponseSource::Network);
+    let response = stack
+        .fetch(request(&url, CacheMode::OnlyIfCached))
+        .await
+        .unwrap();
+    assert_eq!(response.source, ResponseSource::Cache);
+    assert_eq!(origin.hits(), 2);
+}
+
+#[tokio::test]
+async fn test_range_request_is_served_from_cached_body() {
+    let origin =
+        start_cacheable_origin(&[("Cache-Control", "max-age=60"), ("ETag", "\"v1\"")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/range");
+    stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+
+    let mut range = request(&url, CacheMode::Default);
+    range.headers.insert("range", "bytes=7-10".parse().unwrap());
+    range.headers.insert("if-range", "\"v1\"".parse().unwrap());
+    let partial = stack.fetch(range).await.unwrap();
+
+    assert_eq!(partial.status, 206);
+    assert_eq!(partial.source, ResponseSource::Cache);
+    assert_eq!(partial.headers["content-range"], "bytes 7-10/11");
+    assert_eq!(body_bytes(partial.body), b"body");
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_range_request_with_outdated_if_range_gets_full_body() {
+    let origin =
+        start_cacheable_origin(&[("Cache-Control", "max-age=60"), ("ETag", "\"v1\"")]).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/range-changed");
+    stack
+        .fetch(request(&url, CacheMode::Default))
+        .await
+        .unwrap();
+
+    let mut range = request(&url, CacheMode::Default);
+    range.headers.insert("range", "bytes=7-".parse().unwrap());
+    range.headers.insert("if-range", "\"v0\"".parse().unwrap());
+    let full = stack.fetch(range).await.unwrap();
+
+    assert_eq!(full.status, 200);
+    assert_eq!(body_bytes(full.body), b"cached body");
+}
@@ -0,0 +1,99 @@
+//! Integration tests for Subresource Integrity verification
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority, ResponseBody,
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Body from the SRI specification examples
+const BODY: &str = "alert('Hello, world.');";
+const SHA256: &str = "sha256-qznLcsROx4GACP2dm0UCKCzCG+HiZ1guq6ZZDob/Tng=";
+const SHA384: &str = "sha384-H8BRh8j48O9oYatfu5AZzq6A9RINhZO5H16dQZngK7T62em8MUt1FLm52t+eX6xO";
+const SHA512: &str = "sha512-Q2bFTOhEALkN8hOms2FKTDLy7eugP2zFZ1T8LCvX42Fp3WoNr3bjZSAHeOsHrbV1Fu9/A0EzCinRE7Af1ofPrw==";
+/// sha384 of `other`
+const OTHER_SHA384: &str =
+    "sha384-cA2k5ShGbDszTFJ5DCwUz0SM78jrtzK+xEyMQFDiO7NqJbEU9UCtZBjvgxiEUqsK";
+
+async fn fetch_with_integrity(integrity: &str) -> Result<Vec<u8>, NetworkError> {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[("Content-Type", "text/javascript")], BODY)),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let request = NetworkRequest {
+        url: origin.url("/script.js"),
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: Some(integrity.to_string()),
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    };
+
+    let response = stack.fetch(request).await?;
+    match response.body {
+        ResponseBody::Bytes(bytes) => Ok(bytes),
+        other => panic!("expected buffered body, got {:?}", other),
+    }
+}
+
+fn is_integrity_mismatch(result: &Result<Vec<u8>, NetworkError>) -> bool {
+    matches!(result, Err(NetworkError::Other(msg)) if msg == "integrity mismatch")
+}
+
+#[tokio::test]
+async fn test_matching_hash_passes() {
+    for integrity in [SHA256, SHA384, SHA512] {
+        let body = fetch_with_integrity(integrity).await.unwrap();
+        assert_eq!(body, BODY.as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn test_mismatching_hash_fails() {
+    let result = fetch_with_integrity(OTHER_SHA384).await;
+    assert!(is_integrity_mismatch(&result), "got {:?}", result);
+}
+
+#[tokio::test]
+async fn test_any_matching_hash_of_strongest_algorithm_passes() {
+    let integrity = format!("{} {}", OTHER_SHA384, SHA384);
+    assert!(fetch_with_integrity(&integrity).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_strongest_algorithm_is_enforced() {
+    // The valid sha256 is ignored because a sha384 hash is also declared
+    let integrity = format!("{} {}", SHA256, OTHER_SHA384);
+    let result = fetch_with_integrity(&integrity).await;
+    assert!(is_integrity_mismatch(&result), "got {:?}", result);
+}
+
+#[tokio::test]
+async fn test_options_are_ignored() {
+    let integrity = format!("{}?foo=bar", SHA384);
+    assert!(fetch_with_integrity(&integrity).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_unknown_algorithms_impose_no_requirement() {
+    assert!(fetch_with_integrity("md5-AAAAAAAAAAAAAAAAAAAAAA==")
+        .await
+        .is_ok());
+}
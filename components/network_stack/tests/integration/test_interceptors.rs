@@ -0,0 +1,137 @@
+//! Integration tests for request/response interceptors
+
+use super::origin::{self, Origin};
+use async_trait::async_trait;
+use http::{HeaderMap, HeaderValue};
+use network_stack::{Interceptor, NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, NetworkResponse, RedirectMode,
+    ReferrerPolicy, RequestMode, RequestPriority,
+};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::Url;
+
+/// Adds a bearer token to requests and records the `X-Request-Id` of responses
+#[derive(Default)]
+struct AuthInterceptor {
+    request_ids: Mutex<Vec<String>>,
+}
+
+#[async_trait]
+impl Interceptor for AuthInterceptor {
+    async fn on_request(&self, request: &mut NetworkRequest) {
+        request.headers.insert(
+            http::header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret-token"),
+        );
+    }
+
+    async fn on_response(&self, _request: &NetworkRequest, response: &mut NetworkResponse) {
+        if let Some(id) = response.headers.get("x-request-id") {
+            self.request_ids
+                .lock()
+                .unwrap()
+                .push(id.to_str().unwrap().to_string());
+        }
+    }
+}
+
+/// Appends its name to a shared log from both hooks
+struct RecordingInterceptor {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Interceptor for RecordingInterceptor {
+    async fn on_request(&self, _request: &mut NetworkRequest) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("request:{}", self.name));
+    }
+
+    async fn on_response(&self, _request: &NetworkRequest, _response: &mut NetworkResponse) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("response:{}", self.name));
+    }
+}
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|_| origin::response(200, "OK", &[("X-Request-Id", "req-42")], "ok")),
+    )
+    .await
+}
+
+fn request(url: &Url) -> NetworkRequest {
+    NetworkRequest {
+        url: url.clone(),
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+#[tokio::test]
+async fn test_interceptor_injects_request_header_and_reads_response_header() {
+    let origin = start_origin().await;
+    let auth = Arc::new(AuthInterceptor::default());
+    let mut config = NetworkConfig::default();
+    config.add_interceptor(auth.clone());
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    let response = stack.fetch(request(&origin.url("/api"))).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    let requests = origin.requests();
+    assert_eq!(
+        requests[0].header("authorization"),
+        Some("Bearer secret-token")
+    );
+    assert_eq!(
+        *auth.request_ids.lock().unwrap(),
+        vec!["req-42".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_interceptors_run_in_order_and_unwind_in_reverse() {
+    let origin = start_origin().await;
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut config = NetworkConfig::default();
+    for name in ["outer", "inner"] {
+        config.add_interceptor(Arc::new(RecordingInterceptor {
+            name,
+            log: log.clone(),
+        }));
+    }
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    stack.fetch(request(&origin.url("/"))).await.unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "request:outer",
+            "request:inner",
+            "response:inner",
+            "response:outer"
+        ]
+    );
+}
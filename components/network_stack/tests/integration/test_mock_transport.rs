@@ -0,0 +1,151 @@
+//! Integration tests for fetching through `MockTransport`, without sockets
+
+#![cfg(feature = "test-util")]
+
+use http::HeaderValue;
+use network_errors::NetworkError;
+use network_stack::{MockTransport, NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    HttpMethod, NetworkRequest, NetworkResponse, RedirectMode, ResponseBody, ResponseType,
+};
+use std::sync::Arc;
+use url::Url;
+
+fn url(path: &str) -> Url {
+    Url::parse("https://mock.example")
+        .unwrap()
+        .join(path)
+        .unwrap()
+}
+
+fn response(status: u16, headers: &[(&'static str, &'static str)], body: &str) -> NetworkResponse {
+    let mut response = NetworkResponse::network_error(url("/"));
+    response.status = status;
+    response.type_ = ResponseType::Basic;
+    for (name, value) in headers {
+        response
+            .headers
+            .append(*name, HeaderValue::from_static(value));
+    }
+    if !body.is_empty() {
+        response.body = ResponseBody::Bytes(body.as_bytes().to_vec());
+    }
+    response
+}
+
+fn stack_with(transport: &Arc<MockTransport>) -> NetworkStackImpl {
+    NetworkStackImpl::new(NetworkConfig {
+        transport: Some(transport.clone()),
+        ..NetworkConfig::default()
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_redirect_flow_without_sockets() {
+    let transport = Arc::new(MockTransport::new());
+    transport
+        .on(
+            HttpMethod::Get,
+            url("/start"),
+            response(302, &[("location", "/final")], ""),
+        )
+        .on(
+            HttpMethod::Get,
+            url("/final"),
+            response(200, &[("content-type", "text/plain")], "done"),
+        );
+    let stack = stack_with(&transport);
+
+    let response = stack
+        .fetch(NetworkRequest::builder(url("/start")).build())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert!(response.redirected);
+    assert_eq!(response.url, url("/final"));
+    assert!(matches!(&response.body, ResponseBody::Bytes(body) if body == b"done"));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].url, url("/start"));
+    assert_eq!(requests[1].url, url("/final"));
+    assert!(requests
+        .iter()
+        .all(|request| request.method == HttpMethod::Get));
+}
+
+#[tokio::test]
+async fn test_requests_are_recorded_as_sent() {
+    let transport = Arc::new(MockTransport::new());
+    transport.on(HttpMethod::Post, url("/submit"), response(204, &[], ""));
+    let stack = stack_with(&transport);
+
+    let response = stack
+        .fetch(
+            NetworkRequest::builder(url("/submit"))
+                .method(HttpMethod::Post)
+                .header("x-request-id", HeaderValue::from_static("42"))
+                .redirect(RedirectMode::Error)
+                .build(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 204);
+    let sent = &transport.requests()[0];
+    assert_eq!(sent.method, HttpMethod::Post);
+    assert_eq!(sent.headers["x-request-id"], "42");
+    // Headers added by the stack are visible too
+    assert!(sent.headers.contains_key(http::header::USER_AGENT));
+}
+
+#[tokio::test]
+async fn test_unregistered_request_fails() {
+    let transport = Arc::new(MockTransport::new());
+    transport.on(HttpMethod::Get, url("/known"), response(200, &[], "ok"));
+    let stack = stack_with(&transport);
+
+    let result = stack
+        .fetch(
+            NetworkRequest::builder(url("/known"))
+                .method(HttpMethod::Delete)
+                .build(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+    assert_eq!(transport.request_count(), 1);
+}
+
+#[tokio::test]
+async fn test_alt_svc_header_advertises_http3() {
+    let transport = Arc::new(MockTransport::new());
+    transport
+        .on(
+            HttpMethod::Get,
+            url("/page"),
+            response(200, &[("alt-svc", r#"h3=":443"; ma=3600"#)], "ok"),
+        )
+        .on(
+            HttpMethod::Get,
+            url("/moved"),
+            response(200, &[("alt-svc", "clear")], "ok"),
+        );
+    let stack = stack_with(&transport);
+    assert!(!stack.supports_http3(&url("/")));
+
+    stack
+        .fetch(NetworkRequest::builder(url("/page")).build())
+        .await
+        .unwrap();
+    assert!(stack.supports_http3(&url("/")));
+
+    // `clear` withdraws the advertisement for the whole origin
+    stack
+        .fetch(NetworkRequest::builder(url("/moved")).build())
+        .await
+        .unwrap();
+    assert!(!stack.supports_http3(&url("/")));
+}
@@ -0,0 +1,52 @@
+//! Integration tests for network status estimation from observed fetches
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_stack::{EffectiveConnectionType, NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+fn request(url: Url) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+#[tokio::test]
+async fn test_network_status_reflects_observed_rtt() {
+    let origin = Origin::start(
+        Duration::from_millis(300),
+        Arc::new(|_| origin::response(200, "OK", &[], "slow")),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    assert_eq!(
+        stack.get_network_status().effective_type,
+        EffectiveConnectionType::Type4G
+    );
+
+    stack.fetch(request(origin.url("/"))).await.unwrap();
+
+    let status = stack.get_network_status();
+    assert!(status.rtt_ms >= 300, "rtt {}ms", status.rtt_ms);
+    assert_eq!(status.effective_type, EffectiveConnectionType::Type3G);
+}
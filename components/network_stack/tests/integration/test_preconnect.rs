@@ -0,0 +1,77 @@
+//! Integration tests for warming up connections with preconnect
+
+use super::origin::{self, Origin, RecordedRequest};
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{CacheMode, NetworkRequest};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "ok")),
+    )
+    .await
+}
+
+/// Wait for the background preconnect to park its connection
+async fn wait_for_idle_connection(stack: &NetworkStackImpl) {
+    for _ in 0..100 {
+        if stack.pool_metrics().await.idle_total > 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("preconnect didn't open a connection");
+}
+
+#[tokio::test]
+async fn test_fetch_reuses_preconnected_connection() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack.preconnect(&origin.url("/"));
+    wait_for_idle_connection(&stack).await;
+    assert_eq!(origin.hits(), 0);
+
+    let request = NetworkRequest::builder(origin.url("/page"))
+        .cache(CacheMode::NoStore)
+        .build();
+    let response = stack.fetch(request).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let metrics = stack.pool_metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.reuse_count, 1);
+}
+
+#[tokio::test]
+async fn test_repeated_preconnect_opens_one_connection() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack.preconnect(&origin.url("/"));
+    wait_for_idle_connection(&stack).await;
+    stack.preconnect(&origin.url("/other"));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let metrics = stack.pool_metrics().await;
+    assert_eq!(metrics.created_count, 1);
+    assert_eq!(metrics.idle_total, 1);
+}
+
+#[tokio::test]
+async fn test_preconnect_to_unreachable_origin_is_harmless() {
+    // Nothing listens on the port once the listener is dropped
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    drop(listener);
+
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    stack.preconnect(&url);
+    stack.preconnect(&Url::parse("ftp://127.0.0.1/file").unwrap());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    assert_eq!(stack.pool_metrics().await.created_count, 0);
+}
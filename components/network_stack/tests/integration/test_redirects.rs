@@ -0,0 +1,327 @@
+//! Integration tests for redirect handling
+//!
+//! Covers the three redirect modes, method rewriting on 301/302/303 versus
+//! method and body preservation on 307/308, credential
+//! stripping on cross-origin redirects, and the redirect limit / loop detection.
+
+use super::origin::{self, Origin, RecordedRequest};
+use http::{HeaderMap, HeaderValue};
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestBody, RequestMode, RequestPriority, ResponseBody, ResponseType,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+fn request(url: Url, method: HttpMethod, redirect: RedirectMode) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn redirect_to(status: u16, location: &str) -> Vec<u8> {
+    origin::response(status, "Redirect", &[("Location", location)], "")
+}
+
+/// Origin with `/start` redirecting to `/final`, which serves `done`
+async fn start_redirecting_origin(status: u16) -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(move |req: &RecordedRequest| match req.path.as_str() {
+            "/start" => redirect_to(status, "/final"),
+            _ => origin::response(200, "OK", &[], "done"),
+        }),
+    )
+    .await
+}
+
+fn body_text(body: ResponseBody) -> String {
+    match body {
+        ResponseBody::Bytes(bytes) => String::from_utf8(bytes).unwrap(),
+        ResponseBody::Empty => String::new(),
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_follow_mode_follows_redirect() {
+    let origin = start_redirecting_origin(302).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack
+        .fetch(request(
+            origin.url("/start"),
+            HttpMethod::Get,
+            RedirectMode::Follow,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert!(response.redirected);
+    assert_eq!(response.url, origin.url("/final"));
+    assert_eq!(body_text(response.body), "done");
+}
+
+#[tokio::test]
+async fn test_unredirected_response_is_not_marked_redirected() {
+    let origin = start_redirecting_origin(302).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack
+        .fetch(request(
+            origin.url("/final"),
+            HttpMethod::Get,
+            RedirectMode::Follow,
+        ))
+        .await
+        .unwrap();
+
+    assert!(!response.redirected);
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_error_mode_rejects_redirect() {
+    let origin = start_redirecting_origin(301).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let result = stack
+        .fetch(request(
+            origin.url("/start"),
+            HttpMethod::Get,
+            RedirectMode::Error,
+        ))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Other(_))));
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_manual_mode_returns_redirect_response() {
+    let origin = start_redirecting_origin(307).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack
+        .fetch(request(
+            origin.url("/start"),
+            HttpMethod::Get,
+            RedirectMode::Manual,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 0);
+    assert_eq!(response.type_, ResponseType::OpaqueRedirect);
+    assert!(response.headers.is_empty());
+    assert!(matches!(response.body, ResponseBody::Empty));
+    assert!(!response.redirected);
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_303_converts_post_to_get() {
+    let origin = start_redirecting_origin(303).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let mut post = request(origin.url("/start"), HttpMethod::Post, RedirectMode::Follow);
+    post.headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain"),
+    );
+    post.body = Some(RequestBody::Text("payload".to_string()));
+
+    let response = stack.fetch(post).await.unwrap();
+    assert_eq!(response.status, 200);
+
+    let requests = origin.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].body, b"payload");
+    assert_eq!(requests[1].method, "GET");
+    assert!(requests[1].body.is_empty());
+    assert_eq!(requests[1].header("content-type"), None);
+}
+
+/// POST `payload` to `/start` and return the requests the origin received
+async fn post_through_redirect(status: u16) -> Vec<RecordedRequest> {
+    let origin = start_redirecting_origin(status).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let mut post = request(origin.url("/start"), HttpMethod::Post, RedirectMode::Follow);
+    post.headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain"),
+    );
+    post.body = Some(RequestBody::Text("payload".to_string()));
+
+    let response = stack.fetch(post).await.unwrap();
+    assert_eq!(response.status, 200);
+    origin.requests()
+}
+
+#[tokio::test]
+async fn test_301_converts_post_to_get() {
+    let requests = post_through_redirect(301).await;
+
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[1].method, "GET");
+    assert!(requests[1].body.is_empty());
+    assert_eq!(requests[1].header("content-type"), None);
+}
+
+#[tokio::test]
+async fn test_307_and_308_preserve_post_and_body() {
+    for status in [307, 308] {
+        let requests = post_through_redirect(status).await;
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].path, "/final");
+        assert_eq!(requests[1].method, "POST");
+        assert_eq!(requests[1].body, b"payload");
+        assert_eq!(requests[1].header("content-type"), Some("text/plain"));
+    }
+}
+
+#[tokio::test]
+async fn test_authorization_stripped_on_cross_origin_redirect() {
+    let target = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "target")),
+    )
+    .await;
+    let target_url = target.url("/landing").to_string();
+    let source = Origin::start(
+        Duration::ZERO,
+        Arc::new(move |req: &RecordedRequest| match req.path.as_str() {
+            "/same" => redirect_to(302, "/cross"),
+            _ => redirect_to(302, &target_url),
+        }),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let mut get = request(source.url("/same"), HttpMethod::Get, RedirectMode::Follow);
+    get.headers.insert(
+        http::header::AUTHORIZATION,
+        HeaderValue::from_static("Bearer secret"),
+    );
+
+    let response = stack.fetch(get).await.unwrap();
+    assert_eq!(body_text(response.body), "target");
+
+    // Same-origin hops keep the credentials
+    let source_requests = source.requests();
+    assert_eq!(source_requests.len(), 2);
+    assert_eq!(source_requests[1].path, "/cross");
+    assert_eq!(
+        source_requests[1].header("authorization"),
+        Some("Bearer secret")
+    );
+
+    // The cross-origin hop does not
+    let target_requests = target.requests();
+    assert_eq!(target_requests.len(), 1);
+    assert_eq!(target_requests[0].header("authorization"), None);
+}
+
+#[tokio::test]
+async fn test_redirect_chain_may_revisit_a_url() {
+    // /login bounces to /auth the first time, which sends it back to /login
+    let logins = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&logins);
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(move |req: &RecordedRequest| match req.path.as_str() {
+            "/login" if counter.fetch_add(1, Ordering::SeqCst) == 0 => redirect_to(302, "/auth"),
+            "/login" => origin::response(200, "OK", &[], "welcome"),
+            _ => redirect_to(302, "/login"),
+        }),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = stack
+        .fetch(request(
+            origin.url("/login"),
+            HttpMethod::Get,
+            RedirectMode::Follow,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert!(response.redirected);
+    assert_eq!(body_text(response.body), "welcome");
+    assert_eq!(logins.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_redirect_loop_is_detected() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|req: &RecordedRequest| match req.path.as_str() {
+            "/a" => redirect_to(302, "/b"),
+            _ => redirect_to(302, "/a"),
+        }),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let result = stack
+        .fetch(request(
+            origin.url("/a"),
+            HttpMethod::Get,
+            RedirectMode::Follow,
+        ))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::TooManyRedirects)));
+    // /a and /b are each revisited once; the loop is caught when /a comes
+    // round a third time, long before the redirect limit
+    assert_eq!(origin.hits(), 4);
+}
+
+#[tokio::test]
+async fn test_redirect_limit_is_enforced() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|req: &RecordedRequest| {
+            let hop: usize = req.path.trim_start_matches("/hop/").parse().unwrap();
+            redirect_to(302, &format!("/hop/{}", hop + 1))
+        }),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let result = stack
+        .fetch(request(
+            origin.url("/hop/0"),
+            HttpMethod::Get,
+            RedirectMode::Follow,
+        ))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::TooManyRedirects)));
+    // The initial request plus 20 followed redirects
+    assert_eq!(origin.hits(), 21);
+}
@@ -0,0 +1,114 @@
+//! Integration tests for the Referer header sent by fetch
+
+use super::origin::{self, Origin, RecordedRequest};
+use http::HeaderMap;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestMode, RequestPriority,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+fn request(url: Url, referrer: &str, policy: ReferrerPolicy) -> NetworkRequest {
+    NetworkRequest {
+        url,
+        method: HttpMethod::Get,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: Some(referrer.to_string()),
+        referrer_policy: policy,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+async fn start_origin() -> Origin {
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(|req: &RecordedRequest| match req.path.as_str() {
+            "/start" => origin::response(302, "Found", &[("Location", "/final")], ""),
+            _ => origin::response(200, "OK", &[], "ok"),
+        }),
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_fetch_sends_referer_for_policy() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let page = origin.url("/page?secret=1").to_string();
+
+    // Same-origin request under the default policy carries the full URL
+    stack
+        .fetch(request(
+            origin.url("/final"),
+            &page,
+            ReferrerPolicy::StrictOriginWhenCrossOrigin,
+        ))
+        .await
+        .unwrap();
+    // Cross-origin referrer is reduced to its origin
+    stack
+        .fetch(request(
+            origin.url("/final"),
+            "http://elsewhere.test/page",
+            ReferrerPolicy::StrictOriginWhenCrossOrigin,
+        ))
+        .await
+        .unwrap();
+
+    let requests = origin.requests();
+    assert_eq!(requests[0].header("referer"), Some(page.as_str()));
+    assert_eq!(
+        requests[1].header("referer"),
+        Some("http://elsewhere.test/")
+    );
+}
+
+#[tokio::test]
+async fn test_no_referrer_policy_sends_no_header() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let page = origin.url("/page").to_string();
+
+    stack
+        .fetch(request(
+            origin.url("/final"),
+            &page,
+            ReferrerPolicy::NoReferrer,
+        ))
+        .await
+        .unwrap();
+
+    assert_eq!(origin.requests()[0].header("referer"), None);
+}
+
+#[tokio::test]
+async fn test_referer_is_recomputed_on_redirect() {
+    let origin = start_origin().await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    stack
+        .fetch(request(
+            origin.url("/start"),
+            "http://elsewhere.test/page",
+            ReferrerPolicy::SameOrigin,
+        ))
+        .await
+        .unwrap();
+
+    // Cross-origin under same-origin policy: no Referer on either hop
+    let requests = origin.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests.iter().all(|r| r.header("referer").is_none()));
+}
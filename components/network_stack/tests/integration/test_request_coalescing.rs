@@ -0,0 +1,117 @@
+//! Integration tests for request coalescing
+//!
+//! Concurrent identical GET requests should share a single in-flight fetch,
+//! while unsafe methods and requests bypassing the cache are always sent to
+//! the origin.
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestBody, RequestMode, RequestPriority, ResponseBody,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Start an origin that answers every request with `hello` after `delay`
+async fn start_counting_origin(delay: Duration) -> Origin {
+    Origin::start(
+        delay,
+        Arc::new(|_| origin::response(200, "OK", &[("Content-Type", "text/plain")], "hello")),
+    )
+    .await
+}
+
+fn request(url: &Url, method: HttpMethod) -> NetworkRequest {
+    NetworkRequest {
+        url: url.clone(),
+        method,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::Default,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+/// Ten concurrent identical GETs should reach the origin once and all see the body
+#[tokio::test]
+async fn test_concurrent_identical_gets_hit_origin_once() {
+    let origin = start_counting_origin(Duration::from_millis(200)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/shared");
+
+    let fetches = (0..10).map(|_| stack.fetch(request(&url, HttpMethod::Get)));
+    let responses = futures::future::join_all(fetches).await;
+
+    assert_eq!(origin.hits(), 1);
+    for response in responses {
+        let response = response.expect("coalesced fetch should succeed");
+        assert_eq!(response.status, 200);
+        match response.body {
+            ResponseBody::Bytes(body) => assert_eq!(body, b"hello"),
+            other => panic!("expected buffered body, got {:?}", other),
+        }
+    }
+}
+
+/// Requests issued after the shared fetch completes go back to the origin
+#[tokio::test]
+async fn test_sequential_gets_are_not_coalesced() {
+    let origin = start_counting_origin(Duration::from_millis(0)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/sequential");
+
+    stack.fetch(request(&url, HttpMethod::Get)).await.unwrap();
+    stack.fetch(request(&url, HttpMethod::Get)).await.unwrap();
+
+    assert_eq!(origin.hits(), 2);
+}
+
+/// POST is not safe, so concurrent identical POSTs are each sent
+#[tokio::test]
+async fn test_concurrent_posts_are_not_coalesced() {
+    let origin = start_counting_origin(Duration::from_millis(200)).await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = origin.url("/submit");
+
+    let fetches = (0..3).map(|_| {
+        let mut post = request(&url, HttpMethod::Post);
+        post.body = Some(RequestBody::Text("payload".to_string()));
+        stack.fetch(post)
+    });
+    let responses = futures::future::join_all(fetches).await;
+
+    assert!(responses.iter().all(|r| r.is_ok()));
+    assert_eq!(origin.hits(), 3);
+}
+
+/// Reload and no-store requests want their own network response, so they are each sent
+#[tokio::test]
+async fn test_concurrent_reload_and_no_store_gets_are_not_coalesced() {
+    for cache in [CacheMode::Reload, CacheMode::NoStore] {
+        let origin = start_counting_origin(Duration::from_millis(200)).await;
+        let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+        let url = origin.url("/fresh");
+
+        let fetches = (0..3).map(|_| {
+            let mut get = request(&url, HttpMethod::Get);
+            get.cache = cache;
+            stack.fetch(get)
+        });
+        let responses = futures::future::join_all(fetches).await;
+
+        assert!(responses.iter().all(|r| r.is_ok()));
+        assert_eq!(origin.hits(), 3, "{:?} requests were coalesced", cache);
+    }
+}
@@ -0,0 +1,174 @@
+//! Integration tests for retrying transient failures
+//!
+//! The origin fails a configured number of times before answering normally,
+//! so the number of hits shows how often the stack retried.
+
+use super::origin::{self, Origin};
+use http::HeaderMap;
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl, RetryPolicy};
+use network_types::{
+    CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode, ReferrerPolicy,
+    RequestBody, RequestMode, RequestPriority, ResponseBody,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Start an origin answering `503 Service Unavailable` to the first `failures` requests
+async fn start_flaky_origin(failures: usize) -> Origin {
+    let seen = Arc::new(AtomicUsize::new(0));
+    Origin::start(
+        Duration::ZERO,
+        Arc::new(move |_| {
+            if seen.fetch_add(1, Ordering::SeqCst) < failures {
+                origin::response(503, "Service Unavailable", &[], "try again")
+            } else {
+                origin::response(200, "OK", &[], "recovered")
+            }
+        }),
+    )
+    .await
+}
+
+fn fast_policy(max_retries: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_retries,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(10),
+        ..RetryPolicy::default()
+    }
+}
+
+fn stack_with_retry(policy: Option<RetryPolicy>) -> NetworkStackImpl {
+    NetworkStackImpl::new(NetworkConfig {
+        retry: policy,
+        ..NetworkConfig::default()
+    })
+    .unwrap()
+}
+
+fn request(url: &Url, method: HttpMethod) -> NetworkRequest {
+    NetworkRequest {
+        url: url.clone(),
+        method,
+        headers: HeaderMap::new(),
+        body: None,
+        mode: RequestMode::Cors,
+        credentials: CredentialsMode::SameOrigin,
+        cache: CacheMode::NoStore,
+        redirect: RedirectMode::Follow,
+        referrer: None,
+        referrer_policy: ReferrerPolicy::NoReferrer,
+        integrity: None,
+        keepalive: false,
+        signal: None,
+        priority: RequestPriority::Auto,
+        window: None,
+    }
+}
+
+fn body_bytes(body: ResponseBody) -> Vec<u8> {
+    match body {
+        ResponseBody::Bytes(bytes) => bytes,
+        ResponseBody::Empty => Vec::new(),
+        ResponseBody::Stream(_) => panic!("Expected a buffered body"),
+    }
+}
+
+#[tokio::test]
+async fn test_get_is_retried_until_success() {
+    let origin = start_flaky_origin(2).await;
+    let stack = stack_with_retry(Some(fast_policy(3)));
+
+    let response = stack
+        .fetch(request(&origin.url("/flaky"), HttpMethod::Get))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(body_bytes(response.body), b"recovered");
+    assert_eq!(origin.hits(), 3, "Two failures followed by one success");
+}
+
+#[tokio::test]
+async fn test_last_failure_returned_when_retries_exhausted() {
+    let origin = start_flaky_origin(10).await;
+    let stack = stack_with_retry(Some(fast_policy(2)));
+
+    let response = stack
+        .fetch(request(&origin.url("/down"), HttpMethod::Get))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 503);
+    assert_eq!(origin.hits(), 3, "First attempt plus two retries");
+}
+
+#[tokio::test]
+async fn test_no_retry_without_policy() {
+    let origin = start_flaky_origin(1).await;
+    let stack = stack_with_retry(None);
+
+    let response = stack
+        .fetch(request(&origin.url("/once"), HttpMethod::Get))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status, 503);
+    assert_eq!(origin.hits(), 1);
+}
+
+#[tokio::test]
+async fn test_post_is_not_retried_by_default() {
+    let origin = start_flaky_origin(1).await;
+    let stack = stack_with_retry(Some(fast_policy(3)));
+
+    let mut post = request(&origin.url("/submit"), HttpMethod::Post);
+    post.body = Some(RequestBody::Text("payload".to_string()));
+    let response = stack.fetch(post).await.unwrap();
+
+    assert_eq!(response.status, 503);
+    assert_eq!(origin.hits(), 1, "POST must not be repeated");
+}
+
+#[tokio::test]
+async fn test_post_is_retried_when_opted_in() {
+    let origin = start_flaky_origin(1).await;
+    let stack = stack_with_retry(Some(RetryPolicy {
+        retry_non_idempotent: true,
+        ..fast_policy(3)
+    }));
+
+    let mut post = request(&origin.url("/submit"), HttpMethod::Post);
+    post.body = Some(RequestBody::Text("payload".to_string()));
+    let response = stack.fetch(post).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(origin.hits(), 2);
+    assert!(origin.requests().iter().all(|r| r.body == b"payload"));
+}
+
+#[tokio::test]
+async fn test_connection_failures_consult_retry_on() {
+    // Reserve a port, then close it so connections are refused
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    drop(listener);
+
+    let consulted = Arc::new(AtomicUsize::new(0));
+    let counter = consulted.clone();
+    let stack = stack_with_retry(Some(RetryPolicy {
+        retry_on: Arc::new(move |e: &NetworkError| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            e.is_retryable()
+        }),
+        ..fast_policy(2)
+    }));
+
+    let result = stack.fetch(request(&url, HttpMethod::Get)).await;
+
+    assert!(result.is_err());
+    assert_eq!(consulted.load(Ordering::SeqCst), 2, "One check per retry");
+}
@@ -0,0 +1,70 @@
+//! Integration tests for exposing Server-Timing metrics on responses
+
+use super::origin::{self, Origin, RecordedRequest};
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{CacheMode, NetworkRequest};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_server_timing_header_is_parsed_into_timing() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_: &RecordedRequest| {
+            origin::response(
+                200,
+                "OK",
+                &[
+                    ("Server-Timing", "db;dur=53.2, app;dur=47.2;desc=\"App\""),
+                    ("Server-Timing", "cache;desc=miss"),
+                ],
+                "ok",
+            )
+        }),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let request = NetworkRequest::builder(origin.url("/"))
+        .cache(CacheMode::NoStore)
+        .build();
+    let response = stack.fetch(request).await.unwrap();
+
+    let metrics: Vec<(&str, f64, &str)> = response
+        .timing
+        .server_timing
+        .iter()
+        .map(|entry| {
+            (
+                entry.name.as_str(),
+                entry.duration_ms,
+                entry.description.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        metrics,
+        [
+            ("db", 53.2, ""),
+            ("app", 47.2, "App"),
+            ("cache", 0.0, "miss")
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_response_without_server_timing_has_no_metrics() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "ok")),
+    )
+    .await;
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let request = NetworkRequest::builder(origin.url("/"))
+        .cache(CacheMode::NoStore)
+        .build();
+    let response = stack.fetch(request).await.unwrap();
+
+    assert!(response.timing.server_timing.is_empty());
+}
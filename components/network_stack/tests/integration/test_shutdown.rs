@@ -0,0 +1,89 @@
+//! Integration tests for shutting down the network stack
+
+use super::origin::{self, Origin, RecordedRequest};
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{CacheMode, NetworkRequest};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+fn request(url: url::Url) -> NetworkRequest {
+    NetworkRequest::builder(url)
+        .cache(CacheMode::NoStore)
+        .build()
+}
+
+#[tokio::test]
+async fn test_shutdown_refuses_new_fetches_and_drains_started_ones() {
+    let origin = Origin::start(
+        Duration::from_millis(300),
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "slow")),
+    )
+    .await;
+    let stack = Arc::new(NetworkStackImpl::new(NetworkConfig::default()).unwrap());
+
+    // Given: a fetch the origin has started answering
+    let in_flight = {
+        let stack = stack.clone();
+        let url = origin.url("/slow");
+        tokio::spawn(async move { stack.fetch(request(url)).await })
+    };
+    while origin.hits() == 0 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    // When: the stack is shut down
+    let shutdown = {
+        let stack = stack.clone();
+        tokio::spawn(async move { stack.shutdown(Duration::from_secs(5)).await })
+    };
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    // Then: a new fetch fails without reaching the origin
+    let started = Instant::now();
+    let result = stack.fetch(request(origin.url("/new"))).await;
+    assert!(matches!(result, Err(NetworkError::ConnectionFailed(_))));
+    assert!(started.elapsed() < Duration::from_millis(100));
+    assert_eq!(origin.hits(), 1);
+
+    // And: the started fetch completes, after which shutdown returns
+    let response = in_flight.await.unwrap().unwrap();
+    assert_eq!(response.status, 200);
+    shutdown.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_without_requests_returns_immediately() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(1),
+        stack.shutdown(Duration::from_secs(5)),
+    )
+    .await;
+
+    assert!(matches!(result, Ok(Ok(()))));
+}
+
+#[tokio::test]
+async fn test_shutdown_times_out_on_stalled_request() {
+    let origin = Origin::start(
+        Duration::from_secs(30),
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "stalled")),
+    )
+    .await;
+    let stack = Arc::new(NetworkStackImpl::new(NetworkConfig::default()).unwrap());
+    let stalled = {
+        let stack = stack.clone();
+        let url = origin.url("/stalled");
+        tokio::spawn(async move { stack.fetch(request(url)).await })
+    };
+    while origin.hits() == 0 {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let result = stack.shutdown(Duration::from_millis(50)).await;
+
+    assert!(matches!(result, Err(NetworkError::Timeout(_))));
+    stalled.abort();
+}
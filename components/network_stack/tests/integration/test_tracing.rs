@@ -0,0 +1,248 @@
+//! Integration tests for the spans and events of the `tracing` feature
+
+#![cfg(feature = "tracing")]
+
+use super::origin::{self, Origin, RecordedRequest};
+use async_trait::async_trait;
+use dns_resolver::DnsResolver;
+use network_errors::NetworkError;
+use network_stack::{NetworkConfig, NetworkStack, NetworkStackImpl};
+use network_types::{CacheMode, NetworkRequest};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use tracing_core::span::Current;
+
+/// Span as recorded by [`Recorder`]
+#[derive(Debug, Clone, Default)]
+struct RecordedSpan {
+    name: String,
+    fields: HashMap<String, String>,
+}
+
+/// Event as recorded by [`Recorder`], with the span it was emitted in
+#[derive(Debug, Clone)]
+struct RecordedEvent {
+    message: String,
+    fields: HashMap<String, String>,
+    span: Option<String>,
+}
+
+#[derive(Default)]
+struct Recording {
+    spans: HashMap<u64, RecordedSpan>,
+    metadata: HashMap<u64, &'static Metadata<'static>>,
+    entered: Vec<u64>,
+    events: Vec<RecordedEvent>,
+}
+
+/// Subscriber keeping every span and event in memory
+#[derive(Clone, Default)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    recording: Arc<Mutex<Recording>>,
+}
+
+impl Recorder {
+    fn spans(&self) -> Vec<RecordedSpan> {
+        self.recording
+            .lock()
+            .unwrap()
+            .spans
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    fn events(&self) -> Vec<RecordedEvent> {
+        self.recording.lock().unwrap().events.clone()
+    }
+
+    fn event(&self, message: &str) -> Option<RecordedEvent> {
+        self.events()
+            .into_iter()
+            .find(|event| event.message == message)
+    }
+}
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut span = RecordedSpan {
+            name: attributes.metadata().name().to_string(),
+            ..Default::default()
+        };
+        attributes.record(&mut FieldVisitor(&mut span.fields));
+
+        let mut recording = self.recording.lock().unwrap();
+        recording.spans.insert(id, span);
+        recording.metadata.insert(id, attributes.metadata());
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut recording = self.recording.lock().unwrap();
+        if let Some(span) = recording.spans.get_mut(&span.into_u64()) {
+            values.record(&mut FieldVisitor(&mut span.fields));
+        }
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = HashMap::new();
+        event.record(&mut FieldVisitor(&mut fields));
+        let message = fields.remove("message").unwrap_or_default();
+
+        let mut recording = self.recording.lock().unwrap();
+        let span = recording
+            .entered
+            .last()
+            .and_then(|id| recording.spans.get(id))
+            .map(|span| span.name.clone());
+        recording.events.push(RecordedEvent {
+            message,
+            fields,
+            span,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.recording.lock().unwrap().entered.push(span.into_u64());
+    }
+
+    fn exit(&self, span: &Id) {
+        let mut recording = self.recording.lock().unwrap();
+        if let Some(position) = recording
+            .entered
+            .iter()
+            .rposition(|id| *id == span.into_u64())
+        {
+            recording.entered.remove(position);
+        }
+    }
+
+    fn current_span(&self) -> Current {
+        let recording = self.recording.lock().unwrap();
+        match recording.entered.last() {
+            Some(id) => Current::new(Id::from_u64(*id), recording.metadata[id]),
+            None => Current::none(),
+        }
+    }
+}
+
+/// Resolver answering every lookup with the loopback address
+struct LoopbackResolver;
+
+#[async_trait]
+impl DnsResolver for LoopbackResolver {
+    async fn resolve(&self, _hostname: String) -> Result<Vec<IpAddr>, NetworkError> {
+        Ok(vec![IpAddr::V4(Ipv4Addr::LOCALHOST)])
+    }
+
+    async fn resolve_with_timeout(
+        &self,
+        hostname: String,
+        _timeout: Duration,
+    ) -> Result<Vec<IpAddr>, NetworkError> {
+        self.resolve(hostname).await
+    }
+}
+
+fn request(url: url::Url) -> NetworkRequest {
+    NetworkRequest::builder(url)
+        .cache(CacheMode::NoStore)
+        .build()
+}
+
+#[tokio::test]
+async fn test_fetch_emits_span_and_connection_events() {
+    let origin = Origin::start(
+        Duration::ZERO,
+        Arc::new(|_: &RecordedRequest| origin::response(200, "OK", &[], "ok")),
+    )
+    .await;
+    let config = NetworkConfig {
+        dns_resolver: Some(Arc::new(LoopbackResolver)),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+    let url = url::Url::parse(&format!("http://traced.test:{}/", origin.addr.port())).unwrap();
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    stack.fetch(request(url.clone())).await.unwrap();
+    stack.fetch(request(url.clone())).await.unwrap();
+
+    // One fetch span per request, carrying method, URL and protocol
+    let fetch_spans: Vec<_> = recorder
+        .spans()
+        .into_iter()
+        .filter(|span| span.name == "fetch")
+        .collect();
+    assert_eq!(fetch_spans.len(), 2);
+    for span in &fetch_spans {
+        assert_eq!(span.fields["method"], "Get");
+        assert_eq!(span.fields["url"], url.as_str());
+        assert_eq!(span.fields["protocol"], "http/1.1");
+    }
+
+    // The first request resolves and connects, the second reuses the connection
+    let resolved = recorder.event("dns resolved").expect("dns resolved event");
+    assert_eq!(resolved.fields["host"], "traced.test");
+    assert!(recorder.event("connection created").is_some());
+    let reused = recorder
+        .event("connection reused")
+        .expect("connection reused event");
+    assert_eq!(reused.span.as_deref(), Some("http1.fetch"));
+
+    let statuses: Vec<_> = recorder
+        .events()
+        .into_iter()
+        .filter(|event| event.message == "response received")
+        .filter(|event| event.span.as_deref() == Some("fetch"))
+        .map(|event| event.fields["status"].clone())
+        .collect();
+    assert_eq!(statuses, ["200", "200"]);
+}
+
+#[tokio::test]
+async fn test_failed_fetch_emits_error_event() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let recorder = Recorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let result = stack
+        .fetch(request(url::Url::parse("gopher://example.com/").unwrap()))
+        .await;
+
+    assert!(result.is_err());
+    let failed = recorder
+        .event("request failed")
+        .expect("request failed event");
+    assert_eq!(failed.span.as_deref(), Some("fetch"));
+    assert!(failed.fields["error"].contains("gopher"));
+}
@@ -0,0 +1,226 @@
+//! Integration tests for fetching data:, file: and unsupported URL schemes
+
+use network_errors::NetworkError;
+use network_stack::{
+    NetworkConditions, NetworkConfig, NetworkStack, NetworkStackImpl, UrlHandlersConfig,
+};
+use network_types::{HttpMethod, NetworkRequest, NetworkResponse, ResponseBody};
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Stack serving file: URLs from `dir`
+fn file_stack(dir: &Path) -> NetworkStackImpl {
+    let config = NetworkConfig {
+        url_handlers: Some(UrlHandlersConfig {
+            enable_data_urls: true,
+            enable_file_urls: true,
+            allowed_file_paths: vec![dir.to_path_buf()],
+        }),
+        ..NetworkConfig::default()
+    };
+    NetworkStackImpl::new(config).unwrap()
+}
+
+/// Fresh directory under the system temp dir, removed by the caller
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("network_stack_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.canonicalize().unwrap()
+}
+
+fn body_bytes(response: NetworkResponse) -> Vec<u8> {
+    match response.body {
+        ResponseBody::Bytes(bytes) => bytes,
+        other => panic!("expected buffered body, got {:?}", other),
+    }
+}
+
+fn header<'a>(response: &'a NetworkResponse, name: &str) -> Option<&'a str> {
+    response
+        .headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+async fn fetch(stack: &NetworkStackImpl, url: &str) -> Result<NetworkResponse, NetworkError> {
+    stack
+        .fetch(NetworkRequest::builder(Url::parse(url).unwrap()).build())
+        .await
+}
+
+#[tokio::test]
+async fn test_data_url_is_decoded() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let response = fetch(
+        &stack,
+        "data:text/plain;charset=utf-8;base64,SGVsbG8gV29ybGQ=",
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("text/plain;charset=utf-8")
+    );
+    assert_eq!(header(&response, "content-length"), Some("11"));
+    assert_eq!(body_bytes(response), b"Hello World");
+}
+
+#[tokio::test]
+async fn test_data_url_is_served_offline() {
+    let mut stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    stack.set_network_conditions(NetworkConditions {
+        offline: true,
+        ..NetworkConditions::default()
+    });
+
+    let response = fetch(&stack, "data:,hi").await.unwrap();
+
+    assert_eq!(body_bytes(response), b"hi");
+}
+
+#[tokio::test]
+async fn test_disabled_data_urls_are_rejected() {
+    let config = NetworkConfig {
+        url_handlers: Some(UrlHandlersConfig {
+            enable_data_urls: false,
+            ..UrlHandlersConfig::default()
+        }),
+        ..NetworkConfig::default()
+    };
+    let stack = NetworkStackImpl::new(config).unwrap();
+
+    let result = fetch(&stack, "data:,hi").await;
+
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+}
+
+#[tokio::test]
+async fn test_file_url_is_read() {
+    let dir = temp_dir("file_url_read");
+    std::fs::write(dir.join("page.html"), "<p>local</p>").unwrap();
+    let stack = file_stack(&dir);
+
+    let url = Url::from_file_path(dir.join("page.html")).unwrap();
+    let response = fetch(&stack, url.as_str()).await.unwrap();
+
+    assert_eq!(response.status, 200);
+    assert_eq!(response.url, url);
+    assert_eq!(header(&response, "content-type"), Some("text/html"));
+    assert_eq!(body_bytes(response), b"<p>local</p>");
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_head_file_url_has_no_body() {
+    let dir = temp_dir("file_url_head");
+    std::fs::write(dir.join("data.bin"), [1, 2, 3]).unwrap();
+    let stack = file_stack(&dir);
+
+    let url = Url::from_file_path(dir.join("data.bin")).unwrap();
+    let request = NetworkRequest::builder(url)
+        .method(HttpMethod::Head)
+        .build();
+    let response = stack.fetch(request).await.unwrap();
+
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("application/octet-stream")
+    );
+    assert_eq!(header(&response, "content-length"), Some("3"));
+    assert!(body_bytes(response).is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_directory_is_listed() {
+    let dir = temp_dir("file_url_directory");
+    std::fs::write(dir.join("notes.txt"), "notes").unwrap();
+    let stack = file_stack(&dir);
+
+    let url = Url::from_directory_path(&dir).unwrap();
+    let response = fetch(&stack, url.as_str()).await.unwrap();
+
+    assert_eq!(
+        header(&response, "content-type"),
+        Some("text/html;charset=utf-8")
+    );
+    let html = String::from_utf8(body_bytes(response)).unwrap();
+    assert!(html.contains("notes.txt"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_url_outside_allowed_paths_is_rejected() {
+    let dir = temp_dir("file_url_denied");
+    let stack = file_stack(&dir.join("public"));
+
+    let url = Url::from_file_path(dir.join("secret.txt")).unwrap();
+    std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+    let result = fetch(&stack, url.as_str()).await;
+
+    assert!(result.is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_urls_are_disabled_by_default() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    let result = fetch(&stack, "file:///etc/hosts").await;
+
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+}
+
+#[tokio::test]
+async fn test_blob_url_is_read_until_revoked() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let registry = stack.blob_registry();
+    let url = registry.create_object_url(b"{\"ok\":true}".to_vec(), "application/json");
+
+    let response = fetch(&stack, &url).await.unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(header(&response, "content-type"), Some("application/json"));
+    assert_eq!(body_bytes(response), b"{\"ok\":true}");
+
+    registry.revoke_object_url(&url);
+    let result = fetch(&stack, &url).await;
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+}
+
+#[tokio::test]
+async fn test_blob_url_rejects_post() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+    let url = stack
+        .blob_registry()
+        .create_object_url(b"data".to_vec(), "text/plain");
+
+    let request = NetworkRequest::builder(Url::parse(&url).unwrap())
+        .method(HttpMethod::Post)
+        .build();
+    let result = stack.fetch(request).await;
+
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+}
+
+#[tokio::test]
+async fn test_unknown_scheme_is_invalid_url() {
+    let stack = NetworkStackImpl::new(NetworkConfig::default()).unwrap();
+
+    for url in [
+        "blob:https://example.com/1b2f",
+        "gopher://example.com/",
+        "about:blank",
+    ] {
+        let result = fetch(&stack, url).await;
+        assert!(
+            matches!(result, Err(NetworkError::InvalidUrl(_))),
+            "{} gave {:?}",
+            url,
+            result.map(|response| response.status)
+        );
+    }
+}
@@ -5,3 +5,5 @@
 mod test_network_stack;
 mod test_protocol_routing;
 mod test_config;
+mod test_network_quality;
+mod test_referrer;
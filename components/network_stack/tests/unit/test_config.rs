@@ -2,7 +2,8 @@
 //!
 //! Verifies configuration initialization and defaults.
 
-use network_stack::NetworkConfig;
+use network_stack::{NetworkConfig, RetryPolicy};
+use std::time::Duration;
 
 /// Test NetworkConfig default values
 #[test]
@@ -26,6 +27,10 @@ fn test_network_config_customization() {
         max_connections_per_host: 4,
         enable_keepalive: true,
         enable_pipelining: false,
+        tcp_nodelay: true,
+        tcp_keepalive: None,
+        max_response_body_bytes: None,
+        use_expect_continue: false,
     };
 
     // When: creating NetworkConfig with custom values
@@ -39,6 +44,7 @@ fn test_network_config_customization() {
         security: None,
         proxy: None,
         dns: None,
+        dns_resolver: None,
         cors: None,
         request_scheduling: None,
         bandwidth_limit: None,
@@ -50,9 +56,32 @@ fn test_network_config_customization() {
         ftp: None,
         content_encoding: None,
         platform_integration: None,
+        default_headers: http::HeaderMap::new(),
+        retry: None,
+        interceptors: Vec::new(),
+        transport: None,
     };
 
     // Then: custom values should be preserved
     assert!(config.http1.is_some(), "Should have custom HTTP/1.1 config");
     assert!(config.websocket.is_none(), "Should not have WebSocket config");
 }
+
+#[test]
+fn test_retry_policy_backoff_is_exponential_and_capped() {
+    let policy = RetryPolicy {
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(350),
+        ..Default::default()
+    };
+
+    assert_eq!(policy.backoff(0), Duration::from_millis(100));
+    assert_eq!(policy.backoff(1), Duration::from_millis(200));
+    assert_eq!(policy.backoff(2), Duration::from_millis(350));
+    assert_eq!(policy.backoff(40), Duration::from_millis(350));
+}
+
+#[test]
+fn test_retry_disabled_by_default() {
+    assert!(NetworkConfig::default().retry.is_none());
+}
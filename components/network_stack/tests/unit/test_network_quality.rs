@@ -0,0 +1,112 @@
+//! Tests for effective connection type estimation
+
+use network_stack::{
+    EffectiveConnectionType, NetworkQualityEstimator, MIN_THROUGHPUT_BYTES, SAMPLE_WINDOW,
+};
+use std::time::Duration;
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// Estimator that has seen one RTT sample and a transfer at `downlink_kbps`
+fn estimator(rtt_ms: u64, downlink_kbps: f64) -> NetworkQualityEstimator {
+    let mut estimator = NetworkQualityEstimator::new();
+    estimator.record_rtt(ms(rtt_ms));
+    // 100 KB over the time giving the requested throughput
+    let bytes = 100_000;
+    let secs = bytes as f64 * 8.0 / 1000.0 / downlink_kbps;
+    estimator.record_transfer(bytes, Duration::from_secs_f64(secs));
+    estimator
+}
+
+#[test]
+fn test_no_samples_is_4g() {
+    let estimator = NetworkQualityEstimator::new();
+
+    assert_eq!(estimator.rtt(), None);
+    assert_eq!(estimator.downlink_kbps(), None);
+    assert_eq!(estimator.effective_type(), EffectiveConnectionType::Type4G);
+}
+
+/// Each row: RTT (ms), downlink (kbps), expected classification
+#[test]
+fn test_classification_thresholds() {
+    use EffectiveConnectionType::*;
+
+    let cases: &[(u64, f64, EffectiveConnectionType)] = &[
+        (2500, 30.0, Slow2G),
+        (2000, 1000.0, Slow2G),
+        (100, 50.0, Slow2G),
+        (1999, 1000.0, Type2G),
+        (1400, 1000.0, Type2G),
+        (100, 60.0, Type2G),
+        (1399, 1000.0, Type3G),
+        (270, 1000.0, Type3G),
+        (100, 700.0, Type3G),
+        (269, 701.0, Type4G),
+        (50, 10_000.0, Type4G),
+    ];
+
+    for &(rtt_ms, downlink_kbps, expected) in cases {
+        assert_eq!(
+            estimator(rtt_ms, downlink_kbps).effective_type(),
+            expected,
+            "rtt {}ms, downlink {}kbps",
+            rtt_ms,
+            downlink_kbps
+        );
+    }
+}
+
+#[test]
+fn test_rtt_alone_classifies() {
+    let mut estimator = NetworkQualityEstimator::new();
+    estimator.record_rtt(ms(500));
+
+    assert_eq!(estimator.effective_type(), EffectiveConnectionType::Type3G);
+}
+
+#[test]
+fn test_estimate_is_median_of_window() {
+    let mut estimator = NetworkQualityEstimator::new();
+    for rtt in [100, 110, 3000, 120, 90] {
+        estimator.record_rtt(ms(rtt));
+    }
+
+    assert_eq!(estimator.rtt(), Some(ms(110)));
+    assert_eq!(estimator.effective_type(), EffectiveConnectionType::Type4G);
+}
+
+#[test]
+fn test_old_samples_leave_the_window() {
+    let mut estimator = NetworkQualityEstimator::new();
+    for _ in 0..SAMPLE_WINDOW {
+        estimator.record_rtt(ms(2500));
+    }
+    assert_eq!(estimator.effective_type(), EffectiveConnectionType::Slow2G);
+
+    for _ in 0..SAMPLE_WINDOW {
+        estimator.record_rtt(ms(50));
+    }
+
+    assert_eq!(estimator.rtt(), Some(ms(50)));
+    assert_eq!(estimator.effective_type(), EffectiveConnectionType::Type4G);
+}
+
+#[test]
+fn test_small_transfers_are_ignored() {
+    let mut estimator = NetworkQualityEstimator::new();
+    estimator.record_transfer(MIN_THROUGHPUT_BYTES - 1, Duration::from_secs(10));
+    estimator.record_transfer(MIN_THROUGHPUT_BYTES, Duration::ZERO);
+
+    assert_eq!(estimator.downlink_kbps(), None);
+}
+
+#[test]
+fn test_downlink_in_kbps() {
+    let mut estimator = NetworkQualityEstimator::new();
+    estimator.record_transfer(125_000, Duration::from_secs(1));
+
+    assert_eq!(estimator.downlink_kbps(), Some(1000.0));
+}
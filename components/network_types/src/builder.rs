@@ -0,0 +1,194 @@
+//! Builder for [`NetworkRequest`]
+
+use crate::{
+    AbortToken, CacheMode, CredentialsMode, HttpMethod, NetworkRequest, RedirectMode,
+    ReferrerPolicy, RequestBody, RequestMode, RequestPriority, WindowId,
+};
+use http::header::IntoHeaderName;
+use http::{HeaderMap, HeaderValue};
+use url::Url;
+
+/// Builds a [`NetworkRequest`], filling in defaults for unset fields
+///
+/// A new builder describes a `GET` with no headers or body, `Cors` mode,
+/// `SameOrigin` credentials, `Default` cache mode, `Follow` redirects, no
+/// referrer, the `StrictOriginWhenCrossOrigin` referrer policy and `Auto`
+/// priority.
+///
+/// # Examples
+///
+/// ```
+/// use http::HeaderValue;
+/// use network_types::{HttpMethod, NetworkRequest, RedirectMode};
+/// use url::Url;
+///
+/// let request = NetworkRequest::builder(Url::parse("https://example.com/api").unwrap())
+///     .method(HttpMethod::Post)
+///     .header("content-type", HeaderValue::from_static("application/json"))
+///     .body(r#"{"id":1}"#)
+///     .redirect(RedirectMode::Error)
+///     .build();
+///
+/// assert_eq!(request.method, HttpMethod::Post);
+/// assert_eq!(request.headers["content-type"], "application/json");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NetworkRequestBuilder {
+    request: NetworkRequest,
+}
+
+impl NetworkRequest {
+    /// Start building a request for `url`
+    pub fn builder(url: Url) -> NetworkRequestBuilder {
+        NetworkRequestBuilder::new(url)
+    }
+}
+
+impl NetworkRequestBuilder {
+    /// Create a builder for a request to `url` with default settings
+    pub fn new(url: Url) -> Self {
+        Self {
+            request: NetworkRequest {
+                url,
+                method: HttpMethod::default(),
+                headers: HeaderMap::new(),
+                body: None,
+                mode: RequestMode::default(),
+                credentials: CredentialsMode::default(),
+                cache: CacheMode::default(),
+                redirect: RedirectMode::default(),
+                referrer: None,
+                referrer_policy: ReferrerPolicy::default(),
+                integrity: None,
+                keepalive: false,
+                signal: None,
+                priority: RequestPriority::default(),
+                window: None,
+            },
+        }
+    }
+
+    /// Set the HTTP method
+    pub fn method(mut self, method: HttpMethod) -> Self {
+        self.request.method = method;
+        self
+    }
+
+    /// Append a header, keeping any earlier values for the same name
+    pub fn header<K: IntoHeaderName>(mut self, name: K, value: HeaderValue) -> Self {
+        self.request.headers.append(name, value);
+        self
+    }
+
+    /// Replace all headers
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.request.headers = headers;
+        self
+    }
+
+    /// Request a range of bytes with a `Range` header
+    ///
+    /// Asks for bytes `first` through `last` inclusive, or from `first` to
+    /// the end when `last` is `None`. Replaces any earlier `Range` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use network_types::NetworkRequest;
+    /// use url::Url;
+    ///
+    /// let request = NetworkRequest::builder(Url::parse("https://example.com/video").unwrap())
+    ///     .byte_range(1024, Some(2047))
+    ///     .build();
+    ///
+    /// assert_eq!(request.headers["range"], "bytes=1024-2047");
+    /// ```
+    pub fn byte_range(mut self, first: u64, last: Option<u64>) -> Self {
+        let range = match last {
+            Some(last) => format!("bytes={}-{}", first, last),
+            None => format!("bytes={}-", first),
+        };
+        self.request.headers.insert(
+            http::header::RANGE,
+            HeaderValue::from_str(&range).expect("byte range is a valid header value"),
+        );
+        self
+    }
+
+    /// Set the request body
+    pub fn body(mut self, body: impl Into<RequestBody>) -> Self {
+        self.request.body = Some(body.into());
+        self
+    }
+
+    /// Set the CORS mode
+    pub fn mode(mut self, mode: RequestMode) -> Self {
+        self.request.mode = mode;
+        self
+    }
+
+    /// Set the credentials mode
+    pub fn credentials(mut self, credentials: CredentialsMode) -> Self {
+        self.request.credentials = credentials;
+        self
+    }
+
+    /// Set the cache mode
+    pub fn cache(mut self, cache: CacheMode) -> Self {
+        self.request.cache = cache;
+        self
+    }
+
+    /// Set the redirect mode
+    pub fn redirect(mut self, redirect: RedirectMode) -> Self {
+        self.request.redirect = redirect;
+        self
+    }
+
+    /// Set the referrer URL
+    pub fn referrer(mut self, referrer: impl Into<String>) -> Self {
+        self.request.referrer = Some(referrer.into());
+        self
+    }
+
+    /// Set the referrer policy
+    pub fn referrer_policy(mut self, policy: ReferrerPolicy) -> Self {
+        self.request.referrer_policy = policy;
+        self
+    }
+
+    /// Set the subresource integrity metadata
+    pub fn integrity(mut self, integrity: impl Into<String>) -> Self {
+        self.request.integrity = Some(integrity.into());
+        self
+    }
+
+    /// Keep the request alive after its initiator goes away
+    pub fn keepalive(mut self, keepalive: bool) -> Self {
+        self.request.keepalive = keepalive;
+        self
+    }
+
+    /// Set the abort token observed by the request
+    pub fn signal(mut self, signal: AbortToken) -> Self {
+        self.request.signal = Some(signal);
+        self
+    }
+
+    /// Set the request priority
+    pub fn priority(mut self, priority: RequestPriority) -> Self {
+        self.request.priority = priority;
+        self
+    }
+
+    /// Associate the request with a window
+    pub fn window(mut self, window: WindowId) -> Self {
+        self.request.window = Some(window);
+        self
+    }
+
+    /// Finish building the request
+    pub fn build(self) -> NetworkRequest {
+        self.request
+    }
+}
@@ -0,0 +1,80 @@
+//! `Content-Range` header parsing for partial responses (RFC 9110, section 14.4)
+
+use crate::NetworkResponse;
+
+/// Byte range carried by a `206 Partial Content` response
+///
+/// # Examples
+///
+/// ```
+/// use network_types::ContentRange;
+///
+/// let range = ContentRange::parse("bytes 100-199/1000").unwrap();
+///
+/// assert_eq!((range.first, range.last), (100, 199));
+/// assert_eq!(range.complete_length, Some(1000));
+/// assert_eq!(range.len(), 100);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    /// Position of the first byte in the range
+    pub first: u64,
+    /// Position of the last byte in the range (inclusive)
+    pub last: u64,
+    /// Length of the complete representation, `None` if the server sent `*`
+    pub complete_length: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parse a `Content-Range` value such as `bytes 0-499/1234`
+    ///
+    /// Returns `None` for other units, for the unsatisfied-range form
+    /// `bytes */1234` sent with `416`, and for ranges that end before they
+    /// start, extend past the complete length, or end at `u64::MAX` (so their
+    /// length would overflow).
+    pub fn parse(value: &str) -> Option<Self> {
+        let (unit, spec) = value.trim().split_once(' ')?;
+        if !unit.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+
+        let (range, complete_length) = spec.trim().split_once('/')?;
+        let complete_length = match complete_length {
+            "*" => None,
+            length => Some(length.parse().ok()?),
+        };
+        let (first, last) = range.split_once('-')?;
+        let (first, last): (u64, u64) = (first.parse().ok()?, last.parse().ok()?);
+
+        if last < first || complete_length.is_some_and(|length| last >= length) {
+            return None;
+        }
+        // The length of a range ending at u64::MAX doesn't fit in a u64
+        if last == u64::MAX {
+            return None;
+        }
+        Some(Self {
+            first,
+            last,
+            complete_length,
+        })
+    }
+
+    /// Number of bytes in the range
+    #[allow(clippy::len_without_is_empty)] // A range always holds at least one byte
+    pub fn len(&self) -> u64 {
+        self.last - self.first + 1
+    }
+}
+
+impl NetworkResponse {
+    /// The byte range of a partial response, from its `Content-Range` header
+    ///
+    /// Returns `None` when the header is missing or isn't a valid byte range.
+    pub fn content_range(&self) -> Option<ContentRange> {
+        self.headers
+            .get(http::header::CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(ContentRange::parse)
+    }
+}
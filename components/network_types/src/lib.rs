@@ -14,8 +14,25 @@ use futures::stream::Stream;
 use http::HeaderMap;
 use serde::{Deserialize, Serialize};
 use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::Instant;
 use url::Url;
 
+mod builder;
+mod content_range;
+mod multipart;
+mod origin;
+pub mod public_suffix;
+mod server_timing;
+
+pub use builder::NetworkRequestBuilder;
+pub use content_range::ContentRange;
+pub use multipart::parse_multipart;
+pub use origin::Origin;
+pub use server_timing::ServerTimingEntry;
+
 /// HTTP methods enum
 ///
 /// Represents standard HTTP request methods as defined in RFC 7231.
@@ -42,6 +59,89 @@ pub enum HttpMethod {
     Patch,
 }
 
+impl HttpMethod {
+    /// Canonical upper-case method name, e.g. `"GET"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Patch => "PATCH",
+        }
+    }
+
+    /// Whether the method is safe (read-only) per RFC 9110: GET, HEAD, OPTIONS, TRACE
+    pub fn is_safe(&self) -> bool {
+        matches!(
+            self,
+            HttpMethod::Get | HttpMethod::Head | HttpMethod::Options | HttpMethod::Trace
+        )
+    }
+
+    /// Whether repeating the request has the same effect as sending it once
+    ///
+    /// All safe methods plus PUT and DELETE.
+    pub fn is_idempotent(&self) -> bool {
+        self.is_safe() || matches!(self, HttpMethod::Put | HttpMethod::Delete)
+    }
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for HttpMethod {
+    type Err = NetworkError;
+
+    /// Parse a method name, ignoring ASCII case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const METHODS: [HttpMethod; 9] = [
+            HttpMethod::Get,
+            HttpMethod::Head,
+            HttpMethod::Post,
+            HttpMethod::Put,
+            HttpMethod::Delete,
+            HttpMethod::Connect,
+            HttpMethod::Options,
+            HttpMethod::Trace,
+            HttpMethod::Patch,
+        ];
+
+        METHODS
+            .into_iter()
+            .find(|method| method.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| {
+                NetworkError::new(
+                    NetworkErrorKind::Other,
+                    format!("Unsupported HTTP method: {}", s),
+                )
+            })
+    }
+}
+
+impl From<HttpMethod> for http::Method {
+    fn from(method: HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => http::Method::GET,
+            HttpMethod::Head => http::Method::HEAD,
+            HttpMethod::Post => http::Method::POST,
+            HttpMethod::Put => http::Method::PUT,
+            HttpMethod::Delete => http::Method::DELETE,
+            HttpMethod::Connect => http::Method::CONNECT,
+            HttpMethod::Options => http::Method::OPTIONS,
+            HttpMethod::Trace => http::Method::TRACE,
+            HttpMethod::Patch => http::Method::PATCH,
+        }
+    }
+}
+
 /// Request modes for CORS handling
 ///
 /// Controls how the request interacts with CORS (Cross-Origin Resource Sharing) rules.
@@ -162,6 +262,20 @@ pub enum ResponseType {
     OpaqueRedirect,
 }
 
+/// Where a response was obtained from
+///
+/// Distinguishes responses served by the network from those served by the HTTP cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+pub enum ResponseSource {
+    /// Fetched from the network (default)
+    #[default]
+    Network,
+    /// Served from the HTTP cache without contacting the origin
+    Cache,
+    /// Served from the HTTP cache after the origin answered `304 Not Modified`
+    RevalidatedNotModified,
+}
+
 /// Request body content types
 ///
 /// Represents different formats a request body can take.
@@ -202,6 +316,30 @@ impl Clone for RequestBody {
     }
 }
 
+impl From<Vec<u8>> for RequestBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        RequestBody::Bytes(bytes)
+    }
+}
+
+impl From<String> for RequestBody {
+    fn from(text: String) -> Self {
+        RequestBody::Text(text)
+    }
+}
+
+impl From<&str> for RequestBody {
+    fn from(text: &str) -> Self {
+        RequestBody::Text(text.to_string())
+    }
+}
+
+impl From<FormData> for RequestBody {
+    fn from(data: FormData) -> Self {
+        RequestBody::FormData(data)
+    }
+}
+
 /// Response body content types
 ///
 /// Represents different formats a response body can take.
@@ -243,17 +381,36 @@ pub struct FormData {
 /// Allows sending request body as a stream of chunks.
 pub type BodyStream = Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send + Sync>>;
 
-/// Abort signal for cancelling requests
+/// Controller used to abort in-flight requests
+///
+/// Hands out [`AbortToken`]s that are attached to requests via
+/// `NetworkRequest::signal`. Calling [`AbortController::abort`] from any task
+/// wakes every pending [`AbortToken::aborted`] future immediately.
+#[derive(Debug, Clone)]
+pub struct AbortController {
+    sender: Arc<watch::Sender<Option<String>>>,
+}
+
+/// Cancellation token observed by in-flight requests
 ///
-/// Allows requests to be cancelled mid-flight.
+/// Cheap to clone and safe to move into other tasks. A token whose controller
+/// has been dropped without aborting is never aborted, unless it was made by
+/// [`AbortToken::timeout`].
 #[derive(Debug, Clone)]
-pub struct AbortSignal {
-    /// Whether the request has been aborted
-    pub aborted: bool,
-    /// Reason for abortion (if any)
-    pub reason: Option<String>,
+pub struct AbortToken {
+    receiver: watch::Receiver<Option<String>>,
+    /// When a [`timeout`](AbortToken::timeout) token aborts on its own
+    deadline: Option<Instant>,
 }
 
+/// Reason reported by an [`AbortToken::timeout`] token once its time is up
+pub const TIMEOUT_REASON: &str = "timeout";
+
+/// Abort signal for cancelling requests
+///
+/// Name used by the Fetch standard for the token carried by a request.
+pub type AbortSignal = AbortToken;
+
 /// Window identifier for associating requests with browsing contexts
 ///
 /// Links requests to specific browser windows/tabs.
@@ -323,6 +480,9 @@ pub struct ResourceTiming {
     pub encoded_body_size: u64,
     /// Decoded body size in bytes (uncompressed)
     pub decoded_body_size: u64,
+    /// Metrics the server reported in `Server-Timing` headers
+    #[serde(default)]
+    pub server_timing: Vec<ServerTimingEntry>,
 }
 
 /// Network request structure
@@ -356,9 +516,9 @@ pub struct NetworkRequest {
     pub integrity: Option<String>,
     /// Keep connection alive after completion
     pub keepalive: bool,
-    /// Abort signal for cancellation (if any)
+    /// Abort token for cancellation (if any)
     #[serde(skip)]
-    pub signal: Option<AbortSignal>,
+    pub signal: Option<AbortToken>,
     /// Request priority
     pub priority: RequestPriority,
     /// Associated window ID (if any)
@@ -386,6 +546,45 @@ pub struct NetworkResponse {
     pub type_: ResponseType,
     /// Resource timing information
     pub timing: ResourceTiming,
+    /// Trailer headers sent after the body (chunked or HTTP/2 trailers)
+    ///
+    /// `None` when the response carried no trailers or the body is still streaming.
+    pub trailers: Option<HeaderMap>,
+    /// Whether the response came from the network or the HTTP cache
+    pub source: ResponseSource,
+}
+
+impl NetworkResponse {
+    /// Create a network error response for `url`
+    ///
+    /// Per the Fetch Standard a network error has type `Error`, status 0, an
+    /// empty status message, no headers and no body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use network_types::{NetworkResponse, ResponseType};
+    /// use url::Url;
+    ///
+    /// let response = NetworkResponse::network_error(Url::parse("https://example.com/").unwrap());
+    ///
+    /// assert_eq!(response.status, 0);
+    /// assert_eq!(response.type_, ResponseType::Error);
+    /// ```
+    pub fn network_error(url: Url) -> Self {
+        Self {
+            url,
+            status: 0,
+            status_text: String::new(),
+            headers: HeaderMap::new(),
+            body: ResponseBody::Empty,
+            redirected: false,
+            type_: ResponseType::Error,
+            timing: ResourceTiming::default(),
+            trailers: None,
+            source: ResponseSource::Network,
+        }
+    }
 }
 
 impl Default for ResourceTiming {
@@ -406,6 +605,7 @@ impl Default for ResourceTiming {
             transfer_size: 0,
             encoded_body_size: 0,
             decoded_body_size: 0,
+            server_timing: Vec::new(),
         }
     }
 }
@@ -442,33 +642,110 @@ impl Default for FormData {
     }
 }
 
-impl AbortSignal {
-    /// Create a new abort signal
+impl AbortController {
+    /// Create a new, not yet aborted controller
     pub fn new() -> Self {
+        let (sender, _) = watch::channel(None);
         Self {
-            aborted: false,
-            reason: None,
+            sender: Arc::new(sender),
         }
     }
 
-    /// Check if aborted
-    pub fn is_aborted(&self) -> bool {
-        self.aborted
+    /// Get a token that observes this controller
+    pub fn token(&self) -> AbortToken {
+        AbortToken {
+            receiver: self.sender.subscribe(),
+            deadline: None,
+        }
     }
 
     /// Abort with a reason
-    pub fn abort(&mut self, reason: String) {
-        self.aborted = true;
-        self.reason = Some(reason);
+    ///
+    /// Only the first call has an effect; later reasons are ignored.
+    pub fn abort(&self, reason: impl Into<String>) {
+        let reason = reason.into();
+        self.sender.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(reason);
+            true
+        });
+    }
+
+    /// Check if aborted
+    pub fn is_aborted(&self) -> bool {
+        self.sender.borrow().is_some()
     }
 }
 
-impl Default for AbortSignal {
+impl Default for AbortController {
     fn default() -> Self {
         Self::new()
     }
 }
 
+impl AbortToken {
+    /// Create a token that aborts on its own after `duration`
+    ///
+    /// Mirrors `AbortSignal.timeout()`: once the time has passed the token
+    /// reports itself aborted with the reason [`TIMEOUT_REASON`], and requests
+    /// observing it fail with an abort error. The clock starts now, whether
+    /// or not a runtime is running. A duration too long to represent, such as
+    /// `Duration::MAX`, never times out.
+    pub fn timeout(duration: Duration) -> Self {
+        let (_, receiver) = watch::channel(None);
+        Self {
+            receiver,
+            deadline: Instant::now().checked_add(duration),
+        }
+    }
+
+    /// Check if aborted
+    pub fn is_aborted(&self) -> bool {
+        self.reason().is_some()
+    }
+
+    /// Reason given when aborting (if aborted)
+    pub fn reason(&self) -> Option<String> {
+        self.receiver.borrow().clone().or_else(|| {
+            self.deadline
+                .filter(|deadline| Instant::now() >= *deadline)
+                .map(|_| TIMEOUT_REASON.to_string())
+        })
+    }
+
+    /// Wait until the token is aborted
+    ///
+    /// Resolves immediately if already aborted and never resolves if the
+    /// controller is dropped without aborting, so it can be used as a
+    /// `tokio::select!` branch alongside the request future. A timeout
+    /// token resolves when its time is up.
+    pub async fn aborted(&self) {
+        let mut receiver = self.receiver.clone();
+        let deadline = async {
+            match self.deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline).await,
+                None => futures::future::pending().await,
+            }
+        };
+        let controller = async {
+            loop {
+                if receiver.borrow_and_update().is_some() {
+                    return;
+                }
+                if receiver.changed().await.is_err() {
+                    futures::future::pending::<()>().await;
+                }
+            }
+        };
+        tokio::select! {
+            _ = controller => {}
+            _ = deadline => {}
+        }
+    }
+}
+
 impl NetworkError {
     /// Create a new network error
     pub fn new(kind: NetworkErrorKind, message: String) -> Self {
@@ -0,0 +1,235 @@
+//! multipart/form-data encoding and parsing (RFC 7578)
+
+use crate::{FormData, NetworkError, NetworkErrorKind};
+
+/// Content type of file parts that don't declare one (RFC 7578, section 4.4)
+const DEFAULT_PART_CONTENT_TYPE: &str = "text/plain";
+
+impl FormData {
+    /// `Content-Type` header value for a body encoded with `boundary`
+    pub fn content_type(boundary: &str) -> String {
+        format!("multipart/form-data; boundary={}", boundary)
+    }
+
+    /// Encode as a multipart/form-data body delimited by `boundary`
+    ///
+    /// Fields are written before files. The boundary must not occur in any
+    /// value; quotes and line breaks in names are percent-encoded as browsers do.
+    pub fn encode(&self, boundary: &str) -> Vec<u8> {
+        let mut body = Vec::new();
+
+        for (name, value) in &self.fields {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n\r\n",
+                    escape_name(name)
+                )
+                .as_bytes(),
+            );
+            body.extend_from_slice(value.as_bytes());
+            body.extend_from_slice(b"\r\n");
+        }
+
+        for (name, filename, content_type, data) in &self.files {
+            body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
+            body.extend_from_slice(
+                format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\n",
+                    escape_name(name),
+                    escape_name(filename)
+                )
+                .as_bytes(),
+            );
+            body.extend_from_slice(format!("Content-Type: {}\r\n\r\n", content_type).as_bytes());
+            body.extend_from_slice(data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+        body
+    }
+}
+
+/// Parse a multipart/form-data body
+///
+/// The boundary is taken from `content_type`. Parts with a `filename` in their
+/// `Content-Disposition` become files (defaulting to a `text/plain` content
+/// type), all others become fields; field values that are not valid UTF-8 are
+/// converted lossily. A preamble before the first boundary and an epilogue
+/// after the closing `--boundary--` are ignored.
+///
+/// # Errors
+/// Returns an error if the content type is not multipart/form-data with a
+/// boundary, the closing boundary is missing, or a part lacks a
+/// `Content-Disposition: form-data` header with a name.
+pub fn parse_multipart(content_type: &str, body: &[u8]) -> Result<FormData, NetworkError> {
+    let boundary = extract_boundary(content_type)?;
+    let delimiter = format!("--{}", boundary).into_bytes();
+    let mut next_delimiter = b"\r\n".to_vec();
+    next_delimiter.extend_from_slice(&delimiter);
+
+    // The first delimiter may follow a preamble, but must start a line
+    let mut pos = if body.starts_with(&delimiter) {
+        delimiter.len()
+    } else {
+        find(body, &next_delimiter)
+            .map(|at| at + next_delimiter.len())
+            .ok_or_else(|| invalid("missing opening boundary"))?
+    };
+
+    let mut form = FormData::new();
+    loop {
+        let rest = &body[pos..];
+        if rest.starts_with(b"--") {
+            return Ok(form);
+        }
+
+        // Skip transport padding up to the end of the delimiter line
+        let line_end = find(rest, b"\r\n").ok_or_else(|| invalid("missing closing boundary"))?;
+        if !rest[..line_end].iter().all(|b| *b == b' ' || *b == b'\t') {
+            return Err(invalid("unexpected data after boundary"));
+        }
+        let part_start = pos + line_end + 2;
+
+        let part_len = find(&body[part_start..], &next_delimiter)
+            .ok_or_else(|| invalid("missing closing boundary"))?;
+        parse_part(&body[part_start..part_start + part_len], &mut form)?;
+        pos = part_start + part_len + next_delimiter.len();
+    }
+}
+
+/// Add one body part to `form`
+fn parse_part(part: &[u8], form: &mut FormData) -> Result<(), NetworkError> {
+    let (head, content) = if let Some(content) = part.strip_prefix(b"\r\n") {
+        (&[][..], content)
+    } else {
+        let head_end =
+            find(part, b"\r\n\r\n").ok_or_else(|| invalid("unterminated part headers"))?;
+        (&part[..head_end], &part[head_end + 4..])
+    };
+    let head = String::from_utf8_lossy(head);
+
+    let mut disposition = None;
+    let mut part_type = None;
+    for line in head.split("\r\n") {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.trim().eq_ignore_ascii_case("content-disposition") {
+            disposition = Some(value.to_string());
+        } else if name.trim().eq_ignore_ascii_case("content-type") {
+            part_type = Some(value.to_string());
+        }
+    }
+
+    let disposition = disposition.ok_or_else(|| invalid("part without Content-Disposition"))?;
+    let mut params = disposition.splitn(2, ';');
+    if !params
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .eq_ignore_ascii_case("form-data")
+    {
+        return Err(invalid("Content-Disposition is not form-data"));
+    }
+    let params = parse_params(params.next().unwrap_or_default());
+    let name = param(&params, "name").ok_or_else(|| invalid("part without a name"))?;
+
+    match param(&params, "filename") {
+        Some(filename) => form.add_file(
+            name,
+            filename,
+            part_type.unwrap_or_else(|| DEFAULT_PART_CONTENT_TYPE.to_string()),
+            content.to_vec(),
+        ),
+        None => form.add_field(name, String::from_utf8_lossy(content).into_owned()),
+    }
+    Ok(())
+}
+
+/// Extract the boundary parameter of a multipart/form-data content type
+fn extract_boundary(content_type: &str) -> Result<String, NetworkError> {
+    let mut parts = content_type.splitn(2, ';');
+    let mime = parts.next().unwrap_or_default().trim();
+    if !mime.eq_ignore_ascii_case("multipart/form-data") {
+        return Err(invalid(&format!(
+            "not a multipart/form-data content type: {}",
+            mime
+        )));
+    }
+
+    let params = parse_params(parts.next().unwrap_or_default());
+    match param(&params, "boundary") {
+        Some(boundary) if (1..=70).contains(&boundary.len()) => Ok(boundary),
+        Some(_) => Err(invalid("boundary must be 1 to 70 characters")),
+        None => Err(invalid("missing boundary parameter")),
+    }
+}
+
+/// Parse `; key=value; key="quoted value"` header parameters
+///
+/// Keys are lowercased. Quoted values may contain `;` and backslash escapes.
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    loop {
+        while chars.peek().is_some_and(|c| *c == ';' || c.is_whitespace()) {
+            chars.next();
+        }
+        let key: String = chars.by_ref().take_while(|c| *c != '=').collect();
+        if key.is_empty() {
+            return params;
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    c => value.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ';') {
+                value.push(c);
+            }
+            value = value.trim_end().to_string();
+        }
+
+        params.push((key.trim().to_ascii_lowercase(), value));
+    }
+}
+
+/// Look up a parameter by (lowercase) name
+fn param(params: &[(String, String)], name: &str) -> Option<String> {
+    params
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// Percent-encode the characters that can't appear in a quoted name
+fn escape_name(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Position of the first occurrence of `needle` in `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn invalid(reason: &str) -> NetworkError {
+    NetworkError::new(
+        NetworkErrorKind::Other,
+        format!("Invalid multipart/form-data body: {}", reason),
+    )
+}
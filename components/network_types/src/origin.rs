@@ -0,0 +1,122 @@
+//! Origins and same-origin / same-site comparisons (HTML Standard)
+
+use crate::public_suffix;
+use std::fmt;
+use url::{Host, Url};
+
+/// Origin of a URL
+///
+/// A tuple origin (scheme, host, port) for URLs with a network host, or an
+/// opaque origin for URLs like `data:` that have none. Each opaque origin
+/// is only equal to itself, so two `data:` URLs are never same-origin.
+///
+/// # Examples
+///
+/// ```
+/// use network_types::Origin;
+/// use url::Url;
+///
+/// let page = Origin::from_url(&Url::parse("https://www.example.com/page").unwrap());
+/// let api = Origin::from_url(&Url::parse("https://api.example.com/data").unwrap());
+///
+/// assert!(!page.is_same_origin(&api));
+/// assert!(page.is_same_site(&api));
+/// assert_eq!(page.ascii_serialization(), "https://www.example.com");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Origin(url::Origin);
+
+impl Origin {
+    /// Compute the origin of `url`
+    ///
+    /// Default ports are filled in, so `http://example.com` and
+    /// `http://example.com:80` have the same origin. A `blob:` URL has the
+    /// origin of the URL it wraps.
+    pub fn from_url(url: &Url) -> Self {
+        Self(url.origin())
+    }
+
+    /// Whether this is an opaque origin
+    pub fn is_opaque(&self) -> bool {
+        !self.0.is_tuple()
+    }
+
+    /// Scheme of a tuple origin
+    pub fn scheme(&self) -> Option<&str> {
+        match &self.0 {
+            url::Origin::Tuple(scheme, _, _) => Some(scheme),
+            url::Origin::Opaque(_) => None,
+        }
+    }
+
+    /// Host of a tuple origin
+    pub fn host(&self) -> Option<&Host<String>> {
+        match &self.0 {
+            url::Origin::Tuple(_, host, _) => Some(host),
+            url::Origin::Opaque(_) => None,
+        }
+    }
+
+    /// Port of a tuple origin, with the scheme's default filled in
+    pub fn port(&self) -> Option<u16> {
+        match &self.0 {
+            url::Origin::Tuple(_, _, port) => Some(*port),
+            url::Origin::Opaque(_) => None,
+        }
+    }
+
+    /// Whether both origins have the same scheme, host and port
+    ///
+    /// An opaque origin is only same-origin with itself.
+    pub fn is_same_origin(&self, other: &Origin) -> bool {
+        self == other
+    }
+
+    /// Whether both origins are same-site
+    ///
+    /// Tuple origins are same-site when their schemes match and their hosts
+    /// have the same registrable domain (per the public suffix list), so
+    /// `a.example.com` and `b.example.com` are same-site but `example.com`
+    /// and `example.org` aren't. Ports are ignored. IP addresses and hosts
+    /// that are public suffixes are only same-site with the same host. An
+    /// opaque origin is only same-site with itself.
+    pub fn is_same_site(&self, other: &Origin) -> bool {
+        match (&self.0, &other.0) {
+            (
+                url::Origin::Tuple(scheme, host, _),
+                url::Origin::Tuple(other_scheme, other_host, _),
+            ) => scheme == other_scheme && site_of(host) == site_of(other_host),
+            _ => self == other,
+        }
+    }
+
+    /// ASCII serialization, such as `https://example.com:8443`
+    ///
+    /// Default ports are omitted, and opaque origins serialize as `null`.
+    pub fn ascii_serialization(&self) -> String {
+        self.0.ascii_serialization()
+    }
+}
+
+impl From<&Url> for Origin {
+    fn from(url: &Url) -> Self {
+        Self::from_url(url)
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.ascii_serialization())
+    }
+}
+
+/// The site of a host: its registrable domain, or the host itself for IP
+/// addresses and hosts that are public suffixes
+fn site_of(host: &Host<String>) -> String {
+    match host {
+        Host::Domain(domain) => {
+            public_suffix::registrable_domain(domain).unwrap_or_else(|| domain.to_ascii_lowercase())
+        }
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,43 @@
+//! Public suffix list support
+//!
+//! Bundles a snapshot of the Mozilla Public Suffix List (https://publicsuffix.org)
+//! used to compute registrable domains for same-site checks and to reject
+//! cookies scoped to public suffixes such as `com` or `co.uk`.
+
+use publicsuffix::{List, Psl};
+use std::sync::OnceLock;
+
+/// Bundled copy of `public_suffix_list.dat`
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../data/public_suffix_list.dat");
+
+/// Get the parsed public suffix list
+///
+/// The list is parsed once on first use.
+pub fn list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        PUBLIC_SUFFIX_LIST
+            .parse()
+            .expect("bundled public suffix list should be valid")
+    })
+}
+
+/// Get the registrable domain (eTLD+1) of a host
+///
+/// Returns `None` if the host is itself a public suffix or not a valid domain.
+///
+/// # Examples
+///
+/// ```
+/// use network_types::public_suffix::registrable_domain;
+///
+/// assert_eq!(registrable_domain("www.example.co.uk").as_deref(), Some("example.co.uk"));
+/// assert_eq!(registrable_domain("co.uk"), None);
+/// ```
+pub fn registrable_domain(host: &str) -> Option<String> {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    let domain = list().domain(host.as_bytes())?;
+    std::str::from_utf8(domain.as_bytes())
+        .ok()
+        .map(str::to_string)
+}
@@ -0,0 +1,120 @@
+//! `Server-Timing` header parsing (W3C Server Timing)
+
+use crate::ResourceTiming;
+use serde::{Deserialize, Serialize};
+
+/// One metric reported by a server in its `Server-Timing` header
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerTimingEntry {
+    /// Metric name
+    pub name: String,
+    /// Duration in milliseconds from the `dur` parameter, 0 when absent
+    pub duration_ms: f64,
+    /// Description from the `desc` parameter, empty when absent
+    pub description: String,
+}
+
+impl ResourceTiming {
+    /// Parse a `Server-Timing` header value into its metrics
+    ///
+    /// Metrics are returned in header order. Only the first `dur` and `desc`
+    /// parameter of a metric counts, unknown parameters are ignored, and a
+    /// `dur` that isn't a number is treated as 0. Entries without a valid
+    /// metric name are skipped.
+    ///
+    /// Multiple `Server-Timing` header lines can be parsed by joining their
+    /// values with `,`.
+    pub fn parse_server_timing(header: &str) -> Vec<ServerTimingEntry> {
+        split_unquoted(header, ',')
+            .into_iter()
+            .filter_map(parse_metric)
+            .collect()
+    }
+}
+
+/// Parse `name *( OWS ";" OWS param )`
+fn parse_metric(metric: &str) -> Option<ServerTimingEntry> {
+    let mut parts = split_unquoted(metric, ';').into_iter();
+    let name = parts.next()?.trim();
+    if !is_token(name) {
+        return None;
+    }
+
+    let mut duration_ms = None;
+    let mut description = None;
+    for param in parts {
+        let (param_name, value) = match param.split_once('=') {
+            Some((param_name, value)) => (param_name.trim(), unquote(value.trim())),
+            None => (param.trim(), String::new()),
+        };
+        if param_name.eq_ignore_ascii_case("dur") && duration_ms.is_none() {
+            duration_ms = Some(
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|dur| dur.is_finite())
+                    .unwrap_or(0.0),
+            );
+        } else if param_name.eq_ignore_ascii_case("desc") && description.is_none() {
+            description = Some(value);
+        }
+    }
+
+    Some(ServerTimingEntry {
+        name: name.to_string(),
+        duration_ms: duration_ms.unwrap_or(0.0),
+        description: description.unwrap_or_default(),
+    })
+}
+
+/// Split at `separator`, except inside quoted strings
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if in_quotes && c == '\\' {
+            escaped = true;
+        } else if c == '"' {
+            in_quotes = !in_quotes;
+        } else if c == separator && !in_quotes {
+            parts.push(&value[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Value of a token or quoted-string, with quoted-pairs unescaped
+fn unquote(value: &str) -> String {
+    let Some(inner) = value.strip_prefix('"') else {
+        return value.to_string();
+    };
+    let inner = inner.strip_suffix('"').unwrap_or(inner);
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                unquoted.push(escaped);
+            }
+        } else {
+            unquoted.push(c);
+        }
+    }
+    unquoted
+}
+
+/// Whether `value` is a non-empty RFC 9110 token
+fn is_token(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
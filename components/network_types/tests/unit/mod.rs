@@ -1,15 +1,21 @@
 // Unit tests for network_types
 
+mod test_abort;
 mod test_cache_mode;
+mod test_content_range;
 mod test_credentials_mode;
 mod test_http_method;
+mod test_multipart;
 mod test_network_request;
 mod test_network_response;
+mod test_origin;
 mod test_redirect_mode;
 mod test_referrer_policy;
 mod test_request_body;
+mod test_request_builder;
 mod test_request_mode;
 mod test_request_priority;
 mod test_resource_timing;
 mod test_response_body;
 mod test_response_type;
+mod test_server_timing;
@@ -0,0 +1,118 @@
+use network_types::{AbortController, AbortToken, TIMEOUT_REASON};
+use std::time::Duration;
+
+#[test]
+fn test_new_controller_is_not_aborted() {
+    // Given a new controller
+    let controller = AbortController::new();
+    let token = controller.token();
+
+    // Then neither it nor its token is aborted
+    assert!(!controller.is_aborted());
+    assert!(!token.is_aborted());
+    assert_eq!(token.reason(), None);
+}
+
+#[test]
+fn test_abort_is_visible_to_all_tokens() {
+    // Given a controller with two tokens, one cloned
+    let controller = AbortController::new();
+    let token = controller.token();
+    let cloned: AbortToken = token.clone();
+
+    // When aborting
+    controller.abort("user cancelled");
+
+    // Then both tokens observe it with the reason
+    assert!(controller.is_aborted());
+    assert!(token.is_aborted());
+    assert_eq!(cloned.reason(), Some("user cancelled".to_string()));
+}
+
+#[test]
+fn test_first_abort_reason_wins() {
+    let controller = AbortController::new();
+    let token = controller.token();
+
+    controller.abort("first");
+    controller.abort("second");
+
+    assert_eq!(token.reason(), Some("first".to_string()));
+}
+
+#[tokio::test]
+async fn test_aborted_resolves_when_aborted_from_another_task() {
+    // Given a token waiting for abort
+    let controller = AbortController::new();
+    let token = controller.token();
+
+    // When another task aborts
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        controller.abort("timeout");
+    });
+
+    // Then the waiter wakes promptly
+    tokio::time::timeout(Duration::from_secs(1), token.aborted())
+        .await
+        .expect("aborted() should resolve after abort");
+    assert_eq!(token.reason(), Some("timeout".to_string()));
+}
+
+#[tokio::test]
+async fn test_aborted_resolves_immediately_if_already_aborted() {
+    let controller = AbortController::new();
+    controller.abort("early");
+
+    tokio::time::timeout(Duration::from_millis(100), controller.token().aborted())
+        .await
+        .expect("aborted() should resolve immediately");
+}
+
+#[tokio::test]
+async fn test_dropped_controller_never_aborts() {
+    // Given a token whose controller is dropped without aborting
+    let token = AbortController::new().token();
+
+    // Then waiting on it does not resolve
+    let result = tokio::time::timeout(Duration::from_millis(50), token.aborted()).await;
+    assert!(result.is_err());
+    assert!(!token.is_aborted());
+}
+
+#[tokio::test]
+async fn test_timeout_token_aborts_on_its_own() {
+    // Given a token that times out after 50ms
+    let token = AbortToken::timeout(Duration::from_millis(50));
+    assert!(!token.is_aborted());
+    assert_eq!(token.reason(), None);
+
+    // When waiting on it
+    tokio::time::timeout(Duration::from_secs(2), token.aborted())
+        .await
+        .expect("timeout token should abort by itself");
+
+    // Then it reports the timeout reason, as do its clones
+    assert!(token.is_aborted());
+    assert_eq!(token.reason().as_deref(), Some(TIMEOUT_REASON));
+    assert!(token.clone().is_aborted());
+}
+
+#[tokio::test]
+async fn test_timeout_token_is_pending_until_deadline() {
+    let token = AbortToken::timeout(Duration::from_secs(60));
+
+    let result = tokio::time::timeout(Duration::from_millis(50), token.aborted()).await;
+    assert!(result.is_err());
+    assert!(!token.is_aborted());
+}
+
+#[tokio::test]
+async fn test_timeout_token_with_max_duration_never_aborts() {
+    let token = AbortToken::timeout(Duration::MAX);
+
+    let result = tokio::time::timeout(Duration::from_millis(50), token.aborted()).await;
+    assert!(result.is_err());
+    assert!(!token.is_aborted());
+    assert_eq!(token.reason(), None);
+}
@@ -0,0 +1,66 @@
+//! Unit tests for Content-Range parsing
+
+use http::{HeaderMap, HeaderValue};
+use network_types::{ContentRange, NetworkResponse};
+use url::Url;
+
+#[test]
+fn test_parse_byte_range() {
+    let range = ContentRange::parse("bytes 0-499/1234").unwrap();
+    assert_eq!(range.first, 0);
+    assert_eq!(range.last, 499);
+    assert_eq!(range.complete_length, Some(1234));
+    assert_eq!(range.len(), 500);
+}
+
+#[test]
+fn test_parse_unknown_complete_length() {
+    let range = ContentRange::parse("bytes 10-19/*").unwrap();
+    assert_eq!((range.first, range.last), (10, 19));
+    assert_eq!(range.complete_length, None);
+}
+
+#[test]
+fn test_parse_single_byte_and_unit_case() {
+    let range = ContentRange::parse("Bytes 7-7/8").unwrap();
+    assert_eq!(range.len(), 1);
+}
+
+#[test]
+fn test_parse_rejects_invalid_ranges() {
+    // Unsatisfied-range form sent with 416
+    assert_eq!(ContentRange::parse("bytes */1234"), None);
+    // Other units
+    assert_eq!(ContentRange::parse("items 0-4/10"), None);
+    // Last before first, or past the complete length
+    assert_eq!(ContentRange::parse("bytes 20-10/100"), None);
+    assert_eq!(ContentRange::parse("bytes 0-100/100"), None);
+    // A range whose length doesn't fit in a u64
+    assert_eq!(ContentRange::parse("bytes 0-18446744073709551615/*"), None);
+    // Malformed
+    assert_eq!(ContentRange::parse("bytes 0-/100"), None);
+    assert_eq!(ContentRange::parse("bytes 0-9"), None);
+    assert_eq!(ContentRange::parse(""), None);
+}
+
+#[test]
+fn test_response_content_range() {
+    let mut response = NetworkResponse::network_error(Url::parse("https://example.com/").unwrap());
+    assert_eq!(response.content_range(), None);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        http::header::CONTENT_RANGE,
+        HeaderValue::from_static("bytes 100-199/1000"),
+    );
+    response.headers = headers;
+
+    assert_eq!(
+        response.content_range(),
+        Some(ContentRange {
+            first: 100,
+            last: 199,
+            complete_length: Some(1000),
+        })
+    );
+}
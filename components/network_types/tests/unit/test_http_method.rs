@@ -89,3 +89,91 @@ fn test_http_method_all_variants() {
         }
     }
 }
+
+#[test]
+fn test_http_method_as_str() {
+    assert_eq!(HttpMethod::Get.as_str(), "GET");
+    assert_eq!(HttpMethod::Delete.as_str(), "DELETE");
+    assert_eq!(HttpMethod::Patch.to_string(), "PATCH");
+}
+
+#[test]
+fn test_http_method_parse_is_case_insensitive() {
+    let cases = [
+        ("GET", HttpMethod::Get),
+        ("get", HttpMethod::Get),
+        ("Head", HttpMethod::Head),
+        ("post", HttpMethod::Post),
+        ("pUt", HttpMethod::Put),
+        ("delete", HttpMethod::Delete),
+        ("connect", HttpMethod::Connect),
+        ("options", HttpMethod::Options),
+        ("trace", HttpMethod::Trace),
+        ("patch", HttpMethod::Patch),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(input.parse::<HttpMethod>().unwrap(), expected, "{}", input);
+    }
+}
+
+#[test]
+fn test_http_method_parse_round_trips() {
+    for method in [
+        HttpMethod::Get,
+        HttpMethod::Head,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Delete,
+        HttpMethod::Connect,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+        HttpMethod::Patch,
+    ] {
+        assert_eq!(method.as_str().parse::<HttpMethod>().unwrap(), method);
+        assert_eq!(http::Method::from(method).as_str(), method.as_str());
+    }
+}
+
+#[test]
+fn test_http_method_parse_rejects_unknown() {
+    assert!("PROPFIND".parse::<HttpMethod>().is_err());
+    assert!("".parse::<HttpMethod>().is_err());
+    assert!(" GET".parse::<HttpMethod>().is_err());
+}
+
+#[test]
+fn test_http_method_safe_methods() {
+    let safe = [
+        HttpMethod::Get,
+        HttpMethod::Head,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+    ];
+    let unsafe_methods = [
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Delete,
+        HttpMethod::Connect,
+        HttpMethod::Patch,
+    ];
+
+    assert!(safe.iter().all(|m| m.is_safe()));
+    assert!(unsafe_methods.iter().all(|m| !m.is_safe()));
+}
+
+#[test]
+fn test_http_method_idempotent_methods() {
+    let idempotent = [
+        HttpMethod::Get,
+        HttpMethod::Head,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+        HttpMethod::Put,
+        HttpMethod::Delete,
+    ];
+    let non_idempotent = [HttpMethod::Post, HttpMethod::Connect, HttpMethod::Patch];
+
+    assert!(idempotent.iter().all(|m| m.is_idempotent()));
+    assert!(non_idempotent.iter().all(|m| !m.is_idempotent()));
+}
@@ -0,0 +1,101 @@
+use network_types::{parse_multipart, FormData};
+
+const BOUNDARY: &str = "----corten-boundary-7MA4YWxk";
+
+fn sample_form() -> FormData {
+    let mut form = FormData::new();
+    form.add_field("username".to_string(), "alice".to_string());
+    form.add_field("comment".to_string(), "line one\r\nline two".to_string());
+    form.add_field("username".to_string(), "bob".to_string());
+    form.add_file(
+        "avatar".to_string(),
+        "me.png".to_string(),
+        "image/png".to_string(),
+        vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x00, 0xff],
+    );
+    form.add_file(
+        "notes".to_string(),
+        "empty.txt".to_string(),
+        "text/plain".to_string(),
+        Vec::new(),
+    );
+    form
+}
+
+#[test]
+fn test_form_data_round_trips_through_encode_and_parse() {
+    let form = sample_form();
+    let body = form.encode(BOUNDARY);
+
+    let parsed = parse_multipart(&FormData::content_type(BOUNDARY), &body).unwrap();
+
+    assert_eq!(parsed.fields, form.fields);
+    assert_eq!(parsed.files, form.files);
+}
+
+#[test]
+fn test_empty_form_data_round_trips() {
+    let body = FormData::new().encode(BOUNDARY);
+    let parsed = parse_multipart(&FormData::content_type(BOUNDARY), &body).unwrap();
+
+    assert!(parsed.fields.is_empty());
+    assert!(parsed.files.is_empty());
+}
+
+#[test]
+fn test_parse_quoted_boundary_preamble_and_epilogue() {
+    let body = b"This is the preamble.\r\n\
+        --abc\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\
+        \r\n\
+        Hello\r\n\
+        --abc\r\n\
+        content-disposition: FORM-DATA; name=upload; filename=\"a;b.txt\"\r\n\
+        \r\n\
+        file body\r\n\
+        --abc--\r\n\
+        epilogue";
+
+    let parsed =
+        parse_multipart("Multipart/Form-Data; charset=utf-8; boundary=\"abc\"", body).unwrap();
+
+    assert_eq!(
+        parsed.fields,
+        vec![("title".to_string(), "Hello".to_string())]
+    );
+    assert_eq!(
+        parsed.files,
+        vec![(
+            "upload".to_string(),
+            "a;b.txt".to_string(),
+            "text/plain".to_string(),
+            b"file body".to_vec()
+        )]
+    );
+}
+
+#[test]
+fn test_parse_rejects_wrong_content_type() {
+    let result = parse_multipart("application/json", b"{}");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_rejects_missing_boundary_parameter() {
+    let result = parse_multipart("multipart/form-data", b"--x--\r\n");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_rejects_missing_closing_boundary() {
+    let body = b"--abc\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue";
+    let result = parse_multipart("multipart/form-data; boundary=abc", body);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_parse_rejects_part_without_name() {
+    let body = b"--abc\r\nContent-Disposition: form-data\r\n\r\nvalue\r\n--abc--";
+    let result = parse_multipart("multipart/form-data; boundary=abc", body);
+    assert!(result.is_err());
+}
@@ -1,5 +1,5 @@
 use http::HeaderMap;
-use network_types::{NetworkResponse, ResourceTiming, ResponseBody, ResponseType};
+use network_types::{NetworkResponse, ResourceTiming, ResponseBody, ResponseSource, ResponseType};
 use url::Url;
 
 #[test]
@@ -25,6 +25,7 @@ fn test_network_response_creation() {
         transfer_size: 0,
         encoded_body_size: 0,
         decoded_body_size: 0,
+        server_timing: Vec::new(),
     };
 
     let response = NetworkResponse {
@@ -36,6 +37,8 @@ fn test_network_response_creation() {
         redirected: false,
         type_: ResponseType::Basic,
         timing: timing.clone(),
+        trailers: None,
+        source: ResponseSource::Network,
     };
 
     assert_eq!(response.url, url);
@@ -68,6 +71,7 @@ fn test_network_response_with_body() {
         transfer_size: 1024,
         encoded_body_size: 512,
         decoded_body_size: 768,
+        server_timing: Vec::new(),
     };
 
     let response = NetworkResponse {
@@ -79,6 +83,8 @@ fn test_network_response_with_body() {
         redirected: false,
         type_: ResponseType::Cors,
         timing,
+        trailers: None,
+        source: ResponseSource::Network,
     };
 
     match response.body {
@@ -109,6 +115,7 @@ fn test_network_response_error_status() {
         transfer_size: 0,
         encoded_body_size: 0,
         decoded_body_size: 0,
+        server_timing: Vec::new(),
     };
 
     let response = NetworkResponse {
@@ -120,6 +127,8 @@ fn test_network_response_error_status() {
         redirected: false,
         type_: ResponseType::Error,
         timing,
+        trailers: None,
+        source: ResponseSource::Network,
     };
 
     assert_eq!(response.status, 404);
@@ -149,6 +158,7 @@ fn test_network_response_redirected() {
         transfer_size: 0,
         encoded_body_size: 0,
         decoded_body_size: 0,
+        server_timing: Vec::new(),
     };
 
     let response = NetworkResponse {
@@ -160,6 +170,8 @@ fn test_network_response_redirected() {
         redirected: true,
         type_: ResponseType::Basic,
         timing,
+        trailers: None,
+        source: ResponseSource::Network,
     };
 
     assert_eq!(response.redirected, true);
@@ -188,6 +200,7 @@ fn test_network_response_debug() {
         transfer_size: 0,
         encoded_body_size: 0,
         decoded_body_size: 0,
+        server_timing: Vec::new(),
     };
 
     let response = NetworkResponse {
@@ -199,8 +212,30 @@ fn test_network_response_debug() {
         redirected: false,
         type_: ResponseType::Basic,
         timing,
+        trailers: None,
+        source: ResponseSource::Network,
     };
 
     let debug_str = format!("{:?}", response);
     assert!(debug_str.contains("NetworkResponse"));
 }
+
+#[test]
+fn test_network_error_response_shape() {
+    // Given a URL whose fetch failed
+    // When creating a network error response
+    // Then it should have status 0, type Error, no headers and no body
+    let url = Url::parse("https://example.com/missing").unwrap();
+
+    let response = NetworkResponse::network_error(url.clone());
+
+    assert_eq!(response.url, url);
+    assert_eq!(response.status, 0);
+    assert_eq!(response.status_text, "");
+    assert_eq!(response.type_, ResponseType::Error);
+    assert!(response.headers.is_empty());
+    assert!(matches!(response.body, ResponseBody::Empty));
+    assert!(response.trailers.is_none());
+    assert!(!response.redirected);
+    assert_eq!(response.source, ResponseSource::Network);
+}
@@ -0,0 +1,106 @@
+// Unit tests for Origin
+
+use network_types::Origin;
+use url::Url;
+
+fn origin(url: &str) -> Origin {
+    Origin::from_url(&Url::parse(url).unwrap())
+}
+
+#[test]
+fn test_same_origin_requires_matching_scheme_host_and_port() {
+    let page = origin("https://example.com/page");
+
+    assert!(page.is_same_origin(&origin("https://example.com/other?q=1")));
+    assert!(!page.is_same_origin(&origin("http://example.com/page")));
+    assert!(!page.is_same_origin(&origin("https://www.example.com/page")));
+    assert!(!page.is_same_origin(&origin("https://example.com:8443/page")));
+}
+
+#[test]
+fn test_default_port_is_same_origin_as_implicit_port() {
+    assert!(origin("http://example.com/").is_same_origin(&origin("http://example.com:80/")));
+    assert_eq!(origin("https://example.com:443/").port(), Some(443));
+}
+
+#[test]
+fn test_same_site_ignores_subdomains_and_ports() {
+    let page = origin("https://www.example.com/");
+
+    assert!(page.is_same_site(&origin("https://api.example.com/")));
+    assert!(page.is_same_site(&origin("https://example.com:8443/")));
+    assert!(!page.is_same_site(&origin("https://example.org/")));
+    assert!(!page.is_same_site(&origin("http://www.example.com/")));
+}
+
+#[test]
+fn test_same_site_uses_public_suffix_list() {
+    let page = origin("https://a.example.co.uk/");
+
+    assert!(page.is_same_site(&origin("https://b.example.co.uk/")));
+    assert!(!page.is_same_site(&origin("https://other.co.uk/")));
+}
+
+#[test]
+fn test_ip_hosts_are_only_same_site_with_themselves() {
+    let local = origin("http://127.0.0.1:8080/");
+
+    assert!(local.is_same_site(&origin("http://127.0.0.1:9090/")));
+    assert!(!local.is_same_site(&origin("http://127.0.0.2:8080/")));
+}
+
+#[test]
+fn test_opaque_origins() {
+    let data = origin("data:text/plain,hello");
+
+    assert!(data.is_opaque());
+    assert_eq!(data.scheme(), None);
+    assert_eq!(data.ascii_serialization(), "null");
+    assert!(data.is_same_origin(&data.clone()));
+    assert!(data.is_same_site(&data.clone()));
+
+    let other = origin("data:text/plain,hello");
+    assert!(!data.is_same_origin(&other));
+    assert!(!data.is_same_site(&other));
+    assert!(!data.is_same_site(&origin("https://example.com/")));
+}
+
+#[test]
+fn test_blob_url_has_origin_of_inner_url() {
+    let blob = origin("blob:https://example.com/1b2f");
+
+    assert!(!blob.is_opaque());
+    assert!(blob.is_same_origin(&origin("https://example.com/")));
+}
+
+#[test]
+fn test_ascii_serialization() {
+    assert_eq!(
+        origin("https://Example.COM/path").ascii_serialization(),
+        "https://example.com"
+    );
+    assert_eq!(
+        origin("http://example.com:8080/").ascii_serialization(),
+        "http://example.com:8080"
+    );
+    assert_eq!(
+        origin("http://[::1]:3000/").to_string(),
+        "http://[::1]:3000"
+    );
+}
+
+#[test]
+fn test_registrable_domain() {
+    use network_types::public_suffix::registrable_domain;
+
+    assert_eq!(
+        registrable_domain("www.example.co.uk").as_deref(),
+        Some("example.co.uk")
+    );
+    assert_eq!(
+        registrable_domain("a.b.example.com").as_deref(),
+        Some("example.com")
+    );
+    assert_eq!(registrable_domain("co.uk"), None);
+    assert_eq!(registrable_domain("com"), None);
+}
@@ -0,0 +1,112 @@
+use http::HeaderValue;
+use network_types::{
+    AbortController, CacheMode, CredentialsMode, HttpMethod, NetworkRequest, NetworkRequestBuilder,
+    RedirectMode, ReferrerPolicy, RequestBody, RequestMode, RequestPriority,
+};
+use url::Url;
+
+fn url() -> Url {
+    Url::parse("https://example.com/resource").unwrap()
+}
+
+#[test]
+fn test_builder_defaults() {
+    // Given a builder with only a URL
+    // When building the request
+    let request = NetworkRequest::builder(url()).build();
+
+    // Then every other field has its documented default
+    assert_eq!(request.url, url());
+    assert_eq!(request.method, HttpMethod::Get);
+    assert!(request.headers.is_empty());
+    assert!(request.body.is_none());
+    assert_eq!(request.mode, RequestMode::Cors);
+    assert_eq!(request.credentials, CredentialsMode::SameOrigin);
+    assert_eq!(request.cache, CacheMode::Default);
+    assert_eq!(request.redirect, RedirectMode::Follow);
+    assert_eq!(request.referrer, None);
+    assert_eq!(
+        request.referrer_policy,
+        ReferrerPolicy::StrictOriginWhenCrossOrigin
+    );
+    assert_eq!(request.integrity, None);
+    assert!(!request.keepalive);
+    assert!(request.signal.is_none());
+    assert_eq!(request.priority, RequestPriority::Auto);
+    assert_eq!(request.window, None);
+}
+
+#[test]
+fn test_builder_overrides() {
+    // Given a builder with every field overridden
+    let controller = AbortController::new();
+    let request = NetworkRequestBuilder::new(url())
+        .method(HttpMethod::Put)
+        .header("x-trace", HeaderValue::from_static("a"))
+        .header("x-trace", HeaderValue::from_static("b"))
+        .body(vec![1, 2, 3])
+        .mode(RequestMode::NoCors)
+        .credentials(CredentialsMode::Include)
+        .cache(CacheMode::NoStore)
+        .redirect(RedirectMode::Manual)
+        .referrer("https://example.com/page")
+        .referrer_policy(ReferrerPolicy::NoReferrer)
+        .integrity("sha256-abc")
+        .keepalive(true)
+        .signal(controller.token())
+        .priority(RequestPriority::High)
+        .window(7)
+        .build();
+
+    // Then the overrides stick
+    assert_eq!(request.method, HttpMethod::Put);
+    let traces: Vec<_> = request.headers.get_all("x-trace").iter().collect();
+    assert_eq!(traces, ["a", "b"]);
+    assert!(matches!(request.body, Some(RequestBody::Bytes(ref bytes)) if bytes == &[1, 2, 3]));
+    assert_eq!(request.mode, RequestMode::NoCors);
+    assert_eq!(request.credentials, CredentialsMode::Include);
+    assert_eq!(request.cache, CacheMode::NoStore);
+    assert_eq!(request.redirect, RedirectMode::Manual);
+    assert_eq!(
+        request.referrer.as_deref(),
+        Some("https://example.com/page")
+    );
+    assert_eq!(request.referrer_policy, ReferrerPolicy::NoReferrer);
+    assert_eq!(request.integrity.as_deref(), Some("sha256-abc"));
+    assert!(request.keepalive);
+    assert!(request.signal.is_some());
+    assert_eq!(request.priority, RequestPriority::High);
+    assert_eq!(request.window, Some(7));
+}
+
+#[test]
+fn test_builder_text_body_and_replaced_headers() {
+    let mut headers = http::HeaderMap::new();
+    headers.insert("accept", HeaderValue::from_static("text/html"));
+
+    let request = NetworkRequest::builder(url())
+        .header("x-dropped", HeaderValue::from_static("1"))
+        .headers(headers)
+        .body("hello")
+        .build();
+
+    assert_eq!(request.headers.len(), 1);
+    assert_eq!(request.headers["accept"], "text/html");
+    assert!(matches!(request.body, Some(RequestBody::Text(ref text)) if text == "hello"));
+}
+
+#[test]
+fn test_builder_byte_range() {
+    let request = NetworkRequest::builder(url())
+        .byte_range(0, Some(99))
+        .build();
+    assert_eq!(request.headers["range"], "bytes=0-99");
+
+    // An open-ended range replaces the earlier one
+    let request = NetworkRequest::builder(url())
+        .byte_range(0, Some(99))
+        .byte_range(500, None)
+        .build();
+    assert_eq!(request.headers.get_all("range").iter().count(), 1);
+    assert_eq!(request.headers["range"], "bytes=500-");
+}
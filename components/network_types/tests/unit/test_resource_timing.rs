@@ -21,6 +21,7 @@ fn test_resource_timing_creation() {
         transfer_size: 1024,
         encoded_body_size: 512,
         decoded_body_size: 768,
+        server_timing: Vec::new(),
     };
 
     assert_eq!(timing.start_time, 0.0);
@@ -61,6 +62,7 @@ fn test_resource_timing_debug() {
         transfer_size: 0,
         encoded_body_size: 0,
         decoded_body_size: 0,
+        server_timing: Vec::new(),
     };
 
     let debug_str = format!("{:?}", timing);
@@ -88,6 +90,7 @@ fn test_resource_timing_clone() {
         transfer_size: 2048,
         encoded_body_size: 1024,
         decoded_body_size: 1536,
+        server_timing: Vec::new(),
     };
 
     let cloned = timing.clone();
@@ -118,6 +121,7 @@ fn test_resource_timing_serde() {
         transfer_size: 1024,
         encoded_body_size: 512,
         decoded_body_size: 768,
+        server_timing: Vec::new(),
     };
 
     let json = serde_json::to_string(&timing).unwrap();
@@ -0,0 +1,80 @@
+// Unit tests for Server-Timing header parsing
+
+use network_types::{ResourceTiming, ServerTimingEntry};
+
+fn entry(name: &str, duration_ms: f64, description: &str) -> ServerTimingEntry {
+    ServerTimingEntry {
+        name: name.to_string(),
+        duration_ms,
+        description: description.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_durations_and_description() {
+    let entries = ResourceTiming::parse_server_timing("db;dur=53.2, app;dur=47.2;desc=\"App\"");
+
+    assert_eq!(
+        entries,
+        vec![entry("db", 53.2, ""), entry("app", 47.2, "App")]
+    );
+}
+
+#[test]
+fn test_parse_metric_without_params() {
+    let entries = ResourceTiming::parse_server_timing("miss, cache;desc=hit-front");
+
+    assert_eq!(
+        entries,
+        vec![entry("miss", 0.0, ""), entry("cache", 0.0, "hit-front")]
+    );
+}
+
+#[test]
+fn test_parse_quoted_description_with_separators() {
+    let entries = ResourceTiming::parse_server_timing(r#"total;desc="a, b; \"c\"";dur=12, edge"#);
+
+    assert_eq!(
+        entries,
+        vec![entry("total", 12.0, "a, b; \"c\""), entry("edge", 0.0, "")]
+    );
+}
+
+#[test]
+fn test_parse_first_param_wins_and_names_are_case_insensitive() {
+    let entries = ResourceTiming::parse_server_timing("db;DUR=10;dur=20;Desc=first;desc=second");
+
+    assert_eq!(entries, vec![entry("db", 10.0, "first")]);
+}
+
+#[test]
+fn test_parse_tolerates_whitespace_and_unknown_params() {
+    let entries =
+        ResourceTiming::parse_server_timing("  db ; dur = 5 ; extra=1 ;desc = \"Query\"  ");
+
+    assert_eq!(entries, vec![entry("db", 5.0, "Query")]);
+}
+
+#[test]
+fn test_parse_invalid_duration_is_zero() {
+    let entries = ResourceTiming::parse_server_timing("db;dur=fast, app;dur=NaN");
+
+    assert_eq!(entries, vec![entry("db", 0.0, ""), entry("app", 0.0, "")]);
+}
+
+#[test]
+fn test_parse_skips_entries_without_valid_name() {
+    let entries = ResourceTiming::parse_server_timing(", ;dur=3, \"quoted\";dur=1, ok;dur=2,");
+
+    assert_eq!(entries, vec![entry("ok", 2.0, "")]);
+}
+
+#[test]
+fn test_parse_empty_header() {
+    assert!(ResourceTiming::parse_server_timing("").is_empty());
+}
+
+#[test]
+fn test_default_timing_has_no_server_timing() {
+    assert!(ResourceTiming::default().server_timing.is_empty());
+}
@@ -1,4 +1,5 @@
-use crate::BenchmarkResult;
+use crate::stats::Comparison;
+use crate::{BenchmarkResult, HIGH_VARIANCE_THRESHOLD};
 
 /// Format benchmark results as a human-readable string
 pub fn format_results(results: &[BenchmarkResult]) -> String {
@@ -14,13 +15,33 @@ pub fn format_results(results: &[BenchmarkResult]) -> String {
         output.push_str(&format!("  Average Time: {:.3} ms\n", result.avg_time_ms));
         output.push_str(&format!("  Min Time: {:.3} ms\n", result.min_time_ms));
         output.push_str(&format!("  Max Time: {:.3} ms\n", result.max_time_ms));
+        output.push_str(&format!("  P99 Time: {:.3} ms\n", result.p99_time_ms));
         output.push_str(&format!("  Std Dev: {:.3} ms\n", result.std_dev_ms));
+        output.push_str(&format!("  CV: {:.1}%\n", result.coefficient_of_variation * 100.0));
+        if result.is_noisy() {
+            output.push_str(&format!(
+                "  WARNING: high variance (CV above {:.0}%), results may be unreliable\n",
+                HIGH_VARIANCE_THRESHOLD * 100.0
+            ));
+        }
         output.push_str("\n");
     }
 
     output
 }
 
+/// Format a comparison against a baseline as a human-readable string
+pub fn format_comparison(name: &str, comparison: &Comparison) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!("Comparison: {}\n", name));
+    output.push_str(&format!("  Average: {:+.1}%\n", comparison.avg_change_percent));
+    output.push_str(&format!("  P99: {:+.1}%\n", comparison.p99_change_percent));
+    output.push_str(&format!("  Verdict: {}\n", comparison.verdict));
+
+    output
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,7 +57,9 @@ mod tests {
                 avg_time_ms: 10.0,
                 min_time_ms: 8.0,
                 max_time_ms: 12.0,
+                p99_time_ms: 12.0,
                 std_dev_ms: 1.5,
+                coefficient_of_variation: 0.15,
             },
         ];
 
@@ -44,5 +67,28 @@ mod tests {
 
         assert!(formatted.contains("test_bench"));
         assert!(formatted.contains("10.0"));
+        assert!(formatted.contains("CV: 15.0%"));
+    }
+
+    #[test]
+    fn test_format_results_warns_on_high_variance() {
+        let results = vec![
+            BenchmarkResult {
+                name: "noisy_bench".to_string(),
+                iterations: 10,
+                total_time_ms: 100.0,
+                avg_time_ms: 10.0,
+                min_time_ms: 2.0,
+                max_time_ms: 30.0,
+                p99_time_ms: 30.0,
+                std_dev_ms: 5.0,
+                coefficient_of_variation: 0.5,
+            },
+        ];
+
+        let formatted = format_results(&results);
+
+        assert!(formatted.contains("CV: 50.0%"));
+        assert!(formatted.contains("WARNING: high variance"));
     }
 }
@@ -11,6 +11,9 @@ pub struct BenchmarkConfig {
     pub iterations: usize,
     /// Number of warmup iterations (not measured)
     pub warmup_iterations: usize,
+    /// Percentage of the fastest and of the slowest measurements discarded
+    /// before computing statistics (0.0 keeps every measurement)
+    pub outlier_trim_percent: f64,
 }
 
 /// Result of a benchmark run
@@ -28,8 +31,22 @@ pub struct BenchmarkResult {
     pub min_time_ms: f64,
     /// Maximum time in milliseconds
     pub max_time_ms: f64,
+    /// 99th percentile time in milliseconds
+    pub p99_time_ms: f64,
     /// Standard deviation in milliseconds
     pub std_dev_ms: f64,
+    /// Standard deviation relative to the average time (0.1 = 10%)
+    pub coefficient_of_variation: f64,
+}
+
+/// Coefficient of variation above which a result is considered too noisy
+pub const HIGH_VARIANCE_THRESHOLD: f64 = 0.10;
+
+impl BenchmarkResult {
+    /// Whether the measurements vary too much for the result to be trusted
+    pub fn is_noisy(&self) -> bool {
+        self.coefficient_of_variation > HIGH_VARIANCE_THRESHOLD
+    }
 }
 
 impl BenchmarkRunner {
@@ -37,4 +54,9 @@ impl BenchmarkRunner {
     pub fn format_results(&self, results: &[BenchmarkResult]) -> String {
         formatter::format_results(results)
     }
+
+    /// Compare a result against its baseline and format the verdict
+    pub fn format_comparison(&self, baseline: &BenchmarkResult, current: &BenchmarkResult) -> String {
+        formatter::format_comparison(&current.name, &stats::compare(baseline, current))
+    }
 }
@@ -1,5 +1,8 @@
 use crate::{BenchmarkConfig, BenchmarkResult};
-use crate::stats::{calculate_mean, calculate_std_dev, find_min, find_max};
+use crate::stats::{
+    calculate_coefficient_of_variation, calculate_mean, calculate_std_dev, calculate_percentile,
+    find_max, find_min, trim_outliers,
+};
 use std::time::Instant;
 
 pub struct BenchmarkRunner {
@@ -31,21 +34,35 @@ impl BenchmarkRunner {
             times.push(elapsed.as_secs_f64() * 1000.0); // Convert to milliseconds
         }
 
+        self.summarize(name, &times)
+    }
+
+    /// Compute the result for a set of measured iteration times
+    ///
+    /// Outliers are trimmed according to `outlier_trim_percent` first, so
+    /// every statistic except `iterations` covers the kept measurements only.
+    pub fn summarize(&self, name: &str, times_ms: &[f64]) -> BenchmarkResult {
+        let times = trim_outliers(times_ms, self.config.outlier_trim_percent);
+
         // Calculate statistics
         let total_time_ms = times.iter().sum();
         let avg_time_ms = calculate_mean(&times);
         let min_time_ms = find_min(&times);
         let max_time_ms = find_max(&times);
+        let p99_time_ms = calculate_percentile(&times, 99.0);
         let std_dev_ms = calculate_std_dev(&times);
+        let coefficient_of_variation = calculate_coefficient_of_variation(&times);
 
         BenchmarkResult {
             name: name.to_string(),
-            iterations: self.config.iterations,
+            iterations: times_ms.len(),
             total_time_ms,
             avg_time_ms,
             min_time_ms,
             max_time_ms,
+            p99_time_ms,
             std_dev_ms,
+            coefficient_of_variation,
         }
     }
 
@@ -71,6 +88,7 @@ mod tests {
         let config = BenchmarkConfig {
             iterations: 5,
             warmup_iterations: 2,
+            outlier_trim_percent: 0.0,
         };
 
         let runner = BenchmarkRunner::new(config);
@@ -88,6 +106,7 @@ mod tests {
         let config = BenchmarkConfig {
             iterations: 10,
             warmup_iterations: 2,
+            outlier_trim_percent: 0.0,
         };
 
         let runner = BenchmarkRunner::new(config);
@@ -99,7 +118,9 @@ mod tests {
             avg_time_ms: 10.0,
             min_time_ms: 8.0,
             max_time_ms: 12.0,
+            p99_time_ms: 12.0,
             std_dev_ms: 1.5,
+            coefficient_of_variation: 0.15,
         };
 
         let current = BenchmarkResult {
@@ -109,10 +130,29 @@ mod tests {
             avg_time_ms: 15.0,
             min_time_ms: 13.0,
             max_time_ms: 17.0,
+            p99_time_ms: 17.0,
             std_dev_ms: 2.0,
+            coefficient_of_variation: 0.133,
         };
 
         let change = runner.compare_results(&baseline, &current);
         assert!((change - 50.0).abs() < 0.01); // 50% regression
     }
+
+    #[test]
+    fn test_summarize_trims_outliers() {
+        let runner = BenchmarkRunner::new(BenchmarkConfig {
+            iterations: 10,
+            warmup_iterations: 0,
+            outlier_trim_percent: 10.0,
+        });
+
+        let times = [10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 1.0, 100.0];
+        let result = runner.summarize("trimmed", &times);
+
+        assert_eq!(result.iterations, 10);
+        assert!((result.avg_time_ms - 10.0).abs() < 0.001);
+        assert!((result.max_time_ms - 10.0).abs() < 0.001);
+        assert!(!result.is_noisy());
+    }
 }
@@ -1,3 +1,6 @@
+use crate::BenchmarkResult;
+use std::fmt;
+
 /// Calculate the mean (average) of a list of values
 pub fn calculate_mean(values: &[f64]) -> f64 {
     if values.is_empty() {
@@ -39,6 +42,172 @@ pub fn find_max(values: &[f64]) -> f64 {
         .fold(f64::NEG_INFINITY, f64::max)
 }
 
+/// Calculate the coefficient of variation (standard deviation relative to the mean)
+///
+/// Returns 0.0 when the mean is zero.
+pub fn calculate_coefficient_of_variation(values: &[f64]) -> f64 {
+    let mean = calculate_mean(values);
+    if mean == 0.0 {
+        return 0.0;
+    }
+
+    calculate_std_dev(values) / mean.abs()
+}
+
+/// Discard the fastest and slowest `percent`% of values
+///
+/// The same number of values is dropped from each end, rounded down, and
+/// the remaining values are returned in ascending order.
+pub fn trim_outliers(values: &[f64], percent: f64) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let percent = percent.clamp(0.0, 50.0);
+    let mut trim = (sorted.len() as f64 * percent / 100.0) as usize;
+    if trim * 2 >= sorted.len() {
+        trim = sorted.len().saturating_sub(1) / 2;
+    }
+
+    sorted[trim..sorted.len() - trim].to_vec()
+}
+
+/// Calculate the `percentile`th percentile of a list of values (nearest rank)
+pub fn calculate_percentile(values: &[f64], percentile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Outcome of comparing a benchmark against its baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Significantly faster than the baseline
+    Improved,
+    /// Significantly slower than the baseline
+    Regressed,
+    /// No statistically significant difference
+    NoChange,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Improved => write!(f, "IMPROVED"),
+            Verdict::Regressed => write!(f, "REGRESSED"),
+            Verdict::NoChange => write!(f, "NO CHANGE"),
+        }
+    }
+}
+
+/// Difference between a baseline and a current benchmark result
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    /// Change in average time, in percent (positive = slower)
+    pub avg_change_percent: f64,
+    /// Change in 99th percentile time, in percent (positive = slower)
+    pub p99_change_percent: f64,
+    /// Welch's t statistic for the difference in average time
+    pub t_statistic: f64,
+    /// Whether the change in average time is significant at the 95% level
+    pub significant: bool,
+    /// Overall verdict
+    pub verdict: Verdict,
+}
+
+/// Compare a current benchmark result against a baseline
+///
+/// Significance is decided with Welch's t-test on the average times, using
+/// each result's standard deviation and iteration count, at the 95%
+/// confidence level.
+pub fn compare(baseline: &BenchmarkResult, current: &BenchmarkResult) -> Comparison {
+    let avg_change_percent = percent_change(baseline.avg_time_ms, current.avg_time_ms);
+    let p99_change_percent = percent_change(baseline.p99_time_ms, current.p99_time_ms);
+
+    let (t_statistic, degrees_of_freedom) = welch_t_test(baseline, current);
+    let significant = t_statistic.abs() > t_critical_95(degrees_of_freedom);
+
+    let verdict = if !significant {
+        Verdict::NoChange
+    } else if current.avg_time_ms < baseline.avg_time_ms {
+        Verdict::Improved
+    } else {
+        Verdict::Regressed
+    };
+
+    Comparison {
+        avg_change_percent,
+        p99_change_percent,
+        t_statistic,
+        significant,
+        verdict,
+    }
+}
+
+/// Percentage change from `baseline` to `current`, 0.0 for a zero baseline
+fn percent_change(baseline: f64, current: f64) -> f64 {
+    if baseline == 0.0 {
+        return 0.0;
+    }
+
+    (current - baseline) / baseline * 100.0
+}
+
+/// Welch's t statistic and degrees of freedom for two results
+fn welch_t_test(a: &BenchmarkResult, b: &BenchmarkResult) -> (f64, f64) {
+    let diff = b.avg_time_ms - a.avg_time_ms;
+    if a.iterations < 2 || b.iterations < 2 {
+        return (0.0, 0.0);
+    }
+
+    // Standard error contribution of each result, from the sample variance
+    let error = |result: &BenchmarkResult| {
+        let n = result.iterations as f64;
+        result.std_dev_ms * result.std_dev_ms / (n - 1.0)
+    };
+    let (error_a, error_b) = (error(a), error(b));
+    let standard_error = (error_a + error_b).sqrt();
+
+    if standard_error == 0.0 {
+        // Both runs were perfectly consistent; any difference is real
+        let t = if diff == 0.0 { 0.0 } else { diff.signum() * f64::INFINITY };
+        return (t, f64::INFINITY);
+    }
+
+    let degrees_of_freedom = (error_a + error_b).powi(2)
+        / (error_a.powi(2) / (a.iterations as f64 - 1.0)
+            + error_b.powi(2) / (b.iterations as f64 - 1.0));
+
+    (diff / standard_error, degrees_of_freedom)
+}
+
+/// Two-tailed critical value of Student's t distribution at the 95% level
+fn t_critical_95(degrees_of_freedom: f64) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179,
+        2.160, 2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064,
+        2.060, 2.056, 2.052, 2.048, 2.045, 2.042,
+    ];
+
+    if degrees_of_freedom < 1.0 {
+        return f64::INFINITY;
+    }
+
+    // Round down so the value errs on the conservative side
+    match degrees_of_freedom.floor() as usize {
+        df @ 1..=30 => TABLE[df - 1],
+        31..=40 => 2.042,
+        41..=60 => 2.021,
+        61..=120 => 2.000,
+        _ => 1.980,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +231,10 @@ mod tests {
         assert!((find_min(&values) - 1.0).abs() < 0.001);
         assert!((find_max(&values) - 9.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_trim_outliers_drops_both_ends() {
+        let values = vec![50.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 0.1];
+        assert_eq!(trim_outliers(&values, 10.0), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    }
 }
@@ -8,6 +8,7 @@ async fn test_complete_benchmark_workflow() {
     let config = BenchmarkConfig {
         iterations: 20,
         warmup_iterations: 5,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -39,6 +40,7 @@ async fn test_regression_detection_workflow() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -76,6 +78,7 @@ async fn test_warmup_iterations_exist() {
     let config = BenchmarkConfig {
         iterations: 5,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -94,6 +97,7 @@ async fn test_zero_warmup_iterations() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 0,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -111,6 +115,7 @@ async fn test_statistical_accuracy() {
     let config = BenchmarkConfig {
         iterations: 100,
         warmup_iterations: 10,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
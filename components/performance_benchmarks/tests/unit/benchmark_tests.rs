@@ -1,4 +1,4 @@
-use performance_benchmarks::{BenchmarkRunner, BenchmarkConfig, BenchmarkResult};
+use performance_benchmarks::{BenchmarkRunner, BenchmarkConfig, BenchmarkResult, HIGH_VARIANCE_THRESHOLD};
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -7,6 +7,7 @@ fn test_benchmark_config_creation() {
     let config = BenchmarkConfig {
         iterations: 100,
         warmup_iterations: 10,
+        outlier_trim_percent: 0.0,
     };
 
     assert_eq!(config.iterations, 100);
@@ -18,6 +19,7 @@ fn test_benchmark_runner_creation() {
     let config = BenchmarkConfig {
         iterations: 50,
         warmup_iterations: 5,
+        outlier_trim_percent: 0.0,
     };
 
     let _runner = BenchmarkRunner::new(config);
@@ -29,6 +31,7 @@ async fn test_run_benchmark_basic() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -51,6 +54,7 @@ async fn test_benchmark_min_max_ordering() {
     let config = BenchmarkConfig {
         iterations: 5,
         warmup_iterations: 1,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -71,6 +75,7 @@ async fn test_benchmark_total_time_calculation() {
     let config = BenchmarkConfig {
         iterations: 5,
         warmup_iterations: 0,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -90,6 +95,7 @@ fn test_format_results() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -102,7 +108,9 @@ fn test_format_results() {
             avg_time_ms: 10.0,
             min_time_ms: 8.0,
             max_time_ms: 12.0,
+            p99_time_ms: 12.0,
             std_dev_ms: 1.5,
+            coefficient_of_variation: 0.15,
         },
         BenchmarkResult {
             name: "bench2".to_string(),
@@ -111,7 +119,9 @@ fn test_format_results() {
             avg_time_ms: 20.0,
             min_time_ms: 18.0,
             max_time_ms: 22.0,
+            p99_time_ms: 22.0,
             std_dev_ms: 2.0,
+            coefficient_of_variation: 0.1,
         },
     ];
 
@@ -130,6 +140,7 @@ fn test_compare_results_no_change() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -141,7 +152,9 @@ fn test_compare_results_no_change() {
         avg_time_ms: 10.0,
         min_time_ms: 8.0,
         max_time_ms: 12.0,
+        p99_time_ms: 12.0,
         std_dev_ms: 1.5,
+        coefficient_of_variation: 0.15,
     };
 
     let current = BenchmarkResult {
@@ -151,7 +164,9 @@ fn test_compare_results_no_change() {
         avg_time_ms: 10.0,
         min_time_ms: 8.0,
         max_time_ms: 12.0,
+        p99_time_ms: 12.0,
         std_dev_ms: 1.5,
+        coefficient_of_variation: 0.15,
     };
 
     let change = runner.compare_results(&baseline, &current);
@@ -163,6 +178,7 @@ fn test_compare_results_improvement() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -174,7 +190,9 @@ fn test_compare_results_improvement() {
         avg_time_ms: 10.0,
         min_time_ms: 8.0,
         max_time_ms: 12.0,
+        p99_time_ms: 12.0,
         std_dev_ms: 1.5,
+        coefficient_of_variation: 0.15,
     };
 
     let current = BenchmarkResult {
@@ -184,7 +202,9 @@ fn test_compare_results_improvement() {
         avg_time_ms: 5.0, // 50% faster
         min_time_ms: 4.0,
         max_time_ms: 6.0,
+        p99_time_ms: 6.0,
         std_dev_ms: 0.8,
+        coefficient_of_variation: 0.16,
     };
 
     let change = runner.compare_results(&baseline, &current);
@@ -196,6 +216,7 @@ fn test_compare_results_regression() {
     let config = BenchmarkConfig {
         iterations: 10,
         warmup_iterations: 2,
+        outlier_trim_percent: 0.0,
     };
 
     let runner = BenchmarkRunner::new(config);
@@ -207,7 +228,9 @@ fn test_compare_results_regression() {
         avg_time_ms: 10.0,
         min_time_ms: 8.0,
         max_time_ms: 12.0,
+        p99_time_ms: 12.0,
         std_dev_ms: 1.5,
+        coefficient_of_variation: 0.15,
     };
 
     let current = BenchmarkResult {
@@ -217,9 +240,84 @@ fn test_compare_results_regression() {
         avg_time_ms: 15.0, // 50% slower
         min_time_ms: 13.0,
         max_time_ms: 17.0,
+        p99_time_ms: 17.0,
         std_dev_ms: 2.0,
+        coefficient_of_variation: 0.133,
     };
 
     let change = runner.compare_results(&baseline, &current);
     assert!((change - 50.0).abs() < 0.01); // Should be 50% (regression)
 }
+
+#[test]
+fn test_noisy_dataset_is_flagged() {
+    let config = BenchmarkConfig {
+        iterations: 10,
+        warmup_iterations: 0,
+        outlier_trim_percent: 0.0,
+    };
+
+    let runner = BenchmarkRunner::new(config);
+
+    // Mostly 10ms with two slow iterations
+    let times = [10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 10.0, 40.0, 40.0];
+    let result = runner.summarize("noisy", &times);
+
+    // Mean 16ms, std dev 12ms
+    assert!((result.avg_time_ms - 16.0).abs() < 0.001);
+    assert!((result.coefficient_of_variation - 0.75).abs() < 0.001);
+    assert!(result.coefficient_of_variation > HIGH_VARIANCE_THRESHOLD);
+    assert!(result.is_noisy());
+
+    let formatted = runner.format_results(&[result]);
+    assert!(formatted.contains("CV: 75.0%"));
+    assert!(formatted.contains("WARNING: high variance"));
+}
+
+#[test]
+fn test_outlier_trimming_removes_noise() {
+    let config = BenchmarkConfig {
+        iterations: 10,
+        warmup_iterations: 0,
+        outlier_trim_percent: 20.0,
+    };
+
+    let runner = BenchmarkRunner::new(config);
+
+    let times = [10.0, 10.2, 9.8, 10.1, 9.9, 10.0, 10.0, 10.0, 40.0, 40.0];
+    let result = runner.summarize("trimmed", &times);
+
+    assert_eq!(result.iterations, 10);
+    assert!((result.max_time_ms - 10.2).abs() < 0.001);
+    assert!(result.coefficient_of_variation < 0.01);
+    assert!(!result.is_noisy());
+
+    let formatted = runner.format_results(&[result]);
+    assert!(!formatted.contains("WARNING"));
+}
+
+#[test]
+fn test_format_comparison_verdicts() {
+    let config = BenchmarkConfig {
+        iterations: 10,
+        warmup_iterations: 0,
+        outlier_trim_percent: 0.0,
+    };
+
+    let runner = BenchmarkRunner::new(config);
+
+    let baseline = runner.summarize("bench", &[10.0, 10.1, 9.9, 10.0, 10.2, 9.8, 10.0, 10.1, 9.9, 10.0]);
+    let slower = runner.summarize("bench", &[15.0, 15.1, 14.9, 15.0, 15.2, 14.8, 15.0, 15.1, 14.9, 15.0]);
+    let faster = runner.summarize("bench", &[5.0, 5.1, 4.9, 5.0, 5.2, 4.8, 5.0, 5.1, 4.9, 5.0]);
+
+    let regressed = runner.format_comparison(&baseline, &slower);
+    assert!(regressed.contains("Average: +50.0%"));
+    assert!(regressed.contains("Verdict: REGRESSED"));
+
+    let improved = runner.format_comparison(&baseline, &faster);
+    assert!(improved.contains("Average: -50.0%"));
+    assert!(improved.contains("Verdict: IMPROVED"));
+
+    let unchanged = runner.format_comparison(&baseline, &baseline);
+    assert!(unchanged.contains("Verdict: NO CHANGE"));
+}
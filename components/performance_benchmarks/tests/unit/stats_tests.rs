@@ -1,4 +1,8 @@
-use performance_benchmarks::stats::{calculate_mean, calculate_std_dev, find_min, find_max};
+use performance_benchmarks::stats::{
+    calculate_coefficient_of_variation, calculate_mean, calculate_percentile, calculate_std_dev,
+    compare, find_max, find_min, trim_outliers, Verdict,
+};
+use performance_benchmarks::{BenchmarkConfig, BenchmarkResult, BenchmarkRunner};
 
 #[test]
 fn test_calculate_mean_simple() {
@@ -84,3 +88,102 @@ fn test_find_max_single_value() {
     let max = find_max(&values);
     assert!((max - 42.0).abs() < 0.001);
 }
+
+#[test]
+fn test_calculate_coefficient_of_variation() {
+    let values = vec![2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let cv = calculate_coefficient_of_variation(&values);
+    assert!((cv - 0.4).abs() < 0.001);
+}
+
+#[test]
+fn test_calculate_coefficient_of_variation_zero_mean() {
+    let values = vec![0.0, 0.0, 0.0];
+    assert_eq!(calculate_coefficient_of_variation(&values), 0.0);
+}
+
+#[test]
+fn test_trim_outliers_zero_percent_keeps_all() {
+    let values = vec![3.0, 1.0, 2.0];
+    assert_eq!(trim_outliers(&values, 0.0), vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn test_trim_outliers_keeps_at_least_one_value() {
+    let values = vec![1.0, 2.0, 3.0];
+    assert_eq!(trim_outliers(&values, 50.0), vec![2.0]);
+}
+
+#[test]
+fn test_calculate_percentile() {
+    let values: Vec<f64> = (1..=100).map(f64::from).collect();
+    assert!((calculate_percentile(&values, 99.0) - 99.0).abs() < 0.001);
+    assert!((calculate_percentile(&values, 50.0) - 50.0).abs() < 0.001);
+    assert!((calculate_percentile(&[7.0], 99.0) - 7.0).abs() < 0.001);
+}
+
+fn summarize(name: &str, times: &[f64]) -> BenchmarkResult {
+    let runner = BenchmarkRunner::new(BenchmarkConfig {
+        iterations: times.len(),
+        warmup_iterations: 0,
+        outlier_trim_percent: 0.0,
+    });
+    runner.summarize(name, times)
+}
+
+/// Times around `center` with a small, repeating spread
+fn dataset(center: f64) -> Vec<f64> {
+    (0..30).map(|i| center + f64::from(i % 5) * 0.1 - 0.2).collect()
+}
+
+#[test]
+fn test_compare_clearly_faster() {
+    let baseline = summarize("bench", &dataset(10.0));
+    let current = summarize("bench", &dataset(8.0));
+
+    let comparison = compare(&baseline, &current);
+
+    assert!((comparison.avg_change_percent + 20.0).abs() < 0.001);
+    assert!(comparison.p99_change_percent < 0.0);
+    assert!(comparison.significant);
+    assert_eq!(comparison.verdict, Verdict::Improved);
+}
+
+#[test]
+fn test_compare_clearly_slower() {
+    let baseline = summarize("bench", &dataset(10.0));
+    let current = summarize("bench", &dataset(12.0));
+
+    let comparison = compare(&baseline, &current);
+
+    assert!((comparison.avg_change_percent - 20.0).abs() < 0.001);
+    assert!(comparison.p99_change_percent > 0.0);
+    assert!(comparison.significant);
+    assert_eq!(comparison.verdict, Verdict::Regressed);
+}
+
+#[test]
+fn test_compare_no_difference() {
+    // Same distribution in a different order
+    let times = dataset(10.0);
+    let reversed: Vec<f64> = times.iter().rev().copied().collect();
+    let baseline = summarize("bench", &times);
+    let current = summarize("bench", &reversed);
+
+    let comparison = compare(&baseline, &current);
+
+    assert!(comparison.avg_change_percent.abs() < 0.001);
+    assert!(!comparison.significant);
+    assert_eq!(comparison.verdict, Verdict::NoChange);
+}
+
+#[test]
+fn test_compare_small_shift_in_noisy_data_is_not_significant() {
+    let baseline = summarize("bench", &[5.0, 15.0, 8.0, 12.0, 10.0, 6.0, 14.0, 10.0]);
+    let current = summarize("bench", &[5.5, 15.5, 8.5, 12.5, 10.5, 6.5, 14.5, 10.5]);
+
+    let comparison = compare(&baseline, &current);
+
+    assert!(comparison.avg_change_percent > 0.0);
+    assert_eq!(comparison.verdict, Verdict::NoChange);
+}
@@ -1,6 +1,17 @@
 //! System certificate store access
 
 use network_errors::NetworkError;
+use rustls::pki_types::CertificateDer;
+use rustls::RootCertStore;
+
+/// Outcome of loading DER certificates into a root store
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RootsLoaded {
+    /// Certificates added as trust anchors
+    pub added: usize,
+    /// Malformed certificates that were skipped
+    pub skipped: usize,
+}
 
 /// Get system certificate store
 ///
@@ -30,6 +41,16 @@ pub fn get_system_cert_store() -> Result<Vec<Vec<u8>>, NetworkError> {
     Ok(Vec::new())
 }
 
+/// Add DER-encoded certificates to `store` as trust anchors
+///
+/// Certificates that fail to parse are skipped and counted rather than
+/// failing the whole load.
+pub fn add_der_roots(store: &mut RootCertStore, certs: Vec<Vec<u8>>) -> RootsLoaded {
+    let (added, skipped) =
+        store.add_parsable_certificates(certs.into_iter().map(CertificateDer::from));
+    RootsLoaded { added, skipped }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
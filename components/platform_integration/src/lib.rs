@@ -3,7 +3,7 @@
 //! This component provides platform-specific functionality for:
 //! - System proxy configuration detection
 //! - System certificate store access
-//! - Network connectivity detection
+//! - Network connectivity detection (heuristic or by probing an endpoint)
 //!
 //! # Examples
 //!
@@ -28,11 +28,14 @@
 #![warn(rust_2018_idioms)]
 
 use network_errors::NetworkError;
+use rustls::RootCertStore;
+use std::time::Duration;
 
 mod proxy;
 mod certs;
 mod network;
 
+pub use certs::RootsLoaded;
 pub use proxy::SystemProxyConfig;
 
 /// Platform integration service
@@ -89,6 +92,40 @@ impl PlatformIntegration {
         certs::get_system_cert_store()
     }
 
+    /// Load the system certificate store into a rustls root store
+    ///
+    /// Parses each certificate from
+    /// [`get_system_cert_store`](Self::get_system_cert_store) and adds the
+    /// valid ones to `store` as trust anchors; malformed entries are skipped.
+    ///
+    /// # Returns
+    ///
+    /// Returns how many certificates were added and skipped, or an error if
+    /// the system store can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use platform_integration::PlatformIntegration;
+    /// use rustls::RootCertStore;
+    ///
+    /// let mut roots = RootCertStore::empty();
+    /// let loaded = PlatformIntegration::load_system_roots_into(&mut roots).unwrap();
+    /// println!("Loaded {} roots, skipped {}", loaded.added, loaded.skipped);
+    /// ```
+    pub fn load_system_roots_into(store: &mut RootCertStore) -> Result<RootsLoaded, NetworkError> {
+        let certs = certs::get_system_cert_store()?;
+        Ok(certs::add_der_roots(store, certs))
+    }
+
+    /// Add DER-encoded certificates to a rustls root store
+    ///
+    /// Valid certificates become trust anchors; malformed ones are skipped
+    /// and counted in the returned [`RootsLoaded`].
+    pub fn add_der_roots(store: &mut RootCertStore, certs: Vec<Vec<u8>>) -> RootsLoaded {
+        certs::add_der_roots(store, certs)
+    }
+
     /// Check if network is online
     ///
     /// Detects whether the system has network connectivity.
@@ -112,6 +149,33 @@ impl PlatformIntegration {
     pub fn is_online() -> bool {
         network::is_online()
     }
+
+    /// Check if network is online by probing an endpoint
+    ///
+    /// Unlike [`is_online`](Self::is_online), the caller chooses what to
+    /// reach: `target` is a `host:port` such as a captive-portal check
+    /// endpoint or the application's own server. The check passes if a TCP
+    /// connection to `target` is established within `timeout`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if `target` was reachable, `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use platform_integration::PlatformIntegration;
+    /// use std::time::Duration;
+    ///
+    /// # async fn example() {
+    /// let target = "connectivity.example.com:80";
+    /// let online = PlatformIntegration::is_online_probe(target, Duration::from_secs(2)).await;
+    /// println!("Online: {}", online);
+    /// # }
+    /// ```
+    pub async fn is_online_probe(target: &str, timeout: Duration) -> bool {
+        network::is_online_probe(target, timeout).await
+    }
 }
 
 #[cfg(test)]
@@ -37,6 +37,23 @@ pub fn is_online() -> bool {
     ).is_ok()
 }
 
+/// Check connectivity by connecting to a probe endpoint
+///
+/// Resolves `target` (a `host:port` string) and attempts a TCP connection,
+/// giving up after `timeout`. The connection is closed as soon as it's
+/// established; no data is exchanged.
+///
+/// # Returns
+///
+/// Returns `true` if the connection succeeded within the timeout, `false` if
+/// resolution or connection failed or timed out.
+pub async fn is_online_probe(target: &str, timeout: Duration) -> bool {
+    matches!(
+        tokio::time::timeout(timeout, tokio::net::TcpStream::connect(target)).await,
+        Ok(Ok(_))
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
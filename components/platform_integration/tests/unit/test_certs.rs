@@ -4,7 +4,15 @@
 //! When get_system_cert_store is called
 //! Then certificates are returned or empty result
 
-use platform_integration::PlatformIntegration;
+use platform_integration::{PlatformIntegration, RootsLoaded};
+use rustls::RootCertStore;
+
+fn self_signed_der(name: &str) -> Vec<u8> {
+    rcgen::generate_simple_self_signed(vec![name.to_string()])
+        .unwrap()
+        .serialize_der()
+        .unwrap()
+}
 
 #[test]
 fn test_get_system_cert_store_returns_result() {
@@ -42,3 +50,42 @@ fn test_get_system_cert_store_graceful_on_unsupported_platform() {
     // Should not panic or return error on unsupported platforms
     assert!(result.is_ok());
 }
+
+#[test]
+fn test_add_der_roots_skips_malformed_certs() {
+    // Given: Two valid certificates mixed with garbage DER
+    let certs = vec![
+        self_signed_der("a.example"),
+        b"not a certificate".to_vec(),
+        self_signed_der("b.example"),
+        Vec::new(),
+    ];
+    let mut store = RootCertStore::empty();
+
+    // When: They are added to a root store
+    let loaded = PlatformIntegration::add_der_roots(&mut store, certs);
+
+    // Then: Only the valid certificates are loaded
+    assert_eq!(
+        loaded,
+        RootsLoaded {
+            added: 2,
+            skipped: 2
+        }
+    );
+    assert_eq!(store.len(), 2);
+}
+
+#[test]
+fn test_load_system_roots_into_matches_system_store() {
+    // Given: An empty root store
+    let mut store = RootCertStore::empty();
+
+    // When: System roots are loaded into it
+    let loaded = PlatformIntegration::load_system_roots_into(&mut store).unwrap();
+
+    // Then: Every system certificate is accounted for
+    let system = PlatformIntegration::get_system_cert_store().unwrap();
+    assert_eq!(loaded.added + loaded.skipped, system.len());
+    assert_eq!(store.len(), loaded.added);
+}
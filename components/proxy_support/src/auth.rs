@@ -6,8 +6,10 @@ use base64::{engine::general_purpose::STANDARD, Engine};
 
 /// Proxy authentication credentials
 ///
-/// Currently supports HTTP Basic authentication used by both
-/// HTTP CONNECT proxies and SOCKS5 proxies.
+/// Supports HTTP Basic authentication, used by both HTTP CONNECT proxies
+/// and SOCKS5 proxies, and HTTP Digest authentication (RFC 7616) for HTTP
+/// CONNECT proxies. SOCKS5 proxies get Digest credentials as a plain
+/// username and password.
 #[derive(Debug, Clone)]
 pub enum ProxyAuth {
     /// HTTP Basic authentication with username and password
@@ -17,6 +19,13 @@ pub enum ProxyAuth {
         /// Password
         password: String,
     },
+    /// HTTP Digest authentication, answering the proxy's challenge
+    Digest {
+        /// Username
+        username: String,
+        /// Password
+        password: String,
+    },
 }
 
 impl ProxyAuth {
@@ -38,17 +47,14 @@ impl ProxyAuth {
     /// // encoded will be base64("user:pass")
     /// ```
     pub fn encode_basic(&self) -> String {
-        match self {
-            ProxyAuth::Basic { username, password } => {
-                let credentials = format!("{}:{}", username, password);
-                STANDARD.encode(credentials.as_bytes())
-            }
-        }
+        let (username, password) = self.credentials();
+        let credentials = format!("{}:{}", username, password);
+        STANDARD.encode(credentials.as_bytes())
     }
 
-    /// Get username and password from Basic auth
+    /// Get username and password
     ///
-    /// Returns a tuple of (username, password) for Basic authentication.
+    /// Returns a tuple of (username, password) for either scheme.
     ///
     /// # Examples
     ///
@@ -66,7 +72,9 @@ impl ProxyAuth {
     /// ```
     pub fn credentials(&self) -> (&str, &str) {
         match self {
-            ProxyAuth::Basic { username, password } => (username.as_str(), password.as_str()),
+            ProxyAuth::Basic { username, password } | ProxyAuth::Digest { username, password } => {
+                (username.as_str(), password.as_str())
+            }
         }
     }
 }
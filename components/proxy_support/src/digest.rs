@@ -0,0 +1,325 @@
+//! HTTP Digest access authentication (RFC 7616)
+//!
+//! Parses a proxy's `Proxy-Authenticate: Digest ...` challenge and computes
+//! the matching `Proxy-Authorization` header value.
+
+use md5::Md5;
+use network_errors::NetworkError;
+use sha2::{Digest, Sha256};
+
+/// Hash algorithm named by a Digest challenge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DigestAlgorithm {
+    /// `MD5`, the default when the challenge names none
+    Md5,
+    /// `SHA-256`
+    Sha256,
+}
+
+impl DigestAlgorithm {
+    fn parse(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("MD5") {
+            Some(Self::Md5)
+        } else if name.eq_ignore_ascii_case("SHA-256") {
+            Some(Self::Sha256)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Sha256 => "SHA-256",
+        }
+    }
+
+    /// Lowercase hex digest of `data`
+    fn hash(self, data: &str) -> String {
+        match self {
+            Self::Md5 => format!("{:x}", Md5::digest(data.as_bytes())),
+            Self::Sha256 => format!("{:x}", Sha256::digest(data.as_bytes())),
+        }
+    }
+}
+
+/// Parameters of a `Digest` challenge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    pub algorithm: DigestAlgorithm,
+    /// Whether the proxy offered `qop=auth`; without it the RFC 2069 form is used
+    pub qop_auth: bool,
+}
+
+impl DigestChallenge {
+    /// Parse one `Proxy-Authenticate` header value
+    ///
+    /// Returns None if the value isn't a Digest challenge.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::ProxyError` if the challenge lacks a nonce or
+    /// asks for an algorithm or quality of protection this client can't do.
+    pub fn parse(header: &str) -> Result<Option<Self>, NetworkError> {
+        let header = header.trim();
+        let (scheme, params) = header.split_once(' ').unwrap_or((header, ""));
+        if !scheme.eq_ignore_ascii_case("Digest") {
+            return Ok(None);
+        }
+
+        let mut realm = String::new();
+        let mut nonce = None;
+        let mut opaque = None;
+        let mut algorithm = DigestAlgorithm::Md5;
+        let mut qop = None;
+        for (name, value) in parse_params(params) {
+            match name.to_ascii_lowercase().as_str() {
+                "realm" => realm = value,
+                "nonce" => nonce = Some(value),
+                "opaque" => opaque = Some(value),
+                "algorithm" => {
+                    algorithm = DigestAlgorithm::parse(&value).ok_or_else(|| {
+                        NetworkError::ProxyError(format!("Unsupported Digest algorithm: {}", value))
+                    })?;
+                }
+                "qop" => qop = Some(value),
+                _ => {}
+            }
+        }
+
+        let nonce = nonce
+            .ok_or_else(|| NetworkError::ProxyError("Digest challenge has no nonce".to_string()))?;
+        let qop_auth = match qop {
+            None => false,
+            Some(qop)
+                if qop
+                    .split(',')
+                    .any(|q| q.trim().eq_ignore_ascii_case("auth")) =>
+            {
+                true
+            }
+            Some(qop) => {
+                return Err(NetworkError::ProxyError(format!(
+                    "Unsupported Digest qop: {}",
+                    qop
+                )))
+            }
+        };
+
+        Ok(Some(Self {
+            realm,
+            nonce,
+            opaque,
+            algorithm,
+            qop_auth,
+        }))
+    }
+
+    /// Compute the `response` parameter for a request
+    ///
+    /// `nc` and `cnonce` are only used with `qop=auth`.
+    pub fn response(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        nc: u32,
+        cnonce: &str,
+    ) -> String {
+        let hash = |data: String| self.algorithm.hash(&data);
+        let ha1 = hash(format!("{}:{}:{}", username, self.realm, password));
+        let ha2 = hash(format!("{}:{}", method, uri));
+
+        if self.qop_auth {
+            hash(format!(
+                "{}:{}:{:08x}:{}:auth:{}",
+                ha1, self.nonce, nc, cnonce, ha2
+            ))
+        } else {
+            hash(format!("{}:{}:{}", ha1, self.nonce, ha2))
+        }
+    }
+
+    /// Build the `Proxy-Authorization` header value for a request
+    pub fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        nc: u32,
+        cnonce: &str,
+    ) -> String {
+        let response = self.response(username, password, method, uri, nc, cnonce);
+        let mut header = format!(
+            "Digest username=\"{}\", realm=\"{}\", nonce=\"{}\", uri=\"{}\", algorithm={}, response=\"{}\"",
+            quote(username),
+            quote(&self.realm),
+            quote(&self.nonce),
+            quote(uri),
+            self.algorithm.name(),
+            response
+        );
+        if self.qop_auth {
+            header.push_str(&format!(
+                ", qop=auth, nc={:08x}, cnonce=\"{}\"",
+                nc,
+                quote(cnonce)
+            ));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(", opaque=\"{}\"", quote(opaque)));
+        }
+        header
+    }
+}
+
+/// Split `name=value, name="quoted, value"` auth parameters
+fn parse_params(params: &str) -> Vec<(String, String)> {
+    let mut result = Vec::new();
+    let mut chars = params.chars().peekable();
+
+    loop {
+        // Skip separators before the name
+        while chars.peek().is_some_and(|c| *c == ',' || c.is_whitespace()) {
+            chars.next();
+        }
+        let name: String = chars
+            .by_ref()
+            .take_while(|c| *c != '=')
+            .collect::<String>()
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            break;
+        }
+        while chars.peek().is_some_and(|c| c.is_whitespace()) {
+            chars.next();
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                value.push(c);
+            }
+            value = value.trim().to_string();
+        }
+        result.push((name, value));
+    }
+
+    result
+}
+
+/// Escape a value for use inside a quoted string
+fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Challenge from the RFC 7616 section 3.9.1 example
+    fn rfc7616_challenge(algorithm: &str) -> DigestChallenge {
+        DigestChallenge::parse(&format!(
+            "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", algorithm={}, \
+             nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+             opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"",
+            algorithm
+        ))
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rfc7616_md5_vector() {
+        let challenge = rfc7616_challenge("MD5");
+        assert_eq!(challenge.algorithm, DigestAlgorithm::Md5);
+        assert!(challenge.qop_auth);
+
+        let response = challenge.response(
+            "Mufasa",
+            "Circle of Life",
+            "GET",
+            "/dir/index.html",
+            1,
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        );
+        assert_eq!(response, "8ca523f5e9506fed4657c9700eebdbec");
+    }
+
+    #[test]
+    fn test_rfc7616_sha256_vector() {
+        let challenge = rfc7616_challenge("SHA-256");
+        assert_eq!(challenge.algorithm, DigestAlgorithm::Sha256);
+
+        let response = challenge.response(
+            "Mufasa",
+            "Circle of Life",
+            "GET",
+            "/dir/index.html",
+            1,
+            "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ",
+        );
+        assert_eq!(
+            response,
+            "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1"
+        );
+    }
+
+    #[test]
+    fn test_rfc2617_vector() {
+        let challenge = DigestChallenge::parse(
+            "Digest realm=\"testrealm@host.com\", qop=\"auth,auth-int\", \
+             nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", \
+             opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(challenge.algorithm, DigestAlgorithm::Md5);
+
+        let header = challenge.authorization(
+            "Mufasa",
+            "Circle Of Life",
+            "GET",
+            "/dir/index.html",
+            1,
+            "0a4f113b",
+        );
+        assert!(header.starts_with("Digest username=\"Mufasa\""));
+        assert!(header.contains("response=\"6629fae49393a05397450978507c4ef1\""));
+        assert!(header.contains("qop=auth, nc=00000001, cnonce=\"0a4f113b\""));
+        assert!(header.contains("opaque=\"5ccc069c403ebaf9f0171e9517f40e41\""));
+    }
+
+    #[test]
+    fn test_parse_non_digest_and_unsupported() {
+        assert_eq!(
+            DigestChallenge::parse("Basic realm=\"corp\"").unwrap(),
+            None
+        );
+        assert!(DigestChallenge::parse("Digest realm=\"corp\"").is_err());
+        assert!(DigestChallenge::parse("Digest nonce=\"n\", algorithm=SHA-512-256").is_err());
+        assert!(DigestChallenge::parse("Digest nonce=\"n\", qop=\"auth-int\"").is_err());
+
+        let challenge = DigestChallenge::parse("digest nonce=abc, realm=\"a \\\"b\\\"\"")
+            .unwrap()
+            .unwrap();
+        assert_eq!(challenge.nonce, "abc");
+        assert_eq!(challenge.realm, "a \"b\"");
+        assert!(!challenge.qop_auth);
+    }
+}
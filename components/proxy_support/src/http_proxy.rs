@@ -3,14 +3,34 @@
 //! Implements the HTTP CONNECT method for tunneling TCP connections through HTTP proxies.
 
 use crate::auth::ProxyAuth;
+use crate::digest::DigestChallenge;
 use network_errors::NetworkError;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
+use uuid::Uuid;
+
+/// Upper bound on the size of the proxy's response head
+const MAX_RESPONSE_HEAD: usize = 16 * 1024;
+
+/// Start of the error message when the proxy answers `407`
+pub(crate) const AUTH_REQUIRED: &str = "Proxy authentication required";
+
+/// Status line and relevant headers of the proxy's response to CONNECT
+#[derive(Debug)]
+struct ConnectResponse {
+    status: u16,
+    reason: String,
+    /// Every `Proxy-Authenticate` challenge, in order
+    proxy_authenticate: Vec<String>,
+}
 
 /// Connect to a target host through an HTTP CONNECT proxy
 ///
 /// Establishes a connection to the proxy, sends a CONNECT request,
-/// and returns the tunneled TCP stream if successful.
+/// and returns the tunneled TCP stream if successful. With Digest
+/// credentials the first request goes out without them; if the proxy
+/// answers `407` with a Digest challenge, the request is resent on a new
+/// connection with the computed `Proxy-Authorization`.
 ///
 /// # Arguments
 ///
@@ -26,11 +46,15 @@ use tokio::net::TcpStream;
 ///
 /// # Errors
 ///
-/// Returns `NetworkError` if:
+/// Returns `NetworkError::ProxyError` if:
 /// - Cannot connect to proxy server
-/// - Proxy rejects authentication
-/// - Proxy cannot establish connection to target
-/// - Protocol errors occur
+/// - Proxy requires or rejects authentication (`407`; see
+///   [`is_proxy_auth_required`](crate::is_proxy_auth_required))
+/// - None of the proxy's Digest challenges uses an algorithm or qop this
+///   client supports
+/// - Proxy refuses or cannot establish the connection to the target; the
+///   message carries the status code and reason
+/// - The proxy's response is malformed or truncated
 pub async fn connect(
     proxy_host: &str,
     proxy_port: u16,
@@ -38,22 +62,93 @@ pub async fn connect(
     target_host: &str,
     target_port: u16,
 ) -> Result<TcpStream, NetworkError> {
-    // Connect to proxy server
     let proxy_addr = format!("{}:{}", proxy_host, proxy_port);
-    let mut stream = TcpStream::connect(&proxy_addr).await.map_err(|e| {
-        NetworkError::ProxyError(format!("Failed to connect to HTTP proxy: {}", e))
-    })?;
+    let target = format!("{}:{}", target_host, target_port);
+
+    // Basic credentials are sent up front; Digest needs the proxy's challenge first
+    let authorization = match auth {
+        Some(auth @ ProxyAuth::Basic { .. }) => Some(format!("Basic {}", auth.encode_basic())),
+        _ => None,
+    };
+    let (mut stream, mut response) =
+        send_connect(&proxy_addr, &target, authorization.as_deref()).await?;
+
+    // Answer a Digest challenge on a fresh connection
+    if let (407, Some(ProxyAuth::Digest { username, password })) = (response.status, auth) {
+        // Proxies list challenges in order of preference, so skip any we
+        // can't answer and only fail if none of them is usable
+        let mut challenge = None;
+        let mut unusable = None;
+        for header in &response.proxy_authenticate {
+            match DigestChallenge::parse(header) {
+                Ok(Some(digest)) => {
+                    challenge = Some(digest);
+                    break;
+                }
+                Ok(None) => {}
+                Err(error) => unusable = Some(error),
+            }
+        }
+        if let (None, Some(error)) = (&challenge, unusable) {
+            return Err(error);
+        }
+
+        if let Some(challenge) = challenge {
+            let cnonce = Uuid::new_v4().simple().to_string();
+            let authorization =
+                challenge.authorization(username, password, "CONNECT", &target, 1, &cnonce);
+            (stream, response) = send_connect(&proxy_addr, &target, Some(&authorization)).await?;
+        }
+    }
+
+    // Any 2xx response to CONNECT establishes the tunnel
+    match response.status {
+        200..=299 => {}
+        407 => {
+            let challenge = if response.proxy_authenticate.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " (Proxy-Authenticate: {})",
+                    response.proxy_authenticate.join(", ")
+                )
+            };
+            return Err(NetworkError::ProxyError(format!(
+                "{}: {} {}{}",
+                AUTH_REQUIRED, response.status, response.reason, challenge
+            )));
+        }
+        status => {
+            return Err(NetworkError::ProxyError(format!(
+                "Proxy refused CONNECT: {} {}",
+                status, response.reason
+            )));
+        }
+    }
+
+    // Return the underlying stream (now tunneled to target)
+    Ok(stream)
+}
+
+/// Open a connection to the proxy and send one CONNECT request
+///
+/// Returns the stream positioned just after the response head.
+async fn send_connect(
+    proxy_addr: &str,
+    target: &str,
+    authorization: Option<&str>,
+) -> Result<(TcpStream, ConnectResponse), NetworkError> {
+    // Connect to proxy server
+    let mut stream = TcpStream::connect(proxy_addr)
+        .await
+        .map_err(|e| NetworkError::ProxyError(format!("Failed to connect to HTTP proxy: {}", e)))?;
 
     // Build CONNECT request
-    let mut request = format!(
-        "CONNECT {}:{} HTTP/1.1\r\nHost: {}:{}\r\n",
-        target_host, target_port, target_host, target_port
-    );
+    let mut request = format!("CONNECT {} HTTP/1.1\r\nHost: {}\r\n", target, target);
 
     // Add authentication if provided
-    if let Some(auth) = auth {
-        let encoded = auth.encode_basic();
-        request.push_str(&format!("Proxy-Authorization: Basic {}\r\n", encoded));
+    if let Some(authorization) = authorization {
+        request.push_str(&format!("Proxy-Authorization: {}\r\n", authorization));
     }
 
     // End of headers
@@ -65,50 +160,81 @@ pub async fn connect(
         .await
         .map_err(|e| NetworkError::ProxyError(format!("Failed to send CONNECT request: {}", e)))?;
 
-    // Read response
-    let mut reader = BufReader::new(&mut stream);
-    let mut status_line = String::new();
-    reader
-        .read_line(&mut status_line)
-        .await
-        .map_err(|e| NetworkError::ProxyError(format!("Failed to read proxy response: {}", e)))?;
-
-    // Parse status code
-    let parts: Vec<&str> = status_line.split_whitespace().collect();
-    if parts.len() < 2 {
-        return Err(NetworkError::ProxyError(
-            "Invalid HTTP response from proxy".to_string(),
-        ));
-    }
+    // Read response head; the tunnel starts right after it
+    let head = read_response_head(&mut stream).await?;
+    let response = parse_response_head(&head)?;
+    Ok((stream, response))
+}
+
+/// Read the proxy's response up to and including the blank line
+///
+/// Reads byte by byte so no data sent through the tunnel after the head is
+/// consumed, however the response is split across reads.
+async fn read_response_head(stream: &mut TcpStream) -> Result<Vec<u8>, NetworkError> {
+    let mut head = Vec::new();
+    let mut byte = [0u8; 1];
 
-    let status_code = parts[1].parse::<u16>().map_err(|_| {
-        NetworkError::ProxyError("Invalid status code in proxy response".to_string())
-    })?;
-
-    // Check if connection was successful
-    if status_code != 200 {
-        return Err(NetworkError::ProxyError(format!(
-            "Proxy returned error: {} {}",
-            status_code,
-            parts.get(2).unwrap_or(&"")
-        )));
+    while !(head.ends_with(b"\r\n\r\n") || head.ends_with(b"\n\n")) {
+        if head.len() >= MAX_RESPONSE_HEAD {
+            return Err(NetworkError::ProxyError(
+                "Proxy response headers too large".to_string(),
+            ));
+        }
+        let read = stream.read(&mut byte).await.map_err(|e| {
+            NetworkError::ProxyError(format!("Failed to read proxy response: {}", e))
+        })?;
+        if read == 0 {
+            return Err(NetworkError::ProxyError(
+                "Proxy closed the connection before completing its response".to_string(),
+            ));
+        }
+        head.push(byte[0]);
     }
 
-    // Read remaining headers until empty line
-    loop {
-        let mut line = String::new();
-        reader
-            .read_line(&mut line)
-            .await
-            .map_err(|e| NetworkError::ProxyError(format!("Failed to read headers: {}", e)))?;
+    Ok(head)
+}
 
-        if line == "\r\n" || line == "\n" {
-            break;
-        }
+/// Parse the status line and headers of the proxy's response
+fn parse_response_head(head: &[u8]) -> Result<ConnectResponse, NetworkError> {
+    let malformed = || {
+        NetworkError::ProxyError(format!(
+            "Malformed proxy response: {:?}",
+            String::from_utf8_lossy(head)
+                .lines()
+                .next()
+                .unwrap_or_default()
+        ))
+    };
+
+    let head = std::str::from_utf8(head).map_err(|_| malformed())?;
+    let mut lines = head.lines();
+
+    // Status line: HTTP/1.x SP status SP reason
+    let status_line = lines.next().ok_or_else(malformed)?;
+    let mut parts = status_line.splitn(3, ' ');
+    let version = parts.next().unwrap_or_default();
+    if !version.starts_with("HTTP/") {
+        return Err(malformed());
     }
+    let status = parts
+        .next()
+        .filter(|code| code.len() == 3)
+        .and_then(|code| code.parse::<u16>().ok())
+        .filter(|code| (100..=599).contains(code))
+        .ok_or_else(malformed)?;
+    let reason = parts.next().unwrap_or_default().trim().to_string();
 
-    // Return the underlying stream (now tunneled to target)
-    Ok(stream)
+    let proxy_authenticate = lines
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("proxy-authenticate"))
+        .map(|(_, value)| value.trim().to_string())
+        .collect();
+
+    Ok(ConnectResponse {
+        status,
+        reason,
+        proxy_authenticate,
+    })
 }
 
 #[cfg(test)]
@@ -145,4 +271,27 @@ mod tests {
         assert!(header.contains("Proxy-Authorization: Basic"));
         assert!(header.contains(&encoded));
     }
+
+    #[test]
+    fn test_parse_response_head() {
+        let response = parse_response_head(
+            b"HTTP/1.1 407 Proxy Authentication Required\r\nProxy-Authenticate: Basic realm=\"corp\"\r\n\r\n",
+        )
+        .unwrap();
+        assert_eq!(response.status, 407);
+        assert_eq!(response.reason, "Proxy Authentication Required");
+        assert_eq!(response.proxy_authenticate, vec!["Basic realm=\"corp\""]);
+
+        let response = parse_response_head(b"HTTP/1.0 200\r\n\r\n").unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.reason, "");
+    }
+
+    #[test]
+    fn test_parse_response_head_malformed() {
+        assert!(parse_response_head(b"garbage\r\n\r\n").is_err());
+        assert!(parse_response_head(b"HTTP/1.1 OK\r\n\r\n").is_err());
+        assert!(parse_response_head(b"HTTP/1.1 2000 OK\r\n\r\n").is_err());
+        assert!(parse_response_head(b"\xff\xfe\r\n\r\n").is_err());
+    }
 }
@@ -32,12 +32,16 @@
 
 use network_errors::NetworkError;
 use tokio::net::TcpStream;
+use url::Url;
 
 mod http_proxy;
 mod socks5;
 mod auth;
+mod digest;
+mod resolver;
 
 pub use auth::ProxyAuth;
+pub use resolver::ProxyResolver;
 
 /// Proxy configuration options
 ///
@@ -68,11 +72,40 @@ pub enum ProxyConfig {
     },
 }
 
+/// Check whether an error means the proxy wants (other) credentials
+///
+/// True for the error returned when an HTTP proxy answers CONNECT with
+/// `407 Proxy Authentication Required`, so the caller can retry with
+/// [`ProxyAuth`] credentials.
+///
+/// # Examples
+///
+/// ```no_run
+/// use proxy_support::{is_proxy_auth_required, ProxyClient, ProxyConfig};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = ProxyClient::new(ProxyConfig::Http {
+///     host: "proxy.example.com".to_string(),
+///     port: 8080,
+///     auth: None,
+/// });
+/// match client.connect("example.com", 443).await {
+///     Err(e) if is_proxy_auth_required(&e) => { /* prompt for credentials */ }
+///     other => { other?; }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn is_proxy_auth_required(error: &NetworkError) -> bool {
+    matches!(error, NetworkError::ProxyError(message) if message.starts_with(http_proxy::AUTH_REQUIRED))
+}
+
 /// Proxy client for establishing connections through proxies
 ///
 /// Handles connection establishment through HTTP CONNECT or SOCKS5 proxies.
 pub struct ProxyClient {
     config: ProxyConfig,
+    resolver: Option<ProxyResolver>,
 }
 
 impl ProxyClient {
@@ -91,7 +124,29 @@ impl ProxyClient {
     /// let client = ProxyClient::new(config);
     /// ```
     pub fn new(config: ProxyConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            resolver: None,
+        }
+    }
+
+    /// Create a proxy client that selects the proxy per target URL
+    ///
+    /// [`connect_for_url`](Self::connect_for_url) consults `resolver`;
+    /// [`connect`](Self::connect) connects directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use proxy_support::{ProxyClient, ProxyResolver};
+    ///
+    /// let client = ProxyClient::with_resolver(ProxyResolver::new().bypass("localhost"));
+    /// ```
+    pub fn with_resolver(resolver: ProxyResolver) -> Self {
+        Self {
+            config: ProxyConfig::None,
+            resolver: Some(resolver),
+        }
     }
 
     /// Get a reference to the proxy configuration
@@ -142,7 +197,41 @@ impl ProxyClient {
         target_host: &str,
         target_port: u16,
     ) -> Result<TcpStream, NetworkError> {
-        match &self.config {
+        self.connect_via(&self.config, target_host, target_port)
+            .await
+    }
+
+    /// Establish a connection to the host of `url`
+    ///
+    /// The proxy is chosen by the client's [`ProxyResolver`], falling back to
+    /// the client's configuration if it has none. The port defaults to the
+    /// scheme's well-known port.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidUrl` if the URL has no host or port, and
+    /// otherwise the same errors as [`connect`](Self::connect).
+    pub async fn connect_for_url(&self, url: &Url) -> Result<TcpStream, NetworkError> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| NetworkError::InvalidUrl(format!("URL has no host: {}", url)))?;
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| NetworkError::InvalidUrl(format!("URL has no port: {}", url)))?;
+
+        match &self.resolver {
+            Some(resolver) => self.connect_via(&resolver.resolve(url), host, port).await,
+            None => self.connect_via(&self.config, host, port).await,
+        }
+    }
+
+    async fn connect_via(
+        &self,
+        config: &ProxyConfig,
+        target_host: &str,
+        target_port: u16,
+    ) -> Result<TcpStream, NetworkError> {
+        match config {
             ProxyConfig::None => {
                 // Direct connection
                 self.direct_connect(target_host, target_port).await
@@ -1,3 +1,6 @@
+mod test_http_connect;
+mod test_proxy_resolver;
+
 use proxy_support::{ProxyAuth, ProxyClient, ProxyConfig};
 
 #[tokio::test]
@@ -0,0 +1,346 @@
+//! Integration tests for HTTP CONNECT response handling
+
+use network_errors::NetworkError;
+use proxy_support::{is_proxy_auth_required, ProxyAuth, ProxyClient, ProxyConfig};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Accept one CONNECT request and answer with `chunks`, written separately
+///
+/// Returns the request head the proxy received.
+async fn scripted_proxy(chunks: Vec<&'static [u8]>) -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let mut request = Vec::new();
+        let mut byte = [0u8; 1];
+        while !request.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).await.unwrap();
+            request.push(byte[0]);
+        }
+
+        for chunk in chunks {
+            stream.write_all(chunk).await.unwrap();
+            stream.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        String::from_utf8(request).unwrap()
+    });
+    (port, handle)
+}
+
+fn http_proxy(port: u16, auth: Option<ProxyAuth>) -> ProxyClient {
+    ProxyClient::new(ProxyConfig::Http {
+        host: "127.0.0.1".to_string(),
+        port,
+        auth,
+    })
+}
+
+#[tokio::test]
+async fn test_connect_success_keeps_tunneled_bytes() {
+    // The head arrives in pieces and the target's first bytes follow it directly
+    let (port, proxy) = scripted_proxy(vec![
+        b"HTTP/1.1 200 Conn",
+        b"ection established\r\nVia: 1.1 fake\r\n",
+        b"\r\nhello",
+    ])
+    .await;
+
+    let mut stream = http_proxy(port, None)
+        .connect("example.com", 443)
+        .await
+        .unwrap();
+
+    let mut greeting = [0u8; 5];
+    stream.read_exact(&mut greeting).await.unwrap();
+    assert_eq!(&greeting, b"hello");
+
+    let request = proxy.await.unwrap();
+    assert!(request.starts_with("CONNECT example.com:443 HTTP/1.1\r\n"));
+}
+
+#[tokio::test]
+async fn test_connect_auth_required() {
+    let (port, proxy) = scripted_proxy(vec![
+        b"HTTP/1.1 40",
+        b"7 Proxy Authentication Required\r\nProxy-Authenticate: Basic realm=\"corp\"\r\n\r\n",
+    ])
+    .await;
+
+    let err = http_proxy(port, None)
+        .connect("example.com", 443)
+        .await
+        .unwrap_err();
+    proxy.await.unwrap();
+
+    assert!(is_proxy_auth_required(&err));
+    match err {
+        NetworkError::ProxyError(message) => {
+            assert!(message.contains("407 Proxy Authentication Required"));
+            assert!(message.contains("Basic realm=\"corp\""));
+        }
+        other => panic!("Expected ProxyError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_connect_refused_reports_status() {
+    let (port, proxy) = scripted_proxy(vec![b"HTTP/1.1 403 Forbidden\r\n\r\n"]).await;
+
+    let err = http_proxy(
+        port,
+        Some(ProxyAuth::Basic {
+            username: "user".to_string(),
+            password: "pass".to_string(),
+        }),
+    )
+    .connect("example.com", 443)
+    .await
+    .unwrap_err();
+
+    let request = proxy.await.unwrap();
+    assert!(request.contains("Proxy-Authorization: Basic "));
+    assert!(!is_proxy_auth_required(&err));
+    match err {
+        NetworkError::ProxyError(message) => assert!(message.contains("403 Forbidden")),
+        other => panic!("Expected ProxyError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_connect_malformed_response() {
+    let (port, proxy) = scripted_proxy(vec![b"SSH-2.0-OpenSSH\r\n", b"\r\n"]).await;
+
+    let err = http_proxy(port, None)
+        .connect("example.com", 443)
+        .await
+        .unwrap_err();
+    proxy.await.unwrap();
+
+    assert!(!is_proxy_auth_required(&err));
+    match err {
+        NetworkError::ProxyError(message) => assert!(message.contains("Malformed")),
+        other => panic!("Expected ProxyError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_connect_truncated_response() {
+    let (port, proxy) = scripted_proxy(vec![b"HTTP/1.1 200 Connection established\r\n"]).await;
+
+    let err = http_proxy(port, None)
+        .connect("example.com", 443)
+        .await
+        .unwrap_err();
+    proxy.await.unwrap();
+
+    assert!(matches!(err, NetworkError::ProxyError(_)));
+}
+
+/// Read one request head from `stream`
+async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut byte = [0u8; 1];
+    while !request.ends_with(b"\r\n\r\n") {
+        stream.read_exact(&mut byte).await.unwrap();
+        request.push(byte[0]);
+    }
+    String::from_utf8(request).unwrap()
+}
+
+/// Pull `name=value` or `name="value"` out of a Digest header
+fn digest_param<'a>(header: &'a str, name: &str) -> &'a str {
+    let start = header.find(&format!("{}=", name)).unwrap() + name.len() + 1;
+    let rest = &header[start..];
+    match rest.strip_prefix('"') {
+        Some(quoted) => &quoted[..quoted.find('"').unwrap()],
+        None => &rest[..rest.find(',').unwrap_or(rest.len())],
+    }
+}
+
+#[tokio::test]
+async fn test_connect_digest_auth() {
+    use md5::{Digest, Md5};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let proxy = tokio::spawn(async move {
+        // First attempt: no credentials, answer with Basic and Digest challenges
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        assert!(!request.contains("Proxy-Authorization"));
+        stream
+            .write_all(
+                b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                  Proxy-Authenticate: Basic realm=\"corp\"\r\n\
+                  Proxy-Authenticate: Digest realm=\"corp\", qop=\"auth\", \
+                  nonce=\"abc123\", opaque=\"xyz\"\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        drop(stream);
+
+        // Second attempt on a new connection carries the Digest response
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        let header = request
+            .lines()
+            .find_map(|line| line.strip_prefix("Proxy-Authorization: Digest "))
+            .unwrap()
+            .to_string();
+
+        let hash = |data: String| format!("{:x}", Md5::digest(data.as_bytes()));
+        let ha1 = hash("alice:corp:secret".to_string());
+        let ha2 = hash("CONNECT:example.com:443".to_string());
+        let expected = hash(format!(
+            "{}:abc123:{}:{}:auth:{}",
+            ha1,
+            digest_param(&header, "nc"),
+            digest_param(&header, "cnonce"),
+            ha2
+        ));
+        assert_eq!(digest_param(&header, "uri"), "example.com:443");
+        assert_eq!(digest_param(&header, "opaque"), "xyz");
+        assert_eq!(digest_param(&header, "response"), expected);
+
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+    });
+
+    let client = http_proxy(
+        port,
+        Some(ProxyAuth::Digest {
+            username: "alice".to_string(),
+            password: "secret".to_string(),
+        }),
+    );
+    client.connect("example.com", 443).await.unwrap();
+    proxy.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_digest_skips_unsupported_challenge() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let proxy = tokio::spawn(async move {
+        // The preferred challenge uses an algorithm the client lacks
+        let (mut stream, _) = listener.accept().await.unwrap();
+        read_request(&mut stream).await;
+        stream
+            .write_all(
+                b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                  Proxy-Authenticate: Digest realm=\"corp\", nonce=\"skip\", \
+                  algorithm=SHA-512-256\r\n\
+                  Proxy-Authenticate: Digest realm=\"corp\", nonce=\"use\", \
+                  algorithm=SHA-256\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        drop(stream);
+
+        // The answer is to the supported challenge that followed it
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let request = read_request(&mut stream).await;
+        let header = request
+            .lines()
+            .find_map(|line| line.strip_prefix("Proxy-Authorization: Digest "))
+            .unwrap()
+            .to_string();
+        assert_eq!(digest_param(&header, "nonce"), "use");
+        assert_eq!(digest_param(&header, "algorithm"), "SHA-256");
+
+        stream
+            .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+            .await
+            .unwrap();
+    });
+
+    let client = http_proxy(
+        port,
+        Some(ProxyAuth::Digest {
+            username: "alice".to_string(),
+            password: "secret".to_string(),
+        }),
+    );
+    client.connect("example.com", 443).await.unwrap();
+    proxy.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_connect_digest_without_usable_challenge_fails() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let proxy = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        read_request(&mut stream).await;
+        stream
+            .write_all(
+                b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                  Proxy-Authenticate: Digest realm=\"corp\", nonce=\"n\", \
+                  algorithm=MD5-sess\r\n\r\n",
+            )
+            .await
+            .unwrap();
+    });
+
+    let err = http_proxy(
+        port,
+        Some(ProxyAuth::Digest {
+            username: "alice".to_string(),
+            password: "secret".to_string(),
+        }),
+    )
+    .connect("example.com", 443)
+    .await
+    .unwrap_err();
+    proxy.await.unwrap();
+
+    match err {
+        NetworkError::ProxyError(message) => assert!(message.contains("MD5-sess")),
+        other => panic!("Expected a proxy error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_connect_digest_rejected() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let proxy = tokio::spawn(async move {
+        // Challenge both the first attempt and the answered one
+        let mut authorized = Vec::new();
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut stream).await;
+            authorized.push(request.contains("Proxy-Authorization: Digest "));
+            stream
+                .write_all(
+                    b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+                      Proxy-Authenticate: Digest realm=\"corp\", nonce=\"n\"\r\n\r\n",
+                )
+                .await
+                .unwrap();
+        }
+        authorized
+    });
+
+    let err = http_proxy(
+        port,
+        Some(ProxyAuth::Digest {
+            username: "alice".to_string(),
+            password: "wrong".to_string(),
+        }),
+    )
+    .connect("example.com", 443)
+    .await
+    .unwrap_err();
+
+    assert_eq!(proxy.await.unwrap(), vec![false, true]);
+    assert!(is_proxy_auth_required(&err));
+}
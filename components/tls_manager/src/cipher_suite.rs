@@ -0,0 +1,117 @@
+//! Cipher suite and protocol version selection
+
+use rustls::crypto::ring::cipher_suite;
+use rustls::SupportedCipherSuite;
+use rustls::SupportedProtocolVersion;
+
+/// TLS protocol version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TlsVersion {
+    /// TLS 1.2
+    Tls12,
+    /// TLS 1.3
+    Tls13,
+}
+
+impl TlsVersion {
+    /// The matching rustls protocol version
+    pub(crate) fn to_rustls(self) -> &'static SupportedProtocolVersion {
+        match self {
+            TlsVersion::Tls12 => &rustls::version::TLS12,
+            TlsVersion::Tls13 => &rustls::version::TLS13,
+        }
+    }
+}
+
+/// Cipher suite offered during the TLS handshake
+///
+/// Covers every suite the rustls *ring* provider implements. TLS 1.3 suites
+/// can only be negotiated over TLS 1.3, and the ECDHE suites only over TLS
+/// 1.2; [`CipherSuite::version`] tells which.
+///
+/// # Examples
+///
+/// ```
+/// use tls_manager::{CipherSuite, TlsVersion};
+///
+/// assert_eq!(CipherSuite::Tls13Aes256GcmSha384.version(), TlsVersion::Tls13);
+/// assert_eq!(
+///     CipherSuite::EcdheRsaWithAes128GcmSha256.version(),
+///     TlsVersion::Tls12
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CipherSuite {
+    /// TLS13_AES_256_GCM_SHA384
+    Tls13Aes256GcmSha384,
+    /// TLS13_AES_128_GCM_SHA256
+    Tls13Aes128GcmSha256,
+    /// TLS13_CHACHA20_POLY1305_SHA256
+    Tls13Chacha20Poly1305Sha256,
+    /// TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+    EcdheEcdsaWithAes256GcmSha384,
+    /// TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256
+    EcdheEcdsaWithAes128GcmSha256,
+    /// TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256
+    EcdheEcdsaWithChacha20Poly1305Sha256,
+    /// TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384
+    EcdheRsaWithAes256GcmSha384,
+    /// TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256
+    EcdheRsaWithAes128GcmSha256,
+    /// TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256
+    EcdheRsaWithChacha20Poly1305Sha256,
+}
+
+impl CipherSuite {
+    /// All supported cipher suites, in rustls's default preference order
+    pub const ALL: [CipherSuite; 9] = [
+        CipherSuite::Tls13Aes256GcmSha384,
+        CipherSuite::Tls13Aes128GcmSha256,
+        CipherSuite::Tls13Chacha20Poly1305Sha256,
+        CipherSuite::EcdheEcdsaWithAes256GcmSha384,
+        CipherSuite::EcdheEcdsaWithAes128GcmSha256,
+        CipherSuite::EcdheEcdsaWithChacha20Poly1305Sha256,
+        CipherSuite::EcdheRsaWithAes256GcmSha384,
+        CipherSuite::EcdheRsaWithAes128GcmSha256,
+        CipherSuite::EcdheRsaWithChacha20Poly1305Sha256,
+    ];
+
+    /// The protocol version this suite can be negotiated with
+    pub fn version(self) -> TlsVersion {
+        match self {
+            CipherSuite::Tls13Aes256GcmSha384
+            | CipherSuite::Tls13Aes128GcmSha256
+            | CipherSuite::Tls13Chacha20Poly1305Sha256 => TlsVersion::Tls13,
+            _ => TlsVersion::Tls12,
+        }
+    }
+
+    /// The matching rustls cipher suite
+    pub(crate) fn to_rustls(self) -> SupportedCipherSuite {
+        match self {
+            CipherSuite::Tls13Aes256GcmSha384 => cipher_suite::TLS13_AES_256_GCM_SHA384,
+            CipherSuite::Tls13Aes128GcmSha256 => cipher_suite::TLS13_AES_128_GCM_SHA256,
+            CipherSuite::Tls13Chacha20Poly1305Sha256 => {
+                cipher_suite::TLS13_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::EcdheEcdsaWithAes256GcmSha384 => {
+                cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::EcdheEcdsaWithAes128GcmSha256 => {
+                cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256
+            }
+            CipherSuite::EcdheEcdsaWithChacha20Poly1305Sha256 => {
+                cipher_suite::TLS_ECDHE_ECDSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+            CipherSuite::EcdheRsaWithAes256GcmSha384 => {
+                cipher_suite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384
+            }
+            CipherSuite::EcdheRsaWithAes128GcmSha256 => {
+                cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256
+            }
+            CipherSuite::EcdheRsaWithChacha20Poly1305Sha256 => {
+                cipher_suite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256
+            }
+        }
+    }
+}
@@ -6,15 +6,30 @@
 #![warn(rust_2018_idioms)]
 
 use certificate_pinning::{CertificatePinner, PinResult};
+use certificate_transparency::{CtVerifier, SignedCertificateTimestamp};
 use network_errors::NetworkError;
-use rustls::RootCertStore;
+use rustls::client::danger::ServerCertVerifier;
+use rustls::client::{Resumption, WebPkiServerVerifier};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, RootCertStore};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use x509_parser::prelude::*;
 
+mod cipher_suite;
+mod session_cache;
+
+pub use cipher_suite::{CipherSuite, TlsVersion};
+pub use session_cache::{TlsSessionCache, DEFAULT_SESSION_CACHE_CAPACITY};
+
 /// TLS configuration builder
 ///
 /// Provides a builder pattern for configuring TLS settings including
-/// ALPN protocols, root certificates, and session resumption.
+/// ALPN protocols, root certificates, protocol versions, cipher suites, and
+/// session resumption.
 ///
 /// # Examples
 ///
@@ -28,6 +43,9 @@ use x509_parser::prelude::*;
 pub struct TlsConfig {
     alpn_protocols: Vec<Vec<u8>>,
     root_cert_store: Option<RootCertStore>,
+    session_cache: Option<Arc<TlsSessionCache>>,
+    protocol_versions: Vec<TlsVersion>,
+    cipher_suites: Option<Vec<CipherSuite>>,
 }
 
 impl TlsConfig {
@@ -44,6 +62,9 @@ impl TlsConfig {
         Self {
             alpn_protocols: Vec::new(),
             root_cert_store: None,
+            session_cache: None,
+            protocol_versions: vec![TlsVersion::Tls13, TlsVersion::Tls12],
+            cipher_suites: None,
         }
     }
 
@@ -92,6 +113,102 @@ impl TlsConfig {
         self
     }
 
+    /// Resume sessions through a shared session cache
+    ///
+    /// Connections made with this configuration store the session tickets
+    /// servers issue in `cache`, and later connections to the same host
+    /// resume from them instead of doing a full handshake.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - Session cache, which may be shared with other configurations
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tls_manager::{TlsConfig, TlsSessionCache};
+    ///
+    /// let config = TlsConfig::new()
+    ///     .with_session_cache(Arc::new(TlsSessionCache::default()));
+    /// ```
+    pub fn with_session_cache(mut self, cache: Arc<TlsSessionCache>) -> Self {
+        self.session_cache = Some(cache);
+        self
+    }
+
+    /// Set the protocol versions to enable
+    ///
+    /// Both TLS 1.3 and TLS 1.2 are enabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `versions` - Protocol versions that may be negotiated
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tls_manager::{TlsConfig, TlsVersion};
+    ///
+    /// let config = TlsConfig::new()
+    ///     .with_protocol_versions(vec![TlsVersion::Tls13]);
+    /// ```
+    pub fn with_protocol_versions(mut self, versions: Vec<TlsVersion>) -> Self {
+        self.protocol_versions = versions;
+        self
+    }
+
+    /// Restrict the cipher suites offered to `suites`
+    ///
+    /// Suites are offered in the given order. Without this, every suite in
+    /// [`CipherSuite::ALL`] is offered. The list is checked against the
+    /// enabled protocol versions by [`validate`](Self::validate).
+    ///
+    /// # Arguments
+    ///
+    /// * `suites` - Allowed cipher suites, most preferred first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tls_manager::{CipherSuite, TlsConfig};
+    ///
+    /// let config = TlsConfig::new().with_cipher_suites(vec![
+    ///     CipherSuite::Tls13Aes256GcmSha384,
+    ///     CipherSuite::EcdheRsaWithAes256GcmSha384,
+    /// ]);
+    /// ```
+    pub fn with_cipher_suites(mut self, suites: Vec<CipherSuite>) -> Self {
+        self.cipher_suites = Some(suites);
+        self
+    }
+
+    /// Stop offering the cipher suites in `denied`
+    ///
+    /// Removes them from the suites set with
+    /// [`with_cipher_suites`](Self::with_cipher_suites), or from
+    /// [`CipherSuite::ALL`] if none were set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tls_manager::{CipherSuite, TlsConfig};
+    ///
+    /// let config = TlsConfig::new().without_cipher_suites(&[
+    ///     CipherSuite::Tls13Chacha20Poly1305Sha256,
+    ///     CipherSuite::EcdheEcdsaWithChacha20Poly1305Sha256,
+    ///     CipherSuite::EcdheRsaWithChacha20Poly1305Sha256,
+    /// ]);
+    ///
+    /// assert_eq!(config.cipher_suites().len(), 6);
+    /// ```
+    pub fn without_cipher_suites(mut self, denied: &[CipherSuite]) -> Self {
+        let mut suites = self.cipher_suites().to_vec();
+        suites.retain(|suite| !denied.contains(suite));
+        self.cipher_suites = Some(suites);
+        self
+    }
+
     /// Get the configured ALPN protocols
     ///
     /// # Returns
@@ -109,6 +226,116 @@ impl TlsConfig {
     pub fn root_cert_store(&self) -> Option<&RootCertStore> {
         self.root_cert_store.as_ref()
     }
+
+    /// Get the session cache
+    ///
+    /// # Returns
+    ///
+    /// Optional reference to the shared session cache
+    pub fn session_cache(&self) -> Option<&Arc<TlsSessionCache>> {
+        self.session_cache.as_ref()
+    }
+
+    /// Get the enabled protocol versions
+    ///
+    /// # Returns
+    ///
+    /// Slice of protocol versions
+    pub fn protocol_versions(&self) -> &[TlsVersion] {
+        &self.protocol_versions
+    }
+
+    /// Get the cipher suites that will be offered
+    ///
+    /// # Returns
+    ///
+    /// Slice of cipher suites, most preferred first
+    pub fn cipher_suites(&self) -> &[CipherSuite] {
+        self.cipher_suites.as_deref().unwrap_or(&CipherSuite::ALL)
+    }
+
+    /// Check that the protocol versions and cipher suites can be used together
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidConfig` if no protocol version or no
+    /// cipher suite is enabled, or if an enabled protocol version has no
+    /// cipher suite it can negotiate (such as only TLS 1.2 suites with
+    /// TLS 1.3 as the only version)
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        if self.protocol_versions.is_empty() {
+            return Err(NetworkError::InvalidConfig(
+                "No TLS protocol versions enabled".to_string(),
+            ));
+        }
+        if self.cipher_suites().is_empty() {
+            return Err(NetworkError::InvalidConfig(
+                "No cipher suites enabled".to_string(),
+            ));
+        }
+        for version in &self.protocol_versions {
+            if !self
+                .cipher_suites()
+                .iter()
+                .any(|suite| suite.version() == *version)
+            {
+                return Err(NetworkError::InvalidConfig(format!(
+                    "No enabled cipher suite supports {:?}",
+                    version
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a rustls client configuration from these settings
+    ///
+    /// Uses the configured root certificates, or the Mozilla roots from
+    /// `webpki-roots` if none were set. Without a session cache, rustls keeps
+    /// its own in-memory cache for the returned configuration.
+    ///
+    /// # Returns
+    ///
+    /// Client configuration for use with `tokio-rustls`
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidConfig` if [`validate`](Self::validate)
+    /// fails
+    pub fn to_client_config(&self) -> Result<ClientConfig, NetworkError> {
+        self.validate()?;
+
+        let roots = self
+            .root_cert_store
+            .clone()
+            .unwrap_or_else(|| RootCertStore {
+                roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+            });
+        let provider = CryptoProvider {
+            cipher_suites: self
+                .cipher_suites()
+                .iter()
+                .map(|suite| suite.to_rustls())
+                .collect(),
+            ..rustls::crypto::ring::default_provider()
+        };
+        let versions: Vec<_> = self
+            .protocol_versions
+            .iter()
+            .map(|version| version.to_rustls())
+            .collect();
+
+        let mut config = ClientConfig::builder_with_provider(Arc::new(provider))
+            .with_protocol_versions(&versions)
+            .map_err(|e| NetworkError::InvalidConfig(format!("Invalid TLS configuration: {}", e)))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        config.alpn_protocols = self.alpn_protocols.clone();
+        if let Some(cache) = &self.session_cache {
+            config.resumption = Resumption::store(cache.clone());
+        }
+        Ok(config)
+    }
 }
 
 impl Default for TlsConfig {
@@ -117,6 +344,12 @@ impl Default for TlsConfig {
     }
 }
 
+/// OID of the X.509 extension carrying embedded SCTs (RFC 6962, section 3.3)
+const SCT_LIST_OID: &str = "1.3.6.1.4.1.11129.2.4.2";
+
+/// OID of the poison extension marking a CT precertificate (RFC 6962, section 3.1)
+const CT_POISON_OID: &str = "1.3.6.1.4.1.11129.2.4.3";
+
 /// Certificate store for managing and validating certificates
 ///
 /// Handles certificate validation, including chain verification,
@@ -136,6 +369,9 @@ pub struct CertificateStore {
     certificates: Vec<Vec<u8>>,
     root_cert_store: RootCertStore,
     cert_pinner: CertificatePinner,
+    ct_required: bool,
+    ct_logs: HashSet<Vec<u8>>,
+    min_sct_count: usize,
 }
 
 impl CertificateStore {
@@ -166,9 +402,50 @@ impl CertificateStore {
             certificates: Vec::new(),
             root_cert_store,
             cert_pinner: CertificatePinner::new(),
+            ct_required: false,
+            ct_logs: HashSet::new(),
+            min_sct_count: 1,
         }
     }
 
+    /// Require Certificate Transparency for verified certificates
+    ///
+    /// When required, `verify_certificate` rejects certificates that don't
+    /// embed at least [`set_min_sct_count`](Self::set_min_sct_count) SCTs
+    /// from distinct logs in the trusted log list. Off by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tls_manager::CertificateStore;
+    ///
+    /// let mut store = CertificateStore::new();
+    /// store.require_ct(true);
+    /// assert!(store.is_ct_required());
+    /// ```
+    pub fn require_ct(&mut self, required: bool) {
+        self.ct_required = required;
+    }
+
+    /// Whether Certificate Transparency is required
+    pub fn is_ct_required(&self) -> bool {
+        self.ct_required
+    }
+
+    /// Replace the list of trusted CT logs
+    ///
+    /// Logs are identified by their 32-byte log ID (the SHA-256 hash of the
+    /// log's public key). SCTs from other logs don't count towards the
+    /// minimum.
+    pub fn set_ct_logs(&mut self, log_ids: impl IntoIterator<Item = Vec<u8>>) {
+        self.ct_logs = log_ids.into_iter().collect();
+    }
+
+    /// Set how many SCTs from distinct trusted logs a certificate needs (default 1)
+    pub fn set_min_sct_count(&mut self, count: usize) {
+        self.min_sct_count = count;
+    }
+
     /// Add a certificate pin for a specific hostname
     ///
     /// When a hostname has pins configured, certificate pinning validation
@@ -200,6 +477,7 @@ impl CertificateStore {
                 hasher.update(cert_der);
                 hasher.finalize().to_vec()
             },
+            expires_at: None,
         };
 
         self.cert_pinner.add_pin(hostname, pin);
@@ -296,13 +574,37 @@ impl CertificateStore {
         Ok(())
     }
 
+    /// Trust a root CA certificate for chain validation
+    ///
+    /// The store starts out trusting the Mozilla root program (from
+    /// `webpki-roots`); this adds another trust anchor, such as an
+    /// enterprise or test CA, for [`verify_chain`](Self::verify_chain).
+    ///
+    /// # Arguments
+    ///
+    /// * `cert` - DER-encoded root certificate
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or error
+    pub fn add_root_certificate(&mut self, cert: Vec<u8>) -> Result<(), NetworkError> {
+        self.root_cert_store
+            .add(CertificateDer::from(cert))
+            .map_err(|e| NetworkError::CertificateError(format!("Invalid root certificate: {}", e)))
+    }
+
     /// Verify a certificate against the store
     ///
-    /// Performs comprehensive validation including:
+    /// Performs validation of the certificate on its own, including:
     /// - Certificate pinning (if configured for hostname)
-    /// - Certificate chain validation to trusted root CA
     /// - Expiry checking (not_before/not_after dates)
     /// - Hostname verification (CN/SAN matching with wildcard support)
+    /// - Embedded SCTs from trusted logs (if CT is required)
+    ///
+    /// Without the intermediates no path to a trusted root can be built, so
+    /// the certificate's issuer isn't checked; use
+    /// [`verify_chain`](Self::verify_chain) with the chain the server
+    /// presented for that.
     ///
     /// # Arguments
     ///
@@ -329,6 +631,71 @@ impl CertificateStore {
         cert: &[u8],
         hostname: &str,
     ) -> Result<(), NetworkError> {
+        if self.verify_leaf(cert, hostname)? {
+            return Ok(());
+        }
+
+        // Note: For production, this would use rustls's WebPkiServerVerifier
+        // For now, we perform basic validation
+        self.validate_certificate_chain(cert)?;
+
+        Ok(())
+    }
+
+    /// Verify a certificate chain as presented in a TLS handshake
+    ///
+    /// `chain[0]` is the server's certificate and the rest are the
+    /// intermediates it sent, in any order. The leaf gets the same checks as
+    /// [`verify_certificate`](Self::verify_certificate) (pinning, expiry,
+    /// hostname and CT), then a path is built from it through the
+    /// intermediates to a trusted root, verifying each certificate's
+    /// signature with its issuer's key.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain` - DER-encoded certificates, leaf first
+    /// * `hostname` - Hostname to validate against
+    ///
+    /// # Returns
+    ///
+    /// Result indicating validation success or error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tls_manager::CertificateStore;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let store = CertificateStore::new();
+    /// let leaf = b"leaf certificate".to_vec();
+    /// let intermediate = b"intermediate certificate".to_vec();
+    /// let result = store.verify_chain(&[leaf, intermediate], "example.com").await;
+    /// # })
+    /// ```
+    pub async fn verify_chain(
+        &self,
+        chain: &[Vec<u8>],
+        hostname: &str,
+    ) -> Result<(), NetworkError> {
+        let Some((leaf, intermediates)) = chain.split_first() else {
+            return Err(NetworkError::CertificateError(
+                "Certificate chain cannot be empty".to_string(),
+            ));
+        };
+
+        if self.verify_leaf(leaf, hostname)? {
+            return Ok(());
+        }
+
+        self.validate_chain_to_root(leaf, intermediates, hostname)
+    }
+
+    /// Checks of the leaf certificate shared by `verify_certificate` and
+    /// `verify_chain`
+    ///
+    /// Returns `true` if the certificate matched a pin for `hostname`, in
+    /// which case no further validation is needed.
+    fn verify_leaf(&self, cert: &[u8], hostname: &str) -> Result<bool, NetworkError> {
         // Validate inputs
         if cert.is_empty() {
             return Err(NetworkError::CertificateError(
@@ -347,7 +714,7 @@ impl CertificateStore {
         match self.cert_pinner.verify(hostname, cert) {
             PinResult::Valid => {
                 // Certificate matches pin - validation succeeds
-                return Ok(());
+                return Ok(true);
             }
             PinResult::Invalid { reason } => {
                 // Certificate doesn't match pin for pinned host - fail immediately
@@ -356,8 +723,8 @@ impl CertificateStore {
                     reason
                 )));
             }
-            PinResult::NotPinned => {
-                // No pins for this host - proceed with normal validation
+            PinResult::NotPinned | PinResult::Expired => {
+                // No (unexpired) pins for this host - proceed with normal validation
             }
         }
 
@@ -372,12 +739,12 @@ impl CertificateStore {
         // Step 4: Verify hostname matches certificate
         self.verify_hostname(&parsed_cert, hostname)?;
 
-        // Step 5: Validate certificate chain to trusted root
-        // Note: For production, this would use rustls's WebPkiServerVerifier
-        // For now, we perform basic validation
-        self.validate_certificate_chain(cert)?;
+        // Step 5: Check Certificate Transparency (if required)
+        if self.ct_required {
+            self.verify_embedded_scts(&parsed_cert)?;
+        }
 
-        Ok(())
+        Ok(false)
     }
 
     /// Check if certificate is within its validity period
@@ -412,6 +779,40 @@ impl CertificateStore {
         Ok(())
     }
 
+    /// Check the SCTs embedded in the certificate against the trusted log list
+    ///
+    /// Each SCT must be well-formed and come from a trusted log; only one SCT
+    /// per log counts. Log signatures aren't verified, since the log list
+    /// carries log IDs rather than keys.
+    fn verify_embedded_scts(&self, cert: &X509Certificate<'_>) -> Result<(), NetworkError> {
+        let mut scts = Vec::new();
+        for extension in cert.extensions() {
+            match extension.oid.to_id_string().as_str() {
+                CT_POISON_OID => {
+                    return Err(NetworkError::CertificateError(
+                        "Certificate is a CT precertificate".to_string(),
+                    ));
+                }
+                SCT_LIST_OID => scts.extend(parse_sct_list(extension.value)?),
+                _ => {}
+            }
+        }
+
+        let trusted_logs: HashSet<&[u8]> = scts
+            .iter()
+            .filter(|sct| sct.validate().is_ok() && self.ct_logs.contains(&sct.log_id))
+            .map(|sct| sct.log_id.as_slice())
+            .collect();
+        if trusted_logs.len() < self.min_sct_count {
+            return Err(NetworkError::CertificateError(format!(
+                "Certificate Transparency required: found SCTs from {} trusted log(s), need {}",
+                trusted_logs.len(),
+                self.min_sct_count
+            )));
+        }
+        Ok(())
+    }
+
     /// Verify hostname matches certificate Common Name or Subject Alternative Names
     ///
     /// Supports wildcard certificates (*.example.com)
@@ -507,6 +908,67 @@ impl CertificateStore {
 
         Ok(())
     }
+
+    /// Build and verify a path from `leaf` through `intermediates` to a
+    /// trusted root
+    ///
+    /// Uses rustls's `WebPkiServerVerifier`, which checks each signature
+    /// against the issuer's public key along with validity periods, CA
+    /// constraints and key usage.
+    fn validate_chain_to_root(
+        &self,
+        leaf: &[u8],
+        intermediates: &[Vec<u8>],
+        hostname: &str,
+    ) -> Result<(), NetworkError> {
+        if self.root_cert_store.is_empty() {
+            return Err(NetworkError::CertificateError(
+                "No trusted root certificates configured".to_string(),
+            ));
+        }
+
+        let verifier = WebPkiServerVerifier::builder(Arc::new(self.root_cert_store.clone()))
+            .build()
+            .map_err(|e| {
+                NetworkError::CertificateError(format!("Failed to build chain verifier: {}", e))
+            })?;
+        let server_name = ServerName::try_from(hostname).map_err(|e| {
+            NetworkError::CertificateError(format!("Invalid hostname '{}': {}", hostname, e))
+        })?;
+        let intermediates: Vec<CertificateDer<'_>> = intermediates
+            .iter()
+            .map(|cert| CertificateDer::from(cert.as_slice()))
+            .collect();
+
+        verifier
+            .verify_server_cert(
+                &CertificateDer::from(leaf),
+                &intermediates,
+                &server_name,
+                &[],
+                UnixTime::now(),
+            )
+            .map_err(|e| {
+                NetworkError::CertificateError(format!(
+                    "Certificate chain validation failed: {}",
+                    e
+                ))
+            })?;
+        Ok(())
+    }
+}
+
+/// Parse the value of the embedded SCT list extension
+///
+/// The extension value is an OCTET STRING wrapping the TLS-encoded
+/// `SignedCertificateTimestampList`.
+fn parse_sct_list(value: &[u8]) -> Result<Vec<SignedCertificateTimestamp>, NetworkError> {
+    let invalid =
+        |e: String| NetworkError::CertificateError(format!("Invalid embedded SCT list: {}", e));
+    let (_, list) = x509_parser::der_parser::der::parse_der_octetstring(value)
+        .map_err(|e| invalid(e.to_string()))?;
+    let list = list.as_slice().map_err(|e| invalid(e.to_string()))?;
+    CtVerifier::parse_sct_extension(list).map_err(|e| invalid(e.to_string()))
 }
 
 impl Default for CertificateStore {
@@ -538,7 +1000,25 @@ impl Default for CertificateStore {
 /// ```
 #[derive(Debug, Default)]
 pub struct HstsStore {
-    entries: std::collections::HashMap<String, HstsEntry>,
+    entries: HashMap<String, HstsEntry>,
+    /// Preloaded domains, mapped to whether subdomains are included
+    preload: HashMap<String, bool>,
+}
+
+/// Chromium HSTS preload list document
+#[derive(Debug, Deserialize)]
+struct PreloadList {
+    entries: Vec<PreloadEntry>,
+}
+
+/// One domain in the Chromium HSTS preload list
+#[derive(Debug, Deserialize)]
+struct PreloadEntry {
+    name: String,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    include_subdomains: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -561,13 +1041,15 @@ impl HstsStore {
     /// ```
     pub fn new() -> Self {
         Self {
-            entries: std::collections::HashMap::new(),
+            entries: HashMap::new(),
+            preload: HashMap::new(),
         }
     }
 
     /// Check if HSTS is enabled for a domain
     ///
-    /// Checks both exact domain match and parent domain with subdomain inclusion.
+    /// Checks both exact domain match and parent domain with subdomain inclusion,
+    /// against dynamic entries and the preload list.
     ///
     /// # Arguments
     ///
@@ -594,8 +1076,28 @@ impl HstsStore {
     /// assert!(store.is_hsts_enabled("sub.example.com"));
     /// ```
     pub fn is_hsts_enabled(&self, domain: &str) -> bool {
+        if Self::policy_applies(&self.entries, domain, |entry| entry.include_subdomains) {
+            return true;
+        }
+
+        // Preload list names are lowercase and have no trailing dot
+        let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+        Self::policy_applies(&self.preload, &domain, |include_subdomains| {
+            *include_subdomains
+        })
+    }
+
+    /// Check `domain` against a set of policies
+    ///
+    /// A policy applies on an exact match, or to subdomains of its domain
+    /// when `include_subdomains` says so.
+    fn policy_applies<T>(
+        policies: &HashMap<String, T>,
+        domain: &str,
+        include_subdomains: impl Fn(&T) -> bool,
+    ) -> bool {
         // Check exact domain match
-        if self.entries.contains_key(domain) {
+        if policies.contains_key(domain) {
             return true;
         }
 
@@ -603,8 +1105,8 @@ impl HstsStore {
         let parts: Vec<&str> = domain.split('.').collect();
         for i in 1..parts.len() {
             let parent_domain = parts[i..].join(".");
-            if let Some(entry) = self.entries.get(&parent_domain) {
-                if entry.include_subdomains {
+            if let Some(policy) = policies.get(&parent_domain) {
+                if include_subdomains(policy) {
                     return true;
                 }
             }
@@ -613,6 +1115,73 @@ impl HstsStore {
         false
     }
 
+    /// Load the Chromium HSTS preload list
+    ///
+    /// Reads the JSON format of Chromium's `transport_security_state_static.json`:
+    /// an object whose `entries` array holds `name`, `mode` and
+    /// `include_subdomains` fields. Only entries with `"mode": "force-https"`
+    /// are loaded; pin-only entries are skipped. Whole-line `//` comments, as
+    /// found in the Chromium source file, are allowed. Preloaded domains never
+    /// expire and are kept separately from entries learned from headers.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Source of the preload list JSON
+    ///
+    /// # Returns
+    ///
+    /// The number of preloaded domains added
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidConfig` if the list can't be read or parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tls_manager::HstsStore;
+    ///
+    /// let list = r#"{
+    ///   "entries": [
+    ///     { "name": "example.com", "mode": "force-https", "include_subdomains": true }
+    ///   ]
+    /// }"#;
+    ///
+    /// let mut store = HstsStore::new();
+    /// assert_eq!(store.load_preload_list(list.as_bytes()).unwrap(), 1);
+    /// assert!(store.is_hsts_enabled("www.example.com"));
+    /// ```
+    pub fn load_preload_list<R: std::io::Read>(
+        &mut self,
+        mut reader: R,
+    ) -> Result<usize, NetworkError> {
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).map_err(|e| {
+            NetworkError::InvalidConfig(format!("Failed to read HSTS preload list: {}", e))
+        })?;
+
+        let json: String = contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let list: PreloadList = serde_json::from_str(&json).map_err(|e| {
+            NetworkError::InvalidConfig(format!("Invalid HSTS preload list: {}", e))
+        })?;
+
+        let mut loaded = 0;
+        for entry in list.entries {
+            if entry.mode.as_deref() != Some("force-https") {
+                continue;
+            }
+            let name = entry.name.trim_end_matches('.').to_ascii_lowercase();
+            self.preload.insert(name, entry.include_subdomains);
+            loaded += 1;
+        }
+
+        Ok(loaded)
+    }
+
     /// Add an HSTS entry for a domain
     ///
     /// # Arguments
@@ -0,0 +1,232 @@
+//! TLS session resumption cache
+
+use rustls::client::{ClientSessionStore, Tls12ClientSessionValue, Tls13ClientSessionValue};
+use rustls::pki_types::ServerName;
+use rustls::NamedGroup;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// Default number of hosts a [`TlsSessionCache`] keeps sessions for
+pub const DEFAULT_SESSION_CACHE_CAPACITY: usize = 256;
+
+/// Most TLS 1.3 tickets kept per host; each ticket resumes one connection
+const MAX_TLS13_TICKETS_PER_HOST: usize = 8;
+
+/// Resumption state remembered for one host
+#[derive(Default)]
+struct HostSessions {
+    kx_hint: Option<NamedGroup>,
+    tls12: Option<Tls12ClientSessionValue>,
+    tls13: VecDeque<Tls13ClientSessionValue>,
+}
+
+impl HostSessions {
+    fn is_resumable(&self) -> bool {
+        self.tls12.is_some() || !self.tls13.is_empty()
+    }
+}
+
+/// Hosts in insertion order, so the oldest can be evicted
+#[derive(Default)]
+struct Hosts {
+    sessions: HashMap<ServerName<'static>, HostSessions>,
+    order: VecDeque<ServerName<'static>>,
+}
+
+/// Client-side TLS session cache for resuming connections
+///
+/// Stores the TLS 1.3 tickets and TLS 1.2 sessions servers hand out, so a
+/// repeated connection to the same host can resume the earlier session
+/// instead of performing a full handshake. Attach it to connections with
+/// [`TlsConfig::with_session_cache`](crate::TlsConfig::with_session_cache);
+/// one cache can be shared by many configurations.
+///
+/// Sessions are kept for at most `capacity` hosts. Storing state for a new
+/// host beyond that evicts the host that was added first.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use tls_manager::{TlsConfig, TlsSessionCache};
+///
+/// let cache = Arc::new(TlsSessionCache::new(64));
+/// let config = TlsConfig::new().with_session_cache(cache.clone());
+///
+/// assert!(config.session_cache().is_some());
+/// assert_eq!(cache.hits(), 0);
+/// ```
+pub struct TlsSessionCache {
+    capacity: usize,
+    hosts: Mutex<Hosts>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl TlsSessionCache {
+    /// Create a cache holding sessions for up to `capacity` hosts
+    ///
+    /// A capacity below 1 is treated as 1.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            hosts: Mutex::new(Hosts::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Maximum number of hosts sessions are kept for
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of hosts with cached state
+    pub fn len(&self) -> usize {
+        self.lock().sessions.len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether a session that can be resumed is cached for `host`
+    pub fn contains(&self, host: &str) -> bool {
+        let Ok(name) = ServerName::try_from(host) else {
+            return false;
+        };
+        self.lock()
+            .sessions
+            .get(&name)
+            .is_some_and(HostSessions::is_resumable)
+    }
+
+    /// Number of handshakes that found a session to resume
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Number of handshakes that found no session and did a full handshake
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Forget all cached sessions
+    ///
+    /// The hit and miss counts are kept.
+    pub fn clear(&self) {
+        let mut hosts = self.lock();
+        hosts.sessions.clear();
+        hosts.order.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Hosts> {
+        self.hosts
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Update the state for `server_name`, adding it (and evicting the
+    /// oldest host if full) when it isn't cached yet
+    fn edit(&self, server_name: ServerName<'static>, edit: impl FnOnce(&mut HostSessions)) {
+        let mut hosts = self.lock();
+        if let Some(sessions) = hosts.sessions.get_mut(&server_name) {
+            edit(sessions);
+            return;
+        }
+
+        while hosts.order.len() >= self.capacity {
+            if let Some(oldest) = hosts.order.pop_front() {
+                hosts.sessions.remove(&oldest);
+            }
+        }
+        let mut sessions = HostSessions::default();
+        edit(&mut sessions);
+        hosts.order.push_back(server_name.clone());
+        hosts.sessions.insert(server_name, sessions);
+    }
+}
+
+impl Default for TlsSessionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_SESSION_CACHE_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for TlsSessionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TlsSessionCache")
+            .field("capacity", &self.capacity)
+            .field("hosts", &self.len())
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+impl ClientSessionStore for TlsSessionCache {
+    fn set_kx_hint(&self, server_name: ServerName<'static>, group: NamedGroup) {
+        self.edit(server_name, |sessions| sessions.kx_hint = Some(group));
+    }
+
+    fn kx_hint(&self, server_name: &ServerName<'_>) -> Option<NamedGroup> {
+        self.lock()
+            .sessions
+            .get(server_name)
+            .and_then(|sessions| sessions.kx_hint)
+    }
+
+    fn set_tls12_session(&self, server_name: ServerName<'static>, value: Tls12ClientSessionValue) {
+        self.edit(server_name, |sessions| sessions.tls12 = Some(value));
+    }
+
+    fn tls12_session(&self, server_name: &ServerName<'_>) -> Option<Tls12ClientSessionValue> {
+        self.lock()
+            .sessions
+            .get(server_name)
+            .and_then(|sessions| sessions.tls12.clone())
+    }
+
+    fn remove_tls12_session(&self, server_name: &ServerName<'static>) {
+        if let Some(sessions) = self.lock().sessions.get_mut(server_name) {
+            sessions.tls12 = None;
+        }
+    }
+
+    fn insert_tls13_ticket(
+        &self,
+        server_name: ServerName<'static>,
+        value: Tls13ClientSessionValue,
+    ) {
+        self.edit(server_name, |sessions| {
+            if sessions.tls13.len() >= MAX_TLS13_TICKETS_PER_HOST {
+                sessions.tls13.pop_front();
+            }
+            sessions.tls13.push_back(value);
+        });
+    }
+
+    // rustls looks for a session to resume by taking a TLS 1.3 ticket and,
+    // failing that, reading the TLS 1.2 session, so every handshake calls
+    // this exactly once and it is where hits and misses are counted
+    fn take_tls13_ticket(
+        &self,
+        server_name: &ServerName<'static>,
+    ) -> Option<Tls13ClientSessionValue> {
+        let mut hosts = self.lock();
+        let (ticket, resumable) = match hosts.sessions.get_mut(server_name) {
+            Some(sessions) => {
+                let ticket = sessions.tls13.pop_back();
+                let resumable = ticket.is_some() || sessions.tls12.is_some();
+                (ticket, resumable)
+            }
+            None => (None, false),
+        };
+
+        let counter = if resumable { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        ticket
+    }
+}
@@ -1,6 +1,9 @@
 //! Unit tests for tls_manager
 
+mod test_certificate_chain;
 mod test_certificate_store;
+mod test_certificate_transparency;
 mod test_certificate_validation;
 mod test_hsts_store;
+mod test_session_cache;
 mod test_tls_config;
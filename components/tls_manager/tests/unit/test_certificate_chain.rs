@@ -0,0 +1,143 @@
+//! Unit tests for certificate chain validation
+
+use network_errors::NetworkError;
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, IsCa,
+    KeyUsagePurpose,
+};
+use tls_manager::CertificateStore;
+
+const HOSTNAME: &str = "chain.example.com";
+
+fn ca_certificate(common_name: &str) -> Certificate {
+    let mut params = CertificateParams::new(Vec::new());
+    params.distinguished_name = DistinguishedName::new();
+    params
+        .distinguished_name
+        .push(DnType::CommonName, common_name);
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    Certificate::from_params(params).unwrap()
+}
+
+/// A root CA, an intermediate CA signed by it, and a leaf for `HOSTNAME`
+/// signed by the intermediate, as DER
+struct Chain {
+    root: Vec<u8>,
+    intermediate: Vec<u8>,
+    leaf: Vec<u8>,
+}
+
+fn chain() -> Chain {
+    let root = ca_certificate("Test Root CA");
+    let intermediate = ca_certificate("Test Intermediate CA");
+
+    let mut leaf_params = CertificateParams::new(vec![HOSTNAME.to_string()]);
+    leaf_params.distinguished_name = DistinguishedName::new();
+    leaf_params
+        .distinguished_name
+        .push(DnType::CommonName, HOSTNAME);
+    let leaf = Certificate::from_params(leaf_params).unwrap();
+
+    Chain {
+        root: root.serialize_der().unwrap(),
+        intermediate: intermediate.serialize_der_with_signer(&root).unwrap(),
+        leaf: leaf.serialize_der_with_signer(&intermediate).unwrap(),
+    }
+}
+
+fn store_trusting(root: &[u8]) -> CertificateStore {
+    let mut store = CertificateStore::new();
+    store.add_root_certificate(root.to_vec()).unwrap();
+    store
+}
+
+fn assert_certificate_error(result: Result<(), NetworkError>) {
+    assert!(
+        matches!(result, Err(NetworkError::CertificateError(_))),
+        "expected CertificateError, got {:?}",
+        result
+    );
+}
+
+#[tokio::test]
+async fn test_verify_chain_accepts_complete_chain() {
+    // Given: a leaf, its intermediate, and a trusted root
+    // When: the leaf and intermediate are verified as a chain
+    // Then: a path to the root is found and validation succeeds
+
+    let chain = chain();
+    let store = store_trusting(&chain.root);
+
+    let result = store
+        .verify_chain(&[chain.leaf, chain.intermediate], HOSTNAME)
+        .await;
+
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_verify_chain_rejects_missing_intermediate() {
+    // Given: a trusted root
+    // When: the leaf is presented without its intermediate
+    // Then: no path to the root can be built
+
+    let chain = chain();
+    let store = store_trusting(&chain.root);
+
+    assert_certificate_error(store.verify_chain(&[chain.leaf], HOSTNAME).await);
+}
+
+#[tokio::test]
+async fn test_verify_chain_rejects_untrusted_root() {
+    // Given: a complete chain whose root isn't trusted
+    // When: the chain is verified
+    // Then: validation fails
+
+    let chain = chain();
+    let store = CertificateStore::new();
+
+    assert_certificate_error(
+        store
+            .verify_chain(&[chain.leaf, chain.intermediate], HOSTNAME)
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn test_verify_chain_rejects_wrong_intermediate() {
+    // Given: a trusted root
+    // When: the leaf comes with an intermediate that didn't sign it
+    // Then: the signature check fails
+
+    let chain = chain();
+    let store = store_trusting(&chain.root);
+    let other = ca_certificate("Test Intermediate CA");
+    let root = ca_certificate("Test Root CA");
+    let unrelated_intermediate = other.serialize_der_with_signer(&root).unwrap();
+
+    assert_certificate_error(
+        store
+            .verify_chain(&[chain.leaf, unrelated_intermediate], HOSTNAME)
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn test_verify_chain_checks_leaf_hostname() {
+    let chain = chain();
+    let store = store_trusting(&chain.root);
+
+    assert_certificate_error(
+        store
+            .verify_chain(&[chain.leaf, chain.intermediate], "other.example.com")
+            .await,
+    );
+}
+
+#[tokio::test]
+async fn test_verify_chain_rejects_empty_chain() {
+    let store = CertificateStore::new();
+
+    assert_certificate_error(store.verify_chain(&[], HOSTNAME).await);
+}
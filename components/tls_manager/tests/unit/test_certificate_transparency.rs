@@ -0,0 +1,189 @@
+//! Unit tests for requiring Certificate Transparency in CertificateStore
+
+use network_errors::NetworkError;
+use rcgen::{Certificate, CertificateParams, CustomExtension};
+use tls_manager::CertificateStore;
+
+const SCT_LIST_OID: &[u64] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 2];
+const CT_POISON_OID: &[u64] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 3];
+
+const HOSTNAME: &str = "example.com";
+
+/// Log ID of a test log, filled with `byte`
+fn log_id(byte: u8) -> Vec<u8> {
+    vec![byte; 32]
+}
+
+/// TLS-encoded SCT from the log with ID `log_id`
+fn sct(log_id: &[u8]) -> Vec<u8> {
+    let mut sct = vec![0]; // v1
+    sct.extend_from_slice(log_id);
+    sct.extend_from_slice(&1_700_000_000_000u64.to_be_bytes());
+    sct.extend_from_slice(&[0, 0]); // no extensions
+    sct.extend_from_slice(&[4, 3, 0, 4, 1, 2, 3, 4]); // ecdsa-sha256 signature
+    sct
+}
+
+/// Value of the SCT list extension: an OCTET STRING holding the TLS list
+fn sct_list_extension(scts: &[Vec<u8>]) -> Vec<u8> {
+    let mut list = Vec::new();
+    for sct in scts {
+        list.extend_from_slice(&(sct.len() as u16).to_be_bytes());
+        list.extend_from_slice(sct);
+    }
+    let mut tls = (list.len() as u16).to_be_bytes().to_vec();
+    tls.extend_from_slice(&list);
+
+    let mut der = vec![0x04];
+    if tls.len() < 0x80 {
+        der.push(tls.len() as u8);
+    } else {
+        der.push(0x82);
+        der.extend_from_slice(&(tls.len() as u16).to_be_bytes());
+    }
+    der.extend_from_slice(&tls);
+    der
+}
+
+/// Self-signed certificate for HOSTNAME with the given extensions
+fn certificate(extensions: Vec<CustomExtension>) -> Vec<u8> {
+    let mut params = CertificateParams::new(vec![HOSTNAME.to_string()]);
+    params.custom_extensions = extensions;
+    Certificate::from_params(params)
+        .unwrap()
+        .serialize_der()
+        .unwrap()
+}
+
+fn certificate_with_scts(scts: &[Vec<u8>]) -> Vec<u8> {
+    certificate(vec![CustomExtension::from_oid_content(
+        SCT_LIST_OID,
+        sct_list_extension(scts),
+    )])
+}
+
+fn ct_store(trusted_logs: &[u8]) -> CertificateStore {
+    let mut store = CertificateStore::new();
+    store.require_ct(true);
+    store.set_ct_logs(trusted_logs.iter().map(|byte| log_id(*byte)));
+    store
+}
+
+fn assert_ct_error(result: Result<(), NetworkError>) {
+    match result {
+        Err(NetworkError::CertificateError(msg)) => {
+            assert!(msg.contains("Certificate Transparency"), "{}", msg)
+        }
+        other => panic!("Expected a CT CertificateError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_ct_not_required_by_default() {
+    let store = CertificateStore::new();
+    assert!(!store.is_ct_required());
+}
+
+#[tokio::test]
+async fn test_certificate_with_trusted_sct_passes() {
+    // Given: a store requiring CT and a certificate with an SCT from a trusted log
+    let store = ct_store(&[1]);
+    let cert = certificate_with_scts(&[sct(&log_id(1))]);
+
+    // When: the certificate is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation succeeds
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_certificate_without_scts_fails_when_required() {
+    // Given: a store requiring CT and a certificate without SCTs
+    let store = ct_store(&[1]);
+    let cert = certificate(Vec::new());
+
+    // When: the certificate is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation fails
+    assert_ct_error(result);
+}
+
+#[tokio::test]
+async fn test_certificate_without_scts_passes_when_not_required() {
+    // Given: a store not requiring CT
+    let store = CertificateStore::new();
+    let cert = certificate(Vec::new());
+
+    // When: a certificate without SCTs is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation succeeds
+    assert!(result.is_ok(), "{:?}", result);
+}
+
+#[tokio::test]
+async fn test_scts_from_untrusted_logs_do_not_count() {
+    // Given: a certificate whose only SCT comes from a log not in the list
+    let store = ct_store(&[1]);
+    let cert = certificate_with_scts(&[sct(&log_id(9))]);
+
+    // When: the certificate is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation fails
+    assert_ct_error(result);
+}
+
+#[tokio::test]
+async fn test_minimum_counts_distinct_trusted_logs() {
+    // Given: a store requiring SCTs from two distinct trusted logs
+    let mut store = ct_store(&[1, 2]);
+    store.set_min_sct_count(2);
+
+    // When: certificates with one log twice, and with two logs, are verified
+    let same_log = certificate_with_scts(&[sct(&log_id(1)), sct(&log_id(1))]);
+    let two_logs = certificate_with_scts(&[sct(&log_id(1)), sct(&log_id(2))]);
+
+    // Then: only the certificate with two distinct logs passes
+    assert_ct_error(store.verify_certificate(&same_log, HOSTNAME).await);
+    assert!(store.verify_certificate(&two_logs, HOSTNAME).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_precertificate_is_rejected_when_ct_required() {
+    // Given: a certificate carrying the CT poison extension
+    let store = ct_store(&[1]);
+    let mut poison = CustomExtension::from_oid_content(CT_POISON_OID, vec![0x05, 0x00]);
+    poison.set_criticality(true);
+    let cert = certificate(vec![
+        poison,
+        CustomExtension::from_oid_content(SCT_LIST_OID, sct_list_extension(&[sct(&log_id(1))])),
+    ]);
+
+    // When: the certificate is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation fails
+    match result {
+        Err(NetworkError::CertificateError(msg)) => assert!(msg.contains("precertificate")),
+        other => panic!("Expected CertificateError, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_malformed_sct_list_fails_when_required() {
+    // Given: a certificate whose SCT list extension isn't an OCTET STRING
+    let store = ct_store(&[1]);
+    let cert = certificate(vec![CustomExtension::from_oid_content(
+        SCT_LIST_OID,
+        vec![0x02, 0x01, 0x00],
+    )]);
+
+    // When: the certificate is verified
+    let result = store.verify_certificate(&cert, HOSTNAME).await;
+
+    // Then: validation fails
+    assert!(matches!(result, Err(NetworkError::CertificateError(_))));
+}
@@ -78,3 +78,86 @@ fn test_hsts_store_subdomain_exclusion() {
     assert!(store.is_hsts_enabled("example.com"));
     assert!(!store.is_hsts_enabled("sub.example.com"));
 }
+
+const PRELOAD_LIST: &str = r#"{
+  // Comment lines as in Chromium's transport_security_state_static.json
+  "pinsets": [],
+  "entries": [
+    { "name": "preloaded.example", "policy": "custom", "mode": "force-https", "include_subdomains": true },
+    { "name": "exact.example", "policy": "custom", "mode": "force-https" },
+    // Pin-only entries don't enable HSTS
+    { "name": "pinned.example", "policy": "custom", "pins": "test" },
+    { "name": "Upper.Example", "mode": "force-https", "include_subdomains": false }
+  ]
+}"#;
+
+#[test]
+fn test_hsts_store_load_preload_list() {
+    // Given: a synthetic Chromium-format preload list
+    // When: it is loaded into an empty store
+    // Then: force-https domains are HSTS-enabled without any header
+
+    let mut store = HstsStore::new();
+    let loaded = store.load_preload_list(PRELOAD_LIST.as_bytes()).unwrap();
+
+    assert_eq!(loaded, 3);
+    assert!(store.is_hsts_enabled("preloaded.example"));
+    assert!(store.is_hsts_enabled("exact.example"));
+    assert!(store.is_hsts_enabled("upper.example"));
+    assert!(store.is_hsts_enabled("EXACT.example."));
+    assert!(!store.is_hsts_enabled("pinned.example"));
+    assert!(!store.is_hsts_enabled("unlisted.example"));
+}
+
+#[test]
+fn test_hsts_store_preload_subdomains() {
+    // Given: preloaded entries with and without include_subdomains
+    // When: checking subdomains
+    // Then: only entries that include subdomains cover them
+
+    let mut store = HstsStore::new();
+    store.load_preload_list(PRELOAD_LIST.as_bytes()).unwrap();
+
+    assert!(store.is_hsts_enabled("www.preloaded.example"));
+    assert!(store.is_hsts_enabled("a.b.preloaded.example"));
+    assert!(!store.is_hsts_enabled("www.exact.example"));
+    assert!(!store.is_hsts_enabled("notpreloaded.example"));
+}
+
+#[test]
+fn test_hsts_store_preload_and_dynamic_entries() {
+    // Given: a store with both preloaded and dynamic entries
+    // When: checking domains from either source
+    // Then: both are consulted
+
+    let mut store = HstsStore::new();
+    store.load_preload_list(PRELOAD_LIST.as_bytes()).unwrap();
+    store.add_hsts_entry(
+        "exact.example".to_string(),
+        Duration::from_secs(31536000),
+        true,
+    );
+    store.add_hsts_entry(
+        "dynamic.example".to_string(),
+        Duration::from_secs(31536000),
+        false,
+    );
+
+    assert!(store.is_hsts_enabled("dynamic.example"));
+    assert!(store.is_hsts_enabled("www.exact.example"));
+    assert!(store.is_hsts_enabled("www.preloaded.example"));
+}
+
+#[test]
+fn test_hsts_store_invalid_preload_list() {
+    // Given: malformed preload list input
+    // When: loading it
+    // Then: an error is returned and nothing is preloaded
+
+    let mut store = HstsStore::new();
+    assert!(store
+        .load_preload_list("{ \"entries\": [".as_bytes())
+        .is_err());
+    assert!(store.load_preload_list("[]".as_bytes()).is_err());
+    assert!(!store.is_hsts_enabled("preloaded.example"));
+}
@@ -0,0 +1,149 @@
+//! Unit tests for TlsSessionCache
+
+use rustls::client::ClientSessionStore;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer, ServerName};
+use rustls::{NamedGroup, RootCertStore, ServerConfig};
+use std::sync::Arc;
+use tls_manager::{TlsConfig, TlsSessionCache};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+const HOSTS: [&str; 3] = ["a.test", "b.test", "c.test"];
+
+/// Self-signed server valid for `HOSTS`, and a client config trusting it
+fn server_and_client(cache: &Arc<TlsSessionCache>) -> (Arc<ServerConfig>, TlsConfig) {
+    let cert = rcgen::generate_simple_self_signed(
+        HOSTS
+            .iter()
+            .map(|host| host.to_string())
+            .collect::<Vec<_>>(),
+    )
+    .unwrap();
+    let cert_der = CertificateDer::from(cert.serialize_der().unwrap());
+    let key_der = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(cert.serialize_private_key_der()));
+
+    let server = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert_der.clone()], key_der)
+        .unwrap();
+
+    let mut roots = RootCertStore::empty();
+    roots.add(cert_der).unwrap();
+    let client = TlsConfig::new()
+        .with_root_certificates(roots)
+        .with_session_cache(cache.clone());
+
+    (Arc::new(server), client)
+}
+
+/// Complete a handshake with `host` over an in-memory stream
+///
+/// The client reads a short reply, so the session tickets the server sends
+/// after the handshake are processed and stored.
+async fn connect(server: &Arc<ServerConfig>, client: &TlsConfig, host: &'static str) {
+    let (client_io, server_io) = tokio::io::duplex(64 * 1024);
+    let acceptor = TlsAcceptor::from(server.clone());
+    let server_task = tokio::spawn(async move {
+        let mut stream = acceptor.accept(server_io).await.unwrap();
+        stream.write_all(b"ok").await.unwrap();
+        stream.flush().await.unwrap();
+        stream
+    });
+
+    let connector = TlsConnector::from(Arc::new(client.to_client_config().unwrap()));
+    let mut stream = connector
+        .connect(ServerName::try_from(host).unwrap(), client_io)
+        .await
+        .unwrap();
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await.unwrap();
+    assert_eq!(&reply, b"ok");
+
+    server_task.await.unwrap();
+}
+
+#[test]
+fn test_session_cache_defaults() {
+    let cache = TlsSessionCache::default();
+
+    assert_eq!(
+        cache.capacity(),
+        tls_manager::DEFAULT_SESSION_CACHE_CAPACITY
+    );
+    assert!(cache.is_empty());
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.misses(), 0);
+    assert_eq!(TlsSessionCache::new(0).capacity(), 1);
+}
+
+#[test]
+fn test_session_cache_evicts_oldest_host() {
+    let cache = TlsSessionCache::new(2);
+    for host in HOSTS {
+        cache.set_kx_hint(ServerName::try_from(host).unwrap(), NamedGroup::X25519);
+    }
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        cache.kx_hint(&ServerName::try_from("a.test").unwrap()),
+        None
+    );
+    assert_eq!(
+        cache.kx_hint(&ServerName::try_from("c.test").unwrap()),
+        Some(NamedGroup::X25519)
+    );
+}
+
+#[tokio::test]
+async fn test_session_cache_stores_session_for_host() {
+    let cache = Arc::new(TlsSessionCache::new(8));
+    let (server, client) = server_and_client(&cache);
+
+    connect(&server, &client, "a.test").await;
+
+    assert!(cache.contains("a.test"));
+    assert!(!cache.contains("b.test"));
+    assert_eq!((cache.hits(), cache.misses()), (0, 1));
+}
+
+#[tokio::test]
+async fn test_session_cache_resumes_repeated_connection() {
+    let cache = Arc::new(TlsSessionCache::new(8));
+    let (server, client) = server_and_client(&cache);
+
+    connect(&server, &client, "a.test").await;
+    connect(&server, &client, "a.test").await;
+
+    assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    assert!(cache.contains("a.test"));
+}
+
+#[tokio::test]
+async fn test_session_cache_capacity_evicts_oldest_session() {
+    let cache = Arc::new(TlsSessionCache::new(2));
+    let (server, client) = server_and_client(&cache);
+
+    for host in HOSTS {
+        connect(&server, &client, host).await;
+    }
+
+    assert_eq!(cache.len(), 2);
+    assert!(!cache.contains("a.test"));
+    assert!(cache.contains("b.test"));
+    assert!(cache.contains("c.test"));
+
+    // The evicted host needs a full handshake again
+    connect(&server, &client, "a.test").await;
+    assert_eq!((cache.hits(), cache.misses()), (0, 4));
+}
+
+#[test]
+fn test_session_cache_clear() {
+    let cache = TlsSessionCache::new(4);
+    cache.set_kx_hint(ServerName::try_from("a.test").unwrap(), NamedGroup::X25519);
+
+    cache.clear();
+
+    assert!(cache.is_empty());
+    assert!(!cache.contains("a.test"));
+}
@@ -1,6 +1,7 @@
 //! Unit tests for TlsConfig
 
-use tls_manager::TlsConfig;
+use network_errors::NetworkError;
+use tls_manager::{CipherSuite, TlsConfig, TlsVersion};
 
 #[test]
 fn test_tls_config_new_creates_default_config() {
@@ -56,3 +57,116 @@ fn test_tls_config_supports_http2_and_http3() {
     assert!(alpn.contains(&b"h2".to_vec()));
     assert!(alpn.contains(&b"http/1.1".to_vec()));
 }
+
+#[test]
+fn test_tls_config_default_versions_and_cipher_suites() {
+    // Given: a new TlsConfig
+    // When: no versions or cipher suites are set
+    // Then: TLS 1.3 and 1.2 are enabled with every supported suite
+
+    let config = TlsConfig::new();
+
+    assert_eq!(
+        config.protocol_versions(),
+        &[TlsVersion::Tls13, TlsVersion::Tls12]
+    );
+    assert_eq!(config.cipher_suites(), &CipherSuite::ALL);
+    assert!(config.to_client_config().is_ok());
+}
+
+#[test]
+fn test_tls_config_with_cipher_suites() {
+    // Given: a TlsConfig
+    // When: an allow list of cipher suites is set
+    // Then: exactly those suites are stored, in order
+
+    let suites = vec![
+        CipherSuite::Tls13Aes256GcmSha384,
+        CipherSuite::EcdheEcdsaWithAes256GcmSha384,
+        CipherSuite::EcdheRsaWithAes256GcmSha384,
+    ];
+    let config = TlsConfig::new().with_cipher_suites(suites.clone());
+
+    assert_eq!(config.cipher_suites(), suites.as_slice());
+    assert!(config.validate().is_ok());
+    assert!(config.to_client_config().is_ok());
+}
+
+#[test]
+fn test_tls_config_without_cipher_suites() {
+    // Given: a TlsConfig
+    // When: cipher suites are denied
+    // Then: they are removed from the offered suites
+
+    let config = TlsConfig::new()
+        .with_cipher_suites(vec![
+            CipherSuite::Tls13Aes128GcmSha256,
+            CipherSuite::Tls13Chacha20Poly1305Sha256,
+            CipherSuite::EcdheRsaWithAes128GcmSha256,
+        ])
+        .without_cipher_suites(&[CipherSuite::Tls13Chacha20Poly1305Sha256]);
+
+    assert_eq!(
+        config.cipher_suites(),
+        &[
+            CipherSuite::Tls13Aes128GcmSha256,
+            CipherSuite::EcdheRsaWithAes128GcmSha256
+        ]
+    );
+}
+
+#[test]
+fn test_tls_config_rejects_empty_cipher_suites() {
+    // Given: a TlsConfig with an empty cipher suite list
+    // When: it is validated
+    // Then: it is rejected as an invalid configuration
+
+    let config = TlsConfig::new().with_cipher_suites(Vec::new());
+
+    assert!(matches!(
+        config.validate(),
+        Err(NetworkError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        config.to_client_config(),
+        Err(NetworkError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_tls_config_rejects_suites_incompatible_with_versions() {
+    // Given: TLS 1.3 as the only version
+    // When: only TLS 1.2 cipher suites are allowed
+    // Then: the configuration is rejected
+
+    let config = TlsConfig::new()
+        .with_protocol_versions(vec![TlsVersion::Tls13])
+        .with_cipher_suites(vec![
+            CipherSuite::EcdheRsaWithAes128GcmSha256,
+            CipherSuite::EcdheEcdsaWithAes128GcmSha256,
+        ]);
+    assert!(matches!(
+        config.to_client_config(),
+        Err(NetworkError::InvalidConfig(_))
+    ));
+
+    // An enabled version must also not be left without suites
+    let config = TlsConfig::new().with_cipher_suites(vec![CipherSuite::Tls13Aes128GcmSha256]);
+    assert!(matches!(
+        config.validate(),
+        Err(NetworkError::InvalidConfig(_))
+    ));
+
+    let config = config.with_protocol_versions(vec![TlsVersion::Tls13]);
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_tls_config_rejects_no_protocol_versions() {
+    let config = TlsConfig::new().with_protocol_versions(Vec::new());
+
+    assert!(matches!(
+        config.validate(),
+        Err(NetworkError::InvalidConfig(_))
+    ));
+}
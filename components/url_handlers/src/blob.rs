@@ -0,0 +1,174 @@
+//! Blob URL registry and handling
+//!
+//! Implements the blob URL store from the W3C File API: bytes registered
+//! with [`BlobUrlRegistry::create_object_url`] are readable through their
+//! `blob:` URL until [`BlobUrlRegistry::revoke_object_url`] removes them.
+
+use network_errors::NetworkError;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Origin serialized into blob URLs created without a document origin
+const OPAQUE_ORIGIN: &str = "null";
+
+/// Contents of a registered blob
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlobData {
+    /// MIME type the blob was registered with (may be empty)
+    pub mime_type: String,
+    /// Blob bytes
+    pub data: Vec<u8>,
+}
+
+/// In-memory store mapping `blob:` URLs to their contents
+///
+/// Share one registry behind an [`Arc`] between the code creating object
+/// URLs and the [`BlobUrlHandler`] reading them.
+#[derive(Debug, Default)]
+pub struct BlobUrlRegistry {
+    entries: RwLock<HashMap<String, BlobData>>,
+}
+
+impl BlobUrlRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `data` and return a new `blob:` URL for it
+    ///
+    /// URLs have the form `blob:null/<uuid>`, as for a blob created by a
+    /// document with an opaque origin.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_handlers::BlobUrlRegistry;
+    ///
+    /// let registry = BlobUrlRegistry::new();
+    /// let url = registry.create_object_url(b"Hello".to_vec(), "text/plain");
+    /// assert!(url.starts_with("blob:"));
+    /// ```
+    pub fn create_object_url(&self, data: Vec<u8>, mime_type: &str) -> String {
+        let url = format!("blob:{}/{}", OPAQUE_ORIGIN, uuid::Uuid::new_v4());
+        let blob = BlobData {
+            mime_type: mime_type.to_string(),
+            data,
+        };
+        self.write_entries().insert(url.clone(), blob);
+        url
+    }
+
+    /// Remove the blob registered for `url`
+    ///
+    /// Revoking an unknown or already revoked URL does nothing.
+    pub fn revoke_object_url(&self, url: &str) {
+        self.write_entries().remove(strip_fragment(url));
+    }
+
+    /// Look up the blob registered for `url`, ignoring any fragment
+    pub fn resolve(&self, url: &str) -> Option<BlobData> {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(strip_fragment(url))
+            .cloned()
+    }
+
+    /// Number of registered blobs
+    pub fn len(&self) -> usize {
+        self.entries
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .len()
+    }
+
+    /// Whether no blobs are registered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn write_entries(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, BlobData>> {
+        self.entries
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Blob URL handler
+///
+/// Reads `blob:` URLs from a shared [`BlobUrlRegistry`].
+#[derive(Debug, Clone)]
+pub struct BlobUrlHandler {
+    registry: Arc<BlobUrlRegistry>,
+}
+
+impl BlobUrlHandler {
+    /// Create a handler reading from `registry`
+    pub fn new(registry: Arc<BlobUrlRegistry>) -> Self {
+        Self { registry }
+    }
+
+    /// Check if a URL is a blob URL
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use url_handlers::BlobUrlHandler;
+    ///
+    /// assert!(BlobUrlHandler::is_blob_url("blob:null/1b2f"));
+    /// assert!(!BlobUrlHandler::is_blob_url("data:text/plain,Hello"));
+    /// ```
+    pub fn is_blob_url(url: &str) -> bool {
+        url.starts_with("blob:")
+    }
+
+    /// Registry this handler reads from
+    pub fn registry(&self) -> &Arc<BlobUrlRegistry> {
+        &self.registry
+    }
+
+    /// Read the blob a `blob:` URL refers to
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The blob: URL to read
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(BlobData)` - The registered blob
+    /// * `Err(NetworkError)` - If the URL isn't a blob URL, or was never
+    ///   registered or has been revoked
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use url_handlers::{BlobUrlHandler, BlobUrlRegistry};
+    ///
+    /// let registry = Arc::new(BlobUrlRegistry::new());
+    /// let handler = BlobUrlHandler::new(Arc::clone(&registry));
+    ///
+    /// let url = registry.create_object_url(b"Hello".to_vec(), "text/plain");
+    /// assert_eq!(handler.read(&url).unwrap().data, b"Hello");
+    ///
+    /// registry.revoke_object_url(&url);
+    /// assert!(handler.read(&url).is_err());
+    /// ```
+    pub fn read(&self, url: &str) -> Result<BlobData, NetworkError> {
+        if !Self::is_blob_url(url) {
+            return Err(NetworkError::InvalidUrl(
+                "Not a blob URL (must start with 'blob:')".to_string(),
+            ));
+        }
+
+        self.registry.resolve(url).ok_or_else(|| {
+            NetworkError::InvalidUrl(format!("Unknown or revoked blob URL: {}", url))
+        })
+    }
+}
+
+/// `url` without its fragment, which blob URL lookups ignore
+fn strip_fragment(url: &str) -> &str {
+    url.split_once('#').map_or(url, |(url, _)| url)
+}
@@ -139,12 +139,35 @@ impl DataUrlHandler {
     }
 
     /// Decode base64 encoded data
+    ///
+    /// ASCII whitespace (e.g. line breaks inserted by some encoders) is
+    /// stripped first. The remainder must be canonical base64: only alphabet
+    /// characters, with exactly the padding its length requires.
     fn decode_base64(data: &str) -> Result<Vec<u8>, NetworkError> {
-        use base64::{engine::general_purpose::STANDARD, Engine};
+        use base64::{engine::general_purpose::STANDARD, DecodeError, Engine};
+
+        let data: String = data.chars().filter(|c| !c.is_ascii_whitespace()).collect();
 
-        STANDARD
-            .decode(data)
-            .map_err(|e| NetworkError::InvalidUrl(format!("Invalid base64 encoding: {}", e)))
+        STANDARD.decode(&data).map_err(|e| {
+            let reason = match e {
+                DecodeError::InvalidByte(offset, b'=') => {
+                    format!("unexpected padding at offset {}", offset)
+                }
+                DecodeError::InvalidByte(offset, byte) => format!(
+                    "invalid character {:?} at offset {}",
+                    char::from(byte),
+                    offset
+                ),
+                DecodeError::InvalidLength => {
+                    format!("invalid length {} (missing padding?)", data.len())
+                }
+                DecodeError::InvalidLastSymbol(offset, _) => {
+                    format!("non-canonical final symbol at offset {}", offset)
+                }
+                DecodeError::InvalidPadding => "incorrect padding".to_string(),
+            };
+            NetworkError::InvalidUrl(format!("Invalid base64 encoding in data URL: {}", reason))
+        })
     }
 
     /// Decode plain (URL-encoded) data
@@ -6,10 +6,24 @@
 //! - Same-origin policy enforcement
 
 use crate::security::FileSecurityPolicy;
-use network_errors::NetworkError;
-use std::path::Path;
+use network_errors::{NetworkError, NetworkResult};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tokio::fs;
 
+/// Entry of a directory listing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirEntry {
+    /// File name within the directory
+    pub name: String,
+    /// Size in bytes (0 for directories)
+    pub size: u64,
+    /// Last modification time, if the platform reports one
+    pub modified: Option<SystemTime>,
+    /// Whether the entry is a directory
+    pub is_dir: bool,
+}
+
 /// File URL handler
 ///
 /// Provides secure file reading from file: URLs with configurable
@@ -105,6 +119,103 @@ impl FileUrlHandler {
     /// # }
     /// ```
     pub async fn read(&self, url: &str) -> Result<Vec<u8>, NetworkError> {
+        let file_path = self.resolve_allowed_path(url)?;
+
+        // Read the file
+        fs::read(&file_path).await.map_err(NetworkError::Io)
+    }
+
+    /// List the directory a file URL points to
+    ///
+    /// Entries are sorted by name. Access is checked against the security
+    /// policy for the directory and again for every entry, so symlinks leading
+    /// outside the allowed paths are left out of the listing.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The file: URL of the directory
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<DirEntry>)` - Directory entries
+    /// * `Err(NetworkError)` - If URL is invalid, access denied, or the path
+    ///   is not a readable directory
+    pub async fn read_directory(&self, url: &str) -> NetworkResult<Vec<DirEntry>> {
+        let dir_path = self.resolve_allowed_path(url)?;
+
+        let metadata = fs::metadata(&dir_path).await.map_err(NetworkError::Io)?;
+        if !metadata.is_dir() {
+            return Err(NetworkError::InvalidUrl(format!(
+                "Not a directory: '{}'",
+                dir_path.display()
+            )));
+        }
+
+        let mut entries = Vec::new();
+        let mut reader = fs::read_dir(&dir_path).await.map_err(NetworkError::Io)?;
+        while let Some(entry) = reader.next_entry().await.map_err(NetworkError::Io)? {
+            // Names that could escape the directory are never listed
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name == "." || name == ".." || name.contains(['/', '\\']) {
+                continue;
+            }
+
+            let entry_path = dir_path.join(&name);
+            if !self.is_allowed(&entry_path) {
+                continue;
+            }
+
+            // Entries that vanish or can't be inspected are skipped
+            let Ok(metadata) = fs::metadata(&entry_path).await else {
+                continue;
+            };
+            entries.push(DirEntry {
+                name,
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+                modified: metadata.modified().ok(),
+                is_dir: metadata.is_dir(),
+            });
+        }
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    /// Render a directory listing as an HTML index page
+    ///
+    /// `title` is shown as the page heading (typically the directory path).
+    /// Subdirectories link with a trailing slash; names are HTML-escaped and
+    /// link targets percent-encoded.
+    pub fn render_directory_index(title: &str, entries: &[DirEntry]) -> String {
+        let title = escape_html(title);
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Index of {title}</title></head>\n\
+             <body>\n<h1>Index of {title}</h1>\n<ul>\n<li><a href=\"../\">../</a></li>\n"
+        );
+
+        for entry in entries {
+            let suffix = if entry.is_dir { "/" } else { "" };
+            let size = if entry.is_dir {
+                String::new()
+            } else {
+                format!(" ({} bytes)", entry.size)
+            };
+            html.push_str(&format!(
+                "<li><a href=\"{}{}\">{}{}</a>{}</li>\n",
+                encode_href(&entry.name),
+                suffix,
+                escape_html(&entry.name),
+                suffix,
+                size
+            ));
+        }
+
+        html.push_str("</ul>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Parse a file URL and check the path against the security policy
+    fn resolve_allowed_path(&self, url: &str) -> Result<PathBuf, NetworkError> {
         // Verify it's a file URL
         if !Self::is_file_url(url) {
             return Err(NetworkError::InvalidUrl(
@@ -123,8 +234,7 @@ impl FileUrlHandler {
             )));
         }
 
-        // Read the file
-        fs::read(&file_path).await.map_err(NetworkError::Io)
+        Ok(file_path)
     }
 
     /// Parse a file URL into a file path
@@ -175,10 +285,32 @@ impl FileUrlHandler {
     }
 }
 
+/// Escape text for HTML element content and attribute values
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Percent-encode a file name for use as a relative link target
+fn encode_href(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
 
     #[test]
     fn test_is_file_url() {
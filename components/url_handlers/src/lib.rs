@@ -5,6 +5,7 @@
 //! This component provides handlers for non-HTTP URL schemes:
 //! - Data URLs (data:) with base64 decoding and MIME type parsing
 //! - File URLs (file:) with security policy enforcement
+//! - Blob URLs (blob:) backed by an in-memory registry
 //!
 //! # Examples
 //!
@@ -36,16 +37,32 @@
 //! let data = handler.read(url).await.expect("Failed to read file");
 //! # }
 //! ```
+//!
+//! ## Blob URLs
+//!
+//! ```
+//! use std::sync::Arc;
+//! use url_handlers::{BlobUrlHandler, BlobUrlRegistry};
+//!
+//! let registry = Arc::new(BlobUrlRegistry::new());
+//! let handler = BlobUrlHandler::new(Arc::clone(&registry));
+//!
+//! let url = registry.create_object_url(b"Hello".to_vec(), "text/plain");
+//! let blob = handler.read(&url).expect("Failed to read blob");
+//! assert_eq!(blob.mime_type, "text/plain");
+//! ```
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+mod blob;
 mod data;
 mod file;
 mod security;
 
+pub use blob::{BlobData, BlobUrlHandler, BlobUrlRegistry};
 pub use data::{DataUrlData, DataUrlHandler};
-pub use file::FileUrlHandler;
+pub use file::{DirEntry, FileUrlHandler};
 pub use security::FileSecurityPolicy;
 
 /// URL handler enum for dispatching to specific handlers
@@ -55,4 +72,6 @@ pub enum UrlHandler {
     Data(DataUrlHandler),
     /// File URL handler
     File(FileUrlHandler),
+    /// Blob URL handler
+    Blob(BlobUrlHandler),
 }
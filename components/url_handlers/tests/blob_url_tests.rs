@@ -0,0 +1,98 @@
+//! Unit tests for the blob URL registry and handler
+
+use network_errors::NetworkError;
+use std::sync::Arc;
+use url_handlers::{BlobUrlHandler, BlobUrlRegistry};
+
+fn handler() -> (Arc<BlobUrlRegistry>, BlobUrlHandler) {
+    let registry = Arc::new(BlobUrlRegistry::new());
+    let handler = BlobUrlHandler::new(Arc::clone(&registry));
+    (registry, handler)
+}
+
+#[test]
+fn test_is_blob_url() {
+    assert!(BlobUrlHandler::is_blob_url("blob:null/1b2f"));
+    assert!(BlobUrlHandler::is_blob_url("blob:https://example.com/1b2f"));
+    assert!(!BlobUrlHandler::is_blob_url("data:text/plain,Hello"));
+    assert!(!BlobUrlHandler::is_blob_url("http://example.com"));
+}
+
+#[test]
+fn test_create_read_revoke() {
+    let (registry, handler) = handler();
+
+    let url = registry.create_object_url(b"Hello World".to_vec(), "text/plain");
+    let blob = handler
+        .read(&url)
+        .expect("Registered blob should be readable");
+    assert_eq!(blob.mime_type, "text/plain");
+    assert_eq!(blob.data, b"Hello World");
+
+    registry.revoke_object_url(&url);
+    let result = handler.read(&url);
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+    assert!(registry.is_empty());
+}
+
+#[test]
+fn test_object_urls_are_unique() {
+    let (registry, handler) = handler();
+
+    let first = registry.create_object_url(b"first".to_vec(), "text/plain");
+    let second = registry.create_object_url(b"second".to_vec(), "text/plain");
+
+    assert_ne!(first, second);
+    assert!(first.starts_with("blob:null/"));
+    assert_eq!(registry.len(), 2);
+    assert_eq!(handler.read(&second).unwrap().data, b"second");
+}
+
+#[test]
+fn test_revoke_leaves_other_blobs() {
+    let (registry, handler) = handler();
+    let kept = registry.create_object_url(b"kept".to_vec(), "");
+    let revoked = registry.create_object_url(b"revoked".to_vec(), "");
+
+    registry.revoke_object_url(&revoked);
+    registry.revoke_object_url(&revoked);
+
+    assert_eq!(handler.read(&kept).unwrap().data, b"kept");
+    assert!(handler.read(&revoked).is_err());
+}
+
+#[test]
+fn test_read_ignores_fragment() {
+    let (registry, handler) = handler();
+    let url = registry.create_object_url(b"<svg/>".to_vec(), "image/svg+xml");
+
+    let blob = handler.read(&format!("{}#icon", url)).unwrap();
+
+    assert_eq!(blob.mime_type, "image/svg+xml");
+}
+
+#[test]
+fn test_read_unknown_blob_fails() {
+    let (_registry, handler) = handler();
+
+    let result = handler.read("blob:null/00000000-0000-0000-0000-000000000000");
+
+    assert!(matches!(result, Err(NetworkError::InvalidUrl(_))));
+}
+
+#[test]
+fn test_read_non_blob_url_fails() {
+    let (_registry, handler) = handler();
+
+    assert!(handler.read("data:text/plain,Hello").is_err());
+}
+
+#[test]
+fn test_registry_is_shared_between_handlers() {
+    let (registry, handler) = handler();
+    let other = BlobUrlHandler::new(Arc::clone(handler.registry()));
+
+    let url = registry.create_object_url(vec![1, 2, 3], "application/octet-stream");
+
+    assert_eq!(other.read(&url).unwrap().data, vec![1, 2, 3]);
+}
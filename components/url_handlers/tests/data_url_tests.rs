@@ -129,3 +129,37 @@ fn test_parse_data_url_binary() {
     assert_eq!(data.mime_type, "application/octet-stream");
     assert_eq!(data.data, vec![1, 2, 3]);
 }
+
+#[test]
+fn test_parse_data_url_base64_rejects_invalid_character() {
+    let url = "data:text/plain;base64,SGVs*G8=";
+    let err = DataUrlHandler::parse(url).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("invalid character '*'"), "{}", message);
+}
+
+#[test]
+fn test_parse_data_url_base64_with_embedded_newlines() {
+    let url = "data:text/plain;base64,SGVsbG8g\r\nV29y\nbGQ=";
+    let data = DataUrlHandler::parse(url).unwrap();
+    assert_eq!(data.data, b"Hello World");
+}
+
+#[test]
+fn test_parse_data_url_base64_correct_padding() {
+    let data = DataUrlHandler::parse("data:;base64,SGk=").unwrap();
+    assert_eq!(data.data, b"Hi");
+
+    let data = DataUrlHandler::parse("data:;base64,SA==").unwrap();
+    assert_eq!(data.data, b"H");
+}
+
+#[test]
+fn test_parse_data_url_base64_rejects_incorrect_padding() {
+    // Missing padding
+    assert!(DataUrlHandler::parse("data:;base64,SGk").is_err());
+    // Too much padding
+    assert!(DataUrlHandler::parse("data:;base64,SGk==").is_err());
+    // Padding in the middle
+    assert!(DataUrlHandler::parse("data:;base64,SA==SGk=").is_err());
+}
@@ -150,3 +150,117 @@ async fn test_read_file_url_empty_file() {
     let data = result.unwrap();
     assert_eq!(data, b"", "Empty file should return empty data");
 }
+
+/// Temp directory holding two files and a subdirectory
+async fn create_listing_fixture() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("b.txt"), b"12345")
+        .await
+        .unwrap();
+    fs::write(temp_dir.path().join("a <&> \"q\".html"), b"<p>")
+        .await
+        .unwrap();
+    fs::create_dir(temp_dir.path().join("sub")).await.unwrap();
+    fs::write(temp_dir.path().join("sub").join("nested.txt"), b"x")
+        .await
+        .unwrap();
+    temp_dir
+}
+
+fn handler_for(path: &std::path::Path) -> FileUrlHandler {
+    FileUrlHandler::new(FileSecurityPolicy {
+        allow_directory_traversal: false,
+        allowed_paths: vec![PathBuf::from(path)],
+    })
+}
+
+#[tokio::test]
+async fn test_read_directory_lists_entries() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(temp_dir.path());
+
+    let url = format!("file://{}", temp_dir.path().display());
+    let entries = handler.read_directory(&url).await.unwrap();
+
+    let names: Vec<_> = entries.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, vec!["a <&> \"q\".html", "b.txt", "sub"]);
+
+    let file = &entries[1];
+    assert_eq!(file.size, 5);
+    assert!(!file.is_dir);
+    assert!(file.modified.is_some());
+
+    let dir = &entries[2];
+    assert!(dir.is_dir);
+    assert_eq!(dir.size, 0);
+}
+
+#[tokio::test]
+async fn test_read_directory_subdirectory() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(temp_dir.path());
+
+    let url = format!("file://{}/sub", temp_dir.path().display());
+    let entries = handler.read_directory(&url).await.unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].name, "nested.txt");
+}
+
+#[tokio::test]
+async fn test_read_directory_access_denied() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(&temp_dir.path().join("sub"));
+
+    let url = format!("file://{}", temp_dir.path().display());
+    assert!(handler.read_directory(&url).await.is_err());
+}
+
+#[tokio::test]
+async fn test_read_directory_rejects_parent_traversal() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(temp_dir.path());
+
+    let url = format!("file://{}/sub/..", temp_dir.path().display());
+    assert!(handler.read_directory(&url).await.is_err());
+}
+
+#[tokio::test]
+async fn test_read_directory_rejects_file() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(temp_dir.path());
+
+    let url = format!("file://{}/b.txt", temp_dir.path().display());
+    assert!(handler.read_directory(&url).await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_read_directory_skips_symlinks_outside_allowed_paths() {
+    let temp_dir = create_listing_fixture().await;
+    let outside = TempDir::new().unwrap();
+    std::os::unix::fs::symlink(outside.path(), temp_dir.path().join("escape")).unwrap();
+    let handler = handler_for(temp_dir.path());
+
+    let url = format!("file://{}", temp_dir.path().display());
+    let entries = handler.read_directory(&url).await.unwrap();
+
+    assert!(entries.iter().all(|e| e.name != "escape"));
+}
+
+#[tokio::test]
+async fn test_render_directory_index() {
+    let temp_dir = create_listing_fixture().await;
+    let handler = handler_for(temp_dir.path());
+    let url = format!("file://{}", temp_dir.path().display());
+    let entries = handler.read_directory(&url).await.unwrap();
+
+    let html = FileUrlHandler::render_directory_index("/data", &entries);
+
+    assert!(html.contains("<title>Index of /data</title>"));
+    assert!(html.contains(
+        "<a href=\"a%20%3C%26%3E%20%22q%22.html\">a &lt;&amp;&gt; &quot;q&quot;.html</a> (3 bytes)"
+    ));
+    assert!(html.contains("<a href=\"b.txt\">b.txt</a> (5 bytes)"));
+    assert!(html.contains("<a href=\"sub/\">sub/</a></li>"));
+}
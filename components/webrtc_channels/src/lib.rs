@@ -80,6 +80,32 @@ impl Default for DataChannelOptions {
     }
 }
 
+impl DataChannelOptions {
+    /// Check the options for conflicting or missing settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::WebRtcError` if:
+    /// - Both `max_packet_life_time` and `max_retransmits` are set (they are
+    ///   mutually exclusive)
+    /// - `negotiated` is true but no `id` is given
+    pub fn validate(&self) -> Result<(), NetworkError> {
+        if self.max_packet_life_time.is_some() && self.max_retransmits.is_some() {
+            return Err(NetworkError::WebRtcError(
+                "max_packet_life_time and max_retransmits are mutually exclusive".to_string(),
+            ));
+        }
+
+        if self.negotiated && self.id.is_none() {
+            return Err(NetworkError::WebRtcError(
+                "id is required for a negotiated data channel".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 /// A message sent or received through a data channel
 ///
 /// Data channels can transmit either text or binary data.
@@ -110,6 +136,15 @@ pub enum DataChannelState {
     Closed,
 }
 
+/// Maximum message size assumed until the SCTP association negotiates one
+///
+/// RFC 8841 section 6 specifies 64 KiB when the remote peer doesn't announce
+/// `a=max-message-size`.
+pub const DEFAULT_MAX_MESSAGE_SIZE: u64 = 65536;
+
+/// Callback invoked when the buffered amount drops to the low threshold
+type BufferedAmountLowCallback = Arc<dyn Fn() + Send + Sync>;
+
 /// Internal data channel state
 struct DataChannelInner {
     state: DataChannelState,
@@ -117,6 +152,50 @@ struct DataChannelInner {
     options: DataChannelOptions,
     // Message queue for received messages
     message_queue: Vec<DataChannelMessage>,
+    // Bytes queued for sending but not yet handed to the transport
+    buffered_amount: u64,
+    buffered_amount_low_threshold: u64,
+    on_buffered_amount_low: Option<BufferedAmountLowCallback>,
+    // Largest message the SCTP association accepts; 0 means unlimited
+    max_message_size: u64,
+}
+
+impl DataChannelInner {
+    /// Ensure a channel is open and `len` bytes fit in a single message
+    fn check_sendable(&self, len: usize) -> Result<(), NetworkError> {
+        if self.state != DataChannelState::Open {
+            return Err(NetworkError::WebRtcError(
+                format!("Cannot send: channel is in {:?} state", self.state)
+            ));
+        }
+
+        let len = len as u64;
+        if self.max_message_size != 0 && len > self.max_message_size {
+            return Err(NetworkError::WebRtcError(format!(
+                "Cannot send: message of {} bytes exceeds the maximum message size of {} bytes; \
+                 split it into smaller chunks",
+                len, self.max_message_size
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Account for `bytes` leaving the send buffer
+    ///
+    /// Returns the low-threshold callback if the buffered amount crossed from
+    /// above the threshold to at or below it, so it can be run unlocked.
+    fn drain(&mut self, bytes: u64) -> Option<BufferedAmountLowCallback> {
+        let before = self.buffered_amount;
+        self.buffered_amount = before.saturating_sub(bytes);
+
+        let threshold = self.buffered_amount_low_threshold;
+        if before > threshold && self.buffered_amount <= threshold {
+            self.on_buffered_amount_low.clone()
+        } else {
+            None
+        }
+    }
 }
 
 /// WebRTC data channel
@@ -159,15 +238,26 @@ impl RtcDataChannel {
     ///
     /// * `label` - Human-readable name for the channel
     /// * `options` - Configuration options for the channel
-    pub fn new(label: String, options: DataChannelOptions) -> Self {
-        Self {
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::WebRtcError` if the options fail
+    /// [`DataChannelOptions::validate`]
+    pub fn new(label: String, options: DataChannelOptions) -> Result<Self, NetworkError> {
+        options.validate()?;
+
+        Ok(Self {
             inner: Arc::new(Mutex::new(DataChannelInner {
                 state: DataChannelState::Connecting,
                 label,
                 options,
                 message_queue: Vec::new(),
+                buffered_amount: 0,
+                buffered_amount_low_threshold: 0,
+                on_buffered_amount_low: None,
+                max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
             })),
-        }
+        })
     }
 
     /// Send binary data through the channel
@@ -184,18 +274,15 @@ impl RtcDataChannel {
     ///
     /// Returns `NetworkError::WebRtcError` if:
     /// - The channel is not open
+    /// - The data exceeds [`max_message_size`](Self::max_message_size)
     /// - The send operation fails
     pub async fn send(&self, data: &[u8]) -> Result<(), NetworkError> {
-        let inner = self.inner.lock().await;
-
-        if inner.state != DataChannelState::Open {
-            return Err(NetworkError::WebRtcError(
-                format!("Cannot send: channel is in {:?} state", inner.state)
-            ));
-        }
+        let mut inner = self.inner.lock().await;
+        inner.check_sendable(data.len())?;
 
         // In a real implementation, this would send data through the WebRTC data channel
-        // For now, this is a stub implementation
+        // For now, the data is only accounted for in the send buffer
+        inner.buffered_amount += data.len() as u64;
         Ok(())
     }
 
@@ -213,18 +300,15 @@ impl RtcDataChannel {
     ///
     /// Returns `NetworkError::WebRtcError` if:
     /// - The channel is not open
+    /// - The UTF-8 encoded text exceeds [`max_message_size`](Self::max_message_size)
     /// - The send operation fails
     pub async fn send_text(&self, text: &str) -> Result<(), NetworkError> {
-        let inner = self.inner.lock().await;
-
-        if inner.state != DataChannelState::Open {
-            return Err(NetworkError::WebRtcError(
-                format!("Cannot send: channel is in {:?} state", inner.state)
-            ));
-        }
+        let mut inner = self.inner.lock().await;
+        inner.check_sendable(text.len())?;
 
         // In a real implementation, this would send text through the WebRTC data channel
-        // For now, this is a stub implementation
+        // For now, the UTF-8 bytes are only accounted for in the send buffer
+        inner.buffered_amount += text.len() as u64;
         Ok(())
     }
 
@@ -310,8 +394,76 @@ impl RtcDataChannel {
         inner.options.clone()
     }
 
+    /// Get the largest message, in bytes, that can be sent in one piece
+    ///
+    /// This is the maximum message size of the SCTP association, or
+    /// [`DEFAULT_MAX_MESSAGE_SIZE`] until one is negotiated. Zero means the
+    /// peer accepts messages of any size. Larger payloads must be split by the
+    /// application.
+    pub async fn max_message_size(&self) -> u64 {
+        let inner = self.inner.lock().await;
+        inner.max_message_size
+    }
+
+    /// Record the maximum message size negotiated for the SCTP association
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Maximum message size in bytes; 0 means unlimited
+    pub async fn set_max_message_size(&self, size: u64) {
+        let mut inner = self.inner.lock().await;
+        inner.max_message_size = size;
+    }
+
+    /// Get the number of bytes queued for sending
+    ///
+    /// Mirrors `RTCDataChannel.bufferedAmount`: it grows with every `send` and
+    /// shrinks as the transport takes data off the send buffer.
+    pub async fn buffered_amount(&self) -> u64 {
+        let inner = self.inner.lock().await;
+        inner.buffered_amount
+    }
+
+    /// Get the threshold at or below which the buffered amount counts as low
+    pub async fn buffered_amount_low_threshold(&self) -> u64 {
+        let inner = self.inner.lock().await;
+        inner.buffered_amount_low_threshold
+    }
+
+    /// Set the threshold at or below which the buffered amount counts as low
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Byte count; defaults to 0
+    pub async fn set_buffered_amount_low_threshold(&self, threshold: u64) {
+        let mut inner = self.inner.lock().await;
+        inner.buffered_amount_low_threshold = threshold;
+    }
+
+    /// Register a callback for when the send buffer drains
+    ///
+    /// The callback runs each time the buffered amount drops from above the
+    /// low threshold to at or below it, letting senders resume after pausing.
+    /// Replaces any previously registered callback.
+    pub async fn on_buffered_amount_low<F>(&self, callback: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        let mut inner = self.inner.lock().await;
+        inner.on_buffered_amount_low = Some(Arc::new(callback));
+    }
+
     // Helper methods for testing only - not part of public API
 
+    /// Mark `bytes` as handed to the transport (test helper only)
+    #[doc(hidden)]
+    pub async fn drain_for_testing(&self, bytes: u64) {
+        let callback = self.inner.lock().await.drain(bytes);
+        if let Some(callback) = callback {
+            callback();
+        }
+    }
+
     /// Set the channel state (test helper only)
     #[doc(hidden)]
     pub async fn set_state_for_testing(&self, state: DataChannelState) {
@@ -22,7 +22,7 @@ mod tests {
             id: None,
         };
 
-        let channel = RtcDataChannel::new("test-channel".to_string(), options);
+        let channel = RtcDataChannel::new("test-channel".to_string(), options).unwrap();
 
         // Verify channel label
         assert_eq!(channel.label().await, "test-channel");
@@ -78,7 +78,7 @@ mod tests {
         /// Then appropriate errors should be returned
 
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("error-test".to_string(), options);
+        let channel = RtcDataChannel::new("error-test".to_string(), options).unwrap();
 
         // Try to send when channel is not open (still connecting)
         let result = channel.send_text("This should fail").await;
@@ -122,7 +122,7 @@ mod tests {
             id: None,
         };
 
-        let ordered_channel = RtcDataChannel::new("ordered".to_string(), ordered_opts.clone());
+        let ordered_channel = RtcDataChannel::new("ordered".to_string(), ordered_opts.clone()).unwrap();
         let stored_opts = ordered_channel.options().await;
         assert!(stored_opts.ordered);
         assert_eq!(stored_opts.max_retransmits, Some(10));
@@ -137,7 +137,7 @@ mod tests {
             id: None,
         };
 
-        let unordered_channel = RtcDataChannel::new("unordered".to_string(), unordered_opts.clone());
+        let unordered_channel = RtcDataChannel::new("unordered".to_string(), unordered_opts.clone()).unwrap();
         let stored_opts = unordered_channel.options().await;
         assert!(!stored_opts.ordered);
         assert_eq!(stored_opts.max_packet_life_time, Some(5000));
@@ -151,7 +151,7 @@ mod tests {
         /// Then all messages should be handled correctly
 
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("multi-msg-test".to_string(), options);
+        let channel = RtcDataChannel::new("multi-msg-test".to_string(), options).unwrap();
 
         channel.set_state_for_testing(DataChannelState::Open).await;
 
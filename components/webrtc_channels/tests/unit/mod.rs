@@ -2,8 +2,12 @@
 
 #[cfg(test)]
 mod tests {
+    use network_errors::NetworkError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use webrtc_channels::{
         DataChannelMessage, DataChannelOptions, DataChannelState, RtcDataChannel,
+        DEFAULT_MAX_MESSAGE_SIZE,
     };
 
     // ===== DataChannelOptions Tests =====
@@ -66,6 +70,83 @@ mod tests {
         assert_eq!(options.max_packet_life_time, None);
     }
 
+    #[test]
+    fn test_data_channel_options_default_is_valid() {
+        // Given default options
+        // When validating
+        // Then validation should pass
+        assert!(DataChannelOptions::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_data_channel_options_valid_partial_reliability() {
+        // Given options using only one partial-reliability setting
+        // When validating
+        // Then validation should pass
+        let lifetime = DataChannelOptions {
+            max_packet_life_time: Some(3000),
+            ..DataChannelOptions::default()
+        };
+        let retransmits = DataChannelOptions {
+            max_retransmits: Some(5),
+            ..DataChannelOptions::default()
+        };
+
+        assert!(lifetime.validate().is_ok());
+        assert!(retransmits.validate().is_ok());
+    }
+
+    #[test]
+    fn test_data_channel_options_reject_lifetime_and_retransmits() {
+        // Given both max_packet_life_time and max_retransmits
+        // When validating or creating a channel
+        // Then a WebRtcError should be returned
+        let options = DataChannelOptions {
+            max_packet_life_time: Some(3000),
+            max_retransmits: Some(5),
+            ..DataChannelOptions::default()
+        };
+
+        assert!(matches!(
+            options.validate(),
+            Err(NetworkError::WebRtcError(msg)) if msg.contains("mutually exclusive")
+        ));
+        assert!(RtcDataChannel::new("conflict".to_string(), options).is_err());
+    }
+
+    #[test]
+    fn test_data_channel_options_negotiated_requires_id() {
+        // Given a negotiated channel without an id
+        // When validating or creating a channel
+        // Then a WebRtcError should be returned
+        let options = DataChannelOptions {
+            negotiated: true,
+            id: None,
+            ..DataChannelOptions::default()
+        };
+
+        assert!(matches!(
+            options.validate(),
+            Err(NetworkError::WebRtcError(msg)) if msg.contains("id is required")
+        ));
+        assert!(RtcDataChannel::new("negotiated".to_string(), options).is_err());
+    }
+
+    #[test]
+    fn test_data_channel_options_negotiated_with_id_is_valid() {
+        // Given a negotiated channel with an id
+        // When creating a channel
+        // Then creation should succeed
+        let options = DataChannelOptions {
+            negotiated: true,
+            id: Some(7),
+            ..DataChannelOptions::default()
+        };
+
+        assert!(options.validate().is_ok());
+        assert!(RtcDataChannel::new("negotiated".to_string(), options).is_ok());
+    }
+
     // ===== DataChannelMessage Tests =====
 
     #[test]
@@ -183,7 +264,7 @@ mod tests {
             id: None,
         };
 
-        let channel = RtcDataChannel::new("test-channel".to_string(), options.clone());
+        let channel = RtcDataChannel::new("test-channel".to_string(), options.clone()).unwrap();
         let label = channel.label().await;
         let stored_options = channel.options().await;
 
@@ -198,7 +279,7 @@ mod tests {
         /// When sending text data
         /// Then send_text should succeed
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -213,7 +294,7 @@ mod tests {
         /// When sending binary data
         /// Then send should succeed
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -229,7 +310,7 @@ mod tests {
         /// When calling recv
         /// Then it should return the text message
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state and add a test message
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -252,7 +333,7 @@ mod tests {
         /// When calling recv
         /// Then it should return the binary message
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state and add a test message
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -276,7 +357,7 @@ mod tests {
         /// When calling recv
         /// Then it should return None
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -291,7 +372,7 @@ mod tests {
         /// When calling close
         /// Then the channel should close successfully
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Open state
         channel.set_state_for_testing(DataChannelState::Open).await;
@@ -306,7 +387,7 @@ mod tests {
         /// When calling close again
         /// Then it should return an error
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Closed state
         channel.set_state_for_testing(DataChannelState::Closed).await;
@@ -327,7 +408,7 @@ mod tests {
         /// When attempting to send text data
         /// Then it should return an error
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Closed state
         channel.set_state_for_testing(DataChannelState::Closed).await;
@@ -348,7 +429,7 @@ mod tests {
         /// When attempting to send binary data
         /// Then it should return an error
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Closed state
         channel.set_state_for_testing(DataChannelState::Closed).await;
@@ -370,7 +451,7 @@ mod tests {
         /// When attempting to receive
         /// Then it should return None
         let options = DataChannelOptions::default();
-        let channel = RtcDataChannel::new("test".to_string(), options);
+        let channel = RtcDataChannel::new("test".to_string(), options).unwrap();
 
         // Set channel to Closed state
         channel.set_state_for_testing(DataChannelState::Closed).await;
@@ -378,4 +459,154 @@ mod tests {
         let result = channel.recv().await;
         assert!(result.is_none());
     }
+
+    // ===== Buffered Amount Tests =====
+
+    async fn open_channel() -> RtcDataChannel {
+        let channel =
+            RtcDataChannel::new("flow".to_string(), DataChannelOptions::default()).unwrap();
+        channel.set_state_for_testing(DataChannelState::Open).await;
+        channel
+    }
+
+    #[tokio::test]
+    async fn test_buffered_amount_rises_as_data_is_sent() {
+        // Given an open data channel
+        // When sending binary and text messages
+        // Then the buffered amount grows by their byte length
+        let channel = open_channel().await;
+        assert_eq!(channel.buffered_amount().await, 0);
+
+        channel.send(&[0u8; 100]).await.unwrap();
+        assert_eq!(channel.buffered_amount().await, 100);
+
+        channel.send_text("héllo").await.unwrap();
+        assert_eq!(channel.buffered_amount().await, 106);
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_is_not_buffered() {
+        // Given a closed data channel
+        // When a send fails
+        // Then nothing is added to the buffered amount
+        let channel = open_channel().await;
+        channel.set_state_for_testing(DataChannelState::Closed).await;
+
+        assert!(channel.send(&[0u8; 10]).await.is_err());
+        assert_eq!(channel.buffered_amount().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_amount_low_fires_when_drained_below_threshold() {
+        // Given a channel with a low threshold and a registered callback
+        // When the buffer drains from above the threshold to at or below it
+        // Then the callback fires once
+        let channel = open_channel().await;
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        channel.set_buffered_amount_low_threshold(50).await;
+        channel
+            .on_buffered_amount_low(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+        assert_eq!(channel.buffered_amount_low_threshold().await, 50);
+
+        channel.send(&[0u8; 200]).await.unwrap();
+
+        channel.drain_for_testing(100).await;
+        assert_eq!(channel.buffered_amount().await, 100);
+        assert_eq!(fired.load(Ordering::SeqCst), 0);
+
+        channel.drain_for_testing(60).await;
+        assert_eq!(channel.buffered_amount().await, 40);
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+        // Already below the threshold: no further event
+        channel.drain_for_testing(40).await;
+        assert_eq!(channel.buffered_amount().await, 0);
+        assert_eq!(fired.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_buffered_amount_low_fires_again_after_refill() {
+        // Given a channel with the default threshold of zero
+        // When the buffer fills and fully drains twice
+        // Then the callback fires for each drain
+        let channel = open_channel().await;
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = fired.clone();
+        channel
+            .on_buffered_amount_low(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .await;
+
+        for _ in 0..2 {
+            channel.send_text("payload").await.unwrap();
+            channel.drain_for_testing(7).await;
+        }
+
+        assert_eq!(channel.buffered_amount().await, 0);
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+    }
+
+    // ===== Max Message Size Tests =====
+
+    #[tokio::test]
+    async fn test_max_message_size_defaults_to_64k() {
+        // Given a new data channel
+        // When no size has been negotiated
+        // Then the RFC 8841 default applies
+        let channel = open_channel().await;
+
+        assert_eq!(channel.max_message_size().await, DEFAULT_MAX_MESSAGE_SIZE);
+        assert!(channel.send(&vec![0u8; 65536]).await.is_ok());
+        assert!(channel.send(&vec![0u8; 65537]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_send_respects_configured_max_message_size() {
+        // Given a channel with a 1024 byte max message size
+        // When sending binary messages just under, at and over the limit
+        // Then only the oversized message is rejected with chunking guidance
+        let channel = open_channel().await;
+        channel.set_max_message_size(1024).await;
+
+        assert!(channel.send(&[0u8; 1023]).await.is_ok());
+        assert!(channel.send(&[0u8; 1024]).await.is_ok());
+
+        let result = channel.send(&[0u8; 1025]).await;
+        assert!(matches!(
+            &result,
+            Err(NetworkError::WebRtcError(msg)) if msg.contains("1025") && msg.contains("chunks")
+        ));
+        assert_eq!(channel.buffered_amount().await, 2047);
+    }
+
+    #[tokio::test]
+    async fn test_send_text_respects_max_message_size_in_bytes() {
+        // Given a channel with a 4 byte max message size
+        // When sending text whose UTF-8 encoding is longer than its char count
+        // Then the byte length is checked
+        let channel = open_channel().await;
+        channel.set_max_message_size(4).await;
+
+        assert!(channel.send_text("abcd").await.is_ok());
+        assert!(matches!(
+            channel.send_text("ééé").await,
+            Err(NetworkError::WebRtcError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_zero_max_message_size_is_unlimited() {
+        // Given a peer announcing no message size limit
+        // When sending a large message
+        // Then it is accepted
+        let channel = open_channel().await;
+        channel.set_max_message_size(0).await;
+
+        assert!(channel.send(&vec![0u8; 1 << 20]).await.is_ok());
+    }
 }
@@ -58,6 +58,7 @@ use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
 use webrtc::peer_connection::RTCPeerConnection as WebRTCPeerConnection;
 use webrtc::rtp_transceiver::rtp_codec::{RTCRtpCodecCapability, RTPCodecType};
 use webrtc::rtp_transceiver::rtp_sender::RTCRtpSender;
+use webrtc::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
 use webrtc::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
 use webrtc::track::track_local::TrackLocal;
 use webrtc::track::track_remote::TrackRemote;
@@ -336,6 +337,17 @@ impl std::fmt::Debug for MediaStreamTrack {
     }
 }
 
+/// Map a webrtc-rs codec type to a track kind
+///
+/// Anything that isn't audio is treated as video.
+fn media_track_kind(kind: RTPCodecType) -> MediaTrackKind {
+    if kind == RTPCodecType::Audio {
+        MediaTrackKind::Audio
+    } else {
+        MediaTrackKind::Video
+    }
+}
+
 impl MediaStreamTrack {
     /// Create a new MediaStreamTrack from a remote track
     fn from_track_remote(track: Arc<TrackRemote>) -> Self {
@@ -397,6 +409,71 @@ impl std::fmt::Debug for RtpSender {
     }
 }
 
+/// Direction of an RTP transceiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RtpTransceiverDirection {
+    /// Send and receive media
+    SendRecv,
+    /// Only send media
+    SendOnly,
+    /// Only receive media
+    RecvOnly,
+    /// Neither send nor receive media
+    Inactive,
+    /// Direction not set yet
+    Unspecified,
+}
+
+impl From<RTCRtpTransceiverDirection> for RtpTransceiverDirection {
+    fn from(direction: RTCRtpTransceiverDirection) -> Self {
+        match direction {
+            RTCRtpTransceiverDirection::Sendrecv => Self::SendRecv,
+            RTCRtpTransceiverDirection::Sendonly => Self::SendOnly,
+            RTCRtpTransceiverDirection::Recvonly => Self::RecvOnly,
+            RTCRtpTransceiverDirection::Inactive => Self::Inactive,
+            RTCRtpTransceiverDirection::Unspecified => Self::Unspecified,
+        }
+    }
+}
+
+/// Snapshot of an RTP sender's current parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RtpSenderInfo {
+    /// ID of the track being sent, if any
+    pub track_id: Option<String>,
+    /// Kind of media of the transceiver
+    pub kind: MediaTrackKind,
+    /// Media stream identification tag, once negotiated
+    pub mid: Option<String>,
+    /// SSRC of the outgoing RTP stream
+    pub ssrc: u32,
+    /// MIME type of the preferred (after negotiation: the selected) codec
+    pub codec_mime_type: Option<String>,
+    /// Payload type of that codec
+    pub payload_type: Option<u8>,
+    /// Direction of the transceiver
+    pub direction: RtpTransceiverDirection,
+}
+
+/// Snapshot of an RTP receiver's current parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RtpReceiverInfo {
+    /// ID of the remote track, once media has arrived
+    pub track_id: Option<String>,
+    /// Kind of media of the transceiver
+    pub kind: MediaTrackKind,
+    /// Media stream identification tag, once negotiated
+    pub mid: Option<String>,
+    /// SSRC of the incoming RTP stream, once media has arrived
+    pub ssrc: Option<u32>,
+    /// MIME type of the received codec, or the preferred one before media arrives
+    pub codec_mime_type: Option<String>,
+    /// Payload type of that codec
+    pub payload_type: Option<u8>,
+    /// Direction of the transceiver
+    pub direction: RtpTransceiverDirection,
+}
+
 /// WebRTC peer connection
 ///
 /// Manages a WebRTC peer-to-peer connection with another endpoint.
@@ -439,6 +516,10 @@ pub struct RtcPeerConnection {
     remote_description: Arc<Mutex<Option<SessionDescription>>>,
     /// Local tracks added to this connection
     local_tracks: Arc<Mutex<HashMap<String, Arc<TrackLocalStaticRTP>>>>,
+    /// RTP senders for local tracks, keyed by the ID of the track they send
+    senders: Arc<Mutex<HashMap<String, Arc<RTCRtpSender>>>>,
+    /// Remote ICE candidates received before the remote description
+    pending_candidates: Arc<Mutex<Vec<IceCandidate>>>,
 }
 
 impl RtcPeerConnection {
@@ -503,6 +584,8 @@ impl RtcPeerConnection {
             local_description: Arc::new(Mutex::new(None)),
             remote_description: Arc::new(Mutex::new(None)),
             local_tracks: Arc::new(Mutex::new(HashMap::new())),
+            senders: Arc::new(Mutex::new(HashMap::new())),
+            pending_candidates: Arc::new(Mutex::new(Vec::new())),
         })
     }
 
@@ -640,11 +723,21 @@ impl RtcPeerConnection {
 
         *self.remote_description.lock().await = Some(description);
 
+        // Apply candidates that arrived ahead of the description
+        let pending = std::mem::take(&mut *self.pending_candidates.lock().await);
+        for candidate in pending {
+            self.apply_ice_candidate(candidate).await?;
+        }
+
         Ok(())
     }
 
     /// Add an ICE candidate
     ///
+    /// With trickle ICE, candidates can arrive before the remote description.
+    /// Such candidates are buffered and applied once
+    /// [`set_remote_description`](Self::set_remote_description) succeeds.
+    ///
     /// # Arguments
     ///
     /// * `candidate` - The ICE candidate to add
@@ -653,6 +746,21 @@ impl RtcPeerConnection {
     ///
     /// Returns Ok(()) on success or a NetworkError
     pub async fn add_ice_candidate(&mut self, candidate: IceCandidate) -> NetworkResult<()> {
+        if self.remote_description.lock().await.is_none() {
+            self.pending_candidates.lock().await.push(candidate);
+            return Ok(());
+        }
+
+        self.apply_ice_candidate(candidate).await
+    }
+
+    /// Get the number of ICE candidates waiting for a remote description
+    pub async fn pending_ice_candidate_count(&self) -> usize {
+        self.pending_candidates.lock().await.len()
+    }
+
+    /// Hand an ICE candidate to the underlying peer connection
+    async fn apply_ice_candidate(&self, candidate: IceCandidate) -> NetworkResult<()> {
         let ice_candidate = webrtc::ice_transport::ice_candidate::RTCIceCandidateInit {
             candidate: candidate.candidate,
             sdp_mid: candidate.sdp_mid,
@@ -698,6 +806,10 @@ impl RtcPeerConnection {
             let mut tracks = self.local_tracks.lock().await;
             tracks.insert(track.id.clone(), local_track);
         }
+        self.senders
+            .lock()
+            .await
+            .insert(track.id.clone(), Arc::clone(&sender));
 
         Ok(RtpSender {
             sender,
@@ -705,6 +817,80 @@ impl RtcPeerConnection {
         })
     }
 
+    /// Replace the track sent by an existing sender without renegotiation
+    ///
+    /// The sender keeps its SSRC, so the remote peer and the outbound RTP
+    /// stats see one continuous stream. Afterwards the sender is found under
+    /// the new track's ID.
+    ///
+    /// # Arguments
+    ///
+    /// * `sender_track_id` - ID of the track the sender currently sends
+    /// * `new_track` - The track to send instead; must be of the same kind
+    ///
+    /// # Returns
+    ///
+    /// Returns Ok(()) on success, or a NetworkError if no sender sends
+    /// `sender_track_id` or the new track is of a different kind
+    pub async fn replace_track(
+        &self,
+        sender_track_id: &str,
+        new_track: MediaTrack,
+    ) -> NetworkResult<()> {
+        let mut tracks = self.local_tracks.lock().await;
+        let mut senders = self.senders.lock().await;
+
+        let (Some(old_track), Some(sender)) =
+            (tracks.get(sender_track_id), senders.get(sender_track_id))
+        else {
+            return Err(NetworkError::WebRtcError(format!(
+                "No sender for track {}",
+                sender_track_id
+            )));
+        };
+
+        let new_local = Arc::new(TrackLocalStaticRTP::new(
+            new_track.codec.to_rtc_codec_capability(),
+            new_track.id.clone(),
+            new_track.stream_id.clone(),
+        ));
+        if new_local.kind() != old_track.kind() {
+            return Err(NetworkError::WebRtcError(format!(
+                "Cannot replace {} track {} with {} track {}",
+                old_track.kind(),
+                sender_track_id,
+                new_local.kind(),
+                new_track.id
+            )));
+        }
+
+        let sender = Arc::clone(sender);
+        sender
+            .replace_track(Some(
+                Arc::clone(&new_local) as Arc<dyn TrackLocal + Send + Sync>
+            ))
+            .await
+            .map_err(|e| NetworkError::WebRtcError(format!("Failed to replace track: {}", e)))?;
+
+        tracks.remove(sender_track_id);
+        senders.remove(sender_track_id);
+        tracks.insert(new_track.id.clone(), new_local);
+        senders.insert(new_track.id, sender);
+
+        Ok(())
+    }
+
+    /// Get the sender of a local track
+    ///
+    /// Returns None if no sender sends the track with this ID
+    pub async fn get_sender(&self, track_id: &str) -> Option<RtpSender> {
+        let senders = self.senders.lock().await;
+        senders.get(track_id).map(|sender| RtpSender {
+            sender: Arc::clone(sender),
+            track_id: track_id.to_string(),
+        })
+    }
+
     /// Register a callback for when a remote track is received
     ///
     /// The callback will be invoked whenever the remote peer adds a track.
@@ -737,6 +923,71 @@ impl RtcPeerConnection {
         ));
     }
 
+    /// Enumerate the RTP senders of all transceivers
+    ///
+    /// Reflects the live state of the underlying connection, in transceiver order.
+    pub async fn senders(&self) -> Vec<RtpSenderInfo> {
+        let mut senders = Vec::new();
+        for transceiver in self.peer_connection.get_transceivers().await {
+            let sender = transceiver.sender().await;
+            let track_id = sender.track().await.map(|track| track.id().to_string());
+            let parameters = sender.get_parameters().await;
+            let codec = parameters.rtp_parameters.codecs.first();
+
+            senders.push(RtpSenderInfo {
+                track_id,
+                kind: media_track_kind(transceiver.kind()),
+                mid: transceiver.mid().map(|mid| mid.to_string()),
+                ssrc: parameters
+                    .encodings
+                    .first()
+                    .map(|encoding| encoding.ssrc)
+                    .unwrap_or_default(),
+                codec_mime_type: codec.map(|codec| codec.capability.mime_type.clone()),
+                payload_type: codec.map(|codec| codec.payload_type),
+                direction: transceiver.direction().into(),
+            });
+        }
+        senders
+    }
+
+    /// Enumerate the RTP receivers of all transceivers
+    ///
+    /// Reflects the live state of the underlying connection, in transceiver order.
+    /// Track details are only known once the remote peer starts sending media.
+    pub async fn receivers(&self) -> Vec<RtpReceiverInfo> {
+        let mut receivers = Vec::new();
+        for transceiver in self.peer_connection.get_transceivers().await {
+            let receiver = transceiver.receiver().await;
+            let info = match receiver.track().await {
+                Some(track) => RtpReceiverInfo {
+                    track_id: Some(track.id()),
+                    kind: media_track_kind(transceiver.kind()),
+                    mid: transceiver.mid().map(|mid| mid.to_string()),
+                    ssrc: Some(track.ssrc()),
+                    codec_mime_type: Some(track.codec().capability.mime_type),
+                    payload_type: Some(track.payload_type()),
+                    direction: transceiver.direction().into(),
+                },
+                None => {
+                    let parameters = receiver.get_parameters().await;
+                    let codec = parameters.codecs.first();
+                    RtpReceiverInfo {
+                        track_id: None,
+                        kind: media_track_kind(transceiver.kind()),
+                        mid: transceiver.mid().map(|mid| mid.to_string()),
+                        ssrc: None,
+                        codec_mime_type: codec.map(|codec| codec.capability.mime_type.clone()),
+                        payload_type: codec.map(|codec| codec.payload_type),
+                        direction: transceiver.direction().into(),
+                    }
+                }
+            };
+            receivers.push(info);
+        }
+        receivers
+    }
+
     /// Get the list of local track IDs
     pub async fn get_local_track_ids(&self) -> Vec<String> {
         let tracks = self.local_tracks.lock().await;
@@ -747,6 +998,7 @@ impl RtcPeerConnection {
     ///
     /// Returns true if the track was found and removed
     pub async fn remove_track(&self, track_id: &str) -> bool {
+        self.senders.lock().await.remove(track_id);
         let mut tracks = self.local_tracks.lock().await;
         tracks.remove(track_id).is_some()
     }
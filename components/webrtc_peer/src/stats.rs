@@ -7,7 +7,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Type of RTC statistics
+///
+/// Serialized as the W3C `RTCStatsType` string, e.g. `"inbound-rtp"`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum RtcStatsType {
     /// Inbound RTP stream statistics
     InboundRtp,
@@ -29,6 +32,50 @@ pub enum RtcStatsType {
     Unknown(String),
 }
 
+impl RtcStatsType {
+    /// The W3C `RTCStatsType` string for this type
+    pub fn as_str(&self) -> &str {
+        match self {
+            RtcStatsType::InboundRtp => "inbound-rtp",
+            RtcStatsType::OutboundRtp => "outbound-rtp",
+            RtcStatsType::RemoteInboundRtp => "remote-inbound-rtp",
+            RtcStatsType::CandidatePair => "candidate-pair",
+            RtcStatsType::LocalCandidate => "local-candidate",
+            RtcStatsType::RemoteCandidate => "remote-candidate",
+            RtcStatsType::Transport => "transport",
+            RtcStatsType::Codec => "codec",
+            RtcStatsType::Unknown(other) => other,
+        }
+    }
+
+    /// Placeholder until the enclosing [`RtcStats`] variant fills in the type
+    fn unspecified() -> Self {
+        RtcStatsType::Unknown(String::new())
+    }
+}
+
+impl From<RtcStatsType> for String {
+    fn from(stats_type: RtcStatsType) -> Self {
+        stats_type.as_str().to_string()
+    }
+}
+
+impl From<String> for RtcStatsType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "inbound-rtp" => RtcStatsType::InboundRtp,
+            "outbound-rtp" => RtcStatsType::OutboundRtp,
+            "remote-inbound-rtp" => RtcStatsType::RemoteInboundRtp,
+            "candidate-pair" => RtcStatsType::CandidatePair,
+            "local-candidate" => RtcStatsType::LocalCandidate,
+            "remote-candidate" => RtcStatsType::RemoteCandidate,
+            "transport" => RtcStatsType::Transport,
+            "codec" => RtcStatsType::Codec,
+            _ => RtcStatsType::Unknown(value),
+        }
+    }
+}
+
 /// Base statistics common to all stat types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RtcStatsBase {
@@ -37,11 +84,13 @@ pub struct RtcStatsBase {
     /// Timestamp when these stats were collected (milliseconds since epoch)
     pub timestamp: f64,
     /// Type of statistics
+    #[serde(rename = "type", default = "RtcStatsType::unspecified")]
     pub stats_type: RtcStatsType,
 }
 
 /// Inbound RTP stream statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InboundRtpStats {
     /// Base statistics
     #[serde(flatten)]
@@ -59,11 +108,13 @@ pub struct InboundRtpStats {
     /// Jitter in seconds
     pub jitter: f64,
     /// Fraction of packets lost (0.0 to 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fraction_lost: Option<f64>,
 }
 
 /// Outbound RTP stream statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct OutboundRtpStats {
     /// Base statistics
     #[serde(flatten)]
@@ -77,11 +128,13 @@ pub struct OutboundRtpStats {
     /// Number of bytes sent
     pub bytes_sent: u64,
     /// Target bitrate in bits per second
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub target_bitrate: Option<f64>,
 }
 
 /// Remote inbound RTP statistics (from RTCP receiver reports)
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct RemoteInboundRtpStats {
     /// Base statistics
     #[serde(flatten)]
@@ -93,15 +146,19 @@ pub struct RemoteInboundRtpStats {
     /// Number of packets lost as reported by remote
     pub packets_lost: i64,
     /// Round trip time in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub round_trip_time: Option<f64>,
     /// Jitter as reported by remote (seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub jitter: Option<f64>,
     /// Fraction of packets lost (0.0 to 1.0)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub fraction_lost: Option<f64>,
 }
 
 /// State of an ICE candidate pair
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum CandidatePairState {
     /// Pair is frozen (not yet checked)
     Frozen,
@@ -117,6 +174,7 @@ pub enum CandidatePairState {
 
 /// ICE candidate pair statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CandidatePairStats {
     /// Base statistics
     #[serde(flatten)]
@@ -136,15 +194,21 @@ pub struct CandidatePairStats {
     /// Total round trip time in seconds
     pub total_round_trip_time: f64,
     /// Current round trip time in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub current_round_trip_time: Option<f64>,
     /// Available outgoing bitrate estimate
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub available_outgoing_bitrate: Option<f64>,
     /// Available incoming bitrate estimate
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub available_incoming_bitrate: Option<f64>,
 }
 
 /// Type of ICE candidate
+///
+/// Serialized as the W3C `RTCIceCandidateType` string, e.g. `"srflx"`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum IceCandidateType {
     /// Host candidate (local address)
     Host,
@@ -158,8 +222,33 @@ pub enum IceCandidateType {
     Unknown(String),
 }
 
+impl From<IceCandidateType> for String {
+    fn from(candidate_type: IceCandidateType) -> Self {
+        match candidate_type {
+            IceCandidateType::Host => "host".to_string(),
+            IceCandidateType::Srflx => "srflx".to_string(),
+            IceCandidateType::Prflx => "prflx".to_string(),
+            IceCandidateType::Relay => "relay".to_string(),
+            IceCandidateType::Unknown(other) => other,
+        }
+    }
+}
+
+impl From<String> for IceCandidateType {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "host" => IceCandidateType::Host,
+            "srflx" => IceCandidateType::Srflx,
+            "prflx" => IceCandidateType::Prflx,
+            "relay" => IceCandidateType::Relay,
+            _ => IceCandidateType::Unknown(value),
+        }
+    }
+}
+
 /// ICE candidate statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct IceCandidateStats {
     /// Base statistics
     #[serde(flatten)]
@@ -167,21 +256,26 @@ pub struct IceCandidateStats {
     /// Transport ID
     pub transport_id: String,
     /// IP address
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
     /// Port number
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub port: Option<u16>,
     /// Protocol (udp or tcp)
     pub protocol: String,
     /// Candidate type
     pub candidate_type: IceCandidateType,
     /// Priority of this candidate
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u32>,
     /// URL of the server used to obtain this candidate
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub url: Option<String>,
 }
 
 /// Transport statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TransportStats {
     /// Base statistics
     #[serde(flatten)]
@@ -191,18 +285,25 @@ pub struct TransportStats {
     /// Number of bytes received
     pub bytes_received: u64,
     /// DTLS state
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub dtls_state: Option<String>,
     /// Selected candidate pair ID
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub selected_candidate_pair_id: Option<String>,
     /// Local certificate ID
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_certificate_id: Option<String>,
     /// Remote certificate ID
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_certificate_id: Option<String>,
 }
 
 /// A single RTC statistics entry
+///
+/// Serializes as a W3C stats dictionary whose `type` member comes from the
+/// entry's base stats.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "type")]
+#[serde(untagged, from = "TaggedRtcStats")]
 pub enum RtcStats {
     /// Inbound RTP statistics
     InboundRtp(InboundRtpStats),
@@ -220,8 +321,61 @@ pub enum RtcStats {
     Transport(TransportStats),
 }
 
+/// Stats entry as read from JSON, where the `type` member selects the variant
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+enum TaggedRtcStats {
+    InboundRtp(InboundRtpStats),
+    OutboundRtp(OutboundRtpStats),
+    RemoteInboundRtp(RemoteInboundRtpStats),
+    CandidatePair(CandidatePairStats),
+    LocalCandidate(IceCandidateStats),
+    RemoteCandidate(IceCandidateStats),
+    Transport(TransportStats),
+}
+
+impl From<TaggedRtcStats> for RtcStats {
+    fn from(tagged: TaggedRtcStats) -> Self {
+        // The tag is consumed by the enum, so restore it on the base stats
+        match tagged {
+            TaggedRtcStats::InboundRtp(mut stats) => {
+                stats.base.stats_type = RtcStatsType::InboundRtp;
+                RtcStats::InboundRtp(stats)
+            }
+            TaggedRtcStats::OutboundRtp(mut stats) => {
+                stats.base.stats_type = RtcStatsType::OutboundRtp;
+                RtcStats::OutboundRtp(stats)
+            }
+            TaggedRtcStats::RemoteInboundRtp(mut stats) => {
+                stats.base.stats_type = RtcStatsType::RemoteInboundRtp;
+                RtcStats::RemoteInboundRtp(stats)
+            }
+            TaggedRtcStats::CandidatePair(mut stats) => {
+                stats.base.stats_type = RtcStatsType::CandidatePair;
+                RtcStats::CandidatePair(stats)
+            }
+            TaggedRtcStats::LocalCandidate(mut stats) => {
+                stats.base.stats_type = RtcStatsType::LocalCandidate;
+                RtcStats::LocalCandidate(stats)
+            }
+            TaggedRtcStats::RemoteCandidate(mut stats) => {
+                stats.base.stats_type = RtcStatsType::RemoteCandidate;
+                RtcStats::RemoteCandidate(stats)
+            }
+            TaggedRtcStats::Transport(mut stats) => {
+                stats.base.stats_type = RtcStatsType::Transport;
+                RtcStats::Transport(stats)
+            }
+        }
+    }
+}
+
 /// Complete statistics report from a peer connection
+///
+/// Serializes in the shape of the W3C `RTCStatsReport` returned by
+/// `getStats()`: a map from stats id to stats dictionary.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct RtcStatsReport {
     /// All statistics entries indexed by their ID
     pub stats: HashMap<String, RtcStats>,
@@ -240,6 +394,16 @@ impl RtcStatsReport {
         self.stats.insert(id, stats);
     }
 
+    /// Convert the report to `getStats()` JSON
+    ///
+    /// The result maps each stats id to an object with camelCase members and
+    /// a W3C `type` string such as `"inbound-rtp"` or `"candidate-pair"`, as
+    /// consumed by webrtc-internals style tooling. Absent optional values are
+    /// omitted.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("stats report serializes to JSON")
+    }
+
     /// Get inbound RTP stats
     pub fn inbound_rtp_stats(&self) -> Vec<&InboundRtpStats> {
         self.stats
@@ -387,6 +551,46 @@ impl ConnectionQuality {
 
         score
     }
+
+    /// Estimate the audio Mean Opinion Score (1.0 to 4.5) using a simplified E-model
+    ///
+    /// Follows ITU-T G.107 as simplified by Cole & Rosenbluth:
+    ///
+    /// - One-way delay `d` is half the RTT, plus a jitter buffer of twice the
+    ///   jitter, plus 10ms of codec/packetization delay.
+    /// - Delay impairment `Id = 0.024d + 0.11(d - 177.3)` (the second term only
+    ///   above 177.3ms).
+    /// - Loss impairment `Ie_eff = Ie + (95 - Ie) * Ppl / (Ppl + Bpl)` with
+    ///   `Ie = 0` and `Bpl = 25.1`, the G.113 values for G.711 with packet loss
+    ///   concealment, used as a stand-in for modern codecs such as Opus.
+    /// - `R = 93.2 - Id - Ie_eff`, mapped to MOS with the G.107 formula.
+    ///
+    /// Loss is assumed random (not bursty) and missing metrics are treated as
+    /// ideal, so a report without data yields the maximum of about 4.4.
+    pub fn estimated_mos(&self) -> f64 {
+        const BASE_R: f64 = 93.2;
+        const CODEC_DELAY_MS: f64 = 10.0;
+        const EQUIPMENT_IMPAIRMENT: f64 = 0.0;
+        const PACKET_LOSS_ROBUSTNESS: f64 = 25.1;
+
+        let rtt = self.rtt_ms.unwrap_or(0.0).max(0.0);
+        let jitter = self.jitter_ms.unwrap_or(0.0).max(0.0);
+        let loss = self.packet_loss_percent.unwrap_or(0.0).clamp(0.0, 100.0);
+
+        let delay = rtt / 2.0 + 2.0 * jitter + CODEC_DELAY_MS;
+        let delay_impairment = 0.024 * delay + 0.11 * (delay - 177.3).max(0.0);
+        let loss_impairment = EQUIPMENT_IMPAIRMENT
+            + (95.0 - EQUIPMENT_IMPAIRMENT) * loss / (loss + PACKET_LOSS_ROBUSTNESS);
+
+        let r = BASE_R - delay_impairment - loss_impairment;
+        if r <= 0.0 {
+            1.0
+        } else if r >= 100.0 {
+            4.5
+        } else {
+            1.0 + 0.035 * r + 7.0e-6 * r * (r - 60.0) * (100.0 - r)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -622,6 +826,183 @@ mod tests {
         assert!((quality.quality_score - 1.0).abs() < 0.01); // No data = max score
     }
 
+    fn quality(rtt_ms: f64, jitter_ms: f64, packet_loss_percent: f64) -> ConnectionQuality {
+        ConnectionQuality {
+            rtt_ms: Some(rtt_ms),
+            jitter_ms: Some(jitter_ms),
+            packet_loss_percent: Some(packet_loss_percent),
+            outgoing_bitrate_bps: None,
+            incoming_bitrate_bps: None,
+            quality_score: ConnectionQuality::calculate_quality_score(
+                Some(rtt_ms),
+                Some(jitter_ms),
+                Some(packet_loss_percent),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_estimated_mos_good_network() {
+        let mos = quality(25.0, 5.0, 0.1).estimated_mos();
+        assert!((4.2..=4.5).contains(&mos), "mos = {}", mos);
+    }
+
+    #[test]
+    fn test_estimated_mos_heavy_loss_and_jitter() {
+        let mos = quality(400.0, 100.0, 20.0).estimated_mos();
+        assert!(mos < 1.5, "mos = {}", mos);
+        assert!(mos >= 1.0);
+    }
+
+    #[test]
+    fn test_estimated_mos_degrades_with_each_metric() {
+        let baseline = quality(25.0, 5.0, 0.1).estimated_mos();
+
+        assert!(quality(300.0, 5.0, 0.1).estimated_mos() < baseline);
+        assert!(quality(25.0, 60.0, 0.1).estimated_mos() < baseline);
+        assert!(quality(25.0, 5.0, 5.0).estimated_mos() < baseline);
+    }
+
+    #[test]
+    fn test_estimated_mos_without_data_is_maximal() {
+        let report = RtcStatsReport::new();
+        let mos = ConnectionQuality::from_stats_report(&report).estimated_mos();
+        assert!((4.4..=4.5).contains(&mos), "mos = {}", mos);
+    }
+
+    #[test]
+    fn test_estimated_mos_total_loss_is_floor() {
+        let mos = quality(0.0, 0.0, 100.0).estimated_mos();
+        assert!((mos - 1.0).abs() < 0.2, "mos = {}", mos);
+    }
+
+    fn populated_report() -> RtcStatsReport {
+        let mut report = RtcStatsReport::new();
+        report.add(
+            "inbound-1".to_string(),
+            RtcStats::InboundRtp(InboundRtpStats {
+                base: RtcStatsBase {
+                    id: "inbound-1".to_string(),
+                    timestamp: 1234567890.0,
+                    stats_type: RtcStatsType::InboundRtp,
+                },
+                ssrc: 12345,
+                kind: "audio".to_string(),
+                packets_received: 1000,
+                bytes_received: 160000,
+                packets_lost: 3,
+                jitter: 0.004,
+                fraction_lost: None,
+            }),
+        );
+        report.add(
+            "pair-1".to_string(),
+            RtcStats::CandidatePair(CandidatePairStats {
+                base: RtcStatsBase {
+                    id: "pair-1".to_string(),
+                    timestamp: 1234567890.0,
+                    stats_type: RtcStatsType::CandidatePair,
+                },
+                local_candidate_id: "local-1".to_string(),
+                remote_candidate_id: "remote-1".to_string(),
+                state: CandidatePairState::InProgress,
+                nominated: true,
+                bytes_sent: 500,
+                bytes_received: 700,
+                total_round_trip_time: 0.1,
+                current_round_trip_time: Some(0.025),
+                available_outgoing_bitrate: None,
+                available_incoming_bitrate: None,
+            }),
+        );
+        report.add(
+            "local-1".to_string(),
+            RtcStats::LocalCandidate(IceCandidateStats {
+                base: RtcStatsBase {
+                    id: "local-1".to_string(),
+                    timestamp: 1234567890.0,
+                    stats_type: RtcStatsType::LocalCandidate,
+                },
+                transport_id: "transport-1".to_string(),
+                address: Some("192.0.2.1".to_string()),
+                port: Some(50000),
+                protocol: "udp".to_string(),
+                candidate_type: IceCandidateType::Srflx,
+                priority: Some(1686052607),
+                url: None,
+            }),
+        );
+        report
+    }
+
+    #[test]
+    fn test_stats_report_to_json_shape() {
+        let json = populated_report().to_json();
+
+        let inbound = &json["inbound-1"];
+        assert_eq!(inbound["type"], "inbound-rtp");
+        assert_eq!(inbound["id"], "inbound-1");
+        assert_eq!(inbound["timestamp"], 1234567890.0);
+        assert_eq!(inbound["packetsReceived"], 1000);
+        assert_eq!(inbound["bytesReceived"], 160000);
+        assert!(inbound.get("fractionLost").is_none());
+
+        let pair = &json["pair-1"];
+        assert_eq!(pair["type"], "candidate-pair");
+        assert_eq!(pair["state"], "in-progress");
+        assert_eq!(pair["localCandidateId"], "local-1");
+        assert_eq!(pair["currentRoundTripTime"], 0.025);
+
+        let local = &json["local-1"];
+        assert_eq!(local["type"], "local-candidate");
+        assert_eq!(local["candidateType"], "srflx");
+        assert_eq!(local["transportId"], "transport-1");
+    }
+
+    #[test]
+    fn test_stats_report_json_round_trip() {
+        let report = populated_report();
+        let parsed: RtcStatsReport = serde_json::from_value(report.to_json()).unwrap();
+
+        assert_eq!(parsed.stats.len(), 3);
+        assert_eq!(
+            parsed.inbound_rtp_stats()[0].base.stats_type,
+            RtcStatsType::InboundRtp
+        );
+        assert_eq!(parsed.inbound_rtp_stats()[0].packets_received, 1000);
+
+        let pair = parsed.nominated_candidate_pair().unwrap();
+        assert_eq!(pair.base.stats_type, RtcStatsType::CandidatePair);
+        assert_eq!(pair.state, CandidatePairState::InProgress);
+        assert_eq!(pair.current_round_trip_time, Some(0.025));
+        assert_eq!(pair.available_outgoing_bitrate, None);
+
+        match &parsed.stats["local-1"] {
+            RtcStats::LocalCandidate(candidate) => {
+                assert_eq!(candidate.base.stats_type, RtcStatsType::LocalCandidate);
+                assert_eq!(candidate.candidate_type, IceCandidateType::Srflx);
+                assert_eq!(candidate.port, Some(50000));
+            }
+            other => panic!("expected local candidate, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stats_type_strings() {
+        assert_eq!(
+            RtcStatsType::RemoteInboundRtp.as_str(),
+            "remote-inbound-rtp"
+        );
+        assert_eq!(
+            RtcStatsType::from("outbound-rtp".to_string()),
+            RtcStatsType::OutboundRtp
+        );
+        assert_eq!(
+            RtcStatsType::from("media-playout".to_string()),
+            RtcStatsType::Unknown("media-playout".to_string())
+        );
+    }
+
     #[test]
     fn test_ice_candidate_type_variants() {
         assert_eq!(IceCandidateType::Host, IceCandidateType::Host);
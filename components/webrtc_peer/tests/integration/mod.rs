@@ -83,3 +83,135 @@ async fn test_peer_connection_with_turn_server() {
     let peer = RtcPeerConnection::new(config).await;
     assert!(peer.is_ok());
 }
+
+#[tokio::test]
+async fn test_ice_candidates_buffered_until_remote_description() {
+    // Candidates trickled in before the offer are applied once it is set
+
+    let config = RtcConfiguration {
+        ice_servers: vec![],
+        ice_transport_policy: IceTransportPolicy::All,
+        bundle_policy: BundlePolicy::Balanced,
+    };
+
+    let mut offerer = RtcPeerConnection::new(config.clone()).await.unwrap();
+    let mut answerer = RtcPeerConnection::new(config).await.unwrap();
+
+    offerer
+        .add_track(MediaTrack::audio("audio", "stream"))
+        .await
+        .unwrap();
+    let offer = offerer
+        .create_offer(OfferOptions {
+            voice_activity_detection: true,
+            ice_restart: false,
+        })
+        .await
+        .unwrap();
+    offerer.set_local_description(offer.clone()).await.unwrap();
+
+    for port in [50000, 50001] {
+        answerer
+            .add_ice_candidate(IceCandidate {
+                candidate: format!(
+                    "candidate:{} 1 udp 2130706431 127.0.0.1 {} typ host",
+                    port, port
+                ),
+                sdp_mid: Some("0".to_string()),
+                sdp_m_line_index: Some(0),
+            })
+            .await
+            .expect("early candidates should be buffered");
+    }
+    assert_eq!(answerer.pending_ice_candidate_count().await, 2);
+
+    answerer.set_remote_description(offer).await.unwrap();
+    assert_eq!(answerer.pending_ice_candidate_count().await, 0);
+
+    // Later candidates are applied directly
+    answerer
+        .add_ice_candidate(IceCandidate {
+            candidate: "candidate:3 1 udp 2130706431 127.0.0.1 50002 typ host".to_string(),
+            sdp_mid: Some("0".to_string()),
+            sdp_m_line_index: Some(0),
+        })
+        .await
+        .unwrap();
+    assert_eq!(answerer.pending_ice_candidate_count().await, 0);
+
+    offerer.close().await.unwrap();
+    answerer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_replace_track_keeps_sender() {
+    let config = RtcConfiguration {
+        ice_servers: vec![],
+        ice_transport_policy: IceTransportPolicy::All,
+        bundle_policy: BundlePolicy::Balanced,
+    };
+    let peer = RtcPeerConnection::new(config).await.unwrap();
+
+    let sender = peer
+        .add_track(MediaTrack::audio("mic-1", "stream"))
+        .await
+        .unwrap();
+
+    peer.replace_track("mic-1", MediaTrack::audio("mic-2", "stream"))
+        .await
+        .expect("audio can replace audio");
+
+    // The same sender now sends the new track
+    let replaced = peer.get_sender("mic-2").await.expect("sender moved");
+    assert!(std::sync::Arc::ptr_eq(replaced.inner(), sender.inner()));
+    assert!(peer.get_sender("mic-1").await.is_none());
+    assert_eq!(peer.get_local_track_ids().await, vec!["mic-2".to_string()]);
+
+    // Kind mismatches are rejected and leave the mapping alone
+    let result = peer
+        .replace_track("mic-2", MediaTrack::video_vp8("camera", "stream"))
+        .await;
+    assert!(result.is_err());
+    assert!(peer.get_sender("mic-2").await.is_some());
+    assert!(peer.get_sender("camera").await.is_none());
+
+    // Unknown senders are rejected
+    assert!(peer
+        .replace_track("missing", MediaTrack::audio("mic-3", "stream"))
+        .await
+        .is_err());
+
+    peer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_sender_and_receiver_enumeration() {
+    let config = RtcConfiguration {
+        ice_servers: vec![],
+        ice_transport_policy: IceTransportPolicy::All,
+        bundle_policy: BundlePolicy::Balanced,
+    };
+    let peer = RtcPeerConnection::new(config).await.unwrap();
+    assert!(peer.senders().await.is_empty());
+
+    peer.add_track(MediaTrack::audio("mic", "stream"))
+        .await
+        .unwrap();
+
+    let senders = peer.senders().await;
+    assert_eq!(senders.len(), 1);
+    let sender = &senders[0];
+    assert_eq!(sender.track_id.as_deref(), Some("mic"));
+    assert_eq!(sender.kind, MediaTrackKind::Audio);
+    assert_ne!(sender.ssrc, 0);
+    assert_eq!(sender.direction, RtpTransceiverDirection::SendRecv);
+
+    // The transceiver's receiver exists before any remote media arrives
+    let receivers = peer.receivers().await;
+    assert_eq!(receivers.len(), 1);
+    assert_eq!(receivers[0].kind, MediaTrackKind::Audio);
+    assert!(receivers[0].track_id.is_none());
+    assert!(receivers[0].ssrc.is_none());
+
+    peer.close().await.unwrap();
+}
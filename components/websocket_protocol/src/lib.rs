@@ -1,18 +1,27 @@
 //! websocket_protocol component
 //!
 //! WebSocket client with frame parsing/encoding, ping/pong, compression extensions,
-//! and automatic reconnection with exponential backoff.
+//! and automatic reconnection with exponential backoff, plus a minimal server-side
+//! handshake for local servers.
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
 use futures::{SinkExt, StreamExt};
 use network_errors::NetworkError;
+use std::io::{Read, Write};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{mpsc, watch, Mutex};
 use tokio_tungstenite::tungstenite::{
     self,
+    client::IntoClientRequest,
+    handshake::client::Request,
+    handshake::server::{Request as ServerRequest, Response as ServerResponse},
+    http::{HeaderMap, HeaderValue},
     protocol::CloseFrame as TungsteniteCloseFrame,
     protocol::WebSocketConfig as TungsteniteConfig,
 };
@@ -307,6 +316,12 @@ pub struct WebSocketConfig {
     max_send_queue: Option<usize>,
     /// Accept unmasked frames from server (not recommended for security)
     accept_unmasked_frames: bool,
+    /// Maximum time for the TCP, TLS and upgrade handshake (None = no limit)
+    handshake_timeout: Option<Duration>,
+    /// Capacity of the channel holding received messages until `recv`
+    incoming_buffer: usize,
+    /// Capacity of the channel holding messages queued by `send`
+    outgoing_buffer: usize,
 }
 
 impl Default for WebSocketConfig {
@@ -317,6 +332,9 @@ impl Default for WebSocketConfig {
             max_frame_size: Some(16 << 20),   // 16 MB
             max_send_queue: None,
             accept_unmasked_frames: false,
+            handshake_timeout: None,
+            incoming_buffer: DEFAULT_MESSAGE_BUFFER,
+            outgoing_buffer: DEFAULT_MESSAGE_BUFFER,
         }
     }
 }
@@ -429,6 +447,83 @@ impl WebSocketConfig {
         self.accept_unmasked_frames
     }
 
+    /// Set the opening handshake timeout
+    ///
+    /// Bounds connecting, the TLS handshake and the HTTP upgrade together.
+    /// A handshake taking longer fails with `NetworkError::Timeout`. Messages
+    /// on an established connection aren't affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum handshake duration, or None to wait indefinitely
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use websocket_protocol::WebSocketConfig;
+    ///
+    /// let config = WebSocketConfig::new().handshake_timeout(Some(Duration::from_secs(10)));
+    /// assert_eq!(config.get_handshake_timeout(), Some(Duration::from_secs(10)));
+    /// ```
+    pub fn handshake_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    /// Get the opening handshake timeout setting
+    pub fn get_handshake_timeout(&self) -> Option<Duration> {
+        self.handshake_timeout
+    }
+
+    /// Set how many received messages are buffered until read
+    ///
+    /// Once the buffer is full, the connection stops reading from the socket
+    /// until [`WebSocketConnection::recv`] makes room, so a slow consumer
+    /// pushes back on the server through TCP flow control. Values below 1
+    /// are treated as 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of messages (default: 100)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use websocket_protocol::WebSocketConfig;
+    ///
+    /// let config = WebSocketConfig::new().incoming_buffer(8);
+    /// assert_eq!(config.get_incoming_buffer(), 8);
+    /// ```
+    pub fn incoming_buffer(mut self, capacity: usize) -> Self {
+        self.incoming_buffer = capacity.max(1);
+        self
+    }
+
+    /// Get the incoming message buffer capacity
+    pub fn get_incoming_buffer(&self) -> usize {
+        self.incoming_buffer
+    }
+
+    /// Set how many outgoing messages are buffered until written
+    ///
+    /// Once the buffer is full, [`WebSocketConnection::send`] waits until
+    /// queued messages have been written to the socket. Values below 1 are
+    /// treated as 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Number of messages (default: 100)
+    pub fn outgoing_buffer(mut self, capacity: usize) -> Self {
+        self.outgoing_buffer = capacity.max(1);
+        self
+    }
+
+    /// Get the outgoing message buffer capacity
+    pub fn get_outgoing_buffer(&self) -> usize {
+        self.outgoing_buffer
+    }
+
     /// Convert to internal tungstenite configuration
     #[allow(deprecated)] // max_send_queue is deprecated in tungstenite but we expose it for API completeness
     fn to_tungstenite_config(&self) -> TungsteniteConfig {
@@ -442,6 +537,78 @@ impl WebSocketConfig {
     }
 }
 
+/// Await an opening handshake, giving up after `timeout` if one is set
+async fn with_handshake_timeout<T>(
+    timeout: Option<Duration>,
+    handshake: impl std::future::Future<Output = Result<T, tungstenite::Error>>,
+) -> Result<T, NetworkError> {
+    let result = match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, handshake)
+            .await
+            .map_err(|_| NetworkError::Timeout(timeout))?,
+        None => handshake.await,
+    };
+    result.map_err(|e| NetworkError::WebSocketError(format!("Connection failed: {}", e)))
+}
+
+// ==================== Application-level Message Compression ====================
+
+/// Flag byte marking a `Binary` payload sent as-is by [`WebSocketConnection::send_compressed`]
+///
+/// The flag is followed by the original payload.
+pub const UNCOMPRESSED_MESSAGE_FLAG: u8 = 0x00;
+
+/// Flag byte marking a `Binary` payload deflated by [`WebSocketConnection::send_compressed`]
+///
+/// The flag is followed by the raw DEFLATE stream of the original payload.
+pub const COMPRESSED_MESSAGE_FLAG: u8 = 0x01;
+
+/// Deflate `payload` and prepend [`COMPRESSED_MESSAGE_FLAG`]
+fn deflate_payload(payload: &[u8]) -> Result<Vec<u8>, NetworkError> {
+    let mut encoder = DeflateEncoder::new(
+        vec![COMPRESSED_MESSAGE_FLAG],
+        flate2::Compression::default(),
+    );
+    encoder
+        .write_all(payload)
+        .and_then(|_| encoder.finish())
+        .map_err(|e| NetworkError::WebSocketError(format!("Failed to compress message: {}", e)))
+}
+
+/// Unframe a payload produced by [`WebSocketConnection::send_compressed`]
+///
+/// Inflation stops once the output exceeds `limit` bytes, so a small
+/// payload can't expand into an unbounded allocation.
+fn inflate_payload(mut payload: Vec<u8>, limit: Option<usize>) -> Result<Vec<u8>, NetworkError> {
+    match payload.first() {
+        Some(&UNCOMPRESSED_MESSAGE_FLAG) => {
+            payload.remove(0);
+            return Ok(payload);
+        }
+        Some(&COMPRESSED_MESSAGE_FLAG) => {}
+        _ => {
+            return Err(NetworkError::WebSocketError(
+                "Message is missing its compression flag".to_string(),
+            ))
+        }
+    }
+
+    let max_read = limit.map_or(u64::MAX, |limit| (limit as u64).saturating_add(1));
+    let mut inflated = Vec::new();
+    DeflateDecoder::new(&payload[1..])
+        .take(max_read)
+        .read_to_end(&mut inflated)
+        .map_err(|e| {
+            NetworkError::WebSocketError(format!("Failed to decompress message: {}", e))
+        })?;
+    if limit.is_some_and(|limit| inflated.len() > limit) {
+        return Err(NetworkError::WebSocketError(
+            "Decompressed message exceeds max_message_size".to_string(),
+        ));
+    }
+    Ok(inflated)
+}
+
 // ==================== WebSocket Message Types ====================
 
 /// WebSocket message types
@@ -525,6 +692,9 @@ pub enum WebSocketState {
     Closed,
 }
 
+/// Default capacity of a connection's incoming and outgoing message buffers
+const DEFAULT_MESSAGE_BUFFER: usize = 100;
+
 /// WebSocket connection handle
 ///
 /// Provides methods to send/receive messages and manage the WebSocket connection.
@@ -545,6 +715,12 @@ pub struct WebSocketConnection {
     compression_requested: bool,
     /// Negotiated compression configuration (if compression was successfully negotiated)
     compression_config: Option<CompressionConfig>,
+    /// Application-level compression used by `send_compressed` and `recv`
+    message_compression: Option<CompressionConfig>,
+    /// Largest message `recv` will inflate a compressed payload into
+    max_message_size: Option<usize>,
+    /// Remote address of the underlying socket
+    peer_addr: Option<SocketAddr>,
 }
 
 impl std::fmt::Debug for WebSocketConnection {
@@ -556,6 +732,8 @@ impl std::fmt::Debug for WebSocketConnection {
             .field("state", &self.state)
             .field("compression_requested", &self.compression_requested)
             .field("compression_config", &self.compression_config)
+            .field("message_compression", &self.message_compression)
+            .field("peer_addr", &self.peer_addr)
             .finish()
     }
 }
@@ -573,8 +751,8 @@ impl WebSocketConnection {
         protocol: Option<String>,
         extensions: Vec<String>,
     ) -> (Self, mpsc::Sender<WebSocketMessage>, mpsc::Receiver<WebSocketMessage>) {
-        let (tx_out, rx_out) = mpsc::channel(100);
-        let (tx_in, rx_in) = mpsc::channel(100);
+        let (tx_out, rx_out) = mpsc::channel(DEFAULT_MESSAGE_BUFFER);
+        let (tx_in, rx_in) = mpsc::channel(DEFAULT_MESSAGE_BUFFER);
 
         let connection = Self {
             url,
@@ -585,6 +763,9 @@ impl WebSocketConnection {
             state: WebSocketState::Connecting,
             compression_requested: false,
             compression_config: None,
+            message_compression: None,
+            max_message_size: WebSocketConfig::default().max_message_size,
+            peer_addr: None,
         };
 
         (connection, tx_in, rx_out)
@@ -602,46 +783,7 @@ impl WebSocketConnection {
         ws_stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
         protocol: Option<String>,
     ) -> Self {
-        let (tx_in, mut rx_in) = mpsc::channel::<WebSocketMessage>(100);
-        let (tx_out, rx_out) = mpsc::channel::<WebSocketMessage>(100);
-
-        let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-
-        // Task to forward messages from WebSocket to internal channel
-        tokio::spawn(async move {
-            while let Some(msg_result) = ws_receiver.next().await {
-                match msg_result {
-                    Ok(msg) => {
-                        let ws_msg: WebSocketMessage = msg.into();
-                        if tx_out.send(ws_msg).await.is_err() {
-                            break; // Channel closed
-                        }
-                    }
-                    Err(_) => break, // Error receiving
-                }
-            }
-        });
-
-        // Task to forward messages from internal channel to WebSocket
-        tokio::spawn(async move {
-            while let Some(msg) = rx_in.recv().await {
-                let tungstenite_msg: tungstenite::Message = msg.into();
-                if ws_sender.send(tungstenite_msg).await.is_err() {
-                    break; // Error sending or closed
-                }
-            }
-        });
-
-        Self {
-            url,
-            protocol,
-            extensions: vec![],
-            sender: tx_in,
-            receiver: rx_out,
-            state: WebSocketState::Open,
-            compression_requested: false,
-            compression_config: None,
-        }
+        Self::from_stream_with_compression(url, ws_stream, protocol, false, None)
     }
 
     /// Create a connection from an established WebSocket stream with compression info
@@ -660,12 +802,33 @@ impl WebSocketConnection {
         compression_requested: bool,
         compression_config: Option<CompressionConfig>,
     ) -> Self {
-        let (tx_in, mut rx_in) = mpsc::channel::<WebSocketMessage>(100);
-        let (tx_out, rx_out) = mpsc::channel::<WebSocketMessage>(100);
+        Self::open(
+            url,
+            ws_stream,
+            protocol,
+            compression_requested,
+            compression_config,
+            &WebSocketConfig::default(),
+        )
+    }
+
+    /// Create an open connection, buffering messages as `config` specifies
+    fn open(
+        url: Url,
+        ws_stream: WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+        protocol: Option<String>,
+        compression_requested: bool,
+        compression_config: Option<CompressionConfig>,
+        config: &WebSocketConfig,
+    ) -> Self {
+        let (tx_in, mut rx_in) = mpsc::channel::<WebSocketMessage>(config.outgoing_buffer);
+        let (tx_out, rx_out) = mpsc::channel::<WebSocketMessage>(config.incoming_buffer);
 
+        let peer_addr = stream_peer_addr(&ws_stream);
         let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
-        // Task to forward messages from WebSocket to internal channel
+        // Task to forward messages from WebSocket to internal channel. While
+        // the channel is full the socket isn't read, which applies backpressure.
         tokio::spawn(async move {
             while let Some(msg_result) = ws_receiver.next().await {
                 match msg_result {
@@ -706,6 +869,9 @@ impl WebSocketConnection {
             state: WebSocketState::Open,
             compression_requested,
             compression_config,
+            message_compression: None,
+            max_message_size: config.max_message_size,
+            peer_addr,
         }
     }
 
@@ -738,6 +904,53 @@ impl WebSocketConnection {
         self.compression_config.as_ref()
     }
 
+    /// Enable application-level message compression
+    ///
+    /// Interim fallback while tungstenite lacks permessage-deflate: when
+    /// `config` is enabled, [`send_compressed`](Self::send_compressed)
+    /// prefixes every `Binary` payload with [`COMPRESSED_MESSAGE_FLAG`] or
+    /// [`UNCOMPRESSED_MESSAGE_FLAG`], and [`recv`](Self::recv) expects that
+    /// flag on every `Binary` payload it receives. Both peers must opt in to
+    /// this framing; connecting never enables it. A disabled `config` leaves
+    /// the connection unchanged.
+    pub fn with_message_compression(mut self, config: CompressionConfig) -> Self {
+        self.message_compression = config.is_enabled().then_some(config);
+        self
+    }
+
+    /// Check if application-level message compression is enabled
+    pub fn is_message_compression_enabled(&self) -> bool {
+        self.message_compression.is_some()
+    }
+
+    /// Send a message, deflating `Binary` payloads when message compression is enabled
+    ///
+    /// Each `Binary` payload is prefixed with a flag byte saying whether it was
+    /// deflated; it is sent as-is (behind [`UNCOMPRESSED_MESSAGE_FLAG`]) if
+    /// deflating would not make it smaller. Without message compression, and
+    /// for other message types, this is the same as [`send`](Self::send).
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or network error
+    pub async fn send_compressed(&self, message: WebSocketMessage) -> Result<(), NetworkError> {
+        let message = match message {
+            WebSocketMessage::Binary(data) if self.message_compression.is_some() => {
+                let compressed = deflate_payload(&data)?;
+                if compressed.len() <= data.len() {
+                    WebSocketMessage::Binary(compressed)
+                } else {
+                    let mut framed = Vec::with_capacity(data.len() + 1);
+                    framed.push(UNCOMPRESSED_MESSAGE_FLAG);
+                    framed.extend_from_slice(&data);
+                    WebSocketMessage::Binary(framed)
+                }
+            }
+            other => other,
+        };
+        self.send(message).await
+    }
+
     /// Send a message through the WebSocket
     ///
     /// # Arguments
@@ -759,8 +972,17 @@ impl WebSocketConnection {
     /// # Returns
     ///
     /// Option containing the result of receiving a message, or None if the channel is closed
+    ///
+    /// When message compression is enabled, `Binary` payloads sent by
+    /// [`send_compressed`](Self::send_compressed) are inflated before being returned.
     pub async fn recv(&mut self) -> Option<Result<WebSocketMessage, NetworkError>> {
-        self.receiver.recv().await.map(Ok)
+        let message = self.receiver.recv().await?;
+        match message {
+            WebSocketMessage::Binary(data) if self.message_compression.is_some() => {
+                Some(inflate_payload(data, self.max_message_size).map(WebSocketMessage::Binary))
+            }
+            other => Some(Ok(other)),
+        }
     }
 
     /// Close the WebSocket connection
@@ -781,6 +1003,52 @@ impl WebSocketConnection {
         Ok(())
     }
 
+    /// Close the WebSocket connection, waiting for the peer to acknowledge
+    ///
+    /// Sends a Close frame, then waits for the peer's Close frame before moving
+    /// to `Closed`. Messages still arriving from the peer before its Close
+    /// frame are discarded. The state is `Closed` when this returns, even on
+    /// error.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - Close status code
+    /// * `reason` - Human-readable reason for closure
+    /// * `timeout` - How long to wait for the peer's Close frame
+    ///
+    /// # Returns
+    ///
+    /// The peer's close frame (None if it sent no status or dropped the
+    /// connection without one), or `NetworkError::Timeout` if it did not
+    /// answer in time
+    pub async fn close_gracefully(
+        &mut self,
+        code: u16,
+        reason: String,
+        timeout: Duration,
+    ) -> Result<Option<CloseFrame>, NetworkError> {
+        self.state = WebSocketState::Closing;
+        let close_msg = WebSocketMessage::Close(Some(CloseFrame { code, reason }));
+        if let Err(e) = self.send(close_msg).await {
+            self.state = WebSocketState::Closed;
+            return Err(e);
+        }
+
+        let receiver = &mut self.receiver;
+        let acknowledgment = tokio::time::timeout(timeout, async move {
+            while let Some(message) = receiver.recv().await {
+                if let WebSocketMessage::Close(frame) = message {
+                    return frame;
+                }
+            }
+            None
+        })
+        .await;
+
+        self.state = WebSocketState::Closed;
+        acknowledgment.map_err(|_| NetworkError::Timeout(timeout))
+    }
+
     /// Get connection state
     ///
     /// # Returns
@@ -790,6 +1058,24 @@ impl WebSocketConnection {
         self.state
     }
 
+    /// Number of received messages buffered and not yet read
+    ///
+    /// Reaches the configured [`WebSocketConfig::incoming_buffer`] when the
+    /// consumer falls behind, at which point reading from the socket pauses.
+    pub fn incoming_len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Get the remote address of the underlying socket
+    ///
+    /// # Returns
+    ///
+    /// The peer address captured when the connection was established, or None
+    /// if the connection was not built from a socket or the address was unavailable
+    pub fn peer_addr(&self) -> Option<SocketAddr> {
+        self.peer_addr
+    }
+
     /// Update connection state (internal use)
     #[allow(dead_code)]
     fn set_state(&mut self, state: WebSocketState) {
@@ -797,6 +1083,18 @@ impl WebSocketConnection {
     }
 }
 
+/// Remote address of the TCP socket beneath a WebSocket stream
+fn stream_peer_addr(
+    ws_stream: &WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>,
+) -> Option<SocketAddr> {
+    match ws_stream.get_ref() {
+        MaybeTlsStream::Plain(tcp) => tcp.peer_addr().ok(),
+        // TLS variants only exist when tokio-tungstenite's TLS features are enabled
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
 /// WebSocket client
 ///
 /// Provides methods to establish WebSocket connections.
@@ -898,6 +1196,8 @@ impl WebSocketClient {
     /// does not include built-in permessage-deflate support. When compression is
     /// enabled in the config, the connection will record that compression was requested,
     /// but actual compression will only be active when upstream support is available.
+    /// Application-level message compression is not turned on by the config; opt in
+    /// with [`WebSocketConnection::with_message_compression`].
     pub async fn connect_with_config(
         &self,
         url: Url,
@@ -918,13 +1218,15 @@ impl WebSocketClient {
 
         // Connect using tokio-tungstenite with configuration
         // Note: disable_nagle is set to false to allow Nagle's algorithm for better throughput
-        let (ws_stream, _response) = connect_async_with_config(
-            url.as_str(),
-            Some(tungstenite_config),
-            false, // disable_nagle
+        let (ws_stream, _response) = with_handshake_timeout(
+            config.get_handshake_timeout(),
+            connect_async_with_config(
+                url.as_str(),
+                Some(tungstenite_config),
+                false, // disable_nagle
+            ),
         )
-        .await
-        .map_err(|e| NetworkError::WebSocketError(format!("Connection failed: {}", e)))?;
+        .await?;
 
         // TODO: Extract negotiated protocol and extensions from response headers
         // When tungstenite adds permessage-deflate support, we'll parse the
@@ -935,22 +1237,270 @@ impl WebSocketClient {
         // When support is added, we would parse the response headers here
         let compression_config = None;
 
-        Ok(WebSocketConnection::from_stream_with_compression(
+        Ok(WebSocketConnection::open(
             url,
             ws_stream,
             protocol,
             compression_requested,
             compression_config,
+            &config,
+        ))
+    }
+
+    /// Connect to a WebSocket server with extra handshake headers
+    ///
+    /// Sends `headers` (for example `Authorization` or `Cookie`) on the
+    /// opening handshake request. Requested `protocols` are sent in the
+    /// `Sec-WebSocket-Protocol` header.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - The WebSocket URL to connect to (ws:// or wss://)
+    /// * `protocols` - List of subprotocols to request
+    /// * `headers` - Additional headers to send on the handshake request
+    /// * `config` - WebSocket configuration including compression settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `NetworkError::InvalidUrl` for a non-WebSocket scheme and
+    /// `NetworkError::WebSocketError` if `headers` contains a header that is
+    /// reserved for the handshake (see [`RESERVED_HANDSHAKE_HEADERS`]).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use websocket_protocol::{WebSocketClient, WebSocketConfig};
+    /// use tungstenite::http::{HeaderMap, HeaderValue};
+    /// use url::Url;
+    ///
+    /// # async fn example() -> Result<(), network_errors::NetworkError> {
+    /// let client = WebSocketClient::new();
+    /// let url = Url::parse("wss://example.com/socket").unwrap();
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert("authorization", HeaderValue::from_static("Bearer token"));
+    ///
+    /// let connection = client
+    ///     .connect_with_headers(url, vec![], headers, WebSocketConfig::new())
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_headers(
+        &self,
+        url: Url,
+        protocols: Vec<String>,
+        headers: HeaderMap,
+        config: WebSocketConfig,
+    ) -> Result<WebSocketConnection, NetworkError> {
+        let request = build_handshake_request(&url, &protocols, headers)?;
+
+        let compression_requested = config.is_compression_enabled();
+        let tungstenite_config = config.to_tungstenite_config();
+
+        let (ws_stream, response) = with_handshake_timeout(
+            config.get_handshake_timeout(),
+            connect_async_with_config(request, Some(tungstenite_config), false),
+        )
+        .await?;
+
+        let protocol = response
+            .headers()
+            .get("sec-websocket-protocol")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        Ok(WebSocketConnection::open(
+            url,
+            ws_stream,
+            protocol,
+            compression_requested,
+            None,
+            &config,
         ))
     }
 }
 
+/// Handshake headers managed by the WebSocket client
+///
+/// These headers are generated from the URL, the requested subprotocols and
+/// the RFC 6455 handshake itself, so they cannot be supplied by callers of
+/// [`WebSocketClient::connect_with_headers`].
+pub const RESERVED_HANDSHAKE_HEADERS: &[&str] = &[
+    "host",
+    "connection",
+    "upgrade",
+    "sec-websocket-key",
+    "sec-websocket-version",
+    "sec-websocket-protocol",
+    "sec-websocket-extensions",
+];
+
+/// Build the opening handshake request for `url` with extra headers
+fn build_handshake_request(
+    url: &Url,
+    protocols: &[String],
+    headers: HeaderMap,
+) -> Result<Request, NetworkError> {
+    let scheme = url.scheme();
+    if scheme != "ws" && scheme != "wss" {
+        return Err(NetworkError::InvalidUrl(format!(
+            "Invalid WebSocket scheme: {}. Expected 'ws' or 'wss'",
+            scheme
+        )));
+    }
+
+    if let Some(name) = headers
+        .keys()
+        .find(|name| RESERVED_HANDSHAKE_HEADERS.contains(&name.as_str()))
+    {
+        return Err(NetworkError::WebSocketError(format!(
+            "Header {} is reserved for the WebSocket handshake",
+            name
+        )));
+    }
+
+    let mut request = url
+        .as_str()
+        .into_client_request()
+        .map_err(|e| NetworkError::WebSocketError(format!("Invalid handshake request: {}", e)))?;
+
+    if !protocols.is_empty() {
+        let value = HeaderValue::from_str(&protocols.join(", ")).map_err(|e| {
+            NetworkError::WebSocketError(format!("Invalid subprotocol list: {}", e))
+        })?;
+        request
+            .headers_mut()
+            .insert("sec-websocket-protocol", value);
+    }
+
+    request.headers_mut().extend(headers);
+    Ok(request)
+}
+
 impl Default for WebSocketClient {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// WebSocket server
+///
+/// Accepts WebSocket connections on already-accepted TCP streams by
+/// performing the RFC 6455 server handshake, including the
+/// `Sec-WebSocket-Accept` key derivation. Intended for local servers such as
+/// echo servers in tests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio::net::TcpListener;
+/// use websocket_protocol::{WebSocketMessage, WebSocketServer};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let listener = TcpListener::bind("127.0.0.1:9001").await?;
+/// let (stream, _) = listener.accept().await?;
+///
+/// let mut connection = WebSocketServer::new().accept(stream).await?;
+/// while let Some(Ok(message)) = connection.recv().await {
+///     if let WebSocketMessage::Text(text) = message {
+///         connection.send(WebSocketMessage::Text(text)).await?;
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WebSocketServer {
+    protocols: Vec<String>,
+}
+
+impl WebSocketServer {
+    /// Create a new WebSocket server that supports no subprotocols
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the subprotocols this server supports
+    ///
+    /// The first protocol offered by the client that is in this list is
+    /// selected during the handshake.
+    pub fn with_protocols(mut self, protocols: Vec<String>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
+    /// Perform the server handshake on a freshly accepted TCP stream
+    ///
+    /// The connection URL is built from the request's `Host` header (falling
+    /// back to the local address) and path.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - TCP stream from a listener, before any data was read
+    ///
+    /// # Returns
+    ///
+    /// Result containing the open WebSocket connection or network error
+    // The handshake callback's error type is tungstenite's HTTP error response
+    #[allow(clippy::result_large_err)]
+    pub async fn accept(
+        &self,
+        stream: tokio::net::TcpStream,
+    ) -> Result<WebSocketConnection, NetworkError> {
+        let local_addr = stream
+            .local_addr()
+            .map_err(|e| NetworkError::WebSocketError(format!("Handshake failed: {}", e)))?;
+
+        let mut url = None;
+        let mut protocol = None;
+        let callback = |request: &ServerRequest, mut response: ServerResponse| {
+            let host = request
+                .headers()
+                .get("host")
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+                .unwrap_or_else(|| local_addr.to_string());
+            url = Url::parse(&format!("ws://{}{}", host, request.uri())).ok();
+
+            protocol = select_protocol(request.headers(), &self.protocols);
+            if let Some(value) = protocol
+                .as_deref()
+                .and_then(|protocol| HeaderValue::from_str(protocol).ok())
+            {
+                response
+                    .headers_mut()
+                    .insert("sec-websocket-protocol", value);
+            }
+            Ok(response)
+        };
+
+        let ws_stream =
+            tokio_tungstenite::accept_hdr_async(MaybeTlsStream::Plain(stream), callback)
+                .await
+                .map_err(|e| NetworkError::WebSocketError(format!("Handshake failed: {}", e)))?;
+
+        let url = match url {
+            Some(url) => url,
+            None => Url::parse(&format!("ws://{}/", local_addr))
+                .map_err(|e| NetworkError::InvalidUrl(e.to_string()))?,
+        };
+        Ok(WebSocketConnection::from_stream(url, ws_stream, protocol))
+    }
+}
+
+/// Pick the first subprotocol offered by the client that the server supports
+fn select_protocol(headers: &HeaderMap, supported: &[String]) -> Option<String> {
+    headers
+        .get_all("sec-websocket-protocol")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .find(|offered| supported.iter().any(|protocol| protocol == offered))
+        .map(|offered| offered.to_string())
+}
+
 /// Configuration for WebSocket reconnection behavior
 ///
 /// Controls how the reconnecting WebSocket handles connection drops
@@ -1796,6 +2346,8 @@ mod tests {
         assert_eq!(config.get_max_frame_size(), Some(16 << 20));
         assert_eq!(config.get_max_send_queue(), None);
         assert!(!config.is_accepting_unmasked_frames());
+        assert_eq!(config.get_incoming_buffer(), 100);
+        assert_eq!(config.get_outgoing_buffer(), 100);
     }
 
     #[test]
@@ -1842,6 +2394,20 @@ mod tests {
         assert_eq!(config.get_max_send_queue(), Some(100));
     }
 
+    #[test]
+    fn test_websocket_config_message_buffers() {
+        let config = WebSocketConfig::new()
+            .incoming_buffer(4)
+            .outgoing_buffer(16);
+        assert_eq!(config.get_incoming_buffer(), 4);
+        assert_eq!(config.get_outgoing_buffer(), 16);
+
+        // A zero-capacity buffer can't hold a message, so it is raised to 1
+        let config = WebSocketConfig::new().incoming_buffer(0).outgoing_buffer(0);
+        assert_eq!(config.get_incoming_buffer(), 1);
+        assert_eq!(config.get_outgoing_buffer(), 1);
+    }
+
     #[test]
     fn test_websocket_config_accept_unmasked_frames() {
         let config = WebSocketConfig::new().accept_unmasked_frames(true);
@@ -1900,4 +2466,35 @@ mod tests {
             }
         });
     }
+
+    // ==================== Handshake Header Tests ====================
+
+    #[test]
+    fn test_build_handshake_request_adds_custom_headers() {
+        let url = Url::parse("ws://example.com/socket").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", HeaderValue::from_static("Bearer abc"));
+        headers.insert("cookie", HeaderValue::from_static("session=1"));
+
+        let request = build_handshake_request(&url, &["chat".to_string()], headers).unwrap();
+
+        assert_eq!(request.headers()["authorization"], "Bearer abc");
+        assert_eq!(request.headers()["cookie"], "session=1");
+        assert_eq!(request.headers()["sec-websocket-protocol"], "chat");
+        assert!(request.headers().contains_key("sec-websocket-key"));
+    }
+
+    #[test]
+    fn test_build_handshake_request_rejects_reserved_headers() {
+        let url = Url::parse("ws://example.com/socket").unwrap();
+        for name in RESERVED_HANDSHAKE_HEADERS {
+            let mut headers = HeaderMap::new();
+            headers.insert(*name, HeaderValue::from_static("x"));
+
+            match build_handshake_request(&url, &[], headers) {
+                Err(NetworkError::WebSocketError(msg)) => assert!(msg.contains(name)),
+                other => panic!("Expected WebSocketError for {}, got {:?}", name, other),
+            }
+        }
+    }
 }
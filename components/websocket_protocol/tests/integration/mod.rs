@@ -1,25 +1,12 @@
 //! Integration tests for websocket_protocol
 //!
-//! These tests verify the WebSocket client works with real servers.
-//! They require a running WebSocket echo server to execute.
+//! These tests run the WebSocket client against local servers on loopback,
+//! built with `tokio_tungstenite` or this crate's own [`WebSocketServer`].
 //!
-//! To run integration tests against a real WebSocket server:
-//! ```bash
-//! # Start a WebSocket echo server (example):
-//! # websocat ws-l:127.0.0.1:9001 mirror:
-//! # Then run:
-//! cargo test --test integration
-//! ```
+//! [`WebSocketServer`]: websocket_protocol::WebSocketServer
 
-// Integration tests would go here when a test server is available
-// For now, documentation serves as a placeholder
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn placeholder_integration_test() {
-        // Integration tests require a WebSocket echo server
-        // See module documentation for setup instructions
-        assert!(true);
-    }
-}
+mod test_backpressure;
+mod test_handshake_headers;
+mod test_handshake_timeout;
+mod test_message_compression;
+mod test_server;
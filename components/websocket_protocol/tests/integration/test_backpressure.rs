@@ -0,0 +1,77 @@
+//! Integration tests for incoming message buffering and backpressure
+
+use std::time::Duration;
+use tokio::net::TcpListener;
+use url::Url;
+use websocket_protocol::{
+    WebSocketClient, WebSocketConfig, WebSocketConnection, WebSocketMessage, WebSocketServer,
+};
+
+const MESSAGES: usize = 10;
+
+/// Start a server that sends `MESSAGES` numbered text messages once a client connects
+async fn start_sending_server() -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = WebSocketServer::new().accept(stream).await.unwrap();
+        for i in 0..MESSAGES {
+            connection
+                .send(WebSocketMessage::Text(i.to_string()))
+                .await
+                .unwrap();
+        }
+        // Keep the connection open until the client closes it
+        while connection.recv().await.is_some() {}
+    });
+
+    Url::parse(&format!("ws://{}/", addr)).unwrap()
+}
+
+/// Wait for the incoming buffer to stop growing, returning its length
+async fn settled_incoming_len(connection: &WebSocketConnection) -> usize {
+    let mut len = connection.incoming_len();
+    loop {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let current = connection.incoming_len();
+        if current == len {
+            return len;
+        }
+        len = current;
+    }
+}
+
+#[tokio::test]
+async fn test_small_incoming_buffer_holds_back_messages() {
+    let url = start_sending_server().await;
+    let config = WebSocketConfig::new().incoming_buffer(2);
+    let mut client = WebSocketClient::new()
+        .connect_with_config(url, vec![], config)
+        .await
+        .unwrap();
+
+    // The buffer fills to its capacity and the rest stay unread
+    assert_eq!(settled_incoming_len(&client).await, 2);
+
+    // Reading makes room, and every message arrives in order
+    for i in 0..MESSAGES {
+        match client.recv().await {
+            Some(Ok(WebSocketMessage::Text(text))) => assert_eq!(text, i.to_string()),
+            other => panic!("Expected message {}, got {:?}", i, other),
+        }
+    }
+    assert_eq!(client.incoming_len(), 0);
+}
+
+#[tokio::test]
+async fn test_default_incoming_buffer_holds_all_messages() {
+    let url = start_sending_server().await;
+    let client = WebSocketClient::new()
+        .connect_with_config(url, vec![], WebSocketConfig::new())
+        .await
+        .unwrap();
+
+    assert_eq!(settled_incoming_len(&client).await, MESSAGES);
+}
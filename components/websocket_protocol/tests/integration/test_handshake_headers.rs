@@ -0,0 +1,58 @@
+//! Integration tests for custom handshake headers
+
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tungstenite::http::{HeaderMap, HeaderValue};
+use url::Url;
+use websocket_protocol::{WebSocketClient, WebSocketConfig, WebSocketState};
+
+#[tokio::test]
+#[allow(clippy::result_large_err)]
+async fn test_connect_with_headers_sends_authorization() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let seen = Arc::new(Mutex::new(HeaderMap::new()));
+
+    let server_seen = seen.clone();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+            *server_seen.lock().unwrap() = request.headers().clone();
+            Ok(response)
+        })
+        .await
+        .unwrap()
+    });
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        "authorization",
+        HeaderValue::from_static("Bearer secret-token"),
+    );
+
+    let url = Url::parse(&format!("ws://{}/socket", addr)).unwrap();
+    let connection = WebSocketClient::new()
+        .connect_with_headers(url, vec![], headers, WebSocketConfig::new())
+        .await
+        .expect("handshake should succeed");
+    let _server_stream = server.await.unwrap();
+
+    assert_eq!(connection.state(), WebSocketState::Open);
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen["authorization"], "Bearer secret-token");
+    assert_eq!(seen["upgrade"], "websocket");
+}
+
+#[tokio::test]
+async fn test_connect_with_headers_rejects_reserved_header() {
+    let mut headers = HeaderMap::new();
+    headers.insert("sec-websocket-key", HeaderValue::from_static("forged"));
+
+    let url = Url::parse("ws://127.0.0.1:9/socket").unwrap();
+    let result = WebSocketClient::new()
+        .connect_with_headers(url, vec![], headers, WebSocketConfig::new())
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,69 @@
+//! Integration tests for the opening handshake timeout
+
+use network_errors::NetworkError;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpListener;
+use url::Url;
+use websocket_protocol::{WebSocketClient, WebSocketConfig};
+
+/// Start a server that accepts connections but never answers the upgrade
+async fn start_silent_server() -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buffer = [0u8; 1024];
+                while matches!(stream.read(&mut buffer).await, Ok(n) if n > 0) {}
+            });
+        }
+    });
+    Url::parse(&format!("ws://{}/socket", addr)).unwrap()
+}
+
+#[tokio::test]
+async fn test_connect_with_config_times_out_on_stalled_upgrade() {
+    let url = start_silent_server().await;
+    let config = WebSocketConfig::new().handshake_timeout(Some(Duration::from_millis(100)));
+
+    let started = Instant::now();
+    let result = WebSocketClient::new()
+        .connect_with_config(url, vec![], config)
+        .await;
+
+    assert!(
+        matches!(result, Err(NetworkError::Timeout(timeout)) if timeout == Duration::from_millis(100))
+    );
+    assert!(started.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_connect_with_headers_times_out_on_stalled_upgrade() {
+    let url = start_silent_server().await;
+    let config = WebSocketConfig::new().handshake_timeout(Some(Duration::from_millis(100)));
+
+    let result = WebSocketClient::new()
+        .connect_with_headers(url, vec![], Default::default(), config)
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Timeout(_))));
+}
+
+#[tokio::test]
+async fn test_handshake_without_timeout_keeps_waiting() {
+    let url = start_silent_server().await;
+
+    let result = tokio::time::timeout(
+        Duration::from_millis(200),
+        WebSocketClient::new().connect_with_config(url, vec![], WebSocketConfig::new()),
+    )
+    .await;
+
+    assert!(result.is_err(), "handshake should still be pending");
+}
+
+#[tokio::test]
+async fn test_handshake_timeout_defaults_to_none() {
+    assert_eq!(WebSocketConfig::new().get_handshake_timeout(), None);
+}
@@ -0,0 +1,75 @@
+//! Integration tests for application-level message compression
+
+use tokio::net::TcpListener;
+use url::Url;
+use websocket_protocol::{
+    CompressionConfig, WebSocketClient, WebSocketConfig, WebSocketMessage, WebSocketServer,
+};
+
+/// Start a server that sends `payload` through `send_compressed` once a client connects
+async fn start_compressing_server(payload: Vec<u8>) -> Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = WebSocketServer::new()
+            .accept(stream)
+            .await
+            .unwrap()
+            .with_message_compression(CompressionConfig::enabled());
+        connection
+            .send_compressed(WebSocketMessage::Binary(payload))
+            .await
+            .unwrap();
+        // Keep the connection open until the client closes it
+        while connection.recv().await.is_some() {}
+    });
+
+    Url::parse(&format!("ws://{}/", addr)).unwrap()
+}
+
+#[tokio::test]
+async fn test_connect_with_config_leaves_message_compression_off() {
+    let url = start_compressing_server(vec![0u8; 1024]).await;
+    let config = WebSocketConfig::new().compression(CompressionConfig::enabled());
+    let client = WebSocketClient::new()
+        .connect_with_config(url, vec![], config)
+        .await
+        .unwrap();
+
+    assert!(!client.is_message_compression_enabled());
+}
+
+#[tokio::test]
+async fn test_compressed_message_round_trips_when_opted_in() {
+    let payload = b"corten websocket payload ".repeat(1024);
+    let url = start_compressing_server(payload.clone()).await;
+    let mut client = WebSocketClient::new()
+        .connect_with_config(url, vec![], WebSocketConfig::new())
+        .await
+        .unwrap()
+        .with_message_compression(CompressionConfig::enabled());
+
+    match client.recv().await {
+        Some(Ok(WebSocketMessage::Binary(data))) => assert_eq!(data, payload),
+        other => panic!("Expected Binary message, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_compressed_message_over_max_message_size_is_rejected() {
+    // 16 MiB of zeros deflates to a few KiB on the wire
+    let url = start_compressing_server(vec![0u8; 16 << 20]).await;
+    let config = WebSocketConfig::new().max_message_size(Some(64 * 1024));
+    let mut client = WebSocketClient::new()
+        .connect_with_config(url, vec![], config)
+        .await
+        .unwrap()
+        .with_message_compression(CompressionConfig::enabled());
+
+    match client.recv().await {
+        Some(Err(error)) => assert!(error.to_string().contains("max_message_size")),
+        other => panic!("Expected an error, got {:?}", other),
+    }
+}
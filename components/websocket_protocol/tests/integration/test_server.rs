@@ -0,0 +1,189 @@
+//! Integration tests for the server-side handshake over loopback
+
+use network_errors::NetworkError;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tungstenite::http::HeaderMap;
+use url::Url;
+use websocket_protocol::{
+    WebSocketClient, WebSocketConfig, WebSocketMessage, WebSocketServer, WebSocketState,
+};
+
+/// Start an echo server for one connection, returning its URL
+async fn start_echo_server(server: WebSocketServer) -> (Url, tokio::task::JoinHandle<Url>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let handle = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = server.accept(stream).await.expect("server handshake");
+        assert_eq!(connection.state(), WebSocketState::Open);
+
+        while let Some(Ok(message)) = connection.recv().await {
+            match message {
+                WebSocketMessage::Text(_) | WebSocketMessage::Binary(_) => {
+                    connection.send(message).await.unwrap();
+                }
+                WebSocketMessage::Close(_) => break,
+                _ => {}
+            }
+        }
+        connection.url
+    });
+
+    let url = Url::parse(&format!("ws://{}/echo", addr)).unwrap();
+    (url, handle)
+}
+
+#[tokio::test]
+async fn test_local_text_echo() {
+    let (url, server) = start_echo_server(WebSocketServer::new()).await;
+
+    let mut client = WebSocketClient::new()
+        .connect(url.clone(), vec![])
+        .await
+        .expect("client handshake");
+    assert_eq!(client.state(), WebSocketState::Open);
+
+    client
+        .send(WebSocketMessage::Text("hello, server".to_string()))
+        .await
+        .unwrap();
+    match client.recv().await {
+        Some(Ok(WebSocketMessage::Text(text))) => assert_eq!(text, "hello, server"),
+        other => panic!("Expected echoed text, got {:?}", other),
+    }
+
+    client
+        .send(WebSocketMessage::Binary(vec![1, 2, 3]))
+        .await
+        .unwrap();
+    match client.recv().await {
+        Some(Ok(WebSocketMessage::Binary(data))) => assert_eq!(data, vec![1, 2, 3]),
+        other => panic!("Expected echoed binary, got {:?}", other),
+    }
+
+    client.close(1000, "done".to_string()).await.unwrap();
+    let server_url = server.await.unwrap();
+    assert_eq!(server_url, url);
+}
+
+#[tokio::test]
+async fn test_server_selects_subprotocol() {
+    let server = WebSocketServer::new().with_protocols(vec!["chat".to_string()]);
+    let (url, server) = start_echo_server(server).await;
+
+    let mut client = WebSocketClient::new()
+        .connect_with_headers(
+            url,
+            vec!["superchat".to_string(), "chat".to_string()],
+            HeaderMap::new(),
+            WebSocketConfig::new(),
+        )
+        .await
+        .expect("client handshake");
+    assert_eq!(client.protocol.as_deref(), Some("chat"));
+
+    client.close(1000, "done".to_string()).await.unwrap();
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_server_rejects_plain_http() {
+    use tokio::io::AsyncWriteExt;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        WebSocketServer::new().accept(stream).await
+    });
+
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    stream
+        .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n")
+        .await
+        .unwrap();
+
+    assert!(server.await.unwrap().is_err());
+}
+
+#[tokio::test]
+async fn test_peer_addr_over_loopback() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, client_addr) = listener.accept().await.unwrap();
+        let connection = WebSocketServer::new().accept(stream).await.unwrap();
+        assert_eq!(connection.peer_addr(), Some(client_addr));
+    });
+
+    let url = Url::parse(&format!("ws://{}/", server_addr)).unwrap();
+    let client = WebSocketClient::new().connect(url, vec![]).await.unwrap();
+    server.await.unwrap();
+
+    let peer = client.peer_addr().expect("peer address should be captured");
+    assert_eq!(peer.ip().to_string(), "127.0.0.1");
+    assert_eq!(peer.port(), server_addr.port());
+}
+
+#[tokio::test]
+async fn test_close_gracefully_completes_handshake() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut connection = WebSocketServer::new().accept(stream).await.unwrap();
+        connection
+            .send(WebSocketMessage::Text("in flight".to_string()))
+            .await
+            .unwrap();
+
+        // The server sees the client's Close, replies automatically, and the
+        // stream then ends
+        let mut close_frame = None;
+        while let Some(message) = connection.recv().await {
+            if let WebSocketMessage::Close(frame) = message.unwrap() {
+                close_frame = frame;
+            }
+        }
+        close_frame
+    });
+
+    let url = Url::parse(&format!("ws://{}/", addr)).unwrap();
+    let mut client = WebSocketClient::new().connect(url, vec![]).await.unwrap();
+    let ack = client
+        .close_gracefully(1000, "bye".to_string(), Duration::from_secs(5))
+        .await
+        .expect("peer should acknowledge the close");
+
+    assert_eq!(client.state(), WebSocketState::Closed);
+    assert_eq!(ack.expect("acknowledgment echoes the status").code, 1000);
+
+    let seen = server.await.unwrap().expect("server should receive Close");
+    assert_eq!(seen.code, 1000);
+    assert_eq!(seen.reason, "bye");
+}
+
+#[tokio::test]
+async fn test_close_gracefully_times_out() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        // Complete the handshake but never read, so the Close goes unanswered
+        let (stream, _) = listener.accept().await.unwrap();
+        let ws_stream = tokio_tungstenite::accept_async(stream).await.unwrap();
+        tokio::time::sleep(Duration::from_secs(2)).await;
+        drop(ws_stream);
+    });
+
+    let url = Url::parse(&format!("ws://{}/", addr)).unwrap();
+    let mut client = WebSocketClient::new().connect(url, vec![]).await.unwrap();
+    let result = client
+        .close_gracefully(1000, "bye".to_string(), Duration::from_millis(200))
+        .await;
+
+    assert!(matches!(result, Err(NetworkError::Timeout(_))));
+    assert_eq!(client.state(), WebSocketState::Closed);
+    server.await.unwrap();
+}
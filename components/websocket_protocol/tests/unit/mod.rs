@@ -1,8 +1,9 @@
 //! Unit tests for websocket_protocol
 
 use websocket_protocol::{
-    ReconnectConfig, ReconnectionEvent, ReconnectingWebSocket,
+    CompressionConfig, ReconnectConfig, ReconnectionEvent, ReconnectingWebSocket,
     WebSocketClient, WebSocketConnection, WebSocketMessage, WebSocketState,
+    COMPRESSED_MESSAGE_FLAG, UNCOMPRESSED_MESSAGE_FLAG,
 };
 use std::time::Duration;
 use url::Url;
@@ -398,3 +399,132 @@ async fn test_reconnect_fails_after_intentional_close() {
     let result = ws.reconnect().await;
     assert!(result.is_err());
 }
+
+// ==================== Message Compression Unit Tests ====================
+
+/// Test a large compressible message round-trips through send_compressed/recv
+#[tokio::test]
+async fn test_send_compressed_round_trip() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, tx_in, mut rx_out) = WebSocketConnection::new(url, None, vec![]);
+    let mut conn = conn.with_message_compression(CompressionConfig::enabled());
+    assert!(conn.is_message_compression_enabled());
+
+    let payload = b"corten websocket payload ".repeat(4096);
+    conn.send_compressed(WebSocketMessage::Binary(payload.clone()))
+        .await
+        .expect("Failed to send");
+
+    // The on-wire bytes are framed and smaller than the original payload
+    let wire = match rx_out.recv().await.expect("Should have message") {
+        WebSocketMessage::Binary(data) => data,
+        _ => panic!("Expected Binary message"),
+    };
+    assert_eq!(wire[0], COMPRESSED_MESSAGE_FLAG);
+    assert!(wire.len() < payload.len() / 10);
+
+    // Feeding the wire bytes back in yields the original payload
+    tx_in.send(WebSocketMessage::Binary(wire)).await.unwrap();
+    match conn.recv().await.expect("Should have message") {
+        Ok(WebSocketMessage::Binary(data)) => assert_eq!(data, payload),
+        _ => panic!("Expected Binary message"),
+    }
+}
+
+/// Test send_compressed leaves messages untouched when compression is disabled
+#[tokio::test]
+async fn test_send_compressed_disabled_sends_plain() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, _, mut rx_out) = WebSocketConnection::new(url, None, vec![]);
+    let conn = conn.with_message_compression(CompressionConfig::disabled());
+    assert!(!conn.is_message_compression_enabled());
+
+    let payload = vec![0u8; 4096];
+    conn.send_compressed(WebSocketMessage::Binary(payload.clone()))
+        .await
+        .unwrap();
+
+    match rx_out.recv().await.expect("Should have message") {
+        WebSocketMessage::Binary(data) => assert_eq!(data, payload),
+        _ => panic!("Expected Binary message"),
+    }
+}
+
+/// Test incompressible payloads are sent as-is behind the uncompressed flag
+#[tokio::test]
+async fn test_send_compressed_skips_incompressible_payload() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, tx_in, mut rx_out) = WebSocketConnection::new(url, None, vec![]);
+    let mut conn = conn.with_message_compression(CompressionConfig::enabled());
+
+    let payload = vec![7u8, 1, 9];
+    conn.send_compressed(WebSocketMessage::Binary(payload.clone()))
+        .await
+        .unwrap();
+
+    let wire = match rx_out.recv().await.expect("Should have message") {
+        WebSocketMessage::Binary(data) => data,
+        _ => panic!("Expected Binary message"),
+    };
+    assert_eq!(wire, [UNCOMPRESSED_MESSAGE_FLAG, 7, 1, 9]);
+
+    tx_in.send(WebSocketMessage::Binary(wire)).await.unwrap();
+    match conn.recv().await.expect("Should have message") {
+        Ok(WebSocketMessage::Binary(data)) => assert_eq!(data, payload),
+        _ => panic!("Expected Binary message"),
+    }
+}
+
+/// Test an uncompressed payload that starts with the compressed flag round-trips
+#[tokio::test]
+async fn test_send_compressed_payload_starting_with_flag_byte() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, tx_in, mut rx_out) = WebSocketConnection::new(url, None, vec![]);
+    let mut conn = conn.with_message_compression(CompressionConfig::enabled());
+
+    let payload = vec![COMPRESSED_MESSAGE_FLAG, 0xff, 0xfe];
+    conn.send_compressed(WebSocketMessage::Binary(payload.clone()))
+        .await
+        .unwrap();
+    let wire = match rx_out.recv().await.expect("Should have message") {
+        WebSocketMessage::Binary(data) => data,
+        _ => panic!("Expected Binary message"),
+    };
+
+    tx_in.send(WebSocketMessage::Binary(wire)).await.unwrap();
+    match conn.recv().await.expect("Should have message") {
+        Ok(WebSocketMessage::Binary(data)) => assert_eq!(data, payload),
+        _ => panic!("Expected Binary message"),
+    }
+}
+
+/// Test a payload without a compression flag surfaces an error from recv
+#[tokio::test]
+async fn test_recv_unflagged_payload() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, tx_in, _) = WebSocketConnection::new(url, None, vec![]);
+    let mut conn = conn.with_message_compression(CompressionConfig::enabled());
+
+    tx_in.send(WebSocketMessage::Binary(vec![])).await.unwrap();
+    tx_in
+        .send(WebSocketMessage::Binary(vec![0x7f, 1, 2]))
+        .await
+        .unwrap();
+
+    assert!(conn.recv().await.expect("Should have message").is_err());
+    assert!(conn.recv().await.expect("Should have message").is_err());
+}
+
+/// Test a corrupted compressed payload surfaces an error from recv
+#[tokio::test]
+async fn test_recv_corrupted_compressed_payload() {
+    let url = Url::parse("ws://localhost:8080/ws").unwrap();
+    let (conn, tx_in, _) = WebSocketConnection::new(url, None, vec![]);
+    let mut conn = conn.with_message_compression(CompressionConfig::enabled());
+
+    let mut wire = vec![COMPRESSED_MESSAGE_FLAG];
+    wire.extend_from_slice(&[0xff; 16]);
+    tx_in.send(WebSocketMessage::Binary(wire)).await.unwrap();
+
+    assert!(conn.recv().await.expect("Should have message").is_err());
+}
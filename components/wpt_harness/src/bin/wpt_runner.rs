@@ -21,7 +21,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ("basic_get", WptRequest {
             method: "GET".to_string(),
             url: "https://example.com/test".to_string(),
-            headers: std::collections::HashMap::new(),
+            headers: Vec::new(),
             body: None,
             timeout_ms: Some(30000),
         }),
@@ -64,7 +64,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             wpt_harness::WptTestResult::Error { message } => println!("ERROR: {}", message),
         }
 
-        stats.add_result(&result);
+        stats.add_named_result(name, &result);
     }
 
     stats.print_summary();
@@ -4,7 +4,6 @@
 //! CORS is critical for security, preventing unauthorized cross-origin access.
 
 use crate::{WptHarness, WptRequest, WptResponse, WptTestResult, WptTestStats};
-use std::collections::HashMap;
 
 /// Create CORS test suite
 pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> bool)> {
@@ -23,7 +22,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").is_some()
+                resp.header("access-control-allow-origin").is_some()
             },
         ),
         (
@@ -39,7 +38,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").map(|v| v == "*").unwrap_or(false)
+                resp.header("access-control-allow-origin").map(|v| v == "*").unwrap_or(false)
             },
         ),
         (
@@ -47,14 +46,14 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/cors/simple".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // CORS headers should still be present even without Origin
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").is_some()
+                resp.header("access-control-allow-origin").is_some()
             },
         ),
 
@@ -77,7 +76,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             |resp| {
                 // Preflight should return 204 or 200
                 (resp.status == 204 || resp.status == 200) &&
-                resp.headers.get("access-control-allow-methods").is_some()
+                resp.header("access-control-allow-methods").is_some()
             },
         ),
         (
@@ -96,7 +95,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 (resp.status == 204 || resp.status == 200) &&
-                resp.headers.get("access-control-allow-methods")
+                resp.header("access-control-allow-methods")
                     .map(|v| v.contains("PUT"))
                     .unwrap_or(false)
             },
@@ -116,11 +115,11 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-credentials")
+                resp.header("access-control-allow-credentials")
                     .map(|v| v == "true")
                     .unwrap_or(false) &&
                 // With credentials, origin should NOT be wildcard
-                resp.headers.get("access-control-allow-origin")
+                resp.header("access-control-allow-origin")
                     .map(|v| v != "*")
                     .unwrap_or(false)
             },
@@ -143,7 +142,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
                 // In a real browser, this would be blocked
                 // For testing, we just verify the endpoint works and no CORS headers
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").is_none()
+                resp.header("access-control-allow-origin").is_none()
             },
         ),
 
@@ -163,7 +162,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
                 timeout_ms: Some(30000),
             },
             |resp| {
-                resp.headers.get("access-control-allow-methods")
+                resp.header("access-control-allow-methods")
                     .map(|v| v.contains("DELETE"))
                     .unwrap_or(false)
             },
@@ -184,7 +183,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
                 timeout_ms: Some(30000),
             },
             |resp| {
-                resp.headers.get("access-control-allow-headers").is_some()
+                resp.header("access-control-allow-headers").is_some()
             },
         ),
         (
@@ -203,7 +202,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 // Max-Age header should be present for preflight caching
-                resp.headers.get("access-control-max-age").is_some()
+                resp.header("access-control-max-age").is_some()
             },
         ),
 
@@ -221,7 +220,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").is_some()
+                resp.header("access-control-allow-origin").is_some()
             },
         ),
 
@@ -260,7 +259,7 @@ pub fn create_cors_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("access-control-allow-origin").is_some()
+                resp.header("access-control-allow-origin").is_some()
             },
         ),
 
@@ -303,17 +302,17 @@ pub async fn run_cors_test_suite() -> WptTestStats {
             Ok(response) => {
                 if validator(&response) {
                     println!("PASS");
-                    stats.add_result(&WptTestResult::Pass);
+                    stats.add_named_result(&name, &WptTestResult::Pass);
                 } else {
                     println!("FAIL (validation failed)");
-                    stats.add_result(&WptTestResult::Fail {
+                    stats.add_named_result(&name, &WptTestResult::Fail {
                         reason: "Response validation failed".to_string(),
                     });
                 }
             }
             Err(e) => {
                 println!("ERROR: {}", e);
-                stats.add_result(&WptTestResult::Error {
+                stats.add_named_result(&name, &WptTestResult::Error {
                     message: e.to_string(),
                 });
             }
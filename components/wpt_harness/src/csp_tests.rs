@@ -4,7 +4,6 @@
 //! CSP is critical for preventing XSS and other code injection attacks.
 
 use crate::{WptHarness, WptRequest, WptResponse, WptTestResult, WptTestStats};
-use std::collections::HashMap;
 
 /// Create CSP test suite
 pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> bool)> {
@@ -15,13 +14,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("default-src"))
                     .unwrap_or(false)
             },
@@ -31,13 +30,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/script-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("script-src"))
                     .unwrap_or(false)
             },
@@ -49,13 +48,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/nonce/abc123".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("nonce-abc123"))
                     .unwrap_or(false)
             },
@@ -65,13 +64,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/nonce/xyz789".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("nonce-xyz789"))
                     .unwrap_or(false)
             },
@@ -81,13 +80,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/nonce/test-nonce-value".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("'nonce-") && v.contains("test-nonce-value"))
                     .unwrap_or(false)
             },
@@ -99,13 +98,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/hash".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("sha256-"))
                     .unwrap_or(false)
             },
@@ -117,13 +116,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/multiple".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| {
                         v.contains("default-src") &&
                         v.contains("script-src") &&
@@ -137,13 +136,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/multiple".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains(";"))
                     .unwrap_or(false)
             },
@@ -155,13 +154,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("'self'"))
                     .unwrap_or(false)
             },
@@ -171,13 +170,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/multiple".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("'unsafe-inline'"))
                     .unwrap_or(false)
             },
@@ -189,13 +188,13 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/report-uri".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v.contains("report-uri"))
                     .unwrap_or(false)
             },
@@ -205,7 +204,7 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/report".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -221,14 +220,14 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // CSP header should be present and properly formatted
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy").is_some()
+                resp.header("content-security-policy").is_some()
             },
         ),
         (
@@ -236,15 +235,15 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/script-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // Header name should be lowercase (HTTP/2 requirement)
                 resp.status == 200 &&
-                (resp.headers.get("content-security-policy").is_some() ||
-                 resp.headers.get("Content-Security-Policy").is_some())
+                (resp.header("content-security-policy").is_some() ||
+                 resp.header("Content-Security-Policy").is_some())
             },
         ),
 
@@ -254,14 +253,14 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // Check that directive has proper format: directive-name value
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| {
                         let parts: Vec<&str> = v.split_whitespace().collect();
                         parts.len() >= 2  // At least "directive value"
@@ -276,7 +275,7 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -284,7 +283,7 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
                 // CSP header should not interfere with response body
                 resp.status == 200 &&
                 resp.body.len() > 0 &&
-                resp.headers.get("content-security-policy").is_some()
+                resp.header("content-security-policy").is_some()
             },
         ),
 
@@ -294,14 +293,14 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/multiple".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // Complex policy with multiple directives and keywords
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| {
                         v.contains("default-src") &&
                         v.contains("script-src") &&
@@ -318,14 +317,14 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/default-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // Verify exact policy format
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v == "default-src 'self'")
                     .unwrap_or(false)
             },
@@ -335,14 +334,14 @@ pub fn create_csp_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> b
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/csp/script-src".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
             |resp| {
                 // Verify exact policy format
                 resp.status == 200 &&
-                resp.headers.get("content-security-policy")
+                resp.header("content-security-policy")
                     .map(|v| v == "script-src 'self'")
                     .unwrap_or(false)
             },
@@ -366,17 +365,17 @@ pub async fn run_csp_test_suite() -> WptTestStats {
             Ok(response) => {
                 if validator(&response) {
                     println!("PASS");
-                    stats.add_result(&WptTestResult::Pass);
+                    stats.add_named_result(&name, &WptTestResult::Pass);
                 } else {
                     println!("FAIL (validation failed)");
-                    stats.add_result(&WptTestResult::Fail {
+                    stats.add_named_result(&name, &WptTestResult::Fail {
                         reason: "Response validation failed".to_string(),
                     });
                 }
             }
             Err(e) => {
                 println!("ERROR: {}", e);
-                stats.add_result(&WptTestResult::Error {
+                stats.add_named_result(&name, &WptTestResult::Error {
                     message: e.to_string(),
                 });
             }
@@ -4,7 +4,6 @@
 //! providing equivalent validation to WPT tests without requiring external network access.
 
 use crate::{WptHarness, WptRequest, WptResponse, WptTestResult, WptTestStats};
-use std::collections::HashMap;
 
 /// Create comprehensive HTTP test suite
 pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) -> bool)> {
@@ -15,7 +14,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/get".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -39,7 +38,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "PUT".to_string(),
                 url: "http://127.0.0.1:8080/put".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: Some(b"test data".to_vec()),
                 timeout_ms: Some(30000),
             },
@@ -50,7 +49,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "DELETE".to_string(),
                 url: "http://127.0.0.1:8080/delete".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -61,7 +60,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "PATCH".to_string(),
                 url: "http://127.0.0.1:8080/patch".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: Some(b"patch data".to_vec()),
                 timeout_ms: Some(30000),
             },
@@ -74,7 +73,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/200".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -85,7 +84,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/201".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -96,7 +95,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/204".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -107,7 +106,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/400".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -118,7 +117,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/404".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -129,7 +128,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/status/500".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -158,11 +157,11 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/response-headers?Content-Type=application/json".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
-            |resp| resp.status == 200 && resp.headers.get("content-type").map(|v| v.contains("json")).unwrap_or(false),
+            |resp| resp.status == 200 && resp.header("content-type").map(|v| v.contains("json")).unwrap_or(false),
         ),
 
         // ===== Redirect Tests =====
@@ -171,7 +170,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/redirect/1".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -184,22 +183,22 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/json".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
-            |resp| resp.status == 200 && resp.headers.get("content-type").map(|v| v.contains("json")).unwrap_or(false),
+            |resp| resp.status == 200 && resp.header("content-type").map(|v| v.contains("json")).unwrap_or(false),
         ),
         (
             "content_type_html".to_string(),
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/html".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
-            |resp| resp.status == 200 && resp.headers.get("content-type").map(|v| v.contains("html")).unwrap_or(false),
+            |resp| resp.status == 200 && resp.header("content-type").map(|v| v.contains("html")).unwrap_or(false),
         ),
 
         // ===== Encoding Tests =====
@@ -236,7 +235,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/encoding/utf8".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -249,7 +248,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/cache/60".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -262,7 +261,7 @@ pub fn create_http_test_suite() -> Vec<(String, WptRequest, fn(&WptResponse) ->
             WptRequest {
                 method: "GET".to_string(),
                 url: "http://127.0.0.1:8080/delay/1".to_string(),
-                headers: HashMap::new(),
+                headers: Vec::new(),
                 body: None,
                 timeout_ms: Some(30000),
             },
@@ -287,17 +286,17 @@ pub async fn run_http_test_suite() -> WptTestStats {
             Ok(response) => {
                 if validator(&response) {
                     println!("PASS");
-                    stats.add_result(&WptTestResult::Pass);
+                    stats.add_named_result(&name, &WptTestResult::Pass);
                 } else {
                     println!("FAIL (validation failed)");
-                    stats.add_result(&WptTestResult::Fail {
+                    stats.add_named_result(&name, &WptTestResult::Fail {
                         reason: "Response validation failed".to_string(),
                     });
                 }
             }
             Err(e) => {
                 println!("ERROR: {}", e);
-                stats.add_result(&WptTestResult::Error {
+                stats.add_named_result(&name, &WptTestResult::Error {
                     message: e.to_string(),
                 });
             }
@@ -40,9 +40,9 @@ pub struct WptRequest {
     pub method: String,
     /// Request URL
     pub url: String,
-    /// Request headers
+    /// Request headers, in order; a name may appear more than once
     #[serde(default)]
-    pub headers: std::collections::HashMap<String, String>,
+    pub headers: Vec<(String, String)>,
     /// Request body (optional)
     #[serde(default)]
     pub body: Option<Vec<u8>>,
@@ -56,8 +56,9 @@ pub struct WptRequest {
 pub struct WptResponse {
     /// HTTP status code
     pub status: u16,
-    /// Response headers
-    pub headers: std::collections::HashMap<String, String>,
+    /// Response headers, in order; multi-valued headers such as `Set-Cookie`
+    /// appear once per value
+    pub headers: Vec<(String, String)>,
     /// Response body
     #[serde(with = "serde_bytes")]
     pub body: Vec<u8>,
@@ -65,6 +66,24 @@ pub struct WptResponse {
     pub duration_ms: u64,
 }
 
+impl WptResponse {
+    /// First value of the header `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// All values of the header `name` (case-insensitive), in order
+    pub fn header_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.headers
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
 /// WPT test result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -81,6 +100,28 @@ pub enum WptTestResult {
     Error { message: String },
 }
 
+impl WptTestResult {
+    /// Upper-case status name, e.g. `"PASS"` or `"TIMEOUT"`
+    pub fn status(&self) -> &'static str {
+        match self {
+            WptTestResult::Pass => "PASS",
+            WptTestResult::Fail { .. } => "FAIL",
+            WptTestResult::Timeout => "TIMEOUT",
+            WptTestResult::Skip { .. } => "SKIP",
+            WptTestResult::Error { .. } => "ERROR",
+        }
+    }
+
+    /// Reason or message stored with the result, if any
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            WptTestResult::Fail { reason } | WptTestResult::Skip { reason } => Some(reason),
+            WptTestResult::Error { message } => Some(message),
+            WptTestResult::Pass | WptTestResult::Timeout => None,
+        }
+    }
+}
+
 /// WPT harness adapter
 pub struct WptHarness {
     /// Whether to log verbose output
@@ -132,7 +173,6 @@ impl WptHarness {
             HttpMethod, NetworkRequest, RequestMode, CredentialsMode,
             CacheMode, RedirectMode, ReferrerPolicy, RequestPriority, RequestBody,
         };
-        use http::HeaderMap;
 
         if self.verbose {
             eprintln!("[WPT] Executing: {} {}", request.method, request.url);
@@ -144,29 +184,16 @@ impl WptHarness {
         let url = url::Url::parse(&request.url)?;
 
         // 2. Convert HTTP method
-        let method = match request.method.to_uppercase().as_str() {
-            "GET" => HttpMethod::Get,
-            "POST" => HttpMethod::Post,
-            "PUT" => HttpMethod::Put,
-            "DELETE" => HttpMethod::Delete,
-            "HEAD" => HttpMethod::Head,
-            "OPTIONS" => HttpMethod::Options,
-            "PATCH" => HttpMethod::Patch,
-            _ => HttpMethod::Get,
-        };
+        let method = request
+            .method
+            .parse::<HttpMethod>()
+            .unwrap_or(HttpMethod::Get);
 
         // 3. Convert headers
-        let mut headers = HeaderMap::new();
-        for (key, value) in request.headers {
-            if let Ok(header_name) = http::header::HeaderName::from_bytes(key.as_bytes()) {
-                if let Ok(header_value) = http::header::HeaderValue::from_str(&value) {
-                    headers.insert(header_name, header_value);
-                }
-            }
-        }
+        let headers = convert_request_headers(&request.headers);
 
         // 4. Convert body
-        let body = request.body.map(|b| RequestBody::Bytes(b));
+        let body = request.body.map(RequestBody::Bytes);
 
         // 5. Create NetworkRequest
         let network_request = NetworkRequest {
@@ -198,7 +225,12 @@ impl WptHarness {
             headers: network_response
                 .headers
                 .iter()
-                .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
+                .map(|(k, v)| {
+                    (
+                        k.to_string(),
+                        String::from_utf8_lossy(v.as_bytes()).into_owned(),
+                    )
+                })
                 .collect(),
             body: match network_response.body {
                 network_types::ResponseBody::Bytes(bytes) => bytes,
@@ -235,6 +267,10 @@ impl WptHarness {
             max_connections_per_host: 6,
             enable_keepalive: true,
             enable_pipelining: false,
+            tcp_nodelay: true,
+            tcp_keepalive: Some(std::time::Duration::from_secs(60)),
+            max_response_body_bytes: None,
+            use_expect_continue: false,
         };
         let client = Http1Client::new(config);
 
@@ -272,6 +308,22 @@ impl WptHarness {
     }
 }
 
+/// Convert WPT request headers to a `HeaderMap`
+///
+/// Repeated names are appended rather than replaced, so every value is sent in
+/// order. Names or values that are not valid HTTP are skipped.
+fn convert_request_headers(headers: &[(String, String)]) -> http::HeaderMap {
+    let mut map = http::HeaderMap::new();
+    for (key, value) in headers {
+        if let Ok(header_name) = http::header::HeaderName::from_bytes(key.as_bytes()) {
+            if let Ok(header_value) = http::header::HeaderValue::from_str(value) {
+                map.append(header_name, header_value);
+            }
+        }
+    }
+    map
+}
+
 impl Default for WptHarness {
     fn default() -> Self {
         Self::new()
@@ -293,11 +345,36 @@ pub struct WptTestStats {
     pub skipped: usize,
     /// Tests with errors
     pub errors: usize,
+    /// Individual results, in the order they were added
+    #[serde(default)]
+    pub results: Vec<WptTestCase>,
+}
+
+/// Result of a single named test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WptTestCase {
+    /// Test name
+    pub name: String,
+    /// Test outcome
+    pub result: WptTestResult,
 }
 
 impl WptTestStats {
     /// Add a test result to statistics
+    ///
+    /// The result is recorded as `test_<n>`; use
+    /// [`add_named_result`](Self::add_named_result) when the test name is known.
     pub fn add_result(&mut self, result: &WptTestResult) {
+        let name = format!("test_{}", self.total + 1);
+        self.add_named_result(&name, result);
+    }
+
+    /// Add the result of the test `name` to statistics
+    pub fn add_named_result(&mut self, name: &str, result: &WptTestResult) {
+        self.results.push(WptTestCase {
+            name: name.to_string(),
+            result: result.clone(),
+        });
         self.total += 1;
         match result {
             WptTestResult::Pass => self.passed += 1,
@@ -327,6 +404,115 @@ impl WptTestStats {
         println!("  Skipped:  {}", self.skipped);
         println!("  Errors:   {}", self.errors);
     }
+
+    /// Export the statistics and individual results as JSON
+    ///
+    /// ```json
+    /// {
+    ///   "total": 2, "passed": 1, "failed": 1, "timeout": 0, "skipped": 0, "errors": 0,
+    ///   "pass_rate": 50.0,
+    ///   "results": [{ "name": "basic_get", "status": "FAIL", "message": "..." }]
+    /// }
+    /// ```
+    ///
+    /// `message` is omitted for results without one.
+    pub fn to_json(&self) -> String {
+        let results: Vec<serde_json::Value> = self
+            .results
+            .iter()
+            .map(|case| {
+                let mut entry = serde_json::json!({
+                    "name": case.name,
+                    "status": case.result.status(),
+                });
+                if let Some(message) = case.result.message() {
+                    entry["message"] = message.into();
+                }
+                entry
+            })
+            .collect();
+
+        serde_json::json!({
+            "total": self.total,
+            "passed": self.passed,
+            "failed": self.failed,
+            "timeout": self.timeout,
+            "skipped": self.skipped,
+            "errors": self.errors,
+            "pass_rate": self.pass_rate(),
+            "results": results,
+        })
+        .to_string()
+    }
+
+    /// Export the results as a JUnit XML report with a single `<testsuite>`
+    ///
+    /// Failures become `<failure>` elements, errors and timeouts `<error>`
+    /// elements, and skipped tests `<skipped>`, each carrying the stored
+    /// reason or message. Timeouts are counted as errors in the suite totals.
+    pub fn to_junit_xml(&self, suite_name: &str) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\">\n",
+            xml_escape(suite_name),
+            self.total,
+            self.failed,
+            self.errors + self.timeout,
+            self.skipped
+        ));
+
+        for case in &self.results {
+            let name = xml_escape(&case.name);
+            let child = match &case.result {
+                WptTestResult::Pass => None,
+                WptTestResult::Fail { reason } => {
+                    Some(format!("<failure message=\"{}\"/>", xml_escape(reason)))
+                }
+                WptTestResult::Timeout => {
+                    Some("<error type=\"timeout\" message=\"Test timed out\"/>".to_string())
+                }
+                WptTestResult::Skip { reason } => {
+                    Some(format!("<skipped message=\"{}\"/>", xml_escape(reason)))
+                }
+                WptTestResult::Error { message } => {
+                    Some(format!("<error message=\"{}\"/>", xml_escape(message)))
+                }
+            };
+
+            match child {
+                Some(child) => xml.push_str(&format!(
+                    "  <testcase name=\"{}\" classname=\"{}\">\n    {}\n  </testcase>\n",
+                    name,
+                    xml_escape(suite_name),
+                    child
+                )),
+                None => xml.push_str(&format!(
+                    "  <testcase name=\"{}\" classname=\"{}\"/>\n",
+                    name,
+                    xml_escape(suite_name)
+                )),
+            }
+        }
+
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+}
+
+/// Escape text for use in XML attribute values and content
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 #[cfg(test)]
@@ -394,4 +580,171 @@ mod tests {
         assert_eq!(stats.passed, 1);
         assert_eq!(stats.failed, 1);
     }
+
+    fn sample_stats() -> WptTestStats {
+        let mut stats = WptTestStats::default();
+        stats.add_named_result("basic_get", &WptTestResult::Pass);
+        stats.add_named_result(
+            "bad_status",
+            &WptTestResult::Fail {
+                reason: "Expected 200 & got <404>".to_string(),
+            },
+        );
+        stats.add_named_result("slow", &WptTestResult::Timeout);
+        stats.add_named_result(
+            "unsupported",
+            &WptTestResult::Skip {
+                reason: "Not implemented".to_string(),
+            },
+        );
+        stats.add_named_result(
+            "refused",
+            &WptTestResult::Error {
+                message: "Connection refused".to_string(),
+            },
+        );
+        stats
+    }
+
+    /// Check that every element is closed in order, returning the element names seen
+    fn assert_well_formed(xml: &str) -> Vec<String> {
+        let body = xml
+            .strip_prefix("<?xml version=\"1.0\" encoding=\"UTF-8\"?>")
+            .expect("XML declaration");
+        let mut open: Vec<String> = Vec::new();
+        let mut seen = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find('<') {
+            assert!(rest[..start].trim().is_empty(), "Unexpected text content");
+            let end = rest[start..].find('>').expect("Unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            assert!(!tag.contains('<'), "Unescaped '<' in tag");
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name), "Mismatched closing tag");
+            } else {
+                let name = tag.split_whitespace().next().unwrap().trim_end_matches('/');
+                seen.push(name.to_string());
+                if !tag.ends_with('/') {
+                    open.push(name.to_string());
+                }
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(rest.trim().is_empty());
+        assert!(open.is_empty(), "Unclosed elements: {:?}", open);
+        seen
+    }
+
+    #[test]
+    fn test_stats_add_result_records_cases() {
+        let mut stats = WptTestStats::default();
+        stats.add_result(&WptTestResult::Pass);
+        stats.add_named_result("named", &WptTestResult::Timeout);
+        assert_eq!(stats.results.len(), 2);
+        assert_eq!(stats.results[0].name, "test_1");
+        assert_eq!(stats.results[1].name, "named");
+        assert_eq!(stats.timeout, 1);
+    }
+
+    #[test]
+    fn test_stats_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&sample_stats().to_json()).unwrap();
+
+        assert_eq!(json["total"], 5);
+        assert_eq!(json["passed"], 1);
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["timeout"], 1);
+        assert_eq!(json["skipped"], 1);
+        assert_eq!(json["errors"], 1);
+        assert_eq!(json["pass_rate"], 20.0);
+
+        let results = json["results"].as_array().unwrap();
+        assert_eq!(results.len(), 5);
+        assert_eq!(results[0]["name"], "basic_get");
+        assert_eq!(results[0]["status"], "PASS");
+        assert!(results[0].get("message").is_none());
+        assert_eq!(results[1]["status"], "FAIL");
+        assert_eq!(results[1]["message"], "Expected 200 & got <404>");
+        assert_eq!(results[4]["status"], "ERROR");
+        assert_eq!(results[4]["message"], "Connection refused");
+    }
+
+    #[test]
+    fn test_stats_to_junit_xml() {
+        let xml = sample_stats().to_junit_xml("fetch & cors");
+
+        let elements = assert_well_formed(&xml);
+        assert_eq!(elements.iter().filter(|e| *e == "testcase").count(), 5);
+        assert!(xml.contains(
+            r#"<testsuite name="fetch &amp; cors" tests="5" failures="1" errors="2" skipped="1">"#
+        ));
+        assert!(xml.contains(r#"<failure message="Expected 200 &amp; got &lt;404&gt;"/>"#));
+        assert!(xml.contains(r#"<error type="timeout" message="Test timed out"/>"#));
+        assert!(xml.contains(r#"<error message="Connection refused"/>"#));
+        assert!(xml.contains(r#"<skipped message="Not implemented"/>"#));
+        assert!(xml.contains(r#"<testcase name="basic_get" classname="fetch &amp; cors"/>"#));
+    }
+
+    #[test]
+    fn test_stats_to_junit_xml_empty() {
+        let xml = WptTestStats::default().to_junit_xml("empty");
+        assert_eq!(assert_well_formed(&xml), vec!["testsuite"]);
+        assert!(xml.contains(r#"tests="0" failures="0" errors="0" skipped="0""#));
+    }
+
+    #[test]
+    fn test_duplicate_request_headers_preserved() {
+        let headers = vec![
+            ("Accept".to_string(), "text/html".to_string()),
+            ("X-Test".to_string(), "first".to_string()),
+            ("x-test".to_string(), "second".to_string()),
+        ];
+
+        let map = convert_request_headers(&headers);
+
+        let values: Vec<_> = map.get_all("x-test").iter().collect();
+        assert_eq!(values, vec!["first", "second"]);
+        assert_eq!(map.get("accept").unwrap(), "text/html");
+    }
+
+    #[test]
+    fn test_request_headers_round_trip_through_json() {
+        let request = WptRequest {
+            method: "POST".to_string(),
+            url: "http://127.0.0.1:8080/echo".to_string(),
+            headers: vec![
+                ("Cookie".to_string(), "a=1".to_string()),
+                ("Cookie".to_string(), "b=2".to_string()),
+            ],
+            body: Some(b"\x00binary\xff".to_vec()),
+            timeout_ms: None,
+        };
+
+        let json = serde_json::to_string(&request).unwrap();
+        let parsed: WptRequest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.headers, request.headers);
+        assert_eq!(parsed.body, request.body);
+    }
+
+    #[test]
+    fn test_response_multi_valued_headers() {
+        let response = WptResponse {
+            status: 200,
+            headers: vec![
+                ("set-cookie".to_string(), "a=1".to_string()),
+                ("content-type".to_string(), "text/plain".to_string()),
+                ("set-cookie".to_string(), "b=2".to_string()),
+            ],
+            body: Vec::new(),
+            duration_ms: 0,
+        };
+
+        assert_eq!(
+            response.header_values("Set-Cookie").collect::<Vec<_>>(),
+            vec!["a=1", "b=2"]
+        );
+        assert_eq!(response.header("Content-Type"), Some("text/plain"));
+        assert_eq!(response.header("x-missing"), None);
+    }
 }
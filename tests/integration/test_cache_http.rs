@@ -421,6 +421,8 @@ mod cache_http_integration {
             redirected: false,
             type_: Default::default(),
             timing: Default::default(),
+            trailers: None,
+            source: Default::default(),
         }
     }
 
@@ -56,6 +56,8 @@ pub fn create_test_response(status: u16, body: Vec<u8>) -> NetworkResponse {
         redirected: false,
         type_: Default::default(),
         timing: Default::default(),
+        trailers: None,
+        source: Default::default(),
     }
 }
 
@@ -78,6 +78,8 @@ mod integration_tests {
             redirected: false,
             type_: network_types::ResponseType::Basic,
             timing: network_types::ResourceTiming::default(),
+            trailers: None,
+            source: network_types::ResponseSource::Network,
         };
 
         // When: Store response in cache
@@ -299,6 +301,8 @@ mod integration_tests {
             redirected: false,
             type_: network_types::ResponseType::Basic,
             timing: network_types::ResourceTiming::default(),
+            trailers: None,
+            source: network_types::ResponseSource::Network,
         };
 
         // Store a response